@@ -1,9 +1,10 @@
-use std::fs::File;
-use criterion::{criterion_group, criterion_main, Criterion};
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
 use gemmy::core::{
-    models::{LimitOrder, Operation, Side},
-    orderbook::OrderBook
+    models::{LimitOrder, MarketOrder, Operation, Side},
+    orderbook::OrderBook,
+    types::{OrderId, Price, Quantity},
 };
+use gemmy::engine::persistence::recording::read_order_list;
 
 fn small_limit_ladder(c: &mut Criterion) {
     c.bench_function("small limit ladder", |b| {
@@ -11,9 +12,9 @@ fn small_limit_ladder(c: &mut Criterion) {
         b.iter(|| {
             for i in 0..5_000 {
                 orderbook.execute(Operation::Limit(LimitOrder::new(
-                    i as u128,
-                    12345 + i,
-                    i,
+                    OrderId(i as u128),
+                    Price(12345 + i),
+                    Quantity(i),
                     Side::Bid,
                 )));
             }
@@ -27,9 +28,9 @@ fn big_limit_ladder(c: &mut Criterion) {
         b.iter(|| {
             for i in 0..100_000 {
                 orderbook.execute(Operation::Limit(LimitOrder::new(
-                    i as u128,
-                    12345 + i,
-                    i,
+                    OrderId(i as u128),
+                    Price(12345 + i),
+                    Quantity(i),
                     Side::Bid,
                 )));
             }
@@ -41,38 +42,20 @@ fn insert_and_remove_small_limit_ladder(c: &mut Criterion) {
         let mut book = OrderBook::default();
         b.iter(|| {
             for i in 1..5000u64 {
-                let order = LimitOrder::new(i as u128, 12345 + i, i, Side::Bid);
+                let order =
+                    LimitOrder::new(OrderId(i as u128), Price(12345 + i), Quantity(i), Side::Bid);
                 book.execute(Operation::Limit(order));
             }
             for i in 1..5000u128 {
-                book.execute(Operation::Cancel(i));
+                book.execute(Operation::Cancel(OrderId(i)));
             }
         })
     });
 }
 
-fn load_operations(path: &str) -> Vec<Operation> {
-    let file = File::open(path).unwrap();
-    let mut operations = Vec::new();
-    let mut rdr = csv::ReaderBuilder::new()
-        .has_headers(true)
-        .from_reader(file);
-    let mut id = 0;
-    for record in rdr.deserialize::<(u64, Side, u64, u64)>() {
-        match record {
-            Ok((_, side, price, quantity)) => {
-                operations.push(Operation::Limit(LimitOrder::new(id, price, quantity, side)));
-                id += 1;
-            }
-            Err(e) => eprintln!("Error parsing line: {}", e),
-        }
-    }
-    operations
-}
-
 fn all_orders(c: &mut Criterion) {
     c.bench_function("all orders", |b| {
-        let orders: Vec<Operation> = load_operations("resources/orders.csv");
+        let orders = read_order_list("resources/orders.csv").unwrap();
         let mut orderbook = OrderBook::default();
         b.iter(|| {
             for ord in &orders {
@@ -82,11 +65,125 @@ fn all_orders(c: &mut Criterion) {
     });
 }
 
+/// Builds a book with `levels` ask levels starting at `best_ask`, each holding `orders_per_level`
+/// orders of quantity 10, mirrored by bid levels from `best_ask - 1` down. Ask ids start at 1 and
+/// bid ids follow them.
+fn two_sided_ladder(levels: u64, orders_per_level: u64, best_ask: u64) -> OrderBook {
+    let mut orderbook = OrderBook::default();
+    let mut id = 1u128;
+    for level in 0..levels {
+        for _ in 0..orders_per_level {
+            orderbook.execute(Operation::Limit(LimitOrder::new(
+                OrderId(id),
+                Price(best_ask + level),
+                Quantity(10),
+                Side::Ask,
+            )));
+            id += 1;
+        }
+    }
+    for level in 0..levels {
+        for _ in 0..orders_per_level {
+            orderbook.execute(Operation::Limit(LimitOrder::new(
+                OrderId(id),
+                Price(best_ask - 1 - level),
+                Quantity(10),
+                Side::Bid,
+            )));
+            id += 1;
+        }
+    }
+    orderbook
+}
+
+fn market_sweep(c: &mut Criterion) {
+    c.bench_function("market order sweeping 50 levels", |b| {
+        b.iter_batched(
+            || two_sided_ladder(100, 10, 10_000),
+            |mut orderbook| {
+                let order = MarketOrder::new(OrderId(0), Quantity(5_000), Side::Bid);
+                orderbook.execute(Operation::Market(order))
+            },
+            BatchSize::LargeInput,
+        )
+    });
+}
+
+fn crossing_limit_partial_fills(c: &mut Criterion) {
+    c.bench_function("crossing limits with partial fills", |b| {
+        b.iter_batched(
+            || two_sided_ladder(100, 10, 10_000),
+            |mut orderbook| {
+                // every order takes 2.5 levels of the other side, partially filling the last
+                // order it reaches, and the final one rests the remainder of its quantity
+                for i in 0..20u64 {
+                    let (price, side) = match i % 2 {
+                        0 => (10_000 + 3 * (i / 2 + 1), Side::Bid),
+                        _ => (9_999 - 3 * (i / 2 + 1), Side::Ask),
+                    };
+                    orderbook.execute(Operation::Limit(LimitOrder::new(
+                        OrderId(1_000_000 + i as u128),
+                        Price(price),
+                        Quantity(255),
+                        side,
+                    )));
+                }
+                orderbook.execute(Operation::Limit(LimitOrder::new(
+                    OrderId(2_000_000),
+                    Price(10_500),
+                    Quantity(50_000),
+                    Side::Bid,
+                )));
+                orderbook
+            },
+            BatchSize::LargeInput,
+        )
+    });
+}
+
+fn mixed_cancel_modify_flow(c: &mut Criterion) {
+    c.bench_function("mixed cancel, modify and crossing flow", |b| {
+        b.iter_batched(
+            || two_sided_ladder(100, 10, 10_000),
+            |mut orderbook| {
+                for i in 0..1_000u64 {
+                    let ask_id = 1 + (i * 7 % 1_000) as u128;
+                    let bid_id = 1_001 + (i * 13 % 1_000) as u128;
+                    let operation = match i % 4 {
+                        0 => Operation::Cancel(OrderId(ask_id)),
+                        1 => Operation::Modify(LimitOrder::new(
+                            OrderId(bid_id),
+                            Price(9_950 + i % 50),
+                            Quantity(20),
+                            Side::Bid,
+                        )),
+                        2 => {
+                            Operation::Market(MarketOrder::new(OrderId(0), Quantity(35), Side::Bid))
+                        }
+                        _ => Operation::Limit(LimitOrder::new(
+                            OrderId(1_000_000 + i as u128),
+                            Price(9_990 + i % 20),
+                            Quantity(25),
+                            Side::Ask,
+                        )),
+                    };
+                    orderbook.execute(operation);
+                }
+                orderbook
+            },
+            BatchSize::LargeInput,
+        )
+    });
+}
+
 criterion_group!(
     benches,
     small_limit_ladder,
     insert_and_remove_small_limit_ladder,
     big_limit_ladder,
-    all_orders
+    all_orders,
+    market_sweep,
+    crossing_limit_partial_fills,
+    mixed_cancel_modify_flow
 );
 criterion_main!(benches);
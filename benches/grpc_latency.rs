@@ -0,0 +1,226 @@
+//! Measures the latency of the gRPC order entry end to end, from sending a request to its
+//! acknowledgement and to the execution event it produces, at increasing request rates.
+//!
+//! The server runs in process without persistence and without kafka. Execution events are taken
+//! off the outbox by a null sink instead of being published, so the measured event latency covers
+//! dispatching, batching and matching but not the broker.
+use gemmy::engine::configuration::configuration_loader::ConfigurationLoader;
+use gemmy::engine::persistence::outbox::Outbox;
+use gemmy::engine::services::order_dispatch_service::OrderDispatchService;
+use gemmy::engine::state::server_state::ServerState;
+use gemmy::protobuf::models::{CreateLimitOrderRequest, CreateOrder, OrderSide};
+use gemmy::protobuf::services::order_dispatcher_client::OrderDispatcherClient;
+use hdrhistogram::Histogram;
+use prost::Message;
+use std::collections::HashMap;
+use std::error::Error;
+use std::net::{SocketAddr, TcpListener};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::time::{sleep, sleep_until, Instant};
+use tonic::transport::Channel;
+
+const SYMBOL: &str = "BENCH";
+/// Request rates per second the latency is measured at.
+const LOADS: [u64; 4] = [1_000, 5_000, 20_000, 50_000];
+/// Requests sent at every load.
+const REQUESTS_PER_LOAD: u64 = 10_000;
+/// Connections the requests are spread over, as a single HTTP/2 connection gets reset by the
+/// server once too many streams are in flight on it.
+const CONNECTIONS: usize = 16;
+/// Time the execution events of a load are awaited for before the missing ones are counted.
+const EVENT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Arrival times of the execution events, keyed by the quantity of their order, which is unique
+/// for every request.
+type Arrivals = Arc<Mutex<HashMap<u64, Instant>>>;
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn Error>> {
+    let directory = std::env::temp_dir().join(format!("gemmy-latency-{}", uuid::Uuid::new_v4()));
+    let address = TcpListener::bind("127.0.0.1:0")?.local_addr()?;
+    configure(address, &directory);
+
+    let ConfigurationLoader {
+        server_configuration,
+        kafka_configuration,
+        persistence_configuration,
+        ..
+    } = ConfigurationLoader::load()?;
+    let state = Arc::new(ServerState::create(
+        server_configuration,
+        kafka_configuration,
+        persistence_configuration,
+    )?);
+    let arrivals = Arrivals::default();
+    tokio::spawn(null_sink(Arc::clone(&state.outbox), Arc::clone(&arrivals)));
+    tokio::spawn(
+        tonic::transport::Server::builder()
+            .add_service(OrderDispatchService::create(Arc::clone(&state)))
+            .serve(address),
+    );
+    let mut clients = Vec::with_capacity(CONNECTIONS);
+    for _ in 0..CONNECTIONS {
+        clients.push(connect(address).await?);
+    }
+
+    println!(
+        "{:>10} | {:>32} | {:>32} | {:>8} | {:>8}",
+        "rate", "request -> ack p50/p99/p999", "request -> event p50/p99/p999", "failed", "missing"
+    );
+    for (load, rate) in LOADS.into_iter().enumerate() {
+        let first_quantity = load as u64 * REQUESTS_PER_LOAD + 1;
+        run_load(&clients, &arrivals, rate, first_quantity).await;
+    }
+
+    state.shutdown_notification.notify_waiters();
+    let _ = std::fs::remove_dir_all(&directory);
+    Ok(())
+}
+
+/// Points the configuration at a throwaway outbox and turns off everything that is not needed
+/// to accept and execute orders. The environment takes precedence over `.env`.
+fn configure(address: SocketAddr, directory: &std::path::Path) {
+    let outbox_path = directory.join("outbox");
+    let properties = [
+        ("GRPC_SOCKET_ADDRESS", address.to_string()),
+        ("HTTP_ENABLED", "false".to_string()),
+        ("BINARY_ENABLED", "false".to_string()),
+        ("HEALTH_HTTP_ENABLED", "false".to_string()),
+        ("TICKERS", SYMBOL.to_string()),
+        ("OUTBOX_PATH", outbox_path.to_string_lossy().to_string()),
+        ("RUST_LOG", "warn,rdkafka=off".to_string()),
+        ("ENABLE_FILE_LOG", "false".to_string()),
+        ("WAL_ENABLED", "false".to_string()),
+        ("ORDER_STORE_ENABLED", "false".to_string()),
+        ("AUDIT_ENABLED", "false".to_string()),
+        ("RECORDING_ENABLED", "false".to_string()),
+        ("REPLAY_ENABLED", "false".to_string()),
+        ("LEDGER_ENABLED", "false".to_string()),
+        ("SESSION_ENABLED", "false".to_string()),
+        ("SETTLEMENT_ENABLED", "false".to_string()),
+        ("TRADES_ENABLED", "false".to_string()),
+        ("THROTTLE_ENABLED", "false".to_string()),
+    ];
+    for (key, value) in properties {
+        std::env::set_var(key, value);
+    }
+}
+
+async fn connect(address: SocketAddr) -> Result<OrderDispatcherClient<Channel>, Box<dyn Error>> {
+    let mut attempts = 0;
+    loop {
+        match OrderDispatcherClient::connect(format!("http://{}", address)).await {
+            Ok(client) => return Ok(client),
+            Err(_) if attempts < 50 => attempts += 1,
+            Err(e) => return Err(e.into()),
+        }
+        sleep(Duration::from_millis(100)).await;
+    }
+}
+
+/// Takes every event off the outbox as soon as it is enqueued, noting when the execution events
+/// of created orders arrived.
+async fn null_sink(outbox: Arc<Outbox>, arrivals: Arrivals) {
+    loop {
+        let events = match outbox.peek(1_024) {
+            Ok(events) => events,
+            Err(e) => {
+                eprintln!("failed to read outbox: {}", e);
+                return;
+            }
+        };
+        if events.is_empty() {
+            outbox.wait_for_events().await;
+            continue;
+        }
+        let arrived_at = Instant::now();
+        for (sequence, event) in events {
+            if event.schema_name == "CreateOrder" {
+                if let Ok(order) = CreateOrder::decode(event.payload.as_slice()) {
+                    arrivals.lock().unwrap().insert(order.quantity, arrived_at);
+                }
+            }
+            let _ = outbox.acknowledge(sequence);
+        }
+    }
+}
+
+/// Sends resting bids at a fixed rate without waiting for earlier ones to complete, then reports
+/// the latency distributions of the load.
+async fn run_load(
+    clients: &[OrderDispatcherClient<Channel>],
+    arrivals: &Arrivals,
+    rate: u64,
+    first_quantity: u64,
+) {
+    let interval = Duration::from_nanos(1_000_000_000 / rate);
+    let start = Instant::now();
+    let mut requests = Vec::with_capacity(REQUESTS_PER_LOAD as usize);
+    for i in 0..REQUESTS_PER_LOAD {
+        sleep_until(start + interval * i as u32).await;
+        let mut client = clients[i as usize % clients.len()].clone();
+        let quantity = first_quantity + i;
+        requests.push(tokio::spawn(async move {
+            let request = CreateLimitOrderRequest {
+                price: 1_000,
+                quantity,
+                side: OrderSide::Bid as i32,
+                symbol: SYMBOL.to_string(),
+                ..Default::default()
+            };
+            let sent_at = Instant::now();
+            let acknowledged = client.limit(request).await.is_ok();
+            (quantity, sent_at, acknowledged.then(|| sent_at.elapsed()))
+        }));
+    }
+
+    let mut sent = Vec::with_capacity(requests.len());
+    let mut ack_latency = histogram();
+    let mut failed = 0;
+    for request in requests {
+        match request.await {
+            Ok((quantity, sent_at, Some(latency))) => {
+                ack_latency.saturating_record(latency.as_nanos() as u64);
+                sent.push((quantity, sent_at));
+            }
+            _ => failed += 1,
+        }
+    }
+
+    let deadline = Instant::now() + EVENT_TIMEOUT;
+    while Instant::now() < deadline && arrivals.lock().unwrap().len() < sent.len() {
+        sleep(Duration::from_millis(10)).await;
+    }
+    let mut event_latency = histogram();
+    let mut missing = 0;
+    let mut arrivals = arrivals.lock().unwrap();
+    for (quantity, sent_at) in sent {
+        match arrivals.remove(&quantity) {
+            Some(arrived_at) => {
+                event_latency.saturating_record((arrived_at - sent_at).as_nanos() as u64)
+            }
+            None => missing += 1,
+        }
+    }
+    arrivals.clear();
+
+    println!(
+        "{:>6} r/s | {} | {} | {:>8} | {:>8}",
+        rate,
+        percentiles(&ack_latency),
+        percentiles(&event_latency),
+        failed,
+        missing
+    );
+}
+
+fn histogram() -> Histogram<u64> {
+    Histogram::new_with_bounds(1, 60_000_000_000, 3).unwrap()
+}
+
+fn percentiles(histogram: &Histogram<u64>) -> String {
+    let [p50, p99, p999] = [0.5, 0.99, 0.999]
+        .map(|quantile| Duration::from_nanos(histogram.value_at_quantile(quantile)));
+    format!("{:>10.2?} {:>10.2?} {:>10.2?}", p50, p99, p999)
+}
@@ -0,0 +1,125 @@
+//! Measures how many execution events per second the publisher delivers to kafka, awaiting every
+//! delivery before sending the next event and with increasing numbers of deliveries in flight.
+//!
+//! Unlike the other benchmarks this one needs the broker and the schema registry configured in
+//! `.env` to be running. Every run publishes to the events topic of a throwaway symbol.
+use gemmy::engine::configuration::configuration_loader::ConfigurationLoader;
+use gemmy::engine::persistence::outbox::OutboxEvent;
+use gemmy::engine::state::server_state::ServerState;
+use gemmy::engine::tasks::publisher_task::Publisher;
+use gemmy::engine::utils::time::generate_u128_timestamp;
+use gemmy::protobuf::models::CreateOrder;
+use prost::Message;
+use std::error::Error;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::{sleep, Instant};
+
+/// Deliveries in flight the throughput is measured at, one being the serial publisher.
+const MAX_IN_FLIGHT: [usize; 4] = [1, 10, 100, 1_000];
+/// Events published at every setting.
+const EVENTS: u64 = 20_000;
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn Error>> {
+    let symbol = format!("PUB{}", generate_u128_timestamp() % 1_000_000);
+    let directory = std::env::temp_dir().join(format!("gemmy-publisher-{}", uuid::Uuid::new_v4()));
+    configure(&symbol, &directory);
+
+    let ConfigurationLoader {
+        server_configuration,
+        kafka_configuration,
+        persistence_configuration,
+        ..
+    } = ConfigurationLoader::load()?;
+    let state = Arc::new(
+        ServerState::init(
+            server_configuration,
+            Arc::clone(&kafka_configuration),
+            persistence_configuration,
+        )
+        .await?,
+    );
+    let topic = state.symbols.read().unwrap()[&symbol].kafka_topic.clone();
+
+    println!(
+        "{:>10} | {:>10} | {:>14}",
+        "in flight", "seconds", "events/second"
+    );
+    for max_in_flight in MAX_IN_FLIGHT {
+        let events = (0..EVENTS)
+            .map(|quantity| OutboxEvent {
+                topic: topic.clone(),
+                schema_name: "CreateOrder".to_string(),
+                payload: CreateOrder {
+                    quantity,
+                    ..Default::default()
+                }
+                .encode_to_vec(),
+            })
+            .collect();
+        state.outbox.enqueue(events)?;
+
+        let mut publisher = Publisher::new(Arc::clone(&kafka_configuration), Arc::clone(&state));
+        publisher.max_in_flight = max_in_flight;
+        let started = Instant::now();
+        let drained = tokio::select! {
+            _ = publisher.run() => false,
+            _ = drained(&state) => true,
+        };
+        let elapsed = started.elapsed();
+        if !drained {
+            eprintln!(
+                "publisher stopped with {} events pending",
+                state.outbox.len()
+            );
+            break;
+        }
+        println!(
+            "{:>10} | {:>10.3} | {:>14.0}",
+            max_in_flight,
+            elapsed.as_secs_f64(),
+            EVENTS as f64 / elapsed.as_secs_f64()
+        );
+    }
+
+    state.shutdown_notification.notify_waiters();
+    let _ = std::fs::remove_dir_all(&directory);
+    Ok(())
+}
+
+/// Points the configuration at a throwaway outbox and a single symbol, and turns off everything
+/// that is not needed to publish events. The environment takes precedence over `.env`.
+fn configure(symbol: &str, directory: &std::path::Path) {
+    let outbox_path = directory.join("outbox");
+    let properties = [
+        ("HTTP_ENABLED", "false".to_string()),
+        ("BINARY_ENABLED", "false".to_string()),
+        ("HEALTH_HTTP_ENABLED", "false".to_string()),
+        ("STANDALONE_ENABLED", "false".to_string()),
+        ("TICKERS", symbol.to_string()),
+        ("OUTBOX_PATH", outbox_path.to_string_lossy().to_string()),
+        ("RUST_LOG", "warn,rdkafka=off".to_string()),
+        ("ENABLE_FILE_LOG", "false".to_string()),
+        ("WAL_ENABLED", "false".to_string()),
+        ("ORDER_STORE_ENABLED", "false".to_string()),
+        ("AUDIT_ENABLED", "false".to_string()),
+        ("RECORDING_ENABLED", "false".to_string()),
+        ("REPLAY_ENABLED", "false".to_string()),
+        ("LEDGER_ENABLED", "false".to_string()),
+        ("SESSION_ENABLED", "false".to_string()),
+        ("SETTLEMENT_ENABLED", "false".to_string()),
+        ("TRADES_ENABLED", "false".to_string()),
+        ("KAFKA_DELIVERY_FAILURE_POLICY", "drop".to_string()),
+    ];
+    for (key, value) in properties {
+        std::env::set_var(key, value);
+    }
+}
+
+/// Resolves once the publisher took every event off the outbox.
+async fn drained(state: &ServerState) {
+    while !state.outbox.is_empty() {
+        sleep(Duration::from_millis(1)).await;
+    }
+}
@@ -1,9 +1,11 @@
 use std::io::Result;
 
 fn main() -> Result<()> {
+    // the generated code is only compiled into the engine, so only the engine needs protoc
+    #[cfg(feature = "engine")]
     tonic_build::configure()
         .build_server(true)
-        .build_client(false)
+        .build_client(true)
         .out_dir("src/protobuf")
         .compile_protos(&["resources/protobuf/models.proto", "resources/protobuf/services.proto"],
         &["resources/protobuf"])?;
@@ -0,0 +1,27 @@
+//! Places a limit order on a running server and cancels it again.
+//!
+//! ```sh
+//! cargo run --features client --example place_and_cancel -- http://[::1]:50051 BTCUSDT
+//! ```
+use gemmy::client::gemmy_client::GemmyClient;
+use gemmy::core::models::Side;
+use std::error::Error;
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn Error>> {
+    let mut args = std::env::args().skip(1);
+    let address = args
+        .next()
+        .unwrap_or_else(|| "http://[::1]:50051".to_string());
+    let symbol = args.next().unwrap_or_else(|| "BTCUSDT".to_string());
+
+    let mut client = GemmyClient::connect(address, "example").await?;
+    let placed = client.place_limit(&symbol, Side::Bid, 100, 10).await?;
+    println!(
+        "placed order {:032x}, {} operations queued ahead of it",
+        placed.order_id, placed.queue_depth
+    );
+    let cancelled = client.cancel(&symbol, placed.order_id).await?;
+    println!("cancel of order {:032x} accepted", cancelled.order_id);
+    Ok(())
+}
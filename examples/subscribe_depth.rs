@@ -0,0 +1,32 @@
+//! Prints the best levels of a symbol as the server publishes them, surviving server restarts.
+//!
+//! ```sh
+//! cargo run --features client --example subscribe_depth -- http://[::1]:50051 BTCUSDT
+//! ```
+use gemmy::client::gemmy_client::GemmyClient;
+use std::error::Error;
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn Error>> {
+    let mut args = std::env::args().skip(1);
+    let address = args
+        .next()
+        .unwrap_or_else(|| "http://[::1]:50051".to_string());
+    let symbol = args.next().unwrap_or_else(|| "BTCUSDT".to_string());
+
+    let client = GemmyClient::connect(address, "").await?;
+    let mut subscription = client.subscribe_depth(&symbol, 5);
+    while let Some(snapshot) = subscription.next().await {
+        let snapshot = snapshot?;
+        println!(
+            "{}: bid {} ask {} last {} | {} bid levels, {} ask levels",
+            symbol,
+            snapshot.max_bid,
+            snapshot.min_ask,
+            snapshot.last_trade_price,
+            snapshot.bids.len(),
+            snapshot.asks.len()
+        );
+    }
+    Ok(())
+}
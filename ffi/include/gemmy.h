@@ -0,0 +1,257 @@
+/* Generated by cbindgen from ffi/src/lib.rs, do not edit by hand. */
+
+#ifndef GEMMY_H
+#define GEMMY_H
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+// The call succeeded.
+#define GEMMY_OK 0
+
+// A required pointer was null.
+#define GEMMY_ERROR_NULL_POINTER -1
+
+// An integer passed for an enumeration is not one of its constants.
+#define GEMMY_ERROR_INVALID_ARGUMENT -2
+
+// The orderbook panicked, after which it should no longer be used.
+#define GEMMY_ERROR_PANIC -3
+
+// The order to cancel is not resting in the orderbook.
+#define GEMMY_ERROR_ORDER_NOT_FOUND -4
+
+#define GEMMY_SIDE_BID 0
+
+#define GEMMY_SIDE_ASK 1
+
+#define GEMMY_OPERATION_LIMIT 0
+
+#define GEMMY_OPERATION_MARKET 1
+
+#define GEMMY_OPERATION_MODIFY 2
+
+#define GEMMY_OPERATION_CANCEL 3
+
+// Decreases the quantity of a resting order by `quantity`, keeping its priority.
+#define GEMMY_OPERATION_REDUCE 4
+
+// Starts a call, in which limit orders rest without matching and market orders fail until the
+// uncross. Only the kind is read.
+#define GEMMY_OPERATION_CALL 5
+
+// Ends a call by matching the crossed part of the book at a single price. Only the kind is read.
+#define GEMMY_OPERATION_UNCROSS 6
+
+// The order was placed without matching.
+#define GEMMY_RESULT_CREATED 0
+
+// The order matched and its remainder was placed.
+#define GEMMY_RESULT_PARTIALLY_FILLED 1
+
+// The order matched in full.
+#define GEMMY_RESULT_FILLED 2
+
+// The quantity of a resting order was changed in place.
+#define GEMMY_RESULT_MODIFIED 3
+
+// A resting order was cancelled.
+#define GEMMY_RESULT_CANCELLED 4
+
+// The operation was rejected by the orderbook, for example a cancel of an unknown order.
+#define GEMMY_RESULT_FAILED 5
+
+// The quantity of a resting order was decreased in place, keeping its priority.
+#define GEMMY_RESULT_REDUCED 6
+
+// A quote replaced the orders resting under its ids. What each side left resting is its
+// quantity less the fills under its id.
+#define GEMMY_RESULT_QUOTED 7
+
+// A call was started.
+#define GEMMY_RESULT_CALLED 8
+
+// A call ended. Every fill of the uncross names the bid as the incoming order.
+#define GEMMY_RESULT_UNCROSSED 9
+
+// An orderbook owned by the caller, created with `gemmy_orderbook_new` and released with
+// `gemmy_orderbook_free`.
+typedef struct GemmyOrderBook GemmyOrderBook;
+
+// A 128 bit order id.
+typedef struct GemmyOrderId {
+  uint64_t high;
+  uint64_t low;
+} GemmyOrderId;
+
+// An operation to execute on an orderbook.
+typedef struct GemmyOperation {
+  // One of the `GEMMY_OPERATION_*` constants.
+  uint8_t kind;
+  // One of the `GEMMY_SIDE_*` constants, ignored by cancels and reductions.
+  uint8_t side;
+  struct GemmyOrderId id;
+  // Limit price, ignored by market orders, cancels and reductions.
+  uint64_t price;
+  // Ignored by cancels, the quantity to take off the order for reductions.
+  uint64_t quantity;
+} GemmyOperation;
+
+// A match of an incoming order against a resting one.
+typedef struct GemmyFill {
+  // Id of the incoming order.
+  struct GemmyOrderId order_id;
+  // Id of the resting order it matched.
+  struct GemmyOrderId matched_order_id;
+  // One of the `GEMMY_SIDE_*` constants, the side of the incoming order.
+  uint8_t taker_side;
+  uint64_t price;
+  uint64_t quantity;
+} GemmyFill;
+
+// A limit order resting in the orderbook.
+typedef struct GemmyOrder {
+  struct GemmyOrderId id;
+  // One of the `GEMMY_SIDE_*` constants.
+  uint8_t side;
+  uint64_t price;
+  uint64_t quantity;
+} GemmyOrder;
+
+// The outcome of an executed operation.
+typedef struct GemmyExecution {
+  // One of the `GEMMY_RESULT_*` constants.
+  uint8_t result;
+  // Whether `order` holds the order left resting by the operation.
+  bool has_order;
+  struct GemmyOrder order;
+  // The number of fills the operation produced. Only as many as the buffer passed holds are
+  // written, so a count above its capacity means fills were left out.
+  size_t fill_count;
+} GemmyExecution;
+
+// The aggregated quantity resting at a price.
+typedef struct GemmyLevel {
+  uint64_t price;
+  uint64_t quantity;
+} GemmyLevel;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Creates an empty orderbook.
+//
+// # Arguments
+//
+// * `queue_capacity` - Orders preallocated for every price level.
+// * `store_capacity` - Orders preallocated for the whole book, which grows past it as needed.
+//
+// # Returns
+//
+// * The orderbook, to be released with `gemmy_orderbook_free`.
+struct GemmyOrderBook *gemmy_orderbook_new(size_t queue_capacity, size_t store_capacity);
+
+// Releases an orderbook. Passing null does nothing.
+//
+// # Safety
+//
+// `book` must be null or returned by `gemmy_orderbook_new` and not yet released.
+void gemmy_orderbook_free(struct GemmyOrderBook *book);
+
+// Executes an operation on the orderbook.
+//
+// # Arguments
+//
+// * `fills` - Buffer receiving the fills of the operation, may be null if `fills_capacity` is 0.
+// * `execution` - Receives the outcome of the operation.
+//
+// # Returns
+//
+// * `GEMMY_OK`, or an error code if the operation was not executed.
+//
+// # Safety
+//
+// `book` must be a live orderbook, `operation` and `execution` must point to valid memory and
+// `fills` must hold at least `fills_capacity` fills.
+int32_t gemmy_orderbook_execute(struct GemmyOrderBook *book,
+                                const struct GemmyOperation *operation,
+                                struct GemmyFill *fills,
+                                size_t fills_capacity,
+                                struct GemmyExecution *execution);
+
+// Replaces the orders resting under the ids of the bid and the ask with them, placing the bid
+// first. A side without quantity is withdrawn.
+//
+// # Arguments
+//
+// * `fills` - Buffer receiving the fills of both sides, may be null if `fills_capacity` is 0.
+// * `execution` - Receives the outcome of the quote.
+//
+// # Returns
+//
+// * `GEMMY_OK`, or an error code if the quote was not executed.
+//
+// # Safety
+//
+// `book` must be a live orderbook, `bid`, `ask` and `execution` must point to valid memory and
+// `fills` must hold at least `fills_capacity` fills.
+int32_t gemmy_orderbook_quote(struct GemmyOrderBook *book,
+                              const struct GemmyOrder *bid,
+                              const struct GemmyOrder *ask,
+                              struct GemmyFill *fills,
+                              size_t fills_capacity,
+                              struct GemmyExecution *execution);
+
+// Cancels a resting order, a shorthand for executing a cancel.
+//
+// # Returns
+//
+// * `GEMMY_OK` if the order was cancelled, `GEMMY_ERROR_ORDER_NOT_FOUND` if it was not resting,
+//   or another error code.
+//
+// # Safety
+//
+// `book` must be a live orderbook.
+int32_t gemmy_orderbook_cancel(struct GemmyOrderBook *book, struct GemmyOrderId id);
+
+// Writes the aggregated levels of both sides of the orderbook, as returned by
+// `OrderBook::depth`.
+//
+// # Arguments
+//
+// * `levels` - The number of levels to write per side, both buffers must hold as many.
+// * `bid_count` - Receives the number of bid levels written.
+// * `ask_count` - Receives the number of ask levels written.
+//
+// # Safety
+//
+// `book` must be a live orderbook, `bids` and `asks` must hold at least `levels` levels and the
+// counts must point to valid memory.
+int32_t gemmy_orderbook_depth(const struct GemmyOrderBook *book,
+                              size_t levels,
+                              struct GemmyLevel *bids,
+                              struct GemmyLevel *asks,
+                              size_t *bid_count,
+                              size_t *ask_count);
+
+// Reads the best prices of the orderbook.
+//
+// # Arguments
+//
+// * `max_bid` - Receives the highest bid, or 0 when there are no bids.
+// * `min_ask` - Receives the lowest ask, or `UINT64_MAX` when there are no asks.
+//
+// # Safety
+//
+// `book` must be a live orderbook and the prices must point to valid memory.
+int32_t gemmy_orderbook_best_prices(const struct GemmyOrderBook *book,
+                                    uint64_t *max_bid,
+                                    uint64_t *min_ask);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* GEMMY_H */
@@ -0,0 +1,667 @@
+//! A C ABI over the [`OrderBook`] of gemmy, so that trading systems written in other languages
+//! can embed the matcher in process. The header `include/gemmy.h` is generated from this file by
+//! the build script.
+//!
+//! Enumerations cross the boundary as integers with named constants, so that a value written by
+//! a caller can never be an invalid Rust enum. Order ids are 128 bit and are passed as two 64 bit
+//! halves, as there is no portable 128 bit integer in C.
+//!
+//! None of the functions are thread safe on the same orderbook, callers synchronise access to it.
+use gemmy::core::models::{
+    ExecutionResult, FillMetaData, FillResult, Level, LimitOrder, MarketOrder, ModifyResult,
+    Operation, Side,
+};
+use gemmy::core::orderbook::OrderBook;
+use gemmy::core::types::{OrderId, Price, Quantity};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::slice;
+
+/// The call succeeded.
+pub const GEMMY_OK: i32 = 0;
+/// A required pointer was null.
+pub const GEMMY_ERROR_NULL_POINTER: i32 = -1;
+/// An integer passed for an enumeration is not one of its constants.
+pub const GEMMY_ERROR_INVALID_ARGUMENT: i32 = -2;
+/// The orderbook panicked, after which it should no longer be used.
+pub const GEMMY_ERROR_PANIC: i32 = -3;
+/// The order to cancel is not resting in the orderbook.
+pub const GEMMY_ERROR_ORDER_NOT_FOUND: i32 = -4;
+
+pub const GEMMY_SIDE_BID: u8 = 0;
+pub const GEMMY_SIDE_ASK: u8 = 1;
+
+pub const GEMMY_OPERATION_LIMIT: u8 = 0;
+pub const GEMMY_OPERATION_MARKET: u8 = 1;
+pub const GEMMY_OPERATION_MODIFY: u8 = 2;
+pub const GEMMY_OPERATION_CANCEL: u8 = 3;
+/// Decreases the quantity of a resting order by `quantity`, keeping its priority.
+pub const GEMMY_OPERATION_REDUCE: u8 = 4;
+/// Starts a call, in which limit orders rest without matching and market orders fail until the
+/// uncross. Only the kind is read.
+pub const GEMMY_OPERATION_CALL: u8 = 5;
+/// Ends a call by matching the crossed part of the book at a single price. Only the kind is read.
+pub const GEMMY_OPERATION_UNCROSS: u8 = 6;
+
+/// The order was placed without matching.
+pub const GEMMY_RESULT_CREATED: u8 = 0;
+/// The order matched and its remainder was placed.
+pub const GEMMY_RESULT_PARTIALLY_FILLED: u8 = 1;
+/// The order matched in full.
+pub const GEMMY_RESULT_FILLED: u8 = 2;
+/// The quantity of a resting order was changed in place.
+pub const GEMMY_RESULT_MODIFIED: u8 = 3;
+/// A resting order was cancelled.
+pub const GEMMY_RESULT_CANCELLED: u8 = 4;
+/// The operation was rejected by the orderbook, for example a cancel of an unknown order.
+pub const GEMMY_RESULT_FAILED: u8 = 5;
+/// The quantity of a resting order was decreased in place, keeping its priority.
+pub const GEMMY_RESULT_REDUCED: u8 = 6;
+/// A quote replaced the orders resting under its ids. What each side left resting is its
+/// quantity less the fills under its id.
+pub const GEMMY_RESULT_QUOTED: u8 = 7;
+/// A call was started.
+pub const GEMMY_RESULT_CALLED: u8 = 8;
+/// A call ended. Every fill of the uncross names the bid as the incoming order.
+pub const GEMMY_RESULT_UNCROSSED: u8 = 9;
+
+/// An orderbook owned by the caller, created with `gemmy_orderbook_new` and released with
+/// `gemmy_orderbook_free`.
+pub struct GemmyOrderBook {
+    book: OrderBook,
+}
+
+/// A 128 bit order id.
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+pub struct GemmyOrderId {
+    pub high: u64,
+    pub low: u64,
+}
+
+/// An operation to execute on an orderbook.
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+pub struct GemmyOperation {
+    /// One of the `GEMMY_OPERATION_*` constants.
+    pub kind: u8,
+    /// One of the `GEMMY_SIDE_*` constants, ignored by cancels and reductions.
+    pub side: u8,
+    pub id: GemmyOrderId,
+    /// Limit price, ignored by market orders, cancels and reductions.
+    pub price: u64,
+    /// Ignored by cancels, the quantity to take off the order for reductions.
+    pub quantity: u64,
+}
+
+/// A limit order resting in the orderbook.
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+pub struct GemmyOrder {
+    pub id: GemmyOrderId,
+    /// One of the `GEMMY_SIDE_*` constants.
+    pub side: u8,
+    pub price: u64,
+    pub quantity: u64,
+}
+
+/// A match of an incoming order against a resting one.
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+pub struct GemmyFill {
+    /// Id of the incoming order.
+    pub order_id: GemmyOrderId,
+    /// Id of the resting order it matched.
+    pub matched_order_id: GemmyOrderId,
+    /// One of the `GEMMY_SIDE_*` constants, the side of the incoming order.
+    pub taker_side: u8,
+    pub price: u64,
+    pub quantity: u64,
+}
+
+/// The outcome of an executed operation.
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+pub struct GemmyExecution {
+    /// One of the `GEMMY_RESULT_*` constants.
+    pub result: u8,
+    /// Whether `order` holds the order left resting by the operation.
+    pub has_order: bool,
+    pub order: GemmyOrder,
+    /// The number of fills the operation produced. Only as many as the buffer passed holds are
+    /// written, so a count above its capacity means fills were left out.
+    pub fill_count: usize,
+}
+
+/// The aggregated quantity resting at a price.
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+pub struct GemmyLevel {
+    pub price: u64,
+    pub quantity: u64,
+}
+
+impl From<u128> for GemmyOrderId {
+    fn from(id: u128) -> Self {
+        GemmyOrderId {
+            high: (id >> 64) as u64,
+            low: id as u64,
+        }
+    }
+}
+
+impl From<GemmyOrderId> for u128 {
+    fn from(id: GemmyOrderId) -> Self {
+        (id.high as u128) << 64 | id.low as u128
+    }
+}
+
+impl From<LimitOrder> for GemmyOrder {
+    fn from(order: LimitOrder) -> Self {
+        GemmyOrder {
+            id: order.id.into(),
+            side: side_to_c(order.side),
+            price: order.price,
+            quantity: order.quantity,
+        }
+    }
+}
+
+impl From<&FillMetaData> for GemmyFill {
+    fn from(fill: &FillMetaData) -> Self {
+        GemmyFill {
+            order_id: fill.order_id.into(),
+            matched_order_id: fill.matched_order_id.into(),
+            taker_side: side_to_c(fill.taker_side),
+            price: fill.price,
+            quantity: fill.quantity,
+        }
+    }
+}
+
+impl From<Level> for GemmyLevel {
+    fn from(level: Level) -> Self {
+        GemmyLevel {
+            price: level.price,
+            quantity: level.quantity,
+        }
+    }
+}
+
+fn side_to_c(side: Side) -> u8 {
+    match side {
+        Side::Bid => GEMMY_SIDE_BID,
+        Side::Ask => GEMMY_SIDE_ASK,
+    }
+}
+
+fn side_from_c(side: u8) -> Option<Side> {
+    match side {
+        GEMMY_SIDE_BID => Some(Side::Bid),
+        GEMMY_SIDE_ASK => Some(Side::Ask),
+        _ => None,
+    }
+}
+
+fn operation_from_c(operation: &GemmyOperation) -> Option<Operation> {
+    let id = operation.id.into();
+    let side = || side_from_c(operation.side);
+    match operation.kind {
+        GEMMY_OPERATION_LIMIT => Some(Operation::Limit(LimitOrder::new(
+            OrderId(id),
+            Price(operation.price),
+            Quantity(operation.quantity),
+            side()?,
+        ))),
+        GEMMY_OPERATION_MARKET => Some(Operation::Market(MarketOrder::new(
+            OrderId(id),
+            Quantity(operation.quantity),
+            side()?,
+        ))),
+        GEMMY_OPERATION_MODIFY => Some(Operation::Modify(LimitOrder::new(
+            OrderId(id),
+            Price(operation.price),
+            Quantity(operation.quantity),
+            side()?,
+        ))),
+        GEMMY_OPERATION_CANCEL => Some(Operation::Cancel(OrderId(id))),
+        GEMMY_OPERATION_REDUCE => Some(Operation::Reduce {
+            id: OrderId(id),
+            quantity_delta: operation.quantity,
+        }),
+        GEMMY_OPERATION_CALL => Some(Operation::Call),
+        GEMMY_OPERATION_UNCROSS => Some(Operation::Uncross),
+        _ => None,
+    }
+}
+
+/// Maps the result of an execution, writing as many of its fills as the buffer holds.
+fn execution_to_c(result: &ExecutionResult, fills: &mut [GemmyFill]) -> GemmyExecution {
+    let produced = result.fills();
+    for (slot, fill) in fills.iter_mut().zip(produced) {
+        *slot = fill.into();
+    }
+    let mut execution = GemmyExecution {
+        fill_count: produced.len(),
+        ..GemmyExecution::default()
+    };
+    let fill_result = match result {
+        ExecutionResult::Executed(fill_result) => fill_result,
+        ExecutionResult::Modified(ModifyResult::Created(fill_result)) => fill_result,
+        ExecutionResult::Modified(_) => {
+            execution.result = GEMMY_RESULT_MODIFIED;
+            return execution;
+        }
+        ExecutionResult::Cancelled(_) => {
+            execution.result = GEMMY_RESULT_CANCELLED;
+            return execution;
+        }
+        ExecutionResult::Reduced(order) => {
+            execution.result = GEMMY_RESULT_REDUCED;
+            execution.has_order = true;
+            execution.order = (*order).into();
+            return execution;
+        }
+        ExecutionResult::Quoted(_) => {
+            execution.result = GEMMY_RESULT_QUOTED;
+            return execution;
+        }
+        ExecutionResult::Called => {
+            execution.result = GEMMY_RESULT_CALLED;
+            return execution;
+        }
+        ExecutionResult::Uncrossed(_) => {
+            execution.result = GEMMY_RESULT_UNCROSSED;
+            return execution;
+        }
+        ExecutionResult::Failed(_) => {
+            execution.result = GEMMY_RESULT_FAILED;
+            return execution;
+        }
+    };
+    let (result, order) = match fill_result {
+        FillResult::Created(order) => (GEMMY_RESULT_CREATED, Some(*order)),
+        FillResult::PartiallyFilled(order, _) => (GEMMY_RESULT_PARTIALLY_FILLED, Some(*order)),
+        FillResult::Filled(_) => (GEMMY_RESULT_FILLED, None),
+        FillResult::Failed => (GEMMY_RESULT_FAILED, None),
+    };
+    execution.result = result;
+    execution.has_order = order.is_some();
+    execution.order = order.map(GemmyOrder::from).unwrap_or_default();
+    execution
+}
+
+/// Runs a call, turning a panic into an error code instead of unwinding into the caller.
+fn guard(call: impl FnOnce() -> i32) -> i32 {
+    catch_unwind(AssertUnwindSafe(call)).unwrap_or(GEMMY_ERROR_PANIC)
+}
+
+/// Creates an empty orderbook.
+///
+/// # Arguments
+///
+/// * `queue_capacity` - Orders preallocated for every price level.
+/// * `store_capacity` - Orders preallocated for the whole book, which grows past it as needed.
+///
+/// # Returns
+///
+/// * The orderbook, to be released with `gemmy_orderbook_free`.
+#[no_mangle]
+pub extern "C" fn gemmy_orderbook_new(
+    queue_capacity: usize,
+    store_capacity: usize,
+) -> *mut GemmyOrderBook {
+    let book = OrderBook::new(String::from("ffi"), queue_capacity, store_capacity);
+    Box::into_raw(Box::new(GemmyOrderBook { book }))
+}
+
+/// Releases an orderbook. Passing null does nothing.
+///
+/// # Safety
+///
+/// `book` must be null or returned by `gemmy_orderbook_new` and not yet released.
+#[no_mangle]
+pub unsafe extern "C" fn gemmy_orderbook_free(book: *mut GemmyOrderBook) {
+    if !book.is_null() {
+        drop(Box::from_raw(book));
+    }
+}
+
+/// Executes an operation on the orderbook.
+///
+/// # Arguments
+///
+/// * `fills` - Buffer receiving the fills of the operation, may be null if `fills_capacity` is 0.
+/// * `execution` - Receives the outcome of the operation.
+///
+/// # Returns
+///
+/// * `GEMMY_OK`, or an error code if the operation was not executed.
+///
+/// # Safety
+///
+/// `book` must be a live orderbook, `operation` and `execution` must point to valid memory and
+/// `fills` must hold at least `fills_capacity` fills.
+#[no_mangle]
+pub unsafe extern "C" fn gemmy_orderbook_execute(
+    book: *mut GemmyOrderBook,
+    operation: *const GemmyOperation,
+    fills: *mut GemmyFill,
+    fills_capacity: usize,
+    execution: *mut GemmyExecution,
+) -> i32 {
+    if book.is_null()
+        || operation.is_null()
+        || execution.is_null()
+        || (fills.is_null() && fills_capacity > 0)
+    {
+        return GEMMY_ERROR_NULL_POINTER;
+    }
+    let Some(operation) = operation_from_c(&*operation) else {
+        return GEMMY_ERROR_INVALID_ARGUMENT;
+    };
+    let fills: &mut [GemmyFill] = match fills_capacity {
+        0 => &mut [],
+        _ => slice::from_raw_parts_mut(fills, fills_capacity),
+    };
+    guard(|| {
+        let result = (*book).book.execute(operation);
+        *execution = execution_to_c(&result, fills);
+        GEMMY_OK
+    })
+}
+
+/// Replaces the orders resting under the ids of the bid and the ask with them, placing the bid
+/// first. A side without quantity is withdrawn.
+///
+/// # Arguments
+///
+/// * `fills` - Buffer receiving the fills of both sides, may be null if `fills_capacity` is 0.
+/// * `execution` - Receives the outcome of the quote.
+///
+/// # Returns
+///
+/// * `GEMMY_OK`, or an error code if the quote was not executed.
+///
+/// # Safety
+///
+/// `book` must be a live orderbook, `bid`, `ask` and `execution` must point to valid memory and
+/// `fills` must hold at least `fills_capacity` fills.
+#[no_mangle]
+pub unsafe extern "C" fn gemmy_orderbook_quote(
+    book: *mut GemmyOrderBook,
+    bid: *const GemmyOrder,
+    ask: *const GemmyOrder,
+    fills: *mut GemmyFill,
+    fills_capacity: usize,
+    execution: *mut GemmyExecution,
+) -> i32 {
+    if book.is_null()
+        || bid.is_null()
+        || ask.is_null()
+        || execution.is_null()
+        || (fills.is_null() && fills_capacity > 0)
+    {
+        return GEMMY_ERROR_NULL_POINTER;
+    }
+    let order = |order: &GemmyOrder| {
+        Some(LimitOrder::new(
+            OrderId(order.id.into()),
+            Price(order.price),
+            Quantity(order.quantity),
+            side_from_c(order.side)?,
+        ))
+    };
+    let (Some(bid), Some(ask)) = (order(&*bid), order(&*ask)) else {
+        return GEMMY_ERROR_INVALID_ARGUMENT;
+    };
+    let fills: &mut [GemmyFill] = match fills_capacity {
+        0 => &mut [],
+        _ => slice::from_raw_parts_mut(fills, fills_capacity),
+    };
+    guard(|| {
+        let result = (*book).book.execute(Operation::Quote { bid, ask });
+        *execution = execution_to_c(&result, fills);
+        GEMMY_OK
+    })
+}
+
+/// Cancels a resting order, a shorthand for executing a cancel.
+///
+/// # Returns
+///
+/// * `GEMMY_OK` if the order was cancelled, `GEMMY_ERROR_ORDER_NOT_FOUND` if it was not resting,
+///   or another error code.
+///
+/// # Safety
+///
+/// `book` must be a live orderbook.
+#[no_mangle]
+pub unsafe extern "C" fn gemmy_orderbook_cancel(
+    book: *mut GemmyOrderBook,
+    id: GemmyOrderId,
+) -> i32 {
+    if book.is_null() {
+        return GEMMY_ERROR_NULL_POINTER;
+    }
+    guard(
+        || match (*book).book.execute(Operation::Cancel(OrderId(id.into()))) {
+            ExecutionResult::Cancelled(_) => GEMMY_OK,
+            _ => GEMMY_ERROR_ORDER_NOT_FOUND,
+        },
+    )
+}
+
+/// Writes the aggregated levels of both sides of the orderbook, as returned by
+/// `OrderBook::depth`.
+///
+/// # Arguments
+///
+/// * `levels` - The number of levels to write per side, both buffers must hold as many.
+/// * `bid_count` - Receives the number of bid levels written.
+/// * `ask_count` - Receives the number of ask levels written.
+///
+/// # Safety
+///
+/// `book` must be a live orderbook, `bids` and `asks` must hold at least `levels` levels and the
+/// counts must point to valid memory.
+#[no_mangle]
+pub unsafe extern "C" fn gemmy_orderbook_depth(
+    book: *const GemmyOrderBook,
+    levels: usize,
+    bids: *mut GemmyLevel,
+    asks: *mut GemmyLevel,
+    bid_count: *mut usize,
+    ask_count: *mut usize,
+) -> i32 {
+    if book.is_null()
+        || bid_count.is_null()
+        || ask_count.is_null()
+        || (levels > 0 && (bids.is_null() || asks.is_null()))
+    {
+        return GEMMY_ERROR_NULL_POINTER;
+    }
+    guard(|| {
+        let depth = (*book).book.depth(levels);
+        for (buffer, side_levels, count) in [
+            (bids, &depth.bids, bid_count),
+            (asks, &depth.asks, ask_count),
+        ] {
+            for (offset, level) in side_levels.iter().take(levels).enumerate() {
+                *buffer.add(offset) = (*level).into();
+            }
+            *count = side_levels.len().min(levels);
+        }
+        GEMMY_OK
+    })
+}
+
+/// Reads the best prices of the orderbook.
+///
+/// # Arguments
+///
+/// * `max_bid` - Receives the highest bid, or 0 when there are no bids.
+/// * `min_ask` - Receives the lowest ask, or `UINT64_MAX` when there are no asks.
+///
+/// # Safety
+///
+/// `book` must be a live orderbook and the prices must point to valid memory.
+#[no_mangle]
+pub unsafe extern "C" fn gemmy_orderbook_best_prices(
+    book: *const GemmyOrderBook,
+    max_bid: *mut u64,
+    min_ask: *mut u64,
+) -> i32 {
+    if book.is_null() || max_bid.is_null() || min_ask.is_null() {
+        return GEMMY_ERROR_NULL_POINTER;
+    }
+    *max_bid = (*book).book.get_max_bid().unwrap_or(u64::MIN);
+    *min_ask = (*book).book.get_min_ask().unwrap_or(u64::MAX);
+    GEMMY_OK
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        gemmy_orderbook_best_prices, gemmy_orderbook_cancel, gemmy_orderbook_depth,
+        gemmy_orderbook_execute, gemmy_orderbook_free, gemmy_orderbook_new, gemmy_orderbook_quote,
+        GemmyExecution, GemmyFill, GemmyLevel, GemmyOperation, GemmyOrder, GemmyOrderBook,
+        GemmyOrderId, GEMMY_ERROR_INVALID_ARGUMENT, GEMMY_ERROR_NULL_POINTER,
+        GEMMY_ERROR_ORDER_NOT_FOUND, GEMMY_OK, GEMMY_OPERATION_LIMIT, GEMMY_OPERATION_MARKET,
+        GEMMY_OPERATION_REDUCE, GEMMY_RESULT_CREATED, GEMMY_RESULT_FAILED,
+        GEMMY_RESULT_PARTIALLY_FILLED, GEMMY_RESULT_QUOTED, GEMMY_RESULT_REDUCED, GEMMY_SIDE_ASK,
+        GEMMY_SIDE_BID,
+    };
+    use std::ptr;
+
+    fn limit(id: u128, price: u64, quantity: u64, side: u8) -> GemmyOperation {
+        GemmyOperation {
+            kind: GEMMY_OPERATION_LIMIT,
+            side,
+            id: id.into(),
+            price,
+            quantity,
+        }
+    }
+
+    unsafe fn execute(book: *mut GemmyOrderBook, operation: GemmyOperation) -> GemmyExecution {
+        let mut execution = GemmyExecution::default();
+        let code = gemmy_orderbook_execute(book, &operation, ptr::null_mut(), 0, &mut execution);
+        assert_eq!(code, GEMMY_OK);
+        execution
+    }
+
+    #[test]
+    fn it_splits_order_ids_into_halves() {
+        let id = (7u128 << 64) | 9;
+        let halves = GemmyOrderId::from(id);
+        assert_eq!(halves, GemmyOrderId { high: 7, low: 9 });
+        assert_eq!(u128::from(halves), id);
+    }
+
+    #[test]
+    fn it_executes_operations_through_the_c_abi() {
+        unsafe {
+            let book = gemmy_orderbook_new(4, 16);
+            let created = execute(book, limit(1, 100, 50, GEMMY_SIDE_ASK));
+            assert_eq!(created.result, GEMMY_RESULT_CREATED);
+            assert!(created.has_order && created.order.quantity == 50);
+            execute(book, limit(2, 101, 50, GEMMY_SIDE_ASK));
+
+            let market = GemmyOperation {
+                kind: GEMMY_OPERATION_MARKET,
+                side: GEMMY_SIDE_BID,
+                id: 3u128.into(),
+                price: 0,
+                quantity: 120,
+            };
+            let mut fills = [GemmyFill::default(); 1];
+            let mut execution = GemmyExecution::default();
+            let code =
+                gemmy_orderbook_execute(book, &market, fills.as_mut_ptr(), 1, &mut execution);
+            assert_eq!(code, GEMMY_OK);
+            assert_eq!(execution.result, GEMMY_RESULT_PARTIALLY_FILLED);
+            assert_eq!(execution.fill_count, 2);
+            assert_eq!(u128::from(fills[0].matched_order_id), 1);
+            assert_eq!(execution.order.price, 101);
+            assert_eq!(execution.order.quantity, 20);
+
+            let (mut max_bid, mut min_ask) = (0, 0);
+            gemmy_orderbook_best_prices(book, &mut max_bid, &mut min_ask);
+            assert_eq!((max_bid, min_ask), (101, u64::MAX));
+
+            let mut bids = [GemmyLevel::default(); 2];
+            let mut asks = [GemmyLevel::default(); 2];
+            let (mut bid_count, mut ask_count) = (0, 0);
+            let code = gemmy_orderbook_depth(
+                book,
+                2,
+                bids.as_mut_ptr(),
+                asks.as_mut_ptr(),
+                &mut bid_count,
+                &mut ask_count,
+            );
+            assert_eq!(code, GEMMY_OK);
+            assert_eq!(
+                bids[..bid_count],
+                [GemmyLevel {
+                    price: 101,
+                    quantity: 20
+                }]
+            );
+
+            let reduce = GemmyOperation {
+                kind: GEMMY_OPERATION_REDUCE,
+                id: 3u128.into(),
+                quantity: 5,
+                ..GemmyOperation::default()
+            };
+            let reduced = execute(book, reduce);
+            assert_eq!(reduced.result, GEMMY_RESULT_REDUCED);
+            assert_eq!(reduced.order.quantity, 15);
+
+            assert_eq!(gemmy_orderbook_cancel(book, 3u128.into()), GEMMY_OK);
+            assert_eq!(
+                gemmy_orderbook_cancel(book, 3u128.into()),
+                GEMMY_ERROR_ORDER_NOT_FOUND
+            );
+
+            let side = |id: u128, side: u8, price: u64, quantity: u64| GemmyOrder {
+                id: id.into(),
+                side,
+                price,
+                quantity,
+            };
+            let bid = side(4, GEMMY_SIDE_BID, 99, 10);
+            let ask = side(5, GEMMY_SIDE_ASK, 100, 10);
+            let mut execution = GemmyExecution::default();
+            let code = gemmy_orderbook_quote(book, &bid, &ask, ptr::null_mut(), 0, &mut execution);
+            assert_eq!(code, GEMMY_OK);
+            assert_eq!(execution.result, GEMMY_RESULT_QUOTED);
+            let crossed = side(5, GEMMY_SIDE_ASK, 99, 10);
+            gemmy_orderbook_quote(book, &bid, &crossed, ptr::null_mut(), 0, &mut execution);
+            assert_eq!(execution.result, GEMMY_RESULT_FAILED);
+            gemmy_orderbook_free(book);
+        }
+    }
+
+    #[test]
+    fn it_rejects_invalid_arguments() {
+        unsafe {
+            let book = gemmy_orderbook_new(4, 16);
+            let mut execution = GemmyExecution::default();
+            let invalid_side = limit(1, 100, 10, 7);
+            let code =
+                gemmy_orderbook_execute(book, &invalid_side, ptr::null_mut(), 0, &mut execution);
+            assert_eq!(code, GEMMY_ERROR_INVALID_ARGUMENT);
+            let code = gemmy_orderbook_execute(
+                book,
+                &limit(1, 100, 10, GEMMY_SIDE_BID),
+                ptr::null_mut(),
+                1,
+                &mut execution,
+            );
+            assert_eq!(code, GEMMY_ERROR_NULL_POINTER);
+            gemmy_orderbook_free(book);
+        }
+    }
+}
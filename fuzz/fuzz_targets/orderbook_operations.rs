@@ -0,0 +1,172 @@
+//! Feeds arbitrary sequences of operations into an orderbook and checks that the book stays
+//! consistent after every one of them. Run with `cargo +nightly fuzz run orderbook_operations`.
+//!
+//! Prices and ids are drawn from small ranges, so that orders meet at the same levels, cross the
+//! book and refer to orders that were placed before. New orders always get fresh ids, as the book
+//! leaves the uniqueness of ids to its callers.
+#![no_main]
+
+use arbitrary::Arbitrary;
+use gemmy::core::models::{LimitOrder, MarketOrder, Operation, Side};
+use gemmy::core::orderbook::OrderBook;
+use gemmy::core::types::{Price, Quantity};
+use libfuzzer_sys::fuzz_target;
+
+const MAX_PRICE: u64 = 64;
+const MAX_QUANTITY: u64 = 1_000;
+
+#[derive(Debug, Arbitrary)]
+enum FuzzOperation {
+    Limit {
+        price: u8,
+        quantity: u16,
+        bid: bool,
+    },
+    Market {
+        quantity: u16,
+        bid: bool,
+    },
+    /// Modifies one of the orders placed so far, picked by `order`.
+    Modify {
+        order: u16,
+        price: u8,
+        quantity: u16,
+        bid: bool,
+    },
+    /// Cancels one of the orders placed so far, picked by `order`.
+    Cancel {
+        order: u16,
+    },
+    /// Reduces one of the orders placed so far, picked by `order`.
+    Reduce {
+        order: u16,
+        quantity: u16,
+    },
+    /// Replaces the quote resting under the two quote ids, withdrawing a side without quantity.
+    Quote {
+        bid_price: u8,
+        bid_quantity: u16,
+        ask_price: u8,
+        ask_quantity: u16,
+    },
+}
+
+/// The ids the bid and the ask of the quote rest under, out of reach of the placed orders.
+const QUOTE_IDS: (u128, u128) = (u128::MAX - 2, u128::MAX - 1);
+
+fn side(bid: bool) -> Side {
+    match bid {
+        true => Side::Bid,
+        false => Side::Ask,
+    }
+}
+
+fn price(price: u8) -> u64 {
+    1 + price as u64 % MAX_PRICE
+}
+
+fn quantity(quantity: u16) -> u64 {
+    1 + quantity as u64 % MAX_QUANTITY
+}
+
+/// Picks one of the ids placed so far, or an id the book has never seen if there are none.
+fn placed_id(order: u16, next_id: u128) -> u128 {
+    match next_id {
+        0 => u128::MAX,
+        next_id => order as u128 % next_id,
+    }
+}
+
+fn check_invariants(book: &OrderBook) {
+    if let Err(inconsistencies) = book.audit() {
+        panic!("book is inconsistent: {:?}", inconsistencies);
+    }
+
+    let resting = book.resting_orders();
+    let resting_quantity = |side: Side| -> u64 {
+        resting
+            .iter()
+            .filter(|order| order.side == side)
+            .map(|order| order.quantity)
+            .sum()
+    };
+    // depth allocates the levels it is asked for, and prices never exceed the maximum price
+    let depth = book.depth(MAX_PRICE as usize);
+    let depth_quantity = |levels: &[gemmy::core::models::Level]| -> u64 {
+        levels.iter().map(|level| level.quantity).sum()
+    };
+    assert_eq!(depth_quantity(&depth.bids), resting_quantity(Side::Bid));
+    assert_eq!(depth_quantity(&depth.asks), resting_quantity(Side::Ask));
+    assert_eq!(
+        book.resting_totals().0,
+        resting_quantity(Side::Bid) + resting_quantity(Side::Ask)
+    );
+}
+
+fuzz_target!(|operations: Vec<FuzzOperation>| {
+    // a small store keeps the audit cheap and has the store grow past its capacity
+    let mut book = OrderBook::new("fuzz".to_string(), 4, 64);
+    let mut next_id = 0u128;
+    for operation in operations {
+        let operation = match operation {
+            FuzzOperation::Limit {
+                price: p,
+                quantity: q,
+                bid,
+            } => {
+                next_id += 1;
+                Operation::Limit(LimitOrder::new(
+                    next_id - 1,
+                    Price(price(p)),
+                    Quantity(quantity(q)),
+                    side(bid),
+                ))
+            }
+            FuzzOperation::Market { quantity: q, bid } => {
+                next_id += 1;
+                Operation::Market(MarketOrder::new(
+                    next_id - 1,
+                    Quantity(quantity(q)),
+                    side(bid),
+                ))
+            }
+            FuzzOperation::Modify {
+                order,
+                price: p,
+                quantity: q,
+                bid,
+            } => Operation::Modify(LimitOrder::new(
+                placed_id(order, next_id),
+                Price(price(p)),
+                Quantity(quantity(q)),
+                side(bid),
+            )),
+            FuzzOperation::Cancel { order } => Operation::Cancel(placed_id(order, next_id)),
+            FuzzOperation::Reduce { order, quantity: q } => Operation::Reduce {
+                id: placed_id(order, next_id),
+                quantity_delta: q as u64 % MAX_QUANTITY,
+            },
+            FuzzOperation::Quote {
+                bid_price,
+                bid_quantity,
+                ask_price,
+                ask_quantity,
+            } => Operation::Quote {
+                bid: LimitOrder::new(
+                    QUOTE_IDS.0,
+                    Price(price(bid_price)),
+                    Quantity(bid_quantity as u64 % (MAX_QUANTITY + 1)),
+                    Side::Bid,
+                ),
+                ask: LimitOrder::new(
+                    QUOTE_IDS.1,
+                    Price(price(ask_price)),
+                    Quantity(ask_quantity as u64 % (MAX_QUANTITY + 1)),
+                    Side::Ask,
+                ),
+            },
+        };
+        book.execute(operation);
+        check_invariants(&book);
+    }
+});
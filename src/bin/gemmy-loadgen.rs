@@ -0,0 +1,305 @@
+//! Sends synthetic order flow to a running server over the gRPC API, for capacity testing and
+//! soak runs. Messages arrive as a poisson process at the configured rate without waiting for
+//! earlier ones to be acknowledged, and the outcome of every interval is printed as it ends.
+//!
+//! Cancels target orders placed earlier in the run, using the ids the dispatcher acknowledges
+//! them with, and are sent for the account and symbol the order was placed with. Orders may have
+//! been filled in the meantime, in which case the engine fails the cancel after dispatching it.
+use clap::Parser;
+use gemmy::core::models::Side;
+use gemmy::engine::utils::order_flow::{
+    FlowEvent, OrderFlow, OrderFlowProperties, PriceDistribution,
+};
+use gemmy::protobuf::models::{
+    AckStatus, CancelLimitOrderRequest, CreateLimitOrderRequest, OrderAck, OrderSide,
+};
+use gemmy::protobuf::services::order_dispatcher_client::OrderDispatcherClient;
+use hdrhistogram::Histogram;
+use std::collections::{BTreeMap, VecDeque};
+use std::error::Error;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::Semaphore;
+use tokio::time::{sleep_until, Instant};
+use tonic::transport::Channel;
+use tonic::{Response, Status};
+
+/// Orders kept as candidates for cancels, the oldest are forgotten beyond it.
+const MAX_TRACKED_ORDERS: usize = 100_000;
+
+#[derive(Debug, Parser)]
+#[command(
+    name = "gemmy-loadgen",
+    version,
+    about = "Generates synthetic order flow against a gemmy server"
+)]
+struct LoadArgs {
+    /// Address of the gRPC server.
+    #[arg(long, default_value = "http://[::1]:50051")]
+    address: String,
+    /// Symbol to send orders for, can be repeated.
+    #[arg(long = "symbol", value_name = "SYMBOL", required = true)]
+    symbols: Vec<String>,
+    /// Average messages per second.
+    #[arg(long, default_value_t = 1_000.0)]
+    rate: f64,
+    /// Seconds to run for, zero runs until interrupted.
+    #[arg(long, value_name = "SECONDS", default_value_t = 60)]
+    duration: u64,
+    /// How limit prices are spread around the mid price, `uniform` or `normal`.
+    #[arg(long, default_value = "normal")]
+    price_distribution: PriceDistribution,
+    #[arg(long, default_value_t = 10_000)]
+    mid_price: u64,
+    /// Largest distance of a limit price from the mid price.
+    #[arg(long, default_value_t = 100)]
+    price_range: u64,
+    #[arg(long, default_value_t = 100)]
+    max_quantity: u64,
+    /// Share of the messages that are cancels, between 0 and 1.
+    #[arg(long, default_value_t = 0.3)]
+    cancel_ratio: f64,
+    /// Number of accounts the flow is spread over.
+    #[arg(long, default_value_t = 10)]
+    accounts: u32,
+    /// Seed of the flow, the same seed sends the same messages.
+    #[arg(long, default_value_t = 42)]
+    seed: u64,
+    /// Connections the messages are spread over.
+    #[arg(long, default_value_t = 8)]
+    connections: usize,
+    /// Messages awaiting acknowledgement before sending is held back.
+    #[arg(long, default_value_t = 10_000)]
+    max_in_flight: usize,
+    /// Seconds between reports.
+    #[arg(long, value_name = "SECONDS", default_value_t = 5)]
+    report_interval: u64,
+}
+
+/// An order acknowledged by the dispatcher, which later cancels may target.
+struct PlacedOrder {
+    symbol: String,
+    account: String,
+    order_id: u128,
+}
+
+/// Outcomes of the messages acknowledged within a reporting interval, where rejections are keyed
+/// by their reason, or by the status code of requests that failed outright.
+struct Outcomes {
+    accepted: u64,
+    rejected: BTreeMap<String, u64>,
+    latency: Histogram<u64>,
+}
+
+impl Outcomes {
+    fn new() -> Self {
+        Self {
+            accepted: 0,
+            rejected: BTreeMap::new(),
+            latency: Histogram::new_with_bounds(1, 60_000_000_000, 3).unwrap(),
+        }
+    }
+
+    fn record(&mut self, outcome: Result<(), String>, latency: Duration) {
+        match outcome {
+            Ok(()) => self.accepted += 1,
+            Err(reason) => *self.rejected.entry(reason).or_default() += 1,
+        }
+        self.latency.saturating_record(latency.as_nanos() as u64);
+    }
+
+    fn merge(&mut self, other: &Outcomes) {
+        self.accepted += other.accepted;
+        for (code, count) in &other.rejected {
+            *self.rejected.entry(code.clone()).or_default() += count;
+        }
+        self.latency.add(&other.latency).unwrap();
+    }
+
+    fn report(&self, label: &str, sent: u64, seconds: f64) {
+        let rejected: u64 = self.rejected.values().sum();
+        let [p50, p99, max] = [0.5, 0.99, 1.0]
+            .map(|quantile| Duration::from_nanos(self.latency.value_at_quantile(quantile)));
+        println!(
+            "{:>8} | sent {:>9} ({:>9.0}/s) | accepted {:>9} | rejected {:>7} | \
+             ack p50 {:>10.2?} p99 {:>10.2?} max {:>10.2?}",
+            label,
+            sent,
+            sent as f64 / seconds,
+            self.accepted,
+            rejected,
+            p50,
+            p99,
+            max
+        );
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn Error>> {
+    let args = LoadArgs::parse();
+    if !(0.0..=1.0).contains(&args.cancel_ratio) {
+        return Err("cancel ratio must be between 0 and 1".into());
+    }
+    if args.rate <= 0.0 {
+        return Err("rate must be positive".into());
+    }
+    let mut clients = Vec::with_capacity(args.connections.max(1));
+    for _ in 0..args.connections.max(1) {
+        clients.push(OrderDispatcherClient::connect(args.address.clone()).await?);
+    }
+    let mut flow = OrderFlow::new(
+        OrderFlowProperties {
+            mid_price: args.mid_price,
+            price_range: args.price_range,
+            price_distribution: args.price_distribution,
+            max_quantity: args.max_quantity,
+            cancel_ratio: args.cancel_ratio,
+            accounts: args.accounts,
+        },
+        args.seed,
+    );
+    println!(
+        "sending {:.0} messages per second to {} for {:?}",
+        args.rate, args.address, args.symbols
+    );
+
+    let outcomes = Arc::new(Mutex::new(Outcomes::new()));
+    let placed = Arc::new(Mutex::new(VecDeque::new()));
+    let in_flight = Arc::new(Semaphore::new(args.max_in_flight.max(1)));
+    let report_interval = Duration::from_secs(args.report_interval.max(1));
+    let start = Instant::now();
+    let end = (args.duration > 0).then(|| start + Duration::from_secs(args.duration));
+    let mut totals = Outcomes::new();
+    let mut next_report = start + report_interval;
+    let mut next_arrival = start;
+    let (mut sent, mut sent_in_interval) = (0u64, 0u64);
+    let interrupted = tokio::signal::ctrl_c();
+    tokio::pin!(interrupted);
+    loop {
+        if end.is_some_and(|end| next_arrival >= end) {
+            break;
+        }
+        if next_arrival >= next_report {
+            let interval = std::mem::replace(&mut *outcomes.lock().unwrap(), Outcomes::new());
+            let elapsed = format!("{}s", (next_report - start).as_secs());
+            interval.report(&elapsed, sent_in_interval, report_interval.as_secs_f64());
+            totals.merge(&interval);
+            sent_in_interval = 0;
+            next_report += report_interval;
+        }
+        tokio::select! {
+            _ = &mut interrupted => break,
+            _ = sleep_until(next_arrival) => (),
+        }
+        let permit = Arc::clone(&in_flight).acquire_owned().await?;
+        let mut client = clients[sent as usize % clients.len()].clone();
+        let mut target: Option<PlacedOrder> = None;
+        let event = flow.next_event(|rng| {
+            let mut placed = placed.lock().unwrap();
+            if placed.is_empty() {
+                return None;
+            }
+            let index = rng.below(placed.len() as u64) as usize;
+            target = placed.swap_remove_back(index);
+            target.as_ref().map(|order| order.order_id)
+        });
+        let (symbol, event) = match (target, event) {
+            (Some(order), FlowEvent::Cancel { order_id, .. }) => (
+                order.symbol,
+                FlowEvent::Cancel {
+                    account: order.account,
+                    order_id,
+                },
+            ),
+            (_, event) => (
+                args.symbols[sent as usize % args.symbols.len()].clone(),
+                event,
+            ),
+        };
+        let outcomes = Arc::clone(&outcomes);
+        let placed = Arc::clone(&placed);
+        tokio::spawn(async move {
+            let sent_at = Instant::now();
+            let outcome = send(&mut client, symbol, event).await.map(|order| {
+                if let Some(order) = order {
+                    let mut placed = placed.lock().unwrap();
+                    if placed.len() == MAX_TRACKED_ORDERS {
+                        placed.pop_front();
+                    }
+                    placed.push_back(order);
+                }
+            });
+            outcomes.lock().unwrap().record(outcome, sent_at.elapsed());
+            drop(permit);
+        });
+        sent += 1;
+        sent_in_interval += 1;
+        next_arrival += flow.next_arrival(args.rate);
+    }
+
+    // waits for the messages still in flight before the final report
+    let _ = in_flight
+        .acquire_many(args.max_in_flight.max(1) as u32)
+        .await?;
+    totals.merge(&outcomes.lock().unwrap());
+    totals.report("total", sent, start.elapsed().as_secs_f64());
+    for (code, count) in &totals.rejected {
+        println!("rejected with {}: {}", code, count);
+    }
+    Ok(())
+}
+
+/// Sends the event, returning the order it placed if it was a limit order, or the reason it was
+/// rejected.
+async fn send(
+    client: &mut OrderDispatcherClient<Channel>,
+    symbol: String,
+    event: FlowEvent,
+) -> Result<Option<PlacedOrder>, String> {
+    match event {
+        FlowEvent::Limit {
+            account,
+            side,
+            price,
+            quantity,
+        } => {
+            let side = match side {
+                Side::Bid => OrderSide::Bid,
+                Side::Ask => OrderSide::Ask,
+            };
+            let request = CreateLimitOrderRequest {
+                price,
+                quantity,
+                side: side as i32,
+                symbol: symbol.clone(),
+                account: account.clone(),
+                ..Default::default()
+            };
+            let ack = acknowledged(client.limit(request).await)?;
+            Ok(ack.order_id.try_into().ok().map(|order_id| PlacedOrder {
+                symbol,
+                account,
+                order_id: u128::from_be_bytes(order_id),
+            }))
+        }
+        FlowEvent::Cancel { account, order_id } => {
+            let request = CancelLimitOrderRequest {
+                order_id: order_id.to_be_bytes().to_vec(),
+                symbol,
+                account,
+            };
+            acknowledged(client.cancel(request).await).map(|_| None)
+        }
+    }
+}
+
+fn acknowledged(response: Result<Response<OrderAck>, Status>) -> Result<OrderAck, String> {
+    let ack = response
+        .map_err(|status| format!("{:?}", status.code()))?
+        .into_inner();
+    match ack.status() {
+        AckStatus::Accepted => Ok(ack),
+        AckStatus::Rejected => Err(ack.rejection_reason),
+    }
+}
@@ -0,0 +1,142 @@
+//! Replays the requests a server recorded with `TRAFFIC_RECORDING_ENABLED` against a running
+//! server, so that an incident can be reproduced locally against a new build. Requests are sent
+//! one after the other in the order they arrived, keeping their recorded spacing, and every
+//! request answered differently than it was when recorded is printed.
+//!
+//! Orders are assigned new ids by the server they are replayed against, so modifies, cancels and
+//! reductions are sent for the id the order they refer to was assigned during the replay. Quotes
+//! rest under ids derived from their symbol, account and level, which the replay leaves as they
+//! are.
+use clap::Parser;
+use gemmy::engine::persistence::traffic::{read_traffic, RecordedOutcome, TrafficRecord};
+use gemmy::protobuf::models::{
+    CancelLimitOrderRequest, CreateLimitOrderRequest, CreateMarketOrderRequest, MassQuoteRequest,
+    ModifyLimitOrderRequest, QuoteRequest, ReduceLimitOrderRequest,
+};
+use gemmy::protobuf::services::order_dispatcher_client::OrderDispatcherClient;
+use prost::Message;
+use std::collections::HashMap;
+use std::error::Error;
+use std::path::PathBuf;
+use std::time::Duration;
+use tokio::time::{sleep_until, Instant};
+use tonic::transport::Channel;
+use tonic::Status;
+
+#[derive(Debug, Parser)]
+#[command(
+    name = "gemmy-replay",
+    version,
+    about = "Replays recorded requests against a gemmy server"
+)]
+struct ReplayArgs {
+    /// Address of the gRPC server.
+    #[arg(long, default_value = "http://[::1]:50051")]
+    address: String,
+    /// Recording to replay.
+    #[arg(long, value_name = "PATH")]
+    path: PathBuf,
+    /// How many times faster than recorded the requests are sent, zero sends them as fast as
+    /// possible.
+    #[arg(long, default_value_t = 1.0)]
+    speed: f64,
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn Error>> {
+    let args = ReplayArgs::parse();
+    if args.speed < 0.0 {
+        return Err("speed must not be negative".into());
+    }
+    // requests are recorded once answered, which concurrent requests may not be in order
+    let mut records = read_traffic(&args.path)?;
+    records.sort_by_key(|record| record.timestamp);
+    let mut client = OrderDispatcherClient::connect(args.address.clone()).await?;
+
+    let mut order_ids = HashMap::new();
+    let mut diverged = 0;
+    let first_timestamp = records.first().map_or(0, |record| record.timestamp);
+    let start = Instant::now();
+    for (index, record) in records.iter().enumerate() {
+        if args.speed > 0.0 {
+            let offset = (record.timestamp - first_timestamp) as f64 / args.speed;
+            sleep_until(start + Duration::from_nanos(offset as u64)).await;
+        }
+        let (outcome, order_id) = match send(&mut client, record, &order_ids).await? {
+            Ok(outcome) => outcome,
+            Err(status) => (format!("{:?}", status.code()), Vec::new()),
+        };
+        if matches!(record.method.as_str(), "limit" | "market")
+            && !record.order_id.is_empty()
+            && !order_id.is_empty()
+        {
+            order_ids.insert(record.order_id.clone(), order_id);
+        }
+        if outcome != record.outcome {
+            diverged += 1;
+            println!(
+                "request {} ({}) was answered {} instead of {}",
+                index + 1,
+                record.method,
+                outcome,
+                record.outcome
+            );
+        }
+    }
+    println!(
+        "replayed {} requests in {:.3}s, {} answered differently",
+        records.len(),
+        start.elapsed().as_secs_f64(),
+        diverged
+    );
+    Ok(())
+}
+
+/// Sends the recorded request, referring to the orders by the ids they were assigned during the
+/// replay.
+///
+/// # Returns
+///
+/// * The outcome the request was answered with, along with the order id it was acknowledged for.
+async fn send(
+    client: &mut OrderDispatcherClient<Channel>,
+    record: &TrafficRecord,
+    order_ids: &HashMap<Vec<u8>, Vec<u8>>,
+) -> Result<Result<(String, Vec<u8>), Status>, Box<dyn Error>> {
+    let payload = record.payload.as_slice();
+    let replayed_id = |order_id: Vec<u8>| order_ids.get(&order_id).cloned().unwrap_or(order_id);
+    let response = match record.method.as_str() {
+        "limit" => {
+            client
+                .limit(CreateLimitOrderRequest::decode(payload)?)
+                .await
+        }
+        "market" => {
+            client
+                .market(CreateMarketOrderRequest::decode(payload)?)
+                .await
+        }
+        "modify" => {
+            let mut request = ModifyLimitOrderRequest::decode(payload)?;
+            request.order_id = replayed_id(request.order_id);
+            client.modify(request).await
+        }
+        "cancel" => {
+            let mut request = CancelLimitOrderRequest::decode(payload)?;
+            request.order_id = replayed_id(request.order_id);
+            client.cancel(request).await
+        }
+        "reduce" => {
+            let mut request = ReduceLimitOrderRequest::decode(payload)?;
+            request.order_id = replayed_id(request.order_id);
+            client.reduce(request).await
+        }
+        "quote" => client.quote(QuoteRequest::decode(payload)?).await,
+        "mass_quote" => {
+            let response = client.mass_quote(MassQuoteRequest::decode(payload)?).await;
+            return Ok(response.map(|response| response.get_ref().recorded_outcome()));
+        }
+        method => return Err(format!("cannot replay {} requests", method).into()),
+    };
+    Ok(response.map(|response| response.get_ref().recorded_outcome()))
+}
@@ -0,0 +1,39 @@
+//! Routes the requests of clients to the gemmy process that owns their symbol, so that the
+//! symbols can be sharded across processes behind a single endpoint. The shards and the symbols
+//! they own are configured through the `ROUTER_*` variables.
+use gemmy::engine::constants::property_loader::RouterProperties;
+use gemmy::engine::services::router_service::{ShardMap, ShardRouter};
+use std::error::Error;
+use tracing::info;
+use tracing_subscriber::EnvFilter;
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn Error>> {
+    let RouterProperties {
+        socket_address,
+        shards,
+    } = RouterProperties::load()?;
+    tracing_subscriber::fmt()
+        .with_env_filter(EnvFilter::from_default_env())
+        .init();
+
+    let shard_map = ShardMap::new(shards)?;
+    for shard in shard_map.shards() {
+        info!(
+            "routing {:?} to shard {} at {}",
+            shard.symbols, shard.name, shard.address
+        );
+    }
+    let (order_dispatcher_service, stat_streamer_service) = ShardRouter::create(shard_map)?;
+
+    info!("successfully started router at: {}", socket_address);
+    tonic::transport::Server::builder()
+        .add_service(order_dispatcher_service)
+        .add_service(stat_streamer_service)
+        .serve_with_shutdown(socket_address, async {
+            let _ = tokio::signal::ctrl_c().await;
+            info!("shutting down router");
+        })
+        .await?;
+    Ok(())
+}
@@ -0,0 +1,80 @@
+use crate::client::errors::ClientError;
+use crate::protobuf::models::{OrderbookData, OrderbookDataRequest};
+use crate::protobuf::services::stat_stream_client::StatStreamClient;
+use std::time::Duration;
+use tokio::sync::mpsc::{self, Receiver, Sender};
+use tokio::task::JoinHandle;
+use tokio::time::sleep;
+use tonic::transport::Channel;
+
+/// Snapshots buffered for a subscriber that has not taken them yet.
+const BUFFER_SIZE: usize = 64;
+const INITIAL_BACKOFF: Duration = Duration::from_millis(100);
+const MAX_BACKOFF: Duration = Duration::from_secs(5);
+
+/// A stream of orderbook snapshots of a symbol. Every snapshot is a full view of the book, so a
+/// reconnect only skips the snapshots the server sent while the connection was down.
+///
+/// The stream is reopened with an exponential backoff when the server cannot be reached, and
+/// ends when the server closes it or refuses the subscription, for example because the symbol
+/// is unknown, in which case the error is returned before the end.
+pub struct DepthSubscription {
+    snapshots: Receiver<Result<OrderbookData, ClientError>>,
+    task: JoinHandle<()>,
+}
+
+impl DepthSubscription {
+    pub(crate) fn spawn(client: StatStreamClient<Channel>, request: OrderbookDataRequest) -> Self {
+        let (sender, snapshots) = mpsc::channel(BUFFER_SIZE);
+        let task = tokio::spawn(Self::run(client, request, sender));
+        DepthSubscription { snapshots, task }
+    }
+
+    /// Waits for the next snapshot, or returns `None` once the subscription ended.
+    pub async fn next(&mut self) -> Option<Result<OrderbookData, ClientError>> {
+        self.snapshots.recv().await
+    }
+
+    async fn run(
+        mut client: StatStreamClient<Channel>,
+        request: OrderbookDataRequest,
+        sender: Sender<Result<OrderbookData, ClientError>>,
+    ) {
+        let mut backoff = INITIAL_BACKOFF;
+        loop {
+            let error = match client.orderbook(request.clone()).await {
+                Ok(response) => {
+                    let mut stream = response.into_inner();
+                    loop {
+                        match stream.message().await {
+                            Ok(Some(snapshot)) => {
+                                backoff = INITIAL_BACKOFF;
+                                if sender.send(Ok(snapshot)).await.is_err() {
+                                    return;
+                                }
+                            }
+                            Ok(None) => return,
+                            Err(status) => break ClientError::from(status),
+                        }
+                    }
+                }
+                Err(status) => ClientError::from(status),
+            };
+            if !error.is_retryable() {
+                let _ = sender.send(Err(error)).await;
+                return;
+            }
+            tokio::select! {
+                _ = sleep(backoff) => (),
+                _ = sender.closed() => return,
+            }
+            backoff = (backoff * 2).min(MAX_BACKOFF);
+        }
+    }
+}
+
+impl Drop for DepthSubscription {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
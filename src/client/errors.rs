@@ -0,0 +1,34 @@
+use thiserror::Error;
+use tonic::Code;
+
+/// The errors of the client, separating orders the server rejected from requests that failed.
+#[derive(Debug, Error)]
+pub enum ClientError {
+    /// The server could not be reached.
+    #[error("failed to connect: {0}")]
+    Transport(#[from] tonic::transport::Error),
+    /// The request failed with a gRPC status.
+    #[error("request failed: {0}")]
+    Status(#[from] tonic::Status),
+    /// The server acknowledged the order as rejected, with the reason it gave.
+    #[error("order rejected: {0}")]
+    Rejected(String),
+    /// The server answered with a message the client cannot decode.
+    #[error("invalid response: {0}")]
+    InvalidResponse(&'static str),
+}
+
+impl ClientError {
+    /// Whether the request may succeed once the server is reachable again, as opposed to having
+    /// been refused by it.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            ClientError::Transport(_) => true,
+            ClientError::Status(status) => matches!(
+                status.code(),
+                Code::Unavailable | Code::Unknown | Code::Cancelled | Code::Aborted
+            ),
+            ClientError::Rejected(_) | ClientError::InvalidResponse(_) => false,
+        }
+    }
+}
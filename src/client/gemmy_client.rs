@@ -0,0 +1,264 @@
+use crate::client::depth_subscription::DepthSubscription;
+use crate::client::errors::ClientError;
+use crate::core::models::Side;
+use crate::protobuf::models::{
+    AckStatus, CancelLimitOrderRequest, CreateLimitOrderRequest, CreateMarketOrderRequest,
+    ExecuteQuoteRequest, Granularity, ModifyLimitOrderRequest, OrderAck, OrderSide,
+    OrderStatusRequest, OrderStatusResponse, OrderbookDataRequest, StreamParameters,
+};
+use crate::protobuf::services::order_dispatcher_client::OrderDispatcherClient;
+use crate::protobuf::services::stat_stream_client::StatStreamClient;
+use tonic::transport::{Channel, Endpoint};
+use tonic::{Response, Status};
+
+/// An operation the dispatcher accepted and handed to the engine. The engine executes it
+/// asynchronously, its outcome is published along with the other execution events.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Acknowledgement {
+    /// The id the order was created with, or the id of the order modified or cancelled.
+    pub order_id: u128,
+    /// Nanoseconds since the unix epoch at which the dispatcher accepted the operation.
+    pub accepted_at: u128,
+    /// Operations the engine had executed for the symbol when the operation was accepted.
+    pub executed_operations: u64,
+    /// Operations queued for the engine of the symbol when the operation was accepted.
+    pub queue_depth: u64,
+}
+
+impl TryFrom<OrderAck> for Acknowledgement {
+    type Error = ClientError;
+
+    fn try_from(ack: OrderAck) -> Result<Self, Self::Error> {
+        if ack.status() == AckStatus::Rejected {
+            return Err(ClientError::Rejected(ack.rejection_reason));
+        }
+        let order_id = ack
+            .order_id
+            .try_into()
+            .map_err(|_| ClientError::InvalidResponse("order id must be 16 bytes"))?;
+        let accepted_at = ack
+            .accepted_at
+            .try_into()
+            .map_err(|_| ClientError::InvalidResponse("accept time must be 16 bytes"))?;
+        Ok(Acknowledgement {
+            order_id: u128::from_be_bytes(order_id),
+            accepted_at: u128::from_be_bytes(accepted_at),
+            executed_operations: ack.executed_operations,
+            queue_depth: ack.queue_depth,
+        })
+    }
+}
+
+/// A client of a gemmy server trading on behalf of a single account. Cloning it is cheap, clones
+/// share the connection.
+#[derive(Debug, Clone)]
+pub struct GemmyClient {
+    orders: OrderDispatcherClient<Channel>,
+    streams: StatStreamClient<Channel>,
+    account: String,
+}
+
+impl GemmyClient {
+    /// Connects to the server.
+    ///
+    /// # Arguments
+    ///
+    /// * `address` - The address of the gRPC server, such as `http://[::1]:50051`.
+    /// * `account` - The account orders are placed for, which may be empty unless the server
+    ///   keeps a ledger.
+    pub async fn connect(
+        address: impl Into<String>,
+        account: impl Into<String>,
+    ) -> Result<Self, ClientError> {
+        let channel = Endpoint::from_shared(address.into())?.connect().await?;
+        Ok(Self::with_channel(channel, account))
+    }
+
+    /// Creates a client over an existing channel.
+    pub fn with_channel(channel: Channel, account: impl Into<String>) -> Self {
+        GemmyClient {
+            orders: OrderDispatcherClient::new(channel.clone()),
+            streams: StatStreamClient::new(channel),
+            account: account.into(),
+        }
+    }
+
+    pub fn account(&self) -> &str {
+        &self.account
+    }
+
+    /// Places a good till cancelled limit order.
+    pub async fn place_limit(
+        &mut self,
+        symbol: &str,
+        side: Side,
+        price: u64,
+        quantity: u64,
+    ) -> Result<Acknowledgement, ClientError> {
+        let request = CreateLimitOrderRequest {
+            price,
+            quantity,
+            side: side_to_proto(side) as i32,
+            symbol: symbol.to_string(),
+            account: self.account.clone(),
+            ..Default::default()
+        };
+        acknowledge(self.orders.limit(request).await)
+    }
+
+    pub async fn place_market(
+        &mut self,
+        symbol: &str,
+        side: Side,
+        quantity: u64,
+    ) -> Result<Acknowledgement, ClientError> {
+        let request = CreateMarketOrderRequest {
+            quantity,
+            side: side_to_proto(side) as i32,
+            symbol: symbol.to_string(),
+            stream: None,
+            account: self.account.clone(),
+            ladder: Vec::new(),
+        };
+        acknowledge(self.orders.market(request).await)
+    }
+
+    /// Changes the price or the quantity of a resting order, the side has to match the order.
+    pub async fn modify(
+        &mut self,
+        symbol: &str,
+        order_id: u128,
+        side: Side,
+        price: u64,
+        quantity: u64,
+    ) -> Result<Acknowledgement, ClientError> {
+        let request = ModifyLimitOrderRequest {
+            order_id: order_id.to_be_bytes().to_vec(),
+            price,
+            quantity,
+            side: side_to_proto(side) as i32,
+            symbol: symbol.to_string(),
+            account: self.account.clone(),
+        };
+        acknowledge(self.orders.modify(request).await)
+    }
+
+    pub async fn cancel(
+        &mut self,
+        symbol: &str,
+        order_id: u128,
+    ) -> Result<Acknowledgement, ClientError> {
+        let request = CancelLimitOrderRequest {
+            order_id: order_id.to_be_bytes().to_vec(),
+            symbol: symbol.to_string(),
+            account: self.account.clone(),
+        };
+        acknowledge(self.orders.cancel(request).await)
+    }
+
+    /// Executes a firm quote of the rfq stream as a market order of the account. The order is
+    /// rejected once the quote expired or the book no longer fills it at the quoted price.
+    pub async fn execute_quote(
+        &mut self,
+        symbol: &str,
+        quote_id: u128,
+    ) -> Result<Acknowledgement, ClientError> {
+        let request = ExecuteQuoteRequest {
+            symbol: symbol.to_string(),
+            quote_id: quote_id.to_be_bytes().to_vec(),
+            account: self.account.clone(),
+        };
+        acknowledge(self.orders.execute_quote(request).await)
+    }
+
+    /// Returns a resting order of the account along with its revisions.
+    pub async fn order_status(
+        &mut self,
+        symbol: &str,
+        order_id: u128,
+    ) -> Result<OrderStatusResponse, ClientError> {
+        let request = OrderStatusRequest {
+            symbol: symbol.to_string(),
+            order_id: order_id.to_be_bytes().to_vec(),
+            account: self.account.clone(),
+        };
+        Ok(self.orders.status(request).await?.into_inner())
+    }
+
+    /// Subscribes to snapshots of the best price levels of a symbol at the interval of the
+    /// server. The stream is reopened whenever the connection to the server is lost.
+    pub fn subscribe_depth(&self, symbol: &str, levels: u32) -> DepthSubscription {
+        let request = OrderbookDataRequest {
+            granularity: Granularity::P00 as i32,
+            symbol: symbol.to_string(),
+            stream: Some(StreamParameters {
+                levels,
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        DepthSubscription::spawn(self.streams.clone(), request)
+    }
+}
+
+fn side_to_proto(side: Side) -> OrderSide {
+    match side {
+        Side::Bid => OrderSide::Bid,
+        Side::Ask => OrderSide::Ask,
+    }
+}
+
+fn acknowledge(
+    response: Result<Response<OrderAck>, Status>,
+) -> Result<Acknowledgement, ClientError> {
+    Acknowledgement::try_from(response?.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::client::errors::ClientError;
+    use crate::client::gemmy_client::Acknowledgement;
+    use crate::protobuf::models::{AckStatus, OrderAck};
+
+    #[test]
+    fn it_decodes_accepted_acknowledgements() {
+        let ack = OrderAck {
+            status: AckStatus::Accepted as i32,
+            order_id: 7u128.to_be_bytes().to_vec(),
+            rejection_reason: String::new(),
+            accepted_at: 1_000u128.to_be_bytes().to_vec(),
+            executed_operations: 12,
+            queue_depth: 3,
+        };
+        assert_eq!(
+            Acknowledgement::try_from(ack).unwrap(),
+            Acknowledgement {
+                order_id: 7,
+                accepted_at: 1_000,
+                executed_operations: 12,
+                queue_depth: 3,
+            }
+        );
+    }
+
+    #[test]
+    fn it_returns_rejections_as_errors() {
+        let ack = OrderAck {
+            status: AckStatus::Rejected as i32,
+            rejection_reason: "trading is halted".to_string(),
+            ..Default::default()
+        };
+        match Acknowledgement::try_from(ack) {
+            Err(ClientError::Rejected(reason)) => assert_eq!(reason, "trading is halted"),
+            result => panic!("expected a rejection, got {:?}", result),
+        }
+        let ack = OrderAck {
+            order_id: vec![0; 15],
+            ..Default::default()
+        };
+        assert!(matches!(
+            Acknowledgement::try_from(ack),
+            Err(ClientError::InvalidResponse(_))
+        ));
+    }
+}
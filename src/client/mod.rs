@@ -0,0 +1,6 @@
+/// Contains the depth subscription, which reconnects its stream when the server goes away.
+pub mod depth_subscription;
+/// Contains the errors returned by the client.
+pub mod errors;
+/// Contains the client of the order dispatcher and stat stream services.
+pub mod gemmy_client;
@@ -0,0 +1,123 @@
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+use thiserror::Error;
+
+/// The reason an operation failed to execute, either in the orderbook itself or because it was
+/// rejected before reaching it.
+#[derive(Debug, Clone, PartialEq, Error, Serialize, Deserialize)]
+pub enum OrderBookError {
+    /// A market order was placed while the opposite side of the book was empty.
+    #[error("placed market order on empty book")]
+    EmptyBook,
+    /// A modification matched the resting order exactly, or the order was not found.
+    #[error("no modification occurred")]
+    NoModification,
+    /// The order to cancel is not resting in the book.
+    #[error("order not found")]
+    OrderNotFound,
+    /// A reduction was zero or would not leave any quantity resting.
+    #[error("reduction must be positive and below the remaining quantity {quantity}")]
+    InvalidReduction { quantity: u64 },
+    /// A quote did not consist of a bid and an ask under distinct ids.
+    #[error("quote must be a bid and an ask with distinct ids")]
+    InvalidQuote,
+    /// The bid of a quote was not below its ask.
+    #[error("quote bid {bid} must be below its ask {ask}")]
+    CrossedQuote { bid: u64, ask: u64 },
+    /// The price of the order is not a multiple of the tick size of the book.
+    #[error("price must be a multiple of the tick size {tick_size}")]
+    OffTick { tick_size: u64 },
+    /// The quantity of the order is not a multiple of the lot size of the book.
+    #[error("quantity must be a multiple of the lot size {lot_size}")]
+    OffLot { lot_size: u64 },
+    /// A call was started while the book was already in one, or a market order was placed during a call.
+    #[error("book is in a call")]
+    InCall,
+    /// An uncross was attempted while the book was not in a call.
+    #[error("book is not in a call")]
+    NotInCall,
+    /// The operation was rejected by a pre-trade check and never reached the book.
+    #[error(transparent)]
+    Rejected(#[from] Rejection),
+}
+
+/// The reason an [`OrderBookBuilder`](crate::core::orderbook::OrderBookBuilder) cannot build a
+/// book.
+#[derive(Debug, Copy, Clone, PartialEq, Error)]
+pub enum ConfigurationError {
+    #[error("tick size must be positive")]
+    ZeroTickSize,
+    #[error("lot size must be positive")]
+    ZeroLotSize,
+}
+
+/// The reason a pre-trade check rejected an operation.
+#[derive(Debug, Clone, PartialEq, Error, Serialize, Deserialize)]
+pub enum Rejection {
+    #[error("resting quantity limit of the symbol reached")]
+    RestingQuantityLimit,
+    #[error("resting notional limit of the symbol reached")]
+    RestingNotionalLimit,
+    #[error("order is not assigned to an account")]
+    UnassignedOrder,
+    /// The market maker protection of the account tripped and was not reset since.
+    #[error("market maker protection of the account tripped")]
+    ProtectionTripped,
+    #[error("insufficient {asset} balance")]
+    InsufficientBalance { asset: String },
+    #[error(transparent)]
+    AccountLimit(#[from] RiskViolation),
+    /// A reason given by a custom risk check.
+    #[error("{0}")]
+    Custom(String),
+}
+
+/// The reason an order was rejected by the risk limits of its account.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum RiskViolation {
+    OrderQuantity { quantity: u64, limit: u64 },
+    OpenOrders { limit: usize },
+    Notional { notional: u64, limit: u64 },
+}
+
+impl RiskViolation {
+    /// A stable identifier of the violated limit, for clients to match on.
+    pub fn code(&self) -> &'static str {
+        match self {
+            RiskViolation::OrderQuantity { .. } => "max_order_quantity",
+            RiskViolation::OpenOrders { .. } => "max_open_orders",
+            RiskViolation::Notional { .. } => "max_notional",
+        }
+    }
+}
+
+impl Display for RiskViolation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RiskViolation::OrderQuantity { quantity, limit } => write!(
+                f,
+                "{}: order quantity {} exceeds limit of {}",
+                self.code(),
+                quantity,
+                limit
+            ),
+            RiskViolation::OpenOrders { limit } => {
+                write!(f, "{}: limit of {} open orders reached", self.code(), limit)
+            }
+            RiskViolation::Notional { notional, limit } => write!(
+                f,
+                "{}: order notional {} exceeds limit of {}",
+                self.code(),
+                notional,
+                limit
+            ),
+        }
+    }
+}
+
+impl std::error::Error for RiskViolation {}
+
+/// The error returned when an integer does not represent a [`Side`](crate::core::models::Side).
+#[derive(Debug, Copy, Clone, PartialEq, Error)]
+#[error("invalid side: {0}")]
+pub struct InvalidSide(pub i32);
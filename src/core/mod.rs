@@ -1,6 +1,10 @@
+/// Contains the errors returned by the orderbook and the checks in front of it.
+pub mod errors;
 /// Contains all the necessary enums and structs to interface with the orderbook.
 pub mod models;
 /// Contains the orderbook and store structs.
 pub mod orderbook;
 /// Store is a private module that contains the structure used to represent the order store.
 mod store;
+/// Contains the price, quantity and order id newtypes.
+pub mod types;
@@ -1,6 +1,13 @@
+use crate::core::errors::{InvalidSide, OrderBookError};
+use crate::core::types::{OrderId, Price, Quantity};
 use serde::{Deserialize, Serialize};
+use std::fmt::Write;
+use std::ops::RangeInclusive;
 use uuid::Uuid;
 
+/// Price levels on either side of the orderbook the depth checksum is computed over.
+pub const CHECKSUM_LEVELS: usize = 25;
+
 /// Side, as the name indicates is used to represent a side of the orderbook.
 /// The traits Serialize, Deserialize are implemented to broaden its utility.
 #[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
@@ -11,12 +18,14 @@ pub enum Side {
     Ask = 1,
 }
 
-impl From<i32> for Side {
-    fn from(value: i32) -> Self {
+impl TryFrom<i32> for Side {
+    type Error = InvalidSide;
+
+    fn try_from(value: i32) -> Result<Self, Self::Error> {
         match value {
-            0 => Side::Bid,
-            1 => Side::Ask,
-            _ => panic!("invalid side"),
+            0 => Ok(Side::Bid),
+            1 => Ok(Side::Ask),
+            _ => Err(InvalidSide(value)),
         }
     }
 }
@@ -34,12 +43,41 @@ pub enum Operation {
     Modify(LimitOrder),
     /// Cancel allows the user to cancel an existing limit order.
     /// This only takes the existing order id.
-    Cancel(u128),
+    Cancel(OrderId),
+    /// Reduce allows the user to trim the quantity of an existing limit order in place, keeping its priority.
+    /// The reduction must leave some quantity resting, removing the order is up to a cancel.
+    Reduce { id: OrderId, quantity_delta: u64 },
+    /// Quote allows a market maker to replace both sides of its quote in one operation.
+    /// The orders resting under the ids of the bid and the ask are removed before the bid and then the ask are placed,
+    /// so the book never shows one side replaced without the other. A side without quantity is withdrawn.
+    Quote { bid: LimitOrder, ask: LimitOrder },
+    /// Call starts a call phase, in which limit orders rest without matching so that the book may cross.
+    /// Market orders are not accepted until the call ends.
+    Call,
+    /// Uncross ends the call phase by matching the crossed part of the book at a single price, the one [`OrderBook::indicative_uncross`](crate::core::orderbook::OrderBook::indicative_uncross) reports.
+    /// Orders match continuously again afterwards.
+    Uncross,
+}
+
+impl Operation {
+    /// Returns the id of the order the operation refers to, the bid for quotes and zero for a call or an uncross.
+    pub fn order_id(&self) -> u128 {
+        match self {
+            Operation::Limit(order) | Operation::Modify(order) => order.id,
+            Operation::Quote { bid, .. } => bid.id,
+            Operation::Market(order) => order.id,
+            Operation::Cancel(OrderId(id))
+            | Operation::Reduce {
+                id: OrderId(id), ..
+            } => *id,
+            Operation::Call | Operation::Uncross => 0,
+        }
+    }
 }
 
 /// This represents the result when an order is placed in the orderbook.
 /// The successful cases contain metadata about which makers got matched and the order that gets created.
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub enum FillResult {
     /// This means that the limit order was fully filled and contains a vector of [`FillMetaData`] struct.
     /// This metadata describes the matched orders.
@@ -56,7 +94,7 @@ pub enum FillResult {
 
 /// This represents the result of an operation execution.
 /// Depending on the flow of the operation, it can amount to one of four possible values.
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub enum ExecutionResult {
     /// This is returned every time an order is matched within the execution flow that generates a [`FillResult`].
     Executed(FillResult),
@@ -64,11 +102,63 @@ pub enum ExecutionResult {
     Modified(ModifyResult),
     /// This is returned when the execution cancels an existing order with the passed id.
     Cancelled(u128),
+    /// This is returned when the execution reduces an existing order, with the [`LimitOrder`] left resting.
+    Reduced(LimitOrder),
+    /// This is returned when the execution replaces a quote and generates a [`QuoteResult`].
+    Quoted(QuoteResult),
+    /// This is returned when the execution starts a call phase.
+    Called,
+    /// This is returned when the execution ends a call phase, with the matches of the uncross.
+    /// Every match names the bid as the order and the ask as the matched order, as neither side takes liquidity from the other.
+    Uncrossed(Vec<FillMetaData>),
     /// This is used to represent any failure scenario in operation execution.
-    Failed(String),
+    Failed(OrderBookError),
+}
+
+impl ExecutionResult {
+    /// Returns the matches the execution produced, which is empty unless an order was filled.
+    pub fn fills(&self) -> &[FillMetaData] {
+        let fill_result = match self {
+            ExecutionResult::Executed(fill_result) => fill_result,
+            ExecutionResult::Modified(ModifyResult::Created(fill_result)) => fill_result,
+            ExecutionResult::Quoted(quote) => return &quote.fills,
+            ExecutionResult::Uncrossed(fills) => return fills,
+            _ => return &[],
+        };
+        match fill_result {
+            FillResult::Filled(fills) | FillResult::PartiallyFilled(_, fills) => fills,
+            _ => &[],
+        }
+    }
+}
+
+/// The price a call would uncross at if it ended now, along with the quantity that would match and what would be left over.
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IndicativeUncross {
+    /// The price that matches the most quantity, leaving the smallest imbalance among those that do.
+    pub price: u64,
+    /// The quantity that would match at the price.
+    pub quantity: u64,
+    /// The quantity that would be left unmatched at the price on the side wanting more.
+    pub imbalance: u64,
+    /// The side wanting more at the price, `None` if both sides match in full.
+    pub imbalance_side: Option<Side>,
+}
+
+/// This represents the result of a quote replacing the orders resting under its ids.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct QuoteResult {
+    /// The ids of the orders that rested under the ids of the quote and were removed.
+    pub replaced: Vec<u128>,
+    /// The bid of the quote left resting, `None` if it was withdrawn or filled in full.
+    pub bid: Option<LimitOrder>,
+    /// The ask of the quote left resting, `None` if it was withdrawn or filled in full.
+    pub ask: Option<LimitOrder>,
+    /// The matches of the bid followed by those of the ask.
+    pub fills: Vec<FillMetaData>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub enum RfqStatus {
     CompleteFill(u64),
     PartialFillAndLimitPlaced(u64, u64),
@@ -77,7 +167,7 @@ pub enum RfqStatus {
 }
 
 /// This represents the result of a modify operation for an existing limit order.
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub enum ModifyResult {
     /// This means that post order modification, a new limit order was created.
     /// [`FillResult`] will contain any matched orders or the created limit order.
@@ -89,7 +179,7 @@ pub enum ModifyResult {
 }
 
 /// This structure represents a limit order.
-#[derive(Debug, Copy, Clone, PartialEq)]
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
 pub struct LimitOrder {
     /// This represents unique 128-bit id can is capable of storing uuid v4.
     /// The uniqueness of this id is not enforced within the book as of now.
@@ -100,6 +190,9 @@ pub struct LimitOrder {
     pub quantity: u64,
     /// This is the side of the orderbook in which the order will get placed.
     pub side: Side,
+    /// This is the time in nanoseconds since the unix epoch after which a resting order expires.
+    /// Orders without an expiry rest in the book until they are filled or cancelled.
+    pub expires_at: Option<u128>,
 }
 
 impl LimitOrder {
@@ -115,12 +208,13 @@ impl LimitOrder {
     /// # Returns
     ///
     /// * A [`LimitOrder`] with the specified arguments.
-    pub fn new(id: u128, price: u64, quantity: u64, side: Side) -> Self {
+    pub fn new(id: OrderId, price: Price, quantity: Quantity, side: Side) -> Self {
         Self {
-            id,
-            price,
-            quantity,
+            id: id.0,
+            price: price.0,
+            quantity: quantity.0,
             side,
+            expires_at: None,
         }
     }
 
@@ -135,15 +229,44 @@ impl LimitOrder {
     /// # Returns
     ///
     /// * A [`LimitOrder`] with the specified arguments and an auto generated 128-bit id.
-    pub fn new_uuid_v4(price: u64, quantity: u64, side: Side) -> Self {
+    pub fn new_uuid_v4(price: Price, quantity: Quantity, side: Side) -> Self {
         Self {
             id: Uuid::new_v4().as_u128(),
-            price,
-            quantity,
+            price: price.0,
+            quantity: quantity.0,
             side,
+            expires_at: None,
         }
     }
 
+    /// This is a builder like method that sets the expiry of the limit order.
+    ///
+    /// # Arguments
+    ///
+    /// * `expires_at` - The time in nanoseconds since the unix epoch after which the order expires.
+    ///
+    /// # Returns
+    ///
+    /// * The same [`LimitOrder`] expiring at the specified time.
+    pub fn with_expiry(mut self, expires_at: u128) -> Self {
+        self.expires_at = Some(expires_at);
+        self
+    }
+
+    /// This is a helper method to check whether the limit order has expired.
+    ///
+    /// # Arguments
+    ///
+    /// * `now` - The current time in nanoseconds since the unix epoch.
+    ///
+    /// # Returns
+    ///
+    /// * `true` if the order carries an expiry that is not later than `now`.
+    #[inline(always)]
+    pub fn is_expired(&self, now: u128) -> bool {
+        self.expires_at.is_some_and(|expires_at| expires_at <= now)
+    }
+
     /// This is a helper method to change the quantity of the limit order in place.
     ///
     /// # Arguments
@@ -184,8 +307,12 @@ impl MarketOrder {
     /// # Returns
     ///
     /// * A [`MarketOrder`] with the specified arguments.
-    pub fn new(id: u128, quantity: u64, side: Side) -> Self {
-        Self { id, quantity, side }
+    pub fn new(id: OrderId, quantity: Quantity, side: Side) -> Self {
+        Self {
+            id: id.0,
+            quantity: quantity.0,
+            side,
+        }
     }
 
     /// This is the same as new, except it auto generates id. (uuid v4)
@@ -198,10 +325,10 @@ impl MarketOrder {
     /// # Returns
     ///
     /// * A [`MarketOrder`] with the specified arguments and an auto generated 128-bit id.
-    pub fn new_uuid_v4(quantity: u64, side: Side) -> Self {
+    pub fn new_uuid_v4(quantity: Quantity, side: Side) -> Self {
         Self {
             id: Uuid::new_v4().as_u128(),
-            quantity,
+            quantity: quantity.0,
             side,
         }
     }
@@ -221,12 +348,13 @@ impl MarketOrder {
             price,
             quantity: self.quantity,
             side: self.side,
+            expires_at: None,
         }
     }
 }
 
 /// This struct represents the data generated whenever an order is matched against one on the opposite side.
-#[derive(Debug, Copy, Clone, PartialEq)]
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
 pub struct FillMetaData {
     /// This is the id of the taker's order.
     pub order_id: u128,
@@ -242,7 +370,7 @@ pub struct FillMetaData {
 
 /// This represents a struct used to return bids and asks in the orderbook at a specific depth.
 /// For example, a level 2 depth will give us top two bids and bottom two asks with aggregated quantities.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Depth {
     /// The number of price levels to be returned on either side from center of the orderbook.
     pub levels: usize,
@@ -253,7 +381,7 @@ pub struct Depth {
 }
 
 /// This is a helper struct used in construction of depth.
-#[derive(Debug, Copy, Clone, PartialEq)]
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Level {
     /// A price point in the orderbook.
     pub price: u64,
@@ -261,6 +389,172 @@ pub struct Level {
     pub quantity: u64,
 }
 
+/// This represents a point of a slippage curve, the expected execution of a market order that
+/// spends a notional on the bid side or raises it on the ask side.
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SlippagePoint {
+    /// The notional the point was estimated for.
+    pub notional: u64,
+    /// The quantity the notional fills, in whole units.
+    pub quantity: u64,
+    /// The average price of the fill, `None` when not even a single unit fills.
+    pub average_price: Option<u64>,
+    /// The last price level the fill reaches.
+    pub worst_price: Option<u64>,
+    /// The distance of the average price from the best price in hundredths of a percent.
+    pub impact_bps: u64,
+    /// Whether the book holds enough liquidity for the whole notional.
+    pub complete: bool,
+}
+
+/// This is a summary of the orderbook returned when all of its consistency checks pass.
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+pub struct AuditReport {
+    /// The number of bid prices with orders resting at them.
+    pub bid_levels: usize,
+    /// The number of ask prices with orders resting at them.
+    pub ask_levels: usize,
+    /// The number of price levels without orders, which matching leaves in the book.
+    pub empty_levels: usize,
+    /// The number of resting orders on both sides.
+    pub resting_orders: usize,
+    /// The number of store slots, used and free.
+    pub store_slots: usize,
+    /// The number of store slots free to hold new orders.
+    pub free_slots: usize,
+}
+
+/// This is a summary of the memory an orderbook holds and how much of it is in use, which tells
+/// whether its store and queue capacities fit the orders it sees.
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+pub struct MemoryStats {
+    /// The number of store slots pre-allocated on creation.
+    pub store_capacity: usize,
+    /// The number of store slots, which grows past the capacity once every slot is in use.
+    pub store_slots: usize,
+    /// The number of store slots holding resting orders.
+    pub store_occupancy: usize,
+    /// The most store slots held by resting orders at once.
+    pub peak_store_occupancy: usize,
+    /// The number of bid prices with orders resting at them.
+    pub bid_levels: usize,
+    /// The number of ask prices with orders resting at them.
+    pub ask_levels: usize,
+    /// The number of price levels without orders, which matching leaves in the book.
+    pub empty_levels: usize,
+    /// The number of order slots the queue of a new price level is allocated with.
+    pub queue_capacity: usize,
+    /// The number of order slots allocated by the queues of every price level.
+    pub queue_allocations: usize,
+    /// An estimate of the heap memory held by the store and the price levels in bytes.
+    pub allocated_bytes: usize,
+}
+
+/// This is a summary of the memory a compaction of the orderbook gave back.
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+pub struct CompactionReport {
+    /// The number of price levels without orders that were removed.
+    pub pruned_levels: usize,
+    /// The number of queues shrunk back to the queue capacity.
+    pub shrunk_queues: usize,
+    /// The number of free store slots beyond the store capacity that were released.
+    pub released_slots: usize,
+    /// An estimate of the heap memory given back in bytes.
+    pub released_bytes: usize,
+}
+
+/// This represents a way in which the internal state of an orderbook contradicts itself.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Inconsistency {
+    /// A price level or the free list refers to a slot beyond the end of the store.
+    IndexOutOfBounds { index: usize },
+    /// A price level holds a slot that no resting order is mapped to.
+    DanglingIndex {
+        side: Side,
+        price: u64,
+        index: usize,
+    },
+    /// A slot is held by more than one price level or more than once by the same level.
+    DuplicateIndex { index: usize },
+    /// An order is held by a price level of another side or price than its own.
+    MisplacedOrder { id: u128, side: Side, price: u64 },
+    /// A resting order has no quantity left.
+    EmptyOrder { id: u128 },
+    /// An order mapped in the store is not held by any price level.
+    UnqueuedOrder { id: u128, index: usize },
+    /// The store maps an id to a slot holding an order with another id.
+    IdMismatch {
+        id: u128,
+        index: usize,
+        stored_id: u128,
+    },
+    /// A slot is on the free list while an order is mapped to it.
+    FreeSlotInUse { id: u128, index: usize },
+    /// A slot is on the free list more than once.
+    DuplicateFreeSlot { index: usize },
+    /// A slot is neither on the free list nor mapped to an order, so it is never used again.
+    LeakedSlot { index: usize },
+    /// The cached maximum bid differs from the highest bid resting in the book.
+    StaleMaxBid {
+        cached: Option<u64>,
+        actual: Option<u64>,
+    },
+    /// The cached minimum ask differs from the lowest ask resting in the book.
+    StaleMinAsk {
+        cached: Option<u64>,
+        actual: Option<u64>,
+    },
+    /// The highest bid is not below the lowest ask.
+    CrossedBook { bid: u64, ask: u64 },
+}
+
+/// The rule by which resting orders at the same price are matched against incoming orders.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub enum MatchingPolicy {
+    /// Orders at the same price are filled in the order they arrived.
+    #[default]
+    PriceTime,
+    /// The largest order at the same price is filled first, orders of the same size in the order
+    /// they arrived.
+    SizeTime,
+    /// Orders at the same price are filled in random order. The draws are derived from the seed
+    /// and the incoming order, so that replaying the same operations fills the same orders.
+    Random { seed: u64 },
+}
+
+impl MatchingPolicy {
+    /// This method picks the resting order of a price level that is filled next.
+    ///
+    /// # Arguments
+    ///
+    /// * `len` - The number of orders resting at the price, which must not be zero.
+    /// * `quantity` - The remaining quantity of the order at a position of the level, in arrival order.
+    /// * `draw` - A number derived from the incoming order and its fills so far, which the random policy picks by.
+    ///
+    /// # Returns
+    ///
+    /// * The position of the order to be filled next, in arrival order.
+    pub fn next_fill(&self, len: usize, quantity: impl Fn(usize) -> u64, draw: u64) -> usize {
+        match self {
+            MatchingPolicy::PriceTime => 0,
+            MatchingPolicy::SizeTime => (1..len).fold(0, |largest, position| {
+                match quantity(position) > quantity(largest) {
+                    true => position,
+                    false => largest,
+                }
+            }),
+            MatchingPolicy::Random { seed } => (mix(seed ^ draw) % len as u64) as usize,
+        }
+    }
+}
+
+/// The finalizer of splitmix64, which spreads similar inputs evenly over the output.
+fn mix(mut value: u64) -> u64 {
+    value = (value ^ (value >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    value = (value ^ (value >> 27)).wrapping_mul(0x94d049bb133111eb);
+    value ^ (value >> 31)
+}
+
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub enum Granularity {
     P00 = 1,
@@ -270,8 +564,127 @@ pub enum Granularity {
     P100 = 10000,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct OrderbookAggregated {
     pub bids: Vec<(u64, u64)>,
     pub asks: Vec<(u64, u64)>,
 }
+
+impl OrderbookAggregated {
+    /// Compares this snapshot with a `previous` one of the same granularity.
+    ///
+    /// # Returns
+    ///
+    /// * A [`DepthDiff`] holding only the price levels whose quantity changed.
+    pub fn diff(&self, previous: &OrderbookAggregated) -> DepthDiff {
+        DepthDiff {
+            bids: diff_levels(&previous.bids, &self.bids),
+            asks: diff_levels(&previous.asks, &self.asks),
+        }
+    }
+
+    /// Keeps only the best `levels` prices on either side.
+    pub fn top(mut self, levels: usize) -> OrderbookAggregated {
+        let bids = self.bids.len().saturating_sub(levels);
+        self.bids.drain(..bids);
+        self.asks.truncate(levels);
+        self
+    }
+
+    /// Keeps only the prices within `prices` on either side.
+    pub fn within(mut self, prices: &RangeInclusive<u64>) -> OrderbookAggregated {
+        self.bids.retain(|(price, _)| prices.contains(price));
+        self.asks.retain(|(price, _)| prices.contains(price));
+        self
+    }
+
+    /// Computes a CRC32 checksum over the best `levels` prices on either side, so that clients
+    /// maintaining a local copy of the orderbook can verify it has not diverged. Levels are
+    /// interleaved from the best price outwards as `bid_price:bid_quantity:ask_price:ask_quantity`,
+    /// joined by colons, where a side that runs out of levels is skipped.
+    pub fn checksum(&self, levels: usize) -> u32 {
+        let mut bids = self.bids.iter().rev().take(levels);
+        let mut asks = self.asks.iter().take(levels);
+        let mut input = String::new();
+        loop {
+            let (bid, ask) = (bids.next(), asks.next());
+            if bid.is_none() && ask.is_none() {
+                break;
+            }
+            for (price, quantity) in bid.into_iter().chain(ask) {
+                if !input.is_empty() {
+                    input.push(':');
+                }
+                let _ = write!(input, "{}:{}", price, quantity);
+            }
+        }
+        crc32fast::hash(input.as_bytes())
+    }
+
+    /// Brings this snapshot up to date with a diff computed against it.
+    pub fn apply(&mut self, diff: &DepthDiff) {
+        apply_levels(&mut self.bids, &diff.bids);
+        apply_levels(&mut self.asks, &diff.asks);
+    }
+}
+
+/// The changes between two aggregated snapshots of the orderbook, as `(price, quantity)` pairs
+/// sorted by price. A quantity of zero means the price level was removed.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DepthDiff {
+    pub bids: Vec<(u64, u64)>,
+    pub asks: Vec<(u64, u64)>,
+}
+
+impl DepthDiff {
+    pub fn is_empty(&self) -> bool {
+        self.bids.is_empty() && self.asks.is_empty()
+    }
+}
+
+/// Walks both sorted level lists at once, collecting every level that was added, changed or
+/// removed.
+fn diff_levels(previous: &[(u64, u64)], current: &[(u64, u64)]) -> Vec<(u64, u64)> {
+    let mut deltas = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < previous.len() || j < current.len() {
+        match (previous.get(i), current.get(j)) {
+            (Some(&(old_price, _)), Some(&(new_price, new_quantity))) if new_price < old_price => {
+                deltas.push((new_price, new_quantity));
+                j += 1;
+            }
+            (Some(&(old_price, old_quantity)), Some(&(new_price, new_quantity)))
+                if new_price == old_price =>
+            {
+                if new_quantity != old_quantity {
+                    deltas.push((new_price, new_quantity));
+                }
+                i += 1;
+                j += 1;
+            }
+            (Some(&(old_price, _)), _) => {
+                deltas.push((old_price, 0));
+                i += 1;
+            }
+            (None, Some(&level)) => {
+                deltas.push(level);
+                j += 1;
+            }
+            (None, None) => break,
+        }
+    }
+    deltas
+}
+
+fn apply_levels(levels: &mut Vec<(u64, u64)>, deltas: &[(u64, u64)]) {
+    for &(price, quantity) in deltas {
+        match levels.binary_search_by_key(&price, |&(price, _)| price) {
+            Ok(index) if quantity == 0 => {
+                levels.remove(index);
+            }
+            Ok(index) => levels[index].1 = quantity,
+            Err(index) if quantity > 0 => levels.insert(index, (price, quantity)),
+            Err(_) => {}
+        }
+    }
+}
@@ -1,12 +1,17 @@
 use super::{
+    errors::{ConfigurationError, OrderBookError},
     models::{
-        Depth, ExecutionResult, FillMetaData, FillResult, Level, LimitOrder, MarketOrder,
-        ModifyResult, Operation, Side,
+        AuditReport, CompactionReport, Depth, ExecutionResult, FillMetaData, FillResult,
+        Inconsistency, IndicativeUncross, Level, LimitOrder, MarketOrder, MatchingPolicy,
+        MemoryStats, ModifyResult, Operation, QuoteResult, Side, SlippagePoint,
     },
     store::Store,
 };
 use crate::core::models::{Granularity, OrderbookAggregated, RfqStatus};
-use std::collections::{BTreeMap, VecDeque};
+use crate::core::types::OrderId;
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, HashSet, VecDeque};
+use std::mem::size_of;
 use std::ops::{Index, IndexMut};
 use uuid::Uuid;
 
@@ -36,6 +41,22 @@ pub struct OrderBook {
     order_store: Store,
     /// Price of the last filled order.
     last_trade_price: u64,
+    /// Prices of orders must be a multiple of this.
+    tick_size: u64,
+    /// Quantities of orders must be a multiple of this.
+    lot_size: u64,
+    /// The rule resting orders at the same price are matched by.
+    matching_policy: MatchingPolicy,
+    /// The latest fills in the order they happened, `None` unless enabled on construction.
+    trade_tape: Option<VecDeque<FillMetaData>>,
+    /// The number of fills kept on the trade tape.
+    trade_tape_capacity: usize,
+    /// The time resting orders are expired against while matching, `None` until it is set.
+    clock: Option<u128>,
+    /// Ids of the expired orders purged while matching, until they are taken.
+    expired: Vec<u128>,
+    /// Whether the book is in a call, in which limit orders rest without matching.
+    call: bool,
 }
 
 /// This assigns the default values for vector dequeue capacity as well as the store capacity when constructing the orderbook.
@@ -78,9 +99,22 @@ impl OrderBook {
             order_store: Store::new(store_capacity),
             last_trade_price: u64::MIN,
             queue_capacity,
+            tick_size: 1,
+            lot_size: 1,
+            matching_policy: MatchingPolicy::default(),
+            trade_tape: None,
+            trade_tape_capacity: 0,
+            clock: None,
+            expired: Vec::new(),
+            call: false,
         }
     }
 
+    /// Creates a builder for an orderbook with more options than [`OrderBook::new`] takes.
+    pub fn builder() -> OrderBookBuilder {
+        OrderBookBuilder::default()
+    }
+
     /// This helps us get the orderbook id
     ///
     /// # Returns
@@ -112,6 +146,421 @@ impl OrderBook {
         self.last_trade_price
     }
 
+    pub fn get_tick_size(&self) -> u64 {
+        self.tick_size
+    }
+
+    pub fn get_lot_size(&self) -> u64 {
+        self.lot_size
+    }
+
+    pub fn get_matching_policy(&self) -> MatchingPolicy {
+        self.matching_policy
+    }
+
+    /// This method returns the latest fills of the book, oldest first.
+    ///
+    /// # Returns
+    ///
+    /// * An iterator over at most the configured number of fills, empty if the tape is disabled.
+    pub fn trade_tape(&self) -> impl Iterator<Item = &FillMetaData> {
+        self.trade_tape.iter().flatten()
+    }
+
+    /// This tells whether the book is in a call, which [`Operation::Call`] starts and [`Operation::Uncross`] ends.
+    ///
+    /// # Returns
+    ///
+    /// * `true` while limit orders rest without matching.
+    pub fn in_call(&self) -> bool {
+        self.call
+    }
+
+    /// This is an internal helper used while restoring a book from a checkpoint.
+    ///
+    /// # Arguments
+    ///
+    /// * `price` - The last traded price to be restored.
+    #[cfg(feature = "engine")]
+    pub(crate) fn set_last_trade_price(&mut self, price: u64) {
+        self.last_trade_price = price;
+    }
+
+    /// This method looks up a resting limit order by its id.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The id of the order to be looked up.
+    ///
+    /// # Returns
+    ///
+    /// * A copy of the resting [`LimitOrder`] with its remaining quantity, `None` if it is not in the book.
+    pub fn get_order(&self, id: u128) -> Option<LimitOrder> {
+        self.order_store.get(id).map(|(order, _)| *order)
+    }
+
+    /// This method collects every resting limit order in the orderbook.
+    /// Bids are returned before asks, each side is walked from the top of the book and orders within a price level keep their time priority.
+    /// Placing the returned orders in sequence on an empty orderbook reproduces the same resting state.
+    ///
+    /// # Returns
+    ///
+    /// * A vector containing copies of all resting [`LimitOrder`].
+    pub fn resting_orders(&self) -> Vec<LimitOrder> {
+        self.iter_orders(Side::Bid)
+            .chain(self.iter_orders(Side::Ask))
+            .copied()
+            .collect()
+    }
+
+    /// This method walks the bid side price levels from the highest price down.
+    ///
+    /// # Returns
+    ///
+    /// * An iterator of [`Level`] with the aggregated resting quantity at each bid price.
+    pub fn iter_bids(&self) -> impl Iterator<Item = Level> + '_ {
+        self.bid_side_book
+            .iter()
+            .rev()
+            .map(|(price, queue)| self.level(*price, queue))
+    }
+
+    /// This method walks the ask side price levels from the lowest price up.
+    ///
+    /// # Returns
+    ///
+    /// * An iterator of [`Level`] with the aggregated resting quantity at each ask price.
+    pub fn iter_asks(&self) -> impl Iterator<Item = Level> + '_ {
+        self.ask_side_book
+            .iter()
+            .map(|(price, queue)| self.level(*price, queue))
+    }
+
+    /// This method returns the top of the book, which is far cheaper than aggregating its depth.
+    ///
+    /// # Returns
+    ///
+    /// * The best bid and the best ask [`Level`], either of which is `None` when its side is
+    ///   empty.
+    pub fn best_bid_offer(&self) -> (Option<Level>, Option<Level>) {
+        (self.iter_bids().next(), self.iter_asks().next())
+    }
+
+    /// This method walks the resting orders of one side of the book from the top of the book and
+    /// by time priority within a price level, which is the order they match under
+    /// [`MatchingPolicy::PriceTime`].
+    ///
+    /// # Arguments
+    ///
+    /// * `side` - The side of the book to walk.
+    ///
+    /// # Returns
+    ///
+    /// * An iterator of references to the resting [`LimitOrder`] with their remaining quantity.
+    pub fn iter_orders(&self, side: Side) -> impl Iterator<Item = &LimitOrder> + '_ {
+        self.queues(side)
+            .flat_map(|queue| queue.iter())
+            .map(|index| &self.order_store[*index])
+    }
+
+    /// This method peeks at the resting orders of one side of the book that the next incoming orders would match first, so that a market maker can tell whether it is at the front of the queue.
+    /// Within a price level the orders are ranked by the matching policy, except under [`MatchingPolicy::Random`] whose draws depend on the incoming order, so they are listed by time priority.
+    /// Orders expired at the clock of the book are left out, as matching purges them before filling any order.
+    ///
+    /// # Arguments
+    ///
+    /// * `side` - The side of the book to peek at.
+    /// * `n` - The most orders to return.
+    ///
+    /// # Returns
+    ///
+    /// * A vector of copies of at most `n` resting [`LimitOrder`], first in priority first.
+    pub fn top_orders(&self, side: Side, n: usize) -> Vec<LimitOrder> {
+        let mut orders = Vec::new();
+        for queue in self.queues(side) {
+            if orders.len() == n {
+                break;
+            }
+            let mut level: Vec<LimitOrder> = queue
+                .iter()
+                .map(|index| self.order_store[*index])
+                .filter(|order| self.clock.is_none_or(|now| !order.is_expired(now)))
+                .collect();
+            if self.matching_policy == MatchingPolicy::SizeTime {
+                // the sort is stable, which keeps orders of the same size in time priority
+                level.sort_by_key(|order| Reverse(order.quantity));
+            }
+            let remaining = n - orders.len();
+            orders.extend(level.into_iter().take(remaining));
+        }
+        orders
+    }
+
+    /// The price levels of one side of the book, from the top of the book.
+    fn queues(&self, side: Side) -> Box<dyn Iterator<Item = &VecDeque<usize>> + '_> {
+        match side {
+            Side::Bid => Box::new(self.bid_side_book.values().rev()),
+            Side::Ask => Box::new(self.ask_side_book.values()),
+        }
+    }
+
+    fn level(&self, price: u64, queue: &VecDeque<usize>) -> Level {
+        Level {
+            price,
+            quantity: queue
+                .iter()
+                .map(|index| self.order_store[*index].quantity)
+                .sum(),
+        }
+    }
+
+    /// This method totals the quantity and notional value of every resting limit order on both sides of the book.
+    /// Every resting order is visited, so the cost grows with the size of the book.
+    ///
+    /// # Returns
+    ///
+    /// * A tuple of the resting quantity and the resting notional.
+    pub fn resting_totals(&self) -> (u64, u64) {
+        self.bid_side_book
+            .values()
+            .chain(self.ask_side_book.values())
+            .flat_map(|queue| queue.iter())
+            .map(|index| &self.order_store[*index])
+            .fold((0u64, 0u64), |(quantity, notional), order| {
+                (
+                    quantity.saturating_add(order.quantity),
+                    notional.saturating_add(order.price.saturating_mul(order.quantity)),
+                )
+            })
+    }
+
+    /// This method cross-checks the internal state of the orderbook: the price levels of both sides,
+    /// the id map and the free list of the store, and the cached best prices.
+    /// Every resting order and store slot is visited, so it is meant for tests and for integrity checks of snapshots rather than the hot path.
+    ///
+    /// # Returns
+    ///
+    /// * An [`AuditReport`] summarizing the orderbook if it is consistent, otherwise every [`Inconsistency`] found.
+    pub fn audit(&self) -> Result<AuditReport, Vec<Inconsistency>> {
+        let mut inconsistencies = self.order_store.audit();
+        let mut report = AuditReport {
+            store_slots: self.order_store.slots(),
+            free_slots: self.order_store.free_slots(),
+            ..AuditReport::default()
+        };
+        let mut queued = HashSet::new();
+        for (side, book) in [
+            (Side::Bid, &self.bid_side_book),
+            (Side::Ask, &self.ask_side_book),
+        ] {
+            for (price, queue) in book {
+                match (queue.is_empty(), side) {
+                    (true, _) => report.empty_levels += 1,
+                    (false, Side::Bid) => report.bid_levels += 1,
+                    (false, Side::Ask) => report.ask_levels += 1,
+                }
+                for index in queue.iter().copied() {
+                    if !queued.insert(index) {
+                        inconsistencies.push(Inconsistency::DuplicateIndex { index });
+                        continue;
+                    }
+                    if index >= self.order_store.slots() {
+                        inconsistencies.push(Inconsistency::IndexOutOfBounds { index });
+                        continue;
+                    }
+                    let order = &self.order_store[index];
+                    if self.order_store.get(order.id).map(|(_, i)| i) != Some(index) {
+                        inconsistencies.push(Inconsistency::DanglingIndex {
+                            side,
+                            price: *price,
+                            index,
+                        });
+                        continue;
+                    }
+                    report.resting_orders += 1;
+                    if order.side != side || order.price != *price {
+                        inconsistencies.push(Inconsistency::MisplacedOrder {
+                            id: order.id,
+                            side,
+                            price: *price,
+                        });
+                    }
+                    if order.quantity == 0 {
+                        inconsistencies.push(Inconsistency::EmptyOrder { id: order.id });
+                    }
+                }
+            }
+        }
+        for (id, index) in self.order_store.ids() {
+            if !queued.contains(&index) {
+                inconsistencies.push(Inconsistency::UnqueuedOrder { id, index });
+            }
+        }
+        let (max_bid, min_ask) = (self.top_bid_price(), self.top_ask_price());
+        if self.max_bid != max_bid {
+            inconsistencies.push(Inconsistency::StaleMaxBid {
+                cached: self.max_bid,
+                actual: max_bid,
+            });
+        }
+        if self.min_ask != min_ask {
+            inconsistencies.push(Inconsistency::StaleMinAsk {
+                cached: self.min_ask,
+                actual: min_ask,
+            });
+        }
+        if let (Some(bid), Some(ask)) = (max_bid, min_ask) {
+            if bid >= ask && !self.call {
+                inconsistencies.push(Inconsistency::CrossedBook { bid, ask });
+            }
+        }
+        match inconsistencies.is_empty() {
+            true => Ok(report),
+            false => Err(inconsistencies),
+        }
+    }
+
+    /// This method summarizes the memory held by the store and the price levels, so that the store and queue capacities can be sized from the orders the book actually sees.
+    /// Only the price levels are visited, which keeps it cheap enough to be called on a live book now and then.
+    ///
+    /// # Returns
+    ///
+    /// * The [`MemoryStats`] of the orderbook.
+    pub fn memory_stats(&self) -> MemoryStats {
+        let mut stats = MemoryStats {
+            store_capacity: self.order_store.capacity(),
+            store_slots: self.order_store.slots(),
+            store_occupancy: self.order_store.slots() - self.order_store.free_slots(),
+            peak_store_occupancy: self.order_store.peak_occupancy(),
+            queue_capacity: self.queue_capacity,
+            allocated_bytes: self.order_store.allocated_bytes(),
+            ..MemoryStats::default()
+        };
+        for (side, book) in [
+            (Side::Bid, &self.bid_side_book),
+            (Side::Ask, &self.ask_side_book),
+        ] {
+            for queue in book.values() {
+                match (queue.is_empty(), side) {
+                    (true, _) => stats.empty_levels += 1,
+                    (false, Side::Bid) => stats.bid_levels += 1,
+                    (false, Side::Ask) => stats.ask_levels += 1,
+                }
+                stats.queue_allocations += queue.capacity();
+                stats.allocated_bytes += size_of::<u64>()
+                    + size_of::<VecDeque<usize>>()
+                    + queue.capacity() * size_of::<usize>();
+            }
+        }
+        stats
+    }
+
+    /// This method gives back memory the book no longer needs: it removes the price levels matching left without orders,
+    /// shrinks queues that grew past the queue capacity, and releases the free store slots the store grew past its capacity by.
+    /// Resting orders keep their priority, so matching is not affected, but the book is mutated and it has to be called by whoever executes its operations.
+    ///
+    /// # Returns
+    ///
+    /// * A [`CompactionReport`] of what was given back.
+    pub fn compact(&mut self) -> CompactionReport {
+        let allocated_bytes = self.memory_stats().allocated_bytes;
+        let mut report = CompactionReport::default();
+        let queue_capacity = self.queue_capacity;
+        for book in [&mut self.bid_side_book, &mut self.ask_side_book] {
+            book.retain(|_, queue| {
+                if queue.is_empty() {
+                    report.pruned_levels += 1;
+                    return false;
+                }
+                let capacity = queue.len().max(queue_capacity);
+                if queue.capacity() > capacity {
+                    queue.shrink_to(capacity);
+                    report.shrunk_queues += 1;
+                }
+                true
+            });
+        }
+        report.released_slots = self.order_store.compact();
+        report.released_bytes = allocated_bytes.saturating_sub(self.memory_stats().allocated_bytes);
+        report
+    }
+
+    /// This method computes a deterministic hash over every resting order, covering its id, side,
+    /// price, quantity and its priority within the price level.
+    /// Two books hash equally exactly when they hold the same resting orders in the same sequence,
+    /// which makes the hash suited to compare a book with its replica or with the book rebuilt from a snapshot or a replay.
+    /// The hash is a 64-bit FNV-1a, which is stable across processes and platforms but not collision resistant against crafted input.
+    ///
+    /// # Returns
+    ///
+    /// * The hash of the resting orders, which is the FNV-1a offset basis for an empty book.
+    pub fn state_hash(&self) -> u64 {
+        const OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+        const PRIME: u64 = 0x100000001b3;
+        let bids = self.bid_side_book.iter().rev();
+        let asks = self.ask_side_book.iter();
+        bids.chain(asks)
+            .flat_map(|(_, queue)| queue.iter().enumerate())
+            .map(|(priority, index)| (priority, &self.order_store[*index]))
+            .fold(OFFSET_BASIS, |hash, (priority, order)| {
+                let side = match order.side {
+                    Side::Bid => [0u8],
+                    Side::Ask => [1u8],
+                };
+                [
+                    &order.id.to_be_bytes()[..],
+                    &side,
+                    &order.price.to_be_bytes(),
+                    &order.quantity.to_be_bytes(),
+                    &(priority as u64).to_be_bytes(),
+                ]
+                .concat()
+                .into_iter()
+                .fold(hash, |hash, byte| (hash ^ byte as u64).wrapping_mul(PRIME))
+            })
+    }
+
+    /// This method collects the ids of every resting limit order that has expired.
+    /// The orders are left in the book, cancelling them is up to the caller so that the cancellations go through [`OrderBook::execute`].
+    ///
+    /// # Arguments
+    ///
+    /// * `now` - The current time in nanoseconds since the unix epoch.
+    ///
+    /// # Returns
+    ///
+    /// * A vector containing the ids of the expired orders in the same sequence as [`OrderBook::resting_orders`].
+    pub fn expired_orders(&self, now: u128) -> Vec<u128> {
+        let bids = self.bid_side_book.iter().rev();
+        let asks = self.ask_side_book.iter();
+        bids.chain(asks)
+            .flat_map(|(_, queue)| queue.iter())
+            .map(|index| &self.order_store[*index])
+            .filter(|order| order.is_expired(now))
+            .map(|order| order.id)
+            .collect()
+    }
+
+    /// This method sets the time resting orders are expired against while matching.
+    /// Expired orders a taker reaches are purged from the book instead of being matched, so takers never trade against stale liquidity between sweeps of [`OrderBook::expired_orders`].
+    /// Until the clock is set, expired orders are matched like any other.
+    ///
+    /// # Arguments
+    ///
+    /// * `now` - The current time in nanoseconds since the unix epoch.
+    pub fn set_clock(&mut self, now: u128) {
+        self.clock = Some(now);
+    }
+
+    /// This method hands over the ids of the expired orders purged while matching since it was last called.
+    ///
+    /// # Returns
+    ///
+    /// * A vector containing the ids of the purged orders in the sequence they were purged.
+    pub fn take_expired(&mut self) -> Vec<u128> {
+        std::mem::take(&mut self.expired)
+    }
+
     /// This method is used to execute an [`Operation`] on the orderbook.
     /// The flow of this method is dictated by the operation provided, leading to an [`ExecutionResult`].
     ///
@@ -119,61 +568,249 @@ impl OrderBook {
     /// - A limit/market operation leads to `Executed(Filled/PartiallyFilled/Created)` states on success and to `Failed` otherwise.
     /// - A modification operation leads to `Executed(Modified/Created)` states on success and to `Failed` otherwise.
     /// - A cancel operation leads to `Cancelled(u128)` state on success and to `Failed` otherwise.
+    /// - A call operation leads to `Called` unless the book is in a call already, limit orders rest without matching and market orders fail until the uncross.
+    /// - An uncross operation leads to `Uncrossed` with the matches at the uncross price if the book is in a call and to `Failed` otherwise.
     ///
     /// Check out the individual enums [`FillResult`], [`FillMetaData`] and [`ModifyResult`] for more details.
     ///
     /// # Arguments
     ///
-    /// * `operation` - This can be one of four different types, [`Operation::Limit`], [`Operation::Market`], [`Operation::Modify`], [`Operation::Cancel`].
+    /// * `operation` - This can be one of eight different types, [`Operation::Limit`], [`Operation::Market`], [`Operation::Modify`], [`Operation::Cancel`], [`Operation::Reduce`], [`Operation::Quote`], [`Operation::Call`], [`Operation::Uncross`].
     ///
     /// # Returns
     ///
     /// * [`ExecutionResult`] that depicts the status of execution of the operation.
     pub fn execute(&mut self, operation: Operation) -> ExecutionResult {
+        if let Err(error) = self.check_increments(&operation) {
+            return ExecutionResult::Failed(error);
+        }
+        let result = self.dispatch(operation);
+        if let Some(tape) = self.trade_tape.as_mut() {
+            for fill in result.fills() {
+                if tape.len() == self.trade_tape_capacity {
+                    tape.pop_front();
+                }
+                tape.push_back(*fill);
+            }
+        }
+        result
+    }
+
+    /// Checks that the price and quantity of an operation are multiples of the tick and lot size.
+    fn check_increments(&self, operation: &Operation) -> Result<(), OrderBookError> {
+        let (price, quantity) = match operation {
+            Operation::Limit(order) | Operation::Modify(order) => (order.price, order.quantity),
+            Operation::Market(order) => (0, order.quantity),
+            Operation::Reduce { quantity_delta, .. } => (0, *quantity_delta),
+            Operation::Quote { bid, ask } => {
+                self.check_increments(&Operation::Limit(*bid))?;
+                return self.check_increments(&Operation::Limit(*ask));
+            }
+            Operation::Cancel(_) | Operation::Call | Operation::Uncross => return Ok(()),
+        };
+        if price % self.tick_size != 0 {
+            return Err(OrderBookError::OffTick {
+                tick_size: self.tick_size,
+            });
+        }
+        if quantity % self.lot_size != 0 {
+            return Err(OrderBookError::OffLot {
+                lot_size: self.lot_size,
+            });
+        }
+        Ok(())
+    }
+
+    fn dispatch(&mut self, operation: Operation) -> ExecutionResult {
         match operation {
             Operation::Limit(order) => match order.side {
                 Side::Bid => ExecutionResult::Executed(self.limit_bid_order(order)),
                 Side::Ask => ExecutionResult::Executed(self.limit_ask_order(order)),
             },
+            Operation::Market(_) if self.call => ExecutionResult::Failed(OrderBookError::InCall),
             Operation::Market(order) => match order.side {
                 Side::Bid => {
                     let result = self.market_bid_order(order);
                     match result {
-                        FillResult::Failed => {
-                            ExecutionResult::Failed("placed market order on empty book".to_string())
-                        }
+                        FillResult::Failed => ExecutionResult::Failed(OrderBookError::EmptyBook),
                         _ => ExecutionResult::Executed(result),
                     }
                 }
                 Side::Ask => {
                     let result = self.market_ask_order(order);
                     match result {
-                        FillResult::Failed => {
-                            ExecutionResult::Failed("placed market order on empty book".to_string())
-                        }
+                        FillResult::Failed => ExecutionResult::Failed(OrderBookError::EmptyBook),
                         _ => ExecutionResult::Executed(result),
                     }
                 }
             },
             Operation::Modify(order) => match order.side {
                 Side::Bid => match self.modify_limit_buy_order(order) {
-                    ModifyResult::Failed => {
-                        ExecutionResult::Failed("no modification occurred".to_string())
-                    }
+                    ModifyResult::Failed => ExecutionResult::Failed(OrderBookError::NoModification),
                     result => ExecutionResult::Modified(result),
                 },
                 Side::Ask => match self.modify_limit_ask_order(order) {
-                    ModifyResult::Failed => {
-                        ExecutionResult::Failed("no modification occurred".to_string())
-                    }
+                    ModifyResult::Failed => ExecutionResult::Failed(OrderBookError::NoModification),
                     result => ExecutionResult::Modified(result),
                 },
             },
-            Operation::Cancel(id) => match self.cancel_order(id) {
-                None => ExecutionResult::Failed("order not found".to_string()),
+            Operation::Cancel(OrderId(id)) => match self.cancel_order(id) {
+                None => ExecutionResult::Failed(OrderBookError::OrderNotFound),
                 Some(id) => ExecutionResult::Cancelled(id),
             },
+            Operation::Reduce {
+                id: OrderId(id),
+                quantity_delta,
+            } => match self.reduce_order(id, quantity_delta) {
+                Ok(order) => ExecutionResult::Reduced(order),
+                Err(error) => ExecutionResult::Failed(error),
+            },
+            Operation::Quote { bid, ask } => match self.quote(bid, ask) {
+                Ok(result) => ExecutionResult::Quoted(result),
+                Err(error) => ExecutionResult::Failed(error),
+            },
+            Operation::Call if self.call => ExecutionResult::Failed(OrderBookError::InCall),
+            Operation::Call => {
+                self.call = true;
+                ExecutionResult::Called
+            }
+            Operation::Uncross if !self.call => ExecutionResult::Failed(OrderBookError::NotInCall),
+            Operation::Uncross => ExecutionResult::Uncrossed(self.uncross()),
+        }
+    }
+
+    /// This method works out the price a call would uncross at if it ended now.
+    /// The price is the one matching the most quantity, among those the one leaving the smallest imbalance and then the one closest to the last traded price, the lowest if that still ties.
+    /// Expired orders are left out once the clock is set, as the uncross purges them.
+    /// Every price level is visited, so the cost grows with the depth of the book.
+    ///
+    /// # Returns
+    ///
+    /// * The [`IndicativeUncross`] of the book, `None` unless the book is crossed.
+    pub fn indicative_uncross(&self) -> Option<IndicativeUncross> {
+        let live_quantity = |queue: &VecDeque<usize>| -> u64 {
+            queue
+                .iter()
+                .map(|index| &self.order_store[*index])
+                .filter(|order| self.clock.is_none_or(|now| !order.is_expired(now)))
+                .map(|order| order.quantity)
+                .sum()
+        };
+        let levels = |book: &BTreeMap<u64, VecDeque<usize>>| -> Vec<(u64, u64)> {
+            book.iter()
+                .map(|(price, queue)| (*price, live_quantity(queue)))
+                .filter(|(_, quantity)| *quantity > 0)
+                .collect()
+        };
+        // both sides ascending by price
+        let bids = levels(&self.bid_side_book);
+        let asks = levels(&self.ask_side_book);
+        let (highest_bid, lowest_ask) = (bids.last()?.0, asks.first()?.0);
+        if highest_bid < lowest_ask {
+            return None;
+        }
+        let mut prices: Vec<u64> = bids
+            .iter()
+            .chain(&asks)
+            .map(|(price, _)| *price)
+            .filter(|price| (lowest_ask..=highest_bid).contains(price))
+            .collect();
+        prices.sort_unstable();
+        prices.dedup();
+
+        let mut demand: u64 = bids.iter().map(|(_, quantity)| quantity).sum();
+        let mut supply = 0u64;
+        let (mut bid_levels, mut ask_levels) = (bids.iter().peekable(), asks.iter().peekable());
+        let mut best: Option<IndicativeUncross> = None;
+        for price in prices {
+            while let Some((_, quantity)) = bid_levels.next_if(|(bid, _)| *bid < price) {
+                demand -= quantity;
+            }
+            while let Some((_, quantity)) = ask_levels.next_if(|(ask, _)| *ask <= price) {
+                supply += quantity;
+            }
+            let candidate = IndicativeUncross {
+                price,
+                quantity: demand.min(supply),
+                imbalance: demand.abs_diff(supply),
+                imbalance_side: match demand.cmp(&supply) {
+                    std::cmp::Ordering::Greater => Some(Side::Bid),
+                    std::cmp::Ordering::Less => Some(Side::Ask),
+                    std::cmp::Ordering::Equal => None,
+                },
+            };
+            let rank = |uncross: &IndicativeUncross| {
+                (
+                    Reverse(uncross.quantity),
+                    uncross.imbalance,
+                    uncross.price.abs_diff(self.last_trade_price),
+                )
+            };
+            if best.is_none_or(|best| rank(&candidate) < rank(&best)) {
+                best = Some(candidate);
+            }
+        }
+        best
+    }
+
+    /// This is an internal method used to end a call by matching the crossed part of the book at the indicative uncross price.
+    /// Bids are walked from the top of the book in time priority and each is matched against the asks at or below the price the way a taker would be, until either side runs out.
+    /// What is left on either side rests and no longer crosses, after which orders match continuously again.
+    ///
+    /// # Returns
+    ///
+    /// * A vector containing the [`FillMetaData`] of every match, empty if the book was not crossed.
+    fn uncross(&mut self) -> Vec<FillMetaData> {
+        self.call = false;
+        let Some(IndicativeUncross { price, .. }) = self.indicative_uncross() else {
+            return Vec::new();
+        };
+        let mut fills = Vec::new();
+        'bids: for (_, bid_queue) in self.bid_side_book.range_mut(price..).rev() {
+            Self::purge_expired(
+                bid_queue,
+                &mut self.order_store,
+                self.clock,
+                &mut self.expired,
+            );
+            while let Some(index) = bid_queue.front().copied() {
+                let bid = self.order_store[index];
+                let mut remaining_quantity = bid.quantity;
+                for (_, ask_queue) in self.ask_side_book.range_mut(..=price) {
+                    Self::purge_expired(
+                        ask_queue,
+                        &mut self.order_store,
+                        self.clock,
+                        &mut self.expired,
+                    );
+                    let level_consumed = Self::process_order_queue(
+                        &bid.id,
+                        &price,
+                        Side::Bid,
+                        &mut remaining_quantity,
+                        ask_queue,
+                        &mut self.order_store,
+                        &mut fills,
+                        self.matching_policy,
+                    );
+                    if !level_consumed {
+                        break;
+                    }
+                }
+                if remaining_quantity > 0 {
+                    self.order_store[index].quantity = remaining_quantity;
+                    break 'bids;
+                }
+                self.order_store.delete(&bid.id);
+                bid_queue.pop_front();
+            }
         }
+        self.max_bid = self.top_bid_price();
+        self.min_ask = self.top_ask_price();
+        if let Some(fill) = fills.last() {
+            self.last_trade_price = fill.price;
+        }
+        fills
     }
 
     /// This method returns the depth of the orderbook upto specified levels.
@@ -212,7 +849,7 @@ impl OrderBook {
                             order_queue.retain(|i| index != *i);
                             if order_queue.is_empty() {
                                 self.bid_side_book.remove(&order.price);
-                                self.max_bid = self.bid_side_book.keys().next_back().cloned();
+                                self.max_bid = self.top_bid_price();
                             }
                         }
                     }
@@ -221,7 +858,7 @@ impl OrderBook {
                             order_queue.retain(|i| index != *i);
                             if order_queue.is_empty() {
                                 self.ask_side_book.remove(&order.price);
-                                self.min_ask = self.ask_side_book.keys().next().cloned();
+                                self.min_ask = self.top_ask_price();
                             }
                         }
                     }
@@ -233,6 +870,98 @@ impl OrderBook {
         }
     }
 
+    /// This is an internal method used to reduce the quantity of an existing order in place.
+    /// The order keeps its slot in the store and its position in the queue, so it keeps its priority.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - This represents the id of the limit order to be reduced.
+    /// * `quantity_delta` - The quantity taken off the order.
+    ///
+    /// # Returns
+    ///
+    /// * The reduced [`LimitOrder`], or an [`OrderBookError`] if it is not in the book or the reduction would not leave any quantity.
+    fn reduce_order(
+        &mut self,
+        id: u128,
+        quantity_delta: u64,
+    ) -> Result<LimitOrder, OrderBookError> {
+        let (order, _) = self
+            .order_store
+            .get_mut(id)
+            .ok_or(OrderBookError::OrderNotFound)?;
+        if quantity_delta == 0 || quantity_delta >= order.quantity {
+            return Err(OrderBookError::InvalidReduction {
+                quantity: order.quantity,
+            });
+        }
+        order.quantity -= quantity_delta;
+        Ok(*order)
+    }
+
+    /// This is an internal method used to replace the orders resting under the ids of a quote with
+    /// its bid and ask, which are placed in that order and may trade. A side without quantity is
+    /// only withdrawn. Nothing changes unless the quote is valid as a whole.
+    fn quote(&mut self, bid: LimitOrder, ask: LimitOrder) -> Result<QuoteResult, OrderBookError> {
+        if bid.side != Side::Bid || ask.side != Side::Ask || bid.id == ask.id {
+            return Err(OrderBookError::InvalidQuote);
+        }
+        if bid.quantity > 0 && ask.quantity > 0 && bid.price >= ask.price {
+            return Err(OrderBookError::CrossedQuote {
+                bid: bid.price,
+                ask: ask.price,
+            });
+        }
+        let replaced = [bid.id, ask.id]
+            .into_iter()
+            .filter_map(|id| self.cancel_order(id))
+            .collect();
+        let mut fills = Vec::new();
+        let mut rest = |result: FillResult| match result {
+            FillResult::Created(order) => Some(order),
+            FillResult::PartiallyFilled(order, filled) => {
+                fills.extend(filled);
+                Some(order)
+            }
+            FillResult::Filled(filled) => {
+                fills.extend(filled);
+                None
+            }
+            FillResult::Failed => None,
+        };
+        let bid = (bid.quantity > 0)
+            .then(|| self.limit_bid_order(bid))
+            .and_then(&mut rest);
+        let ask = (ask.quantity > 0)
+            .then(|| self.limit_ask_order(ask))
+            .and_then(&mut rest);
+        Ok(QuoteResult {
+            replaced,
+            bid,
+            ask,
+            fills,
+        })
+    }
+
+    /// This is an internal method used to find the highest bid price with orders resting at it,
+    /// as matching leaves the levels it empties in the book.
+    fn top_bid_price(&self) -> Option<u64> {
+        self.bid_side_book
+            .iter()
+            .rev()
+            .find(|(_, queue)| !queue.is_empty())
+            .map(|(price, _)| *price)
+    }
+
+    /// This is an internal method used to find the lowest ask price with orders resting at it,
+    /// as matching leaves the levels it empties in the book.
+    fn top_ask_price(&self) -> Option<u64> {
+        self.ask_side_book
+            .iter()
+            .find(|(_, queue)| !queue.is_empty())
+            .map(|(price, _)| *price)
+    }
+
     /// This is an internal method used to modify an existing bid order.
     ///
     /// # Arguments
@@ -247,7 +976,16 @@ impl OrderBook {
             if let Some(order_queue) = self.bid_side_book.get_mut(&existing_order.price) {
                 if let Some(position) = order_queue.iter().position(|i| index == *i) {
                     if existing_order.price != order.price {
+                        let order = LimitOrder {
+                            expires_at: order.expires_at.or(existing_order.expires_at),
+                            ..order
+                        };
+                        let existing_price = existing_order.price;
                         order_queue.remove(position);
+                        if order_queue.is_empty() {
+                            self.bid_side_book.remove(&existing_price);
+                            self.max_bid = self.top_bid_price();
+                        }
                         self.order_store.delete(&order.id);
                         return ModifyResult::Created(self.limit_bid_order(order));
                     }
@@ -275,7 +1013,16 @@ impl OrderBook {
             if let Some(order_queue) = self.ask_side_book.get_mut(&existing_order.price) {
                 if let Some(position) = order_queue.iter().position(|i| index == *i) {
                     if existing_order.price != order.price {
+                        let order = LimitOrder {
+                            expires_at: order.expires_at.or(existing_order.expires_at),
+                            ..order
+                        };
+                        let existing_price = existing_order.price;
                         order_queue.remove(position);
+                        if order_queue.is_empty() {
+                            self.ask_side_book.remove(&existing_price);
+                            self.min_ask = self.top_ask_price();
+                        }
                         self.order_store.delete(&order.id);
                         return ModifyResult::Created(self.limit_ask_order(order));
                     }
@@ -292,6 +1039,7 @@ impl OrderBook {
     /// This is an internal method used to place a limit bid order.
     ///
     /// *Algorithm:*
+    /// - during a call, rest the order without matching.
     /// - start matching from the top of the book till the limit price exceeds top of the book or the quantity is extinguished.
     /// - skip empty levels
     /// - update min_ask if a partial fill takes place on a specific level.
@@ -308,6 +1056,9 @@ impl OrderBook {
     ///     - Partially filled with a [`LimitOrder`] being placed with *remaining* quantity and a vector containing this [`FillMetaData`].
     ///     - Created, returning a [`LimitOrder`] with no fills.
     fn limit_bid_order(&mut self, order: LimitOrder) -> FillResult {
+        if self.call {
+            return self.process_bid_fills(order, Vec::new(), order.quantity);
+        }
         let mut order_fills = Vec::new();
         let mut remaining_quantity = order.quantity;
         let mut level_consumed = false;
@@ -320,6 +1071,7 @@ impl OrderBook {
                 level_consumed = false;
                 break;
             }
+            Self::purge_expired(queue, &mut self.order_store, self.clock, &mut self.expired);
             level_consumed = Self::process_order_queue(
                 &order.id,
                 ask_price,
@@ -328,7 +1080,11 @@ impl OrderBook {
                 queue,
                 &mut self.order_store,
                 &mut order_fills,
+                self.matching_policy,
             );
+            if !level_consumed {
+                break;
+            }
         }
         if level_consumed {
             self.min_ask = None;
@@ -339,6 +1095,7 @@ impl OrderBook {
     /// This is an internal method used to place a limit ask order.
     ///
     /// *Algorithm:*
+    /// - during a call, rest the order without matching.
     /// - start matching from the top of the book till the limit price exceeds top of the book or the quantity is extinguished.
     /// - skip empty levels
     /// - update max_bid if a partial fill takes place on a specific level.
@@ -356,6 +1113,9 @@ impl OrderBook {
     ///     - Partially filled with a [`LimitOrder`] being placed with *remaining* quantity and a vector containing this [`FillMetaData`].
     ///     - Created, returning a [`LimitOrder`] with no fills.
     fn limit_ask_order(&mut self, order: LimitOrder) -> FillResult {
+        if self.call {
+            return self.process_ask_fills(order, Vec::new(), order.quantity);
+        }
         let mut order_fills = Vec::new();
         let mut remaining_quantity = order.quantity;
         let mut level_consumed = false;
@@ -368,6 +1128,7 @@ impl OrderBook {
                 level_consumed = false;
                 break;
             }
+            Self::purge_expired(queue, &mut self.order_store, self.clock, &mut self.expired);
             level_consumed = Self::process_order_queue(
                 &order.id,
                 bid_price,
@@ -376,7 +1137,11 @@ impl OrderBook {
                 queue,
                 &mut self.order_store,
                 &mut order_fills,
+                self.matching_policy,
             );
+            if !level_consumed {
+                break;
+            }
         }
         if level_consumed {
             self.max_bid = None;
@@ -408,19 +1173,16 @@ impl OrderBook {
         let mut order_fills = Vec::new();
         let mut remaining_quantity = order.quantity;
         let mut level_consumed = false;
-        let mut update_min_ask = false;
         if self.min_ask.is_none() || self.min_ask.unwrap() == u64::MAX {
             return FillResult::Failed;
         }
 
         for (ask_price, queue) in self.ask_side_book.iter_mut() {
-            if update_min_ask {
-                self.min_ask = Some(*ask_price);
-                update_min_ask = false;
-            }
             if queue.is_empty() {
                 continue;
             }
+            self.min_ask = Some(*ask_price);
+            Self::purge_expired(queue, &mut self.order_store, self.clock, &mut self.expired);
             level_consumed = Self::process_order_queue(
                 &order.id,
                 ask_price,
@@ -429,9 +1191,10 @@ impl OrderBook {
                 queue,
                 &mut self.order_store,
                 &mut order_fills,
+                self.matching_policy,
             );
-            if remaining_quantity > 0 {
-                update_min_ask = true
+            if !level_consumed {
+                break;
             }
         }
         let order = order.to_limit(self.min_ask.unwrap_or(u64::MAX));
@@ -515,19 +1278,16 @@ impl OrderBook {
         let mut order_fills = Vec::new();
         let mut remaining_quantity = order.quantity;
         let mut level_consumed = false;
-        let mut update_max_bid = false;
         if self.max_bid.is_none() {
             return FillResult::Failed;
         }
 
         for (bid_price, queue) in self.bid_side_book.iter_mut().rev() {
-            if update_max_bid {
-                self.max_bid = Some(*bid_price);
-                update_max_bid = false;
-            }
             if queue.is_empty() {
                 continue;
             }
+            self.max_bid = Some(*bid_price);
+            Self::purge_expired(queue, &mut self.order_store, self.clock, &mut self.expired);
             level_consumed = Self::process_order_queue(
                 &order.id,
                 bid_price,
@@ -536,9 +1296,10 @@ impl OrderBook {
                 queue,
                 &mut self.order_store,
                 &mut order_fills,
+                self.matching_policy,
             );
-            if remaining_quantity > 0 {
-                update_max_bid = true
+            if !level_consumed {
+                break;
             }
         }
         let order = order.to_limit(self.max_bid.unwrap_or(u64::MIN));
@@ -600,9 +1361,9 @@ impl OrderBook {
     /// This is an internal method used to process the queue of orders at a particular price.
     /// Whenever a limit or a market order starts matching, this method is used to pop orders against the quantity in the order.
     /// *Algorithm:*
-    /// - Dequeue each front index at a price.
+    /// - Pick the next index at a price as per the matching policy, which is the front index under price-time priority.
     /// - Get its order details, from store.
-    /// - If it has enough quantity, modify in place. Else, pop and update store.
+    /// - If it has enough quantity, modify in place. Else, remove it from the queue and update store.
     /// - Repeat till queue is empty or no quantity remains to be filled.
     ///
     /// # Arguments
@@ -614,10 +1375,12 @@ impl OrderBook {
     /// * `queue` - The current(price) order queue to fill the order that has been placed.
     /// * `store` - The order store.
     /// * `order_fills` - This represents each match that takes place across the entire matching process.
+    /// * `matching_policy` - The rule that decides which order at the price is filled next.
     ///
     /// # Returns
     ///
     /// * A resultant vector containing [`FillMetaData`] generated in order matching.
+    #[allow(clippy::too_many_arguments)]
     fn process_order_queue(
         id: &u128,
         price: &u64,
@@ -626,35 +1389,46 @@ impl OrderBook {
         queue: &mut VecDeque<usize>,
         store: &mut Store,
         order_fills: &mut Vec<FillMetaData>,
+        matching_policy: MatchingPolicy,
     ) -> bool {
         let mut level_consumed = false;
-        while let Some(front_order_index) = queue.front() {
+        while !queue.is_empty() {
             if *remaining_quantity == 0 {
                 break;
             }
-            let front_order_data = store.index_mut(*front_order_index);
-            if front_order_data.quantity > *remaining_quantity {
-                front_order_data.quantity -= *remaining_quantity;
+            // the draw only depends on the operation, so replays of it fill the same orders
+            let draw = (*id as u64)
+                ^ ((*id >> 64) as u64)
+                ^ price.rotate_left(32)
+                ^ order_fills.len() as u64;
+            let position = matching_policy.next_fill(
+                queue.len(),
+                |position| store.index(queue[position]).quantity,
+                draw,
+            );
+            let resting_order = store.index_mut(queue[position]);
+            if resting_order.quantity > *remaining_quantity {
+                resting_order.quantity -= *remaining_quantity;
                 order_fills.push(FillMetaData {
                     order_id: *id,
-                    matched_order_id: front_order_data.id,
+                    matched_order_id: resting_order.id,
                     taker_side: side,
                     price: *price,
                     quantity: *remaining_quantity,
                 });
                 *remaining_quantity = 0;
             } else {
-                *remaining_quantity -= front_order_data.quantity;
+                *remaining_quantity -= resting_order.quantity;
                 order_fills.push(FillMetaData {
                     order_id: *id,
-                    matched_order_id: front_order_data.id,
+                    matched_order_id: resting_order.id,
                     taker_side: side,
                     price: *price,
-                    quantity: front_order_data.quantity,
+                    quantity: resting_order.quantity,
                 });
-                let id = front_order_data.id;
+                let id = resting_order.id;
                 store.delete(&id);
-                queue.pop_front();
+                queue.remove(position);
             }
         }
         if queue.is_empty() {
@@ -663,6 +1437,37 @@ impl OrderBook {
         level_consumed
     }
 
+    /// This is an internal method used to purge the expired orders of a price level before a taker matches against it.
+    ///
+    /// # Arguments
+    ///
+    /// * `queue` - The order queue at the price about to be matched.
+    /// * `store` - The order store.
+    /// * `now` - The clock of the book, nothing is purged if it is not set.
+    /// * `expired` - The ids of the purged orders are appended to this.
+    fn purge_expired(
+        queue: &mut VecDeque<usize>,
+        store: &mut Store,
+        now: Option<u128>,
+        expired: &mut Vec<u128>,
+    ) {
+        let Some(now) = now else {
+            return;
+        };
+        let purged = expired.len();
+        queue.retain(|index| {
+            let order = store.index(*index);
+            if !order.is_expired(now) {
+                return true;
+            }
+            expired.push(order.id);
+            false
+        });
+        for id in &expired[purged..] {
+            store.delete(id);
+        }
+    }
+
     /// This is an internal helper method used to aggregate quantity at prices going down the top of the book
     ///
     /// # Arguments
@@ -727,12 +1532,115 @@ impl OrderBook {
         }
     }
 
-    pub fn request_for_quote(&self, market_order: MarketOrder) -> RfqStatus {
-        let quantity = market_order.quantity;
-        if quantity == 0 {
-            return RfqStatus::NotPossible;
-        }
-        match market_order.side {
+    /// This method walks the opposite side of the book the way a market order would, without executing it.
+    ///
+    /// # Arguments
+    ///
+    /// * `side` - The side of the market order.
+    /// * `quantity` - The quantity of the market order.
+    ///
+    /// # Returns
+    ///
+    /// * A tuple of the quantity that can be filled, the notional of that quantity and the worst price reached, which is `None` when the opposite side is empty.
+    pub fn sweep(&self, side: Side, quantity: u64) -> (u64, u64, Option<u64>) {
+        let levels: Box<dyn Iterator<Item = (&u64, &VecDeque<usize>)>> = match side {
+            Side::Bid => Box::new(self.ask_side_book.iter()),
+            Side::Ask => Box::new(self.bid_side_book.iter().rev()),
+        };
+        let mut remaining_quantity = quantity;
+        let mut notional: u64 = 0;
+        let mut worst_price = None;
+        for (price, queue) in levels {
+            if remaining_quantity == 0 {
+                break;
+            }
+            let level_quantity: u64 = queue
+                .iter()
+                .map(|index| self.order_store.index(*index).quantity)
+                .sum();
+            if level_quantity == 0 {
+                continue;
+            }
+            let filled = level_quantity.min(remaining_quantity);
+            notional = notional.saturating_add(price.saturating_mul(filled));
+            remaining_quantity -= filled;
+            worst_price = Some(*price);
+        }
+        (quantity - remaining_quantity, notional, worst_price)
+    }
+
+    /// This method estimates the price impact of market orders of increasing size, by walking the
+    /// opposite side of the book for each of the notionals.
+    ///
+    /// # Arguments
+    ///
+    /// * `side` - The side of the market orders.
+    /// * `notionals` - The notionals the market orders spend or raise, in any order.
+    ///
+    /// # Returns
+    ///
+    /// * A [`SlippagePoint`] for each of the notionals, in the same order.
+    pub fn slippage_curve(&self, side: Side, notionals: &[u64]) -> Vec<SlippagePoint> {
+        let levels: Vec<Level> = match side {
+            Side::Bid => self
+                .iter_asks()
+                .filter(|level| level.quantity > 0)
+                .collect(),
+            Side::Ask => self
+                .iter_bids()
+                .filter(|level| level.quantity > 0)
+                .collect(),
+        };
+        notionals
+            .iter()
+            .map(|&notional| {
+                let mut remaining_notional = notional;
+                let mut quantity: u64 = 0;
+                let mut worst_price = None;
+                let mut exhausted = true;
+                for level in &levels {
+                    let affordable = remaining_notional / level.price.max(1);
+                    if affordable == 0 {
+                        exhausted = false;
+                        break;
+                    }
+                    let filled = affordable.min(level.quantity);
+                    remaining_notional -= filled * level.price;
+                    quantity += filled;
+                    worst_price = Some(level.price);
+                    if filled < level.quantity {
+                        exhausted = false;
+                        break;
+                    }
+                }
+                let average_price = match quantity {
+                    0 => None,
+                    _ => Some((notional - remaining_notional) / quantity),
+                };
+                let impact_bps = match (levels.first(), average_price) {
+                    (Some(best), Some(average_price)) => {
+                        best.price.abs_diff(average_price) * 10_000 / best.price.max(1)
+                    }
+                    _ => 0,
+                };
+                SlippagePoint {
+                    notional,
+                    quantity,
+                    average_price,
+                    worst_price,
+                    impact_bps,
+                    complete: !exhausted || remaining_notional == 0,
+                }
+            })
+            .collect()
+    }
+
+    pub fn request_for_quote(&self, market_order: MarketOrder) -> RfqStatus {
+        let quantity = market_order.quantity;
+        if quantity == 0 {
+            return RfqStatus::NotPossible;
+        }
+        match market_order.side {
             Side::Bid => {
                 let min_ask = match self.min_ask {
                     Some(ask) => ask,
@@ -833,32 +1741,131 @@ impl OrderBook {
     }
 }
 
+/// A builder for an [`OrderBook`], created by [`OrderBook::builder`]. Options that are not set
+/// take the values [`OrderBook::default`] uses.
+#[derive(Debug, Clone)]
+pub struct OrderBookBuilder {
+    id: Option<String>,
+    queue_capacity: usize,
+    store_capacity: usize,
+    tick_size: u64,
+    lot_size: u64,
+    matching_policy: MatchingPolicy,
+    trade_tape_capacity: Option<usize>,
+}
+
+impl Default for OrderBookBuilder {
+    fn default() -> Self {
+        OrderBookBuilder {
+            id: None,
+            queue_capacity: 10,
+            store_capacity: 10000,
+            tick_size: 1,
+            lot_size: 1,
+            matching_policy: MatchingPolicy::default(),
+            trade_tape_capacity: None,
+        }
+    }
+}
+
+impl OrderBookBuilder {
+    /// Sets the id of the book, usually its symbol. A `Uuid::new_v4()` based id is used otherwise.
+    pub fn id(mut self, id: impl Into<String>) -> Self {
+        self.id = Some(id.into());
+        self
+    }
+
+    /// Sets the pre-allocated size of the queues of orders at each price.
+    pub fn queue_capacity(mut self, queue_capacity: usize) -> Self {
+        self.queue_capacity = queue_capacity;
+        self
+    }
+
+    /// Sets the pre-allocated size of the order store.
+    pub fn store_capacity(mut self, store_capacity: usize) -> Self {
+        self.store_capacity = store_capacity;
+        self
+    }
+
+    /// Rejects orders whose price is not a multiple of `tick_size`.
+    pub fn tick_size(mut self, tick_size: u64) -> Self {
+        self.tick_size = tick_size;
+        self
+    }
+
+    /// Rejects orders whose quantity is not a multiple of `lot_size`.
+    pub fn lot_size(mut self, lot_size: u64) -> Self {
+        self.lot_size = lot_size;
+        self
+    }
+
+    pub fn matching_policy(mut self, matching_policy: MatchingPolicy) -> Self {
+        self.matching_policy = matching_policy;
+        self
+    }
+
+    /// Keeps the latest `capacity` fills of the book, see [`OrderBook::trade_tape`].
+    pub fn trade_tape(mut self, capacity: usize) -> Self {
+        self.trade_tape_capacity = Some(capacity);
+        self
+    }
+
+    /// This method builds the orderbook.
+    ///
+    /// # Returns
+    ///
+    /// * The [`OrderBook`], or a [`ConfigurationError`] if the tick or lot size is zero.
+    pub fn build(self) -> Result<OrderBook, ConfigurationError> {
+        if self.tick_size == 0 {
+            return Err(ConfigurationError::ZeroTickSize);
+        }
+        if self.lot_size == 0 {
+            return Err(ConfigurationError::ZeroLotSize);
+        }
+        let id = self.id.unwrap_or_else(|| Uuid::new_v4().to_string());
+        let mut orderbook = OrderBook::new(id, self.queue_capacity, self.store_capacity);
+        orderbook.tick_size = self.tick_size;
+        orderbook.lot_size = self.lot_size;
+        orderbook.matching_policy = self.matching_policy;
+        if let Some(capacity) = self.trade_tape_capacity {
+            orderbook.trade_tape = Some(VecDeque::with_capacity(capacity));
+            orderbook.trade_tape_capacity = capacity;
+        }
+        Ok(orderbook)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::core::models::Granularity;
+    use crate::core::types::{OrderId, Price, Quantity};
     use crate::core::{
+        errors::{ConfigurationError, OrderBookError},
         models::{
-            ExecutionResult, FillMetaData, FillResult, LimitOrder, MarketOrder, Operation, Side,
+            AuditReport, CompactionReport, ExecutionResult, FillMetaData, FillResult,
+            Inconsistency, IndicativeUncross, LimitOrder, MarketOrder, MatchingPolicy, MemoryStats,
+            Operation, Side, SlippagePoint,
         },
         orderbook::OrderBook,
         store::Store,
     };
-    use std::collections::{BTreeMap, VecDeque};
+    use std::collections::{BTreeMap, HashSet, VecDeque};
+    use std::mem::size_of;
     use std::ops::Index;
 
     fn create_orderbook() -> OrderBook {
         let mut book = OrderBook::default();
         let orders = vec![
-            LimitOrder::new(1, 100, 100, Side::Bid),
-            LimitOrder::new(2, 100, 150, Side::Bid),
-            LimitOrder::new(3, 100, 50, Side::Bid),
-            LimitOrder::new(4, 110, 200, Side::Bid),
-            LimitOrder::new(5, 110, 100, Side::Bid),
-            LimitOrder::new(6, 120, 100, Side::Ask),
-            LimitOrder::new(7, 120, 150, Side::Ask),
-            LimitOrder::new(8, 120, 50, Side::Ask),
-            LimitOrder::new(9, 130, 200, Side::Ask),
-            LimitOrder::new(10, 130, 100, Side::Ask),
+            LimitOrder::new(OrderId(1), Price(100), Quantity(100), Side::Bid),
+            LimitOrder::new(OrderId(2), Price(100), Quantity(150), Side::Bid),
+            LimitOrder::new(OrderId(3), Price(100), Quantity(50), Side::Bid),
+            LimitOrder::new(OrderId(4), Price(110), Quantity(200), Side::Bid),
+            LimitOrder::new(OrderId(5), Price(110), Quantity(100), Side::Bid),
+            LimitOrder::new(OrderId(6), Price(120), Quantity(100), Side::Ask),
+            LimitOrder::new(OrderId(7), Price(120), Quantity(150), Side::Ask),
+            LimitOrder::new(OrderId(8), Price(120), Quantity(50), Side::Ask),
+            LimitOrder::new(OrderId(9), Price(130), Quantity(200), Side::Ask),
+            LimitOrder::new(OrderId(10), Price(130), Quantity(100), Side::Ask),
         ];
         for order in orders {
             book.execute(Operation::Limit(order));
@@ -894,7 +1901,7 @@ mod tests {
     #[test]
     fn it_cancels_order_when_it_exists() {
         let mut book = create_orderbook();
-        let order = LimitOrder::new(11, 115, 100, Side::Bid);
+        let order = LimitOrder::new(OrderId(11), Price(115), Quantity(100), Side::Bid);
         book.execute(Operation::Limit(order));
         match book.cancel_order(order.id) {
             Some(id) => {
@@ -916,7 +1923,12 @@ mod tests {
     #[test]
     fn it_cancels_a_single_bid() {
         let mut book = OrderBook::default();
-        book.execute(Operation::Limit(LimitOrder::new(1, 100, 100, Side::Bid)));
+        book.execute(Operation::Limit(LimitOrder::new(
+            OrderId(1),
+            Price(100),
+            Quantity(100),
+            Side::Bid,
+        )));
         match book.cancel_order(1) {
             None => panic!("test failed"),
             Some(order_id) => {
@@ -928,7 +1940,12 @@ mod tests {
     #[test]
     fn it_cancels_a_single_ask() {
         let mut book = OrderBook::default();
-        book.execute(Operation::Limit(LimitOrder::new(1, 100, 100, Side::Ask)));
+        book.execute(Operation::Limit(LimitOrder::new(
+            OrderId(1),
+            Price(100),
+            Quantity(100),
+            Side::Ask,
+        )));
         match book.cancel_order(1) {
             None => panic!("test failed"),
             Some(order_id) => {
@@ -940,7 +1957,7 @@ mod tests {
     #[test]
     fn it_executes_a_limit_bid_that_is_created() {
         let mut book = create_orderbook();
-        let order = LimitOrder::new(11, 100, 500, Side::Bid);
+        let order = LimitOrder::new(OrderId(11), Price(100), Quantity(500), Side::Bid);
         match book.limit_bid_order(order) {
             FillResult::Created(created_order) => {
                 let (stored_order, _) = book.order_store.get(order.id).unwrap();
@@ -953,7 +1970,7 @@ mod tests {
     #[test]
     fn it_executes_a_limit_bid_that_is_filled() {
         let mut book = create_orderbook();
-        let order = LimitOrder::new(11, 130, 400, Side::Bid);
+        let order = LimitOrder::new(OrderId(11), Price(130), Quantity(400), Side::Bid);
         match book.limit_bid_order(order) {
             FillResult::Filled(order_fills) => {
                 let quantity =
@@ -967,11 +1984,12 @@ mod tests {
     #[test]
     fn it_executes_a_limit_bid_that_is_partially_filled() {
         let mut book = create_orderbook();
-        let order = LimitOrder::new(11, 150, 700, Side::Bid);
+        let order = LimitOrder::new(OrderId(11), Price(150), Quantity(700), Side::Bid);
         match book.limit_bid_order(order) {
             FillResult::PartiallyFilled(order_placed, order_fills) => {
                 let (stored_order, _) = book.order_store.get(order.id).unwrap();
-                let created_order = LimitOrder::new(11, 150, 100, Side::Bid);
+                let created_order =
+                    LimitOrder::new(OrderId(11), Price(150), Quantity(100), Side::Bid);
                 assert!(
                     fills_to_ids(order_fills) == vec![6, 7, 8, 9, 10]
                         && order_placed == created_order
@@ -985,7 +2003,7 @@ mod tests {
     #[test]
     fn it_executes_a_limit_ask_that_is_created() {
         let mut book = create_orderbook();
-        let order = LimitOrder::new(11, 120, 250, Side::Ask);
+        let order = LimitOrder::new(OrderId(11), Price(120), Quantity(250), Side::Ask);
         match book.limit_ask_order(order) {
             FillResult::Created(created_order) => {
                 let (stored_order, _) = book.order_store.get(order.id).unwrap();
@@ -998,7 +2016,7 @@ mod tests {
     #[test]
     fn it_executes_a_limit_ask_that_is_filled() {
         let mut book = create_orderbook();
-        let order = LimitOrder::new(11, 100, 400, Side::Ask);
+        let order = LimitOrder::new(OrderId(11), Price(100), Quantity(400), Side::Ask);
         match book.limit_ask_order(order) {
             FillResult::Filled(order_fills) => {
                 let quantity = get_total_quantity_at_price(
@@ -1015,11 +2033,12 @@ mod tests {
     #[test]
     fn it_executes_a_limit_ask_that_is_partially_filled() {
         let mut book = create_orderbook();
-        let order = LimitOrder::new(11, 90, 700, Side::Ask);
+        let order = LimitOrder::new(OrderId(11), Price(90), Quantity(700), Side::Ask);
         match book.limit_ask_order(order) {
             FillResult::PartiallyFilled(order_placed, order_fills) => {
                 let (stored_order, _) = book.order_store.get(order.id).unwrap();
-                let created_order = LimitOrder::new(11, 90, 100, Side::Ask);
+                let created_order =
+                    LimitOrder::new(OrderId(11), Price(90), Quantity(100), Side::Ask);
                 assert!(
                     fills_to_ids(order_fills) == vec![4, 5, 1, 2, 3]
                         && order_placed == created_order
@@ -1033,7 +2052,7 @@ mod tests {
     #[test]
     fn it_modifies_limit_bid_order_quantity() {
         let mut book = create_orderbook();
-        let order = LimitOrder::new(1, 100, 150, Side::Bid);
+        let order = LimitOrder::new(OrderId(1), Price(100), Quantity(150), Side::Bid);
         book.modify_limit_buy_order(order);
         assert_eq!(
             get_total_quantity_at_price(&order.price, &book.bid_side_book, &book.order_store),
@@ -1044,7 +2063,7 @@ mod tests {
     #[test]
     fn it_modifies_limit_ask_order_quantity() {
         let mut book = create_orderbook();
-        let order = LimitOrder::new(6, 120, 150, Side::Ask);
+        let order = LimitOrder::new(OrderId(6), Price(120), Quantity(150), Side::Ask);
         book.modify_limit_ask_order(order);
         assert_eq!(
             get_total_quantity_at_price(&order.price, &book.ask_side_book, &book.order_store),
@@ -1055,7 +2074,7 @@ mod tests {
     #[test]
     fn it_modifies_limit_bid_order_price() {
         let mut book = create_orderbook();
-        let order = LimitOrder::new(1, 120, 400, Side::Bid);
+        let order = LimitOrder::new(OrderId(1), Price(120), Quantity(400), Side::Bid);
         book.modify_limit_buy_order(order);
         let quantity_at_100 =
             get_total_quantity_at_price(&100, &book.bid_side_book, &book.order_store);
@@ -1067,7 +2086,7 @@ mod tests {
     #[test]
     fn it_modifies_limit_ask_order_price() {
         let mut book = create_orderbook();
-        let order = LimitOrder::new(6, 110, 400, Side::Ask);
+        let order = LimitOrder::new(OrderId(6), Price(110), Quantity(400), Side::Ask);
         book.modify_limit_ask_order(order);
         let quantity_at_120 =
             get_total_quantity_at_price(&120, &book.ask_side_book, &book.order_store);
@@ -1079,7 +2098,7 @@ mod tests {
     #[test]
     fn it_modifies_nothing_when_price_and_quantity_are_same() {
         let mut book = create_orderbook();
-        let order = LimitOrder::new(1, 100, 100, Side::Bid);
+        let order = LimitOrder::new(OrderId(1), Price(100), Quantity(100), Side::Bid);
         book.modify_limit_buy_order(order);
         assert_eq!(
             get_total_quantity_at_price(&100, &book.bid_side_book, &book.order_store),
@@ -1090,7 +2109,7 @@ mod tests {
     #[test]
     fn it_executes_a_market_bid_filled() {
         let mut book = create_orderbook();
-        let order = MarketOrder::new(11, 500, Side::Bid);
+        let order = MarketOrder::new(OrderId(11), Quantity(500), Side::Bid);
         match book.market_bid_order(order) {
             FillResult::Filled(order_fills) => {
                 let quantity =
@@ -1104,7 +2123,7 @@ mod tests {
     #[test]
     fn it_executes_a_market_ask_filled() {
         let mut book = create_orderbook();
-        let order = MarketOrder::new(11, 500, Side::Ask);
+        let order = MarketOrder::new(OrderId(11), Quantity(500), Side::Ask);
         match book.market_ask_order(order) {
             FillResult::Filled(order_fills) => {
                 let quantity =
@@ -1118,12 +2137,13 @@ mod tests {
     #[test]
     fn it_executes_a_market_bid_partially_filled() {
         let mut book = create_orderbook();
-        let order = MarketOrder::new(11, 700, Side::Bid);
+        let order = MarketOrder::new(OrderId(11), Quantity(700), Side::Bid);
         match book.market_bid_order(order) {
             FillResult::PartiallyFilled(order_placed, order_fills) => {
                 assert!(
                     fills_to_ids(order_fills) == vec![6, 7, 8, 9, 10]
-                        && order_placed == LimitOrder::new(11, 130, 100, Side::Bid)
+                        && order_placed
+                            == LimitOrder::new(OrderId(11), Price(130), Quantity(100), Side::Bid)
                 );
             }
             _ => panic!("test failed"),
@@ -1133,12 +2153,13 @@ mod tests {
     #[test]
     fn it_executes_a_market_ask_partially_filled() {
         let mut book = create_orderbook();
-        let order = MarketOrder::new(11, 700, Side::Ask);
+        let order = MarketOrder::new(OrderId(11), Quantity(700), Side::Ask);
         match book.market_ask_order(order) {
             FillResult::PartiallyFilled(order_placed, order_fills) => {
                 assert!(
                     fills_to_ids(order_fills) == vec![4, 5, 1, 2, 3]
-                        && order_placed == LimitOrder::new(11, 100, 100, Side::Ask)
+                        && order_placed
+                            == LimitOrder::new(OrderId(11), Price(100), Quantity(100), Side::Ask)
                 );
             }
             _ => panic!("test failed"),
@@ -1148,11 +2169,9 @@ mod tests {
     #[test]
     fn it_does_not_execute_market_bid_when_max_bid_is_none() {
         let mut book = OrderBook::default();
-        let order = MarketOrder::new(1, 100, Side::Bid);
+        let order = MarketOrder::new(OrderId(1), Quantity(100), Side::Bid);
         match book.execute(Operation::Market(order)) {
-            ExecutionResult::Failed(message) => {
-                assert_eq!(message, "placed market order on empty book")
-            }
+            ExecutionResult::Failed(error) => assert_eq!(error, OrderBookError::EmptyBook),
             _ => panic!("test failed"),
         }
     }
@@ -1160,11 +2179,9 @@ mod tests {
     #[test]
     fn it_does_not_execute_market_ask_when_max_bid_is_none() {
         let mut book = OrderBook::default();
-        let order = MarketOrder::new(1, 100, Side::Ask);
+        let order = MarketOrder::new(OrderId(1), Quantity(100), Side::Ask);
         match book.execute(Operation::Market(order)) {
-            ExecutionResult::Failed(message) => {
-                assert_eq!(message, "placed market order on empty book")
-            }
+            ExecutionResult::Failed(error) => assert_eq!(error, OrderBookError::EmptyBook),
             _ => panic!("test failed"),
         }
     }
@@ -1172,7 +2189,7 @@ mod tests {
     #[test]
     fn it_updates_top_price_when_bid_is_created() {
         let mut book = create_orderbook();
-        let order = LimitOrder::new(11, 115, 500, Side::Bid);
+        let order = LimitOrder::new(OrderId(11), Price(115), Quantity(500), Side::Bid);
         book.limit_bid_order(order);
         match book.max_bid {
             Some(price) => assert_eq!(price, order.price),
@@ -1183,7 +2200,7 @@ mod tests {
     #[test]
     fn it_updates_top_price_when_ask_is_created() {
         let mut book = create_orderbook();
-        let order = LimitOrder::new(11, 115, 500, Side::Ask);
+        let order = LimitOrder::new(OrderId(11), Price(115), Quantity(500), Side::Ask);
         book.limit_ask_order(order);
         match book.min_ask {
             Some(price) => assert_eq!(price, order.price),
@@ -1194,7 +2211,7 @@ mod tests {
     #[test]
     fn it_updates_top_price_when_bid_is_filled_and_asks_remain() {
         let mut book = create_orderbook();
-        let order = LimitOrder::new(11, 120, 300, Side::Bid);
+        let order = LimitOrder::new(OrderId(11), Price(120), Quantity(300), Side::Bid);
         book.limit_bid_order(order);
         assert_eq!(book.min_ask, Some(130));
     }
@@ -1202,7 +2219,7 @@ mod tests {
     #[test]
     fn it_updates_top_price_when_ask_is_filled_and_bids_remain() {
         let mut book = create_orderbook();
-        let order = LimitOrder::new(11, 110, 300, Side::Ask);
+        let order = LimitOrder::new(OrderId(11), Price(110), Quantity(300), Side::Ask);
         book.limit_ask_order(order);
         assert_eq!(book.max_bid, Some(100));
     }
@@ -1210,7 +2227,7 @@ mod tests {
     #[test]
     fn it_updates_top_price_when_bid_is_filled_and_asks_are_empty() {
         let mut book = create_orderbook();
-        let order = LimitOrder::new(11, 130, 600, Side::Bid);
+        let order = LimitOrder::new(OrderId(11), Price(130), Quantity(600), Side::Bid);
         book.limit_bid_order(order);
         assert_eq!(book.min_ask, None);
     }
@@ -1218,7 +2235,7 @@ mod tests {
     #[test]
     fn it_updates_top_price_when_ask_is_filled_and_bids_are_empty() {
         let mut book = create_orderbook();
-        let order = LimitOrder::new(11, 100, 600, Side::Ask);
+        let order = LimitOrder::new(OrderId(11), Price(100), Quantity(600), Side::Ask);
         book.limit_ask_order(order);
         assert_eq!(book.max_bid, None);
     }
@@ -1226,7 +2243,7 @@ mod tests {
     #[test]
     fn it_updates_top_price_when_bid_is_partially_filled_and_asks_remain() {
         let mut book = create_orderbook();
-        let order = LimitOrder::new(11, 120, 400, Side::Bid);
+        let order = LimitOrder::new(OrderId(11), Price(120), Quantity(400), Side::Bid);
         book.limit_bid_order(order);
         assert!(book.min_ask == Some(130) && book.max_bid == Some(order.price))
     }
@@ -1234,7 +2251,7 @@ mod tests {
     #[test]
     fn it_updates_top_price_when_ask_is_partially_filled_and_bids_remain() {
         let mut book = create_orderbook();
-        let order = LimitOrder::new(11, 110, 400, Side::Ask);
+        let order = LimitOrder::new(OrderId(11), Price(110), Quantity(400), Side::Ask);
         book.limit_ask_order(order);
         assert!(book.max_bid == Some(100) && book.min_ask == Some(order.price))
     }
@@ -1242,7 +2259,7 @@ mod tests {
     #[test]
     fn it_updates_top_price_when_bid_is_partially_filled_and_asks_are_empty() {
         let mut book = create_orderbook();
-        let order = LimitOrder::new(11, 130, 700, Side::Bid);
+        let order = LimitOrder::new(OrderId(11), Price(130), Quantity(700), Side::Bid);
         book.limit_bid_order(order);
         assert!(book.min_ask.is_none() && book.max_bid == Some(order.price))
     }
@@ -1250,11 +2267,225 @@ mod tests {
     #[test]
     fn it_updates_top_price_when_ask_is_partially_filled_and_bids_are_empty() {
         let mut book = create_orderbook();
-        let order = LimitOrder::new(11, 100, 700, Side::Ask);
+        let order = LimitOrder::new(OrderId(11), Price(100), Quantity(700), Side::Ask);
         book.limit_ask_order(order);
         assert!(book.max_bid.is_none() && book.min_ask == Some(order.price))
     }
 
+    #[test]
+    fn it_keeps_top_price_when_bid_partially_consumes_a_level() {
+        let mut book = create_orderbook();
+        let order = LimitOrder::new(OrderId(11), Price(130), Quantity(100), Side::Bid);
+        book.limit_bid_order(order);
+        assert_eq!(book.min_ask, Some(120));
+    }
+
+    #[test]
+    fn it_keeps_top_price_when_ask_partially_consumes_a_level() {
+        let mut book = create_orderbook();
+        let order = LimitOrder::new(OrderId(11), Price(100), Quantity(100), Side::Ask);
+        book.limit_ask_order(order);
+        assert_eq!(book.max_bid, Some(110));
+    }
+
+    #[test]
+    fn it_updates_top_price_when_market_bid_consumes_a_level() {
+        let mut book = create_orderbook();
+        book.market_bid_order(MarketOrder::new(OrderId(11), Quantity(300), Side::Bid));
+        assert_eq!(book.min_ask, Some(130));
+    }
+
+    #[test]
+    fn it_updates_top_price_when_market_ask_consumes_a_level() {
+        let mut book = create_orderbook();
+        book.market_ask_order(MarketOrder::new(OrderId(11), Quantity(300), Side::Ask));
+        assert_eq!(book.max_bid, Some(100));
+    }
+
+    #[test]
+    fn it_updates_top_price_when_modify_moves_the_last_order_of_a_level() {
+        let mut book = create_orderbook();
+        for id in [6, 7, 8] {
+            book.execute(Operation::Modify(LimitOrder::new(
+                OrderId(id),
+                Price(140),
+                Quantity(100),
+                Side::Ask,
+            )));
+        }
+        assert_eq!(book.min_ask, Some(130));
+    }
+
+    #[test]
+    fn it_skips_consumed_levels_when_cancel_empties_the_top_level() {
+        let mut book = create_orderbook();
+        book.limit_ask_order(LimitOrder::new(
+            OrderId(11),
+            Price(110),
+            Quantity(300),
+            Side::Ask,
+        ));
+        book.execute(Operation::Limit(LimitOrder::new(
+            OrderId(12),
+            Price(105),
+            Quantity(100),
+            Side::Bid,
+        )));
+        book.execute(Operation::Cancel(OrderId(12)));
+        assert_eq!(book.max_bid, Some(100));
+    }
+
+    #[test]
+    fn it_audits_a_consistent_book() {
+        let mut book = create_orderbook();
+        book.execute(Operation::Limit(LimitOrder::new(
+            OrderId(11),
+            Price(120),
+            Quantity(300),
+            Side::Bid,
+        )));
+        book.execute(Operation::Cancel(OrderId(1)));
+        let report = book.audit().unwrap();
+        assert_eq!(
+            report,
+            AuditReport {
+                bid_levels: 2,
+                ask_levels: 1,
+                empty_levels: 1,
+                resting_orders: 6,
+                store_slots: 10000,
+                free_slots: 9994,
+            }
+        );
+    }
+
+    #[test]
+    fn it_reports_memory_stats() {
+        let mut book = OrderBook::new("test".to_string(), 4, 2);
+        for (id, price, side) in [
+            (1, 100, Side::Bid),
+            (2, 100, Side::Bid),
+            (3, 110, Side::Ask),
+        ] {
+            book.execute(Operation::Limit(LimitOrder::new(
+                OrderId(id),
+                Price(price),
+                Quantity(10),
+                side,
+            )));
+        }
+        book.execute(Operation::Market(MarketOrder::new(
+            OrderId(4),
+            Quantity(20),
+            Side::Ask,
+        )));
+        let stats = book.memory_stats();
+        assert_eq!(
+            stats,
+            MemoryStats {
+                store_capacity: 2,
+                store_slots: 3,
+                store_occupancy: 1,
+                peak_store_occupancy: 3,
+                bid_levels: 0,
+                ask_levels: 1,
+                empty_levels: 1,
+                queue_capacity: 4,
+                queue_allocations: stats.queue_allocations,
+                allocated_bytes: stats.allocated_bytes,
+            }
+        );
+        assert!(stats.queue_allocations >= 8);
+        assert!(stats.allocated_bytes >= 3 * size_of::<LimitOrder>());
+    }
+
+    #[test]
+    fn it_compacts_the_book() {
+        let mut book = OrderBook::new("test".to_string(), 2, 2);
+        for (id, price, side) in [
+            (1, 100, Side::Bid),
+            (2, 100, Side::Bid),
+            (3, 100, Side::Bid),
+            (4, 90, Side::Bid),
+            (5, 110, Side::Ask),
+        ] {
+            book.execute(Operation::Limit(LimitOrder::new(
+                OrderId(id),
+                Price(price),
+                Quantity(10),
+                side,
+            )));
+        }
+        book.execute(Operation::Market(MarketOrder::new(
+            OrderId(6),
+            Quantity(20),
+            Side::Ask,
+        )));
+        book.execute(Operation::Market(MarketOrder::new(
+            OrderId(7),
+            Quantity(10),
+            Side::Bid,
+        )));
+        let state_hash = book.state_hash();
+        let report = book.compact();
+        assert_eq!(
+            report,
+            CompactionReport {
+                pruned_levels: 1,
+                shrunk_queues: 1,
+                released_slots: 1,
+                released_bytes: report.released_bytes,
+            }
+        );
+        assert!(report.released_bytes > 0);
+        assert_eq!(book.state_hash(), state_hash);
+        assert_eq!(book.audit().unwrap().empty_levels, 0);
+        assert_eq!(book.order_store.slots(), 4);
+        book.execute(Operation::Limit(LimitOrder::new(
+            OrderId(8),
+            Price(80),
+            Quantity(10),
+            Side::Bid,
+        )));
+        assert_eq!(book.order_store.get(8).unwrap().1, 0);
+        assert_eq!(book.compact(), CompactionReport::default());
+    }
+
+    #[test]
+    fn it_reports_inconsistencies_of_a_corrupted_book() {
+        let mut book = create_orderbook();
+        book.max_bid = Some(90);
+        book.order_store.delete(&6);
+        let index = book.order_store.get(9).unwrap().1;
+        book.ask_side_book.get_mut(&120).unwrap().push_back(index);
+        let inconsistencies = book.audit().unwrap_err();
+        let dangling = book.ask_side_book[&120][0];
+        assert_eq!(inconsistencies.len(), 4);
+        for inconsistency in [
+            Inconsistency::DanglingIndex {
+                side: Side::Ask,
+                price: 120,
+                index: dangling,
+            },
+            Inconsistency::MisplacedOrder {
+                id: 9,
+                side: Side::Ask,
+                price: 120,
+            },
+            Inconsistency::DuplicateIndex { index },
+            Inconsistency::StaleMaxBid {
+                cached: Some(90),
+                actual: Some(110),
+            },
+        ] {
+            assert!(
+                inconsistencies.contains(&inconsistency),
+                "{:?}",
+                inconsistency
+            );
+        }
+    }
+
     #[test]
     fn it_tests_orderbook_depth() {
         let book = create_orderbook();
@@ -1306,9 +2537,9 @@ mod tests {
     fn it_fetches_orderbook_data() {
         let mut book = create_orderbook();
         let orders = vec![
-            LimitOrder::new(11, 115, 200, Side::Bid),
-            LimitOrder::new(12, 118, 300, Side::Ask),
-            LimitOrder::new(13, 314, 300, Side::Ask),
+            LimitOrder::new(OrderId(11), Price(115), Quantity(200), Side::Bid),
+            LimitOrder::new(OrderId(12), Price(118), Quantity(300), Side::Ask),
+            LimitOrder::new(OrderId(13), Price(314), Quantity(300), Side::Ask),
         ];
         for order in orders {
             book.execute(Operation::Limit(order));
@@ -1318,13 +2549,722 @@ mod tests {
         assert_eq!(result.bids.last().unwrap().1, 500)
     }
 
+    #[test]
+    fn it_diffs_consecutive_orderbook_data() {
+        let mut book = create_orderbook();
+        let previous = book.orderbook_data(Granularity::P00);
+        book.execute(Operation::Market(MarketOrder::new(
+            OrderId(11),
+            Quantity(300),
+            Side::Bid,
+        )));
+        book.execute(Operation::Limit(LimitOrder::new(
+            OrderId(12),
+            Price(105),
+            Quantity(10),
+            Side::Bid,
+        )));
+        book.execute(Operation::Cancel(OrderId(5)));
+        let current = book.orderbook_data(Granularity::P00);
+        let diff = current.diff(&previous);
+        assert_eq!(diff.bids, vec![(105, 10), (110, 200)]);
+        assert_eq!(diff.asks, vec![(120, 0)]);
+        let mut updated = previous.clone();
+        updated.apply(&diff);
+        assert_eq!(updated, current);
+        assert!(current.diff(&current).is_empty());
+        let top = current.top(1);
+        assert_eq!(top.bids, vec![(110, 200)]);
+        assert_eq!(top.asks, vec![(130, 300)]);
+    }
+
+    #[test]
+    fn it_gets_resting_order_with_remaining_quantity() {
+        let mut book = create_orderbook();
+        book.execute(Operation::Market(MarketOrder::new(
+            OrderId(11),
+            Quantity(120),
+            Side::Bid,
+        )));
+        assert!(
+            book.get_order(6).is_none()
+                && book.get_order(7)
+                    == Some(LimitOrder::new(
+                        OrderId(7),
+                        Price(120),
+                        Quantity(130),
+                        Side::Ask
+                    ))
+        );
+    }
+
+    #[test]
+    fn it_rebuilds_the_same_book_from_resting_orders() {
+        let mut book = create_orderbook();
+        book.execute(Operation::Market(MarketOrder::new(
+            OrderId(11),
+            Quantity(120),
+            Side::Bid,
+        )));
+        let mut rebuilt = OrderBook::default();
+        for order in book.resting_orders() {
+            rebuilt.execute(Operation::Limit(order));
+        }
+        assert!(
+            rebuilt.resting_orders() == book.resting_orders()
+                && rebuilt.depth(5) == book.depth(5)
+                && rebuilt.get_max_bid() == book.get_max_bid()
+                && rebuilt.get_min_ask() == book.get_min_ask()
+        );
+    }
+
     #[test]
     fn it_updates_last_trade_price() {
         let mut book = create_orderbook();
-        let orders = vec![MarketOrder::new(11, 400, Side::Ask)];
+        let orders = vec![MarketOrder::new(OrderId(11), Quantity(400), Side::Ask)];
         for order in orders {
             book.execute(Operation::Market(order));
         }
         assert_eq!(book.last_trade_price, 100);
     }
+
+    #[test]
+    fn it_collects_expired_orders() {
+        let mut book = create_orderbook();
+        book.execute(Operation::Limit(
+            LimitOrder::new(OrderId(11), Price(90), Quantity(100), Side::Bid).with_expiry(1_000),
+        ));
+        book.execute(Operation::Limit(
+            LimitOrder::new(OrderId(12), Price(140), Quantity(100), Side::Ask).with_expiry(2_000),
+        ));
+        book.execute(Operation::Modify(LimitOrder::new(
+            OrderId(11),
+            Price(95),
+            Quantity(50),
+            Side::Bid,
+        )));
+        assert_eq!(book.get_order(11).unwrap().expires_at, Some(1_000));
+        assert!(book.expired_orders(999).is_empty());
+        assert_eq!(book.expired_orders(1_000), vec![11]);
+        assert_eq!(book.expired_orders(2_000), vec![11, 12]);
+        assert_eq!(book.resting_orders().len(), 12);
+    }
+
+    #[test]
+    fn it_purges_expired_orders_while_matching() {
+        let mut book = OrderBook::default();
+        let resting = [
+            LimitOrder::new(OrderId(1), Price(100), Quantity(10), Side::Ask).with_expiry(1_000),
+            LimitOrder::new(OrderId(2), Price(100), Quantity(10), Side::Ask),
+            LimitOrder::new(OrderId(3), Price(110), Quantity(10), Side::Ask).with_expiry(500),
+            LimitOrder::new(OrderId(4), Price(120), Quantity(10), Side::Ask).with_expiry(5_000),
+        ];
+        for order in resting {
+            book.execute(Operation::Limit(order));
+        }
+        book.set_clock(1_000);
+        let result = book.execute(Operation::Market(MarketOrder::new(
+            OrderId(5),
+            Quantity(20),
+            Side::Bid,
+        )));
+        let matched: Vec<u128> = result.fills().iter().map(|f| f.matched_order_id).collect();
+        assert_eq!(matched, vec![2, 4]);
+        assert_eq!(book.take_expired(), vec![1, 3]);
+        assert!(book.take_expired().is_empty());
+        assert!(book.get_order(1).is_none());
+        assert!(book.get_order(3).is_none());
+        assert!(book.audit().is_ok());
+    }
+
+    #[test]
+    fn it_matches_expired_orders_until_the_clock_is_set() {
+        let mut book = OrderBook::default();
+        book.execute(Operation::Limit(
+            LimitOrder::new(OrderId(1), Price(100), Quantity(10), Side::Bid).with_expiry(1_000),
+        ));
+        let result = book.execute(Operation::Limit(LimitOrder::new(
+            OrderId(2),
+            Price(100),
+            Quantity(10),
+            Side::Ask,
+        )));
+        assert_eq!(result.fills().len(), 1);
+        assert!(book.take_expired().is_empty());
+    }
+
+    #[test]
+    fn it_totals_resting_orders() {
+        let mut book = OrderBook::default();
+        assert_eq!(book.resting_totals(), (0, 0));
+        book.execute(Operation::Limit(LimitOrder::new(
+            OrderId(1),
+            Price(90),
+            Quantity(10),
+            Side::Bid,
+        )));
+        book.execute(Operation::Limit(LimitOrder::new(
+            OrderId(2),
+            Price(110),
+            Quantity(5),
+            Side::Ask,
+        )));
+        assert_eq!(book.resting_totals(), (15, 1_450));
+        book.execute(Operation::Market(MarketOrder::new(
+            OrderId(3),
+            Quantity(2),
+            Side::Bid,
+        )));
+        assert_eq!(book.resting_totals(), (13, 1_230));
+    }
+
+    #[test]
+    fn it_checksums_top_levels() {
+        let mut book = OrderBook::default();
+        book.execute(Operation::Limit(LimitOrder::new(
+            OrderId(1),
+            Price(90),
+            Quantity(10),
+            Side::Bid,
+        )));
+        book.execute(Operation::Limit(LimitOrder::new(
+            OrderId(2),
+            Price(95),
+            Quantity(3),
+            Side::Bid,
+        )));
+        book.execute(Operation::Limit(LimitOrder::new(
+            OrderId(3),
+            Price(110),
+            Quantity(5),
+            Side::Ask,
+        )));
+        let data = book.orderbook_data(Granularity::P00);
+        assert_eq!(data.checksum(25), crc32fast::hash(b"95:3:110:5:90:10"));
+        assert_eq!(data.checksum(1), crc32fast::hash(b"95:3:110:5"));
+        assert_eq!(data.clone().top(1).checksum(25), data.checksum(1));
+        let within = data.clone().within(&(92..=200));
+        assert_eq!(within.checksum(25), crc32fast::hash(b"95:3:110:5"));
+        assert!(data.within(&(96..=100)).bids.is_empty());
+    }
+
+    #[test]
+    fn it_hashes_resting_orders_deterministically() {
+        let mut book = OrderBook::default();
+        let empty = book.state_hash();
+        book.execute(Operation::Limit(LimitOrder::new(
+            OrderId(1),
+            Price(100),
+            Quantity(10),
+            Side::Bid,
+        )));
+        book.execute(Operation::Limit(LimitOrder::new(
+            OrderId(2),
+            Price(100),
+            Quantity(5),
+            Side::Bid,
+        )));
+        book.execute(Operation::Limit(LimitOrder::new(
+            OrderId(3),
+            Price(110),
+            Quantity(5),
+            Side::Ask,
+        )));
+        let hash = book.state_hash();
+        assert_ne!(hash, empty);
+        assert_eq!(book.clone().state_hash(), hash);
+
+        let mut reordered = OrderBook::default();
+        reordered.execute(Operation::Limit(LimitOrder::new(
+            OrderId(2),
+            Price(100),
+            Quantity(5),
+            Side::Bid,
+        )));
+        reordered.execute(Operation::Limit(LimitOrder::new(
+            OrderId(1),
+            Price(100),
+            Quantity(10),
+            Side::Bid,
+        )));
+        reordered.execute(Operation::Limit(LimitOrder::new(
+            OrderId(3),
+            Price(110),
+            Quantity(5),
+            Side::Ask,
+        )));
+        assert_ne!(reordered.state_hash(), hash);
+
+        book.execute(Operation::Cancel(OrderId(3)));
+        assert_ne!(book.state_hash(), hash);
+        book.execute(Operation::Cancel(OrderId(1)));
+        book.execute(Operation::Cancel(OrderId(2)));
+        assert_eq!(book.state_hash(), empty);
+    }
+
+    #[test]
+    fn it_builds_orderbooks_with_options() {
+        let book = OrderBook::builder()
+            .id("BTCUSDT")
+            .tick_size(5)
+            .lot_size(10)
+            .matching_policy(MatchingPolicy::PriceTime)
+            .build()
+            .unwrap();
+        assert_eq!(book.get_id(), "BTCUSDT");
+        assert_eq!(book.get_tick_size(), 5);
+        assert_eq!(book.get_lot_size(), 10);
+        assert_eq!(book.get_matching_policy(), MatchingPolicy::PriceTime);
+        assert_eq!(book.trade_tape().count(), 0);
+
+        assert_eq!(
+            OrderBook::builder().tick_size(0).build().unwrap_err(),
+            ConfigurationError::ZeroTickSize
+        );
+        assert_eq!(
+            OrderBook::builder().lot_size(0).build().unwrap_err(),
+            ConfigurationError::ZeroLotSize
+        );
+    }
+
+    #[test]
+    fn it_reduces_orders_in_place_keeping_priority() {
+        let mut book = OrderBook::default();
+        book.execute(Operation::Limit(LimitOrder::new(
+            OrderId(1),
+            Price(100),
+            Quantity(10),
+            Side::Ask,
+        )));
+        book.execute(Operation::Limit(LimitOrder::new(
+            OrderId(2),
+            Price(100),
+            Quantity(10),
+            Side::Ask,
+        )));
+        match book.execute(Operation::Reduce {
+            id: OrderId(2),
+            quantity_delta: 4,
+        }) {
+            ExecutionResult::Reduced(order) => assert_eq!((order.id, order.quantity), (2, 6)),
+            result => panic!("expected a reduction, got {:?}", result),
+        }
+        for (id, quantity_delta) in [(1, 0), (1, 10), (3, 1)] {
+            assert!(matches!(
+                book.execute(Operation::Reduce {
+                    id: OrderId(id),
+                    quantity_delta
+                }),
+                ExecutionResult::Failed(
+                    OrderBookError::InvalidReduction { .. } | OrderBookError::OrderNotFound
+                )
+            ));
+        }
+        assert!(book.audit().is_ok());
+
+        // the reduced order keeps its place behind the first one at the level
+        let result = book.execute(Operation::Market(MarketOrder::new(
+            OrderId(3),
+            Quantity(12),
+            Side::Bid,
+        )));
+        let fills: Vec<(u128, u64)> = result
+            .fills()
+            .iter()
+            .map(|fill| (fill.matched_order_id, fill.quantity))
+            .collect();
+        assert_eq!(fills, vec![(1, 10), (2, 2)]);
+        assert_eq!(book.get_order(2).map(|order| order.quantity), Some(4));
+    }
+
+    #[test]
+    fn it_replaces_quotes() {
+        let mut book = OrderBook::default();
+        let quote = |bid_price: u64, bid_quantity: u64, ask_price: u64, ask_quantity: u64| {
+            Operation::Quote {
+                bid: LimitOrder::new(
+                    OrderId(1),
+                    Price(bid_price),
+                    Quantity(bid_quantity),
+                    Side::Bid,
+                ),
+                ask: LimitOrder::new(
+                    OrderId(2),
+                    Price(ask_price),
+                    Quantity(ask_quantity),
+                    Side::Ask,
+                ),
+            }
+        };
+        match book.execute(quote(99, 10, 101, 10)) {
+            ExecutionResult::Quoted(result) => {
+                assert!(result.replaced.is_empty());
+                assert_eq!(result.bid.map(|order| order.price), Some(99));
+                assert_eq!(result.ask.map(|order| order.price), Some(101));
+            }
+            result => panic!("expected a quote, got {:?}", result),
+        }
+        book.execute(Operation::Limit(LimitOrder::new(
+            OrderId(3),
+            Price(101),
+            Quantity(10),
+            Side::Ask,
+        )));
+
+        // crossed and malformed quotes leave the resting one alone
+        assert!(matches!(
+            book.execute(quote(101, 10, 101, 10)),
+            ExecutionResult::Failed(OrderBookError::CrossedQuote { .. })
+        ));
+        assert!(matches!(
+            book.execute(Operation::Quote {
+                bid: LimitOrder::new(OrderId(1), Price(99), Quantity(10), Side::Bid),
+                ask: LimitOrder::new(OrderId(1), Price(101), Quantity(10), Side::Ask),
+            }),
+            ExecutionResult::Failed(OrderBookError::InvalidQuote)
+        ));
+        assert_eq!(book.get_order(1).map(|order| order.price), Some(99));
+
+        // a replaced ask loses its place behind the order that joined the level after it
+        match book.execute(quote(100, 5, 101, 10)) {
+            ExecutionResult::Quoted(result) => assert_eq!(result.replaced, vec![1, 2]),
+            result => panic!("expected a quote, got {:?}", result),
+        }
+        let result = book.execute(Operation::Market(MarketOrder::new(
+            OrderId(4),
+            Quantity(10),
+            Side::Bid,
+        )));
+        assert_eq!(result.fills()[0].matched_order_id, 3);
+
+        // a side without quantity is withdrawn, and the bid cannot trade against the replaced ask
+        match book.execute(quote(101, 4, 0, 0)) {
+            ExecutionResult::Quoted(result) => {
+                assert_eq!(result.replaced, vec![1, 2]);
+                assert_eq!(result.bid.map(|order| order.price), Some(101));
+                assert_eq!(result.ask, None);
+                assert!(result.fills.is_empty());
+            }
+            result => panic!("expected a quote, got {:?}", result),
+        }
+        assert_eq!(book.get_order(2), None);
+
+        // an aggressive side trades before resting
+        book.execute(Operation::Limit(LimitOrder::new(
+            OrderId(5),
+            Price(102),
+            Quantity(3),
+            Side::Ask,
+        )));
+        match book.execute(quote(102, 4, 0, 0)) {
+            ExecutionResult::Quoted(result) => {
+                assert_eq!(result.replaced, vec![1]);
+                assert_eq!(result.bid.map(|order| order.quantity), Some(1));
+                assert_eq!(result.fills.len(), 1);
+                assert_eq!(result.fills[0].matched_order_id, 5);
+            }
+            result => panic!("expected a quote, got {:?}", result),
+        }
+        assert!(book.audit().is_ok());
+    }
+
+    #[test]
+    fn it_peeks_at_the_top_orders() {
+        let orders = [
+            LimitOrder::new(OrderId(1), Price(100), Quantity(5), Side::Ask),
+            LimitOrder::new(OrderId(2), Price(100), Quantity(20), Side::Ask),
+            LimitOrder::new(OrderId(3), Price(110), Quantity(10), Side::Ask),
+            LimitOrder::new(OrderId(4), Price(90), Quantity(10), Side::Bid),
+            LimitOrder::new(OrderId(5), Price(100), Quantity(10), Side::Ask).with_expiry(1_000),
+        ];
+        let top_orders = |matching_policy: MatchingPolicy, side: Side, n: usize| {
+            let mut book = OrderBook::builder()
+                .matching_policy(matching_policy)
+                .build()
+                .unwrap();
+            for order in orders {
+                book.execute(Operation::Limit(order));
+            }
+            book.set_clock(2_000);
+            book.top_orders(side, n)
+                .iter()
+                .map(|order| (order.id, order.price, order.quantity))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            top_orders(MatchingPolicy::PriceTime, Side::Ask, 10),
+            vec![(1, 100, 5), (2, 100, 20), (3, 110, 10)]
+        );
+        assert_eq!(
+            top_orders(MatchingPolicy::SizeTime, Side::Ask, 2),
+            vec![(2, 100, 20), (1, 100, 5)]
+        );
+        assert_eq!(
+            top_orders(MatchingPolicy::PriceTime, Side::Bid, 1),
+            vec![(4, 90, 10)]
+        );
+        assert!(top_orders(MatchingPolicy::PriceTime, Side::Ask, 0).is_empty());
+    }
+
+    #[test]
+    fn it_breaks_ties_by_the_matching_policy() {
+        let fills = |matching_policy: MatchingPolicy, quantity: u64| {
+            let mut book = OrderBook::builder()
+                .matching_policy(matching_policy)
+                .build()
+                .unwrap();
+            for (id, quantity) in [(1, 5), (2, 20), (3, 10), (4, 20)] {
+                book.execute(Operation::Limit(LimitOrder::new(
+                    OrderId(id),
+                    Price(100),
+                    Quantity(quantity),
+                    Side::Ask,
+                )));
+            }
+            let result = book.execute(Operation::Market(MarketOrder::new(
+                OrderId(11),
+                Quantity(quantity),
+                Side::Bid,
+            )));
+            assert!(book.audit().is_ok());
+            result
+                .fills()
+                .iter()
+                .map(|fill| (fill.matched_order_id, fill.quantity))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            fills(MatchingPolicy::PriceTime, 30),
+            vec![(1, 5), (2, 20), (3, 5)]
+        );
+        assert_eq!(
+            fills(MatchingPolicy::SizeTime, 45),
+            vec![(2, 20), (4, 20), (3, 5)]
+        );
+
+        let random = MatchingPolicy::Random { seed: 7 };
+        let mut matched: Vec<_> = fills(random, 55).iter().map(|(id, _)| *id).collect();
+        assert_eq!(fills(random, 55), fills(random, 55));
+        matched.sort();
+        assert_eq!(matched, vec![1, 2, 3, 4]);
+        let orders = (0..16).map(|seed| fills(MatchingPolicy::Random { seed }, 55));
+        assert!(orders.collect::<HashSet<_>>().len() > 1);
+    }
+
+    #[test]
+    fn it_rejects_orders_off_tick_and_lot() {
+        let mut book = OrderBook::builder()
+            .tick_size(5)
+            .lot_size(10)
+            .build()
+            .unwrap();
+        assert_eq!(
+            book.execute(Operation::Limit(LimitOrder::new(
+                OrderId(1),
+                Price(102),
+                Quantity(10),
+                Side::Bid
+            ))),
+            ExecutionResult::Failed(OrderBookError::OffTick { tick_size: 5 })
+        );
+        assert_eq!(
+            book.execute(Operation::Limit(LimitOrder::new(
+                OrderId(1),
+                Price(100),
+                Quantity(15),
+                Side::Bid
+            ))),
+            ExecutionResult::Failed(OrderBookError::OffLot { lot_size: 10 })
+        );
+        assert_eq!(
+            book.execute(Operation::Market(MarketOrder::new(
+                OrderId(2),
+                Quantity(15),
+                Side::Ask
+            ))),
+            ExecutionResult::Failed(OrderBookError::OffLot { lot_size: 10 })
+        );
+        book.execute(Operation::Limit(LimitOrder::new(
+            OrderId(1),
+            Price(100),
+            Quantity(20),
+            Side::Bid,
+        )));
+        assert_eq!(
+            book.execute(Operation::Modify(LimitOrder::new(
+                OrderId(1),
+                Price(101),
+                Quantity(20),
+                Side::Bid
+            ))),
+            ExecutionResult::Failed(OrderBookError::OffTick { tick_size: 5 })
+        );
+        assert_eq!(
+            book.execute(Operation::Cancel(OrderId(1))),
+            ExecutionResult::Cancelled(1)
+        );
+    }
+
+    #[test]
+    fn it_keeps_the_latest_fills_on_the_trade_tape() {
+        let mut book = OrderBook::builder().trade_tape(2).build().unwrap();
+        for id in 1..=3 {
+            book.execute(Operation::Limit(LimitOrder::new(
+                OrderId(id),
+                Price(100 + id as u64),
+                Quantity(10),
+                Side::Ask,
+            )));
+        }
+        book.execute(Operation::Market(MarketOrder::new(
+            OrderId(4),
+            Quantity(30),
+            Side::Bid,
+        )));
+        let tape: Vec<u128> = book
+            .trade_tape()
+            .map(|fill| fill.matched_order_id)
+            .collect();
+        assert_eq!(tape, vec![2, 3]);
+        assert_eq!(create_orderbook().trade_tape().count(), 0);
+    }
+
+    #[test]
+    fn it_iterates_levels_from_the_top_of_the_book() {
+        let book = create_orderbook();
+        let bids: Vec<(u64, u64)> = book.iter_bids().map(|l| (l.price, l.quantity)).collect();
+        let asks: Vec<(u64, u64)> = book.iter_asks().map(|l| (l.price, l.quantity)).collect();
+        assert_eq!(bids, vec![(110, 300), (100, 300)]);
+        assert_eq!(asks, vec![(120, 300), (130, 300)]);
+        assert_eq!(OrderBook::default().iter_bids().count(), 0);
+    }
+
+    #[test]
+    fn it_gets_the_best_bid_and_offer() {
+        let book = create_orderbook();
+        let (bid, ask) = book.best_bid_offer();
+        assert_eq!(bid.map(|l| (l.price, l.quantity)), Some((110, 300)));
+        assert_eq!(ask.map(|l| (l.price, l.quantity)), Some((120, 300)));
+        assert_eq!(OrderBook::default().best_bid_offer(), (None, None));
+    }
+
+    #[test]
+    fn it_estimates_the_slippage_curve_of_market_orders() {
+        let book = create_orderbook();
+        let curve = book.slippage_curve(Side::Bid, &[12_000, 60_000, 100, 100_000]);
+        assert_eq!(
+            curve[0],
+            SlippagePoint {
+                notional: 12_000,
+                quantity: 100,
+                average_price: Some(120),
+                worst_price: Some(120),
+                impact_bps: 0,
+                complete: true,
+            }
+        );
+        // 300 at 120 and 184 of the 300 at 130
+        assert_eq!(curve[1].quantity, 484);
+        assert_eq!(curve[1].average_price, Some(59_920 / 484));
+        assert_eq!(curve[1].worst_price, Some(130));
+        assert_eq!(curve[1].impact_bps, 250);
+        assert!(curve[1].complete);
+        assert_eq!((curve[2].quantity, curve[2].average_price), (0, None));
+        assert!(curve[2].complete);
+        assert_eq!(curve[3].quantity, 600);
+        assert!(!curve[3].complete);
+
+        let curve = book.slippage_curve(Side::Ask, &[33_000]);
+        assert_eq!(
+            (curve[0].quantity, curve[0].average_price),
+            (300, Some(110))
+        );
+        assert!(!OrderBook::default().slippage_curve(Side::Ask, &[1])[0].complete);
+    }
+
+    #[test]
+    fn it_uncrosses_a_call_at_the_indicative_price() {
+        let mut book = OrderBook::default();
+        assert_eq!(book.execute(Operation::Call), ExecutionResult::Called);
+        assert!(book.in_call());
+        assert_eq!(
+            book.execute(Operation::Call),
+            ExecutionResult::Failed(OrderBookError::InCall)
+        );
+        assert_eq!(
+            book.execute(Operation::Market(MarketOrder::new(
+                OrderId(9),
+                Quantity(10),
+                Side::Bid,
+            ))),
+            ExecutionResult::Failed(OrderBookError::InCall)
+        );
+        let orders = [
+            LimitOrder::new(OrderId(1), Price(105), Quantity(100), Side::Bid),
+            LimitOrder::new(OrderId(2), Price(100), Quantity(50), Side::Bid),
+            LimitOrder::new(OrderId(3), Price(98), Quantity(80), Side::Ask),
+            LimitOrder::new(OrderId(4), Price(102), Quantity(60), Side::Ask),
+        ];
+        for order in orders {
+            assert_eq!(
+                book.execute(Operation::Limit(order)),
+                ExecutionResult::Executed(FillResult::Created(order))
+            );
+        }
+        // the book crosses while in a call
+        assert!(book.audit().is_ok());
+        // 100 match at 102 and 105 alike, 102 is closer to the last trade price
+        assert_eq!(
+            book.indicative_uncross(),
+            Some(IndicativeUncross {
+                price: 102,
+                quantity: 100,
+                imbalance: 40,
+                imbalance_side: Some(Side::Ask),
+            })
+        );
+
+        let fill = |matched_order_id, quantity| FillMetaData {
+            order_id: 1,
+            matched_order_id,
+            taker_side: Side::Bid,
+            price: 102,
+            quantity,
+        };
+        assert_eq!(
+            book.execute(Operation::Uncross),
+            ExecutionResult::Uncrossed(vec![fill(3, 80), fill(4, 20)])
+        );
+        assert!(!book.in_call());
+        assert_eq!(book.get_last_trade_price(), 102);
+        assert_eq!(book.get_max_bid(), Some(100));
+        assert_eq!(book.get_min_ask(), Some(102));
+        assert_eq!(book.indicative_uncross(), None);
+        assert!(book.audit().is_ok());
+        assert_eq!(
+            book.execute(Operation::Uncross),
+            ExecutionResult::Failed(OrderBookError::NotInCall)
+        );
+    }
+
+    #[test]
+    fn it_iterates_orders_in_matching_order() {
+        let mut book = create_orderbook();
+        let bids: Vec<u128> = book.iter_orders(Side::Bid).map(|o| o.id).collect();
+        let asks: Vec<u128> = book.iter_orders(Side::Ask).map(|o| o.id).collect();
+        assert_eq!(bids, vec![4, 5, 1, 2, 3]);
+        assert_eq!(asks, vec![6, 7, 8, 9, 10]);
+
+        book.execute(Operation::Market(MarketOrder::new(
+            OrderId(11),
+            Quantity(120),
+            Side::Bid,
+        )));
+        let asks: Vec<(u128, u64)> = book
+            .iter_orders(Side::Ask)
+            .map(|o| (o.id, o.quantity))
+            .take(2)
+            .collect();
+        assert_eq!(asks, vec![(7, 130), (8, 50)]);
+    }
 }
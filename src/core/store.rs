@@ -1,5 +1,7 @@
-use super::models::{LimitOrder, Side};
+use super::models::{Inconsistency, LimitOrder, Side};
+use crate::core::types::{OrderId, Price, Quantity};
 use std::collections::HashMap;
+use std::mem::size_of;
 use std::ops::{Index, IndexMut};
 
 #[derive(Debug, Clone)]
@@ -13,6 +15,10 @@ pub struct Store {
     free_indexes: Vec<usize>,
     /// THis map creates a relation between the index on our BTreeMap in the orderbook and the orders vector here.
     order_id_index_map: HashMap<u128, usize>,
+    /// The number of slots pre-allocated on creation.
+    capacity: usize,
+    /// The most orders stored at once.
+    peak_occupancy: usize,
 }
 
 impl Store {
@@ -31,9 +37,11 @@ impl Store {
             orders: Vec::with_capacity(capacity),
             free_indexes: Vec::with_capacity(capacity),
             order_id_index_map: HashMap::with_capacity(capacity),
+            capacity,
+            peak_occupancy: 0,
         };
         for index in 0..capacity {
-            let dummy = LimitOrder::new(0, 0, 0, Side::Bid);
+            let dummy = LimitOrder::new(OrderId(0), Price(0), Quantity(0), Side::Bid);
             store.orders.push(dummy);
             store.free_indexes.push(index);
         }
@@ -81,6 +89,7 @@ impl Store {
     ///
     /// * The index of the stored limit order.
     pub fn insert(&mut self, order: LimitOrder) -> usize {
+        self.peak_occupancy = self.peak_occupancy.max(self.order_id_index_map.len() + 1);
         match self.free_indexes.pop() {
             None => {
                 self.orders.push(order);
@@ -94,6 +103,7 @@ impl Store {
                 existing.quantity = order.quantity;
                 existing.price = order.price;
                 existing.side = order.side;
+                existing.expires_at = order.expires_at;
                 self.order_id_index_map.insert(order.id, index);
                 index
             }
@@ -120,6 +130,109 @@ impl Store {
         }
         false
     }
+
+    /// This method returns the number of slots in the store, both used and free.
+    pub fn slots(&self) -> usize {
+        self.orders.len()
+    }
+
+    /// This method returns the number of slots free to hold new orders.
+    pub fn free_slots(&self) -> usize {
+        self.free_indexes.len()
+    }
+
+    /// This method returns the number of slots pre-allocated on creation.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// This method returns the most orders stored at once since the store was created.
+    pub fn peak_occupancy(&self) -> usize {
+        self.peak_occupancy
+    }
+
+    /// This method estimates the heap memory held by the store in bytes, counting every allocated slot.
+    pub fn allocated_bytes(&self) -> usize {
+        self.orders.capacity() * size_of::<LimitOrder>()
+            + self.free_indexes.capacity() * size_of::<usize>()
+            // hashbrown keeps a control byte next to every bucket
+            + self.order_id_index_map.capacity() * (size_of::<(u128, usize)>() + 1)
+    }
+
+    /// This method releases the free slots at the end of the store beyond its capacity and gives the spare memory back.
+    /// The free list is sorted so that the lowest slots are reused first, which keeps the orders towards the start where they do not hold back the next compaction.
+    ///
+    /// # Returns
+    ///
+    /// * The number of slots released.
+    pub fn compact(&mut self) -> usize {
+        let slots = self.orders.len();
+        self.free_indexes.sort_unstable();
+        while self.orders.len() > self.capacity
+            && self.free_indexes.last() == Some(&(self.orders.len() - 1))
+        {
+            self.free_indexes.pop();
+            self.orders.pop();
+        }
+        // insert pops the free list from its end
+        self.free_indexes.reverse();
+        self.orders.shrink_to(self.capacity);
+        self.free_indexes.shrink_to(self.capacity);
+        self.order_id_index_map.shrink_to(self.capacity);
+        slots - self.orders.len()
+    }
+
+    /// This method iterates over the ids of the stored orders along with their index.
+    pub fn ids(&self) -> impl Iterator<Item = (u128, usize)> + '_ {
+        self.order_id_index_map
+            .iter()
+            .map(|(id, index)| (*id, *index))
+    }
+
+    /// This method checks the id map and the free list against each other.
+    /// Every slot has to be either mapped to the order it holds or free, but never both.
+    ///
+    /// # Returns
+    ///
+    /// * A vector of every [`Inconsistency`] found, empty if the store is consistent.
+    pub fn audit(&self) -> Vec<Inconsistency> {
+        let mut inconsistencies = Vec::new();
+        let mut used = vec![None; self.orders.len()];
+        for (id, index) in self.ids() {
+            match self.orders.get(index) {
+                None => inconsistencies.push(Inconsistency::IndexOutOfBounds { index }),
+                Some(order) => {
+                    if order.id != id {
+                        inconsistencies.push(Inconsistency::IdMismatch {
+                            id,
+                            index,
+                            stored_id: order.id,
+                        });
+                    }
+                    used[index] = Some(id);
+                }
+            }
+        }
+        let mut free = vec![false; self.orders.len()];
+        for index in self.free_indexes.iter().copied() {
+            match free.get_mut(index) {
+                None => inconsistencies.push(Inconsistency::IndexOutOfBounds { index }),
+                Some(true) => inconsistencies.push(Inconsistency::DuplicateFreeSlot { index }),
+                Some(is_free) => {
+                    *is_free = true;
+                    if let Some(id) = used[index] {
+                        inconsistencies.push(Inconsistency::FreeSlotInUse { id, index });
+                    }
+                }
+            }
+        }
+        for (index, (used, free)) in used.iter().zip(free).enumerate() {
+            if used.is_none() && !free {
+                inconsistencies.push(Inconsistency::LeakedSlot { index });
+            }
+        }
+        inconsistencies
+    }
 }
 
 /// [`Index`] trait is implemented to get an immutable reference to the [`LimitOrder`] in the orders vector.
@@ -0,0 +1,131 @@
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+
+/// Implements the conversions and the overflow checked arithmetic shared by [`Price`] and
+/// [`Quantity`].
+macro_rules! unit {
+    ($name:ident) => {
+        impl $name {
+            pub const ZERO: $name = $name(0);
+
+            /// Adds `other`, returning `None` on overflow.
+            pub fn checked_add(self, other: $name) -> Option<$name> {
+                self.0.checked_add(other.0).map($name)
+            }
+
+            /// Subtracts `other`, returning `None` if it is larger.
+            pub fn checked_sub(self, other: $name) -> Option<$name> {
+                self.0.checked_sub(other.0).map($name)
+            }
+
+            pub fn saturating_sub(self, other: $name) -> $name {
+                $name(self.0.saturating_sub(other.0))
+            }
+
+            /// Whether this is a whole number of `increment`s, which is never the case for a zero
+            /// increment.
+            pub fn is_multiple_of(self, increment: u64) -> bool {
+                increment != 0 && self.0 % increment == 0
+            }
+        }
+
+        impl From<u64> for $name {
+            fn from(value: u64) -> Self {
+                $name(value)
+            }
+        }
+
+        impl From<$name> for u64 {
+            fn from(value: $name) -> Self {
+                value.0
+            }
+        }
+
+        impl Display for $name {
+            fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+                Display::fmt(&self.0, f)
+            }
+        }
+    };
+}
+
+/// A price in ticks of the quote asset. Being a distinct type from [`Quantity`] keeps the two
+/// from being swapped when passed side by side, as in
+/// [`LimitOrder::new`](crate::core::models::LimitOrder::new).
+#[derive(
+    Debug, Copy, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[serde(transparent)]
+pub struct Price(pub u64);
+
+/// A quantity in lots of the base asset.
+#[derive(
+    Debug, Copy, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[serde(transparent)]
+pub struct Quantity(pub u64);
+
+unit!(Price);
+unit!(Quantity);
+
+impl Price {
+    /// The value of `quantity` at this price, returning `None` on overflow.
+    pub fn notional(self, quantity: Quantity) -> Option<u64> {
+        self.0.checked_mul(quantity.0)
+    }
+}
+
+/// The 128-bit id of an order, which is able to hold a uuid v4.
+#[derive(
+    Debug, Copy, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[serde(transparent)]
+pub struct OrderId(pub u128);
+
+impl From<u128> for OrderId {
+    fn from(value: u128) -> Self {
+        OrderId(value)
+    }
+}
+
+impl From<OrderId> for u128 {
+    fn from(value: OrderId) -> Self {
+        value.0
+    }
+}
+
+impl Display for OrderId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:032x}", self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::types::{OrderId, Price, Quantity};
+
+    #[test]
+    fn it_guards_arithmetic_against_overflow() {
+        assert_eq!(Quantity(5).checked_add(Quantity(7)), Some(Quantity(12)));
+        assert_eq!(Quantity(u64::MAX).checked_add(Quantity(1)), None);
+        assert_eq!(Price(5).checked_sub(Price(7)), None);
+        assert_eq!(Price(5).saturating_sub(Price(7)), Price::ZERO);
+        assert_eq!(Price(100).notional(Quantity(3)), Some(300));
+        assert_eq!(Price(u64::MAX).notional(Quantity(2)), None);
+    }
+
+    #[test]
+    fn it_checks_increments() {
+        assert!(Price(150).is_multiple_of(50));
+        assert!(!Price(150).is_multiple_of(40));
+        assert!(!Quantity(10).is_multiple_of(0));
+    }
+
+    #[test]
+    fn it_converts_from_and_to_raw_values() {
+        assert_eq!(u64::from(Price::from(42)), 42);
+        assert_eq!(u128::from(OrderId::from(7)), 7);
+        assert_eq!(OrderId(255).to_string(), format!("{:032x}", 255));
+        assert_eq!(Quantity(12).to_string(), "12");
+    }
+}
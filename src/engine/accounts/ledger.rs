@@ -0,0 +1,652 @@
+use crate::core::errors::Rejection;
+use crate::core::models::{ExecutionResult, FillMetaData, MarketOrder, Operation, Side};
+use crate::core::orderbook::OrderBook;
+use crate::engine::accounts::limits::RiskLimits;
+use crate::engine::accounts::positions::Position;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// The balance of an account in a single asset.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Balance {
+    pub total: u64,
+    /// The part of the total committed to resting orders.
+    pub held: u64,
+}
+
+impl Balance {
+    pub fn available(&self) -> u64 {
+        self.total.saturating_sub(self.held)
+    }
+}
+
+/// The assets a symbol trades, quantities are in the base asset and prices in the quote asset.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SymbolAssets {
+    pub base: String,
+    pub quote: String,
+}
+
+impl SymbolAssets {
+    /// Splits a symbol such as `ETH/USD`, `ETH-USD` or `ETHUSD` into its assets. Symbols without a
+    /// separator are expected to end with a three letter quote asset.
+    pub fn from_symbol(symbol: &str) -> Self {
+        let (base, quote) = symbol
+            .split_once(['/', '-'])
+            .unwrap_or_else(|| symbol.split_at(symbol.len().saturating_sub(3)));
+        Self {
+            base: base.to_string(),
+            quote: quote.to_string(),
+        }
+    }
+
+    /// The asset and amount a resting order of the side commits.
+    fn commitment(&self, side: Side, price: u64, quantity: u64) -> (&str, u64) {
+        match side {
+            Side::Bid => (&self.quote, price.saturating_mul(quantity)),
+            Side::Ask => (&self.base, quantity),
+        }
+    }
+}
+
+/// The amount a resting order holds from the balance of its account.
+struct Hold {
+    asset: String,
+    amount: u64,
+}
+
+/// What the operations of a batch checked so far require, by account and asset, and the orders
+/// they may leave resting, by account.
+#[derive(Clone, Default)]
+struct Reservations<'a> {
+    amounts: HashMap<(&'a str, &'a str), u64>,
+    resting: HashMap<&'a str, usize>,
+}
+
+#[derive(Default)]
+struct LedgerState {
+    balances: HashMap<String, HashMap<String, Balance>>,
+    owners: HashMap<u128, String>,
+    holds: HashMap<u128, Hold>,
+    positions: HashMap<String, HashMap<String, Position>>,
+    limits: HashMap<String, RiskLimits>,
+    open_orders: HashMap<String, usize>,
+}
+
+/// Tracks the balances and positions of every account across all symbols.
+/// Resting orders hold the amount they commit, so an account can never commit more than it owns.
+/// Orders are checked against the available balances before they are executed, and fills move
+/// the traded amounts between the accounts of the taker and the maker.
+/// Orders also have to stay within the risk limits of their account.
+#[derive(Default)]
+pub struct Ledger {
+    state: Mutex<LedgerState>,
+    /// Number of fills applied to positions so far.
+    pub position_updates: Arc<AtomicU64>,
+    /// Limits of the accounts that have none of their own.
+    default_limits: RiskLimits,
+}
+
+impl Ledger {
+    pub fn new(default_limits: RiskLimits) -> Self {
+        Self {
+            default_limits,
+            ..Self::default()
+        }
+    }
+
+    /// The risk limits in effect for the account.
+    pub fn limits(&self, account: &str) -> RiskLimits {
+        self.state
+            .lock()
+            .unwrap()
+            .limits
+            .get(account)
+            .copied()
+            .unwrap_or(self.default_limits)
+    }
+
+    /// Replaces the risk limits of the account, which apply to every order checked from then on.
+    pub fn set_limits(&self, account: &str, limits: RiskLimits) {
+        self.state
+            .lock()
+            .unwrap()
+            .limits
+            .insert(account.to_string(), limits);
+    }
+
+    /// The number of orders the account has resting across all symbols.
+    pub fn open_orders(&self, account: &str) -> usize {
+        let state = self.state.lock().unwrap();
+        state.open_orders.get(account).copied().unwrap_or(0)
+    }
+
+    /// Adds the amount to the balance of the account.
+    ///
+    /// # Returns
+    ///
+    /// * The updated balance.
+    pub fn credit(&self, account: &str, asset: &str, amount: u64) -> Result<Balance, String> {
+        let mut state = self.state.lock().unwrap();
+        let balance = state.balance_mut(account, asset);
+        balance.total = balance
+            .total
+            .checked_add(amount)
+            .ok_or_else(|| format!("{} balance overflows", asset))?;
+        Ok(*balance)
+    }
+
+    /// Removes the amount from the available balance of the account.
+    ///
+    /// # Returns
+    ///
+    /// * The updated balance.
+    pub fn debit(&self, account: &str, asset: &str, amount: u64) -> Result<Balance, String> {
+        let mut state = self.state.lock().unwrap();
+        let balance = state.balance_mut(account, asset);
+        if balance.available() < amount {
+            return Err(format!("insufficient {} balance", asset));
+        }
+        balance.total -= amount;
+        Ok(*balance)
+    }
+
+    /// Every balance of the account, sorted by asset.
+    pub fn balances(&self, account: &str) -> Vec<(String, Balance)> {
+        let state = self.state.lock().unwrap();
+        let mut balances: Vec<_> = state
+            .balances
+            .get(account)
+            .map(|balances| balances.iter().map(|(a, b)| (a.clone(), *b)).collect())
+            .unwrap_or_default();
+        balances.sort_by(|(a, _), (b, _)| a.cmp(b));
+        balances
+    }
+
+    /// Every position of the account, sorted by symbol.
+    pub fn positions(&self, account: &str) -> Vec<(String, Position)> {
+        let state = self.state.lock().unwrap();
+        let mut positions: Vec<_> = state
+            .positions
+            .get(account)
+            .map(|positions| positions.iter().map(|(s, p)| (s.clone(), *p)).collect())
+            .unwrap_or_default();
+        positions.sort_by(|(a, _), (b, _)| a.cmp(b));
+        positions
+    }
+
+    /// Records the account an order belongs to, which has to happen before it is executed.
+    pub fn assign(&self, id: u128, account: &str) {
+        self.state
+            .lock()
+            .unwrap()
+            .owners
+            .insert(id, account.to_string());
+    }
+
+    /// Forgets an order that was assigned but never reached the executor.
+    pub fn unassign(&self, id: u128) {
+        self.state.lock().unwrap().owners.remove(&id);
+    }
+
+    pub fn owner(&self, id: u128) -> Option<String> {
+        self.state.lock().unwrap().owners.get(&id).cloned()
+    }
+
+    /// Checks a batch of operations against the risk limits and available balances of their
+    /// accounts before it is executed. Amounts required by accepted operations are reserved for
+    /// the rest of the batch, while proceeds of fills are not counted until the batch is applied.
+    /// Accepted orders that can rest count towards the open orders of their account likewise.
+    ///
+    /// # Returns
+    ///
+    /// * The outcome for every operation of the batch, in order.
+    pub fn check_batch(
+        &self,
+        batch: &[Operation],
+        assets: &SymbolAssets,
+        orderbook: &OrderBook,
+    ) -> Vec<Result<(), Rejection>> {
+        let state = self.state.lock().unwrap();
+        let mut reservations = Reservations::default();
+        batch
+            .iter()
+            .map(|operation| match operation {
+                // each side of a quote is checked as an order of its own, and neither is reserved
+                // unless both pass
+                Operation::Quote { bid, ask } => {
+                    let before = reservations.clone();
+                    let checked = [bid, ask]
+                        .into_iter()
+                        .filter(|order| order.quantity > 0)
+                        .try_for_each(|order| {
+                            let side = match state.holds.contains_key(&order.id) {
+                                true => Operation::Modify(*order),
+                                false => Operation::Limit(*order),
+                            };
+                            self.check(&state, &mut reservations, &side, assets, orderbook)
+                        });
+                    if checked.is_err() {
+                        reservations = before;
+                    }
+                    checked
+                }
+                operation => self.check(&state, &mut reservations, operation, assets, orderbook),
+            })
+            .collect()
+    }
+
+    /// Checks a single order of a batch, reserving what it requires for the rest of the batch.
+    fn check<'a>(
+        &self,
+        state: &'a LedgerState,
+        reservations: &mut Reservations<'a>,
+        operation: &Operation,
+        assets: &'a SymbolAssets,
+        orderbook: &OrderBook,
+    ) -> Result<(), Rejection> {
+        let id = operation.order_id();
+        let (asset, amount) = match requirement(operation, assets, orderbook) {
+            None => return Ok(()),
+            Some(requirement) => requirement,
+        };
+        let account = state.owners.get(&id).ok_or(Rejection::UnassignedOrder)?;
+        let can_rest = !matches!(operation, Operation::Modify(_));
+        let open_orders = can_rest.then(|| {
+            state.open_orders.get(account).copied().unwrap_or(0)
+                + reservations
+                    .resting
+                    .get(account.as_str())
+                    .copied()
+                    .unwrap_or(0)
+                + 1
+        });
+        let (quantity, notional) = size(operation, orderbook);
+        state
+            .limits
+            .get(account)
+            .unwrap_or(&self.default_limits)
+            .check(quantity, notional, open_orders)
+            .map_err(Rejection::AccountLimit)?;
+        let amount = amount.saturating_sub(
+            state
+                .holds
+                .get(&id)
+                .filter(|hold| hold.asset == asset)
+                .map_or(0, |hold| hold.amount),
+        );
+        let reserved = reservations
+            .amounts
+            .entry((account.as_str(), asset))
+            .or_default();
+        let available = state
+            .balances
+            .get(account)
+            .and_then(|balances| balances.get(asset))
+            .map_or(0, |balance| balance.available())
+            .saturating_sub(*reserved);
+        if amount > available {
+            return Err(Rejection::InsufficientBalance {
+                asset: asset.to_string(),
+            });
+        }
+        *reserved += amount;
+        if can_rest {
+            *reservations.resting.entry(account.as_str()).or_default() += 1;
+        }
+        Ok(())
+    }
+
+    /// Applies the outcome of an executed operation: the amounts traded by its fills are moved
+    /// between the accounts of the buyer and the seller, whose positions in the symbol are updated,
+    /// and the holds of every order it touched are updated to what still rests in the book.
+    pub fn apply(
+        &self,
+        operation: &Operation,
+        result: &ExecutionResult,
+        symbol: &str,
+        assets: &SymbolAssets,
+        orderbook: &OrderBook,
+    ) {
+        let mut state = self.state.lock().unwrap();
+        let fills = result.fills();
+        for fill in fills {
+            state.settle(fill, symbol, assets);
+        }
+        if !fills.is_empty() {
+            self.position_updates
+                .fetch_add(fills.len() as u64, Ordering::Relaxed);
+        }
+        let placed = match operation {
+            Operation::Quote { bid, ask } => vec![bid.id, ask.id],
+            // the bids of an uncross are the orders of its fills
+            Operation::Uncross => fills.iter().map(|fill| fill.order_id).collect(),
+            Operation::Call => Vec::new(),
+            operation => vec![operation.order_id()],
+        };
+        let touched = placed
+            .into_iter()
+            .chain(fills.iter().map(|fill| fill.matched_order_id));
+        for id in touched {
+            state.release(id);
+            match orderbook.get_order(id) {
+                Some(order) => {
+                    let (asset, amount) =
+                        assets.commitment(order.side, order.price, order.quantity);
+                    state.hold(id, asset, amount);
+                }
+                None => {
+                    state.owners.remove(&id);
+                }
+            }
+        }
+    }
+}
+
+impl LedgerState {
+    fn balance_mut(&mut self, account: &str, asset: &str) -> &mut Balance {
+        self.balances
+            .entry(account.to_string())
+            .or_default()
+            .entry(asset.to_string())
+            .or_default()
+    }
+
+    fn position_mut(&mut self, account: &str, symbol: &str) -> &mut Position {
+        self.positions
+            .entry(account.to_string())
+            .or_default()
+            .entry(symbol.to_string())
+            .or_default()
+    }
+
+    fn settle(&mut self, fill: &FillMetaData, symbol: &str, assets: &SymbolAssets) {
+        let (buyer, seller) = match fill.taker_side {
+            Side::Bid => (fill.order_id, fill.matched_order_id),
+            Side::Ask => (fill.matched_order_id, fill.order_id),
+        };
+        let notional = fill.price.saturating_mul(fill.quantity);
+        if let Some(buyer) = self.owners.get(&buyer).cloned() {
+            let base = self.balance_mut(&buyer, &assets.base);
+            base.total = base.total.saturating_add(fill.quantity);
+            let quote = self.balance_mut(&buyer, &assets.quote);
+            quote.total = quote.total.saturating_sub(notional);
+            self.position_mut(&buyer, symbol)
+                .buy(fill.quantity, notional);
+        }
+        if let Some(seller) = self.owners.get(&seller).cloned() {
+            let base = self.balance_mut(&seller, &assets.base);
+            base.total = base.total.saturating_sub(fill.quantity);
+            let quote = self.balance_mut(&seller, &assets.quote);
+            quote.total = quote.total.saturating_add(notional);
+            self.position_mut(&seller, symbol)
+                .sell(fill.quantity, notional);
+        }
+    }
+
+    fn hold(&mut self, id: u128, asset: &str, amount: u64) {
+        let Some(account) = self.owners.get(&id).cloned() else {
+            return;
+        };
+        self.balance_mut(&account, asset).held += amount;
+        *self.open_orders.entry(account).or_default() += 1;
+        let asset = asset.to_string();
+        self.holds.insert(id, Hold { asset, amount });
+    }
+
+    fn release(&mut self, id: u128) {
+        let (Some(hold), Some(account)) = (self.holds.remove(&id), self.owners.get(&id).cloned())
+        else {
+            return;
+        };
+        let balance = self.balance_mut(&account, &hold.asset);
+        balance.held = balance.held.saturating_sub(hold.amount);
+        if let Some(open_orders) = self.open_orders.get_mut(&account) {
+            *open_orders = open_orders.saturating_sub(1);
+        }
+    }
+}
+
+/// The asset and amount an operation needs to be available before it is executed.
+/// Market bids are valued at the notional they would sweep, with any remainder valued at the worst
+/// price reached, where it would rest.
+fn requirement<'a>(
+    operation: &Operation,
+    assets: &'a SymbolAssets,
+    orderbook: &OrderBook,
+) -> Option<(&'a str, u64)> {
+    match operation {
+        Operation::Limit(order) | Operation::Modify(order) => {
+            Some(assets.commitment(order.side, order.price, order.quantity))
+        }
+        Operation::Market(order) => match order.side {
+            Side::Bid => Some((&assets.quote, market_notional(order, orderbook))),
+            Side::Ask => Some((&assets.base, order.quantity)),
+        },
+        // quotes are checked side by side
+        Operation::Cancel(_)
+        | Operation::Reduce { .. }
+        | Operation::Quote { .. }
+        | Operation::Call
+        | Operation::Uncross => None,
+    }
+}
+
+/// The quantity and notional value of an order, as checked against the risk limits.
+fn size(operation: &Operation, orderbook: &OrderBook) -> (u64, u64) {
+    match operation {
+        Operation::Limit(order) | Operation::Modify(order) => {
+            (order.quantity, order.price.saturating_mul(order.quantity))
+        }
+        Operation::Market(order) => (order.quantity, market_notional(order, orderbook)),
+        Operation::Cancel(_)
+        | Operation::Reduce { .. }
+        | Operation::Quote { .. }
+        | Operation::Call
+        | Operation::Uncross => (0, 0),
+    }
+}
+
+/// The notional a market order would sweep, with any remainder valued at the worst price reached.
+fn market_notional(order: &MarketOrder, orderbook: &OrderBook) -> u64 {
+    let (filled, notional, worst_price) = orderbook.sweep(order.side, order.quantity);
+    let remainder = worst_price
+        .unwrap_or(0)
+        .saturating_mul(order.quantity - filled);
+    notional.saturating_add(remainder)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::errors::Rejection;
+    use crate::core::models::{LimitOrder, MarketOrder, Operation, Side};
+    use crate::core::orderbook::OrderBook;
+    use crate::core::types::{OrderId, Price, Quantity};
+    use crate::engine::accounts::ledger::{Balance, Ledger, SymbolAssets};
+    use crate::engine::accounts::limits::{RiskLimits, RiskViolation};
+    use crate::engine::accounts::positions::Position;
+    use std::sync::atomic::Ordering;
+
+    fn execute(
+        ledger: &Ledger,
+        book: &mut OrderBook,
+        operation: Operation,
+    ) -> Result<(), Rejection> {
+        let assets = SymbolAssets::from_symbol("ETHUSD");
+        ledger.check_batch(&[operation], &assets, book).remove(0)?;
+        let result = book.execute(operation);
+        ledger.apply(&operation, &result, "ETHUSD", &assets, book);
+        Ok(())
+    }
+
+    #[test]
+    fn it_checks_and_settles_balances() {
+        assert_eq!(
+            SymbolAssets::from_symbol("BTC-USDT"),
+            SymbolAssets {
+                base: "BTC".to_string(),
+                quote: "USDT".to_string()
+            }
+        );
+        let ledger = Ledger::default();
+        let mut book = OrderBook::default();
+        ledger.credit("maker", "ETH", 10).unwrap();
+        ledger.credit("taker", "USD", 1_000).unwrap();
+        assert!(ledger.debit("maker", "ETH", 11).is_err());
+
+        ledger.assign(1, "maker");
+        execute(
+            &ledger,
+            &mut book,
+            Operation::Limit(LimitOrder::new(
+                OrderId(1),
+                Price(100),
+                Quantity(8),
+                Side::Ask,
+            )),
+        )
+        .unwrap();
+        ledger.assign(2, "maker");
+        let oversold = Operation::Limit(LimitOrder::new(
+            OrderId(2),
+            Price(100),
+            Quantity(3),
+            Side::Ask,
+        ));
+        assert_eq!(
+            execute(&ledger, &mut book, oversold),
+            Err(Rejection::InsufficientBalance {
+                asset: "ETH".to_string()
+            })
+        );
+
+        ledger.assign(3, "taker");
+        let too_large = Operation::Market(MarketOrder::new(OrderId(3), Quantity(11), Side::Bid));
+        assert!(execute(&ledger, &mut book, too_large).is_err());
+        execute(
+            &ledger,
+            &mut book,
+            Operation::Market(MarketOrder::new(OrderId(3), Quantity(5), Side::Bid)),
+        )
+        .unwrap();
+        assert_eq!(
+            ledger.balances("maker"),
+            vec![
+                ("ETH".to_string(), Balance { total: 5, held: 3 }),
+                (
+                    "USD".to_string(),
+                    Balance {
+                        total: 500,
+                        held: 0
+                    }
+                )
+            ]
+        );
+        assert_eq!(
+            ledger.balances("taker"),
+            vec![
+                ("ETH".to_string(), Balance { total: 5, held: 0 }),
+                (
+                    "USD".to_string(),
+                    Balance {
+                        total: 500,
+                        held: 0
+                    }
+                )
+            ]
+        );
+
+        assert_eq!(ledger.positions("maker")[0].1.net_quantity, -5);
+        assert_eq!(
+            ledger.positions("taker"),
+            vec![(
+                "ETHUSD".to_string(),
+                Position {
+                    net_quantity: 5,
+                    bought_quantity: 5,
+                    sold_quantity: 0,
+                    bought_notional: 500,
+                    sold_notional: 0
+                }
+            )]
+        );
+        assert_eq!(ledger.position_updates.load(Ordering::Relaxed), 1);
+
+        execute(&ledger, &mut book, Operation::Cancel(OrderId(1))).unwrap();
+        assert_eq!(ledger.balances("maker")[0].1, Balance { total: 5, held: 0 });
+        assert_eq!(ledger.owner(1), None);
+    }
+
+    #[test]
+    fn it_enforces_risk_limits() {
+        let ledger = Ledger::new(RiskLimits::from_raw(10, 0, 0));
+        let mut book = OrderBook::default();
+        ledger.credit("maker", "ETH", 100).unwrap();
+        ledger.assign(1, "maker");
+        let too_large = Operation::Limit(LimitOrder::new(
+            OrderId(1),
+            Price(100),
+            Quantity(11),
+            Side::Ask,
+        ));
+        assert_eq!(
+            execute(&ledger, &mut book, too_large),
+            Err(Rejection::AccountLimit(RiskViolation::OrderQuantity {
+                quantity: 11,
+                limit: 10
+            }))
+        );
+
+        ledger.set_limits("maker", RiskLimits::from_raw(0, 1, 1_000));
+        assert_eq!(ledger.limits("taker"), RiskLimits::from_raw(10, 0, 0));
+        execute(
+            &ledger,
+            &mut book,
+            Operation::Limit(LimitOrder::new(
+                OrderId(1),
+                Price(100),
+                Quantity(10),
+                Side::Ask,
+            )),
+        )
+        .unwrap();
+        assert_eq!(ledger.open_orders("maker"), 1);
+        ledger.assign(2, "maker");
+        let second = Operation::Limit(LimitOrder::new(
+            OrderId(2),
+            Price(100),
+            Quantity(1),
+            Side::Ask,
+        ));
+        assert_eq!(
+            execute(&ledger, &mut book, second),
+            Err(Rejection::AccountLimit(RiskViolation::OpenOrders {
+                limit: 1
+            }))
+        );
+        let expensive = Operation::Modify(LimitOrder::new(
+            OrderId(1),
+            Price(200),
+            Quantity(10),
+            Side::Ask,
+        ));
+        assert!(matches!(
+            execute(&ledger, &mut book, expensive),
+            Err(Rejection::AccountLimit(RiskViolation::Notional { .. }))
+        ));
+        execute(
+            &ledger,
+            &mut book,
+            Operation::Modify(LimitOrder::new(
+                OrderId(1),
+                Price(90),
+                Quantity(10),
+                Side::Ask,
+            )),
+        )
+        .unwrap();
+        execute(&ledger, &mut book, Operation::Cancel(OrderId(1))).unwrap();
+        assert_eq!(ledger.open_orders("maker"), 0);
+    }
+}
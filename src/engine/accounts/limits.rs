@@ -0,0 +1,51 @@
+pub use crate::core::errors::RiskViolation;
+
+/// Caps an account has to stay within, where `None` leaves the dimension unlimited.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct RiskLimits {
+    /// The largest quantity of a single order.
+    pub max_order_quantity: Option<u64>,
+    /// The number of orders the account may have resting across all symbols.
+    pub max_open_orders: Option<usize>,
+    /// The largest notional value of a single order, in the quote asset of its symbol.
+    pub max_notional: Option<u64>,
+}
+
+impl RiskLimits {
+    /// Builds limits from raw values, where zero stands for unlimited.
+    pub fn from_raw(max_order_quantity: u64, max_open_orders: u64, max_notional: u64) -> Self {
+        Self {
+            max_order_quantity: (max_order_quantity > 0).then_some(max_order_quantity),
+            max_open_orders: (max_open_orders > 0).then_some(max_open_orders as usize),
+            max_notional: (max_notional > 0).then_some(max_notional),
+        }
+    }
+
+    /// Checks an order against the limits.
+    ///
+    /// # Arguments
+    ///
+    /// * `quantity` - The quantity of the order.
+    /// * `notional` - The notional value of the order.
+    /// * `open_orders` - The orders the account has resting, including the checked one if it can
+    ///   rest, or `None` for modifications which never add a resting order.
+    pub fn check(
+        &self,
+        quantity: u64,
+        notional: u64,
+        open_orders: Option<usize>,
+    ) -> Result<(), RiskViolation> {
+        if let Some(limit) = self.max_order_quantity.filter(|limit| quantity > *limit) {
+            return Err(RiskViolation::OrderQuantity { quantity, limit });
+        }
+        if let Some(limit) = self.max_notional.filter(|limit| notional > *limit) {
+            return Err(RiskViolation::Notional { notional, limit });
+        }
+        match (self.max_open_orders, open_orders) {
+            (Some(limit), Some(open_orders)) if open_orders > limit => {
+                Err(RiskViolation::OpenOrders { limit })
+            }
+            _ => Ok(()),
+        }
+    }
+}
@@ -0,0 +1,292 @@
+use crate::core::models::{FillMetaData, Side};
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt::{Display, Formatter};
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// The number of levels an account can quote a symbol at, a single quote being at level 0.
+pub const MAX_QUOTE_LEVELS: u32 = 16;
+
+/// The id the quote of an account at a level rests under on one side of a symbol. Every quote of
+/// the account at the level replaces the orders resting under the same ids, which are derived
+/// rather than stored so that they survive restarts.
+pub fn quote_id(symbol: &str, account: &str, level: u32, side: Side) -> u128 {
+    let mut hasher = Sha256::new();
+    hasher.update(b"quote");
+    for part in [symbol, account] {
+        hasher.update((part.len() as u64).to_be_bytes());
+        hasher.update(part.as_bytes());
+    }
+    hasher.update(level.to_be_bytes());
+    hasher.update([side as u8]);
+    let digest = hasher.finalize();
+    u128::from_be_bytes(digest[..16].try_into().unwrap())
+}
+
+/// How much the quotes of an account may trade within a rolling window before they are pulled,
+/// where `None` leaves the dimension unlimited.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ProtectionLimits {
+    pub window: Duration,
+    /// Fills of the quotes of an account within the window.
+    pub max_fills: Option<u64>,
+    /// Quantity the quotes of an account filled within the window.
+    pub max_quantity: Option<u64>,
+}
+
+impl ProtectionLimits {
+    /// Builds limits from raw values, where zero stands for unlimited.
+    pub fn from_raw(window: Duration, max_fills: u64, max_quantity: u64) -> Self {
+        Self {
+            window,
+            max_fills: (max_fills > 0).then_some(max_fills),
+            max_quantity: (max_quantity > 0).then_some(max_quantity),
+        }
+    }
+}
+
+/// The limit the quotes of an account exceeded.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ProtectionBreach {
+    Fills { limit: u64 },
+    Quantity { limit: u64 },
+}
+
+impl ProtectionBreach {
+    /// A stable identifier of the exceeded limit, for clients to match on.
+    pub fn code(&self) -> &'static str {
+        match self {
+            ProtectionBreach::Fills { .. } => "max_fills",
+            ProtectionBreach::Quantity { .. } => "max_quantity",
+        }
+    }
+}
+
+impl Display for ProtectionBreach {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ProtectionBreach::Fills { limit } => {
+                write!(f, "{}: more than {} quote fills", self.code(), limit)
+            }
+            ProtectionBreach::Quantity { limit } => {
+                write!(
+                    f,
+                    "{}: more than {} quoted quantity filled",
+                    self.code(),
+                    limit
+                )
+            }
+        }
+    }
+}
+
+/// The protection of an account tripped, with the fills of its quotes within the window that
+/// tripped it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProtectionTrip {
+    pub account: String,
+    pub breach: ProtectionBreach,
+    pub fills: u64,
+    pub quantity: u64,
+}
+
+#[derive(Default)]
+struct ProtectionState {
+    /// The account every quote id seen so far belongs to.
+    quotes: HashMap<u128, String>,
+    /// The time in nanoseconds since the unix epoch and the quantity of every fill of the quotes
+    /// of an account within the window.
+    fills: HashMap<String, VecDeque<(u128, u64)>>,
+    tripped: Vec<ProtectionTrip>,
+    /// The accounts whose protection tripped, which cannot quote until reset.
+    locked: HashSet<String>,
+}
+
+/// Market maker protection of a symbol, kept by its executor. Counts the fills of the quotes of
+/// every account over a rolling window and trips once they exceed a limit, after which the
+/// executor pulls the quotes the account has left in the symbol before it executes anything else,
+/// and the count starts over. The account cannot quote the symbol again until it is reset.
+pub struct MarketMakerProtection {
+    limits: ProtectionLimits,
+    state: Mutex<ProtectionState>,
+}
+
+impl MarketMakerProtection {
+    pub fn new(limits: ProtectionLimits) -> Self {
+        Self {
+            limits,
+            state: Mutex::new(ProtectionState::default()),
+        }
+    }
+
+    /// Tells the ids the quote of the account rests under, so that their fills are counted.
+    pub fn register(&self, account: &str, ids: [u128; 2]) {
+        let mut state = self.state.lock().unwrap();
+        for id in ids {
+            state.quotes.insert(id, account.to_string());
+        }
+    }
+
+    /// The registered quote ids of the account.
+    pub fn quotes_of(&self, account: &str) -> Vec<u128> {
+        let state = self.state.lock().unwrap();
+        state
+            .quotes
+            .iter()
+            .filter(|(_, owner)| *owner == account)
+            .map(|(id, _)| *id)
+            .collect()
+    }
+
+    /// Counts the fill against the account of every quote it traded, tripping the protection of
+    /// those that exceed a limit with it.
+    ///
+    /// # Arguments
+    ///
+    /// * `now` - The current time in nanoseconds since the unix epoch.
+    pub fn record(&self, fill: &FillMetaData, now: u128) {
+        let mut state = self.state.lock().unwrap();
+        let ProtectionState {
+            quotes,
+            fills,
+            tripped,
+            locked,
+        } = &mut *state;
+        let window_start = now.saturating_sub(self.limits.window.as_nanos());
+        for id in [fill.order_id, fill.matched_order_id] {
+            let Some(account) = quotes.get(&id) else {
+                continue;
+            };
+            let window = fills.entry(account.clone()).or_default();
+            while window.front().is_some_and(|(at, _)| *at < window_start) {
+                window.pop_front();
+            }
+            window.push_back((now, fill.quantity));
+            let count = window.len() as u64;
+            let quantity = window.iter().map(|(_, quantity)| quantity).sum();
+            let breach = match self.limits {
+                ProtectionLimits {
+                    max_fills: Some(limit),
+                    ..
+                } if count > limit => ProtectionBreach::Fills { limit },
+                ProtectionLimits {
+                    max_quantity: Some(limit),
+                    ..
+                } if quantity > limit => ProtectionBreach::Quantity { limit },
+                _ => continue,
+            };
+            // the fills that follow within the operation that tripped it are counted afresh, as
+            // its quotes are only pulled after the operation
+            window.clear();
+            if !locked.insert(account.clone()) {
+                continue;
+            }
+            tripped.push(ProtectionTrip {
+                account: account.clone(),
+                breach,
+                fills: count,
+                quantity,
+            });
+        }
+    }
+
+    /// Whether a protection tripped since last taken.
+    pub fn has_tripped(&self) -> bool {
+        !self.state.lock().unwrap().tripped.is_empty()
+    }
+
+    /// The protections that tripped since last taken.
+    pub fn take_tripped(&self) -> Vec<ProtectionTrip> {
+        std::mem::take(&mut self.state.lock().unwrap().tripped)
+    }
+
+    /// Whether the protection of the account tripped and was not reset since.
+    pub fn is_locked(&self, account: &str) -> bool {
+        self.state.lock().unwrap().locked.contains(account)
+    }
+
+    /// Whether the quote id was registered by an account whose protection tripped and was not
+    /// reset since. The ids are derived from the account, so they stay locked once pulled.
+    pub fn is_locked_quote(&self, id: u128) -> bool {
+        let state = self.state.lock().unwrap();
+        state
+            .quotes
+            .get(&id)
+            .is_some_and(|account| state.locked.contains(account))
+    }
+
+    /// Lets the account quote again once its protection tripped.
+    ///
+    /// # Returns
+    ///
+    /// * Whether the account was locked.
+    pub fn reset(&self, account: &str) -> bool {
+        self.state.lock().unwrap().locked.remove(account)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::models::{FillMetaData, Side};
+    use crate::engine::accounts::market_making::{
+        quote_id, MarketMakerProtection, ProtectionBreach, ProtectionLimits,
+    };
+    use std::time::Duration;
+
+    #[test]
+    fn it_derives_distinct_quote_ids() {
+        let bid = quote_id("BTCUSDT", "maker", 0, Side::Bid);
+        assert_eq!(bid, quote_id("BTCUSDT", "maker", 0, Side::Bid));
+        assert_ne!(bid, quote_id("BTCUSDT", "maker", 0, Side::Ask));
+        assert_ne!(bid, quote_id("BTCUSDT", "maker", 1, Side::Bid));
+        assert_ne!(bid, quote_id("ETHUSDT", "maker", 0, Side::Bid));
+        assert_ne!(bid, quote_id("BTCUSDT", "taker", 0, Side::Bid));
+        // lengths are hashed along, so the parts cannot run into each other
+        assert_ne!(
+            quote_id("AB", "C", 0, Side::Bid),
+            quote_id("A", "BC", 0, Side::Bid)
+        );
+    }
+
+    #[test]
+    fn it_trips_once_quote_fills_exceed_a_limit() {
+        let limits = ProtectionLimits::from_raw(Duration::from_nanos(100), 2, 25);
+        let protection = MarketMakerProtection::new(limits);
+        protection.register("maker", [1, 2]);
+        let fill = |matched_order_id: u128, quantity: u64| FillMetaData {
+            order_id: 9,
+            matched_order_id,
+            taker_side: Side::Bid,
+            price: 100,
+            quantity,
+        };
+        // fills of other orders, and those that left the window, do not count
+        protection.record(&fill(3, 10), 0);
+        protection.record(&fill(1, 10), 0);
+        protection.record(&fill(2, 10), 200);
+        protection.record(&fill(1, 10), 250);
+        assert!(protection.take_tripped().is_empty());
+        protection.record(&fill(2, 10), 300);
+        assert!(protection.has_tripped());
+        let tripped = protection.take_tripped();
+        assert_eq!(tripped.len(), 1);
+        assert_eq!(tripped[0].account, "maker");
+        assert_eq!(tripped[0].breach, ProtectionBreach::Fills { limit: 2 });
+        assert_eq!((tripped[0].fills, tripped[0].quantity), (3, 30));
+        assert!(protection.is_locked("maker"));
+        assert!(protection.is_locked_quote(1) && !protection.is_locked_quote(3));
+        assert!(protection.reset("maker"));
+        assert!(!protection.is_locked("maker"));
+        assert!(!protection.reset("maker"));
+
+        // the count starts over once tripped
+        protection.record(&fill(1, 30), 310);
+        let tripped = protection.take_tripped();
+        assert_eq!(tripped[0].breach, ProtectionBreach::Quantity { limit: 25 });
+        assert!(protection.is_locked("maker"));
+        let mut quotes = protection.quotes_of("maker");
+        quotes.sort();
+        assert_eq!(quotes, vec![1, 2]);
+    }
+}
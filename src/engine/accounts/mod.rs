@@ -0,0 +1,6 @@
+pub mod ledger;
+pub mod limits;
+pub mod market_making;
+pub mod positions;
+pub mod settlement;
+pub mod throttle;
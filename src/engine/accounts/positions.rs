@@ -0,0 +1,34 @@
+/// The net position of an account in a single symbol, built up from its fills.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Position {
+    /// Bought minus sold quantity, positive when long and negative when short.
+    pub net_quantity: i64,
+    pub bought_quantity: u64,
+    pub sold_quantity: u64,
+    pub bought_notional: u64,
+    pub sold_notional: u64,
+}
+
+impl Position {
+    pub fn buy(&mut self, quantity: u64, notional: u64) {
+        self.bought_quantity = self.bought_quantity.saturating_add(quantity);
+        self.bought_notional = self.bought_notional.saturating_add(notional);
+        self.net_quantity = self.net_quantity.saturating_add_unsigned(quantity);
+    }
+
+    pub fn sell(&mut self, quantity: u64, notional: u64) {
+        self.sold_quantity = self.sold_quantity.saturating_add(quantity);
+        self.sold_notional = self.sold_notional.saturating_add(notional);
+        self.net_quantity = self.net_quantity.saturating_sub_unsigned(quantity);
+    }
+
+    /// The average price paid per unit bought, `None` before the first buy.
+    pub fn average_buy_price(&self) -> Option<u64> {
+        self.bought_notional.checked_div(self.bought_quantity)
+    }
+
+    /// The average price received per unit sold, `None` before the first sell.
+    pub fn average_sell_price(&self) -> Option<u64> {
+        self.sold_notional.checked_div(self.sold_quantity)
+    }
+}
@@ -0,0 +1,104 @@
+use crate::core::models::{FillMetaData, Side};
+
+/// Fees charged on the notional of every fill, in basis points.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct FeeSchedule {
+    pub maker_fee_bps: u64,
+    pub taker_fee_bps: u64,
+}
+
+impl FeeSchedule {
+    fn fee(bps: u64, notional: u64) -> u64 {
+        (notional as u128 * bps as u128 / 10_000) as u64
+    }
+}
+
+/// Where settlement instructions are published and the fees they carry.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SettlementSettings {
+    pub kafka_topic: String,
+    pub fee_schedule: FeeSchedule,
+}
+
+/// The obligations a single fill creates between the buyer and the seller: the buyer owes the
+/// notional to the seller, who owes the quantity in return, and both owe their fees.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SettlementInstruction {
+    pub buy_order_id: u128,
+    pub sell_order_id: u128,
+    /// The accounts of the orders, empty for orders without one.
+    pub buyer: String,
+    pub seller: String,
+    pub price: u64,
+    pub quantity: u64,
+    pub notional: u64,
+    pub buyer_fee: u64,
+    pub seller_fee: u64,
+    pub taker_side: Side,
+}
+
+impl SettlementInstruction {
+    /// Builds the instruction of a fill, looking up the accounts of both orders with `owner`.
+    pub fn from_fill(
+        fill: &FillMetaData,
+        fee_schedule: &FeeSchedule,
+        owner: impl Fn(u128) -> Option<String>,
+    ) -> Self {
+        let notional = fill.price.saturating_mul(fill.quantity);
+        let taker_fee = FeeSchedule::fee(fee_schedule.taker_fee_bps, notional);
+        let maker_fee = FeeSchedule::fee(fee_schedule.maker_fee_bps, notional);
+        let (buy_order_id, sell_order_id, buyer_fee, seller_fee) = match fill.taker_side {
+            Side::Bid => (fill.order_id, fill.matched_order_id, taker_fee, maker_fee),
+            Side::Ask => (fill.matched_order_id, fill.order_id, maker_fee, taker_fee),
+        };
+        Self {
+            buy_order_id,
+            sell_order_id,
+            buyer: owner(buy_order_id).unwrap_or_default(),
+            seller: owner(sell_order_id).unwrap_or_default(),
+            price: fill.price,
+            quantity: fill.quantity,
+            notional,
+            buyer_fee,
+            seller_fee,
+            taker_side: fill.taker_side,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::models::{FillMetaData, Side};
+    use crate::engine::accounts::settlement::{FeeSchedule, SettlementInstruction};
+
+    #[test]
+    fn it_builds_settlement_instructions_from_fills() {
+        let fill = FillMetaData {
+            order_id: 2,
+            matched_order_id: 1,
+            taker_side: Side::Ask,
+            price: 250,
+            quantity: 40,
+        };
+        let fee_schedule = FeeSchedule {
+            maker_fee_bps: 10,
+            taker_fee_bps: 25,
+        };
+        let owner = |id: u128| (id == 1).then(|| "maker".to_string());
+        assert_eq!(
+            SettlementInstruction::from_fill(&fill, &fee_schedule, owner),
+            SettlementInstruction {
+                buy_order_id: 1,
+                sell_order_id: 2,
+                buyer: "maker".to_string(),
+                seller: String::new(),
+                price: 250,
+                quantity: 40,
+                notional: 10_000,
+                buyer_fee: 10,
+                seller_fee: 25,
+                taker_side: Side::Ask,
+            }
+        );
+    }
+}
@@ -0,0 +1,221 @@
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use std::sync::Mutex;
+use std::time::Duration;
+
+const NANOS_PER_SECOND: u128 = 1_000_000_000;
+/// Length of the window the message to trade ratio is measured over.
+const RATIO_WINDOW_SECONDS: u128 = 60;
+
+/// What happens to an order that exceeds the order rate of its account.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ThrottleMode {
+    /// The order is rejected right away.
+    Reject,
+    /// The order is held back until the next second and rejected if the rate is still exceeded.
+    Delay,
+}
+
+impl FromStr for ThrottleMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "reject" => Ok(ThrottleMode::Reject),
+            "delay" => Ok(ThrottleMode::Delay),
+            _ => Err(format!("unknown throttle mode: {}", s)),
+        }
+    }
+}
+
+/// Flow limits every account has to stay within, where `None` leaves the dimension unlimited.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ThrottleLimits {
+    /// Orders, modifications and cancels an account may send within a second.
+    pub max_orders_per_second: Option<u32>,
+    /// Messages an account may send for each of its trades within a minute. Trades are
+    /// attributed to accounts by the ledger, so the ratio is only enforced with the ledger enabled.
+    pub max_message_to_trade_ratio: Option<u32>,
+    pub mode: ThrottleMode,
+}
+
+impl ThrottleLimits {
+    /// Builds limits from raw values, where zero stands for unlimited.
+    pub fn from_raw(
+        max_orders_per_second: u32,
+        max_message_to_trade_ratio: u32,
+        mode: ThrottleMode,
+    ) -> Self {
+        Self {
+            max_orders_per_second: (max_orders_per_second > 0).then_some(max_orders_per_second),
+            max_message_to_trade_ratio: (max_message_to_trade_ratio > 0)
+                .then_some(max_message_to_trade_ratio),
+            mode,
+        }
+    }
+}
+
+/// The limit an account exceeded.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ThrottleViolation {
+    OrderRate { limit: u32, retry_after: Duration },
+    MessageToTradeRatio { limit: u32 },
+}
+
+impl ThrottleViolation {
+    /// A stable identifier of the exceeded limit, for clients to match on.
+    pub fn code(&self) -> &'static str {
+        match self {
+            ThrottleViolation::OrderRate { .. } => "max_orders_per_second",
+            ThrottleViolation::MessageToTradeRatio { .. } => "max_message_to_trade_ratio",
+        }
+    }
+}
+
+impl Display for ThrottleViolation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ThrottleViolation::OrderRate { limit, .. } => {
+                write!(f, "{}: more than {} orders per second", self.code(), limit)
+            }
+            ThrottleViolation::MessageToTradeRatio { limit } => {
+                write!(f, "{}: more than {} messages per trade", self.code(), limit)
+            }
+        }
+    }
+}
+
+/// A message the throttle refused. Only the first refusal of an account within a second is
+/// reported, so that a flood of excess messages does not turn into a flood of events.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Throttled {
+    pub violation: ThrottleViolation,
+    pub report: bool,
+}
+
+#[derive(Default)]
+struct AccountFlow {
+    second: u128,
+    messages_in_second: u32,
+    reported_second: Option<u128>,
+    ratio_window: u128,
+    messages_in_window: u64,
+    trades_in_window: u64,
+}
+
+/// Counts the messages and trades of every account to hold back those that send too much flow.
+pub struct Throttle {
+    limits: ThrottleLimits,
+    flows: Mutex<HashMap<String, AccountFlow>>,
+}
+
+impl Throttle {
+    pub fn new(limits: ThrottleLimits) -> Self {
+        Self {
+            limits,
+            flows: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn limits(&self) -> ThrottleLimits {
+        self.limits
+    }
+
+    /// Counts a message of the account unless it exceeds a limit. Cancels are always admitted so
+    /// that accounts can reduce their exposure, but they still count towards the limits.
+    ///
+    /// # Arguments
+    ///
+    /// * `now` - The current time in nanoseconds since the unix epoch.
+    pub fn admit(&self, account: &str, is_cancel: bool, now: u128) -> Result<(), Throttled> {
+        let mut flows = self.flows.lock().unwrap();
+        let flow = flows.entry(account.to_string()).or_default();
+        let second = now / NANOS_PER_SECOND;
+        if flow.second != second {
+            flow.second = second;
+            flow.messages_in_second = 0;
+        }
+        let ratio_window = second / RATIO_WINDOW_SECONDS;
+        if flow.ratio_window != ratio_window {
+            flow.ratio_window = ratio_window;
+            flow.messages_in_window = 0;
+            flow.trades_in_window = 0;
+        }
+        let violation = match self.limits {
+            _ if is_cancel => None,
+            ThrottleLimits {
+                max_orders_per_second: Some(limit),
+                ..
+            } if flow.messages_in_second >= limit => Some(ThrottleViolation::OrderRate {
+                limit,
+                retry_after: Duration::from_nanos(
+                    (NANOS_PER_SECOND - now % NANOS_PER_SECOND) as u64,
+                ),
+            }),
+            ThrottleLimits {
+                max_message_to_trade_ratio: Some(limit),
+                ..
+            } if flow.messages_in_window >= limit as u64 * flow.trades_in_window.max(1) => {
+                Some(ThrottleViolation::MessageToTradeRatio { limit })
+            }
+            _ => None,
+        };
+        if let Some(violation) = violation {
+            let report = flow.reported_second != Some(second);
+            flow.reported_second = Some(second);
+            return Err(Throttled { violation, report });
+        }
+        flow.messages_in_second += 1;
+        flow.messages_in_window += 1;
+        Ok(())
+    }
+
+    /// Counts a trade of the account towards its message to trade ratio.
+    pub fn record_trade(&self, account: &str) {
+        if let Some(flow) = self.flows.lock().unwrap().get_mut(account) {
+            flow.trades_in_window += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::engine::accounts::throttle::{
+        Throttle, ThrottleLimits, ThrottleMode, ThrottleViolation, Throttled,
+    };
+    use std::time::Duration;
+
+    const SECOND: u128 = 1_000_000_000;
+
+    #[test]
+    fn it_throttles_order_rate_and_message_to_trade_ratio() {
+        let throttle = Throttle::new(ThrottleLimits::from_raw(2, 3, ThrottleMode::Reject));
+        let now = 1_000 * 60 * SECOND;
+        assert!(throttle.admit("a", false, now).is_ok());
+        assert!(throttle.admit("a", false, now).is_ok());
+        assert_eq!(
+            throttle.admit("a", false, now + SECOND / 4),
+            Err(Throttled {
+                violation: ThrottleViolation::OrderRate {
+                    limit: 2,
+                    retry_after: Duration::from_millis(750)
+                },
+                report: true
+            })
+        );
+        assert!(!throttle.admit("a", false, now).unwrap_err().report);
+        assert!(throttle.admit("a", true, now).is_ok());
+        assert!(throttle.admit("b", false, now).is_ok());
+
+        let next = now + SECOND;
+        assert_eq!(
+            throttle.admit("a", false, next).unwrap_err().violation,
+            ThrottleViolation::MessageToTradeRatio { limit: 3 }
+        );
+        throttle.record_trade("a");
+        throttle.record_trade("a");
+        assert!(throttle.admit("a", false, next + SECOND).is_ok());
+        assert!(throttle.admit("a", false, now + 60 * SECOND).is_ok());
+    }
+}
@@ -1,5 +1,6 @@
 use crate::engine::configuration::kafka_configuration::KafkaConfiguration;
 use crate::engine::configuration::log_configuration::LogConfiguration;
+use crate::engine::configuration::persistence_configuration::PersistenceConfiguration;
 use crate::engine::configuration::server_configuration::ServerConfiguration;
 use crate::engine::constants::property_loader::EnvironmentProperties;
 use std::error::Error;
@@ -10,6 +11,7 @@ pub struct ConfigurationLoader {
     pub server_configuration: Arc<ServerConfiguration>,
     pub log_configuration: Arc<LogConfiguration>,
     pub kafka_configuration: Arc<KafkaConfiguration>,
+    pub persistence_configuration: Arc<PersistenceConfiguration>,
 }
 
 impl ConfigurationLoader {
@@ -20,6 +22,7 @@ impl ConfigurationLoader {
             kafka_admin_properties,
             kafka_producer_properties,
             log_properties,
+            persistence_properties,
         } = EnvironmentProperties::load()?;
 
         info!("successfully loaded environment properties for orderbook");
@@ -28,7 +31,7 @@ impl ConfigurationLoader {
         let server_configuration = Arc::new(ServerConfiguration::load(server_properties));
 
         // log configuration
-        let log_configuration = Arc::new(LogConfiguration::load(log_properties));
+        let log_configuration = Arc::new(LogConfiguration::load(log_properties)?);
 
         // kafka configuration & producer
         let kafka_configuration = Arc::new(KafkaConfiguration {
@@ -36,10 +39,15 @@ impl ConfigurationLoader {
             kafka_producer_properties,
         });
 
+        // persistence configuration
+        let persistence_configuration =
+            Arc::new(PersistenceConfiguration::load(persistence_properties));
+
         Ok(ConfigurationLoader {
             server_configuration,
             log_configuration,
             kafka_configuration,
+            persistence_configuration,
         })
     }
 }
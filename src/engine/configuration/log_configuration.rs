@@ -1,6 +1,9 @@
-use crate::engine::constants::property_loader::LogProperties;
+use crate::engine::constants::property_loader::{LogFormat, LogProperties};
+use std::error::Error;
 use tracing_appender::non_blocking::WorkerGuard;
 use tracing_appender::rolling::{RollingFileAppender, Rotation};
+use tracing_subscriber::fmt::writer::BoxMakeWriter;
+use tracing_subscriber::EnvFilter;
 
 pub struct LogConfiguration {
     pub log_properties: LogProperties,
@@ -8,26 +11,35 @@ pub struct LogConfiguration {
 }
 
 impl LogConfiguration {
-    pub fn load(log_properties: LogProperties) -> LogConfiguration {
+    pub fn load(log_properties: LogProperties) -> Result<LogConfiguration, Box<dyn Error>> {
+        // RUST_LOG takes precedence so that levels can be raised for a single run
+        let filter = match std::env::var(EnvFilter::DEFAULT_ENV) {
+            Ok(directives) => EnvFilter::try_new(directives)?,
+            Err(_) => EnvFilter::try_new(&log_properties.log_filter)?,
+        };
         let mut worker_guard = None;
-        if log_properties.enable_file_log {
+        let writer = if log_properties.enable_file_log {
             let file_appender = RollingFileAppender::new(Rotation::DAILY, "log", "gemmy.log");
             let (file_writer, guard) = tracing_appender::non_blocking(file_appender);
-            tracing_subscriber::fmt()
-                .with_ansi(false)
-                .with_max_level(tracing::Level::INFO)
-                .with_writer(file_writer)
-                .init();
             worker_guard = Some(guard);
+            BoxMakeWriter::new(file_writer)
         } else {
-            tracing_subscriber::fmt()
-                .with_ansi(true)
-                .with_max_level(tracing::Level::INFO)
-                .init();
+            BoxMakeWriter::new(std::io::stdout)
+        };
+        let builder = tracing_subscriber::fmt()
+            .with_env_filter(filter)
+            .with_writer(writer);
+        match log_properties.log_format {
+            LogFormat::Text => builder.with_ansi(!log_properties.enable_file_log).init(),
+            LogFormat::Json => builder
+                .json()
+                .with_current_span(true)
+                .with_span_list(false)
+                .init(),
         }
-        LogConfiguration {
+        Ok(LogConfiguration {
             log_properties,
             worker_guard,
-        }
+        })
     }
 }
@@ -1,4 +1,5 @@
 pub mod configuration_loader;
 pub mod kafka_configuration;
 pub mod log_configuration;
+pub mod persistence_configuration;
 pub mod server_configuration;
@@ -0,0 +1,13 @@
+use crate::engine::constants::property_loader::PersistenceProperties;
+
+pub struct PersistenceConfiguration {
+    pub persistence_properties: PersistenceProperties,
+}
+
+impl PersistenceConfiguration {
+    pub fn load(persistence_properties: PersistenceProperties) -> PersistenceConfiguration {
+        PersistenceConfiguration {
+            persistence_properties,
+        }
+    }
+}
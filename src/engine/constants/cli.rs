@@ -0,0 +1,139 @@
+use clap::{Args, Parser, Subcommand};
+use std::error::Error;
+use std::net::SocketAddr;
+use std::path::PathBuf;
+
+/// Command line arguments of the server.
+/// Every argument overrides its environment property, which in turn overrides the config file.
+#[derive(Debug, Parser)]
+#[command(name = "gemmy", version, about = "A high performance orderbook server")]
+pub struct Cli {
+    /// Env file to load the configuration from, loaded before `.env`.
+    #[arg(long, value_name = "PATH")]
+    pub config: Option<PathBuf>,
+    /// Address the gRPC server listens on, overrides `GRPC_SOCKET_ADDRESS`.
+    #[arg(long, value_name = "ADDRESS")]
+    pub socket_address: Option<SocketAddr>,
+    /// Symbol to trade, can be repeated, overrides `TICKERS`.
+    #[arg(long = "symbol", value_name = "SYMBOL")]
+    pub symbols: Vec<String>,
+    /// Log filter directives such as `info,gemmy::engine::tasks=debug`, overrides `RUST_LOG`.
+    #[arg(long, value_name = "DIRECTIVES")]
+    pub log_level: Option<String>,
+    /// Runs without kafka, the schema registry and the snapshot store, logging events instead of
+    /// publishing them, overrides `STANDALONE_ENABLED`.
+    #[arg(long)]
+    pub standalone: bool,
+    /// File a standalone server appends its events to instead of logging them, overrides
+    /// `STANDALONE_EVENT_PATH`.
+    #[arg(long, value_name = "PATH", requires = "standalone")]
+    pub event_path: Option<PathBuf>,
+    /// Runs without kafka and the schema registry, counting events instead of publishing them,
+    /// overrides `DRY_RUN_ENABLED`.
+    #[arg(long)]
+    pub dry_run: bool,
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Debug, Subcommand, PartialEq)]
+pub enum Command {
+    /// Loads and validates the configuration, then exits.
+    ValidateConfig,
+    /// Verifies the audit log and prints the records matching the filters, then exits.
+    Audit(AuditArgs),
+}
+
+#[derive(Debug, Args, PartialEq)]
+pub struct AuditArgs {
+    /// Directory of the audit log.
+    #[arg(long, value_name = "PATH", default_value = "audit")]
+    pub directory: PathBuf,
+    /// Only prints records of the principal.
+    #[arg(long)]
+    pub principal: Option<String>,
+    /// Only prints records of the method.
+    #[arg(long)]
+    pub method: Option<String>,
+    /// Only prints records from this sequence number on.
+    #[arg(long, value_name = "SEQUENCE", default_value_t = 0)]
+    pub from: u64,
+}
+
+impl Cli {
+    /// Loads the config file and applies the arguments on top of the environment, so that the
+    /// regular property loading picks them up.
+    pub fn apply(&self) -> Result<(), Box<dyn Error>> {
+        if let Some(config) = &self.config {
+            dotenv::from_path(config)?;
+            std::env::set_var("CONFIG_PATH", config);
+        }
+        if let Some(socket_address) = &self.socket_address {
+            std::env::set_var("GRPC_SOCKET_ADDRESS", socket_address.to_string());
+        }
+        if !self.symbols.is_empty() {
+            std::env::set_var("TICKERS", self.symbols.join(","));
+        }
+        if let Some(log_level) = &self.log_level {
+            std::env::set_var("RUST_LOG", log_level);
+        }
+        if self.standalone {
+            std::env::set_var("STANDALONE_ENABLED", "true");
+            std::env::set_var("SNAPSHOT_STORE_ENABLED", "false");
+        }
+        if let Some(event_path) = &self.event_path {
+            std::env::set_var("STANDALONE_EVENT_PATH", event_path);
+        }
+        if self.dry_run {
+            std::env::set_var("DRY_RUN_ENABLED", "true");
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::engine::constants::cli::{AuditArgs, Cli, Command};
+    use clap::Parser;
+
+    #[test]
+    fn it_parses_overrides_and_subcommands() {
+        let cli = Cli::try_parse_from([
+            "gemmy",
+            "--socket-address",
+            "127.0.0.1:50051",
+            "--symbol",
+            "ETHUSD",
+            "--symbol",
+            "BTCUSD",
+            "--dry-run",
+            "validate-config",
+        ])
+        .unwrap();
+        assert_eq!(cli.socket_address, Some("127.0.0.1:50051".parse().unwrap()));
+        assert_eq!(cli.symbols, vec!["ETHUSD", "BTCUSD"]);
+        assert!(cli.dry_run);
+        assert_eq!(cli.command, Some(Command::ValidateConfig));
+        assert!(Cli::try_parse_from(["gemmy", "--socket-address", "localhost"]).is_err());
+
+        let cli =
+            Cli::try_parse_from(["gemmy", "--standalone", "--event-path", "events.tsv"]).unwrap();
+        assert!(cli.standalone && !cli.dry_run);
+        assert_eq!(cli.event_path, Some("events.tsv".into()));
+        assert!(!Cli::try_parse_from(["gemmy"]).unwrap().standalone);
+        // events are only written to a file when running standalone
+        assert!(Cli::try_parse_from(["gemmy", "--event-path", "events.tsv"]).is_err());
+
+        let cli = Cli::try_parse_from(["gemmy", "audit", "--principal", "trader", "--from", "5"])
+            .unwrap();
+        assert_eq!(
+            cli.command,
+            Some(Command::Audit(AuditArgs {
+                directory: "audit".into(),
+                principal: Some("trader".to_string()),
+                method: None,
+                from: 5,
+            }))
+        );
+    }
+}
@@ -1,26 +1,259 @@
+use crate::core::models::MatchingPolicy;
+use crate::engine::accounts::limits::RiskLimits;
+use crate::engine::accounts::market_making::ProtectionLimits;
+use crate::engine::accounts::settlement::FeeSchedule;
+use crate::engine::accounts::throttle::ThrottleLimits;
+use crate::engine::risk::resting_limits::RestingLimits;
 use dotenv::dotenv;
 use schema_registry_converter::async_impl::schema_registry::SrSettings;
+use std::collections::HashMap;
 use std::error::Error;
 use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::str::FromStr;
 use std::sync::Arc;
 use std::time::Duration;
 
 pub struct ServerProperties {
     pub socket_address: SocketAddr,
+    /// Address of the REST gateway, which is not started when not set.
+    pub http_socket_address: Option<SocketAddr>,
+    /// Address of the binary order entry gateway, which is not started when not set.
+    pub binary_socket_address: Option<SocketAddr>,
+    /// Address of the plain HTTP health endpoints, which are not served when not set.
+    pub health_socket_address: Option<SocketAddr>,
+    pub health_settings: HealthSettings,
     pub rfq_max_count: usize,
     pub rfq_buffer_size: usize,
-    pub order_exec_batch_size: usize,
-    pub order_exec_batch_timeout: Duration,
-    pub orderbook_ticker: String,
+    /// How long a firm quote of the rfq stream can be executed for.
+    pub rfq_quote_ttl: Duration,
+    /// Shortest interval a stream can request.
+    pub stream_min_interval: Duration,
+    /// Most price levels a stream can request on either side of the orderbook.
+    pub stream_max_levels: usize,
+    /// Longest a stream goes without sending anything before a heartbeat is sent.
+    pub stream_heartbeat_interval: Duration,
+    /// What happens to a stream subscriber that cannot keep up with its stream.
+    pub slow_consumer: SlowConsumerSettings,
+    /// How often the resting liquidity of every symbol is sampled for heatmaps.
+    pub heatmap_interval: Duration,
+    /// Price levels sampled on either side of the orderbook for heatmaps.
+    pub heatmap_levels: usize,
+    /// How often the rolling average prices of every symbol are computed.
+    pub average_price_interval: Duration,
+    /// The windows rolling VWAP and TWAP are computed over.
+    pub average_price_windows: Vec<Duration>,
+    /// The delayed market data feed, which is not served when not set.
+    pub delayed_data: Option<DelayedDataSettings>,
+    /// Token the real-time stat stream requires as `bearer` metadata, open to everyone when not
+    /// set.
+    pub market_data_token: Option<String>,
+    pub orderbook_tickers: Vec<String>,
     pub orderbook_queue_capacity: usize,
     pub orderbook_store_capacity: usize,
+    /// Tie-break rule within the price levels of the books, unless a symbol is created with
+    /// another one.
+    pub matching_policy: MatchingPolicy,
+    pub channel_capacities: ChannelCapacities,
+    pub admin_token: String,
+    /// How long every task is given to exit on shutdown before it is aborted.
+    pub task_stop_timeouts: TaskStopTimeouts,
+    /// Env file given on the command line, re-read along with `.env` when reloading.
+    pub config_path: Option<PathBuf>,
+    pub tunable_properties: TunableProperties,
+    /// Daily market hours, the market stays open around the clock when not set.
+    pub session_schedule: Option<SessionSchedule>,
+    /// Whether orders need an account with sufficient balance.
+    pub ledger_enabled: bool,
+    /// Risk limits of the accounts that were not given limits of their own.
+    pub default_risk_limits: RiskLimits,
+    /// Caps on the liquidity resting in the orderbook of every symbol that has none of its own.
+    pub resting_limits: RestingLimits,
+    /// Fees of the settlement instructions published for every fill, none are published when not
+    /// set.
+    pub settlement_fee_schedule: Option<FeeSchedule>,
+    /// Whether every fill is also published as a trade to a topic of its own.
+    pub trades_enabled: bool,
+    /// Flow limits of every account, accounts are not throttled when not set.
+    pub throttle_limits: Option<ThrottleLimits>,
+    /// How much the quotes of an account may trade before they are pulled, which they never are
+    /// when not set.
+    pub protection_limits: Option<ProtectionLimits>,
+    /// When low priority order flow is shed, which it never is when not set.
+    pub overload: Option<OverloadSettings>,
+    /// When the books are compacted, which they never are when not set.
+    pub compaction: Option<CompactionSettings>,
+    /// Role of the server in an active/standby pair, which always runs as primary when not set.
+    pub replication: Option<ReplicationSettings>,
+}
+
+/// Server properties that can be reloaded while the server is running.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TunableProperties {
+    pub order_exec_batch_size: usize,
+    pub order_exec_batch_timeout: Duration,
+    /// Longest time a changed orderbook goes without a snapshot.
     pub orderbook_snapshot_interval: Duration,
+    /// Number of operations after which an orderbook is snapshotted right away.
+    pub orderbook_snapshot_operations: u64,
+    pub metrics_report_interval: Duration,
+    pub stream_interval: Duration,
+    /// How often resting orders are checked for expiry.
+    pub order_expiry_interval: Duration,
+}
+
+impl TunableProperties {
+    pub const VARIABLES: [&'static str; 7] = [
+        "ORDER_EXEC_BATCH_SIZE",
+        "ORDER_EXEC_BATCH_TIMEOUT",
+        "ORDERBOOK_SNAPSHOT_INTERVAL_MILLIS",
+        "ORDERBOOK_SNAPSHOT_OPERATIONS",
+        "METRICS_REPORT_INTERVAL_MILLIS",
+        "STREAM_INTERVAL_MILLIS",
+        "ORDER_EXPIRY_INTERVAL_MILLIS",
+    ];
+
+    pub fn load() -> Result<Self, Box<dyn Error>> {
+        Ok(Self {
+            order_exec_batch_size: std::env::var("ORDER_EXEC_BATCH_SIZE")?.parse()?,
+            order_exec_batch_timeout: Duration::from_millis(
+                std::env::var("ORDER_EXEC_BATCH_TIMEOUT")?.parse()?,
+            ),
+            orderbook_snapshot_interval: Duration::from_millis(
+                std::env::var("ORDERBOOK_SNAPSHOT_INTERVAL_MILLIS")?.parse()?,
+            ),
+            orderbook_snapshot_operations: std::env::var("ORDERBOOK_SNAPSHOT_OPERATIONS")?
+                .parse()?,
+            metrics_report_interval: Duration::from_millis(
+                std::env::var("METRICS_REPORT_INTERVAL_MILLIS")?.parse()?,
+            ),
+            stream_interval: Duration::from_millis(
+                std::env::var("STREAM_INTERVAL_MILLIS")?.parse()?,
+            ),
+            order_expiry_interval: Duration::from_millis(
+                std::env::var("ORDER_EXPIRY_INTERVAL_MILLIS")?.parse()?,
+            ),
+        })
+    }
 }
 
 pub struct KafkaAdminProperties {
     pub kafka_broker_address: String,
     pub kafka_topic: String,
     pub sr_settings: Arc<SrSettings>,
+    /// Where events go instead of kafka when running standalone, without a broker or schema
+    /// registry. Kafka is used when not set.
+    pub standalone_sink: Option<EventSink>,
+    /// How long the server keeps retrying to register its schemas and create its topics.
+    pub startup_retry: StartupRetry,
+    /// How the execution event topics of the symbols are created.
+    pub event_topic_settings: TopicSettings,
+    /// How the settlement instruction topic is created.
+    pub settlement_topic_settings: TopicSettings,
+    /// How the trade topic is created.
+    pub trade_topic_settings: TopicSettings,
+}
+
+/// How a kafka topic is created when it does not exist yet. Existing topics are left as they are.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TopicSettings {
+    pub partitions: i32,
+    pub replication: i32,
+    /// How long messages are kept, the broker default when not set.
+    pub retention: Option<Duration>,
+    pub cleanup_policy: CleanupPolicy,
+}
+
+impl TopicSettings {
+    /// Loads the settings of a topic from the `KAFKA_<TOPIC>_TOPIC_*` variables.
+    fn load(topic: &str) -> Result<Self, Box<dyn Error>> {
+        let variable = |setting: &str| std::env::var(format!("KAFKA_{}_TOPIC_{}", topic, setting));
+        let settings = Self {
+            partitions: variable("PARTITIONS")?.parse()?,
+            replication: variable("REPLICATION")?.parse()?,
+            retention: match variable("RETENTION_MILLIS")?.as_str() {
+                "" => None,
+                retention => Some(Duration::from_millis(retention.parse()?)),
+            },
+            cleanup_policy: variable("CLEANUP_POLICY")?.parse()?,
+        };
+        if settings.partitions < 1 || settings.replication < 1 {
+            return Err(format!("{} topic needs at least one partition and replica", topic).into());
+        }
+        Ok(settings)
+    }
+}
+
+/// What the broker does with old messages of a topic.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CleanupPolicy {
+    /// Deletes messages older than the retention.
+    Delete,
+    /// Keeps the latest message of every key.
+    Compact,
+    /// Compacts and deletes messages older than the retention.
+    CompactDelete,
+}
+
+impl CleanupPolicy {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CleanupPolicy::Delete => "delete",
+            CleanupPolicy::Compact => "compact",
+            CleanupPolicy::CompactDelete => "compact,delete",
+        }
+    }
+}
+
+impl FromStr for CleanupPolicy {
+    type Err = String;
+
+    fn from_str(policy: &str) -> Result<Self, Self::Err> {
+        match policy {
+            "delete" => Ok(CleanupPolicy::Delete),
+            "compact" => Ok(CleanupPolicy::Compact),
+            "compact,delete" | "delete,compact" => Ok(CleanupPolicy::CompactDelete),
+            _ => Err(format!("unknown cleanup policy: {}", policy)),
+        }
+    }
+}
+
+/// Backoff between the attempts to set up a dependency at startup, which are given up on once
+/// the deadline passed.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StartupRetry {
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+    pub deadline: Duration,
+}
+
+/// Where a standalone server writes the events it would otherwise publish to kafka.
+#[derive(Debug, Clone, PartialEq)]
+pub enum EventSink {
+    /// Logs every event.
+    Log,
+    /// Appends every event as a line to the file.
+    File(PathBuf),
+    /// Only counts every event as delivered, so that the engine can be measured without the
+    /// cost of publishing.
+    Count,
+}
+
+impl EventSink {
+    /// Loads the sink from the `STANDALONE_*` variables, a dry run counting the events even when
+    /// not running standalone.
+    fn load() -> Result<Option<Self>, Box<dyn Error>> {
+        if std::env::var("DRY_RUN_ENABLED")?.parse()? {
+            return Ok(Some(EventSink::Count));
+        }
+        if !std::env::var("STANDALONE_ENABLED")?.parse()? {
+            return Ok(None);
+        }
+        Ok(match std::env::var("STANDALONE_EVENT_PATH")?.as_str() {
+            "" => Some(EventSink::Log),
+            path => Some(EventSink::File(PathBuf::from(path))),
+        })
+    }
 }
 
 pub struct KafkaProducerProperties {
@@ -33,10 +266,498 @@ pub struct KafkaProducerProperties {
     pub retry_backoff: String,
     pub delivery_timeout: String,
     pub enable_idempotence: String,
+    pub outbox_initial_backoff: Duration,
+    pub outbox_max_backoff: Duration,
+    pub delivery_failure_policy: DeliveryFailurePolicy,
+    /// Events the publisher hands to the producer before awaiting the delivery of the oldest of
+    /// them. With a single event in flight the events are published strictly one after another.
+    pub max_in_flight: usize,
+}
+
+/// Decides what the publisher does when an event cannot be delivered to kafka.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DeliveryFailurePolicy {
+    /// Retries the event up to the given number of times before dropping it.
+    Retry(u32),
+    /// Halts trading and keeps retrying the event until it is delivered.
+    Halt,
+    /// Drops the event right away.
+    Drop,
+}
+
+impl DeliveryFailurePolicy {
+    pub fn parse(policy: &str, max_retries: u32) -> Result<Self, Box<dyn Error>> {
+        match policy {
+            "retry" => Ok(DeliveryFailurePolicy::Retry(max_retries)),
+            "halt" => Ok(DeliveryFailurePolicy::Halt),
+            "drop" => Ok(DeliveryFailurePolicy::Drop),
+            _ => Err(format!("unknown delivery failure policy: {}", policy).into()),
+        }
+    }
+}
+
+/// Reads a tie-break rule by its name, the seed only being used by the random one.
+pub fn parse_matching_policy(policy: &str, seed: u64) -> Result<MatchingPolicy, String> {
+    match policy {
+        "price_time" => Ok(MatchingPolicy::PriceTime),
+        "size_time" => Ok(MatchingPolicy::SizeTime),
+        "random" => Ok(MatchingPolicy::Random { seed }),
+        _ => Err(format!("unknown matching policy: {}", policy)),
+    }
+}
+
+/// The placeholder the admin token used to be shipped with.
+const ADMIN_TOKEN_PLACEHOLDER: &str = "change-me";
+
+/// Reads the token that authorises admin calls, as well as fencing and promotion between the
+/// servers of a replicated pair, which may neither be left empty nor at the shipped placeholder.
+fn parse_admin_token(token: String) -> Result<String, String> {
+    if token.is_empty() || token == ADMIN_TOKEN_PLACEHOLDER {
+        return Err("ADMIN_TOKEN has to be set to a secret of its own".to_string());
+    }
+    Ok(token)
+}
+
+/// How the health task checks the dependencies of the server.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HealthSettings {
+    pub interval: Duration,
+    /// Longest a check of a remote dependency may take before it fails.
+    pub timeout: Duration,
+    /// How full the order channel of a symbol may be in percent of its capacity.
+    pub max_channel_saturation: u64,
+    /// Longest executed operations may wait for a snapshot of the orderbook.
+    pub max_snapshot_age: Duration,
+}
+
+/// What happens to a stream subscriber that stays too far behind.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SlowConsumerPolicy {
+    /// The stream ends with a resource exhausted status.
+    Disconnect,
+    /// The stream goes on conflated, sending the latest state whenever the subscriber made room
+    /// for it. Event streams cannot be conflated, so their subscribers are disconnected instead.
+    Conflate,
+}
+
+impl FromStr for SlowConsumerPolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "disconnect" => Ok(SlowConsumerPolicy::Disconnect),
+            "conflate" => Ok(SlowConsumerPolicy::Conflate),
+            _ => Err(format!("unknown slow consumer policy: {}", s)),
+        }
+    }
+}
+
+/// When a stream subscriber counts as too slow. A subscriber is behind while it has not taken
+/// `max_backlog` frames yet, and too slow once it stayed behind for the grace period.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SlowConsumerSettings {
+    pub policy: SlowConsumerPolicy,
+    pub max_backlog: usize,
+    pub grace: Duration,
+}
+
+impl SlowConsumerSettings {
+    /// Loads the settings from the `STREAM_SLOW_CONSUMER_*` variables. The backlog has to leave
+    /// room in the buffer of a stream for the status disconnecting its subscriber.
+    fn load(buffer_size: usize) -> Result<Self, Box<dyn Error>> {
+        let settings = Self {
+            policy: std::env::var("STREAM_SLOW_CONSUMER_POLICY")?.parse()?,
+            max_backlog: std::env::var("STREAM_SLOW_CONSUMER_MAX_BACKLOG")?.parse()?,
+            grace: Duration::from_millis(
+                std::env::var("STREAM_SLOW_CONSUMER_GRACE_MILLIS")?.parse()?,
+            ),
+        };
+        if settings.max_backlog == 0 || settings.max_backlog >= buffer_size {
+            return Err(format!(
+                "slow consumer backlog has to be between 1 and {}, got {}",
+                buffer_size.saturating_sub(1),
+                settings.max_backlog
+            )
+            .into());
+        }
+        Ok(settings)
+    }
+}
+
+/// How long tasks are given to exit on shutdown before they are aborted.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TaskStopTimeouts {
+    pub default: Duration,
+    /// Timeouts of the tasks that are given more or less time than the default, by task id.
+    pub tasks: HashMap<String, Duration>,
+}
+
+impl Default for TaskStopTimeouts {
+    fn default() -> Self {
+        Self {
+            default: Duration::from_secs(10),
+            tasks: HashMap::new(),
+        }
+    }
+}
+
+impl TaskStopTimeouts {
+    pub fn of(&self, id: &str) -> Duration {
+        self.tasks.get(id).copied().unwrap_or(self.default)
+    }
+
+    /// Loads the timeouts from `TASK_STOP_TIMEOUT_MILLIS` and the `id=millis` pairs of
+    /// `TASK_STOP_TIMEOUTS_MILLIS`.
+    fn load() -> Result<Self, Box<dyn Error>> {
+        Ok(Self {
+            default: Duration::from_millis(std::env::var("TASK_STOP_TIMEOUT_MILLIS")?.parse()?),
+            tasks: Self::parse(&std::env::var("TASK_STOP_TIMEOUTS_MILLIS")?)?,
+        })
+    }
+
+    fn parse(timeouts: &str) -> Result<HashMap<String, Duration>, String> {
+        timeouts
+            .split(',')
+            .map(|timeout| timeout.trim())
+            .filter(|timeout| !timeout.is_empty())
+            .map(|timeout| {
+                let (id, millis) = timeout
+                    .split_once('=')
+                    .ok_or_else(|| format!("invalid task stop timeout: {}", timeout))?;
+                let millis = millis
+                    .trim()
+                    .parse()
+                    .map_err(|_| format!("invalid task stop timeout: {}", timeout))?;
+                Ok((id.trim().to_string(), Duration::from_millis(millis)))
+            })
+            .collect()
+    }
+}
+
+/// Capacities of the internal channels of every symbol.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ChannelCapacities {
+    /// Operations queued by the dispatcher for the executor.
+    pub orders: usize,
+    /// Operator commands queued for the executor.
+    pub commands: usize,
+    /// Batches of book events buffered for every book subscriber, which is dropped further
+    /// behind.
+    pub book_feed: usize,
+    /// Executed batches buffered for every replica, which starts over from a checkpoint further
+    /// behind.
+    pub replication_feed: usize,
+    /// Updates buffered for every replica on top of the replication feed.
+    pub replication_updates: usize,
+}
+
+impl Default for ChannelCapacities {
+    fn default() -> Self {
+        Self {
+            orders: 10000,
+            commands: 16,
+            book_feed: 1024,
+            replication_feed: 1024,
+            replication_updates: 64,
+        }
+    }
+}
+
+impl ChannelCapacities {
+    /// The broadcast feeds allocate their buffer up front, which is why every capacity is capped.
+    pub const MAX_CAPACITY: usize = 1 << 20;
+
+    /// Loads the capacities from the `CHANNEL_*_CAPACITY` variables.
+    fn load() -> Result<Self, Box<dyn Error>> {
+        let variable = |channel: &str| std::env::var(format!("CHANNEL_{}_CAPACITY", channel));
+        let capacities = Self {
+            orders: variable("ORDERS")?.parse()?,
+            commands: variable("COMMANDS")?.parse()?,
+            book_feed: variable("BOOK_FEED")?.parse()?,
+            replication_feed: variable("REPLICATION_FEED")?.parse()?,
+            replication_updates: variable("REPLICATION_UPDATES")?.parse()?,
+        };
+        capacities.validate()?;
+        Ok(capacities)
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        let capacities = [
+            ("orders", self.orders),
+            ("commands", self.commands),
+            ("book feed", self.book_feed),
+            ("replication feed", self.replication_feed),
+            ("replication updates", self.replication_updates),
+        ];
+        for (channel, capacity) in capacities {
+            if !(1..=Self::MAX_CAPACITY).contains(&capacity) {
+                return Err(format!(
+                    "capacity of the {} channel has to be between 1 and {}, got {}",
+                    channel,
+                    Self::MAX_CAPACITY,
+                    capacity
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// When the executor of a symbol counts as overloaded. A symbol starts shedding low priority order
+/// flow once either its queue or its matching latency crosses the shed threshold, and only stops
+/// once both are back under the recover threshold.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OverloadSettings {
+    /// How often the load of every symbol is sampled.
+    pub interval: Duration,
+    /// How full the order channel may be, in percent of its capacity, before shedding starts.
+    pub shed_queue_saturation: u64,
+    pub recover_queue_saturation: u64,
+    /// Average matching latency over the sampling interval at which shedding starts.
+    pub shed_latency: Duration,
+    pub recover_latency: Duration,
+}
+
+impl OverloadSettings {
+    /// Loads the settings from the `OVERLOAD_*` variables.
+    fn load() -> Result<Option<Self>, Box<dyn Error>> {
+        if !std::env::var("OVERLOAD_ENABLED")?.parse()? {
+            return Ok(None);
+        }
+        let micros = |variable: &str| -> Result<Duration, Box<dyn Error>> {
+            Ok(Duration::from_micros(std::env::var(variable)?.parse()?))
+        };
+        let settings = Self {
+            interval: Duration::from_millis(std::env::var("OVERLOAD_INTERVAL_MILLIS")?.parse()?),
+            shed_queue_saturation: std::env::var("OVERLOAD_SHED_QUEUE_SATURATION")?.parse()?,
+            recover_queue_saturation: std::env::var("OVERLOAD_RECOVER_QUEUE_SATURATION")?
+                .parse()?,
+            shed_latency: micros("OVERLOAD_SHED_LATENCY_MICROS")?,
+            recover_latency: micros("OVERLOAD_RECOVER_LATENCY_MICROS")?,
+        };
+        if settings.interval.is_zero() {
+            return Err("overload sampling interval must not be zero".into());
+        }
+        if settings.shed_queue_saturation > 100
+            || settings.recover_queue_saturation >= settings.shed_queue_saturation
+        {
+            return Err(
+                "overload queue saturation must recover below where it sheds, at most 100".into(),
+            );
+        }
+        if settings.recover_latency >= settings.shed_latency {
+            return Err("overload latency has to recover below where it sheds".into());
+        }
+        Ok(Some(settings))
+    }
+}
+
+/// When the book of a symbol is compacted, which is only while its executor is quiet so that the
+/// compaction does not hold up order flow.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CompactionSettings {
+    /// How often every symbol is checked.
+    pub interval: Duration,
+    /// The most operations a symbol may have executed since it was last checked to count as quiet.
+    pub quiet_operations: u64,
+}
+
+impl CompactionSettings {
+    /// Loads the settings from the `COMPACTION_*` variables.
+    fn load() -> Result<Option<Self>, Box<dyn Error>> {
+        if !std::env::var("COMPACTION_ENABLED")?.parse()? {
+            return Ok(None);
+        }
+        let settings = Self {
+            interval: Duration::from_millis(std::env::var("COMPACTION_INTERVAL_MILLIS")?.parse()?),
+            quiet_operations: std::env::var("COMPACTION_QUIET_OPERATIONS")?.parse()?,
+        };
+        if settings.interval.is_zero() {
+            return Err("compaction interval must not be zero".into());
+        }
+        Ok(Some(settings))
+    }
+}
+
+/// How the delayed market data feed samples the market of every symbol.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DelayedDataSettings {
+    /// How far the feed lags behind the market.
+    pub delay: Duration,
+    /// How often the market is sampled.
+    pub interval: Duration,
+    /// Only the first of every `sampling` samples is served.
+    pub sampling: u64,
+    /// Price levels sampled on either side of the orderbook.
+    pub levels: usize,
+}
+
+/// How the server takes part in an active/standby pair.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReplicationSettings {
+    /// URL of the gRPC server of the primary to follow, the server starts as primary when not
+    /// set.
+    pub primary_address: Option<String>,
+    /// File the role and fencing token of the server are persisted to.
+    pub state_path: String,
+    /// How long a replica waits before subscribing again once its primary went away.
+    pub retry_interval: Duration,
+    /// Elects the primary through a lease in etcd instead of the configured primary address.
+    pub election: Option<ElectionSettings>,
+}
+
+/// How the servers of an active/standby pair elect their primary through a lease in etcd.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ElectionSettings {
+    /// URL of the JSON gateway of etcd.
+    pub endpoint: String,
+    /// Key held by the leader, shared by every server of the pair.
+    pub key: String,
+    /// How long the leadership outlives the last renewal of its lease, in whole seconds.
+    pub lease_ttl: Duration,
+    /// URL of the gRPC server of this server, which the replicas follow once it is elected.
+    pub advertised_address: String,
+}
+
+impl ElectionSettings {
+    /// Loads the settings from the `LEADER_ELECTION_*` variables.
+    fn load() -> Result<Option<Self>, Box<dyn Error>> {
+        if !std::env::var("LEADER_ELECTION_ENABLED")?.parse()? {
+            return Ok(None);
+        }
+        let settings = Self {
+            endpoint: std::env::var("LEADER_ELECTION_ENDPOINT")?,
+            key: std::env::var("LEADER_ELECTION_KEY")?,
+            lease_ttl: Duration::from_secs(
+                std::env::var("LEADER_ELECTION_LEASE_TTL_SECS")?.parse()?,
+            ),
+            advertised_address: std::env::var("LEADER_ELECTION_ADVERTISED_ADDRESS")?,
+        };
+        if settings.lease_ttl < Duration::from_secs(2) {
+            return Err("leader election needs a lease of at least two seconds".into());
+        }
+        if settings.key.is_empty() || settings.advertised_address.is_empty() {
+            return Err("leader election needs a key and an advertised address".into());
+        }
+        Ok(Some(settings))
+    }
+}
+
+/// Daily market hours in seconds since midnight UTC.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SessionSchedule {
+    /// Start of the pre-open phase, in which orders rest in a call.
+    pub pre_open: u32,
+    pub open: u32,
+    /// End of the session, at which DAY orders are purged.
+    pub close: u32,
+}
+
+impl SessionSchedule {
+    /// Parses the schedule from `HH:MM` times, which must follow each other within a single day.
+    pub fn parse(pre_open: &str, open: &str, close: &str) -> Result<Self, Box<dyn Error>> {
+        let schedule = Self {
+            pre_open: parse_time_of_day(pre_open)?,
+            open: parse_time_of_day(open)?,
+            close: parse_time_of_day(close)?,
+        };
+        if schedule.pre_open > schedule.open || schedule.open >= schedule.close {
+            return Err("session times must satisfy pre-open <= open < close".into());
+        }
+        Ok(schedule)
+    }
+}
+
+fn parse_time_of_day(time: &str) -> Result<u32, Box<dyn Error>> {
+    let (hours, minutes) = time
+        .split_once(':')
+        .ok_or_else(|| format!("invalid time of day: {}", time))?;
+    let (hours, minutes): (u32, u32) = (hours.parse()?, minutes.parse()?);
+    if hours > 23 || minutes > 59 {
+        return Err(format!("invalid time of day: {}", time).into());
+    }
+    Ok(hours * 3600 + minutes * 60)
 }
 
 pub struct LogProperties {
     pub enable_file_log: bool,
+    pub log_format: LogFormat,
+    /// Default `RUST_LOG` style directives, used when `RUST_LOG` is not set.
+    pub log_filter: String,
+}
+
+/// The format log lines are written in.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LogFormat {
+    /// Human readable lines.
+    Text,
+    /// One JSON object per line, including the fields of the event and its current span.
+    Json,
+}
+
+impl FromStr for LogFormat {
+    type Err = String;
+
+    fn from_str(format: &str) -> Result<Self, Self::Err> {
+        match format {
+            "text" => Ok(LogFormat::Text),
+            "json" => Ok(LogFormat::Json),
+            _ => Err(format!("unknown log format: {}", format)),
+        }
+    }
+}
+
+pub struct PersistenceProperties {
+    pub wal_enabled: bool,
+    pub wal_directory: String,
+    pub wal_checkpoint_interval: Duration,
+    pub order_store_enabled: bool,
+    pub order_store_path: String,
+    pub outbox_path: String,
+    /// Directory of the audit log, which is only kept when set.
+    pub audit_directory: Option<String>,
+    pub audit_max_file_size: u64,
+    /// File the accepted order flow is recorded to, only recorded when set. Paths ending in
+    /// `.parquet` are written as parquet, everything else as CSV.
+    pub recording_path: Option<String>,
+    /// File the requests reaching the dispatcher are recorded to along with their answers, only
+    /// recorded when set.
+    pub traffic_recording_path: Option<String>,
+    /// Recording replayed through the engine on startup, only replayed when set.
+    pub replay_path: Option<String>,
+    /// How many times faster than recorded the replay runs, zero replays as fast as possible.
+    pub replay_speed: f64,
+    /// Bucket the book snapshots are uploaded to, only uploaded when set.
+    pub snapshot_store: Option<SnapshotStoreSettings>,
+    /// Where a crash dump is written when the server panics, none is written when not set.
+    pub crash_dump: Option<CrashDumpSettings>,
+}
+
+/// What a crash dump holds besides the resting orders of every book.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CrashDumpSettings {
+    /// Every dump is written into a subdirectory of its own.
+    pub directory: String,
+    /// Operations processed last by every executor.
+    pub operations: usize,
+}
+
+/// An S3 compatible bucket the book snapshots of every symbol are uploaded to.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SnapshotStoreSettings {
+    /// Base URL of the object storage, the bucket is addressed by its path.
+    pub endpoint: String,
+    pub bucket: String,
+    pub region: String,
+    pub access_key: String,
+    pub secret_key: String,
+    /// Prefix of the keys the snapshots are stored under.
+    pub prefix: String,
+    /// How often the books that changed since their last upload are uploaded.
+    pub interval: Duration,
+    /// Whether books without local persistence to recover from are restored from their latest
+    /// snapshot on startup.
+    pub restore: bool,
 }
 
 pub struct EnvironmentProperties {
@@ -44,6 +765,61 @@ pub struct EnvironmentProperties {
     pub kafka_admin_properties: KafkaAdminProperties,
     pub kafka_producer_properties: KafkaProducerProperties,
     pub log_properties: LogProperties,
+    pub persistence_properties: PersistenceProperties,
+}
+
+/// Configuration of the router in front of the shards, which is loaded on its own as the router
+/// runs without the rest of the engine.
+pub struct RouterProperties {
+    pub socket_address: SocketAddr,
+    pub shards: Vec<ShardProperties>,
+}
+
+/// A gemmy process owning a subset of the symbols.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShardProperties {
+    pub name: String,
+    /// URL of the gRPC server of the shard.
+    pub address: String,
+    pub symbols: Vec<String>,
+}
+
+impl RouterProperties {
+    pub fn load() -> Result<Self, Box<dyn Error>> {
+        dotenv().ok();
+        let shards = std::env::var("ROUTER_SHARDS")?
+            .split(',')
+            .map(|name| name.trim())
+            .filter(|name| !name.is_empty())
+            .map(ShardProperties::load)
+            .collect::<Result<_, _>>()?;
+        Ok(Self {
+            socket_address: std::env::var("ROUTER_SOCKET_ADDRESS")?.parse()?,
+            shards,
+        })
+    }
+}
+
+impl ShardProperties {
+    /// Loads a shard from the `ROUTER_SHARD_<NAME>_*` variables.
+    fn load(name: &str) -> Result<Self, Box<dyn Error>> {
+        let variable = |setting: &str| {
+            std::env::var(format!(
+                "ROUTER_SHARD_{}_{}",
+                name.to_uppercase().replace('-', "_"),
+                setting
+            ))
+        };
+        Ok(Self {
+            name: name.to_string(),
+            address: variable("ADDRESS")?,
+            symbols: variable("SYMBOLS")?
+                .split(',')
+                .map(|symbol| symbol.trim().to_string())
+                .filter(|symbol| !symbol.is_empty())
+                .collect(),
+        })
+    }
 }
 
 impl EnvironmentProperties {
@@ -52,18 +828,148 @@ impl EnvironmentProperties {
         let properties = Self {
             server_properties: ServerProperties {
                 socket_address: std::env::var("GRPC_SOCKET_ADDRESS")?.parse()?,
+                http_socket_address: match std::env::var("HTTP_ENABLED")?.parse()? {
+                    true => Some(std::env::var("HTTP_SOCKET_ADDRESS")?.parse()?),
+                    false => None,
+                },
+                binary_socket_address: match std::env::var("BINARY_ENABLED")?.parse()? {
+                    true => Some(std::env::var("BINARY_SOCKET_ADDRESS")?.parse()?),
+                    false => None,
+                },
+                health_socket_address: match std::env::var("HEALTH_HTTP_ENABLED")?.parse()? {
+                    true => Some(std::env::var("HEALTH_HTTP_SOCKET_ADDRESS")?.parse()?),
+                    false => None,
+                },
+                health_settings: HealthSettings {
+                    interval: Duration::from_millis(
+                        std::env::var("HEALTH_CHECK_INTERVAL_MILLIS")?.parse()?,
+                    ),
+                    timeout: Duration::from_millis(
+                        std::env::var("HEALTH_CHECK_TIMEOUT_MILLIS")?.parse()?,
+                    ),
+                    max_channel_saturation: std::env::var("HEALTH_MAX_CHANNEL_SATURATION_PERCENT")?
+                        .parse()?,
+                    max_snapshot_age: Duration::from_millis(
+                        std::env::var("HEALTH_MAX_SNAPSHOT_AGE_MILLIS")?.parse()?,
+                    ),
+                },
                 rfq_max_count: std::env::var("RFQ_MAX_COUNT")?.parse()?,
                 rfq_buffer_size: std::env::var("RFQ_BUFFER_SIZE")?.parse()?,
-                order_exec_batch_size: std::env::var("ORDER_EXEC_BATCH_SIZE")?.parse()?,
-                order_exec_batch_timeout: Duration::from_millis(
-                    std::env::var("ORDER_EXEC_BATCH_TIMEOUT")?.parse()?,
+                rfq_quote_ttl: Duration::from_millis(
+                    std::env::var("RFQ_QUOTE_TTL_MILLIS")?.parse()?,
+                ),
+                stream_min_interval: Duration::from_millis(
+                    std::env::var("STREAM_MIN_INTERVAL_MILLIS")?.parse()?,
+                ),
+                stream_max_levels: std::env::var("STREAM_MAX_LEVELS")?.parse()?,
+                stream_heartbeat_interval: Duration::from_millis(
+                    std::env::var("STREAM_HEARTBEAT_INTERVAL_MILLIS")?.parse()?,
+                ),
+                slow_consumer: SlowConsumerSettings::load(
+                    std::env::var("RFQ_BUFFER_SIZE")?.parse()?,
+                )?,
+                heatmap_interval: Duration::from_millis(
+                    std::env::var("HEATMAP_INTERVAL_MILLIS")?.parse()?,
+                ),
+                heatmap_levels: std::env::var("HEATMAP_LEVELS")?.parse()?,
+                average_price_interval: Duration::from_millis(
+                    std::env::var("AVERAGE_PRICE_INTERVAL_MILLIS")?.parse()?,
                 ),
-                orderbook_ticker: std::env::var("TICKER")?.parse()?,
+                average_price_windows: std::env::var("AVERAGE_PRICE_WINDOWS_SECS")?
+                    .split(',')
+                    .map(|window| window.trim())
+                    .filter(|window| !window.is_empty())
+                    .map(|window| window.parse().map(Duration::from_secs))
+                    .collect::<Result<_, _>>()?,
+                delayed_data: match std::env::var("DELAYED_DATA_ENABLED")?.parse()? {
+                    true => Some(DelayedDataSettings {
+                        delay: Duration::from_millis(
+                            std::env::var("DELAYED_DATA_DELAY_MILLIS")?.parse()?,
+                        ),
+                        interval: Duration::from_millis(
+                            std::env::var("DELAYED_DATA_INTERVAL_MILLIS")?.parse()?,
+                        ),
+                        sampling: std::env::var("DELAYED_DATA_SAMPLING")?
+                            .parse::<u64>()?
+                            .max(1),
+                        levels: std::env::var("DELAYED_DATA_LEVELS")?.parse()?,
+                    }),
+                    false => None,
+                },
+                market_data_token: Some(std::env::var("MARKET_DATA_TOKEN")?)
+                    .filter(|token| !token.is_empty()),
+                orderbook_tickers: std::env::var("TICKERS")?
+                    .split(',')
+                    .map(|ticker| ticker.trim().to_string())
+                    .filter(|ticker| !ticker.is_empty())
+                    .collect(),
                 orderbook_queue_capacity: std::env::var("ORDERBOOK_QUEUE_CAPACITY")?.parse()?,
                 orderbook_store_capacity: std::env::var("ORDERBOOK_STORE_CAPACITY")?.parse()?,
-                orderbook_snapshot_interval: Duration::from_millis(
-                    std::env::var("ORDERBOOK_SNAPSHOT_INTERVAL_MILLIS")?.parse()?,
+                matching_policy: parse_matching_policy(
+                    &std::env::var("ORDERBOOK_MATCHING_POLICY")?,
+                    std::env::var("ORDERBOOK_MATCHING_SEED")?.parse()?,
+                )?,
+                channel_capacities: ChannelCapacities::load()?,
+                admin_token: parse_admin_token(std::env::var("ADMIN_TOKEN")?)?,
+                task_stop_timeouts: TaskStopTimeouts::load()?,
+                config_path: std::env::var("CONFIG_PATH").ok().map(PathBuf::from),
+                tunable_properties: TunableProperties::load()?,
+                session_schedule: match std::env::var("SESSION_ENABLED")?.parse()? {
+                    true => Some(SessionSchedule::parse(
+                        &std::env::var("SESSION_PRE_OPEN")?,
+                        &std::env::var("SESSION_OPEN")?,
+                        &std::env::var("SESSION_CLOSE")?,
+                    )?),
+                    false => None,
+                },
+                ledger_enabled: std::env::var("LEDGER_ENABLED")?.parse()?,
+                default_risk_limits: RiskLimits::from_raw(
+                    std::env::var("RISK_MAX_ORDER_QUANTITY")?.parse()?,
+                    std::env::var("RISK_MAX_OPEN_ORDERS")?.parse()?,
+                    std::env::var("RISK_MAX_NOTIONAL")?.parse()?,
                 ),
+                resting_limits: RestingLimits::from_raw(
+                    std::env::var("SYMBOL_MAX_RESTING_QUANTITY")?.parse()?,
+                    std::env::var("SYMBOL_MAX_RESTING_NOTIONAL")?.parse()?,
+                ),
+                settlement_fee_schedule: match std::env::var("SETTLEMENT_ENABLED")?.parse()? {
+                    true => Some(FeeSchedule {
+                        maker_fee_bps: std::env::var("SETTLEMENT_MAKER_FEE_BPS")?.parse()?,
+                        taker_fee_bps: std::env::var("SETTLEMENT_TAKER_FEE_BPS")?.parse()?,
+                    }),
+                    false => None,
+                },
+                trades_enabled: std::env::var("TRADES_ENABLED")?.parse()?,
+                throttle_limits: match std::env::var("THROTTLE_ENABLED")?.parse()? {
+                    true => Some(ThrottleLimits::from_raw(
+                        std::env::var("THROTTLE_MAX_ORDERS_PER_SECOND")?.parse()?,
+                        std::env::var("THROTTLE_MAX_MESSAGE_TO_TRADE_RATIO")?.parse()?,
+                        std::env::var("THROTTLE_MODE")?.parse()?,
+                    )),
+                    false => None,
+                },
+                protection_limits: match std::env::var("MMP_ENABLED")?.parse()? {
+                    true => Some(ProtectionLimits::from_raw(
+                        Duration::from_millis(std::env::var("MMP_WINDOW_MILLIS")?.parse()?),
+                        std::env::var("MMP_MAX_FILLS")?.parse()?,
+                        std::env::var("MMP_MAX_QUANTITY")?.parse()?,
+                    )),
+                    false => None,
+                },
+                overload: OverloadSettings::load()?,
+                compaction: CompactionSettings::load()?,
+                replication: match std::env::var("REPLICATION_ENABLED")?.parse()? {
+                    true => Some(ReplicationSettings {
+                        primary_address: Some(std::env::var("REPLICATION_PRIMARY_ADDRESS")?)
+                            .filter(|address| !address.is_empty()),
+                        state_path: std::env::var("REPLICATION_STATE_PATH")?,
+                        retry_interval: Duration::from_millis(
+                            std::env::var("REPLICATION_RETRY_INTERVAL_MILLIS")?.parse()?,
+                        ),
+                        election: ElectionSettings::load()?,
+                    }),
+                    false => None,
+                },
             },
             kafka_admin_properties: KafkaAdminProperties {
                 kafka_broker_address: std::env::var("KAFKA_BROKER_ADDRESS")?.parse()?,
@@ -71,6 +977,21 @@ impl EnvironmentProperties {
                 sr_settings: Arc::new(SrSettings::new(
                     std::env::var("SCHEMA_REGISTRY_URL")?.parse()?,
                 )),
+                standalone_sink: EventSink::load()?,
+                event_topic_settings: TopicSettings::load("EVENTS")?,
+                settlement_topic_settings: TopicSettings::load("SETTLEMENTS")?,
+                trade_topic_settings: TopicSettings::load("TRADES")?,
+                startup_retry: StartupRetry {
+                    initial_backoff: Duration::from_millis(
+                        std::env::var("STARTUP_INITIAL_BACKOFF_MILLIS")?.parse()?,
+                    ),
+                    max_backoff: Duration::from_millis(
+                        std::env::var("STARTUP_MAX_BACKOFF_MILLIS")?.parse()?,
+                    ),
+                    deadline: Duration::from_millis(
+                        std::env::var("STARTUP_DEADLINE_MILLIS")?.parse()?,
+                    ),
+                },
             },
             kafka_producer_properties: KafkaProducerProperties {
                 message_timeout: std::env::var("KAFKA_PRODUCER_MESSAGE_TIMEOUT_MILLIS")?.parse()?,
@@ -82,11 +1003,89 @@ impl EnvironmentProperties {
                 retry_backoff: std::env::var("KAFKA_RETRY_BACKOFF_MILLIS")?.parse()?,
                 delivery_timeout: std::env::var("KAFKA_DELIVERY_TIMEOUT_MILLIS")?.parse()?,
                 enable_idempotence: std::env::var("KAFKA_ENABLE_IDEMPOTENCE")?.parse()?,
+                outbox_initial_backoff: Duration::from_millis(
+                    std::env::var("KAFKA_OUTBOX_INITIAL_BACKOFF_MILLIS")?.parse()?,
+                ),
+                outbox_max_backoff: Duration::from_millis(
+                    std::env::var("KAFKA_OUTBOX_MAX_BACKOFF_MILLIS")?.parse()?,
+                ),
+                delivery_failure_policy: DeliveryFailurePolicy::parse(
+                    &std::env::var("KAFKA_DELIVERY_FAILURE_POLICY")?,
+                    std::env::var("KAFKA_DELIVERY_MAX_RETRIES")?.parse()?,
+                )?,
+                max_in_flight: std::env::var("KAFKA_PUBLISH_MAX_IN_FLIGHT")?.parse()?,
             },
             log_properties: LogProperties {
                 enable_file_log: std::env::var("ENABLE_FILE_LOG")?.parse()?,
+                log_format: std::env::var("LOG_FORMAT")?.parse()?,
+                log_filter: std::env::var("LOG_FILTER")?.parse()?,
+            },
+            persistence_properties: PersistenceProperties {
+                wal_enabled: std::env::var("WAL_ENABLED")?.parse()?,
+                wal_directory: std::env::var("WAL_DIRECTORY")?.parse()?,
+                wal_checkpoint_interval: Duration::from_millis(
+                    std::env::var("WAL_CHECKPOINT_INTERVAL_MILLIS")?.parse()?,
+                ),
+                order_store_enabled: std::env::var("ORDER_STORE_ENABLED")?.parse()?,
+                order_store_path: std::env::var("ORDER_STORE_PATH")?.parse()?,
+                outbox_path: std::env::var("OUTBOX_PATH")?.parse()?,
+                audit_directory: match std::env::var("AUDIT_ENABLED")?.parse()? {
+                    true => Some(std::env::var("AUDIT_DIRECTORY")?.parse()?),
+                    false => None,
+                },
+                audit_max_file_size: std::env::var("AUDIT_MAX_FILE_BYTES")?.parse()?,
+                recording_path: match std::env::var("RECORDING_ENABLED")?.parse()? {
+                    true => Some(std::env::var("RECORDING_PATH")?.parse()?),
+                    false => None,
+                },
+                traffic_recording_path: match std::env::var("TRAFFIC_RECORDING_ENABLED")?.parse()? {
+                    true => Some(std::env::var("TRAFFIC_RECORDING_PATH")?.parse()?),
+                    false => None,
+                },
+                replay_path: match std::env::var("REPLAY_ENABLED")?.parse()? {
+                    true => Some(std::env::var("REPLAY_PATH")?.parse()?),
+                    false => None,
+                },
+                replay_speed: std::env::var("REPLAY_SPEED")?.parse()?,
+                snapshot_store: match std::env::var("SNAPSHOT_STORE_ENABLED")?.parse()? {
+                    true => Some(SnapshotStoreSettings {
+                        endpoint: std::env::var("SNAPSHOT_STORE_ENDPOINT")?,
+                        bucket: std::env::var("SNAPSHOT_STORE_BUCKET")?,
+                        region: std::env::var("SNAPSHOT_STORE_REGION")?,
+                        access_key: std::env::var("SNAPSHOT_STORE_ACCESS_KEY")?,
+                        secret_key: std::env::var("SNAPSHOT_STORE_SECRET_KEY")?,
+                        prefix: std::env::var("SNAPSHOT_STORE_PREFIX")?,
+                        interval: Duration::from_millis(
+                            std::env::var("SNAPSHOT_STORE_INTERVAL_MILLIS")?.parse()?,
+                        ),
+                        restore: std::env::var("SNAPSHOT_STORE_RESTORE")?.parse()?,
+                    }),
+                    false => None,
+                },
+                crash_dump: match std::env::var("CRASH_DUMP_ENABLED")?.parse()? {
+                    true => Some(CrashDumpSettings {
+                        directory: std::env::var("CRASH_DUMP_DIRECTORY")?,
+                        operations: std::env::var("CRASH_DUMP_OPERATIONS")?.parse()?,
+                    }),
+                    false => None,
+                },
             },
         };
         Ok(properties)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::engine::constants::property_loader::parse_admin_token;
+
+    #[test]
+    fn it_refuses_an_unset_admin_token() {
+        assert!(parse_admin_token(String::new()).is_err());
+        assert!(parse_admin_token("change-me".to_string()).is_err());
+        assert_eq!(
+            parse_admin_token("s3cr3t".to_string()),
+            Ok("s3cr3t".to_string())
+        );
+    }
+}
@@ -0,0 +1,146 @@
+use crate::core::errors::{InvalidSide, OrderBookError, Rejection};
+use crate::engine::state::quotes::QuoteError;
+use thiserror::Error;
+use tonic::{Code, Status};
+
+/// The errors of the engine, each of which maps to the gRPC status it is reported to clients with.
+#[derive(Debug, Error)]
+pub enum EngineError {
+    /// A request could not be decoded or does not fit the specification of its symbol.
+    #[error(transparent)]
+    Validation(#[from] ValidationError),
+    /// An operation was rejected by a pre-trade check or failed to execute in the orderbook.
+    #[error(transparent)]
+    OrderBook(#[from] OrderBookError),
+    /// A firm quote could not be executed.
+    #[error(transparent)]
+    Quote(#[from] QuoteError),
+    #[error("unknown symbol: {0}")]
+    UnknownSymbol(String),
+    /// The engine cannot take the request at the moment, for example because trading is halted.
+    #[error("{0}")]
+    Unavailable(String),
+    #[error("storage error: {0}")]
+    Storage(#[from] sled::Error),
+    /// Persisted state could not be decoded.
+    #[error("corrupted {0}")]
+    Corrupted(&'static str),
+}
+
+/// The reason a request is invalid.
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum ValidationError {
+    #[error(transparent)]
+    Side(#[from] InvalidSide),
+    #[error("order id must be 16 bytes, got {0}")]
+    OrderIdLength(usize),
+    #[error("invalid granularity: {0}")]
+    Granularity(i32),
+    #[error("invalid candle interval: {0}")]
+    CandleInterval(i32),
+    #[error("price must be a multiple of the tick size {0}")]
+    TickSize(u64),
+    #[error("quantity must be a multiple of the lot size {0}")]
+    LotSize(u64),
+}
+
+impl From<Rejection> for EngineError {
+    fn from(rejection: Rejection) -> Self {
+        EngineError::OrderBook(OrderBookError::Rejected(rejection))
+    }
+}
+
+impl EngineError {
+    /// The gRPC status code the error is reported with.
+    pub fn code(&self) -> Code {
+        match self {
+            EngineError::Validation(_) => Code::InvalidArgument,
+            EngineError::OrderBook(OrderBookError::OrderNotFound) => Code::NotFound,
+            EngineError::OrderBook(_) => Code::FailedPrecondition,
+            EngineError::Quote(QuoteError::Unknown) => Code::NotFound,
+            EngineError::Quote(QuoteError::Dropped) => Code::Unavailable,
+            EngineError::Quote(_) => Code::FailedPrecondition,
+            EngineError::UnknownSymbol(_) => Code::NotFound,
+            EngineError::Unavailable(_) => Code::Unavailable,
+            EngineError::Storage(_) | EngineError::Corrupted(_) => Code::Internal,
+        }
+    }
+}
+
+impl From<EngineError> for Status {
+    fn from(error: EngineError) -> Self {
+        match error.code() {
+            // infrastructure failures are logged by the engine rather than leaked to clients
+            Code::Internal => Status::internal("internal server error"),
+            code => Status::new(code, error.to_string()),
+        }
+    }
+}
+
+impl From<ValidationError> for Status {
+    fn from(error: ValidationError) -> Self {
+        EngineError::from(error).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::errors::{InvalidSide, OrderBookError, Rejection};
+    use crate::engine::errors::{EngineError, ValidationError};
+    use crate::engine::state::quotes::QuoteError;
+    use tonic::{Code, Status};
+
+    #[test]
+    fn it_maps_errors_to_grpc_status_codes() {
+        let cases = [
+            (
+                EngineError::from(ValidationError::from(InvalidSide(2))),
+                Code::InvalidArgument,
+            ),
+            (
+                EngineError::from(OrderBookError::OrderNotFound),
+                Code::NotFound,
+            ),
+            (
+                EngineError::from(OrderBookError::EmptyBook),
+                Code::FailedPrecondition,
+            ),
+            (
+                EngineError::from(Rejection::UnassignedOrder),
+                Code::FailedPrecondition,
+            ),
+            (EngineError::from(QuoteError::Unknown), Code::NotFound),
+            (
+                EngineError::from(QuoteError::Liquidity),
+                Code::FailedPrecondition,
+            ),
+            (
+                EngineError::from(QuoteError::Failed(OrderBookError::InCall)),
+                Code::FailedPrecondition,
+            ),
+            (EngineError::from(QuoteError::Dropped), Code::Unavailable),
+            (
+                EngineError::UnknownSymbol("ETHUSD".to_string()),
+                Code::NotFound,
+            ),
+            (
+                EngineError::Unavailable("trading is halted".to_string()),
+                Code::Unavailable,
+            ),
+            (EngineError::Corrupted("outbox"), Code::Internal),
+        ];
+        for (error, code) in cases {
+            assert_eq!(Status::from(error).code(), code);
+        }
+    }
+
+    #[test]
+    fn it_keeps_the_reason_in_the_status_message() {
+        let status = Status::from(EngineError::from(Rejection::InsufficientBalance {
+            asset: "ETH".to_string(),
+        }));
+        assert_eq!(status.message(), "insufficient ETH balance");
+        let status = Status::from(EngineError::Corrupted("persistent order store"));
+        assert_eq!(status.message(), "internal server error");
+    }
+}
@@ -1,5 +1,9 @@
+pub mod accounts;
 pub mod configuration;
 pub mod constants;
+pub mod errors;
+pub mod persistence;
+pub mod risk;
 pub mod services;
 pub mod state;
 pub mod tasks;
@@ -0,0 +1,280 @@
+use sha2::{Digest, Sha256};
+use std::error::Error;
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tracing::info;
+
+const FILE_PREFIX: &str = "audit-";
+const FILE_SUFFIX: &str = ".log";
+/// Hash the chain starts from, in place of the hash of a previous record.
+const GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+/// A single inbound request along with the decision taken on it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AuditRecord {
+    pub sequence: u64,
+    /// Nanoseconds since the unix epoch.
+    pub timestamp: u128,
+    pub principal: String,
+    pub method: String,
+    pub payload: String,
+    pub decision: String,
+}
+
+/// An append only log of every inbound request, kept apart from the application logs.
+/// Every line carries the hash of the line before it and a hash over its own content chained to
+/// that one, so removing, reordering or editing a record breaks the chain from there on.
+/// The chain continues across files, which are rotated once they reach their maximum size and
+/// are named after the sequence of their first record.
+pub struct AuditLog {
+    directory: PathBuf,
+    max_file_size: u64,
+    writer: Mutex<AuditWriter>,
+}
+
+struct AuditWriter {
+    file: File,
+    size: u64,
+    next_sequence: u64,
+    last_hash: String,
+}
+
+impl AuditLog {
+    /// Opens the log in the given directory, creating it if needed, and continues the chain of
+    /// the records already in it, which are verified first.
+    pub fn open(directory: impl AsRef<Path>, max_file_size: u64) -> Result<Self, Box<dyn Error>> {
+        let directory = directory.as_ref().to_path_buf();
+        fs::create_dir_all(&directory)?;
+        let records = Self::read(&directory)?;
+        let (next_sequence, last_hash) = match records.last() {
+            Some((record, hash)) => (record.sequence + 1, hash.clone()),
+            None => (0, GENESIS_HASH.to_string()),
+        };
+        let path = match log_files(&directory)?.pop() {
+            Some(path) => path,
+            None => directory.join(file_name(next_sequence)),
+        };
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        let size = file.metadata()?.len();
+        info!(
+            "opened audit log at {:?} with {} records",
+            path,
+            records.len()
+        );
+        Ok(Self {
+            directory,
+            max_file_size,
+            writer: Mutex::new(AuditWriter {
+                file,
+                size,
+                next_sequence,
+                last_hash,
+            }),
+        })
+    }
+
+    /// Appends a record of the request, rotating to a new file first if the current one is full.
+    ///
+    /// # Returns
+    ///
+    /// * The sequence number of the record.
+    pub fn record(
+        &self,
+        timestamp: u128,
+        principal: &str,
+        method: &str,
+        payload: &str,
+        decision: &str,
+    ) -> Result<u64, Box<dyn Error>> {
+        let mut writer = self.writer.lock().unwrap();
+        let sequence = writer.next_sequence;
+        if writer.size >= self.max_file_size {
+            let path = self.directory.join(file_name(sequence));
+            writer.file = OpenOptions::new().create(true).append(true).open(&path)?;
+            writer.size = 0;
+            info!("rotated audit log to {:?}", path);
+        }
+        let body = [
+            sequence.to_string(),
+            timestamp.to_string(),
+            escape(principal),
+            escape(method),
+            escape(payload),
+            escape(decision),
+        ]
+        .join("\t");
+        let hash = chain_hash(&writer.last_hash, &body);
+        let line = format!("{}\t{}\t{}\n", body, writer.last_hash, hash);
+        writer.file.write_all(line.as_bytes())?;
+        writer.file.flush()?;
+        writer.size += line.len() as u64;
+        writer.next_sequence += 1;
+        writer.last_hash = hash;
+        Ok(sequence)
+    }
+
+    /// Reads every record in the directory in sequence, verifying the chain along the way.
+    ///
+    /// # Returns
+    ///
+    /// * The records along with their hashes, or an error naming the first record that breaks
+    ///   the chain.
+    pub fn read(directory: impl AsRef<Path>) -> Result<Vec<(AuditRecord, String)>, Box<dyn Error>> {
+        let mut records = vec![];
+        let mut last_hash = GENESIS_HASH.to_string();
+        for path in log_files(directory.as_ref())? {
+            for line in BufReader::new(File::open(&path)?).lines() {
+                let line = line?;
+                let (record, previous_hash, hash) = parse(&line)
+                    .ok_or_else(|| format!("malformed audit record in {:?}: {}", path, line))?;
+                let expected_sequence = records.len() as u64;
+                let body = &line[..line.len() - previous_hash.len() - hash.len() - 2];
+                if record.sequence != expected_sequence
+                    || previous_hash != last_hash
+                    || chain_hash(&last_hash, body) != hash
+                {
+                    return Err(format!(
+                        "audit log chain is broken at record {} in {:?}",
+                        expected_sequence, path
+                    )
+                    .into());
+                }
+                last_hash = hash.to_string();
+                records.push((record, last_hash.clone()));
+            }
+        }
+        Ok(records)
+    }
+}
+
+fn file_name(first_sequence: u64) -> String {
+    format!("{}{:020}{}", FILE_PREFIX, first_sequence, FILE_SUFFIX)
+}
+
+/// The log files in the directory, ordered by the sequence of their first record.
+fn log_files(directory: &Path) -> Result<Vec<PathBuf>, Box<dyn Error>> {
+    let mut paths: Vec<PathBuf> = fs::read_dir(directory)?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with(FILE_PREFIX) && name.ends_with(FILE_SUFFIX))
+        })
+        .collect();
+    paths.sort();
+    Ok(paths)
+}
+
+fn chain_hash(previous_hash: &str, body: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(previous_hash.as_bytes());
+    hasher.update(b"\t");
+    hasher.update(body.as_bytes());
+    hasher
+        .finalize()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+/// Escapes the characters that delimit fields and records.
+fn escape(field: &str) -> String {
+    let mut escaped = String::with_capacity(field.len());
+    for c in field.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '\t' => escaped.push_str("\\t"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn unescape(field: &str) -> String {
+    let mut unescaped = String::with_capacity(field.len());
+    let mut chars = field.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('t') => unescaped.push('\t'),
+            Some('n') => unescaped.push('\n'),
+            Some('r') => unescaped.push('\r'),
+            Some(c) => unescaped.push(c),
+            None => (),
+        }
+    }
+    unescaped
+}
+
+fn parse(line: &str) -> Option<(AuditRecord, &str, &str)> {
+    let fields: Vec<&str> = line.split('\t').collect();
+    let [sequence, timestamp, principal, method, payload, decision, previous_hash, hash] =
+        fields[..]
+    else {
+        return None;
+    };
+    let record = AuditRecord {
+        sequence: sequence.parse().ok()?,
+        timestamp: timestamp.parse().ok()?,
+        principal: unescape(principal),
+        method: unescape(method),
+        payload: unescape(payload),
+        decision: unescape(decision),
+    };
+    Some((record, previous_hash, hash))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::engine::persistence::audit_log::AuditLog;
+    use std::fs;
+
+    #[test]
+    fn it_chains_rotates_and_detects_tampering() {
+        let directory = std::env::temp_dir().join(format!("gemmy-audit-{}", uuid::Uuid::new_v4()));
+        let audit_log = AuditLog::open(&directory, 150).unwrap();
+        for sequence in 0..4 {
+            let recorded = audit_log
+                .record(
+                    sequence as u128,
+                    "trader",
+                    "limit",
+                    "price:\t100\n",
+                    "accepted",
+                )
+                .unwrap();
+            assert_eq!(recorded, sequence);
+        }
+        drop(audit_log);
+        let reopened = AuditLog::open(&directory, 150).unwrap();
+        assert_eq!(
+            reopened.record(4, "admin", "halt", "", "rejected").unwrap(),
+            4
+        );
+
+        let records = AuditLog::read(&directory).unwrap();
+        assert_eq!(records.len(), 5);
+        assert_eq!(records[1].0.payload, "price:\t100\n");
+        assert_eq!(records[4].0.principal, "admin");
+        let mut files: Vec<_> = fs::read_dir(&directory)
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .collect();
+        assert!(files.len() > 1);
+
+        files.sort();
+        let tampered = fs::read_to_string(&files[0])
+            .unwrap()
+            .replacen("accepted", "rejected", 1);
+        fs::write(&files[0], tampered).unwrap();
+        assert!(AuditLog::read(&directory).is_err());
+        fs::remove_dir_all(&directory).unwrap();
+    }
+}
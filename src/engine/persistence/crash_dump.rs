@@ -0,0 +1,242 @@
+use crate::core::models::Operation;
+use crate::engine::persistence::recording::FlowRecorder;
+use crate::engine::state::server_state::Symbols;
+use crate::engine::state::symbol_state::SymbolState;
+use crate::engine::utils::time::generate_u128_timestamp;
+use std::error::Error;
+use std::fmt::Write as _;
+use std::fs;
+use std::future::Future;
+use std::panic;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, TryLockError};
+use tracing::error;
+
+const SUMMARY_FILE: &str = "panic.txt";
+
+tokio::task_local! {
+    static EXECUTOR_SYMBOL: String;
+}
+
+/// Runs the executor of a symbol, so that its panics write a crash dump.
+pub async fn run_executor<F: Future>(symbol: String, executor: F) -> F::Output {
+    EXECUTOR_SYMBOL.scope(symbol, executor).await
+}
+
+/// The symbol of the executor running on the current task, if any.
+fn current_executor() -> Option<String> {
+    EXECUTOR_SYMBOL.try_with(Clone::clone).ok()
+}
+
+/// Installs a panic hook that writes a crash dump into a new subdirectory of `directory` when an
+/// executor panics, before the panic is reported as usual. The panics of the tasks supervised by
+/// the task manager do not write one, unlike them a symbol is lost along with its executor.
+/// Panics raised while a dump is being written do not write one of their own.
+pub fn install(directory: PathBuf, symbols: Symbols) {
+    let report = panic::take_hook();
+    let dumping = AtomicBool::new(false);
+    panic::set_hook(Box::new(move |info| {
+        if let Some(symbol) = current_executor() {
+            if !dumping.swap(true, Ordering::SeqCst) {
+                let reason = format!("executor of {} panicked: {}", symbol, info);
+                match write(&directory, &symbols, &reason) {
+                    Ok(path) => error!("wrote crash dump to {:?}", path),
+                    Err(e) => error!("failed to write crash dump: {}", e),
+                }
+                dumping.store(false, Ordering::SeqCst);
+            }
+        }
+        report(info);
+    }));
+}
+
+/// Writes a crash dump, which holds a summary of the panic and of every symbol, and per symbol
+/// the resting orders of its last snapshot and the operations its executor processed last. Both
+/// are written as recordings, so that the book can be rebuilt by replaying its resting orders,
+/// which are listed in time priority. The primary books are never read, the executors of other
+/// symbols keep changing them while the dump is written.
+///
+/// # Returns
+///
+/// * The directory the dump was written to.
+pub fn write(directory: &Path, symbols: &Symbols, reason: &str) -> Result<PathBuf, Box<dyn Error>> {
+    let now = generate_u128_timestamp();
+    let path = directory.join(now.to_string());
+    fs::create_dir_all(&path)?;
+    // the panicking thread may hold the symbols, so they are not waited for
+    let symbols: Vec<Arc<SymbolState>> = match symbols.try_read() {
+        Ok(symbols) => symbols.values().cloned().collect(),
+        Err(TryLockError::Poisoned(poisoned)) => poisoned.into_inner().values().cloned().collect(),
+        Err(TryLockError::WouldBlock) => Vec::new(),
+    };
+    let mut summary = format!("{}\n", reason);
+    for symbol_state in symbols {
+        let line = dump_symbol(&path, &symbol_state, now)?;
+        writeln!(summary, "{}", line)?;
+    }
+    fs::write(path.join(SUMMARY_FILE), summary)?;
+    Ok(path)
+}
+
+/// Writes the snapshot and the recent operations of a symbol, returning its summary line.
+fn dump_symbol(
+    path: &Path,
+    symbol_state: &SymbolState,
+    now: u128,
+) -> Result<String, Box<dyn Error>> {
+    let symbol = &symbol_state.symbol;
+    let secondary = symbol_state.orderbook_manager.get_secondary();
+    let resting_orders = secondary.resting_orders();
+    let book = resting_orders
+        .iter()
+        .map(|order| (now, Operation::Limit(*order)));
+    write_recording(&path.join(format!("{}.book.csv", symbol)), symbol, book)?;
+    let operations = symbol_state
+        .recent_operations
+        .as_ref()
+        .map(|recent_operations| recent_operations.latest())
+        .unwrap_or_default();
+    write_recording(
+        &path.join(format!("{}.operations.csv", symbol)),
+        symbol,
+        operations.iter().copied(),
+    )?;
+    // the snapshot task may hold the mark, so it is not waited for
+    let snapshot_operations = match symbol_state.last_snapshot.try_lock() {
+        Ok(mark) => mark.map(|mark| mark.operation_count),
+        Err(TryLockError::Poisoned(poisoned)) => {
+            poisoned.into_inner().map(|mark| mark.operation_count)
+        }
+        Err(TryLockError::WouldBlock) => None,
+    };
+    Ok(format!(
+        "{}: operations={} snapshot_operations={} resting_orders={} last_trade_price={} state_hash={:016x}",
+        symbol,
+        symbol_state.operation_count.load(Ordering::Relaxed),
+        snapshot_operations.map_or("none".to_string(), |count| count.to_string()),
+        resting_orders.len(),
+        secondary.get_last_trade_price(),
+        secondary.state_hash()
+    ))
+}
+
+fn write_recording(
+    path: &Path,
+    symbol: &str,
+    operations: impl Iterator<Item = (u128, Operation)>,
+) -> Result<(), Box<dyn Error>> {
+    let recorder = FlowRecorder::create(path)?;
+    for (timestamp, operation) in operations {
+        recorder.record(timestamp, symbol, "", &operation)?;
+    }
+    recorder.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::models::{LimitOrder, MatchingPolicy, Operation, Side};
+    use crate::core::types::{OrderId, Price, Quantity};
+    use crate::engine::accounts::ledger::SymbolAssets;
+    use crate::engine::constants::property_loader::{
+        ChannelCapacities, CrashDumpSettings, PersistenceProperties,
+    };
+    use crate::engine::persistence::crash_dump::{current_executor, run_executor, write};
+    use crate::engine::persistence::recording::read_recording;
+    use crate::engine::risk::resting_limits::RestingLimits;
+    use crate::engine::state::symbol_state::{SymbolSpecification, SymbolState};
+    use std::collections::HashMap;
+    use std::fs;
+    use std::sync::{Arc, RwLock};
+    use std::time::Duration;
+
+    #[test]
+    fn it_dumps_the_book_and_the_recent_operations() {
+        let directory =
+            std::env::temp_dir().join(format!("gemmy-crash-dump-{}", uuid::Uuid::new_v4()));
+        let persistence_properties = PersistenceProperties {
+            wal_enabled: false,
+            wal_directory: String::new(),
+            wal_checkpoint_interval: Duration::ZERO,
+            order_store_enabled: false,
+            order_store_path: String::new(),
+            outbox_path: String::new(),
+            audit_directory: None,
+            audit_max_file_size: 0,
+            recording_path: None,
+            traffic_recording_path: None,
+            replay_path: None,
+            replay_speed: 1.0,
+            snapshot_store: None,
+            crash_dump: Some(CrashDumpSettings {
+                directory: directory.to_string_lossy().to_string(),
+                operations: 10,
+            }),
+        };
+        let specification = SymbolSpecification {
+            tick_size: 1,
+            lot_size: 1,
+            queue_capacity: 10,
+            store_capacity: 100,
+            matching_policy: MatchingPolicy::PriceTime,
+            channel_capacities: ChannelCapacities::default(),
+            assets: SymbolAssets::from_symbol("ETHUSD"),
+            resting_limits: RestingLimits::default(),
+        };
+        let symbol_state =
+            SymbolState::init("ETHUSD", specification, "events", &persistence_properties).unwrap();
+        let batch = [
+            Operation::Limit(LimitOrder::new(
+                OrderId(1),
+                Price(100),
+                Quantity(10),
+                Side::Bid,
+            )),
+            Operation::Limit(LimitOrder::new(
+                OrderId(2),
+                Price(100),
+                Quantity(5),
+                Side::Bid,
+            )),
+            Operation::Cancel(OrderId(1)),
+        ];
+        let primary = symbol_state.orderbook_manager.get_primary();
+        for operation in batch {
+            unsafe { (*primary).execute(operation) };
+        }
+        symbol_state.orderbook_manager.snapshot();
+        // changes after the snapshot are left to the recent operations
+        unsafe { (*primary).execute(Operation::Cancel(OrderId(2))) };
+        let recent_operations = symbol_state.recent_operations.as_ref().unwrap();
+        recent_operations.record(7, &batch);
+        let symbols = Arc::new(RwLock::new(HashMap::from([(
+            "ETHUSD".to_string(),
+            Arc::new(symbol_state),
+        )])));
+
+        let path = write(&directory, &symbols, "executor panicked").unwrap();
+        let book = read_recording(path.join("ETHUSD.book.csv")).unwrap();
+        assert_eq!(book.len(), 1);
+        assert_eq!(book[0].operation.order_id(), 2);
+        let operations = read_recording(path.join("ETHUSD.operations.csv")).unwrap();
+        let operations: Vec<_> = operations
+            .iter()
+            .map(|recorded| (recorded.timestamp, recorded.operation.order_id()))
+            .collect();
+        assert_eq!(operations, vec![(7, 1), (7, 2), (7, 1)]);
+        let summary = fs::read_to_string(path.join("panic.txt")).unwrap();
+        assert!(summary.starts_with(
+            "executor panicked\nETHUSD: operations=0 snapshot_operations=none resting_orders=1"
+        ));
+        fs::remove_dir_all(directory).unwrap();
+    }
+
+    #[tokio::test]
+    async fn it_only_marks_executor_tasks() {
+        assert_eq!(current_executor(), None);
+        let symbol = run_executor("ETHUSD".to_string(), async { current_executor() }).await;
+        assert_eq!(symbol.as_deref(), Some("ETHUSD"));
+        let supervised = tokio::spawn(async { current_executor() });
+        assert_eq!(supervised.await.unwrap(), None);
+    }
+}
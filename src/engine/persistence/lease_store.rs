@@ -0,0 +1,210 @@
+use crate::engine::constants::property_loader::ElectionSettings;
+use base64::prelude::{Engine, BASE64_STANDARD};
+use reqwest::header::CONTENT_TYPE;
+use reqwest::{Client, StatusCode, Url};
+use serde_json::{json, Value};
+use std::time::Duration;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum LeaseStoreError {
+    #[error("invalid endpoint: {0}")]
+    Endpoint(String),
+    #[error(transparent)]
+    Http(#[from] reqwest::Error),
+    #[error("etcd responded with {status}: {body}")]
+    Status { status: StatusCode, body: String },
+    #[error("unexpected response from etcd: {0}")]
+    Response(String),
+}
+
+/// The server holding the leadership key.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Leader {
+    /// Address the leader advertised to its replicas.
+    pub address: String,
+    /// Revision the key was created at, which increases with every election and serves as the
+    /// fencing token of the leader.
+    pub revision: u64,
+}
+
+/// The outcome of a campaign for the leadership key.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Campaign {
+    /// The key was created under the lease at the passed revision.
+    Elected(u64),
+    /// Another lease holds the key, unless it expired before it could be read.
+    Lost(Option<Leader>),
+}
+
+/// The leadership key of an active/standby pair in etcd, accessed through its JSON gateway. The
+/// key is only created when absent and is bound to a lease, so that etcd deletes it once its
+/// holder stops renewing the lease.
+pub struct LeaseStore {
+    client: Client,
+    endpoint: Url,
+    key: String,
+}
+
+impl LeaseStore {
+    /// Requests time out after a third of the lease, so that a renewal can be retried before it
+    /// runs out.
+    pub fn new(settings: &ElectionSettings) -> Result<Self, LeaseStoreError> {
+        let endpoint =
+            Url::parse(&settings.endpoint).map_err(|e| LeaseStoreError::Endpoint(e.to_string()))?;
+        if endpoint.host_str().is_none() {
+            return Err(LeaseStoreError::Endpoint(settings.endpoint.clone()));
+        }
+        Ok(Self {
+            client: Client::builder().timeout(settings.lease_ttl / 3).build()?,
+            endpoint,
+            key: settings.key.clone(),
+        })
+    }
+
+    /// Grants a lease that expires after the passed time unless renewed, returning its id.
+    pub async fn grant(&self, ttl: Duration) -> Result<i64, LeaseStoreError> {
+        let response = self
+            .post("lease/grant", json!({ "TTL": ttl.as_secs().to_string() }))
+            .await?;
+        integer(&response, "/ID")
+    }
+
+    /// Creates the leadership key under the lease holding the address, unless another lease
+    /// holds it already.
+    pub async fn campaign(&self, lease: i64, address: &str) -> Result<Campaign, LeaseStoreError> {
+        let key = BASE64_STANDARD.encode(&self.key);
+        let request = json!({
+            "compare": [{
+                "key": key,
+                "target": "CREATE",
+                "result": "EQUAL",
+                "create_revision": "0",
+            }],
+            "success": [{
+                "request_put": {
+                    "key": key,
+                    "value": BASE64_STANDARD.encode(address),
+                    "lease": lease.to_string(),
+                },
+            }],
+            "failure": [{ "request_range": { "key": key } }],
+        });
+        campaign_outcome(&self.post("kv/txn", request).await?)
+    }
+
+    /// Renews the lease, returning whether it was still alive.
+    pub async fn keep_alive(&self, lease: i64) -> Result<bool, LeaseStoreError> {
+        let response = self
+            .post("lease/keepalive", json!({ "ID": lease.to_string() }))
+            .await?;
+        if let Some(error) = response.get("error") {
+            return Err(LeaseStoreError::Response(error.to_string()));
+        }
+        Ok(integer(&response, "/result/TTL").is_ok_and(|ttl| ttl > 0))
+    }
+
+    /// Revokes the lease, which deletes the leadership key if the lease held it.
+    pub async fn revoke(&self, lease: i64) -> Result<(), LeaseStoreError> {
+        self.post("lease/revoke", json!({ "ID": lease.to_string() }))
+            .await?;
+        Ok(())
+    }
+
+    /// Posts the request to the gateway, returning the first message of the response.
+    async fn post(&self, path: &str, request: Value) -> Result<Value, LeaseStoreError> {
+        let url = format!(
+            "{}/v3/{}",
+            self.endpoint.as_str().trim_end_matches('/'),
+            path
+        );
+        let response = self
+            .client
+            .post(url)
+            .header(CONTENT_TYPE, "application/json")
+            .body(request.to_string())
+            .send()
+            .await?;
+        let status = response.status();
+        let body = response.text().await?;
+        if !status.is_success() {
+            return Err(LeaseStoreError::Status { status, body });
+        }
+        // streaming calls such as the keepalive answer with one message per line
+        let message = body.lines().next().unwrap_or_default();
+        serde_json::from_str(message).map_err(|e| LeaseStoreError::Response(e.to_string()))
+    }
+}
+
+/// Reads the outcome of the transaction creating the leadership key.
+fn campaign_outcome(response: &Value) -> Result<Campaign, LeaseStoreError> {
+    if response["succeeded"].as_bool().unwrap_or(false) {
+        let revision = integer(response, "/header/revision")?;
+        return Ok(Campaign::Elected(revision as u64));
+    }
+    let Some(holder) = response.pointer("/responses/0/response_range/kvs/0") else {
+        return Ok(Campaign::Lost(None));
+    };
+    let value = holder["value"].as_str().unwrap_or_default();
+    let address = BASE64_STANDARD
+        .decode(value)
+        .ok()
+        .and_then(|address| String::from_utf8(address).ok())
+        .ok_or_else(|| LeaseStoreError::Response(format!("invalid leader address {}", value)))?;
+    Ok(Campaign::Lost(Some(Leader {
+        address,
+        revision: integer(holder, "/create_revision")? as u64,
+    })))
+}
+
+/// The gateway encodes 64 bit integers as strings.
+fn integer(value: &Value, pointer: &str) -> Result<i64, LeaseStoreError> {
+    match value.pointer(pointer) {
+        Some(Value::String(integer)) => integer.parse().ok(),
+        Some(Value::Number(integer)) => integer.as_i64(),
+        _ => None,
+    }
+    .ok_or_else(|| LeaseStoreError::Response(format!("missing {} in {}", pointer, value)))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::engine::persistence::lease_store::{campaign_outcome, Campaign, Leader};
+    use serde_json::json;
+
+    #[test]
+    fn it_reads_the_outcome_of_a_campaign() {
+        let elected = json!({
+            "header": { "revision": "42" },
+            "succeeded": true,
+            "responses": [{ "response_put": { "header": { "revision": "42" } } }],
+        });
+        assert_eq!(campaign_outcome(&elected).unwrap(), Campaign::Elected(42));
+
+        let lost = json!({
+            "header": { "revision": "57" },
+            "responses": [{
+                "response_range": {
+                    "kvs": [{
+                        "key": "Z2VtbXkvbGVhZGVy",
+                        "create_revision": "42",
+                        "mod_revision": "42",
+                        "value": "aHR0cDovL1s6OjFdOjUwMDUx",
+                        "lease": "7587862045532400649",
+                    }],
+                    "count": "1",
+                },
+            }],
+        });
+        assert_eq!(
+            campaign_outcome(&lost).unwrap(),
+            Campaign::Lost(Some(Leader {
+                address: "http://[::1]:50051".to_string(),
+                revision: 42,
+            }))
+        );
+
+        let expired = json!({ "responses": [{ "response_range": {} }] });
+        assert_eq!(campaign_outcome(&expired).unwrap(), Campaign::Lost(None));
+    }
+}
@@ -0,0 +1,11 @@
+pub mod audit_log;
+pub mod crash_dump;
+pub mod lease_store;
+pub mod order_store;
+pub mod outbox;
+pub mod recording;
+pub mod snapshot_store;
+#[cfg(test)]
+pub(crate) mod test_utils;
+pub mod traffic;
+pub mod wal;
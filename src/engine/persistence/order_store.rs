@@ -0,0 +1,336 @@
+use crate::core::models::{ExecutionResult, FillResult, LimitOrder, ModifyResult, Operation, Side};
+use crate::core::orderbook::OrderBook;
+use crate::core::types::{OrderId, Price, Quantity};
+use crate::engine::errors::EngineError;
+use sled::{Batch, Db};
+use std::collections::HashMap;
+use std::error::Error;
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// priority(8) + price(8) + quantity(8) + side(1) + expires_at(16), zero meaning no expiry
+const VALUE_SIZE: usize = 41;
+
+/// A disk backed mirror of the resting orders of the primary book.
+/// Every resting order is stored under its id together with a priority sequence, which preserves
+/// time priority within price levels so the book can be rebuilt without replaying its history.
+pub struct PersistentOrderStore {
+    db: Db,
+    next_priority: AtomicU64,
+}
+
+/// A difference found between the persisted orders and the in-memory book.
+#[derive(Debug, PartialEq)]
+pub enum StoreMismatch {
+    /// The order is persisted but does not rest in the book.
+    MissingInBook(u128),
+    /// The order rests in the book but is not persisted.
+    MissingInStore(u128),
+    /// The order exists in both but with different price, quantity or side.
+    Diverged(u128),
+}
+
+struct StoredOrder {
+    priority: u64,
+    order: LimitOrder,
+}
+
+impl PersistentOrderStore {
+    pub fn open(path: impl AsRef<Path>) -> Result<PersistentOrderStore, EngineError> {
+        let db = sled::open(path)?;
+        let mut next_priority = 0;
+        for entry in db.iter() {
+            let (key, value) = entry?;
+            let stored =
+                decode(&key, &value).ok_or(EngineError::Corrupted("persistent order store"))?;
+            next_priority = next_priority.max(stored.priority + 1);
+        }
+        Ok(PersistentOrderStore {
+            db,
+            next_priority: AtomicU64::new(next_priority),
+        })
+    }
+
+    /// Places every persisted order on the passed book in its original priority.
+    /// The book is expected to be empty. Orders that cross were persisted during a call, which is
+    /// started again before they are placed.
+    ///
+    /// # Returns
+    ///
+    /// * The number of orders restored.
+    pub fn restore(&self, orderbook: &mut OrderBook) -> Result<usize, Box<dyn Error>> {
+        let mut orders = self.load()?;
+        orders.sort_by_key(|stored| stored.priority);
+        let top = |side: Side| {
+            orders
+                .iter()
+                .filter(move |stored| stored.order.side == side)
+                .map(|stored| stored.order.price)
+        };
+        if let (Some(bid), Some(ask)) = (top(Side::Bid).max(), top(Side::Ask).min()) {
+            if bid >= ask {
+                orderbook.execute(Operation::Call);
+            }
+        }
+        for stored in &orders {
+            orderbook.execute(Operation::Limit(stored.order));
+        }
+        Ok(orders.len())
+    }
+
+    /// Cross-checks the persisted orders against the resting orders of the passed book.
+    ///
+    /// # Returns
+    ///
+    /// * Every [`StoreMismatch`] found, an empty vector means both are consistent.
+    pub fn verify(&self, orderbook: &OrderBook) -> Result<Vec<StoreMismatch>, Box<dyn Error>> {
+        let mut persisted: HashMap<u128, LimitOrder> = self
+            .load()?
+            .into_iter()
+            .map(|stored| (stored.order.id, stored.order))
+            .collect();
+        let mut mismatches = vec![];
+        for order in orderbook.resting_orders() {
+            match persisted.remove(&order.id) {
+                None => mismatches.push(StoreMismatch::MissingInStore(order.id)),
+                Some(stored) if stored != order => {
+                    mismatches.push(StoreMismatch::Diverged(order.id))
+                }
+                Some(_) => (),
+            }
+        }
+        mismatches.extend(persisted.into_keys().map(StoreMismatch::MissingInBook));
+        Ok(mismatches)
+    }
+
+    /// Replaces the persisted contents with the resting orders of the passed book.
+    pub fn resync(&self, orderbook: &OrderBook) -> Result<(), Box<dyn Error>> {
+        let mut batch = Batch::default();
+        for key in self.db.iter().keys() {
+            batch.remove(key?);
+        }
+        for (priority, order) in orderbook.resting_orders().into_iter().enumerate() {
+            batch.insert(
+                &order.id.to_be_bytes(),
+                &encode(priority as u64, &order)[..],
+            );
+        }
+        self.db.apply_batch(batch)?;
+        self.next_priority
+            .store(orderbook.resting_orders().len() as u64, Ordering::SeqCst);
+        self.db.flush()?;
+        Ok(())
+    }
+
+    /// Mirrors the changes produced by a batch of executions.
+    /// Every order touched by the results is looked up in the book after execution: resting orders are
+    /// written with their remaining quantity and orders that left the book are removed.
+    /// Orders that entered a price level within the batch receive a new priority in execution order.
+    pub fn apply<'a>(
+        &self,
+        results: impl IntoIterator<Item = &'a ExecutionResult>,
+        orderbook: &OrderBook,
+    ) -> sled::Result<()> {
+        let mut touched = vec![];
+        let mut priorities = HashMap::new();
+        for result in results {
+            for (id, enters_level) in affected_orders(result) {
+                if enters_level {
+                    priorities.insert(id, self.next_priority.fetch_add(1, Ordering::SeqCst));
+                }
+                touched.push(id);
+            }
+        }
+        touched.sort_unstable();
+        touched.dedup();
+
+        let mut batch = Batch::default();
+        for id in touched {
+            let key = id.to_be_bytes();
+            match orderbook.get_order(id) {
+                Some(order) => {
+                    let priority = match priorities.get(&id) {
+                        Some(priority) => *priority,
+                        None => match self.db.get(key)?.and_then(|v| decode(&key, &v)) {
+                            Some(stored) => stored.priority,
+                            None => self.next_priority.fetch_add(1, Ordering::SeqCst),
+                        },
+                    };
+                    batch.insert(&key, &encode(priority, &order)[..]);
+                }
+                None => batch.remove(&key),
+            }
+        }
+        self.db.apply_batch(batch)?;
+        self.db.flush()?;
+        Ok(())
+    }
+
+    fn load(&self) -> Result<Vec<StoredOrder>, Box<dyn Error>> {
+        let mut orders = vec![];
+        for entry in self.db.iter() {
+            let (key, value) = entry?;
+            orders.push(decode(&key, &value).ok_or("corrupted entry in persistent order store")?);
+        }
+        Ok(orders)
+    }
+}
+
+/// Lists the orders touched by an execution, flagging the ones that entered a price level.
+fn affected_orders(result: &ExecutionResult) -> Vec<(u128, bool)> {
+    match result {
+        ExecutionResult::Executed(fill_result) => fill_result_orders(fill_result),
+        ExecutionResult::Modified(ModifyResult::Created(fill_result)) => {
+            fill_result_orders(fill_result)
+        }
+        ExecutionResult::Modified(ModifyResult::Modified(id)) => vec![(*id, false)],
+        ExecutionResult::Modified(ModifyResult::Failed) => vec![],
+        ExecutionResult::Cancelled(id) => vec![(*id, false)],
+        ExecutionResult::Reduced(order) => vec![(order.id, false)],
+        ExecutionResult::Quoted(quote) => quote
+            .replaced
+            .iter()
+            .map(|id| (*id, false))
+            .chain(quote.fills.iter().map(|f| (f.matched_order_id, false)))
+            .chain(
+                quote
+                    .bid
+                    .iter()
+                    .chain(&quote.ask)
+                    .map(|order| (order.id, true)),
+            )
+            .collect(),
+        ExecutionResult::Uncrossed(fills) => fills
+            .iter()
+            .flat_map(|f| [(f.order_id, false), (f.matched_order_id, false)])
+            .collect(),
+        ExecutionResult::Called | ExecutionResult::Failed(_) => vec![],
+    }
+}
+
+fn fill_result_orders(fill_result: &FillResult) -> Vec<(u128, bool)> {
+    match fill_result {
+        FillResult::Created(order) => vec![(order.id, true)],
+        FillResult::Filled(fills) => fills.iter().map(|f| (f.matched_order_id, false)).collect(),
+        FillResult::PartiallyFilled(order, fills) => std::iter::once((order.id, true))
+            .chain(fills.iter().map(|f| (f.matched_order_id, false)))
+            .collect(),
+        FillResult::Failed => vec![],
+    }
+}
+
+fn encode(priority: u64, order: &LimitOrder) -> [u8; VALUE_SIZE] {
+    let mut value = [0u8; VALUE_SIZE];
+    value[0..8].copy_from_slice(&priority.to_be_bytes());
+    value[8..16].copy_from_slice(&order.price.to_be_bytes());
+    value[16..24].copy_from_slice(&order.quantity.to_be_bytes());
+    value[24] = order.side as u8;
+    value[25..41].copy_from_slice(&order.expires_at.unwrap_or(0).to_be_bytes());
+    value
+}
+
+fn decode(key: &[u8], value: &[u8]) -> Option<StoredOrder> {
+    if value.len() != VALUE_SIZE {
+        return None;
+    }
+    let side = match value[24] {
+        0 => Side::Bid,
+        1 => Side::Ask,
+        _ => return None,
+    };
+    let expires_at = u128::from_be_bytes(value[25..41].try_into().ok()?);
+    Some(StoredOrder {
+        priority: u64::from_be_bytes(value[0..8].try_into().ok()?),
+        order: LimitOrder {
+            expires_at: (expires_at != 0).then_some(expires_at),
+            ..LimitOrder::new(
+                OrderId(u128::from_be_bytes(key.try_into().ok()?)),
+                Price(u64::from_be_bytes(value[8..16].try_into().ok()?)),
+                Quantity(u64::from_be_bytes(value[16..24].try_into().ok()?)),
+                side,
+            )
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::models::{LimitOrder, MarketOrder, Operation, Side};
+    use crate::core::orderbook::OrderBook;
+    use crate::core::types::{OrderId, Price, Quantity};
+    use crate::engine::persistence::order_store::{PersistentOrderStore, StoreMismatch};
+    use crate::engine::persistence::test_utils::reopen;
+
+    #[test]
+    fn it_mirrors_and_restores_resting_orders() {
+        let path = std::env::temp_dir().join(format!("gemmy-store-{}", uuid::Uuid::new_v4()));
+        let mut book = OrderBook::default();
+        {
+            let store = PersistentOrderStore::open(&path).unwrap();
+            let operations = vec![
+                Operation::Limit(LimitOrder::new(
+                    OrderId(1),
+                    Price(100),
+                    Quantity(100),
+                    Side::Bid,
+                )),
+                Operation::Limit(LimitOrder::new(
+                    OrderId(2),
+                    Price(100),
+                    Quantity(50),
+                    Side::Bid,
+                )),
+                Operation::Limit(LimitOrder::new(
+                    OrderId(6),
+                    Price(100),
+                    Quantity(40),
+                    Side::Bid,
+                )),
+                Operation::Modify(LimitOrder::new(
+                    OrderId(2),
+                    Price(90),
+                    Quantity(50),
+                    Side::Bid,
+                )),
+                Operation::Modify(LimitOrder::new(
+                    OrderId(2),
+                    Price(100),
+                    Quantity(50),
+                    Side::Bid,
+                )),
+                Operation::Limit(
+                    LimitOrder::new(OrderId(3), Price(110), Quantity(70), Side::Ask)
+                        .with_expiry(1_000),
+                ),
+                Operation::Modify(LimitOrder::new(
+                    OrderId(1),
+                    Price(100),
+                    Quantity(60),
+                    Side::Bid,
+                )),
+                Operation::Market(MarketOrder::new(OrderId(4), Quantity(80), Side::Ask)),
+                Operation::Limit(LimitOrder::new(
+                    OrderId(5),
+                    Price(120),
+                    Quantity(10),
+                    Side::Ask,
+                )),
+                Operation::Cancel(OrderId(5)),
+            ];
+            let results: Vec<_> = operations.into_iter().map(|op| book.execute(op)).collect();
+            store.apply(&results, &book).unwrap();
+            assert!(store.verify(&book).unwrap().is_empty());
+        }
+        let store = reopen(|| PersistentOrderStore::open(&path));
+        let mut restored = OrderBook::default();
+        assert_eq!(store.restore(&mut restored).unwrap(), 3);
+        assert_eq!(restored.resting_orders(), book.resting_orders());
+        restored.execute(Operation::Cancel(OrderId(2)));
+        assert_eq!(
+            store.verify(&restored).unwrap(),
+            vec![StoreMismatch::MissingInBook(2)]
+        );
+        drop(store);
+        std::fs::remove_dir_all(path).unwrap();
+    }
+}
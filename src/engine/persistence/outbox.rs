@@ -0,0 +1,146 @@
+use crate::engine::errors::EngineError;
+use sled::{Batch, Db};
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use tokio::sync::Notify;
+
+/// An execution event waiting to be published, holding the raw protobuf payload, its schema name
+/// and the topic it belongs to.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OutboxEvent {
+    pub topic: String,
+    pub schema_name: String,
+    pub payload: Vec<u8>,
+}
+
+/// A durable, ordered queue of execution events that sits between the executor and kafka.
+/// The executor enqueues events and moves on, while the publisher task drains them in order
+/// and only removes an event once the broker has acknowledged it.
+pub struct Outbox {
+    db: Db,
+    next_sequence: AtomicU64,
+    pending: Notify,
+}
+
+impl Outbox {
+    pub fn open(path: impl AsRef<Path>) -> Result<Outbox, EngineError> {
+        let db = sled::open(path)?;
+        let next_sequence = match db.last()? {
+            Some((key, _)) => {
+                let key = key.as_ref().try_into();
+                u64::from_be_bytes(key.map_err(|_| EngineError::Corrupted("outbox"))?) + 1
+            }
+            None => 0,
+        };
+        Ok(Outbox {
+            db,
+            next_sequence: AtomicU64::new(next_sequence),
+            pending: Notify::new(),
+        })
+    }
+
+    /// Durably appends the events in order and wakes up the publisher.
+    pub fn enqueue(&self, events: Vec<OutboxEvent>) -> sled::Result<()> {
+        let mut batch = Batch::default();
+        for event in events {
+            let sequence = self.next_sequence.fetch_add(1, Ordering::SeqCst);
+            batch.insert(&sequence.to_be_bytes(), encode(&event));
+        }
+        self.db.apply_batch(batch)?;
+        self.db.flush()?;
+        self.pending.notify_one();
+        Ok(())
+    }
+
+    /// Returns up to `max` of the oldest events along with their sequence in the outbox.
+    pub fn peek(&self, max: usize) -> sled::Result<Vec<(u64, OutboxEvent)>> {
+        let mut events = Vec::with_capacity(max);
+        for entry in self.db.iter().take(max) {
+            let (key, value) = entry?;
+            if let (Ok(key), Some(event)) = (key.as_ref().try_into(), decode(&value)) {
+                events.push((u64::from_be_bytes(key), event));
+            }
+        }
+        Ok(events)
+    }
+
+    /// Removes a published event from the outbox.
+    pub fn acknowledge(&self, sequence: u64) -> sled::Result<()> {
+        self.db.remove(sequence.to_be_bytes())?;
+        Ok(())
+    }
+
+    /// Returns the number of events waiting to be published.
+    pub fn len(&self) -> usize {
+        self.db.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.db.is_empty()
+    }
+
+    /// Waits until new events are enqueued.
+    pub async fn wait_for_events(&self) {
+        self.pending.notified().await
+    }
+}
+
+fn encode(event: &OutboxEvent) -> Vec<u8> {
+    let mut value =
+        Vec::with_capacity(2 + event.topic.len() + event.schema_name.len() + event.payload.len());
+    value.push(event.topic.len() as u8);
+    value.extend_from_slice(event.topic.as_bytes());
+    value.push(event.schema_name.len() as u8);
+    value.extend_from_slice(event.schema_name.as_bytes());
+    value.extend_from_slice(&event.payload);
+    value
+}
+
+fn decode(value: &[u8]) -> Option<OutboxEvent> {
+    let (topic, value) = decode_str(value)?;
+    let (schema_name, value) = decode_str(value)?;
+    Some(OutboxEvent {
+        topic: topic.to_string(),
+        schema_name: schema_name.to_string(),
+        payload: value.to_vec(),
+    })
+}
+
+/// Splits a length prefixed string off the front of the value.
+fn decode_str(value: &[u8]) -> Option<(&str, &[u8])> {
+    let length = *value.first()? as usize;
+    let string = std::str::from_utf8(value.get(1..1 + length)?).ok()?;
+    Some((string, &value[1 + length..]))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::engine::persistence::outbox::{Outbox, OutboxEvent};
+    use crate::engine::persistence::test_utils::reopen;
+
+    fn event(payload: u8) -> OutboxEvent {
+        OutboxEvent {
+            topic: "orderbook-events-ethusd".to_string(),
+            schema_name: "CreateOrder".to_string(),
+            payload: vec![payload; 3],
+        }
+    }
+
+    #[test]
+    fn it_keeps_unacknowledged_events_in_order_across_restarts() {
+        let path = std::env::temp_dir().join(format!("gemmy-outbox-{}", uuid::Uuid::new_v4()));
+        {
+            let outbox = Outbox::open(&path).unwrap();
+            outbox.enqueue(vec![event(1), event(2)]).unwrap();
+            outbox.enqueue(vec![event(3)]).unwrap();
+            let (sequence, _) = outbox.peek(1).unwrap()[0].clone();
+            outbox.acknowledge(sequence).unwrap();
+        }
+        let outbox = reopen(|| Outbox::open(&path));
+        outbox.enqueue(vec![event(4)]).unwrap();
+        let pending: Vec<_> = outbox.peek(10).unwrap().into_iter().collect();
+        assert_eq!(pending, vec![(1, event(2)), (2, event(3)), (3, event(4))]);
+        drop(outbox);
+        std::fs::remove_dir_all(path).unwrap();
+    }
+}
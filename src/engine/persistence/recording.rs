@@ -0,0 +1,589 @@
+use crate::core::models::{LimitOrder, MarketOrder, Operation, Side};
+use crate::core::types::{OrderId, Price, Quantity};
+use std::error::Error;
+use std::fs::File;
+use std::path::Path;
+use std::sync::Mutex;
+use tracing::{error, info};
+
+const CSV_HEADER: [&str; 9] = [
+    "timestamp",
+    "symbol",
+    "account",
+    "operation",
+    "order_id",
+    "side",
+    "price",
+    "quantity",
+    "expires_at",
+];
+
+/// An operation accepted by the dispatcher, as it is read back from a recording.
+#[derive(Debug, Clone)]
+pub struct RecordedOperation {
+    /// Nanoseconds since the unix epoch the operation was accepted at.
+    pub timestamp: u128,
+    pub symbol: String,
+    pub account: String,
+    pub operation: Operation,
+}
+
+/// File format of a recording, told apart by the extension of its path.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RecordingFormat {
+    Csv,
+    /// Only available with the `parquet` feature.
+    Parquet,
+}
+
+impl RecordingFormat {
+    pub fn of(path: impl AsRef<Path>) -> Self {
+        match path
+            .as_ref()
+            .extension()
+            .and_then(|extension| extension.to_str())
+        {
+            Some("parquet") => RecordingFormat::Parquet,
+            _ => RecordingFormat::Csv,
+        }
+    }
+}
+
+/// The columns every format stores, with the fields an operation does not have left at zero.
+struct Row {
+    kind: &'static str,
+    order_id: u128,
+    side: Side,
+    price: u64,
+    quantity: u64,
+    expires_at: u128,
+}
+
+impl Row {
+    /// The rows of an operation, quotes take a row for each side with the bid written first.
+    fn of(operation: &Operation) -> Vec<Self> {
+        let (kind, order_id, side, price, quantity, expires_at) = match operation {
+            Operation::Quote { bid, ask } => {
+                return vec![Self::order("quote_bid", bid), Self::order("quote", ask)]
+            }
+            Operation::Limit(order) | Operation::Modify(order) => (
+                match operation {
+                    Operation::Limit(_) => "limit",
+                    _ => "modify",
+                },
+                order.id,
+                order.side,
+                order.price,
+                order.quantity,
+                order.expires_at.unwrap_or(0),
+            ),
+            Operation::Market(order) => ("market", order.id, order.side, 0, order.quantity, 0),
+            Operation::Cancel(OrderId(id)) => ("cancel", *id, Side::Bid, 0, 0, 0),
+            Operation::Reduce {
+                id: OrderId(id),
+                quantity_delta,
+            } => ("reduce", *id, Side::Bid, 0, *quantity_delta, 0),
+            Operation::Call => ("call", 0, Side::Bid, 0, 0, 0),
+            Operation::Uncross => ("uncross", 0, Side::Bid, 0, 0, 0),
+        };
+        vec![Self {
+            kind,
+            order_id,
+            side,
+            price,
+            quantity,
+            expires_at,
+        }]
+    }
+
+    fn order(kind: &'static str, order: &LimitOrder) -> Self {
+        Self {
+            kind,
+            order_id: order.id,
+            side: order.side,
+            price: order.price,
+            quantity: order.quantity,
+            expires_at: order.expires_at.unwrap_or(0),
+        }
+    }
+
+    /// Reads the operation of a row back. The bid row of a quote is kept in `quote_bid` until the
+    /// ask row following it completes the quote, so it reads as no operation.
+    fn operation(
+        kind: &str,
+        order_id: u128,
+        side: Side,
+        price: u64,
+        quantity: u64,
+        expires_at: u128,
+        quote_bid: &mut Option<LimitOrder>,
+    ) -> Result<Option<Operation>, Box<dyn Error>> {
+        let mut limit_order =
+            LimitOrder::new(OrderId(order_id), Price(price), Quantity(quantity), side);
+        if expires_at != 0 {
+            limit_order = limit_order.with_expiry(expires_at);
+        }
+        if quote_bid.is_some() != (kind == "quote") {
+            return Err(format!("{} row recorded out of place", kind).into());
+        }
+        Ok(Some(match kind {
+            "limit" => Operation::Limit(limit_order),
+            "modify" => Operation::Modify(limit_order),
+            "market" => Operation::Market(MarketOrder::new(
+                OrderId(order_id),
+                Quantity(quantity),
+                side,
+            )),
+            "cancel" => Operation::Cancel(OrderId(order_id)),
+            "reduce" => Operation::Reduce {
+                id: OrderId(order_id),
+                quantity_delta: quantity,
+            },
+            "call" => Operation::Call,
+            "uncross" => Operation::Uncross,
+            "quote_bid" => {
+                *quote_bid = Some(limit_order);
+                return Ok(None);
+            }
+            "quote" => Operation::Quote {
+                bid: quote_bid.take().unwrap(),
+                ask: limit_order,
+            },
+            kind => return Err(format!("unknown recorded operation: {}", kind).into()),
+        }))
+    }
+}
+
+enum RecordingWriter {
+    Csv(csv::Writer<File>),
+    #[cfg(feature = "parquet")]
+    Parquet(parquet_format::ParquetRecordingWriter),
+}
+
+/// Records the operations accepted by the dispatcher, so that the flow can later be replayed
+/// through the engine or a simulation. CSV recordings are written as operations arrive, while
+/// parquet recordings are written in row groups and only complete once the recorder is finished.
+pub struct FlowRecorder {
+    writer: Mutex<Option<RecordingWriter>>,
+}
+
+impl FlowRecorder {
+    /// Creates the recording at the path, replacing any file already there.
+    pub fn create(path: impl AsRef<Path>) -> Result<Self, Box<dyn Error>> {
+        let path = path.as_ref();
+        let writer = match RecordingFormat::of(path) {
+            RecordingFormat::Csv => {
+                let mut writer = csv::Writer::from_path(path)?;
+                writer.write_record(CSV_HEADER)?;
+                writer.flush()?;
+                RecordingWriter::Csv(writer)
+            }
+            #[cfg(feature = "parquet")]
+            RecordingFormat::Parquet => RecordingWriter::Parquet(
+                parquet_format::ParquetRecordingWriter::create(File::create(path)?)?,
+            ),
+            #[cfg(not(feature = "parquet"))]
+            RecordingFormat::Parquet => {
+                return Err("parquet recordings require the parquet feature".into())
+            }
+        };
+        info!("recording order flow to {:?}", path);
+        Ok(Self {
+            writer: Mutex::new(Some(writer)),
+        })
+    }
+
+    /// Appends an operation to the recording.
+    ///
+    /// # Arguments
+    ///
+    /// * `timestamp` - Nanoseconds since the unix epoch the operation was accepted at.
+    pub fn record(
+        &self,
+        timestamp: u128,
+        symbol: &str,
+        account: &str,
+        operation: &Operation,
+    ) -> Result<(), Box<dyn Error>> {
+        let rows = Row::of(operation);
+        match self.writer.lock().unwrap().as_mut() {
+            Some(RecordingWriter::Csv(writer)) => {
+                for row in rows {
+                    writer.write_record([
+                        timestamp.to_string().as_str(),
+                        symbol,
+                        account,
+                        row.kind,
+                        format!("{:032x}", row.order_id).as_str(),
+                        side_name(row.side),
+                        row.price.to_string().as_str(),
+                        row.quantity.to_string().as_str(),
+                        row.expires_at.to_string().as_str(),
+                    ])?;
+                }
+                writer.flush()?;
+            }
+            #[cfg(feature = "parquet")]
+            Some(RecordingWriter::Parquet(writer)) => {
+                for row in rows {
+                    writer.push(timestamp, symbol, account, row)?
+                }
+            }
+            None => return Err("recording is already finished".into()),
+        }
+        Ok(())
+    }
+
+    /// Writes out whatever is still buffered and closes the recording, after which nothing more
+    /// is recorded.
+    pub fn finish(&self) -> Result<(), Box<dyn Error>> {
+        match self.writer.lock().unwrap().take() {
+            Some(RecordingWriter::Csv(mut writer)) => writer.flush()?,
+            #[cfg(feature = "parquet")]
+            Some(RecordingWriter::Parquet(writer)) => writer.finish()?,
+            None => (),
+        }
+        Ok(())
+    }
+}
+
+impl Drop for FlowRecorder {
+    fn drop(&mut self) {
+        if let Err(e) = self.finish() {
+            error!("failed to finish recording: {}", e);
+        }
+    }
+}
+
+/// Reads a recording made by the [`FlowRecorder`], in the order it was recorded.
+pub fn read_recording(path: impl AsRef<Path>) -> Result<Vec<RecordedOperation>, Box<dyn Error>> {
+    let path = path.as_ref();
+    match RecordingFormat::of(path) {
+        RecordingFormat::Csv => {
+            let mut reader = csv::Reader::from_path(path)?;
+            let mut recorded = vec![];
+            let mut quote_bid = None;
+            for record in reader.records() {
+                let record = record?;
+                let field = |index: usize| record.get(index).unwrap_or_default();
+                let operation = Row::operation(
+                    field(3),
+                    u128::from_str_radix(field(4), 16)?,
+                    parse_side(field(5))?,
+                    field(6).parse()?,
+                    field(7).parse()?,
+                    field(8).parse()?,
+                    &mut quote_bid,
+                )?;
+                if let Some(operation) = operation {
+                    recorded.push(RecordedOperation {
+                        timestamp: field(0).parse()?,
+                        symbol: field(1).to_string(),
+                        account: field(2).to_string(),
+                        operation,
+                    });
+                }
+            }
+            Ok(recorded)
+        }
+        #[cfg(feature = "parquet")]
+        RecordingFormat::Parquet => parquet_format::read(File::open(path)?),
+        #[cfg(not(feature = "parquet"))]
+        RecordingFormat::Parquet => Err("parquet recordings require the parquet feature".into()),
+    }
+}
+
+/// Reads a plain list of limit orders with a `trader_id,side,price,qty` header, such as
+/// `resources/orders.csv`, numbering the orders from zero.
+pub fn read_order_list(path: impl AsRef<Path>) -> Result<Vec<Operation>, Box<dyn Error>> {
+    let mut reader = csv::Reader::from_path(path)?;
+    let mut operations = vec![];
+    for (id, record) in reader.deserialize::<(u64, Side, u64, u64)>().enumerate() {
+        let (_, side, price, quantity) = record?;
+        operations.push(Operation::Limit(LimitOrder::new(
+            OrderId(id as u128),
+            Price(price),
+            Quantity(quantity),
+            side,
+        )));
+    }
+    Ok(operations)
+}
+
+fn side_name(side: Side) -> &'static str {
+    match side {
+        Side::Bid => "bid",
+        Side::Ask => "ask",
+    }
+}
+
+fn parse_side(side: &str) -> Result<Side, Box<dyn Error>> {
+    match side {
+        "bid" => Ok(Side::Bid),
+        "ask" => Ok(Side::Ask),
+        side => Err(format!("unknown side: {}", side).into()),
+    }
+}
+
+#[cfg(feature = "parquet")]
+mod parquet_format {
+    use crate::core::models::Side;
+    use crate::engine::persistence::recording::{RecordedOperation, Row};
+    use parquet::basic::Compression;
+    use parquet::data_type::{
+        ByteArray, ByteArrayType, FixedLenByteArray, FixedLenByteArrayType, Int32Type, Int64Type,
+    };
+    use parquet::file::properties::WriterProperties;
+    use parquet::file::reader::{FileReader, SerializedFileReader};
+    use parquet::file::writer::SerializedFileWriter;
+    use parquet::record::RowAccessor;
+    use parquet::schema::parser::parse_message_type;
+    use std::error::Error;
+    use std::fs::File;
+    use std::sync::Arc;
+
+    /// Rows buffered before they are written out as a row group.
+    const ROW_GROUP_SIZE: usize = 65_536;
+    // timestamps and expiries are kept in nanoseconds, which fit into 64 bits until 2262
+    const SCHEMA: &str = "
+        message recording {
+            REQUIRED INT64 timestamp;
+            REQUIRED BYTE_ARRAY symbol (UTF8);
+            REQUIRED BYTE_ARRAY account (UTF8);
+            REQUIRED BYTE_ARRAY operation (UTF8);
+            REQUIRED FIXED_LEN_BYTE_ARRAY (16) order_id;
+            REQUIRED INT32 side;
+            REQUIRED INT64 price;
+            REQUIRED INT64 quantity;
+            REQUIRED INT64 expires_at;
+        }
+    ";
+
+    #[derive(Default)]
+    struct Columns {
+        timestamp: Vec<i64>,
+        symbol: Vec<ByteArray>,
+        account: Vec<ByteArray>,
+        operation: Vec<ByteArray>,
+        order_id: Vec<FixedLenByteArray>,
+        side: Vec<i32>,
+        price: Vec<i64>,
+        quantity: Vec<i64>,
+        expires_at: Vec<i64>,
+    }
+
+    pub struct ParquetRecordingWriter {
+        writer: SerializedFileWriter<File>,
+        columns: Columns,
+    }
+
+    impl ParquetRecordingWriter {
+        pub fn create(file: File) -> Result<Self, Box<dyn Error>> {
+            let properties = WriterProperties::builder()
+                .set_compression(Compression::SNAPPY)
+                .build();
+            let writer = SerializedFileWriter::new(
+                file,
+                Arc::new(parse_message_type(SCHEMA)?),
+                Arc::new(properties),
+            )?;
+            Ok(Self {
+                writer,
+                columns: Columns::default(),
+            })
+        }
+
+        pub fn push(
+            &mut self,
+            timestamp: u128,
+            symbol: &str,
+            account: &str,
+            row: Row,
+        ) -> Result<(), Box<dyn Error>> {
+            let columns = &mut self.columns;
+            columns.timestamp.push(timestamp as i64);
+            columns.symbol.push(symbol.into());
+            columns.account.push(account.into());
+            columns.operation.push(row.kind.into());
+            columns
+                .order_id
+                .push(ByteArray::from(row.order_id.to_be_bytes().to_vec()).into());
+            columns.side.push(row.side as i32);
+            columns.price.push(row.price as i64);
+            columns.quantity.push(row.quantity as i64);
+            columns.expires_at.push(row.expires_at as i64);
+            if columns.timestamp.len() >= ROW_GROUP_SIZE {
+                self.write_row_group()?;
+            }
+            Ok(())
+        }
+
+        pub fn finish(mut self) -> Result<(), Box<dyn Error>> {
+            self.write_row_group()?;
+            self.writer.close()?;
+            Ok(())
+        }
+
+        fn write_row_group(&mut self) -> Result<(), Box<dyn Error>> {
+            if self.columns.timestamp.is_empty() {
+                return Ok(());
+            }
+            let columns = std::mem::take(&mut self.columns);
+            let mut row_group = self.writer.next_row_group()?;
+            let mut index = 0;
+            while let Some(mut column) = row_group.next_column()? {
+                match index {
+                    0 => write::<Int64Type>(&mut column, &columns.timestamp)?,
+                    1 => write::<ByteArrayType>(&mut column, &columns.symbol)?,
+                    2 => write::<ByteArrayType>(&mut column, &columns.account)?,
+                    3 => write::<ByteArrayType>(&mut column, &columns.operation)?,
+                    4 => write::<FixedLenByteArrayType>(&mut column, &columns.order_id)?,
+                    5 => write::<Int32Type>(&mut column, &columns.side)?,
+                    6 => write::<Int64Type>(&mut column, &columns.price)?,
+                    7 => write::<Int64Type>(&mut column, &columns.quantity)?,
+                    _ => write::<Int64Type>(&mut column, &columns.expires_at)?,
+                }
+                column.close()?;
+                index += 1;
+            }
+            row_group.close()?;
+            Ok(())
+        }
+    }
+
+    fn write<T: parquet::data_type::DataType>(
+        column: &mut parquet::file::writer::SerializedColumnWriter<'_>,
+        values: &[T::T],
+    ) -> Result<(), Box<dyn Error>> {
+        column.typed::<T>().write_batch(values, None, None)?;
+        Ok(())
+    }
+
+    pub fn read(file: File) -> Result<Vec<RecordedOperation>, Box<dyn Error>> {
+        let reader = SerializedFileReader::new(file)?;
+        let mut recorded = vec![];
+        let mut quote_bid = None;
+        for row in reader.get_row_iter(None)? {
+            let row = row?;
+            let order_id: [u8; 16] = row.get_bytes(4)?.data().try_into()?;
+            let side = match row.get_int(5)? {
+                0 => Side::Bid,
+                _ => Side::Ask,
+            };
+            let operation = Row::operation(
+                row.get_string(3)?,
+                u128::from_be_bytes(order_id),
+                side,
+                row.get_long(6)? as u64,
+                row.get_long(7)? as u64,
+                row.get_long(8)? as u128,
+                &mut quote_bid,
+            )?;
+            if let Some(operation) = operation {
+                recorded.push(RecordedOperation {
+                    timestamp: row.get_long(0)? as u128,
+                    symbol: row.get_string(1)?.clone(),
+                    account: row.get_string(2)?.clone(),
+                    operation,
+                });
+            }
+        }
+        Ok(recorded)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::models::{LimitOrder, MarketOrder, Operation, Side};
+    use crate::core::types::{OrderId, Price, Quantity};
+    use crate::engine::persistence::recording::{read_order_list, read_recording, FlowRecorder};
+
+    fn round_trip(extension: &str) {
+        let path = std::env::temp_dir().join(format!(
+            "gemmy-recording-{}.{}",
+            uuid::Uuid::new_v4(),
+            extension
+        ));
+        let operations = [
+            Operation::Limit(
+                LimitOrder::new(OrderId(u128::MAX), Price(100), Quantity(5), Side::Ask)
+                    .with_expiry(7),
+            ),
+            Operation::Market(MarketOrder::new(OrderId(2), Quantity(3), Side::Bid)),
+            Operation::Modify(LimitOrder::new(
+                OrderId(u128::MAX),
+                Price(101),
+                Quantity(4),
+                Side::Ask,
+            )),
+            Operation::Quote {
+                bid: LimitOrder::new(OrderId(3), Price(99), Quantity(2), Side::Bid),
+                ask: LimitOrder::new(OrderId(4), Price(102), Quantity(0), Side::Ask).with_expiry(9),
+            },
+            Operation::Cancel(OrderId(u128::MAX)),
+        ];
+        let recorder = FlowRecorder::create(&path).unwrap();
+        for (timestamp, operation) in operations.iter().enumerate() {
+            recorder
+                .record(timestamp as u128, "ETHUSD", "trader, \"one\"", operation)
+                .unwrap();
+        }
+        recorder.finish().unwrap();
+        assert!(recorder
+            .record(4, "ETHUSD", "", &Operation::Cancel(OrderId(1)))
+            .is_err());
+
+        let recorded = read_recording(&path).unwrap();
+        assert_eq!(recorded.len(), operations.len());
+        for (timestamp, (recorded, operation)) in recorded.iter().zip(operations).enumerate() {
+            assert_eq!(recorded.timestamp, timestamp as u128);
+            assert_eq!(recorded.symbol, "ETHUSD");
+            assert_eq!(recorded.account, "trader, \"one\"");
+            assert_eq!(
+                format!("{:?}", recorded.operation),
+                format!("{:?}", operation)
+            );
+        }
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn it_round_trips_csv_recordings() {
+        round_trip("csv");
+
+        let path = std::env::temp_dir().join(format!("gemmy-orders-{}.csv", uuid::Uuid::new_v4()));
+        std::fs::write(
+            &path,
+            "trader_id,side,price,qty\n8,Bid,4799,500\n4,Ask,4801,800\n",
+        )
+        .unwrap();
+        let orders = read_order_list(&path).unwrap();
+        assert_eq!(
+            format!("{:?}", orders),
+            format!(
+                "{:?}",
+                [
+                    Operation::Limit(LimitOrder::new(
+                        OrderId(0),
+                        Price(4799),
+                        Quantity(500),
+                        Side::Bid
+                    )),
+                    Operation::Limit(LimitOrder::new(
+                        OrderId(1),
+                        Price(4801),
+                        Quantity(800),
+                        Side::Ask
+                    ))
+                ]
+            )
+        );
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[cfg(feature = "parquet")]
+    #[test]
+    fn it_round_trips_parquet_recordings() {
+        round_trip("parquet");
+    }
+}
@@ -0,0 +1,381 @@
+use crate::core::models::LimitOrder;
+use crate::engine::constants::property_loader::SnapshotStoreSettings;
+use crate::engine::persistence::wal::{decode_checkpoint, encode_checkpoint};
+use crate::engine::utils::time::generate_u128_timestamp;
+use reqwest::{Client, Method, StatusCode, Url};
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+
+const SIGNING_ALGORITHM: &str = "AWS4-HMAC-SHA256";
+const SIGNED_HEADERS: &str = "host;x-amz-content-sha256;x-amz-date";
+const SNAPSHOT_EXTENSION: &str = ".snapshot";
+
+#[derive(Debug, Error)]
+pub enum SnapshotStoreError {
+    #[error("invalid endpoint: {0}")]
+    Endpoint(String),
+    #[error(transparent)]
+    Http(#[from] reqwest::Error),
+    #[error("object storage responded with {status}: {body}")]
+    Status { status: StatusCode, body: String },
+    /// A snapshot in the bucket could not be decoded.
+    #[error("corrupted snapshot {key}: {reason}")]
+    Corrupted { key: String, reason: String },
+}
+
+/// The resting orders of a book at a point in time, as uploaded to the bucket.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BookSnapshot {
+    /// Nanoseconds since the unix epoch at which the snapshot was taken.
+    pub taken_at: u128,
+    /// Operations the executor had executed when the snapshot was taken.
+    pub operation_count: u64,
+    pub last_trade_price: u64,
+    /// Whether the book was in a call, in which case its orders may cross.
+    pub call: bool,
+    pub orders: Vec<LimitOrder>,
+}
+
+/// Versioned book snapshots in an S3 compatible bucket. Every upload is stored under a new key
+/// made of the symbol and the time the snapshot was taken, so that earlier versions are kept
+/// until the lifecycle rules of the bucket expire them.
+pub struct SnapshotStore {
+    client: Client,
+    endpoint: Url,
+    settings: SnapshotStoreSettings,
+}
+
+impl SnapshotStore {
+    pub fn new(settings: SnapshotStoreSettings) -> Result<Self, SnapshotStoreError> {
+        let endpoint = Url::parse(&settings.endpoint)
+            .map_err(|e| SnapshotStoreError::Endpoint(e.to_string()))?;
+        if endpoint.host_str().is_none() {
+            return Err(SnapshotStoreError::Endpoint(settings.endpoint));
+        }
+        Ok(Self {
+            client: Client::new(),
+            endpoint,
+            settings,
+        })
+    }
+
+    /// Uploads the snapshot of the symbol, returning the key it is stored under.
+    pub async fn upload(
+        &self,
+        symbol: &str,
+        snapshot: &BookSnapshot,
+    ) -> Result<String, SnapshotStoreError> {
+        let key = snapshot_key(&self.settings.prefix, symbol, snapshot.taken_at);
+        let body = encode_checkpoint(
+            snapshot.operation_count,
+            snapshot.last_trade_price,
+            snapshot.call,
+            &snapshot.orders,
+        );
+        self.send(Method::PUT, &key, &[], body).await?;
+        Ok(key)
+    }
+
+    /// The most recent snapshot of the symbol, if any was uploaded.
+    pub async fn latest(&self, symbol: &str) -> Result<Option<BookSnapshot>, SnapshotStoreError> {
+        let prefix = symbol_prefix(&self.settings.prefix, symbol);
+        let Some(key) = self.list(&prefix).await?.into_iter().max() else {
+            return Ok(None);
+        };
+        let corrupted = |reason: String| SnapshotStoreError::Corrupted {
+            key: key.clone(),
+            reason,
+        };
+        let taken_at = key
+            .strip_prefix(&prefix)
+            .and_then(|name| name.strip_suffix(SNAPSHOT_EXTENSION))
+            .and_then(|name| name.parse().ok())
+            .ok_or_else(|| corrupted("unexpected key".to_string()))?;
+        let body = self.send(Method::GET, &key, &[], Vec::new()).await?;
+        let (operation_count, last_trade_price, call, orders) =
+            decode_checkpoint(&body).map_err(|e| corrupted(e.to_string()))?;
+        Ok(Some(BookSnapshot {
+            taken_at,
+            operation_count,
+            last_trade_price,
+            call,
+            orders,
+        }))
+    }
+
+    /// Every key starting with the prefix, following the pages of the listing.
+    async fn list(&self, prefix: &str) -> Result<Vec<String>, SnapshotStoreError> {
+        let mut keys = Vec::new();
+        let mut continuation_token = None;
+        loop {
+            let mut query = vec![
+                ("list-type", "2".to_string()),
+                ("prefix", prefix.to_string()),
+            ];
+            if let Some(token) = continuation_token.take() {
+                query.push(("continuation-token", token));
+            }
+            let body = self.send(Method::GET, "", &query, Vec::new()).await?;
+            let listing = String::from_utf8_lossy(&body);
+            keys.extend(xml_elements(&listing, "Key"));
+            continuation_token = xml_elements(&listing, "NextContinuationToken").pop();
+            if xml_elements(&listing, "IsTruncated") != ["true"] || continuation_token.is_none() {
+                return Ok(keys);
+            }
+        }
+    }
+
+    /// Sends a signed request for the key of the bucket, returning the body of the response.
+    async fn send(
+        &self,
+        method: Method,
+        key: &str,
+        query: &[(&str, String)],
+        body: Vec<u8>,
+    ) -> Result<Vec<u8>, SnapshotStoreError> {
+        let path = format!(
+            "{}/{}/{}",
+            self.endpoint.path().trim_end_matches('/'),
+            uri_encode(&self.settings.bucket, false),
+            uri_encode(key, true)
+        );
+        let mut query: Vec<(String, String)> = query
+            .iter()
+            .map(|(name, value)| (uri_encode(name, false), uri_encode(value, false)))
+            .collect();
+        query.sort();
+        let query = query
+            .iter()
+            .map(|(name, value)| format!("{}={}", name, value))
+            .collect::<Vec<_>>()
+            .join("&");
+        let host = match self.endpoint.port() {
+            Some(port) => format!("{}:{}", self.endpoint.host_str().unwrap_or_default(), port),
+            None => self.endpoint.host_str().unwrap_or_default().to_string(),
+        };
+        let payload_hash = hex(&Sha256::digest(&body));
+        let amz_date = amz_date(generate_u128_timestamp());
+        let authorization = self.authorization(
+            method.as_str(),
+            &path,
+            &query,
+            &host,
+            &payload_hash,
+            &amz_date,
+        );
+
+        let mut url = self.endpoint.clone();
+        url.set_path(&path);
+        url.set_query((!query.is_empty()).then_some(query.as_str()));
+        let response = self
+            .client
+            .request(method, url)
+            .header("x-amz-content-sha256", payload_hash)
+            .header("x-amz-date", amz_date)
+            .header("authorization", authorization)
+            .body(body)
+            .send()
+            .await?;
+        let status = response.status();
+        let body = response.bytes().await?.to_vec();
+        if !status.is_success() {
+            return Err(SnapshotStoreError::Status {
+                status,
+                body: String::from_utf8_lossy(&body).to_string(),
+            });
+        }
+        Ok(body)
+    }
+
+    /// Signs the request with AWS signature version 4.
+    fn authorization(
+        &self,
+        method: &str,
+        path: &str,
+        query: &str,
+        host: &str,
+        payload_hash: &str,
+        amz_date: &str,
+    ) -> String {
+        let canonical_request = format!(
+            "{}\n{}\n{}\nhost:{}\nx-amz-content-sha256:{}\nx-amz-date:{}\n\n{}\n{}",
+            method, path, query, host, payload_hash, amz_date, SIGNED_HEADERS, payload_hash
+        );
+        let date = &amz_date[..8];
+        let scope = format!("{}/{}/s3/aws4_request", date, self.settings.region);
+        let string_to_sign = format!(
+            "{}\n{}\n{}\n{}",
+            SIGNING_ALGORITHM,
+            amz_date,
+            scope,
+            hex(&Sha256::digest(canonical_request.as_bytes()))
+        );
+        let key = signing_key(&self.settings.secret_key, date, &self.settings.region, "s3");
+        format!(
+            "{} Credential={}/{}, SignedHeaders={}, Signature={}",
+            SIGNING_ALGORITHM,
+            self.settings.access_key,
+            scope,
+            SIGNED_HEADERS,
+            hex(&hmac_sha256(&key, string_to_sign.as_bytes()))
+        )
+    }
+}
+
+fn symbol_prefix(prefix: &str, symbol: &str) -> String {
+    match prefix.trim_matches('/') {
+        "" => format!("{}/", symbol),
+        prefix => format!("{}/{}/", prefix, symbol),
+    }
+}
+
+/// The key of a snapshot, padded so that the keys of a symbol sort by the time they were taken.
+fn snapshot_key(prefix: &str, symbol: &str, taken_at: u128) -> String {
+    format!(
+        "{}{:020}{}",
+        symbol_prefix(prefix, symbol),
+        taken_at,
+        SNAPSHOT_EXTENSION
+    )
+}
+
+/// The text of every element with the given name, which is all that is needed of the listings.
+fn xml_elements(xml: &str, name: &str) -> Vec<String> {
+    let open = format!("<{}>", name);
+    let close = format!("</{}>", name);
+    xml.split(open.as_str())
+        .skip(1)
+        .filter_map(|element| {
+            element.split_once(close.as_str()).map(|(text, _)| {
+                text.replace("&lt;", "<")
+                    .replace("&gt;", ">")
+                    .replace("&quot;", "\"")
+                    .replace("&apos;", "'")
+                    .replace("&amp;", "&")
+            })
+        })
+        .collect()
+}
+
+/// Percent encodes everything but the unreserved characters, and the slashes of paths.
+fn uri_encode(value: &str, path: bool) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                encoded.push(byte as char)
+            }
+            b'/' if path => encoded.push('/'),
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+fn hmac_sha256(key: &[u8], message: &[u8]) -> [u8; 32] {
+    const BLOCK_SIZE: usize = 64;
+    let mut block = [0u8; BLOCK_SIZE];
+    if key.len() > BLOCK_SIZE {
+        block[..32].copy_from_slice(&Sha256::digest(key));
+    } else {
+        block[..key.len()].copy_from_slice(key);
+    }
+    let mut inner = Sha256::new();
+    inner.update(block.map(|byte| byte ^ 0x36));
+    inner.update(message);
+    let mut outer = Sha256::new();
+    outer.update(block.map(|byte| byte ^ 0x5c));
+    outer.update(inner.finalize());
+    outer.finalize().into()
+}
+
+fn signing_key(secret_key: &str, date: &str, region: &str, service: &str) -> [u8; 32] {
+    let key = hmac_sha256(format!("AWS4{}", secret_key).as_bytes(), date.as_bytes());
+    let key = hmac_sha256(&key, region.as_bytes());
+    let key = hmac_sha256(&key, service.as_bytes());
+    hmac_sha256(&key, b"aws4_request")
+}
+
+/// Formats nanoseconds since the unix epoch as the basic ISO 8601 time of the signature.
+fn amz_date(timestamp: u128) -> String {
+    let seconds = (timestamp / 1_000_000_000) as i64;
+    let (days, time) = (seconds.div_euclid(86_400), seconds.rem_euclid(86_400));
+    // civil date from days since the epoch, after Howard Hinnant's algorithm
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    format!(
+        "{:04}{:02}{:02}T{:02}{:02}{:02}Z",
+        year,
+        month,
+        day,
+        time / 3_600,
+        time % 3_600 / 60,
+        time % 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::engine::persistence::snapshot_store::{
+        amz_date, hex, hmac_sha256, signing_key, snapshot_key, uri_encode, xml_elements,
+    };
+
+    #[test]
+    fn it_signs_with_the_aws_test_vectors() {
+        assert_eq!(
+            hex(&hmac_sha256(b"Jefe", b"what do ya want for nothing?")),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+        assert_eq!(
+            hex(&signing_key(
+                "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY",
+                "20120215",
+                "us-east-1",
+                "iam"
+            )),
+            "f4780e2d9f65fa895f9c67b32ce1baf0b0d8a43505a000a1a9e090d414db404d"
+        );
+        assert_eq!(amz_date(0), "19700101T000000Z");
+        assert_eq!(amz_date(1_709_251_199_000_000_000), "20240229T235959Z");
+        assert_eq!(uri_encode("a b/c+d", true), "a%20b/c%2Bd");
+        assert_eq!(uri_encode("a/b", false), "a%2Fb");
+    }
+
+    #[test]
+    fn it_sorts_keys_by_time_and_reads_listings() {
+        let earlier = snapshot_key("snapshots/", "ETHUSD", 999_999_999);
+        let later = snapshot_key("snapshots", "ETHUSD", 1_000_000_000);
+        assert_eq!(earlier, "snapshots/ETHUSD/00000000000999999999.snapshot");
+        assert!(earlier < later);
+        assert_eq!(
+            snapshot_key("", "BTCUSD", 1),
+            "BTCUSD/00000000000000000001.snapshot"
+        );
+
+        let listing = "<ListBucketResult><IsTruncated>true</IsTruncated>\
+            <Contents><Key>a/1.snapshot</Key></Contents>\
+            <Contents><Key>a/&amp;2.snapshot</Key></Contents>\
+            <NextContinuationToken>next</NextContinuationToken></ListBucketResult>";
+        assert_eq!(
+            xml_elements(listing, "Key"),
+            vec!["a/1.snapshot", "a/&2.snapshot"]
+        );
+        assert_eq!(xml_elements(listing, "NextContinuationToken"), vec!["next"]);
+    }
+}
@@ -0,0 +1,14 @@
+use std::fmt::Debug;
+use std::time::Duration;
+
+/// Opens a sled backed store again right after it was dropped. sled releases its file lock from a
+/// background thread, so the first attempts may briefly fail.
+pub fn reopen<T, E: Debug>(open: impl Fn() -> Result<T, E>) -> T {
+    for _ in 0..100 {
+        if let Ok(store) = open() {
+            return store;
+        }
+        std::thread::sleep(Duration::from_millis(10));
+    }
+    open().unwrap()
+}
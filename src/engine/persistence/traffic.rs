@@ -0,0 +1,176 @@
+use crate::protobuf::models::{AckStatus, MassQuoteAck, OrderAck};
+use std::error::Error;
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+use std::sync::Mutex;
+
+/// An order entry request as it reached the dispatcher, along with how it was answered, so that
+/// a replay can tell where a new build answers differently.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrafficRecord {
+    /// Nanoseconds since the unix epoch the request was received at.
+    pub timestamp: u128,
+    /// Name of the dispatcher method, such as `limit` or `cancel`.
+    pub method: String,
+    /// The protobuf encoded request.
+    pub payload: Vec<u8>,
+    /// The status of the acknowledgment, or the code of the status the request failed with.
+    pub outcome: String,
+    /// The order id of the acknowledgment, which later requests of the recording refer to.
+    pub order_id: Vec<u8>,
+}
+
+/// An answer of the dispatcher, as it is told apart in a recording.
+pub trait RecordedOutcome {
+    /// The outcome of the request, along with the order id later requests refer to.
+    fn recorded_outcome(&self) -> (String, Vec<u8>);
+}
+
+impl RecordedOutcome for OrderAck {
+    fn recorded_outcome(&self) -> (String, Vec<u8>) {
+        (
+            self.status().as_str_name().to_string(),
+            self.order_id.clone(),
+        )
+    }
+}
+
+/// Mass quotes are recorded with the outcome of every level, their orders rest under derived
+/// ids that no request has to refer to.
+impl RecordedOutcome for MassQuoteAck {
+    fn recorded_outcome(&self) -> (String, Vec<u8>) {
+        let outcome = match self.status() {
+            AckStatus::Rejected => AckStatus::Rejected.as_str_name().to_string(),
+            AckStatus::Accepted => self
+                .levels
+                .iter()
+                .map(|level| level.status().as_str_name())
+                .collect::<Vec<_>>()
+                .join(","),
+        };
+        (outcome, Vec::new())
+    }
+}
+
+/// Records the requests reaching the dispatcher to a file, one per line of its timestamp, method,
+/// hex encoded payload, outcome and hex encoded order id, separated by tabs. Every request is
+/// flushed as it is recorded, so the recording of a server that died holds the requests leading
+/// up to it.
+pub struct TrafficRecorder {
+    writer: Mutex<BufWriter<File>>,
+}
+
+impl TrafficRecorder {
+    /// Opens the recording, appending to it if it exists.
+    pub fn create(path: impl AsRef<Path>) -> Result<Self, Box<dyn Error>> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(Self {
+            writer: Mutex::new(BufWriter::new(file)),
+        })
+    }
+
+    pub fn record(&self, record: &TrafficRecord) -> Result<(), Box<dyn Error>> {
+        let mut writer = self.writer.lock().unwrap();
+        writeln!(
+            writer,
+            "{}\t{}\t{}\t{}\t{}",
+            record.timestamp,
+            record.method,
+            to_hex(&record.payload),
+            record.outcome,
+            to_hex(&record.order_id)
+        )?;
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+/// Reads a recording made by the [`TrafficRecorder`], in the order it was recorded.
+pub fn read_traffic(path: impl AsRef<Path>) -> Result<Vec<TrafficRecord>, Box<dyn Error>> {
+    let mut records = Vec::new();
+    for (number, line) in BufReader::new(File::open(path)?).lines().enumerate() {
+        let line = line?;
+        let fields: Vec<&str> = line.split('\t').collect();
+        let [timestamp, method, payload, outcome, order_id] = fields[..] else {
+            return Err(format!("malformed traffic record on line {}", number + 1).into());
+        };
+        records.push(TrafficRecord {
+            timestamp: timestamp.parse()?,
+            method: method.to_string(),
+            payload: from_hex(payload)?,
+            outcome: outcome.to_string(),
+            order_id: from_hex(order_id)?,
+        });
+    }
+    Ok(records)
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+fn from_hex(hex: &str) -> Result<Vec<u8>, Box<dyn Error>> {
+    if !hex.len().is_multiple_of(2) {
+        return Err(format!("odd length hex: {}", hex).into());
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|index| Ok(u8::from_str_radix(&hex[index..index + 2], 16)?))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::engine::persistence::traffic::{
+        read_traffic, RecordedOutcome, TrafficRecord, TrafficRecorder,
+    };
+    use crate::protobuf::models::{AckStatus, MassQuoteAck, OrderAck};
+
+    #[test]
+    fn it_reads_back_recorded_traffic() {
+        let path = std::env::temp_dir().join(format!("gemmy-traffic-{}.tsv", uuid::Uuid::new_v4()));
+        let records = vec![
+            TrafficRecord {
+                timestamp: 1,
+                method: "limit".to_string(),
+                payload: vec![0x08, 0xff, 0x00],
+                outcome: "Accepted".to_string(),
+                order_id: vec![0xab; 16],
+            },
+            TrafficRecord {
+                timestamp: 2,
+                method: "cancel".to_string(),
+                payload: Vec::new(),
+                outcome: "InvalidArgument".to_string(),
+                order_id: Vec::new(),
+            },
+        ];
+        let recorder = TrafficRecorder::create(&path).unwrap();
+        for record in &records {
+            recorder.record(record).unwrap();
+        }
+        assert_eq!(read_traffic(&path).unwrap(), records);
+        std::fs::write(&path, "1\tlimit\t0\n").unwrap();
+        assert!(read_traffic(&path).is_err());
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn it_records_the_outcome_of_every_quoted_level() {
+        let level = |status: AckStatus| OrderAck {
+            status: status as i32,
+            order_id: vec![1; 16],
+            ..Default::default()
+        };
+        let mut ack = MassQuoteAck {
+            status: AckStatus::Accepted as i32,
+            rejection_reason: String::new(),
+            levels: vec![level(AckStatus::Accepted), level(AckStatus::Rejected)],
+        };
+        let outcome = ("Accepted,Rejected".to_string(), Vec::new());
+        assert_eq!(ack.recorded_outcome(), outcome);
+        ack.status = AckStatus::Rejected as i32;
+        assert_eq!(ack.recorded_outcome(), ("Rejected".to_string(), Vec::new()));
+    }
+}
@@ -0,0 +1,864 @@
+use crate::core::models::{LimitOrder, MarketOrder, Operation, Side};
+use crate::core::orderbook::OrderBook;
+use crate::core::types::{OrderId, Price, Quantity};
+use std::error::Error;
+use std::fs::{self, File, OpenOptions};
+use std::io::{ErrorKind, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use tracing::{error, info, warn};
+
+const LOG_FILE: &str = "operations.wal";
+const CHECKPOINT_FILE: &str = "checkpoint.bin";
+const CHECKPOINT_TEMP_FILE: &str = "checkpoint.tmp";
+
+/// sequence(8) + tag(1) + id(16) + price(8) + quantity(8) + side(1) + expires_at(16)
+const RECORD_BODY_SIZE: usize = 58;
+/// record body + crc32(4)
+const RECORD_SIZE: usize = RECORD_BODY_SIZE + 4;
+/// id(16) + price(8) + quantity(8) + side(1) + expires_at(16)
+const CHECKPOINT_ORDER_SIZE: usize = 49;
+
+const TAG_LIMIT: u8 = 0;
+const TAG_MARKET: u8 = 1;
+const TAG_MODIFY: u8 = 2;
+const TAG_CANCEL: u8 = 3;
+/// Sets the clock of the book to the time stored in the id field.
+const TAG_CLOCK: u8 = 4;
+/// Reduces the order by the quantity stored in the quantity field.
+const TAG_REDUCE: u8 = 5;
+/// The sides of a quote, logged as consecutive records with the bid first.
+const TAG_QUOTE_BID: u8 = 6;
+const TAG_QUOTE_ASK: u8 = 7;
+const TAG_CALL: u8 = 8;
+const TAG_UNCROSS: u8 = 9;
+
+/// An entry of the log, either an operation, the time the following operations executed at or
+/// a side of a quote.
+#[derive(Debug)]
+enum Record {
+    Operation(Operation),
+    Clock(u128),
+    QuoteBid(LimitOrder),
+    QuoteAsk(LimitOrder),
+}
+
+impl Record {
+    /// The records an operation is logged as, quotes take a record for each side.
+    fn of(operation: Operation) -> Vec<Record> {
+        match operation {
+            Operation::Quote { bid, ask } => vec![Record::QuoteBid(bid), Record::QuoteAsk(ask)],
+            operation => vec![Record::Operation(operation)],
+        }
+    }
+}
+
+/// An append only log of every operation accepted by the executor.
+/// Operations are written and synced to disk in batches before they are executed, so the primary book
+/// can be rebuilt after a crash from the latest checkpoint followed by the operations logged after it.
+pub struct WriteAheadLog {
+    directory: PathBuf,
+    writer: Mutex<WalWriter>,
+    checkpoint_requested: AtomicBool,
+}
+
+struct WalWriter {
+    file: File,
+    /// The byte length of the records appended so far, which a failed append is truncated to.
+    length: u64,
+    next_sequence: u64,
+    /// Set once a failed append could not be rolled back, after which nothing is appended.
+    failed: bool,
+}
+
+/// The outcome of replaying the log on startup.
+#[derive(Debug, Default)]
+pub struct RecoveryReport {
+    pub checkpoint_sequence: u64,
+    pub restored_orders: usize,
+    pub replayed_operations: usize,
+}
+
+impl WriteAheadLog {
+    /// Opens the log in the given directory, creating it if needed, and replays the latest checkpoint
+    /// together with any logged operations onto the passed orderbook.
+    pub fn open(
+        directory: impl AsRef<Path>,
+        orderbook: &mut OrderBook,
+    ) -> Result<(WriteAheadLog, RecoveryReport), Box<dyn Error>> {
+        let directory = directory.as_ref().to_path_buf();
+        fs::create_dir_all(&directory)?;
+
+        let mut report = RecoveryReport::default();
+        if let Some((sequence, last_trade_price, call, orders)) =
+            read_checkpoint(&directory.join(CHECKPOINT_FILE))?
+        {
+            report.checkpoint_sequence = sequence;
+            report.restored_orders = orders.len();
+            // orders taken in a call may cross, so they are placed in one
+            if call {
+                orderbook.execute(Operation::Call);
+            }
+            for order in orders {
+                orderbook.execute(Operation::Limit(order));
+            }
+            orderbook.set_last_trade_price(last_trade_price);
+        }
+
+        let log_path = directory.join(LOG_FILE);
+        let (records, valid_length) = read_records(&log_path)?;
+        let mut last_sequence = report.checkpoint_sequence;
+        for (sequence, record) in records {
+            if sequence <= report.checkpoint_sequence {
+                continue;
+            }
+            match record {
+                Record::Operation(operation) => {
+                    orderbook.execute(operation);
+                    report.replayed_operations += 1;
+                }
+                Record::Clock(now) => orderbook.set_clock(now),
+                Record::QuoteBid(_) | Record::QuoteAsk(_) => {
+                    unreachable!("the sides of quotes are joined as they are read")
+                }
+            }
+            last_sequence = sequence;
+        }
+        // orders purged during the replay were reported before the restart
+        orderbook.take_expired();
+
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&log_path)?;
+        if file.metadata()?.len() != valid_length {
+            warn!(
+                "truncating torn tail of write ahead log at {} bytes",
+                valid_length
+            );
+            file.set_len(valid_length)?;
+            file.sync_all()?;
+        }
+
+        Ok((
+            WriteAheadLog {
+                directory,
+                writer: Mutex::new(WalWriter {
+                    file,
+                    length: valid_length,
+                    next_sequence: last_sequence + 1,
+                    failed: false,
+                }),
+                checkpoint_requested: AtomicBool::new(false),
+            },
+            report,
+        ))
+    }
+
+    /// Appends a batch of operations and syncs them to disk with a single fsync. A batch that
+    /// fails to append is truncated off the log again, so that it is never replayed and the
+    /// batches appended after it are not lost behind a torn record.
+    ///
+    /// # Arguments
+    ///
+    /// * `now` - The clock the batch executes at, logged ahead of its operations.
+    ///
+    /// # Returns
+    ///
+    /// * The sequence number assigned to the last operation of the batch.
+    pub fn append_batch(&self, now: u128, operations: &[Operation]) -> std::io::Result<u64> {
+        let mut writer = self.writer.lock().unwrap();
+        if writer.failed {
+            return Err(std::io::Error::other(
+                "write ahead log failed to roll back a batch, restart to recover",
+            ));
+        }
+        let records = encode_records(writer.next_sequence, now, operations);
+        let appended = writer
+            .file
+            .write_all(&records)
+            .and_then(|()| writer.file.sync_data());
+        if let Err(e) = appended {
+            let length = writer.length;
+            if let Err(rollback) = writer
+                .file
+                .set_len(length)
+                .and_then(|()| writer.file.sync_data())
+            {
+                error!(
+                    "failed to roll back write ahead log to {} bytes: {}",
+                    length, rollback
+                );
+                writer.failed = true;
+            }
+            return Err(e);
+        }
+        writer.length += records.len() as u64;
+        writer.next_sequence += (records.len() / RECORD_SIZE) as u64;
+        Ok(writer.next_sequence - 1)
+    }
+
+    /// Truncates the records of the passed operations off the end of the log, so that the
+    /// operations a batch stopped short of executing are not replayed. They must be the last
+    /// operations appended, in the order they were appended.
+    pub fn retract(&self, operations: &[Operation]) -> std::io::Result<()> {
+        let mut writer = self.writer.lock().unwrap();
+        let records: u64 = operations
+            .iter()
+            .map(|operation| Record::of(*operation).len() as u64)
+            .sum();
+        let Some(length) = writer.length.checked_sub(records * RECORD_SIZE as u64) else {
+            return Err(std::io::Error::other(
+                "cannot retract more records than were appended",
+            ));
+        };
+        if let Err(e) = writer
+            .file
+            .set_len(length)
+            .and_then(|()| writer.file.sync_data())
+        {
+            writer.failed = true;
+            return Err(e);
+        }
+        writer.length = length;
+        writer.next_sequence -= records;
+        Ok(())
+    }
+
+    /// Marks that a checkpoint should be taken at the next batch boundary.
+    pub fn request_checkpoint(&self) {
+        self.checkpoint_requested.store(true, Ordering::Release);
+    }
+
+    /// Returns and clears the pending checkpoint request.
+    pub fn take_checkpoint_request(&self) -> bool {
+        self.checkpoint_requested.swap(false, Ordering::AcqRel)
+    }
+
+    /// Persists the resting state of the passed orderbook and truncates the log.
+    /// This must only be called by the writer of the book, between batches, so that the book reflects
+    /// exactly the operations appended so far.
+    pub fn checkpoint(&self, orderbook: &OrderBook) -> std::io::Result<()> {
+        let mut writer = self.writer.lock().unwrap();
+        let sequence = writer.next_sequence - 1;
+        let orders = orderbook.resting_orders();
+        let buffer = encode_checkpoint(
+            sequence,
+            orderbook.get_last_trade_price(),
+            orderbook.in_call(),
+            &orders,
+        );
+
+        let temp_path = self.directory.join(CHECKPOINT_TEMP_FILE);
+        let mut temp_file = File::create(&temp_path)?;
+        temp_file.write_all(&buffer)?;
+        temp_file.sync_all()?;
+        fs::rename(&temp_path, self.directory.join(CHECKPOINT_FILE))?;
+        // the rename is only durable once the directory is synced, the log must not be
+        // truncated before that
+        File::open(&self.directory)?.sync_all()?;
+
+        writer.file.set_len(0)?;
+        writer.file.sync_all()?;
+        writer.length = 0;
+        info!(
+            "write ahead log checkpoint taken at sequence {} with {} resting orders",
+            sequence,
+            orders.len()
+        );
+        Ok(())
+    }
+}
+
+fn encode_record(sequence: u64, record: &Record) -> [u8; RECORD_SIZE] {
+    let operation = match *record {
+        Record::Operation(operation) => operation,
+        Record::Clock(now) => {
+            return encode_fields(sequence, TAG_CLOCK, now, 0, 0, Side::Bid, None)
+        }
+        Record::QuoteBid(order) => return encode_order(sequence, TAG_QUOTE_BID, &order),
+        Record::QuoteAsk(order) => return encode_order(sequence, TAG_QUOTE_ASK, &order),
+    };
+    let (tag, id, price, quantity, side, expires_at) = match operation {
+        Operation::Limit(order) => (
+            TAG_LIMIT,
+            order.id,
+            order.price,
+            order.quantity,
+            order.side,
+            order.expires_at,
+        ),
+        Operation::Market(order) => (TAG_MARKET, order.id, 0, order.quantity, order.side, None),
+        Operation::Modify(order) => (
+            TAG_MODIFY,
+            order.id,
+            order.price,
+            order.quantity,
+            order.side,
+            order.expires_at,
+        ),
+        Operation::Cancel(OrderId(id)) => (TAG_CANCEL, id, 0, 0, Side::Bid, None),
+        Operation::Reduce {
+            id: OrderId(id),
+            quantity_delta,
+        } => (TAG_REDUCE, id, 0, quantity_delta, Side::Bid, None),
+        Operation::Call => (TAG_CALL, 0, 0, 0, Side::Bid, None),
+        Operation::Uncross => (TAG_UNCROSS, 0, 0, 0, Side::Bid, None),
+        Operation::Quote { .. } => unreachable!("quotes are logged as a record per side"),
+    };
+    encode_fields(sequence, tag, id, price, quantity, side, expires_at)
+}
+
+fn encode_order(sequence: u64, tag: u8, order: &LimitOrder) -> [u8; RECORD_SIZE] {
+    encode_fields(
+        sequence,
+        tag,
+        order.id,
+        order.price,
+        order.quantity,
+        order.side,
+        order.expires_at,
+    )
+}
+
+fn encode_fields(
+    sequence: u64,
+    tag: u8,
+    id: u128,
+    price: u64,
+    quantity: u64,
+    side: Side,
+    expires_at: Option<u128>,
+) -> [u8; RECORD_SIZE] {
+    let mut record = [0u8; RECORD_SIZE];
+    record[0..8].copy_from_slice(&sequence.to_be_bytes());
+    record[8] = tag;
+    record[9..25].copy_from_slice(&id.to_be_bytes());
+    record[25..33].copy_from_slice(&price.to_be_bytes());
+    record[33..41].copy_from_slice(&quantity.to_be_bytes());
+    record[41] = side as u8;
+    record[42..58].copy_from_slice(&encode_expiry(expires_at).to_be_bytes());
+    let checksum = crc32fast::hash(&record[..RECORD_BODY_SIZE]);
+    record[RECORD_BODY_SIZE..].copy_from_slice(&checksum.to_be_bytes());
+    record
+}
+
+fn decode_record(record: &[u8]) -> Option<(u64, Record)> {
+    let checksum = u32::from_be_bytes(record[RECORD_BODY_SIZE..RECORD_SIZE].try_into().ok()?);
+    if crc32fast::hash(&record[..RECORD_BODY_SIZE]) != checksum {
+        return None;
+    }
+    let sequence = u64::from_be_bytes(record[0..8].try_into().ok()?);
+    let id = u128::from_be_bytes(record[9..25].try_into().ok()?);
+    let price = u64::from_be_bytes(record[25..33].try_into().ok()?);
+    let quantity = u64::from_be_bytes(record[33..41].try_into().ok()?);
+    let side = decode_side(record[41])?;
+    let expires_at = decode_expiry(u128::from_be_bytes(record[42..58].try_into().ok()?));
+    let limit_order = LimitOrder {
+        expires_at,
+        ..LimitOrder::new(OrderId(id), Price(price), Quantity(quantity), side)
+    };
+    let operation = match record[8] {
+        TAG_LIMIT => Operation::Limit(limit_order),
+        TAG_MARKET => Operation::Market(MarketOrder::new(OrderId(id), Quantity(quantity), side)),
+        TAG_MODIFY => Operation::Modify(limit_order),
+        TAG_CANCEL => Operation::Cancel(OrderId(id)),
+        TAG_REDUCE => Operation::Reduce {
+            id: OrderId(id),
+            quantity_delta: quantity,
+        },
+        TAG_CALL => Operation::Call,
+        TAG_UNCROSS => Operation::Uncross,
+        TAG_CLOCK => return Some((sequence, Record::Clock(id))),
+        TAG_QUOTE_BID => return Some((sequence, Record::QuoteBid(limit_order))),
+        TAG_QUOTE_ASK => return Some((sequence, Record::QuoteAsk(limit_order))),
+        _ => return None,
+    };
+    Some((sequence, Record::Operation(operation)))
+}
+
+/// Joins the sides of a quote back into its operation. The bid is held until the ask logged
+/// right after it, any other record in between means a side is missing.
+fn join_quote(
+    quote_bid: &mut Option<LimitOrder>,
+    record: Record,
+) -> Result<Option<Record>, &'static str> {
+    match (quote_bid.take(), record) {
+        (None, Record::QuoteBid(bid)) => {
+            *quote_bid = Some(bid);
+            Ok(None)
+        }
+        (Some(bid), Record::QuoteAsk(ask)) => {
+            Ok(Some(Record::Operation(Operation::Quote { bid, ask })))
+        }
+        (None, Record::QuoteAsk(_)) | (Some(_), _) => Err("quote is missing a side"),
+        (None, record) => Ok(Some(record)),
+    }
+}
+
+fn decode_side(value: u8) -> Option<Side> {
+    match value {
+        0 => Some(Side::Bid),
+        1 => Some(Side::Ask),
+        _ => None,
+    }
+}
+
+/// Orders without an expiry are stored with an expiry of zero.
+fn encode_expiry(expires_at: Option<u128>) -> u128 {
+    expires_at.unwrap_or(0)
+}
+
+fn decode_expiry(value: u128) -> Option<u128> {
+    (value != 0).then_some(value)
+}
+
+/// Reads every intact record of the log, stopping at the first torn or corrupted one.
+///
+/// # Returns
+///
+/// * The decoded records and the byte length of the valid prefix of the file.
+fn read_records(path: &Path) -> std::io::Result<(Vec<(u64, Record)>, u64)> {
+    let mut bytes = Vec::new();
+    match File::open(path) {
+        Ok(mut file) => {
+            file.read_to_end(&mut bytes)?;
+        }
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok((vec![], 0)),
+        Err(e) => return Err(e),
+    }
+    let mut records = Vec::with_capacity(bytes.len() / RECORD_SIZE);
+    let mut valid_records = 0;
+    let mut quote_bid = None;
+    for (chunk, count) in bytes.chunks_exact(RECORD_SIZE).zip(1..) {
+        let Some((sequence, record)) = decode_record(chunk) else {
+            break;
+        };
+        // a quote torn after its bid is dropped along with the bid
+        match join_quote(&mut quote_bid, record) {
+            Ok(Some(record)) => records.push((sequence, record)),
+            Ok(None) => continue,
+            Err(_) => break,
+        }
+        valid_records = count;
+    }
+    let valid_length = (valid_records * RECORD_SIZE) as u64;
+    Ok((records, valid_length))
+}
+
+/// Encodes the records the log stores for a batch, the clock followed by the operations,
+/// numbered from the passed sequence on.
+fn encode_records(first_sequence: u64, now: u128, operations: &[Operation]) -> Vec<u8> {
+    std::iter::once(Record::Clock(now))
+        .chain(operations.iter().copied().flat_map(Record::of))
+        .zip(first_sequence..)
+        .flat_map(|(record, sequence)| encode_record(sequence, &record))
+        .collect()
+}
+
+/// Encodes a batch as the records the log stores for it, numbered by their position in the batch.
+pub(crate) fn encode_batch(now: u128, operations: &[Operation]) -> Vec<u8> {
+    encode_records(0, now, operations)
+}
+
+/// Decodes a batch encoded by [`encode_batch`] into its clock and operations.
+pub(crate) fn decode_batch(bytes: &[u8]) -> Result<(u128, Vec<Operation>), Box<dyn Error>> {
+    if bytes.is_empty() || !bytes.len().is_multiple_of(RECORD_SIZE) {
+        return Err("batch is truncated".into());
+    }
+    let mut now = None;
+    let mut operations = Vec::with_capacity(bytes.len() / RECORD_SIZE - 1);
+    let mut quote_bid = None;
+    for (chunk, position) in bytes.chunks_exact(RECORD_SIZE).zip(0..) {
+        let record = match decode_record(chunk) {
+            Some((sequence, _)) if sequence != position => {
+                return Err("batch records are out of order".into())
+            }
+            Some((_, record)) => join_quote(&mut quote_bid, record)?,
+            None => return Err("batch failed checksum validation".into()),
+        };
+        match record {
+            Some(Record::Clock(clock)) if position == 0 => now = Some(clock),
+            Some(Record::Operation(operation)) if position > 0 => operations.push(operation),
+            Some(_) => return Err("batch does not start with its clock".into()),
+            None => (),
+        }
+    }
+    if quote_bid.is_some() {
+        return Err("quote is missing a side".into());
+    }
+    Ok((now.ok_or("batch has no clock")?, operations))
+}
+
+/// The sequence the checkpoint was taken at, the last trade price and the resting orders.
+pub(crate) type Checkpoint = (u64, u64, bool, Vec<LimitOrder>);
+
+fn read_checkpoint(path: &Path) -> Result<Option<Checkpoint>, Box<dyn Error>> {
+    let bytes = match fs::read(path) {
+        Ok(bytes) => bytes,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e.into()),
+    };
+    decode_checkpoint(&bytes).map(Some)
+}
+
+/// Encodes the resting orders of a book along with the sequence and last trade price it was
+/// taken at and whether it was in a call, followed by a checksum of the whole.
+pub(crate) fn encode_checkpoint(
+    sequence: u64,
+    last_trade_price: u64,
+    call: bool,
+    orders: &[LimitOrder],
+) -> Vec<u8> {
+    let mut buffer = Vec::with_capacity(24 + orders.len() * CHECKPOINT_ORDER_SIZE + 5);
+    buffer.extend_from_slice(&sequence.to_be_bytes());
+    buffer.extend_from_slice(&last_trade_price.to_be_bytes());
+    buffer.extend_from_slice(&(orders.len() as u64).to_be_bytes());
+    for order in orders {
+        buffer.extend_from_slice(&order.id.to_be_bytes());
+        buffer.extend_from_slice(&order.price.to_be_bytes());
+        buffer.extend_from_slice(&order.quantity.to_be_bytes());
+        buffer.push(order.side as u8);
+        buffer.extend_from_slice(&encode_expiry(order.expires_at).to_be_bytes());
+    }
+    buffer.push(call as u8);
+    let checksum = crc32fast::hash(&buffer);
+    buffer.extend_from_slice(&checksum.to_be_bytes());
+    buffer
+}
+
+pub(crate) fn decode_checkpoint(bytes: &[u8]) -> Result<Checkpoint, Box<dyn Error>> {
+    if bytes.len() < 28 {
+        return Err("write ahead log checkpoint is truncated".into());
+    }
+    let (body, checksum) = bytes.split_at(bytes.len() - 4);
+    if crc32fast::hash(body) != u32::from_be_bytes(checksum.try_into()?) {
+        return Err("write ahead log checkpoint failed checksum validation".into());
+    }
+    let sequence = u64::from_be_bytes(body[0..8].try_into()?);
+    let last_trade_price = u64::from_be_bytes(body[8..16].try_into()?);
+    let count = u64::from_be_bytes(body[16..24].try_into()?) as usize;
+    let orders_data = &body[24..];
+    // checkpoints taken before books could be in a call end with the orders
+    let call = match count
+        .checked_mul(CHECKPOINT_ORDER_SIZE)
+        .and_then(|size| orders_data.len().checked_sub(size))
+    {
+        Some(0) => false,
+        Some(1) => orders_data[orders_data.len() - 1] != 0,
+        _ => return Err("write ahead log checkpoint has an invalid order count".into()),
+    };
+    let orders_data = &orders_data[..count * CHECKPOINT_ORDER_SIZE];
+    let mut orders = Vec::with_capacity(count);
+    for chunk in orders_data.chunks_exact(CHECKPOINT_ORDER_SIZE) {
+        let side = decode_side(chunk[32]).ok_or("invalid side in checkpoint")?;
+        orders.push(LimitOrder {
+            expires_at: decode_expiry(u128::from_be_bytes(chunk[33..49].try_into()?)),
+            ..LimitOrder::new(
+                OrderId(u128::from_be_bytes(chunk[0..16].try_into()?)),
+                Price(u64::from_be_bytes(chunk[16..24].try_into()?)),
+                Quantity(u64::from_be_bytes(chunk[24..32].try_into()?)),
+                side,
+            )
+        });
+    }
+    Ok((sequence, last_trade_price, call, orders))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::models::{LimitOrder, MarketOrder, Operation, Side};
+    use crate::core::orderbook::OrderBook;
+    use crate::core::types::{OrderId, Price, Quantity};
+    use crate::engine::persistence::wal::{
+        decode_batch, decode_checkpoint, decode_record, encode_batch, encode_checkpoint,
+        encode_record, Record, WriteAheadLog, LOG_FILE, RECORD_SIZE,
+    };
+    use std::fs::{File, OpenOptions};
+    use std::io::Write;
+    use std::path::PathBuf;
+
+    fn test_directory(name: &str) -> PathBuf {
+        let directory =
+            std::env::temp_dir().join(format!("gemmy-wal-{}-{}", name, uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&directory).unwrap();
+        directory
+    }
+
+    fn operations() -> Vec<Operation> {
+        vec![
+            Operation::Limit(LimitOrder::new(
+                OrderId(1),
+                Price(100),
+                Quantity(100),
+                Side::Bid,
+            )),
+            Operation::Limit(
+                LimitOrder::new(OrderId(2), Price(110), Quantity(50), Side::Ask).with_expiry(1_000),
+            ),
+            Operation::Market(MarketOrder::new(OrderId(3), Quantity(20), Side::Bid)),
+            Operation::Modify(LimitOrder::new(
+                OrderId(1),
+                Price(100),
+                Quantity(80),
+                Side::Bid,
+            )),
+            Operation::Limit(LimitOrder::new(
+                OrderId(4),
+                Price(95),
+                Quantity(10),
+                Side::Bid,
+            )),
+            Operation::Cancel(OrderId(4)),
+        ]
+    }
+
+    #[test]
+    fn it_round_trips_records() {
+        let records = operations()
+            .into_iter()
+            .map(Record::Operation)
+            .chain([Record::Clock(1_000)]);
+        for (sequence, record) in records.enumerate() {
+            let encoded = encode_record(sequence as u64, &record);
+            let (decoded_sequence, decoded) = decode_record(&encoded).unwrap();
+            assert_eq!(decoded_sequence, sequence as u64);
+            assert_eq!(format!("{:?}", decoded), format!("{:?}", record));
+        }
+    }
+
+    #[test]
+    fn it_round_trips_batches() {
+        let encoded = encode_batch(1_000, &operations());
+        let (now, decoded) = decode_batch(&encoded).unwrap();
+        assert_eq!(now, 1_000);
+        assert_eq!(format!("{:?}", decoded), format!("{:?}", operations()));
+        assert!(decode_batch(&encoded[..encoded.len() - 1]).is_err());
+        let mut reordered = encoded.clone();
+        reordered.rotate_left(62);
+        assert!(decode_batch(&reordered).is_err());
+    }
+
+    #[test]
+    fn it_replays_logged_operations_after_a_checkpoint() {
+        let directory = test_directory("replay");
+        let mut expected = OrderBook::default();
+        {
+            let mut book = OrderBook::default();
+            let (wal, _) = WriteAheadLog::open(&directory, &mut book).unwrap();
+            let operations = operations();
+            let (first, second) = operations.split_at(3);
+            wal.append_batch(0, first).unwrap();
+            first.iter().for_each(|op| {
+                book.execute(*op);
+            });
+            wal.checkpoint(&book).unwrap();
+            wal.append_batch(0, second).unwrap();
+            second.iter().for_each(|op| {
+                book.execute(*op);
+            });
+            operations.into_iter().for_each(|op| {
+                expected.execute(op);
+            });
+        }
+        let mut recovered = OrderBook::default();
+        let (_, report) = WriteAheadLog::open(&directory, &mut recovered).unwrap();
+        assert!(
+            report.checkpoint_sequence == 4
+                && report.replayed_operations == 3
+                && recovered.resting_orders() == expected.resting_orders()
+                && recovered.get_last_trade_price() == expected.get_last_trade_price()
+        );
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+
+    #[test]
+    fn it_restores_a_call_from_a_checkpoint() {
+        let directory = test_directory("call");
+        let crossing = [
+            LimitOrder::new(OrderId(1), Price(105), Quantity(100), Side::Bid),
+            LimitOrder::new(OrderId(2), Price(98), Quantity(80), Side::Ask),
+        ];
+        {
+            let mut book = OrderBook::default();
+            let (wal, _) = WriteAheadLog::open(&directory, &mut book).unwrap();
+            let batch: Vec<Operation> = [Operation::Call]
+                .into_iter()
+                .chain(crossing.map(Operation::Limit))
+                .collect();
+            wal.append_batch(0, &batch).unwrap();
+            batch.iter().for_each(|op| {
+                book.execute(*op);
+            });
+            wal.checkpoint(&book).unwrap();
+        }
+        let mut recovered = OrderBook::default();
+        let (wal, _) = WriteAheadLog::open(&directory, &mut recovered).unwrap();
+        assert!(recovered.in_call());
+        assert_eq!(recovered.resting_orders(), crossing.to_vec());
+        wal.append_batch(0, &[Operation::Uncross]).unwrap();
+        drop(wal);
+
+        let mut recovered = OrderBook::default();
+        WriteAheadLog::open(&directory, &mut recovered).unwrap();
+        assert!(!recovered.in_call());
+        assert_eq!(recovered.get_last_trade_price(), 98);
+        assert_eq!(
+            recovered.resting_orders(),
+            vec![LimitOrder::new(
+                OrderId(1),
+                Price(105),
+                Quantity(20),
+                Side::Bid
+            )]
+        );
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+
+    #[test]
+    fn it_decodes_checkpoints_taken_before_calls() {
+        let orders = [LimitOrder::new(
+            OrderId(1),
+            Price(100),
+            Quantity(10),
+            Side::Bid,
+        )];
+        let encoded = encode_checkpoint(7, 100, true, &orders);
+        let (_, _, call, _) = decode_checkpoint(&encoded).unwrap();
+        assert!(call);
+        // the same checkpoint without the trailing call flag
+        let mut legacy = encoded[..encoded.len() - 5].to_vec();
+        let checksum = crc32fast::hash(&legacy);
+        legacy.extend_from_slice(&checksum.to_be_bytes());
+        let (sequence, last_trade_price, call, decoded) = decode_checkpoint(&legacy).unwrap();
+        assert_eq!((sequence, last_trade_price, call), (7, 100, false));
+        assert_eq!(decoded, orders.to_vec());
+    }
+
+    #[test]
+    fn it_ignores_a_torn_tail_record() {
+        let directory = test_directory("torn");
+        {
+            let mut book = OrderBook::default();
+            let (wal, _) = WriteAheadLog::open(&directory, &mut book).unwrap();
+            wal.append_batch(0, &operations()[..2]).unwrap();
+        }
+        let mut file = OpenOptions::new()
+            .append(true)
+            .open(directory.join(LOG_FILE))
+            .unwrap();
+        file.write_all(&[7u8; 11]).unwrap();
+        let mut recovered = OrderBook::default();
+        let (wal, report) = WriteAheadLog::open(&directory, &mut recovered).unwrap();
+        assert_eq!(report.replayed_operations, 2);
+        assert_eq!(wal.append_batch(0, &operations()[2..3]).unwrap(), 5);
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+
+    #[test]
+    fn it_retracts_the_tail_of_a_batch() {
+        let directory = test_directory("retract");
+        let quote = Operation::Quote {
+            bid: LimitOrder::new(OrderId(5), Price(99), Quantity(10), Side::Bid),
+            ask: LimitOrder::new(OrderId(6), Price(101), Quantity(10), Side::Ask),
+        };
+        {
+            let mut book = OrderBook::default();
+            let (wal, _) = WriteAheadLog::open(&directory, &mut book).unwrap();
+            let batch = [operations()[0], quote, operations()[1]];
+            assert_eq!(wal.append_batch(0, &batch).unwrap(), 5);
+            wal.retract(&batch[1..]).unwrap();
+            assert_eq!(wal.append_batch(0, &[operations()[4]]).unwrap(), 4);
+            assert!(wal.retract(&operations()).is_err());
+        }
+        let mut recovered = OrderBook::default();
+        let (_, report) = WriteAheadLog::open(&directory, &mut recovered).unwrap();
+        assert_eq!(report.replayed_operations, 2);
+        let ids: Vec<u128> = recovered
+            .resting_orders()
+            .iter()
+            .map(|order| order.id)
+            .collect();
+        assert_eq!(ids, vec![1, 4]);
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+
+    #[test]
+    fn it_refuses_appends_after_a_failed_batch_cannot_be_rolled_back() {
+        let directory = test_directory("failed");
+        let mut book = OrderBook::default();
+        let (wal, _) = WriteAheadLog::open(&directory, &mut book).unwrap();
+        assert_eq!(wal.append_batch(0, &operations()[..2]).unwrap(), 3);
+        // a read only handle fails to append and to truncate alike
+        wal.writer.lock().unwrap().file = File::open(directory.join(LOG_FILE)).unwrap();
+        assert!(wal.append_batch(0, &operations()[2..3]).is_err());
+        assert_eq!(wal.writer.lock().unwrap().next_sequence, 4);
+        assert!(wal.append_batch(0, &[]).is_err());
+        drop(wal);
+
+        let mut recovered = OrderBook::default();
+        let (wal, report) = WriteAheadLog::open(&directory, &mut recovered).unwrap();
+        assert_eq!(report.replayed_operations, 2);
+        assert_eq!(wal.append_batch(0, &operations()[2..3]).unwrap(), 5);
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+
+    #[test]
+    fn it_logs_quotes_as_a_record_per_side() {
+        let quote = Operation::Quote {
+            bid: LimitOrder::new(OrderId(5), Price(99), Quantity(10), Side::Bid),
+            ask: LimitOrder::new(OrderId(6), Price(101), Quantity(10), Side::Ask)
+                .with_expiry(1_000),
+        };
+        let operations = [operations()[0], quote];
+        let encoded = encode_batch(0, &operations);
+        assert_eq!(encoded.len(), 4 * RECORD_SIZE);
+        let (_, decoded) = decode_batch(&encoded).unwrap();
+        assert_eq!(format!("{:?}", decoded), format!("{:?}", operations));
+        assert!(decode_batch(&encoded[..3 * RECORD_SIZE]).is_err());
+
+        let directory = test_directory("quote");
+        {
+            let mut book = OrderBook::default();
+            let (wal, _) = WriteAheadLog::open(&directory, &mut book).unwrap();
+            wal.append_batch(0, &operations).unwrap();
+            wal.append_batch(0, &[quote]).unwrap();
+        }
+        // the second quote is torn after its bid
+        let log = OpenOptions::new()
+            .write(true)
+            .open(directory.join(LOG_FILE))
+            .unwrap();
+        log.set_len(6 * RECORD_SIZE as u64).unwrap();
+        let mut recovered = OrderBook::default();
+        let (wal, report) = WriteAheadLog::open(&directory, &mut recovered).unwrap();
+        assert_eq!(report.replayed_operations, 2);
+        assert_eq!(recovered.resting_orders().len(), 3);
+        assert_eq!(wal.append_batch(0, &[]).unwrap(), 6);
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+
+    #[test]
+    fn it_replays_the_clock_of_each_batch() {
+        let directory = test_directory("clock");
+        let mut expected = OrderBook::default();
+        {
+            let mut book = OrderBook::default();
+            let (wal, _) = WriteAheadLog::open(&directory, &mut book).unwrap();
+            for (now, batch) in [(0, &operations()[..2]), (1_000, &operations()[2..3])] {
+                wal.append_batch(now, batch).unwrap();
+                expected.set_clock(now);
+                batch.iter().for_each(|op| {
+                    expected.execute(*op);
+                });
+            }
+        }
+        assert_eq!(expected.take_expired(), vec![2]);
+        let mut recovered = OrderBook::default();
+        WriteAheadLog::open(&directory, &mut recovered).unwrap();
+        assert_eq!(recovered.resting_orders(), expected.resting_orders());
+        assert!(recovered.take_expired().is_empty());
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+}
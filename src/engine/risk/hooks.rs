@@ -0,0 +1,182 @@
+use crate::core::errors::Rejection;
+use crate::core::models::{ExecutionResult, Operation};
+use crate::core::orderbook::OrderBook;
+use crate::engine::persistence::outbox::OutboxEvent;
+use crate::engine::risk::BookStats;
+use std::sync::Arc;
+
+/// Runs on every operation of a batch before it is matched, alongside the risk check, so that
+/// features such as custom rejections can be added without changing the executor. Like the risk
+/// check it runs before the batch is written to the write ahead log, which only ever records
+/// accepted operations, so it sees the book as it was at the start of the batch.
+pub trait PreTradeHook: Send + Sync {
+    /// Decides whether the operation may be executed, the error is the reason it is rejected.
+    fn before_trade(&self, operation: &Operation, stats: &BookStats) -> Result<(), Rejection>;
+}
+
+/// Runs on every operation the primary executed right after it was matched, so that features
+/// such as surveillance or enrichment can be added without changing the executor.
+pub trait PostTradeHook: Send + Sync {
+    /// Observes the outcome of the operation along with the book it left behind.
+    ///
+    /// # Arguments
+    ///
+    /// * `timestamp` - The time the operation was executed at in nanoseconds since the unix epoch.
+    ///
+    /// # Returns
+    ///
+    /// * Events to publish along with those of the operation.
+    fn after_trade(
+        &self,
+        operation: &Operation,
+        result: &ExecutionResult,
+        orderbook: &OrderBook,
+        timestamp: u128,
+    ) -> Vec<OutboxEvent>;
+}
+
+/// The hooks installed on an executor, each run in the order it was added.
+#[derive(Default, Clone)]
+pub struct TradeHooks {
+    pre_trade: Vec<Arc<dyn PreTradeHook>>,
+    post_trade: Vec<Arc<dyn PostTradeHook>>,
+}
+
+impl TradeHooks {
+    pub fn add_pre_trade(&mut self, hook: Arc<dyn PreTradeHook>) {
+        self.pre_trade.push(hook);
+    }
+
+    pub fn add_post_trade(&mut self, hook: Arc<dyn PostTradeHook>) {
+        self.post_trade.push(hook);
+    }
+
+    /// Runs the pre-trade hooks until one rejects the operation.
+    pub fn before_trade(&self, operation: &Operation, stats: &BookStats) -> Result<(), Rejection> {
+        self.pre_trade
+            .iter()
+            .try_for_each(|hook| hook.before_trade(operation, stats))
+    }
+
+    /// Runs every post-trade hook, collecting the events they publish.
+    pub fn after_trade(
+        &self,
+        operation: &Operation,
+        result: &ExecutionResult,
+        orderbook: &OrderBook,
+        timestamp: u128,
+    ) -> Vec<OutboxEvent> {
+        self.post_trade
+            .iter()
+            .flat_map(|hook| hook.after_trade(operation, result, orderbook, timestamp))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::errors::Rejection;
+    use crate::core::models::{ExecutionResult, LimitOrder, Operation, Side};
+    use crate::core::orderbook::OrderBook;
+    use crate::core::types::{OrderId, Price, Quantity};
+    use crate::engine::persistence::outbox::OutboxEvent;
+    use crate::engine::risk::hooks::{PostTradeHook, PreTradeHook, TradeHooks};
+    use crate::engine::risk::BookStats;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    /// Rejects orders above a price, counting every operation it sees.
+    struct PriceBand {
+        max_price: u64,
+        seen: AtomicUsize,
+    }
+
+    impl PreTradeHook for PriceBand {
+        fn before_trade(&self, operation: &Operation, _stats: &BookStats) -> Result<(), Rejection> {
+            self.seen.fetch_add(1, Ordering::Relaxed);
+            match operation {
+                Operation::Limit(order) if order.price > self.max_price => {
+                    Err(Rejection::Custom(format!("price above {}", self.max_price)))
+                }
+                _ => Ok(()),
+            }
+        }
+    }
+
+    /// Reports the fills of every operation.
+    struct FillReport;
+
+    impl PostTradeHook for FillReport {
+        fn after_trade(
+            &self,
+            _operation: &Operation,
+            result: &ExecutionResult,
+            orderbook: &OrderBook,
+            _timestamp: u128,
+        ) -> Vec<OutboxEvent> {
+            result
+                .fills()
+                .iter()
+                .map(|fill| OutboxEvent {
+                    topic: orderbook.get_id().clone(),
+                    schema_name: "FillReport".to_string(),
+                    payload: fill.quantity.to_be_bytes().to_vec(),
+                })
+                .collect()
+        }
+    }
+
+    #[test]
+    fn it_runs_the_hooks_in_order() {
+        let narrow = Arc::new(PriceBand {
+            max_price: 100,
+            seen: AtomicUsize::new(0),
+        });
+        let wide = Arc::new(PriceBand {
+            max_price: 200,
+            seen: AtomicUsize::new(0),
+        });
+        let mut hooks = TradeHooks::default();
+        hooks.add_pre_trade(narrow.clone());
+        hooks.add_pre_trade(wide.clone());
+        hooks.add_post_trade(Arc::new(FillReport));
+
+        let mut orderbook = OrderBook::default();
+        let ask = Operation::Limit(LimitOrder::new(
+            OrderId(1),
+            Price(150),
+            Quantity(10),
+            Side::Ask,
+        ));
+        let stats = BookStats::capture(&orderbook, false);
+        assert_eq!(
+            hooks.before_trade(&ask, &stats),
+            Err(Rejection::Custom("price above 100".to_string()))
+        );
+        // a rejection skips the hooks after it
+        assert_eq!(narrow.seen.load(Ordering::Relaxed), 1);
+        assert_eq!(wide.seen.load(Ordering::Relaxed), 0);
+
+        let ask = Operation::Limit(LimitOrder::new(
+            OrderId(1),
+            Price(90),
+            Quantity(10),
+            Side::Ask,
+        ));
+        assert_eq!(hooks.before_trade(&ask, &stats), Ok(()));
+        assert_eq!(wide.seen.load(Ordering::Relaxed), 1);
+
+        let result = orderbook.execute(ask);
+        assert!(hooks.after_trade(&ask, &result, &orderbook, 0).is_empty());
+        let bid = Operation::Limit(LimitOrder::new(
+            OrderId(2),
+            Price(90),
+            Quantity(4),
+            Side::Bid,
+        ));
+        let result = orderbook.execute(bid);
+        let events = hooks.after_trade(&bid, &result, &orderbook, 0);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].payload, 4u64.to_be_bytes().to_vec());
+    }
+}
@@ -0,0 +1,120 @@
+use crate::core::errors::Rejection;
+use crate::core::models::Operation;
+use crate::engine::risk::resting_limits::RestingLimits;
+use crate::engine::risk::{BookStats, RiskCheck};
+
+/// Rejects passive operations that would take the liquidity resting in the book beyond the
+/// resting limits of the symbol. This is the check executors run unless another one is installed.
+pub struct LimitsRiskCheck {
+    pub resting_limits: RestingLimits,
+}
+
+impl LimitsRiskCheck {
+    pub fn new(resting_limits: RestingLimits) -> Self {
+        Self { resting_limits }
+    }
+}
+
+impl RiskCheck for LimitsRiskCheck {
+    fn check(&self, operation: &Operation, stats: &BookStats) -> Result<(), Rejection> {
+        let (quantity, notional) = stats.resting_increase(operation);
+        let RestingLimits {
+            max_quantity,
+            max_notional,
+        } = self.resting_limits;
+        let resting_quantity = stats.resting_quantity.saturating_add(quantity);
+        if quantity > 0 && max_quantity.is_some_and(|limit| resting_quantity > limit) {
+            return Err(Rejection::RestingQuantityLimit);
+        }
+        let resting_notional = stats.resting_notional.saturating_add(notional);
+        if notional > 0 && max_notional.is_some_and(|limit| resting_notional > limit) {
+            return Err(Rejection::RestingNotionalLimit);
+        }
+        Ok(())
+    }
+
+    fn needs_resting_totals(&self) -> bool {
+        !self.resting_limits.is_unlimited()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::models::{LimitOrder, Operation, Side};
+    use crate::core::orderbook::OrderBook;
+    use crate::core::types::{OrderId, Price, Quantity};
+    use crate::engine::risk::limits_check::LimitsRiskCheck;
+    use crate::engine::risk::resting_limits::RestingLimits;
+    use crate::engine::risk::{BookStats, NoRiskCheck, RiskCheck};
+
+    fn check_batch(risk_check: &dyn RiskCheck, batch: &[Operation], book: &OrderBook) -> Vec<bool> {
+        let mut stats = BookStats::capture(book, risk_check.needs_resting_totals());
+        batch
+            .iter()
+            .map(|operation| {
+                let accepted = risk_check.check(operation, &stats).is_ok();
+                if accepted {
+                    stats.record(operation);
+                }
+                accepted
+            })
+            .collect()
+    }
+
+    #[test]
+    fn it_rejects_passive_orders_beyond_the_resting_limits() {
+        let mut book = OrderBook::default();
+        book.execute(Operation::Limit(LimitOrder::new(
+            OrderId(1),
+            Price(100),
+            Quantity(5),
+            Side::Ask,
+        )));
+        let batch = [
+            Operation::Limit(LimitOrder::new(
+                OrderId(2),
+                Price(90),
+                Quantity(5),
+                Side::Bid,
+            )),
+            Operation::Limit(LimitOrder::new(
+                OrderId(3),
+                Price(110),
+                Quantity(1),
+                Side::Ask,
+            )),
+            Operation::Limit(LimitOrder::new(
+                OrderId(4),
+                Price(100),
+                Quantity(5),
+                Side::Bid,
+            )),
+            Operation::Modify(LimitOrder::new(
+                OrderId(1),
+                Price(100),
+                Quantity(4),
+                Side::Ask,
+            )),
+            Operation::Modify(LimitOrder::new(
+                OrderId(1),
+                Price(105),
+                Quantity(5),
+                Side::Ask,
+            )),
+            Operation::Modify(LimitOrder::new(
+                OrderId(1),
+                Price(120),
+                Quantity(5),
+                Side::Ask,
+            )),
+        ];
+        let limits_check = LimitsRiskCheck::new(RestingLimits::from_raw(0, 1_000));
+        assert_eq!(
+            check_batch(&limits_check, &batch, &book),
+            vec![true, false, true, true, true, false]
+        );
+        assert!(check_batch(&NoRiskCheck, &batch, &book)
+            .into_iter()
+            .all(|accepted| accepted));
+    }
+}
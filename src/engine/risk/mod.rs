@@ -0,0 +1,100 @@
+use crate::core::errors::Rejection;
+use crate::core::models::{LimitOrder, Operation, Side};
+use crate::core::orderbook::OrderBook;
+
+pub mod hooks;
+pub mod limits_check;
+pub mod resting_limits;
+
+/// A pre-trade check the executor runs on every operation before it is matched, so that custom
+/// risk logic such as margin or credit checks can be plugged in without changing the executor.
+/// Operations are checked before the batch is written to the write ahead log, which only ever
+/// records accepted operations, so checks have to decide on the operation and the stats alone.
+pub trait RiskCheck: Send + Sync {
+    /// Decides whether the operation may be executed, the error is the reason it is rejected.
+    fn check(&self, operation: &Operation, stats: &BookStats) -> Result<(), Rejection>;
+
+    /// Whether the check reads the resting totals of the stats, which takes a walk over the book.
+    fn needs_resting_totals(&self) -> bool {
+        false
+    }
+}
+
+/// Accepts every operation.
+pub struct NoRiskCheck;
+
+impl RiskCheck for NoRiskCheck {
+    fn check(&self, _operation: &Operation, _stats: &BookStats) -> Result<(), Rejection> {
+        Ok(())
+    }
+}
+
+/// The state of the orderbook at the start of a batch, along with the resting totals of the
+/// operations accepted earlier in the batch.
+pub struct BookStats<'a> {
+    pub orderbook: &'a OrderBook,
+    pub max_bid: Option<u64>,
+    pub min_ask: Option<u64>,
+    pub last_trade_price: u64,
+    /// Quantity resting on both sides, zero unless resting totals were collected.
+    pub resting_quantity: u64,
+    /// Notional resting on both sides, zero unless resting totals were collected.
+    pub resting_notional: u64,
+}
+
+impl<'a> BookStats<'a> {
+    pub fn capture(orderbook: &'a OrderBook, resting_totals: bool) -> Self {
+        let (resting_quantity, resting_notional) = match resting_totals {
+            true => orderbook.resting_totals(),
+            false => (0, 0),
+        };
+        Self {
+            orderbook,
+            max_bid: orderbook.get_max_bid(),
+            min_ask: orderbook.get_min_ask(),
+            last_trade_price: orderbook.get_last_trade_price(),
+            resting_quantity,
+            resting_notional,
+        }
+    }
+
+    /// Whether the order would rest without matching against the top of the opposite side.
+    pub fn is_passive(&self, order: &LimitOrder) -> bool {
+        match order.side {
+            Side::Bid => self.min_ask.is_none_or(|min_ask| order.price < min_ask),
+            Side::Ask => self.max_bid.is_none_or(|max_bid| order.price > max_bid),
+        }
+    }
+
+    /// The quantity and notional a passive operation adds to the resting totals. Modifications
+    /// only add what they exceed the order they replace by.
+    pub fn resting_increase(&self, operation: &Operation) -> (u64, u64) {
+        match operation {
+            Operation::Limit(order) if self.is_passive(order) => size(order),
+            Operation::Modify(order) if self.is_passive(order) => {
+                let (quantity, notional) = size(order);
+                let (old_quantity, old_notional) = self
+                    .orderbook
+                    .get_order(order.id)
+                    .as_ref()
+                    .map_or((0, 0), size);
+                (
+                    quantity.saturating_sub(old_quantity),
+                    notional.saturating_sub(old_notional),
+                )
+            }
+            _ => (0, 0),
+        }
+    }
+
+    /// Counts an accepted operation towards the resting totals seen by the rest of the batch.
+    pub fn record(&mut self, operation: &Operation) {
+        let (quantity, notional) = self.resting_increase(operation);
+        self.resting_quantity = self.resting_quantity.saturating_add(quantity);
+        self.resting_notional = self.resting_notional.saturating_add(notional);
+    }
+}
+
+fn size(order: &LimitOrder) -> (u64, u64) {
+    (order.quantity, order.price.saturating_mul(order.quantity))
+}
@@ -0,0 +1,24 @@
+/// Caps on the liquidity resting in the orderbook of a symbol, where `None` leaves the dimension
+/// unlimited. Once a cap is reached passive orders are rejected, while orders that take liquidity
+/// are still accepted so the book can drain.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct RestingLimits {
+    /// The largest total quantity resting on both sides of the book.
+    pub max_quantity: Option<u64>,
+    /// The largest total notional value resting on both sides of the book.
+    pub max_notional: Option<u64>,
+}
+
+impl RestingLimits {
+    /// Builds limits from raw values, where zero stands for unlimited.
+    pub fn from_raw(max_quantity: u64, max_notional: u64) -> Self {
+        Self {
+            max_quantity: (max_quantity > 0).then_some(max_quantity),
+            max_notional: (max_notional > 0).then_some(max_notional),
+        }
+    }
+
+    pub fn is_unlimited(&self) -> bool {
+        self.max_quantity.is_none() && self.max_notional.is_none()
+    }
+}
@@ -0,0 +1,590 @@
+use crate::engine::accounts::ledger::{Balance, Ledger, SymbolAssets};
+use crate::engine::accounts::limits::RiskLimits as AccountRiskLimits;
+use crate::engine::configuration::kafka_configuration::KafkaConfiguration;
+use crate::engine::configuration::persistence_configuration::PersistenceConfiguration;
+use crate::engine::configuration::server_configuration::ServerConfiguration;
+use crate::engine::constants::property_loader::parse_matching_policy;
+use crate::engine::errors::EngineError;
+use crate::engine::risk::resting_limits::RestingLimits;
+use crate::engine::services::replication_service::fence_primary;
+use crate::engine::state::replication::{ReplicationError, Role};
+use crate::engine::state::server_state::ServerState;
+use crate::engine::state::symbol_state::{SymbolSpecification, SymbolState, TradingStatus};
+use crate::engine::tasks::order_exec_task::{Executor, ExecutorCommand};
+use crate::engine::tasks::task_manager::TaskManager;
+use crate::engine::utils::audit::audited;
+use crate::engine::utils::protobuf::{
+    balances_to_proto, memory_stats_to_proto, replication_status_to_proto, risk_limits_to_proto,
+    tasks_to_proto,
+};
+use crate::protobuf::models::{
+    AccountRequest, AdminSymbolRequest, BalanceAdjustmentRequest, BalanceList, CreateSymbolRequest,
+    FaultRequest, ListTasksRequest, MemoryStats, PromoteRequest, ProtectionResetRequest,
+    ReloadConfigRequest, ReplicationStatus, RiskLimits, SnapshotRequest, StringResponse, TaskList,
+};
+use crate::protobuf::services::admin_server::{Admin, AdminServer};
+use std::fmt::Debug;
+use std::future::Future;
+use std::sync::Arc;
+use subtle::ConstantTimeEq;
+use tokio::sync::oneshot;
+use tonic::service::Interceptor;
+use tonic::{codegen::InterceptedService, Code, Request, Response, Status};
+use tracing::{error, info, warn};
+
+pub type AdminServiceServer = InterceptedService<AdminServer<AdminService>, AdminInterceptor>;
+
+/// Principal audited requests are recorded under, as the admin token is shared by all operators.
+const ADMIN_PRINCIPAL: &str = "admin";
+
+/// Operator facing service that controls trading without restarting the process.
+pub struct AdminService {
+    server_configuration: Arc<ServerConfiguration>,
+    kafka_configuration: Arc<KafkaConfiguration>,
+    persistence_configuration: Arc<PersistenceConfiguration>,
+    state: Arc<ServerState>,
+    task_manager: Arc<TaskManager>,
+}
+
+/// Only lets through requests carrying the configured admin token in their `bearer` metadata,
+/// compared in constant time so that the token cannot be guessed byte by byte.
+#[derive(Clone)]
+pub struct AdminInterceptor {
+    token: String,
+}
+
+impl AdminInterceptor {
+    pub fn new(token: String) -> Self {
+        Self { token }
+    }
+}
+
+impl Interceptor for AdminInterceptor {
+    fn call(&mut self, request: Request<()>) -> Result<Request<()>, Status> {
+        match request.metadata().get("bearer") {
+            Some(token) if bool::from(token.as_bytes().ct_eq(self.token.as_bytes())) => Ok(request),
+            _ => {
+                warn!("rejected unauthenticated admin request");
+                Err(Status::unauthenticated("invalid admin token"))
+            }
+        }
+    }
+}
+
+impl AdminService {
+    pub fn create(
+        server_configuration: Arc<ServerConfiguration>,
+        kafka_configuration: Arc<KafkaConfiguration>,
+        persistence_configuration: Arc<PersistenceConfiguration>,
+        state: Arc<ServerState>,
+        task_manager: Arc<TaskManager>,
+    ) -> AdminServiceServer {
+        let token = server_configuration.server_properties.admin_token.clone();
+        AdminServer::with_interceptor(
+            AdminService {
+                server_configuration,
+                kafka_configuration,
+                persistence_configuration,
+                state,
+                task_manager,
+            },
+            AdminInterceptor { token },
+        )
+    }
+
+    fn symbol_state(
+        &self,
+        request: Request<AdminSymbolRequest>,
+    ) -> Result<Arc<SymbolState>, Status> {
+        let symbol = request.into_inner().symbol;
+        self.state
+            .symbol(&symbol)
+            .ok_or_else(|| EngineError::UnknownSymbol(symbol).into())
+    }
+
+    fn build_symbol_specification(
+        &self,
+        request: &CreateSymbolRequest,
+    ) -> Result<SymbolSpecification, Status> {
+        let defaults = SymbolSpecification::from_properties(
+            &self.server_configuration.server_properties,
+            &request.symbol,
+        );
+        Ok(SymbolSpecification {
+            tick_size: request.tick_size,
+            lot_size: request.lot_size,
+            queue_capacity: match request.queue_capacity {
+                0 => defaults.queue_capacity,
+                queue_capacity => queue_capacity as usize,
+            },
+            store_capacity: match request.store_capacity {
+                0 => defaults.store_capacity,
+                store_capacity => store_capacity as usize,
+            },
+            matching_policy: match request.matching_policy.as_str() {
+                "" => defaults.matching_policy,
+                policy => parse_matching_policy(policy, request.matching_seed)
+                    .map_err(Status::invalid_argument)?,
+            },
+            channel_capacities: defaults.channel_capacities,
+            assets: SymbolAssets {
+                base: match request.base_asset.as_str() {
+                    "" => defaults.assets.base,
+                    base_asset => base_asset.to_string(),
+                },
+                quote: match request.quote_asset.as_str() {
+                    "" => defaults.assets.quote,
+                    quote_asset => quote_asset.to_string(),
+                },
+            },
+            resting_limits: RestingLimits {
+                max_quantity: match request.max_resting_quantity {
+                    0 => defaults.resting_limits.max_quantity,
+                    max_quantity => Some(max_quantity),
+                },
+                max_notional: match request.max_resting_notional {
+                    0 => defaults.resting_limits.max_notional,
+                    max_notional => Some(max_notional),
+                },
+            },
+        })
+    }
+
+    /// Handles a request that changes the state of the server, recording it in the audit log.
+    async fn audited<T: Debug, R, F>(
+        &self,
+        method: &str,
+        request: Request<T>,
+        handler: impl FnOnce(Request<T>) -> F,
+    ) -> Result<Response<R>, Status>
+    where
+        F: Future<Output = Result<Response<R>, Status>>,
+    {
+        let audit_log = self.state.audit_log.as_deref();
+        audited(audit_log, ADMIN_PRINCIPAL, method, request, handler).await
+    }
+
+    fn ledger(&self) -> Result<&Ledger, Status> {
+        self.state
+            .ledger
+            .as_deref()
+            .ok_or_else(|| Status::failed_precondition("the ledger is not enabled"))
+    }
+
+    fn adjust_balance(
+        &self,
+        request: Request<BalanceAdjustmentRequest>,
+        adjustment: impl FnOnce(&Ledger, &str, &str, u64) -> Result<Balance, String>,
+    ) -> Result<Response<BalanceList>, Status> {
+        let request = request.into_inner();
+        if request.account.is_empty() || request.asset.is_empty() {
+            return Err(Status::invalid_argument(
+                "account and asset must not be empty",
+            ));
+        }
+        let ledger = self.ledger()?;
+        adjustment(ledger, &request.account, &request.asset, request.amount)
+            .map_err(Status::failed_precondition)?;
+        info!(
+            "adjusted {} balance of {} by {}",
+            request.asset, request.account, request.amount
+        );
+        let balances = ledger.balances(&request.account);
+        Ok(Response::new(balances_to_proto(request.account, balances)))
+    }
+
+    fn set_trading_status(
+        &self,
+        request: Request<AdminSymbolRequest>,
+        trading_status: TradingStatus,
+    ) -> Result<Response<StringResponse>, Status> {
+        let symbol_state = self.symbol_state(request)?;
+        symbol_state.set_trading_status(trading_status);
+        info!(
+            "trading status of {} set to {:?}",
+            symbol_state.symbol, trading_status
+        );
+        Ok(Response::new(StringResponse {
+            message: "ok".to_string(),
+        }))
+    }
+
+    /// Sends a command to the executor of the symbol and waits for it to be carried out.
+    async fn send_command<T>(
+        &self,
+        symbol_state: &SymbolState,
+        command: impl FnOnce(oneshot::Sender<T>) -> ExecutorCommand,
+    ) -> Result<T, Status> {
+        let (reply, response) = oneshot::channel();
+        if let Err(e) = symbol_state.command_sender.send(command(reply)).await {
+            error!("failed to send command to executor: {}", e);
+            return Err(Status::internal("internal server error"));
+        }
+        response
+            .await
+            .map_err(|_| Status::unavailable("executor stopped before completing the command"))
+    }
+}
+
+#[tonic::async_trait]
+impl Admin for AdminService {
+    async fn halt(
+        &self,
+        request: Request<AdminSymbolRequest>,
+    ) -> Result<Response<StringResponse>, Status> {
+        self.audited("halt", request, |request| async move {
+            self.set_trading_status(request, TradingStatus::Halted)
+        })
+        .await
+    }
+
+    async fn resume(
+        &self,
+        request: Request<AdminSymbolRequest>,
+    ) -> Result<Response<StringResponse>, Status> {
+        self.audited("resume", request, |request| async move {
+            self.set_trading_status(request, TradingStatus::Open)
+        })
+        .await
+    }
+
+    async fn cancel_only(
+        &self,
+        request: Request<AdminSymbolRequest>,
+    ) -> Result<Response<StringResponse>, Status> {
+        self.audited("cancel_only", request, |request| async move {
+            self.set_trading_status(request, TradingStatus::CancelOnly)
+        })
+        .await
+    }
+
+    async fn clear(
+        &self,
+        request: Request<AdminSymbolRequest>,
+    ) -> Result<Response<StringResponse>, Status> {
+        self.audited("clear", request, |request| async move {
+            let symbol_state = self.symbol_state(request)?;
+            let cleared = self
+                .send_command(&symbol_state, ExecutorCommand::Clear)
+                .await?;
+            Ok(Response::new(StringResponse {
+                message: format!("cancelled {} orders", cleared),
+            }))
+        })
+        .await
+    }
+
+    async fn drain(
+        &self,
+        request: Request<AdminSymbolRequest>,
+    ) -> Result<Response<StringResponse>, Status> {
+        self.audited("drain", request, |request| async move {
+            let symbol_state = self.symbol_state(request)?;
+            let drained = self
+                .send_command(&symbol_state, ExecutorCommand::Drain)
+                .await?;
+            Ok(Response::new(StringResponse {
+                message: format!("executed {} queued operations", drained),
+            }))
+        })
+        .await
+    }
+
+    async fn snapshot(
+        &self,
+        request: Request<SnapshotRequest>,
+    ) -> Result<Response<StringResponse>, Status> {
+        self.audited("snapshot", request, |_request| async move {
+            self.state.snapshot_notification.notify_one();
+            Ok(Response::new(StringResponse {
+                message: "ok".to_string(),
+            }))
+        })
+        .await
+    }
+
+    async fn create_symbol(
+        &self,
+        request: Request<CreateSymbolRequest>,
+    ) -> Result<Response<StringResponse>, Status> {
+        self.audited("create_symbol", request, |request| async move {
+            let request = request.into_inner();
+            if request.symbol.is_empty() {
+                return Err(Status::invalid_argument("symbol must not be empty"));
+            }
+            let specification = self.build_symbol_specification(&request)?;
+            let symbol_state = self
+                .state
+                .create_symbol(
+                    &request.symbol,
+                    specification,
+                    &self.kafka_configuration.kafka_admin_properties,
+                    &self.persistence_configuration.persistence_properties,
+                )
+                .await
+                .map_err(|e| Status::failed_precondition(e.to_string()))?;
+            Executor::spawn(Arc::clone(&self.state), symbol_state);
+            Ok(Response::new(StringResponse {
+                message: "ok".to_string(),
+            }))
+        })
+        .await
+    }
+
+    /// Stops routing to the symbol, executes whatever is still queued for it and stops its
+    /// executor. Resting orders are kept in the persistence of the symbol, so they are restored if
+    /// the symbol is created again.
+    async fn retire_symbol(
+        &self,
+        request: Request<AdminSymbolRequest>,
+    ) -> Result<Response<StringResponse>, Status> {
+        self.audited("retire_symbol", request, |request| async move {
+            let symbol = request.into_inner().symbol;
+            let symbol_state = self
+                .state
+                .remove_symbol(&symbol)
+                .ok_or_else(|| EngineError::UnknownSymbol(symbol.clone()))?;
+            let drained = self
+                .send_command(&symbol_state, ExecutorCommand::Stop)
+                .await?;
+            if let Some(executor_task) = symbol_state.take_executor_task() {
+                if let Err(e) = executor_task.await {
+                    error!("executor of {} failed while stopping: {}", symbol, e);
+                }
+            }
+            info!("successfully retired symbol {}", symbol);
+            Ok(Response::new(StringResponse {
+                message: format!("executed {} queued operations", drained),
+            }))
+        })
+        .await
+    }
+
+    async fn reload_config(
+        &self,
+        request: Request<ReloadConfigRequest>,
+    ) -> Result<Response<StringResponse>, Status> {
+        self.audited("reload_config", request, |_request| async move {
+            let tunable_properties = self.state.runtime_settings.reload().map_err(|e| {
+                Status::invalid_argument(format!("failed to reload settings: {}", e))
+            })?;
+            Ok(Response::new(StringResponse {
+                message: format!("{:?}", tunable_properties),
+            }))
+        })
+        .await
+    }
+
+    async fn list_tasks(
+        &self,
+        _request: Request<ListTasksRequest>,
+    ) -> Result<Response<TaskList>, Status> {
+        Ok(Response::new(tasks_to_proto(self.task_manager.list())))
+    }
+
+    /// Reports the memory held by the book of the symbol, so that its store and queue capacities
+    /// can be sized from the orders it actually sees.
+    async fn memory_stats(
+        &self,
+        request: Request<AdminSymbolRequest>,
+    ) -> Result<Response<MemoryStats>, Status> {
+        let symbol_state = self.symbol_state(request)?;
+        let stats = symbol_state.memory_stats().await.map_err(|e| {
+            error!(
+                "failed to read memory stats of {}: {}",
+                symbol_state.symbol, e
+            );
+            Status::unavailable("executor stopped before completing the command")
+        })?;
+        Ok(Response::new(memory_stats_to_proto(
+            symbol_state.symbol.clone(),
+            stats,
+        )))
+    }
+
+    async fn credit(
+        &self,
+        request: Request<BalanceAdjustmentRequest>,
+    ) -> Result<Response<BalanceList>, Status> {
+        self.audited("credit", request, |request| async move {
+            self.adjust_balance(request, Ledger::credit)
+        })
+        .await
+    }
+
+    async fn debit(
+        &self,
+        request: Request<BalanceAdjustmentRequest>,
+    ) -> Result<Response<BalanceList>, Status> {
+        self.audited("debit", request, |request| async move {
+            self.adjust_balance(request, Ledger::debit)
+        })
+        .await
+    }
+
+    async fn balances(
+        &self,
+        request: Request<AccountRequest>,
+    ) -> Result<Response<BalanceList>, Status> {
+        let account = request.into_inner().account;
+        let balances = self.ledger()?.balances(&account);
+        Ok(Response::new(balances_to_proto(account, balances)))
+    }
+
+    async fn set_risk_limits(
+        &self,
+        request: Request<RiskLimits>,
+    ) -> Result<Response<RiskLimits>, Status> {
+        self.audited("set_risk_limits", request, |request| async move {
+            let request = request.into_inner();
+            if request.account.is_empty() {
+                return Err(Status::invalid_argument("account must not be empty"));
+            }
+            let ledger = self.ledger()?;
+            let limits = AccountRiskLimits::from_raw(
+                request.max_order_quantity,
+                request.max_open_orders,
+                request.max_notional,
+            );
+            ledger.set_limits(&request.account, limits);
+            info!("risk limits of {} set to {:?}", request.account, limits);
+            Ok(Response::new(risk_limits_to_proto(request.account, limits)))
+        })
+        .await
+    }
+
+    async fn risk_limits(
+        &self,
+        request: Request<AccountRequest>,
+    ) -> Result<Response<RiskLimits>, Status> {
+        let account = request.into_inner().account;
+        let limits = self.ledger()?.limits(&account);
+        Ok(Response::new(risk_limits_to_proto(account, limits)))
+    }
+
+    /// Lets the account quote the symbol again once its market maker protection tripped.
+    async fn reset_protection(
+        &self,
+        request: Request<ProtectionResetRequest>,
+    ) -> Result<Response<StringResponse>, Status> {
+        self.audited("reset_protection", request, |request| async move {
+            let request = request.into_inner();
+            if self.state.protection_limits.is_none() {
+                return Err(Status::failed_precondition(
+                    "market maker protection is not enabled",
+                ));
+            }
+            let symbol_state = self
+                .state
+                .symbol(&request.symbol)
+                .ok_or(EngineError::UnknownSymbol(request.symbol))?;
+            let account = request.account;
+            let reset = self
+                .send_command(&symbol_state, |reply| {
+                    ExecutorCommand::ResetProtection(account.clone(), reply)
+                })
+                .await?;
+            if !reset {
+                return Err(Status::failed_precondition(format!(
+                    "market maker protection of {} has not tripped",
+                    account
+                )));
+            }
+            info!(
+                "reset market maker protection of {} in {}",
+                account, symbol_state.symbol
+            );
+            Ok(Response::new(StringResponse {
+                message: "ok".to_string(),
+            }))
+        })
+        .await
+    }
+
+    /// Makes this replica the primary. The primary is fenced first so that it stops accepting
+    /// orders, which fails the promotion unless forced when the primary cannot be reached. The
+    /// executors apply the replicated batches queued before the promotion ahead of any order and
+    /// drop the ones arriving after it. Not available when the primary is elected.
+    async fn promote(
+        &self,
+        request: Request<PromoteRequest>,
+    ) -> Result<Response<ReplicationStatus>, Status> {
+        self.audited("promote", request, |request| async move {
+            let force = request.into_inner().force;
+            let replication = self
+                .state
+                .replication
+                .as_ref()
+                .ok_or_else(|| Status::failed_precondition("replication is not enabled"))?;
+            let elected = self
+                .server_configuration
+                .server_properties
+                .replication
+                .as_ref()
+                .is_some_and(|settings| settings.election.is_some());
+            if elected {
+                return Err(Status::failed_precondition(
+                    "the primary is decided by leader election",
+                ));
+            }
+            let role = replication.role();
+            if role != Role::Replica {
+                let error = ReplicationError::NotReplica(role.name());
+                return Err(Status::failed_precondition(error.to_string()));
+            }
+            let fencing_token = replication.fencing_token() + 1;
+            let address = replication.primary_address().unwrap_or_default();
+            let admin_token = &self.server_configuration.server_properties.admin_token;
+            match fence_primary(&address, admin_token, fencing_token).await {
+                Ok(status) => info!(
+                    "fenced primary at {}, which is now {}",
+                    address, status.role
+                ),
+                Err(e) if force && e.code() == Code::Unavailable => warn!(
+                    "promoting without fencing the primary at {}: {}",
+                    address,
+                    e.message()
+                ),
+                Err(e) => return Err(e),
+            }
+            replication
+                .promote(fencing_token)
+                .map_err(|e| Status::failed_precondition(e.to_string()))?;
+            Ok(Response::new(replication_status_to_proto(replication)))
+        })
+        .await
+    }
+
+    /// Arms faults in the engine, kafka sends and snapshot uploads, so that integration tests can
+    /// verify the retries, drop policies and draining they are meant to trigger. Only available
+    /// when built with the `chaos` feature.
+    async fn inject_fault(
+        &self,
+        request: Request<FaultRequest>,
+    ) -> Result<Response<StringResponse>, Status> {
+        self.audited("inject_fault", request, |request| async move {
+            #[cfg(feature = "chaos")]
+            {
+                let request = request.into_inner();
+                let faults = &self.state.faults;
+                faults.delay_matching(std::time::Duration::from_micros(
+                    request.matching_delay_micros,
+                ));
+                faults.fail_sends(request.failed_sends);
+                faults.drop_snapshots(request.dropped_snapshots);
+                warn!("injected faults: {:?}", request);
+                Ok(Response::new(StringResponse {
+                    message: "faults injected".to_string(),
+                }))
+            }
+            #[cfg(not(feature = "chaos"))]
+            {
+                let _ = request;
+                Err(Status::unimplemented(
+                    "fault injection requires the chaos feature",
+                ))
+            }
+        })
+        .await
+    }
+}
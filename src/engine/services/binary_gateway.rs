@@ -0,0 +1,496 @@
+use crate::engine::services::order_dispatch_service::OrderDispatchService;
+use crate::engine::state::server_state::ServerState;
+use crate::protobuf::models::{
+    AckStatus, CancelLimitOrderRequest, CreateLimitOrderRequest, CreateMarketOrderRequest,
+    ModifyLimitOrderRequest, OrderAck, OrderSide,
+};
+use crate::protobuf::services::order_dispatcher_server::OrderDispatcher;
+use std::io;
+use std::sync::Arc;
+use thiserror::Error;
+use tokio::io::{AsyncReadExt, AsyncWriteExt, BufReader, BufWriter};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::Notify;
+use tonic::{Code, Request, Response, Status};
+use tracing::{debug, error, info, warn};
+
+/// Sets the account of every following order of the connection, answered with nothing.
+pub const LOGIN: u8 = b'L';
+pub const ENTER_LIMIT: u8 = b'O';
+pub const ENTER_MARKET: u8 = b'M';
+pub const REPLACE: u8 = b'U';
+pub const CANCEL: u8 = b'X';
+pub const ACCEPTED: u8 = b'A';
+pub const REJECTED: u8 = b'J';
+
+const SYMBOL_LENGTH: usize = 8;
+const ACCOUNT_LENGTH: usize = 16;
+const REASON_LENGTH: usize = 64;
+
+/// A fixed-size binary order entry protocol over plain TCP, for traders who cannot afford the
+/// overhead of HTTP/2. Orders are handed to the same dispatcher as the gRPC service, so they pass
+/// the same checks on their way to the executors, and are answered in the order they were sent.
+///
+/// Every message starts with its type, followed by big endian integers and text fields that are
+/// padded with spaces:
+///
+/// * `L` login: account (16).
+/// * `O` enter limit: token (8), symbol (8), side (1), price (8), quantity (8).
+/// * `M` enter market: token (8), symbol (8), side (1), quantity (8).
+/// * `U` replace: token (8), symbol (8), order id (16), side (1), price (8), quantity (8).
+/// * `X` cancel: token (8), symbol (8), order id (16).
+///
+/// Sides are `B` or `S`. The token is chosen by the client and echoed back on the answer:
+///
+/// * `A` accepted: token (8), order id (16), accepted at (16), executed operations (8),
+///   queue depth (8).
+/// * `J` rejected: token (8), order id (16), gRPC status code (1), reason (64).
+///
+/// The connection is closed on a message of an unknown type or with a side other than `B` or `S`,
+/// and every connection is closed on shutdown.
+pub struct BinaryGateway {
+    dispatcher: OrderDispatchService,
+    shutdown_notification: Arc<Notify>,
+}
+
+/// A message sent by a client.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Inbound {
+    Login {
+        account: String,
+    },
+    Limit {
+        token: u64,
+        symbol: String,
+        side: i32,
+        price: u64,
+        quantity: u64,
+    },
+    Market {
+        token: u64,
+        symbol: String,
+        side: i32,
+        quantity: u64,
+    },
+    Replace {
+        token: u64,
+        symbol: String,
+        order_id: u128,
+        side: i32,
+        price: u64,
+        quantity: u64,
+    },
+    Cancel {
+        token: u64,
+        symbol: String,
+        order_id: u128,
+    },
+}
+
+#[derive(Debug, Error)]
+pub enum ProtocolError {
+    #[error("unknown message type {0:#04x}")]
+    UnknownMessage(u8),
+    #[error("message of type {kind:#04x} is {actual} bytes long instead of {expected}")]
+    Length {
+        kind: u8,
+        expected: usize,
+        actual: usize,
+    },
+    #[error("invalid side {0:#04x}")]
+    InvalidSide(u8),
+    #[error(transparent)]
+    Io(#[from] io::Error),
+}
+
+impl Inbound {
+    /// The length of a message of the type, including the type.
+    pub fn length(kind: u8) -> Option<usize> {
+        match kind {
+            LOGIN => Some(1 + ACCOUNT_LENGTH),
+            ENTER_LIMIT => Some(1 + 8 + SYMBOL_LENGTH + 1 + 8 + 8),
+            ENTER_MARKET => Some(1 + 8 + SYMBOL_LENGTH + 1 + 8),
+            REPLACE => Some(1 + 8 + SYMBOL_LENGTH + 16 + 1 + 8 + 8),
+            CANCEL => Some(1 + 8 + SYMBOL_LENGTH + 16),
+            _ => None,
+        }
+    }
+
+    /// Decodes a whole message, whose length has to match its type.
+    pub fn decode(message: &[u8]) -> Result<Inbound, ProtocolError> {
+        let (&kind, fields) = message.split_first().ok_or(ProtocolError::Length {
+            kind: 0,
+            expected: 1,
+            actual: 0,
+        })?;
+        let expected = Inbound::length(kind).ok_or(ProtocolError::UnknownMessage(kind))?;
+        if message.len() != expected {
+            return Err(ProtocolError::Length {
+                kind,
+                expected,
+                actual: message.len(),
+            });
+        }
+        let mut reader = Fields(fields);
+        Ok(match kind {
+            LOGIN => Inbound::Login {
+                account: reader.text(ACCOUNT_LENGTH),
+            },
+            ENTER_LIMIT => Inbound::Limit {
+                token: reader.u64(),
+                symbol: reader.text(SYMBOL_LENGTH),
+                side: reader.side()?,
+                price: reader.u64(),
+                quantity: reader.u64(),
+            },
+            ENTER_MARKET => Inbound::Market {
+                token: reader.u64(),
+                symbol: reader.text(SYMBOL_LENGTH),
+                side: reader.side()?,
+                quantity: reader.u64(),
+            },
+            REPLACE => Inbound::Replace {
+                token: reader.u64(),
+                symbol: reader.text(SYMBOL_LENGTH),
+                order_id: reader.u128(),
+                side: reader.side()?,
+                price: reader.u64(),
+                quantity: reader.u64(),
+            },
+            CANCEL => Inbound::Cancel {
+                token: reader.u64(),
+                symbol: reader.text(SYMBOL_LENGTH),
+                order_id: reader.u128(),
+            },
+            kind => return Err(ProtocolError::UnknownMessage(kind)),
+        })
+    }
+}
+
+/// Reads the fields of a message in sequence, whose length was checked against its type.
+struct Fields<'a>(&'a [u8]);
+
+impl Fields<'_> {
+    fn take(&mut self, length: usize) -> &[u8] {
+        let (field, rest) = self.0.split_at(length);
+        self.0 = rest;
+        field
+    }
+
+    fn u64(&mut self) -> u64 {
+        u64::from_be_bytes(self.take(8).try_into().unwrap())
+    }
+
+    fn u128(&mut self) -> u128 {
+        u128::from_be_bytes(self.take(16).try_into().unwrap())
+    }
+
+    fn text(&mut self, length: usize) -> String {
+        let field = self.take(length);
+        String::from_utf8_lossy(field)
+            .trim_end_matches([' ', '\0'])
+            .to_string()
+    }
+
+    fn side(&mut self) -> Result<i32, ProtocolError> {
+        match self.take(1)[0] {
+            b'B' => Ok(OrderSide::Bid as i32),
+            b'S' => Ok(OrderSide::Ask as i32),
+            side => Err(ProtocolError::InvalidSide(side)),
+        }
+    }
+}
+
+/// Encodes the answer to an order, where failures of the server itself are rejected with their
+/// status code like any other rejection.
+pub fn encode_answer(token: u64, outcome: Result<Response<OrderAck>, Status>) -> Vec<u8> {
+    let (ack, code) = match outcome {
+        Ok(response) => {
+            let ack = response.into_inner();
+            let code = match ack.status() {
+                AckStatus::Accepted => Code::Ok,
+                AckStatus::Rejected => Code::FailedPrecondition,
+            };
+            (ack, code)
+        }
+        Err(status) => (
+            OrderAck {
+                status: AckStatus::Rejected as i32,
+                rejection_reason: status.message().to_string(),
+                ..Default::default()
+            },
+            status.code(),
+        ),
+    };
+    let order_id = <[u8; 16]>::try_from(ack.order_id.as_slice()).unwrap_or_default();
+    let mut message = Vec::with_capacity(1 + 8 + 16 + 1 + REASON_LENGTH);
+    match ack.status() {
+        AckStatus::Accepted => {
+            let accepted_at = <[u8; 16]>::try_from(ack.accepted_at.as_slice()).unwrap_or_default();
+            message.push(ACCEPTED);
+            message.extend_from_slice(&token.to_be_bytes());
+            message.extend_from_slice(&order_id);
+            message.extend_from_slice(&accepted_at);
+            message.extend_from_slice(&ack.executed_operations.to_be_bytes());
+            message.extend_from_slice(&ack.queue_depth.to_be_bytes());
+        }
+        AckStatus::Rejected => {
+            let mut reason = [b' '; REASON_LENGTH];
+            let text = ack.rejection_reason.as_bytes();
+            let length = text.len().min(REASON_LENGTH);
+            reason[..length].copy_from_slice(&text[..length]);
+            message.push(REJECTED);
+            message.extend_from_slice(&token.to_be_bytes());
+            message.extend_from_slice(&order_id);
+            message.push(code as u8);
+            message.extend_from_slice(&reason);
+        }
+    }
+    message
+}
+
+impl BinaryGateway {
+    pub fn new(state: &ServerState) -> BinaryGateway {
+        BinaryGateway {
+            dispatcher: OrderDispatchService::new(state),
+            shutdown_notification: Arc::clone(&state.shutdown_notification),
+        }
+    }
+
+    /// Accepts connections until shutdown, serving each of them on a task of its own.
+    pub async fn serve(self: Arc<Self>, listener: TcpListener) -> io::Result<()> {
+        let shutdown = self.shutdown_notification.notified();
+        tokio::pin!(shutdown);
+        loop {
+            let (stream, address) = tokio::select! {
+                accepted = listener.accept() => accepted?,
+                _ = &mut shutdown => return Ok(()),
+            };
+            debug!("accepted binary order entry connection from {}", address);
+            let gateway = Arc::clone(&self);
+            tokio::spawn(async move {
+                match gateway.handle(stream).await {
+                    Ok(()) => debug!("binary order entry connection from {} closed", address),
+                    Err(e) => warn!(
+                        "closed binary order entry connection from {}: {}",
+                        address, e
+                    ),
+                }
+            });
+        }
+    }
+
+    async fn handle(&self, stream: TcpStream) -> Result<(), ProtocolError> {
+        stream.set_nodelay(true)?;
+        let (reader, writer) = stream.into_split();
+        let mut reader = BufReader::new(reader);
+        let mut writer = BufWriter::new(writer);
+        let mut account = String::new();
+        let mut message = Vec::new();
+        let shutdown = self.shutdown_notification.notified();
+        tokio::pin!(shutdown);
+        shutdown.as_mut().enable();
+        loop {
+            let read = tokio::select! {
+                read = reader.read_u8() => read,
+                _ = &mut shutdown => return Ok(()),
+            };
+            let kind = match read {
+                Ok(kind) => kind,
+                Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(()),
+                Err(e) => return Err(e.into()),
+            };
+            let length = Inbound::length(kind).ok_or(ProtocolError::UnknownMessage(kind))?;
+            message.resize(length, 0);
+            message[0] = kind;
+            reader.read_exact(&mut message[1..]).await?;
+            let answer = match Inbound::decode(&message)? {
+                Inbound::Login { account: login } => {
+                    info!("binary order entry connection logged in as {}", login);
+                    account = login;
+                    continue;
+                }
+                order => self.dispatch(order, &account).await,
+            };
+            writer.write_all(&answer).await?;
+            // answers are batched while more orders are waiting to be read
+            if reader.buffer().is_empty() {
+                writer.flush().await?;
+            }
+        }
+    }
+
+    async fn dispatch(&self, order: Inbound, account: &str) -> Vec<u8> {
+        let account = account.to_string();
+        let (token, outcome) = match order {
+            Inbound::Limit {
+                token,
+                symbol,
+                side,
+                price,
+                quantity,
+            } => {
+                let request = CreateLimitOrderRequest {
+                    price,
+                    quantity,
+                    side,
+                    symbol,
+                    account,
+                    ..Default::default()
+                };
+                (token, self.dispatcher.limit(Request::new(request)).await)
+            }
+            Inbound::Market {
+                token,
+                symbol,
+                side,
+                quantity,
+            } => {
+                let request = CreateMarketOrderRequest {
+                    quantity,
+                    side,
+                    symbol,
+                    account,
+                    ..Default::default()
+                };
+                (token, self.dispatcher.market(Request::new(request)).await)
+            }
+            Inbound::Replace {
+                token,
+                symbol,
+                order_id,
+                side,
+                price,
+                quantity,
+            } => {
+                let request = ModifyLimitOrderRequest {
+                    order_id: order_id.to_be_bytes().to_vec(),
+                    price,
+                    quantity,
+                    side,
+                    symbol,
+                    account,
+                };
+                (token, self.dispatcher.modify(Request::new(request)).await)
+            }
+            Inbound::Cancel {
+                token,
+                symbol,
+                order_id,
+            } => {
+                let request = CancelLimitOrderRequest {
+                    order_id: order_id.to_be_bytes().to_vec(),
+                    symbol,
+                    account,
+                };
+                (token, self.dispatcher.cancel(Request::new(request)).await)
+            }
+            Inbound::Login { .. } => unreachable!("logins are handled by the connection"),
+        };
+        if let Err(status) = &outcome {
+            error!("failed to dispatch binary order: {}", status);
+        }
+        encode_answer(token, outcome)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::engine::services::binary_gateway::{
+        encode_answer, Inbound, ProtocolError, ACCEPTED, CANCEL, ENTER_LIMIT, ENTER_MARKET, LOGIN,
+        REJECTED,
+    };
+    use crate::protobuf::models::{AckStatus, OrderAck};
+    use tonic::{Code, Response, Status};
+
+    #[test]
+    fn it_decodes_fixed_size_messages() {
+        let mut login = vec![LOGIN];
+        login.extend_from_slice(b"alice           ");
+        assert_eq!(login.len(), Inbound::length(LOGIN).unwrap());
+        assert_eq!(
+            Inbound::decode(&login).unwrap(),
+            Inbound::Login {
+                account: "alice".to_string()
+            }
+        );
+
+        let mut limit = vec![ENTER_LIMIT];
+        limit.extend_from_slice(&7u64.to_be_bytes());
+        limit.extend_from_slice(b"ETHUSD  ");
+        limit.push(b'S');
+        limit.extend_from_slice(&100u64.to_be_bytes());
+        limit.extend_from_slice(&5u64.to_be_bytes());
+        assert_eq!(limit.len(), Inbound::length(ENTER_LIMIT).unwrap());
+        assert_eq!(
+            Inbound::decode(&limit).unwrap(),
+            Inbound::Limit {
+                token: 7,
+                symbol: "ETHUSD".to_string(),
+                side: 1,
+                price: 100,
+                quantity: 5,
+            }
+        );
+        assert!(Inbound::length(b'?').is_none());
+        assert!(Inbound::decode(b"?").is_err());
+    }
+
+    #[test]
+    fn it_rejects_malformed_messages() {
+        assert!(matches!(
+            Inbound::decode(&[]),
+            Err(ProtocolError::Length { actual: 0, .. })
+        ));
+        assert!(matches!(
+            Inbound::decode(&[ENTER_LIMIT, 0, 0]),
+            Err(ProtocolError::Length {
+                kind: ENTER_LIMIT,
+                expected: 34,
+                actual: 3
+            })
+        ));
+        assert!(matches!(
+            Inbound::decode(&[CANCEL; 50]),
+            Err(ProtocolError::Length { actual: 50, .. })
+        ));
+
+        // raw protobuf sides are not taken for `B` and `S`
+        for side in [0x00, 0x01, b'b'] {
+            let mut market = vec![ENTER_MARKET];
+            market.extend_from_slice(&7u64.to_be_bytes());
+            market.extend_from_slice(b"ETHUSD  ");
+            market.push(side);
+            market.extend_from_slice(&5u64.to_be_bytes());
+            assert!(matches!(
+                Inbound::decode(&market),
+                Err(ProtocolError::InvalidSide(invalid)) if invalid == side
+            ));
+        }
+    }
+
+    #[test]
+    fn it_encodes_answers() {
+        let ack = OrderAck {
+            status: AckStatus::Accepted as i32,
+            order_id: 9u128.to_be_bytes().to_vec(),
+            accepted_at: 42u128.to_be_bytes().to_vec(),
+            executed_operations: 3,
+            queue_depth: 1,
+            ..Default::default()
+        };
+        let accepted = encode_answer(7, Ok(Response::new(ack)));
+        assert_eq!(accepted.len(), 57);
+        assert_eq!(accepted[0], ACCEPTED);
+        assert_eq!(&accepted[1..9], &7u64.to_be_bytes());
+        assert_eq!(&accepted[9..25], &9u128.to_be_bytes());
+        assert_eq!(&accepted[41..49], &3u64.to_be_bytes());
+
+        let rejected = encode_answer(8, Err(Status::not_found("unknown symbol: DOGEUSD")));
+        assert_eq!(rejected.len(), 90);
+        assert_eq!(rejected[0], REJECTED);
+        assert_eq!(&rejected[9..25], &[0; 16]);
+        assert_eq!(rejected[25], Code::NotFound as u8);
+        assert!(rejected[26..].starts_with(b"unknown symbol: DOGEUSD "));
+    }
+}
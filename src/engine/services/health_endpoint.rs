@@ -0,0 +1,40 @@
+use crate::engine::state::health::{Health, HealthReport};
+use crate::engine::state::server_state::ServerState;
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::routing::get;
+use axum::{Json, Router};
+use std::sync::Arc;
+
+/// Plain HTTP health endpoints for probes that do not speak gRPC. Both answer with the report of
+/// the last health check, with `200 OK` when passing and `503 Service Unavailable` otherwise.
+///
+/// * `GET /health/live` passes until an executor exited.
+/// * `GET /health/ready` passes while every dependency of the server is healthy.
+pub struct HealthEndpoint;
+
+impl HealthEndpoint {
+    pub fn create(state: Arc<ServerState>) -> Router {
+        Router::new()
+            .route("/health/live", get(live))
+            .route("/health/ready", get(ready))
+            .with_state(Arc::clone(&state.health))
+    }
+}
+
+async fn live(State(health): State<Arc<Health>>) -> (StatusCode, Json<HealthReport>) {
+    let report = health.report();
+    (status_code(report.is_live()), Json(report))
+}
+
+async fn ready(State(health): State<Arc<Health>>) -> (StatusCode, Json<HealthReport>) {
+    let report = health.report();
+    (status_code(report.is_ready()), Json(report))
+}
+
+fn status_code(passing: bool) -> StatusCode {
+    match passing {
+        true => StatusCode::OK,
+        false => StatusCode::SERVICE_UNAVAILABLE,
+    }
+}
@@ -0,0 +1,366 @@
+use crate::core::models::{Depth, Side};
+use crate::engine::configuration::server_configuration::ServerConfiguration;
+use crate::engine::errors::EngineError;
+use crate::engine::services::order_dispatch_service::OrderDispatchService;
+use crate::engine::state::order_history::OrderRevisions;
+use crate::engine::state::server_state::{ServerState, Symbols};
+use crate::protobuf::models::{
+    AckStatus, CancelLimitOrderRequest, CreateLimitOrderRequest, CreateMarketOrderRequest,
+    OrderAck, OrderSide, TimeInForce,
+};
+use crate::protobuf::services::order_dispatcher_server::OrderDispatcher;
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use tonic::{Code, Request, Status};
+
+/// Levels returned on either side of the book when a depth request does not ask for a number.
+const DEFAULT_DEPTH_LEVELS: usize = 10;
+
+/// JSON endpoints for integrators that cannot adopt gRPC. Orders are handed to the same
+/// dispatcher as the gRPC service, so they pass the same checks on their way to the executors.
+///
+/// * `POST /v1/orders/limit` places a limit order.
+/// * `POST /v1/orders/market` places a market order.
+/// * `GET /v1/orders/{symbol}/{order_id}?account=` returns a resting order with its revisions.
+/// * `DELETE /v1/orders/{symbol}/{order_id}?account=` cancels a resting order.
+/// * `GET /v1/depth/{symbol}?levels=` returns the best levels of the book.
+///
+/// Order ids are 32 hexadecimal digits, sides are `Bid` or `Ask`.
+pub struct HttpGateway {
+    dispatcher: OrderDispatchService,
+    symbols: Symbols,
+    max_levels: usize,
+}
+
+impl HttpGateway {
+    pub fn create(
+        server_configuration: Arc<ServerConfiguration>,
+        state: Arc<ServerState>,
+    ) -> Router {
+        let gateway = HttpGateway {
+            dispatcher: OrderDispatchService::new(&state),
+            symbols: Arc::clone(&state.symbols),
+            max_levels: server_configuration.server_properties.stream_max_levels,
+        };
+        Router::new()
+            .route("/v1/orders/limit", post(limit))
+            .route("/v1/orders/market", post(market))
+            .route("/v1/orders/:symbol/:order_id", get(status).delete(cancel))
+            .route("/v1/depth/:symbol", get(depth))
+            .with_state(Arc::new(gateway))
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct LimitOrderBody {
+    pub symbol: String,
+    pub side: Side,
+    pub price: u64,
+    pub quantity: u64,
+    /// Milliseconds since the unix epoch after which the order expires, zero never expires.
+    #[serde(default)]
+    pub expires_at_millis: u64,
+    /// Whether the order expires at the end of the trading session.
+    #[serde(default)]
+    pub day: bool,
+    /// Required when the ledger is enabled.
+    #[serde(default)]
+    pub account: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MarketOrderBody {
+    pub symbol: String,
+    pub side: Side,
+    pub quantity: u64,
+    /// Required when the ledger is enabled.
+    #[serde(default)]
+    pub account: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AccountQuery {
+    #[serde(default)]
+    pub account: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DepthQuery {
+    pub levels: Option<usize>,
+}
+
+/// The acknowledgement of an order, see [`OrderAck`].
+#[derive(Debug, PartialEq, Serialize)]
+pub struct AckBody {
+    pub accepted: bool,
+    /// Not set for rejected new orders, which are never assigned an id.
+    pub order_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rejection_reason: Option<String>,
+    /// Nanoseconds since the unix epoch at which the order was accepted.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub accepted_at: Option<u128>,
+    /// Operations the engine had executed for the symbol when the order was accepted.
+    pub executed_operations: u64,
+    pub queue_depth: u64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct StatusBody {
+    pub order_id: String,
+    #[serde(flatten)]
+    pub revisions: OrderRevisions,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DepthBody {
+    pub symbol: String,
+    /// Number of operations the orderbook has executed.
+    pub sequence: u64,
+    pub last_trade_price: u64,
+    #[serde(flatten)]
+    pub depth: Depth,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ErrorBody {
+    pub error: String,
+}
+
+/// A failed request, answered with the HTTP status closest to its gRPC status.
+#[derive(Debug)]
+pub struct ApiError(Status);
+
+impl From<Status> for ApiError {
+    fn from(status: Status) -> Self {
+        ApiError(status)
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let body = Json(ErrorBody {
+            error: self.0.message().to_string(),
+        });
+        (http_status(self.0.code()), body).into_response()
+    }
+}
+
+fn http_status(code: Code) -> StatusCode {
+    match code {
+        Code::Ok => StatusCode::OK,
+        Code::InvalidArgument | Code::OutOfRange => StatusCode::BAD_REQUEST,
+        Code::Unauthenticated => StatusCode::UNAUTHORIZED,
+        Code::PermissionDenied => StatusCode::FORBIDDEN,
+        Code::NotFound => StatusCode::NOT_FOUND,
+        Code::AlreadyExists | Code::Aborted | Code::FailedPrecondition => StatusCode::CONFLICT,
+        Code::ResourceExhausted => StatusCode::TOO_MANY_REQUESTS,
+        Code::Unimplemented => StatusCode::NOT_IMPLEMENTED,
+        Code::Unavailable => StatusCode::SERVICE_UNAVAILABLE,
+        Code::DeadlineExceeded => StatusCode::GATEWAY_TIMEOUT,
+        Code::Cancelled | Code::Unknown | Code::Internal | Code::DataLoss => {
+            StatusCode::INTERNAL_SERVER_ERROR
+        }
+    }
+}
+
+fn order_id_from_path(order_id: &str) -> Result<u128, ApiError> {
+    match order_id.len() {
+        32 => u128::from_str_radix(order_id, 16).ok(),
+        _ => None,
+    }
+    .ok_or_else(|| Status::invalid_argument("order id must be 32 hexadecimal digits").into())
+}
+
+fn side_to_proto(side: Side) -> OrderSide {
+    match side {
+        Side::Bid => OrderSide::Bid,
+        Side::Ask => OrderSide::Ask,
+    }
+}
+
+/// Rejected orders are answered with 422 along with the reason, like the gRPC service they are
+/// still acknowledged rather than failed.
+fn acknowledge(ack: OrderAck) -> (StatusCode, Json<AckBody>) {
+    let accepted = ack.status() == AckStatus::Accepted;
+    let body = AckBody {
+        accepted,
+        order_id: <[u8; 16]>::try_from(ack.order_id.as_slice())
+            .ok()
+            .map(|id| format!("{:032x}", u128::from_be_bytes(id))),
+        rejection_reason: (!accepted).then_some(ack.rejection_reason),
+        accepted_at: <[u8; 16]>::try_from(ack.accepted_at.as_slice())
+            .ok()
+            .map(u128::from_be_bytes),
+        executed_operations: ack.executed_operations,
+        queue_depth: ack.queue_depth,
+    };
+    match accepted {
+        true => (StatusCode::OK, Json(body)),
+        false => (StatusCode::UNPROCESSABLE_ENTITY, Json(body)),
+    }
+}
+
+async fn limit(
+    State(gateway): State<Arc<HttpGateway>>,
+    Json(body): Json<LimitOrderBody>,
+) -> Result<(StatusCode, Json<AckBody>), ApiError> {
+    let time_in_force = match body.day {
+        true => TimeInForce::Day,
+        false => TimeInForce::GoodTillCancelled,
+    };
+    let request = CreateLimitOrderRequest {
+        price: body.price,
+        quantity: body.quantity,
+        side: side_to_proto(body.side) as i32,
+        symbol: body.symbol,
+        expires_at_millis: body.expires_at_millis,
+        time_in_force: time_in_force as i32,
+        account: body.account,
+    };
+    let ack = gateway.dispatcher.limit(Request::new(request)).await?;
+    Ok(acknowledge(ack.into_inner()))
+}
+
+async fn market(
+    State(gateway): State<Arc<HttpGateway>>,
+    Json(body): Json<MarketOrderBody>,
+) -> Result<(StatusCode, Json<AckBody>), ApiError> {
+    let request = CreateMarketOrderRequest {
+        quantity: body.quantity,
+        side: side_to_proto(body.side) as i32,
+        symbol: body.symbol,
+        account: body.account,
+        ..Default::default()
+    };
+    let ack = gateway.dispatcher.market(Request::new(request)).await?;
+    Ok(acknowledge(ack.into_inner()))
+}
+
+async fn cancel(
+    State(gateway): State<Arc<HttpGateway>>,
+    Path((symbol, order_id)): Path<(String, String)>,
+    Query(query): Query<AccountQuery>,
+) -> Result<(StatusCode, Json<AckBody>), ApiError> {
+    let request = CancelLimitOrderRequest {
+        order_id: order_id_from_path(&order_id)?.to_be_bytes().to_vec(),
+        symbol,
+        account: query.account,
+    };
+    let ack = gateway.dispatcher.cancel(Request::new(request)).await?;
+    Ok(acknowledge(ack.into_inner()))
+}
+
+async fn status(
+    State(gateway): State<Arc<HttpGateway>>,
+    Path((symbol, order_id)): Path<(String, String)>,
+    Query(query): Query<AccountQuery>,
+) -> Result<Json<StatusBody>, ApiError> {
+    let id = order_id_from_path(&order_id)?;
+    let revisions = gateway
+        .dispatcher
+        .order_revisions(&symbol, &query.account, id)?;
+    Ok(Json(StatusBody {
+        order_id: format!("{:032x}", id),
+        revisions,
+    }))
+}
+
+async fn depth(
+    State(gateway): State<Arc<HttpGateway>>,
+    Path(symbol): Path<String>,
+    Query(query): Query<DepthQuery>,
+) -> Result<Json<DepthBody>, ApiError> {
+    let symbol_state = match gateway.symbols.read().unwrap().get(&symbol) {
+        Some(symbol_state) => Arc::clone(symbol_state),
+        None => return Err(Status::from(EngineError::UnknownSymbol(symbol)).into()),
+    };
+    let levels = query
+        .levels
+        .unwrap_or(DEFAULT_DEPTH_LEVELS)
+        .min(gateway.max_levels);
+    let secondary = symbol_state.orderbook_manager.get_secondary();
+    Ok(Json(DepthBody {
+        symbol,
+        sequence: symbol_state.operation_count.load(Ordering::Relaxed),
+        last_trade_price: secondary.get_last_trade_price(),
+        depth: secondary.depth(levels),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::engine::services::http_gateway::{
+        acknowledge, http_status, order_id_from_path, AckBody,
+    };
+    use crate::protobuf::models::{AckStatus, OrderAck};
+    use axum::http::StatusCode;
+    use tonic::Code;
+
+    #[test]
+    fn it_parses_order_ids_of_the_path() {
+        let id = u128::MAX - 1;
+        assert_eq!(order_id_from_path(&format!("{:032x}", id)).unwrap(), id);
+        assert!(order_id_from_path("ff").is_err());
+        assert!(order_id_from_path(&"z".repeat(32)).is_err());
+    }
+
+    #[test]
+    fn it_answers_acknowledgements_by_their_status() {
+        let accepted = OrderAck {
+            status: AckStatus::Accepted as i32,
+            order_id: 7u128.to_be_bytes().to_vec(),
+            accepted_at: 42u128.to_be_bytes().to_vec(),
+            executed_operations: 3,
+            queue_depth: 1,
+            ..Default::default()
+        };
+        let (status, body) = acknowledge(accepted);
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(
+            body.0,
+            AckBody {
+                accepted: true,
+                order_id: Some(format!("{:032x}", 7)),
+                rejection_reason: None,
+                accepted_at: Some(42),
+                executed_operations: 3,
+                queue_depth: 1,
+            }
+        );
+
+        let rejected = OrderAck {
+            status: AckStatus::Rejected as i32,
+            rejection_reason: "trading is halted".to_string(),
+            ..Default::default()
+        };
+        let (status, body) = acknowledge(rejected);
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(body.0.order_id, None);
+        assert_eq!(
+            body.0.rejection_reason.as_deref(),
+            Some("trading is halted")
+        );
+    }
+
+    #[test]
+    fn it_maps_grpc_status_codes_to_http() {
+        assert_eq!(http_status(Code::InvalidArgument), StatusCode::BAD_REQUEST);
+        assert_eq!(http_status(Code::NotFound), StatusCode::NOT_FOUND);
+        assert_eq!(http_status(Code::PermissionDenied), StatusCode::FORBIDDEN);
+        assert_eq!(
+            http_status(Code::Unavailable),
+            StatusCode::SERVICE_UNAVAILABLE
+        );
+        assert_eq!(
+            http_status(Code::Internal),
+            StatusCode::INTERNAL_SERVER_ERROR
+        );
+    }
+}
@@ -1,3 +1,9 @@
+pub mod admin_service;
+pub mod binary_gateway;
+pub mod health_endpoint;
+pub mod http_gateway;
 pub mod order_dispatch_service;
 pub mod orderbook_manager_service;
+pub mod replication_service;
+pub mod router_service;
 pub mod stat_stream_service;
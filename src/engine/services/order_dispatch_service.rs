@@ -1,100 +1,636 @@
+use crate::core::errors::OrderBookError;
 use crate::core::models::{LimitOrder, MarketOrder, Operation, Side};
-use crate::engine::configuration::kafka_configuration::KafkaConfiguration;
-use crate::engine::configuration::server_configuration::ServerConfiguration;
-use crate::engine::state::server_state::ServerState;
-use crate::engine::tasks::order_exec_task::Executor;
-use crate::engine::tasks::task_manager::TaskManager;
+use crate::core::types::{OrderId, Price, Quantity};
+use crate::engine::accounts::ledger::Ledger;
+use crate::engine::accounts::market_making::{quote_id, MAX_QUOTE_LEVELS};
+use crate::engine::accounts::throttle::{Throttle, ThrottleMode, ThrottleViolation, Throttled};
+use crate::engine::errors::EngineError;
+use crate::engine::persistence::audit_log::AuditLog;
+use crate::engine::persistence::outbox::{Outbox, OutboxEvent};
+use crate::engine::persistence::recording::FlowRecorder;
+use crate::engine::persistence::traffic::{RecordedOutcome, TrafficRecord, TrafficRecorder};
+use crate::engine::state::order_history::OrderRevisions;
+use crate::engine::state::overload::is_low_priority;
+use crate::engine::state::replication::Replication;
+use crate::engine::state::server_state::{ServerState, Symbols};
+use crate::engine::state::session::{Session, SessionPhase};
+use crate::engine::state::symbol_state::{SymbolState, TradingStatus};
+use crate::engine::tasks::order_exec_task::{Executor, ExecutorCommand};
+use crate::engine::utils::audit::audited;
+use crate::engine::utils::protobuf::{
+    order_id_from_proto, order_status_to_proto, side_from_proto, throttle_event_to_proto,
+};
+use crate::engine::utils::time::generate_u128_timestamp;
 use crate::protobuf::models::{
-    CancelLimitOrderRequest, CreateLimitOrderRequest, CreateMarketOrderRequest,
-    ModifyLimitOrderRequest, StringResponse,
+    AckStatus, CancelLimitOrderRequest, CreateLimitOrderRequest, CreateMarketOrderRequest,
+    ExecuteQuoteRequest, MassQuoteAck, MassQuoteRequest, ModifyLimitOrderRequest, OrderAck,
+    OrderStatusRequest, OrderStatusResponse, QuoteRequest, ReduceLimitOrderRequest, TimeInForce,
 };
 use crate::protobuf::services::order_dispatcher_server::{OrderDispatcher, OrderDispatcherServer};
+use prost::Message;
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
-use tokio::sync::mpsc;
-use tokio::sync::mpsc::Sender;
-use tonic::{codegen::InterceptedService, Request, Response, Status};
-use tracing::{error, info};
+use tokio::sync::oneshot;
+use tokio::time::sleep;
+use tonic::{codegen::InterceptedService, Code, Request, Response, Status};
+use tracing::{debug, error};
 
 pub type DispatchService = InterceptedService<
     OrderDispatcherServer<OrderDispatchService>,
     fn(Request<()>) -> Result<Request<()>, Status>,
 >;
 
-#[derive(Debug)]
+/// The symbol, the account and the operation of a request.
+type Payload = (String, String, Operation);
+
 pub struct OrderDispatchService {
-    tx: Sender<Operation>,
+    symbols: Symbols,
+    trading_halted: Arc<AtomicBool>,
+    session: Arc<Session>,
+    ledger: Option<Arc<Ledger>>,
+    throttle: Option<Arc<Throttle>>,
+    outbox: Arc<Outbox>,
+    audit_log: Option<Arc<AuditLog>>,
+    recorder: Option<Arc<FlowRecorder>>,
+    traffic_recorder: Option<Arc<TrafficRecorder>>,
+    replication: Option<Arc<Replication>>,
 }
 
 impl OrderDispatchService {
-    pub fn create(
-        server_configuration: Arc<ServerConfiguration>,
-        kafka_configuration: Arc<KafkaConfiguration>,
-        state: Arc<ServerState>,
-        task_manager: &mut TaskManager,
-    ) -> DispatchService {
-        let (tx, rx) = mpsc::channel(10000);
-        task_manager.register("order_exec_task", {
-            async move {
-                Executor::new(server_configuration, kafka_configuration, state, rx)
-                    .run()
-                    .await;
-            }
-        });
-        OrderDispatcherServer::with_interceptor(OrderDispatchService { tx }, Self::interceptor)
+    pub fn create(state: Arc<ServerState>) -> DispatchService {
+        for symbol_state in state.symbols.read().unwrap().values() {
+            Executor::spawn(Arc::clone(&state), Arc::clone(symbol_state));
+        }
+        OrderDispatcherServer::with_interceptor(Self::new(&state), Self::interceptor)
+    }
+
+    /// A dispatcher handing operations to the executors of the symbols, which are expected to be
+    /// spawned by [`OrderDispatchService::create`].
+    pub fn new(state: &ServerState) -> OrderDispatchService {
+        OrderDispatchService {
+            symbols: Arc::clone(&state.symbols),
+            trading_halted: Arc::clone(&state.trading_halted),
+            session: Arc::clone(&state.session),
+            ledger: state.ledger.clone(),
+            throttle: state.throttle.clone(),
+            outbox: Arc::clone(&state.outbox),
+            audit_log: state.audit_log.clone(),
+            recorder: state.recorder.clone(),
+            traffic_recorder: state.traffic_recorder.clone(),
+            replication: state.replication.clone(),
+        }
+    }
+
+    fn build_limit_payload(
+        &self,
+        request: Request<CreateLimitOrderRequest>,
+    ) -> Result<Payload, Status> {
+        let request = request.into_inner();
+        let mut expires_at =
+            (request.expires_at_millis != 0).then(|| request.expires_at_millis as u128 * 1_000_000);
+        if request.time_in_force == TimeInForce::Day as i32 {
+            let session_close = self
+                .session
+                .session_close(generate_u128_timestamp())
+                .ok_or_else(|| {
+                    Status::invalid_argument("day orders require a trading session schedule")
+                })?;
+            expires_at = Some(expires_at.map_or(session_close, |e| e.min(session_close)));
+        }
+        let side = side_from_proto(request.side)?;
+        let mut order =
+            LimitOrder::new_uuid_v4(Price(request.price), Quantity(request.quantity), side);
+        if let Some(expires_at) = expires_at {
+            order = order.with_expiry(expires_at);
+        }
+        Ok((request.symbol, request.account, Operation::Limit(order)))
+    }
+
+    fn build_market_payload(request: Request<CreateMarketOrderRequest>) -> Result<Payload, Status> {
+        let request = request.into_inner();
+        let side = side_from_proto(request.side)?;
+        let operation =
+            Operation::Market(MarketOrder::new_uuid_v4(Quantity(request.quantity), side));
+        Ok((request.symbol, request.account, operation))
     }
 
-    fn build_limit_payload(request: Request<CreateLimitOrderRequest>) -> Operation {
+    fn build_modify_payload(request: Request<ModifyLimitOrderRequest>) -> Result<Payload, Status> {
         let request = request.into_inner();
-        Operation::Limit(LimitOrder::new_uuid_v4(
-            request.price,
-            request.quantity,
-            Side::from(request.side),
-        ))
+        let side = side_from_proto(request.side)?;
+        let operation = Operation::Modify(LimitOrder::new(
+            OrderId(order_id_from_proto(&request.order_id)?),
+            Price(request.price),
+            Quantity(request.quantity),
+            side,
+        ));
+        Ok((request.symbol, request.account, operation))
     }
 
-    fn build_market_payload(request: Request<CreateMarketOrderRequest>) -> Operation {
+    fn build_cancel_payload(request: Request<CancelLimitOrderRequest>) -> Result<Payload, Status> {
         let request = request.into_inner();
-        Operation::Market(MarketOrder::new_uuid_v4(
-            request.quantity,
-            Side::from(request.side),
-        ))
+        let operation = Operation::Cancel(OrderId(order_id_from_proto(&request.order_id)?));
+        Ok((request.symbol, request.account, operation))
     }
 
-    fn build_modify_payload(request: Request<ModifyLimitOrderRequest>) -> Operation {
+    fn build_reduce_payload(request: Request<ReduceLimitOrderRequest>) -> Result<Payload, Status> {
         let request = request.into_inner();
-        Operation::Modify(LimitOrder::new(
-            u128::from_be_bytes(request.order_id.try_into().unwrap()),
-            request.price,
-            request.quantity,
-            Side::from(request.side),
-        ))
+        let operation = Operation::Reduce {
+            id: OrderId(order_id_from_proto(&request.order_id)?),
+            quantity_delta: request.quantity_delta,
+        };
+        Ok((request.symbol, request.account, operation))
     }
 
-    fn build_cancel_payload(request: Request<CancelLimitOrderRequest>) -> Operation {
+    /// Quotes rest under the ids derived for their account, symbol and level, which every quote
+    /// of the account at the level replaces. Single quotes are at level 0.
+    fn build_quote_payload(request: Request<QuoteRequest>) -> Result<Payload, Status> {
         let request = request.into_inner();
-        Operation::Cancel(u128::from_be_bytes(request.order_id.try_into().unwrap()))
+        if request.account.is_empty() {
+            return Err(Status::invalid_argument("account must not be empty"));
+        }
+        let operation = Self::quote(
+            &request.symbol,
+            &request.account,
+            0,
+            (request.bid_price, request.bid_quantity),
+            (request.ask_price, request.ask_quantity),
+        );
+        Ok((request.symbol, request.account, operation))
+    }
+
+    /// The quotes of every level of a mass quote, in the order the levels were sent.
+    fn build_mass_quote_payloads(request: &MassQuoteRequest) -> Result<Vec<Operation>, Status> {
+        if request.account.is_empty() {
+            return Err(Status::invalid_argument("account must not be empty"));
+        }
+        if request.levels.is_empty() {
+            return Err(Status::invalid_argument("mass quote must carry a level"));
+        }
+        let mut quoted = HashSet::new();
+        request
+            .levels
+            .iter()
+            .map(|level| {
+                if level.level >= MAX_QUOTE_LEVELS {
+                    return Err(Status::invalid_argument(format!(
+                        "level must be below {}",
+                        MAX_QUOTE_LEVELS
+                    )));
+                }
+                if !quoted.insert(level.level) {
+                    return Err(Status::invalid_argument(format!(
+                        "level {} is quoted more than once",
+                        level.level
+                    )));
+                }
+                Ok(Self::quote(
+                    &request.symbol,
+                    &request.account,
+                    level.level,
+                    (level.bid_price, level.bid_quantity),
+                    (level.ask_price, level.ask_quantity),
+                ))
+            })
+            .collect()
+    }
+
+    /// The quote of the account at the level, out of the price and quantity of either side.
+    fn quote(
+        symbol: &str,
+        account: &str,
+        level: u32,
+        bid: (u64, u64),
+        ask: (u64, u64),
+    ) -> Operation {
+        let order = |side: Side, (price, quantity): (u64, u64)| {
+            let id = quote_id(symbol, account, level, side);
+            LimitOrder::new(OrderId(id), Price(price), Quantity(quantity), side)
+        };
+        Operation::Quote {
+            bid: order(Side::Bid, bid),
+            ask: order(Side::Ask, ask),
+        }
+    }
+
+    /// Assigns new orders and quotes to the account of the request, and makes sure orders that
+    /// are modified, cancelled or reduced belong to it.
+    fn authorize(ledger: &Ledger, account: &str, payload: &Operation) -> Result<(), Status> {
+        if account.is_empty() {
+            return Err(Status::invalid_argument("account must not be empty"));
+        }
+        match payload {
+            Operation::Limit(_) | Operation::Market(_) => {
+                ledger.assign(payload.order_id(), account);
+                Ok(())
+            }
+            // the ids of a quote are derived from the account, so no other account owns them
+            Operation::Quote { bid, ask } => {
+                ledger.assign(bid.id, account);
+                ledger.assign(ask.id, account);
+                Ok(())
+            }
+            Operation::Modify(_) | Operation::Cancel(_) | Operation::Reduce { .. } => {
+                match ledger.owner(payload.order_id()) {
+                    Some(owner) if owner == account => Ok(()),
+                    _ => Err(Status::permission_denied(
+                        "order does not belong to the account",
+                    )),
+                }
+            }
+            // calls follow the trading session rather than any account
+            Operation::Call | Operation::Uncross => Err(Status::permission_denied(
+                "calls are started and ended by the trading session",
+            )),
+        }
+    }
+
+    /// Admits the message against the flow limits of its account. In delay mode a message over the
+    /// order rate is held back until the next second and only rejected if it is still over.
+    /// The first rejection of an account within a second is published to the topic of the symbol.
+    async fn throttle(
+        &self,
+        throttle: &Throttle,
+        symbol_state: &SymbolState,
+        account: &str,
+        is_cancel: bool,
+    ) -> Result<(), Status> {
+        let mut admitted = throttle.admit(account, is_cancel, generate_u128_timestamp());
+        if let Err(Throttled {
+            violation: ThrottleViolation::OrderRate { retry_after, .. },
+            ..
+        }) = admitted
+        {
+            if throttle.limits().mode == ThrottleMode::Delay {
+                sleep(retry_after).await;
+                admitted = throttle.admit(account, is_cancel, generate_u128_timestamp());
+            }
+        }
+        let Err(throttled) = admitted else {
+            return Ok(());
+        };
+        let reason = throttled.violation.to_string();
+        if throttled.report {
+            let (payload, schema_name) = throttle_event_to_proto(
+                account.to_string(),
+                symbol_state.symbol.clone(),
+                reason.clone(),
+                generate_u128_timestamp(),
+            );
+            let event = OutboxEvent {
+                topic: symbol_state.kafka_topic.clone(),
+                schema_name: schema_name.to_string(),
+                payload,
+            };
+            if let Err(e) = self.outbox.enqueue(vec![event]) {
+                error!("failed to enqueue throttle event to outbox: {}", e);
+            }
+        }
+        Err(Status::resource_exhausted(reason))
     }
 
     fn interceptor(request: Request<()>) -> Result<Request<()>, Status> {
         if let Some(token) = request.metadata().get("bearer") {
-            info!("gRPC request received: {:?}", token);
+            debug!("gRPC request received: {:?}", token);
         }
-        info!("passing through interceptor");
+        debug!("passing through interceptor");
         Ok(request)
     }
 
-    async fn execute(&self, payload: Operation) -> Result<Response<StringResponse>, Status> {
-        match self.tx.send(payload).await {
-            Ok(_) => (),
+    fn symbol_state(&self, symbol: &str) -> Result<Arc<SymbolState>, Status> {
+        match self.symbols.read().unwrap().get(symbol) {
+            Some(symbol_state) => Ok(Arc::clone(symbol_state)),
+            None => Err(EngineError::UnknownSymbol(symbol.to_string()).into()),
+        }
+    }
+
+    /// Runs the checks an operation has to pass before it is handed to the executor of its
+    /// symbol, which assigns new orders to the account once passed.
+    async fn admit(
+        &self,
+        symbol_state: &SymbolState,
+        account: &str,
+        payload: &Operation,
+    ) -> Result<(), Status> {
+        let symbol = &symbol_state.symbol;
+        // reductions and quotes withdrawing both sides only take quantity off the book, so they
+        // pass wherever cancels do
+        let is_cancel = match payload {
+            Operation::Cancel(_) | Operation::Reduce { .. } => true,
+            Operation::Quote { bid, ask } => bid.quantity == 0 && ask.quantity == 0,
+            _ => false,
+        };
+        // only the primary of an active/standby pair takes order flow, cancels included
+        if let Some(replication) = self.replication.as_ref().filter(|r| !r.is_primary()) {
+            return Err(EngineError::Unavailable(format!(
+                "server is {}, send orders to the primary",
+                replication.role().name()
+            ))
+            .into());
+        }
+        match self.session.phase() {
+            SessionPhase::Open => (),
+            // orders rest in the call of the pre-open, but there is no price to take yet
+            SessionPhase::PreOpen if matches!(payload, Operation::Market(_)) => {
+                return Err(Status::failed_precondition(
+                    "market is pre-open, market orders are not accepted",
+                ));
+            }
+            SessionPhase::PreOpen => (),
+            _ if is_cancel => (),
+            phase => {
+                return Err(Status::failed_precondition(format!(
+                    "market is {}, only cancels are accepted",
+                    phase.name()
+                )));
+            }
+        }
+        match symbol_state.trading_status() {
+            TradingStatus::Halted => {
+                return Err(
+                    EngineError::Unavailable(format!("trading in {} is halted", symbol)).into(),
+                );
+            }
+            TradingStatus::CancelOnly if !is_cancel => {
+                return Err(Status::failed_precondition(format!(
+                    "{} only accepts cancels",
+                    symbol
+                )));
+            }
+            _ => (),
+        }
+        // cancels are still accepted while halted so that participants can reduce their exposure
+        if self.trading_halted.load(Ordering::SeqCst) && !is_cancel {
+            return Err(EngineError::Unavailable("trading is halted".to_string()).into());
+        }
+        if symbol_state.overload.is_shedding() {
+            let secondary = symbol_state.orderbook_manager.get_secondary();
+            if is_low_priority(payload, &secondary) {
+                return Err(Status::resource_exhausted(format!(
+                    "{} is overloaded, only cancels and orders that trade are accepted",
+                    symbol
+                )));
+            }
+        }
+        symbol_state.validate(payload)?;
+        if let Some(throttle) = &self.throttle {
+            self.throttle(throttle, symbol_state, account, is_cancel)
+                .await?;
+        }
+        if let Some(ledger) = &self.ledger {
+            Self::authorize(ledger, account, payload)?;
+        }
+        Ok(())
+    }
+
+    /// Forgets the account of a new order that never made it to the executor.
+    fn unassign(&self, payload: &Operation) {
+        if let (Some(ledger), Operation::Limit(_) | Operation::Market(_)) = (&self.ledger, payload)
+        {
+            ledger.unassign(payload.order_id());
+        }
+    }
+
+    /// Records an operation the executor took and acknowledges it.
+    fn accept(
+        &self,
+        symbol_state: &SymbolState,
+        account: &str,
+        payload: &Operation,
+    ) -> Response<OrderAck> {
+        let accepted_at = generate_u128_timestamp();
+        if let Some(recorder) = &self.recorder {
+            if let Err(e) = recorder.record(accepted_at, &symbol_state.symbol, account, payload) {
+                error!("failed to record operation: {}", e);
+            }
+        }
+        let order_sender = &symbol_state.order_sender;
+        Response::new(OrderAck {
+            status: AckStatus::Accepted as i32,
+            order_id: payload.order_id().to_be_bytes().to_vec(),
+            rejection_reason: String::new(),
+            accepted_at: accepted_at.to_be_bytes().to_vec(),
+            executed_operations: symbol_state.operation_count.load(Ordering::Relaxed),
+            queue_depth: (order_sender.max_capacity() - order_sender.capacity()) as u64,
+        })
+    }
+
+    async fn execute(
+        &self,
+        (symbol, account, payload): Payload,
+    ) -> Result<Response<OrderAck>, Status> {
+        let symbol_state = self.symbol_state(&symbol)?;
+        self.admit(&symbol_state, &account, &payload).await?;
+        if let Err(e) = symbol_state.order_sender.send(payload).await {
+            error!("failed to dispatch message: {}", e);
+            self.unassign(&payload);
+            return Err(Status::internal("internal server error"));
+        }
+        Ok(self.accept(&symbol_state, &account, &payload))
+    }
+
+    /// Executes a firm quote as a market order of the account. Unlike other operations the
+    /// acknowledgment waits for the executor, which rejects the order unless the book still
+    /// fills it at the quoted price or better.
+    async fn execute_firm_quote(
+        &self,
+        request: ExecuteQuoteRequest,
+    ) -> Result<Response<OrderAck>, Status> {
+        let symbol_state = self.symbol_state(&request.symbol)?;
+        let quote_id = order_id_from_proto(&request.quote_id)?;
+        let quote = symbol_state
+            .quotes
+            .get(quote_id, generate_u128_timestamp())
+            .map_err(EngineError::from)?;
+        let order = MarketOrder::new_uuid_v4(Quantity(quote.quantity), quote.side);
+        let payload = Operation::Market(order);
+        self.admit(&symbol_state, &request.account, &payload)
+            .await?;
+        // the quote is only used up once its order was admitted
+        if let Err(e) = symbol_state
+            .quotes
+            .redeem(quote_id, generate_u128_timestamp())
+        {
+            self.unassign(&payload);
+            return Err(EngineError::from(e).into());
+        }
+        let (reply, executed) = oneshot::channel();
+        let command = ExecutorCommand::ExecuteQuote(quote, order, reply);
+        let executed = match symbol_state.command_sender.send(command).await {
+            Ok(()) => executed.await.map_err(|e| e.to_string()),
+            Err(e) => Err(e.to_string()),
+        };
+        match executed {
+            Ok(Ok(())) => Ok(self.accept(&symbol_state, &request.account, &payload)),
+            Ok(Err(rejection)) => {
+                self.unassign(&payload);
+                Err(EngineError::from(rejection).into())
+            }
             Err(e) => {
-                error!("failed to dispatch message: {}", e);
-                return Err(Status::internal("internal server error"));
+                error!("failed to execute quote: {}", e);
+                self.unassign(&payload);
+                Err(Status::internal("internal server error"))
             }
         }
-        Ok(Response::new(StringResponse {
-            message: "ok".to_string(),
+    }
+
+    /// Admits the quote of every level on its own and has the executor execute those admitted
+    /// in one go. Unlike single quotes the acknowledgment waits for the executor, so that every
+    /// level is acknowledged with the outcome of its quote.
+    async fn execute_mass_quote(
+        &self,
+        request: MassQuoteRequest,
+    ) -> Result<Response<MassQuoteAck>, Status> {
+        let quotes = Self::build_mass_quote_payloads(&request)?;
+        let symbol_state = self.symbol_state(&request.symbol)?;
+        let mut admissions = Vec::with_capacity(quotes.len());
+        for quote in &quotes {
+            admissions.push(self.admit(&symbol_state, &request.account, quote).await);
+        }
+        let admitted: Vec<Operation> = quotes
+            .iter()
+            .zip(&admissions)
+            .filter(|(_, admission)| admission.is_ok())
+            .map(|(quote, _)| *quote)
+            .collect();
+        let mut outcomes = Vec::new().into_iter();
+        if !admitted.is_empty() {
+            let (reply, executed) = oneshot::channel();
+            let command = ExecutorCommand::MassQuote(admitted, reply);
+            let executed = match symbol_state.command_sender.send(command).await {
+                Ok(()) => executed.await.map_err(|e| e.to_string()),
+                Err(e) => Err(e.to_string()),
+            };
+            outcomes = match executed {
+                Ok(Some(executed)) => executed.into_iter(),
+                Ok(None) => return Err(Status::internal("internal server error")),
+                Err(e) => {
+                    error!("failed to execute mass quote: {}", e);
+                    return Err(Status::internal("internal server error"));
+                }
+            };
+        }
+        let levels = quotes
+            .iter()
+            .zip(admissions)
+            .map(|(quote, admission)| {
+                let order_id = quote.order_id().to_be_bytes().to_vec();
+                let rejection_reason = match admission {
+                    Err(status) => status.message().to_string(),
+                    Ok(()) => match outcomes.next() {
+                        Some(Err(reason)) => reason.to_string(),
+                        _ => {
+                            return self
+                                .accept(&symbol_state, &request.account, quote)
+                                .into_inner();
+                        }
+                    },
+                };
+                OrderAck {
+                    status: AckStatus::Rejected as i32,
+                    order_id,
+                    rejection_reason,
+                    ..Default::default()
+                }
+            })
+            .collect();
+        Ok(Response::new(MassQuoteAck {
+            status: AckStatus::Accepted as i32,
+            rejection_reason: String::new(),
+            levels,
         }))
     }
+
+    /// Answers a rejected request with an acknowledgment carrying the reason, along with the
+    /// metadata of the status. Failures of the server itself are still returned as errors.
+    fn acknowledge(
+        outcome: Result<Response<OrderAck>, Status>,
+        order_id: Vec<u8>,
+    ) -> Result<Response<OrderAck>, Status> {
+        Self::reject(outcome, |rejection_reason| OrderAck {
+            status: AckStatus::Rejected as i32,
+            order_id,
+            rejection_reason,
+            ..Default::default()
+        })
+    }
+
+    /// Answers a rejected request with the response built from the reason, along with the
+    /// metadata of the status. Failures of the server itself are still returned as errors.
+    fn reject<R>(
+        outcome: Result<Response<R>, Status>,
+        rejected: impl FnOnce(String) -> R,
+    ) -> Result<Response<R>, Status> {
+        match outcome {
+            Err(status) if !matches!(status.code(), Code::Internal | Code::Unknown) => {
+                let mut response = Response::new(rejected(status.message().to_string()));
+                *response.metadata_mut() = status.metadata().clone();
+                Ok(response)
+            }
+            outcome => outcome,
+        }
+    }
+
+    /// The time the request was received at along with its encoding, only taken when the traffic
+    /// is recorded.
+    fn receive<T: Message>(&self, request: &Request<T>) -> Option<(u128, Vec<u8>)> {
+        self.traffic_recorder
+            .as_ref()
+            .map(|_| (generate_u128_timestamp(), request.get_ref().encode_to_vec()))
+    }
+
+    /// Records a received request along with how it was answered.
+    fn record_traffic<R: RecordedOutcome>(
+        &self,
+        method: &str,
+        received: Option<(u128, Vec<u8>)>,
+        outcome: &Result<Response<R>, Status>,
+    ) {
+        let (Some(traffic_recorder), Some((timestamp, payload))) =
+            (&self.traffic_recorder, received)
+        else {
+            return;
+        };
+        let (outcome, order_id) = match outcome {
+            Ok(response) => response.get_ref().recorded_outcome(),
+            Err(status) => (format!("{:?}", status.code()), Vec::new()),
+        };
+        let record = TrafficRecord {
+            timestamp,
+            method: method.to_string(),
+            payload,
+            outcome,
+            order_id,
+        };
+        if let Err(e) = traffic_recorder.record(&record) {
+            error!("failed to record {} request: {}", method, e);
+        }
+    }
+
+    /// Looks up a resting order of the account with its history. Orders recovered on startup are
+    /// read from the latest snapshot of the book, without a history.
+    pub fn order_revisions(
+        &self,
+        symbol: &str,
+        account: &str,
+        order_id: u128,
+    ) -> Result<OrderRevisions, Status> {
+        let symbol_state = self.symbol_state(symbol)?;
+        if let Some(ledger) = &self.ledger {
+            if ledger.owner(order_id).is_some_and(|owner| owner != account) {
+                return Err(Status::permission_denied(
+                    "order does not belong to the account",
+                ));
+            }
+        }
+        let history = symbol_state.order_history.get(order_id).or_else(|| {
+            let secondary = symbol_state.orderbook_manager.get_secondary();
+            secondary
+                .get_order(order_id)
+                .map(|order| OrderRevisions::unknown(&order))
+        });
+        history.ok_or_else(|| EngineError::OrderBook(OrderBookError::OrderNotFound).into())
+    }
 }
 
 #[tonic::async_trait]
@@ -102,28 +638,325 @@ impl OrderDispatcher for OrderDispatchService {
     async fn limit(
         &self,
         request: Request<CreateLimitOrderRequest>,
-    ) -> Result<Response<StringResponse>, Status> {
-        self.execute(Self::build_limit_payload(request)).await
+    ) -> Result<Response<OrderAck>, Status> {
+        let received = self.receive(&request);
+        let account = request.get_ref().account.clone();
+        let outcome = audited(
+            self.audit_log.as_deref(),
+            &account,
+            "limit",
+            request,
+            |request| async move {
+                let payload = self.build_limit_payload(request)?;
+                self.execute(payload).await
+            },
+        )
+        .await;
+        let outcome = Self::acknowledge(outcome, Vec::new());
+        self.record_traffic("limit", received, &outcome);
+        outcome
     }
 
     async fn market(
         &self,
         request: Request<CreateMarketOrderRequest>,
-    ) -> Result<Response<StringResponse>, Status> {
-        self.execute(Self::build_market_payload(request)).await
+    ) -> Result<Response<OrderAck>, Status> {
+        let received = self.receive(&request);
+        let account = request.get_ref().account.clone();
+        let outcome = audited(
+            self.audit_log.as_deref(),
+            &account,
+            "market",
+            request,
+            |request| async move {
+                let payload = Self::build_market_payload(request)?;
+                self.execute(payload).await
+            },
+        )
+        .await;
+        let outcome = Self::acknowledge(outcome, Vec::new());
+        self.record_traffic("market", received, &outcome);
+        outcome
     }
 
     async fn modify(
         &self,
         request: Request<ModifyLimitOrderRequest>,
-    ) -> Result<Response<StringResponse>, Status> {
-        self.execute(Self::build_modify_payload(request)).await
+    ) -> Result<Response<OrderAck>, Status> {
+        let received = self.receive(&request);
+        let account = request.get_ref().account.clone();
+        let order_id = request.get_ref().order_id.clone();
+        let outcome = audited(
+            self.audit_log.as_deref(),
+            &account,
+            "modify",
+            request,
+            |request| async move {
+                let payload = Self::build_modify_payload(request)?;
+                self.execute(payload).await
+            },
+        )
+        .await;
+        let outcome = Self::acknowledge(outcome, order_id);
+        self.record_traffic("modify", received, &outcome);
+        outcome
     }
 
     async fn cancel(
         &self,
         request: Request<CancelLimitOrderRequest>,
-    ) -> Result<Response<StringResponse>, Status> {
-        self.execute(Self::build_cancel_payload(request)).await
+    ) -> Result<Response<OrderAck>, Status> {
+        let received = self.receive(&request);
+        let account = request.get_ref().account.clone();
+        let order_id = request.get_ref().order_id.clone();
+        let outcome = audited(
+            self.audit_log.as_deref(),
+            &account,
+            "cancel",
+            request,
+            |request| async move {
+                let payload = Self::build_cancel_payload(request)?;
+                self.execute(payload).await
+            },
+        )
+        .await;
+        let outcome = Self::acknowledge(outcome, order_id);
+        self.record_traffic("cancel", received, &outcome);
+        outcome
+    }
+
+    async fn reduce(
+        &self,
+        request: Request<ReduceLimitOrderRequest>,
+    ) -> Result<Response<OrderAck>, Status> {
+        let received = self.receive(&request);
+        let account = request.get_ref().account.clone();
+        let order_id = request.get_ref().order_id.clone();
+        let outcome = audited(
+            self.audit_log.as_deref(),
+            &account,
+            "reduce",
+            request,
+            |request| async move {
+                let payload = Self::build_reduce_payload(request)?;
+                self.execute(payload).await
+            },
+        )
+        .await;
+        let outcome = Self::acknowledge(outcome, order_id);
+        self.record_traffic("reduce", received, &outcome);
+        outcome
+    }
+
+    async fn quote(&self, request: Request<QuoteRequest>) -> Result<Response<OrderAck>, Status> {
+        let received = self.receive(&request);
+        let account = request.get_ref().account.clone();
+        let outcome = audited(
+            self.audit_log.as_deref(),
+            &account,
+            "quote",
+            request,
+            |request| async move {
+                let payload = Self::build_quote_payload(request)?;
+                self.execute(payload).await
+            },
+        )
+        .await;
+        let outcome = Self::acknowledge(outcome, Vec::new());
+        self.record_traffic("quote", received, &outcome);
+        outcome
+    }
+
+    async fn mass_quote(
+        &self,
+        request: Request<MassQuoteRequest>,
+    ) -> Result<Response<MassQuoteAck>, Status> {
+        let received = self.receive(&request);
+        let account = request.get_ref().account.clone();
+        let outcome = audited(
+            self.audit_log.as_deref(),
+            &account,
+            "mass_quote",
+            request,
+            |request| self.execute_mass_quote(request.into_inner()),
+        )
+        .await;
+        let outcome = Self::reject(outcome, |rejection_reason| MassQuoteAck {
+            status: AckStatus::Rejected as i32,
+            rejection_reason,
+            levels: Vec::new(),
+        });
+        self.record_traffic("mass_quote", received, &outcome);
+        outcome
+    }
+
+    async fn execute_quote(
+        &self,
+        request: Request<ExecuteQuoteRequest>,
+    ) -> Result<Response<OrderAck>, Status> {
+        let account = request.get_ref().account.clone();
+        let outcome = audited(
+            self.audit_log.as_deref(),
+            &account,
+            "execute_quote",
+            request,
+            |request| self.execute_firm_quote(request.into_inner()),
+        )
+        .await;
+        Self::acknowledge(outcome, Vec::new())
+    }
+
+    async fn status(
+        &self,
+        request: Request<OrderStatusRequest>,
+    ) -> Result<Response<OrderStatusResponse>, Status> {
+        let request = request.into_inner();
+        let order_id = order_id_from_proto(&request.order_id)?;
+        let history = self.order_revisions(&request.symbol, &request.account, order_id)?;
+        Ok(Response::new(order_status_to_proto(order_id, history)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::models::{Operation, Side};
+    use crate::core::types::OrderId;
+    use crate::engine::accounts::market_making::{quote_id, MAX_QUOTE_LEVELS};
+    use crate::engine::services::order_dispatch_service::OrderDispatchService;
+    use crate::protobuf::models::{
+        AckStatus, CancelLimitOrderRequest, CreateMarketOrderRequest, MassQuoteRequest,
+        ModifyLimitOrderRequest, QuoteLevel,
+    };
+    use tonic::{Code, Request, Status};
+
+    #[test]
+    fn it_decodes_the_side_of_a_request() {
+        let request = CreateMarketOrderRequest {
+            symbol: "test".to_string(),
+            side: 1,
+            quantity: 10,
+            ..Default::default()
+        };
+        let (_, _, operation) =
+            OrderDispatchService::build_market_payload(Request::new(request)).unwrap();
+        match operation {
+            Operation::Market(order) => assert_eq!(order.side, Side::Ask),
+            _ => panic!("expected a market order"),
+        }
+    }
+
+    #[test]
+    fn it_rejects_an_invalid_side() {
+        let market = CreateMarketOrderRequest {
+            side: 2,
+            quantity: 10,
+            ..Default::default()
+        };
+        let status = OrderDispatchService::build_market_payload(Request::new(market)).unwrap_err();
+        assert_eq!(status.code(), Code::InvalidArgument);
+
+        let modify = ModifyLimitOrderRequest {
+            order_id: 1u128.to_be_bytes().to_vec(),
+            side: -1,
+            ..Default::default()
+        };
+        let status = OrderDispatchService::build_modify_payload(Request::new(modify)).unwrap_err();
+        assert_eq!(status.code(), Code::InvalidArgument);
+    }
+
+    #[test]
+    fn it_decodes_the_order_id_of_a_request() {
+        let request = CancelLimitOrderRequest {
+            order_id: 42u128.to_be_bytes().to_vec(),
+            ..Default::default()
+        };
+        let (_, _, operation) =
+            OrderDispatchService::build_cancel_payload(Request::new(request)).unwrap();
+        assert!(matches!(operation, Operation::Cancel(OrderId(42))));
+    }
+
+    #[test]
+    fn it_rejects_malformed_order_ids() {
+        for order_id in [vec![], vec![0; 15], vec![0; 17]] {
+            let cancel = CancelLimitOrderRequest {
+                order_id: order_id.clone(),
+                ..Default::default()
+            };
+            let status =
+                OrderDispatchService::build_cancel_payload(Request::new(cancel)).unwrap_err();
+            assert_eq!(status.code(), Code::InvalidArgument);
+
+            let modify = ModifyLimitOrderRequest {
+                order_id,
+                price: 100,
+                quantity: 10,
+                ..Default::default()
+            };
+            let status =
+                OrderDispatchService::build_modify_payload(Request::new(modify)).unwrap_err();
+            assert_eq!(status.code(), Code::InvalidArgument);
+        }
+    }
+
+    #[test]
+    fn it_quotes_every_level_of_a_mass_quote() {
+        let level = |level: u32| QuoteLevel {
+            level,
+            bid_price: 100 - level as u64,
+            bid_quantity: 10,
+            ask_price: 101 + level as u64,
+            ask_quantity: 10,
+        };
+        let mut request = MassQuoteRequest {
+            symbol: "BTCUSD".to_string(),
+            account: "maker".to_string(),
+            levels: vec![level(2), level(0)],
+        };
+        let quotes = OrderDispatchService::build_mass_quote_payloads(&request).unwrap();
+        let sides: Vec<(u128, u64, u128, u64)> = quotes
+            .iter()
+            .map(|quote| match quote {
+                Operation::Quote { bid, ask } => (bid.id, bid.price, ask.id, ask.price),
+                _ => panic!("expected a quote"),
+            })
+            .collect();
+        let ids = |level| {
+            (
+                quote_id("BTCUSD", "maker", level, Side::Bid),
+                quote_id("BTCUSD", "maker", level, Side::Ask),
+            )
+        };
+        assert_eq!(
+            sides,
+            vec![
+                (ids(2).0, 98, ids(2).1, 103),
+                (ids(0).0, 100, ids(0).1, 101)
+            ]
+        );
+
+        for levels in [
+            vec![],
+            vec![level(1), level(1)],
+            vec![level(MAX_QUOTE_LEVELS)],
+        ] {
+            request.levels = levels;
+            let status = OrderDispatchService::build_mass_quote_payloads(&request).unwrap_err();
+            assert_eq!(status.code(), Code::InvalidArgument);
+        }
+    }
+
+    #[test]
+    fn it_acknowledges_rejected_requests() {
+        let outcome = Err(Status::failed_precondition("ETHUSD only accepts cancels"));
+        let ack = OrderDispatchService::acknowledge(outcome, vec![1; 16])
+            .unwrap()
+            .into_inner();
+        assert_eq!(ack.status, AckStatus::Rejected as i32);
+        assert_eq!(ack.order_id, vec![1; 16]);
+        assert_eq!(ack.rejection_reason, "ETHUSD only accepts cancels");
+
+        let outcome = Err(Status::internal("internal server error"));
+        let status = OrderDispatchService::acknowledge(outcome, vec![]).unwrap_err();
+        assert_eq!(status.code(), Code::Internal);
     }
 }
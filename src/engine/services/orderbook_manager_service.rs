@@ -1,22 +1,25 @@
 use crate::core::orderbook::OrderBook;
 use std::sync::atomic::{AtomicPtr, Ordering};
+use std::sync::{Arc, RwLock};
 
 pub struct OrderbookManager {
     primary: AtomicPtr<OrderBook>,
-    secondary: AtomicPtr<OrderBook>,
+    /// The latest snapshot of the primary. Readers hold on to the snapshot they took, which is
+    /// only freed once the last of them let go of it.
+    secondary: RwLock<Arc<OrderBook>>,
 }
 
 impl OrderbookManager {
     pub fn new(id: String, queue_capacity: usize, store_capacity: usize) -> OrderbookManager {
-        let primary = Box::into_raw(Box::new(OrderBook::new(
-            id.clone(),
-            queue_capacity,
-            store_capacity,
-        )));
-        let secondary = Box::into_raw(Box::new(OrderBook::new(id, queue_capacity, store_capacity)));
+        Self::with_orderbook(OrderBook::new(id, queue_capacity, store_capacity))
+    }
+
+    /// Creates a manager whose primary and secondary start out as copies of `orderbook`.
+    pub fn with_orderbook(orderbook: OrderBook) -> OrderbookManager {
+        let primary = Box::into_raw(Box::new(orderbook.clone()));
         OrderbookManager {
             primary: AtomicPtr::new(primary),
-            secondary: AtomicPtr::new(secondary),
+            secondary: RwLock::new(Arc::new(orderbook)),
         }
     }
 
@@ -24,32 +27,37 @@ impl OrderbookManager {
         self.primary.load(Ordering::SeqCst)
     }
 
-    pub fn get_secondary(&self) -> *mut OrderBook {
-        self.secondary.load(Ordering::SeqCst)
+    /// The latest snapshot of the primary, which stays valid while held even as newer snapshots
+    /// are taken.
+    pub fn get_secondary(&self) -> Arc<OrderBook> {
+        Arc::clone(&self.secondary.read().unwrap())
     }
 
-    // WARNING: always take fresh secondary reference after snapshot
-    // in case the reference is stored in a variable outside
+    /// Replaces the secondary with a copy of the primary. The primary is read while copied, so
+    /// this has to be called by whoever executes its operations, which is the executor of the
+    /// symbol once started.
     pub fn snapshot(&self) {
         let primary = self.primary.load(Ordering::SeqCst);
-        let old_secondary = self.secondary.load(Ordering::SeqCst);
-        unsafe {
-            let latest = Box::into_raw(Box::new((*primary).clone()));
-            self.secondary.store(latest, Ordering::SeqCst);
-            drop(Box::from_raw(old_secondary));
-        }
+        let latest = Arc::new(unsafe { (*primary).clone() });
+        *self.secondary.write().unwrap() = latest;
     }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::core::models::{LimitOrder, Operation, Side};
+    use crate::core::types::{OrderId, Price, Quantity};
     use crate::engine::services::orderbook_manager_service::OrderbookManager;
 
     #[tokio::test]
     async fn it_tests_successful_snapshot() {
         let orderbook_manager = OrderbookManager::new("test".to_string(), 100, 10000);
-        let operation = Operation::Limit(LimitOrder::new(1, 100, 100, Side::Bid));
+        let operation = Operation::Limit(LimitOrder::new(
+            OrderId(1),
+            Price(100),
+            Quantity(100),
+            Side::Bid,
+        ));
         let primary = orderbook_manager.get_primary();
         unsafe {
             (*primary).execute(operation);
@@ -57,10 +65,12 @@ mod tests {
         unsafe {
             (*primary).execute(operation);
         }
+        let stale = orderbook_manager.get_secondary();
         orderbook_manager.snapshot();
         let secondary = orderbook_manager.get_secondary();
-        unsafe {
-            println!("{:?}", (*secondary).depth(5));
-        }
+        println!("{:?}", secondary.depth(5));
+        // a snapshot taken before stays readable after it was replaced
+        assert_eq!(stale.depth(5).bids.len(), 0);
+        assert_eq!(secondary.depth(5).bids.len(), 1);
     }
 }
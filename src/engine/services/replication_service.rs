@@ -0,0 +1,204 @@
+use crate::engine::configuration::server_configuration::ServerConfiguration;
+use crate::engine::errors::EngineError;
+use crate::engine::persistence::wal::{encode_batch, encode_checkpoint};
+use crate::engine::services::admin_service::AdminInterceptor;
+use crate::engine::state::replication::{Replication, ReplicationSubscription};
+use crate::engine::state::server_state::ServerState;
+use crate::engine::utils::audit::audited;
+use crate::engine::utils::protobuf::replication_status_to_proto;
+use crate::protobuf::models::{
+    FenceRequest, ReplicationRequest, ReplicationStatus, ReplicationUpdate,
+};
+use crate::protobuf::services::replication_client::ReplicationClient;
+use crate::protobuf::services::replication_server::{
+    Replication as ReplicationRpc, ReplicationServer,
+};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::broadcast::error::RecvError;
+use tonic::codegen::tokio_stream::wrappers::ReceiverStream;
+use tonic::codegen::InterceptedService;
+use tonic::metadata::MetadataValue;
+use tonic::transport::Endpoint;
+use tonic::{Request, Response, Status};
+use tracing::{info, warn};
+
+pub type ReplicationServiceServer =
+    InterceptedService<ReplicationServer<ReplicationService>, AdminInterceptor>;
+
+/// Principal audited requests are recorded under, as replicas authenticate with the admin token.
+const REPLICATION_PRINCIPAL: &str = "replication";
+/// Longest a promotion waits for the primary to be fenced.
+const FENCE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Served by the primary of an active/standby pair: streams the batches every symbol executes to
+/// the replicas, and lets a replica that is being promoted fence the primary.
+pub struct ReplicationService {
+    state: Arc<ServerState>,
+    replication: Arc<Replication>,
+    /// Updates buffered for every replica on top of the batches buffered by the replication feed.
+    update_buffer_size: usize,
+}
+
+impl ReplicationService {
+    /// The service is only served when replication is enabled. Replicas authenticate with the
+    /// admin token.
+    pub fn create(
+        server_configuration: Arc<ServerConfiguration>,
+        state: Arc<ServerState>,
+    ) -> Option<ReplicationServiceServer> {
+        let replication = state.replication.clone()?;
+        let server_properties = &server_configuration.server_properties;
+        let token = server_properties.admin_token.clone();
+        let update_buffer_size = server_properties.channel_capacities.replication_updates;
+        Some(ReplicationServer::with_interceptor(
+            ReplicationService {
+                state,
+                replication,
+                update_buffer_size,
+            },
+            AdminInterceptor::new(token),
+        ))
+    }
+}
+
+fn not_primary(replication: &Replication) -> Status {
+    Status::failed_precondition(format!(
+        "server is {} with fencing token {}",
+        replication.role().name(),
+        replication.fencing_token()
+    ))
+}
+
+/// Attaches the admin token to a request to the replication service of another server.
+pub fn authenticated<T>(message: T, admin_token: &str) -> Result<Request<T>, Status> {
+    let token = MetadataValue::try_from(admin_token)
+        .map_err(|_| Status::invalid_argument("admin token is not valid metadata"))?;
+    let mut request = Request::new(message);
+    request.metadata_mut().insert("bearer", token);
+    Ok(request)
+}
+
+/// Fences the primary at the address with the passed token, so that it stops accepting orders.
+pub async fn fence_primary(
+    address: &str,
+    admin_token: &str,
+    fencing_token: u64,
+) -> Result<ReplicationStatus, Status> {
+    let channel = Endpoint::from_shared(address.to_string())
+        .map_err(|_| Status::invalid_argument(format!("invalid primary address: {}", address)))?
+        .connect_timeout(FENCE_TIMEOUT)
+        .timeout(FENCE_TIMEOUT)
+        .connect()
+        .await
+        .map_err(|e| Status::unavailable(format!("failed to reach primary: {}", e)))?;
+    let request = authenticated(FenceRequest { fencing_token }, admin_token)?;
+    let status = ReplicationClient::new(channel).fence(request).await?;
+    Ok(status.into_inner())
+}
+
+#[tonic::async_trait]
+impl ReplicationRpc for ReplicationService {
+    type subscribeStream = ReceiverStream<Result<ReplicationUpdate, Status>>;
+
+    /// Streams the checkpoint of a symbol followed by every batch executed after it. The stream
+    /// ends with a data loss error once the replica fell too far behind, and with a failed
+    /// precondition once this server is no longer the primary. A replica holding a newer token
+    /// than this server was promoted over it, which fences this server.
+    async fn subscribe(
+        &self,
+        request: Request<ReplicationRequest>,
+    ) -> Result<Response<Self::subscribeStream>, Status> {
+        let request = request.into_inner();
+        if request.fencing_token > self.replication.fencing_token() {
+            if let Err(e) = self.replication.fence(request.fencing_token) {
+                warn!("failed to fence by token of replica: {}", e);
+            }
+        }
+        if !self.replication.is_primary() {
+            return Err(not_primary(&self.replication));
+        }
+        let symbol_state = self
+            .state
+            .symbol(&request.symbol)
+            .ok_or_else(|| EngineError::UnknownSymbol(request.symbol.clone()))?;
+        let ReplicationSubscription {
+            orders,
+            last_trade_price,
+            call,
+            sequence,
+            mut batches,
+        } = symbol_state
+            .subscribe_replication()
+            .await
+            .map_err(|e| EngineError::Unavailable(e.to_string()))?;
+        info!(
+            "replica subscribed to {} at batch {} with {} resting orders",
+            request.symbol,
+            sequence,
+            orders.len()
+        );
+        let replication = Arc::clone(&self.replication);
+        let (tx, rx) = tokio::sync::mpsc::channel(self.update_buffer_size);
+        tokio::spawn(async move {
+            let checkpoint = ReplicationUpdate {
+                fencing_token: replication.fencing_token(),
+                sequence,
+                checkpoint: encode_checkpoint(sequence, last_trade_price, call, &orders),
+                batch: Vec::new(),
+            };
+            if tx.send(Ok(checkpoint)).await.is_err() {
+                return;
+            }
+            loop {
+                let batch = tokio::select! {
+                    batch = batches.recv() => batch,
+                    _ = replication.role_changed.notified() => Err(RecvError::Closed),
+                    _ = tx.closed() => return,
+                };
+                let update = match batch {
+                    _ if !replication.is_primary() => Err(not_primary(&replication)),
+                    Ok(batch) => Ok(ReplicationUpdate {
+                        fencing_token: replication.fencing_token(),
+                        sequence: batch.sequence,
+                        checkpoint: Vec::new(),
+                        batch: encode_batch(batch.now, &batch.operations),
+                    }),
+                    Err(RecvError::Lagged(missed)) => Err(Status::data_loss(format!(
+                        "missed {} batches, subscribe again",
+                        missed
+                    ))),
+                    Err(RecvError::Closed) => return,
+                };
+                let done = update.is_err();
+                if tx.send(update).await.is_err() || done {
+                    return;
+                }
+            }
+        });
+        Ok(Response::new(ReceiverStream::new(rx)))
+    }
+
+    /// Fences this server with the token of a replica that is being promoted.
+    async fn fence(
+        &self,
+        request: Request<FenceRequest>,
+    ) -> Result<Response<ReplicationStatus>, Status> {
+        let audit_log = self.state.audit_log.as_deref();
+        audited(
+            audit_log,
+            REPLICATION_PRINCIPAL,
+            "fence",
+            request,
+            |request| async move {
+                self.replication
+                    .fence(request.into_inner().fencing_token)
+                    .map_err(|e| Status::failed_precondition(e.to_string()))?;
+                Ok(Response::new(replication_status_to_proto(
+                    &self.replication,
+                )))
+            },
+        )
+        .await
+    }
+}
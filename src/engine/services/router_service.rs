@@ -0,0 +1,425 @@
+use crate::engine::constants::property_loader::ShardProperties;
+use crate::protobuf::models::{
+    AveragePriceRequest, AveragePrices, Bbo, BboRequest, BookEvent, BookEventsRequest,
+    CancelLimitOrderRequest, Candle, CandleRequest, ChannelMetricsList, ChannelMetricsRequest,
+    CreateLimitOrderRequest, CreateMarketOrderRequest, DeliveryMetrics, DeliveryMetricsRequest,
+    ExecuteQuoteRequest, Heatmap, HeatmapRequest, IndicativeUncross, IndicativeUncrossRequest,
+    LatencyRequest, LatencySummary, MarketSummary, MarketSummaryRequest, MassQuoteAck,
+    MassQuoteRequest, ModifyLimitOrderRequest, OrderAck, OrderStatusRequest, OrderStatusResponse,
+    OrderbookData, OrderbookDataRequest, OrderbookUpdate, PositionList, PositionRequest,
+    QuoteRequest, ReduceLimitOrderRequest, ResyncRequest, RfqResult, SlippageCurve,
+    SlippageRequest, Ticker, TickerRequest, VolumeProfile, VolumeProfileRequest,
+};
+use crate::protobuf::services::order_dispatcher_client::OrderDispatcherClient;
+use crate::protobuf::services::order_dispatcher_server::{OrderDispatcher, OrderDispatcherServer};
+use crate::protobuf::services::stat_stream_client::StatStreamClient;
+use crate::protobuf::services::stat_stream_server::{StatStream, StatStreamServer};
+use std::collections::HashMap;
+use std::sync::Arc;
+use thiserror::Error;
+use tonic::transport::{Channel, Endpoint};
+use tonic::{Request, Response, Status, Streaming};
+
+#[derive(Debug, Error, PartialEq)]
+pub enum ShardMapError {
+    #[error("invalid address of shard {shard}: {address}")]
+    Address { shard: String, address: String },
+    #[error("symbol {symbol} is assigned to both {first} and {second}")]
+    DuplicateSymbol {
+        symbol: String,
+        first: String,
+        second: String,
+    },
+}
+
+/// Which shard owns every symbol.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShardMap {
+    shards: Vec<ShardProperties>,
+    routes: HashMap<String, usize>,
+}
+
+impl ShardMap {
+    /// Maps the symbols to the shards they are listed under, each of which must be listed once.
+    pub fn new(shards: Vec<ShardProperties>) -> Result<Self, ShardMapError> {
+        let mut routes = HashMap::new();
+        for (index, shard) in shards.iter().enumerate() {
+            for symbol in &shard.symbols {
+                if let Some(first) = routes.insert(symbol.clone(), index) {
+                    return Err(ShardMapError::DuplicateSymbol {
+                        symbol: symbol.clone(),
+                        first: shards[first].name.clone(),
+                        second: shard.name.clone(),
+                    });
+                }
+            }
+        }
+        Ok(Self { shards, routes })
+    }
+
+    pub fn shards(&self) -> &[ShardProperties] {
+        &self.shards
+    }
+
+    /// The index of the shard owning the symbol.
+    pub fn route(&self, symbol: &str) -> Option<usize> {
+        self.routes.get(symbol).copied()
+    }
+}
+
+/// A single endpoint in front of the shards, forwarding every request to the shard owning its
+/// symbol along with its metadata. Requests without a symbol, such as the positions of an account
+/// or the summary of every market, are answered by each shard on its own and are not routed.
+pub struct ShardRouter {
+    shard_map: ShardMap,
+    dispatchers: Vec<OrderDispatcherClient<Channel>>,
+    streamers: Vec<StatStreamClient<Channel>>,
+}
+
+impl ShardRouter {
+    /// Connects to the shards lazily, so that the router starts before them and reconnects to
+    /// shards that restart.
+    pub fn create(
+        shard_map: ShardMap,
+    ) -> Result<
+        (
+            OrderDispatcherServer<ShardRouter>,
+            StatStreamServer<ShardRouter>,
+        ),
+        ShardMapError,
+    > {
+        let channels = shard_map
+            .shards()
+            .iter()
+            .map(|shard| {
+                Endpoint::from_shared(shard.address.clone())
+                    .map(|endpoint| endpoint.connect_lazy())
+                    .map_err(|_| ShardMapError::Address {
+                        shard: shard.name.clone(),
+                        address: shard.address.clone(),
+                    })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let router = Arc::new(ShardRouter {
+            shard_map,
+            dispatchers: channels
+                .iter()
+                .cloned()
+                .map(OrderDispatcherClient::new)
+                .collect(),
+            streamers: channels.into_iter().map(StatStreamClient::new).collect(),
+        });
+        Ok((
+            OrderDispatcherServer::from_arc(Arc::clone(&router)),
+            StatStreamServer::from_arc(router),
+        ))
+    }
+
+    fn route(&self, symbol: &str) -> Result<usize, Status> {
+        self.shard_map
+            .route(symbol)
+            .ok_or_else(|| Status::not_found(format!("unknown symbol: {}", symbol)))
+    }
+
+    fn dispatcher(&self, symbol: &str) -> Result<OrderDispatcherClient<Channel>, Status> {
+        Ok(self.dispatchers[self.route(symbol)?].clone())
+    }
+
+    fn streamer(&self, symbol: &str) -> Result<StatStreamClient<Channel>, Status> {
+        Ok(self.streamers[self.route(symbol)?].clone())
+    }
+}
+
+fn unroutable(rpc: &str) -> Status {
+    Status::unimplemented(format!(
+        "{} has no symbol to route by, request it from the shards directly",
+        rpc
+    ))
+}
+
+#[tonic::async_trait]
+impl OrderDispatcher for ShardRouter {
+    async fn limit(
+        &self,
+        request: Request<CreateLimitOrderRequest>,
+    ) -> Result<Response<OrderAck>, Status> {
+        let mut dispatcher = self.dispatcher(&request.get_ref().symbol)?;
+        dispatcher.limit(request).await
+    }
+
+    async fn market(
+        &self,
+        request: Request<CreateMarketOrderRequest>,
+    ) -> Result<Response<OrderAck>, Status> {
+        let mut dispatcher = self.dispatcher(&request.get_ref().symbol)?;
+        dispatcher.market(request).await
+    }
+
+    async fn modify(
+        &self,
+        request: Request<ModifyLimitOrderRequest>,
+    ) -> Result<Response<OrderAck>, Status> {
+        let mut dispatcher = self.dispatcher(&request.get_ref().symbol)?;
+        dispatcher.modify(request).await
+    }
+
+    async fn cancel(
+        &self,
+        request: Request<CancelLimitOrderRequest>,
+    ) -> Result<Response<OrderAck>, Status> {
+        let mut dispatcher = self.dispatcher(&request.get_ref().symbol)?;
+        dispatcher.cancel(request).await
+    }
+
+    async fn reduce(
+        &self,
+        request: Request<ReduceLimitOrderRequest>,
+    ) -> Result<Response<OrderAck>, Status> {
+        let mut dispatcher = self.dispatcher(&request.get_ref().symbol)?;
+        dispatcher.reduce(request).await
+    }
+
+    async fn quote(&self, request: Request<QuoteRequest>) -> Result<Response<OrderAck>, Status> {
+        let mut dispatcher = self.dispatcher(&request.get_ref().symbol)?;
+        dispatcher.quote(request).await
+    }
+
+    async fn mass_quote(
+        &self,
+        request: Request<MassQuoteRequest>,
+    ) -> Result<Response<MassQuoteAck>, Status> {
+        let mut dispatcher = self.dispatcher(&request.get_ref().symbol)?;
+        dispatcher.mass_quote(request).await
+    }
+
+    async fn execute_quote(
+        &self,
+        request: Request<ExecuteQuoteRequest>,
+    ) -> Result<Response<OrderAck>, Status> {
+        let mut dispatcher = self.dispatcher(&request.get_ref().symbol)?;
+        dispatcher.execute_quote(request).await
+    }
+
+    async fn status(
+        &self,
+        request: Request<OrderStatusRequest>,
+    ) -> Result<Response<OrderStatusResponse>, Status> {
+        let mut dispatcher = self.dispatcher(&request.get_ref().symbol)?;
+        dispatcher.status(request).await
+    }
+}
+
+#[tonic::async_trait]
+impl StatStream for ShardRouter {
+    type rfqStream = Streaming<RfqResult>;
+
+    async fn rfq(
+        &self,
+        request: Request<CreateMarketOrderRequest>,
+    ) -> Result<Response<Self::rfqStream>, Status> {
+        let mut streamer = self.streamer(&request.get_ref().symbol)?;
+        streamer.rfq(request).await
+    }
+
+    type orderbookStream = Streaming<OrderbookData>;
+
+    async fn orderbook(
+        &self,
+        request: Request<OrderbookDataRequest>,
+    ) -> Result<Response<Self::orderbookStream>, Status> {
+        let mut streamer = self.streamer(&request.get_ref().symbol)?;
+        streamer.orderbook(request).await
+    }
+
+    type orderbook_updatesStream = Streaming<OrderbookUpdate>;
+
+    async fn orderbook_updates(
+        &self,
+        request: Request<OrderbookDataRequest>,
+    ) -> Result<Response<Self::orderbook_updatesStream>, Status> {
+        let mut streamer = self.streamer(&request.get_ref().symbol)?;
+        streamer.orderbook_updates(request).await
+    }
+
+    async fn resync(
+        &self,
+        _request: Request<ResyncRequest>,
+    ) -> Result<Response<OrderbookUpdate>, Status> {
+        Err(unroutable("resync"))
+    }
+
+    type bboStream = Streaming<Bbo>;
+
+    async fn bbo(&self, request: Request<BboRequest>) -> Result<Response<Self::bboStream>, Status> {
+        let mut streamer = self.streamer(&request.get_ref().symbol)?;
+        streamer.bbo(request).await
+    }
+
+    type indicative_uncrossStream = Streaming<IndicativeUncross>;
+
+    async fn indicative_uncross(
+        &self,
+        request: Request<IndicativeUncrossRequest>,
+    ) -> Result<Response<Self::indicative_uncrossStream>, Status> {
+        let mut streamer = self.streamer(&request.get_ref().symbol)?;
+        streamer.indicative_uncross(request).await
+    }
+
+    type book_eventsStream = Streaming<BookEvent>;
+
+    async fn book_events(
+        &self,
+        request: Request<BookEventsRequest>,
+    ) -> Result<Response<Self::book_eventsStream>, Status> {
+        let mut streamer = self.streamer(&request.get_ref().symbol)?;
+        streamer.book_events(request).await
+    }
+
+    async fn slippage(
+        &self,
+        request: Request<SlippageRequest>,
+    ) -> Result<Response<SlippageCurve>, Status> {
+        let mut streamer = self.streamer(&request.get_ref().symbol)?;
+        streamer.slippage(request).await
+    }
+
+    async fn heatmap(&self, request: Request<HeatmapRequest>) -> Result<Response<Heatmap>, Status> {
+        let mut streamer = self.streamer(&request.get_ref().symbol)?;
+        streamer.heatmap(request).await
+    }
+
+    async fn volume_profile(
+        &self,
+        request: Request<VolumeProfileRequest>,
+    ) -> Result<Response<VolumeProfile>, Status> {
+        let mut streamer = self.streamer(&request.get_ref().symbol)?;
+        streamer.volume_profile(request).await
+    }
+
+    async fn ticker(&self, request: Request<TickerRequest>) -> Result<Response<Ticker>, Status> {
+        let mut streamer = self.streamer(&request.get_ref().symbol)?;
+        streamer.ticker(request).await
+    }
+
+    type average_pricesStream = Streaming<AveragePrices>;
+
+    async fn average_prices(
+        &self,
+        request: Request<AveragePriceRequest>,
+    ) -> Result<Response<Self::average_pricesStream>, Status> {
+        let mut streamer = self.streamer(&request.get_ref().symbol)?;
+        streamer.average_prices(request).await
+    }
+
+    type ticker_updatesStream = Streaming<Ticker>;
+
+    async fn ticker_updates(
+        &self,
+        request: Request<TickerRequest>,
+    ) -> Result<Response<Self::ticker_updatesStream>, Status> {
+        let mut streamer = self.streamer(&request.get_ref().symbol)?;
+        streamer.ticker_updates(request).await
+    }
+
+    type candlesStream = Streaming<Candle>;
+
+    async fn candles(
+        &self,
+        request: Request<CandleRequest>,
+    ) -> Result<Response<Self::candlesStream>, Status> {
+        let mut streamer = self.streamer(&request.get_ref().symbol)?;
+        streamer.candles(request).await
+    }
+
+    type market_summaryStream = Streaming<MarketSummary>;
+
+    async fn market_summary(
+        &self,
+        _request: Request<MarketSummaryRequest>,
+    ) -> Result<Response<Self::market_summaryStream>, Status> {
+        Err(unroutable("market_summary"))
+    }
+
+    async fn delivery_metrics(
+        &self,
+        _request: Request<DeliveryMetricsRequest>,
+    ) -> Result<Response<DeliveryMetrics>, Status> {
+        Err(unroutable("delivery_metrics"))
+    }
+
+    async fn latency(
+        &self,
+        request: Request<LatencyRequest>,
+    ) -> Result<Response<LatencySummary>, Status> {
+        let mut streamer = self.streamer(&request.get_ref().symbol)?;
+        streamer.latency(request).await
+    }
+
+    async fn channel_metrics(
+        &self,
+        request: Request<ChannelMetricsRequest>,
+    ) -> Result<Response<ChannelMetricsList>, Status> {
+        let mut streamer = self.streamer(&request.get_ref().symbol)?;
+        streamer.channel_metrics(request).await
+    }
+
+    async fn positions(
+        &self,
+        _request: Request<PositionRequest>,
+    ) -> Result<Response<PositionList>, Status> {
+        Err(unroutable("positions"))
+    }
+
+    type position_updatesStream = Streaming<PositionList>;
+
+    async fn position_updates(
+        &self,
+        _request: Request<PositionRequest>,
+    ) -> Result<Response<Self::position_updatesStream>, Status> {
+        Err(unroutable("position_updates"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::engine::constants::property_loader::ShardProperties;
+    use crate::engine::services::router_service::{ShardMap, ShardMapError, ShardRouter};
+
+    fn shard(name: &str, address: &str, symbols: &[&str]) -> ShardProperties {
+        ShardProperties {
+            name: name.to_string(),
+            address: address.to_string(),
+            symbols: symbols.iter().map(|symbol| symbol.to_string()).collect(),
+        }
+    }
+
+    #[tokio::test]
+    async fn it_routes_every_symbol_to_a_single_shard() {
+        let shard_map = ShardMap::new(vec![
+            shard("a", "http://[::1]:50051", &["ETHUSD", "BTCUSD"]),
+            shard("b", "http://[::1]:50052", &["SOLUSD"]),
+        ])
+        .unwrap();
+        assert_eq!(shard_map.route("BTCUSD"), Some(0));
+        assert_eq!(shard_map.route("SOLUSD"), Some(1));
+        assert_eq!(shard_map.route("XRPUSD"), None);
+        assert!(ShardRouter::create(shard_map).is_ok());
+
+        assert_eq!(
+            ShardMap::new(vec![
+                shard("a", "http://[::1]:50051", &["ETHUSD"]),
+                shard("b", "http://[::1]:50052", &["SOLUSD", "ETHUSD"]),
+            ]),
+            Err(ShardMapError::DuplicateSymbol {
+                symbol: "ETHUSD".to_string(),
+                first: "a".to_string(),
+                second: "b".to_string(),
+            })
+        );
+        let invalid = ShardMap::new(vec![shard("a", "not a url", &["ETHUSD"])]).unwrap();
+        assert!(matches!(
+            ShardRouter::create(invalid),
+            Err(ShardMapError::Address { .. })
+        ));
+    }
+}
@@ -1,48 +1,669 @@
-use crate::core::models::{Granularity, MarketOrder, Side};
+use crate::core::models::{
+    Granularity, MarketOrder, OrderbookAggregated, RfqStatus, CHECKSUM_LEVELS,
+};
+use crate::core::orderbook::OrderBook;
+use crate::core::types::{OrderId, Quantity};
+use crate::engine::accounts::ledger::Ledger;
+use crate::engine::configuration::server_configuration::ServerConfiguration;
+use crate::engine::constants::property_loader::{
+    DelayedDataSettings, SlowConsumerPolicy, SlowConsumerSettings,
+};
+use crate::engine::errors::EngineError;
+use crate::engine::persistence::outbox::Outbox;
 use crate::engine::services::orderbook_manager_service::OrderbookManager;
-use crate::engine::utils::protobuf::{orderbook_data_to_proto, rfq_to_proto};
+use crate::engine::state::book_feed::BookSubscription;
+use crate::engine::state::candles::MAX_CANDLES;
+use crate::engine::state::delivery_metrics::DeliveryMetrics as DeliveryCounters;
+use crate::engine::state::market_summary::summarize;
+use crate::engine::state::quotes::{FirmQuote, Quotes};
+use crate::engine::state::runtime_settings::RuntimeSettings;
+use crate::engine::state::server_state::{ServerState, Symbols};
+use crate::engine::state::symbol_state::SymbolState;
+use crate::engine::utils::protobuf::{
+    average_prices_to_proto, bbo_to_proto, book_event_to_proto, book_snapshot_to_proto,
+    candle_interval_from_proto, candle_to_proto, channel_metrics_to_proto,
+    delivery_metrics_to_proto, depth_diff_to_proto, granularity_from_proto, heatmap_to_proto,
+    indicative_uncross_to_proto, latency_summaries_to_proto, market_summary_to_proto,
+    orderbook_data_to_proto, positions_to_proto, rfq_to_proto, side_from_proto,
+    slippage_curve_to_proto, stream_header, ticker_to_proto, volume_profile_to_proto,
+};
+use crate::engine::utils::time::generate_u128_timestamp;
 use crate::protobuf::models::{
-    CreateMarketOrderRequest, OrderbookData, OrderbookDataRequest, RfqResult,
+    AveragePriceRequest, AveragePrices, Bbo, BboRequest, BookEvent, BookEventsRequest, Candle,
+    CandleRequest, ChannelMetricsList, ChannelMetricsRequest, CreateMarketOrderRequest,
+    DeliveryMetrics, DeliveryMetricsRequest, Heatmap, HeatmapRequest, IndicativeUncross,
+    IndicativeUncrossRequest, LatencyRequest, LatencySummary, MarketSummary, MarketSummaryRequest,
+    OrderbookData, OrderbookDataRequest, OrderbookUpdate, PositionList, PositionRequest,
+    ResyncRequest, RfqResult, SlippageCurve, SlippageRequest, StreamHeader, StreamParameters,
+    Ticker, TickerRequest, VolumeProfile, VolumeProfileRequest,
+};
+use crate::protobuf::services::delayed_stat_stream_server::{
+    DelayedStatStream, DelayedStatStreamServer,
 };
 use crate::protobuf::services::stat_stream_server::{StatStream, StatStreamServer};
-use std::sync::Arc;
+use std::collections::HashMap;
+use std::future::Future;
+use std::ops::RangeInclusive;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::mpsc::Sender;
+use tokio::time::{sleep_until, timeout, Instant};
 use tonic::codegen::tokio_stream::wrappers::ReceiverStream;
+use tonic::codegen::InterceptedService;
+use tonic::metadata::MetadataValue;
+use tonic::service::Interceptor;
 use tonic::{Request, Response, Status};
+use tracing::warn;
+
+pub type StatStreamService =
+    InterceptedService<StatStreamServer<StatStreamer>, MarketDataInterceptor>;
+
+/// Metadata keys of a depth stream response carrying the number of levels sent on either side
+/// of the book, the granularity prices are aggregated at as the price increment of a level, and
+/// the interval between updates in milliseconds.
+pub const STREAM_LEVELS_KEY: &str = "stream-levels";
+pub const STREAM_GRANULARITY_KEY: &str = "stream-granularity";
+pub const STREAM_INTERVAL_KEY: &str = "stream-interval-millis";
+
+/// Quantities a single rfq stream can quote besides the quantity of the request.
+pub const MAX_RFQ_LADDER: usize = 10;
+
+/// Notionals a single slippage curve can be estimated at.
+pub const MAX_SLIPPAGE_POINTS: usize = 100;
 
 pub struct StatStreamer {
     max_quote_count: usize,
     max_buffer_size: usize,
-    orderbook_manager: Arc<OrderbookManager>,
+    quote_ttl: Duration,
+    /// How often the liquidity of the symbols is sampled, the narrowest heatmap bucket.
+    heatmap_interval: Duration,
+    min_interval: Duration,
+    max_levels: usize,
+    heartbeat_interval: Duration,
+    slow_consumer: SlowConsumerSettings,
+    symbols: Symbols,
+    outbox: Arc<Outbox>,
+    delivery_metrics: Arc<DeliveryCounters>,
+    trading_halted: Arc<AtomicBool>,
+    runtime_settings: Arc<RuntimeSettings>,
+    ledger: Option<Arc<Ledger>>,
+    /// The delayed feed served by the delayed stat stream, if enabled.
+    delayed_data: Option<DelayedDataSettings>,
+    next_channel: AtomicU64,
+    /// The open orderbook updates streams, keyed by their channel.
+    depth_channels: Arc<Mutex<HashMap<u64, Arc<DepthChannel>>>>,
 }
+
+/// Restricts the real-time stat stream to clients presenting the market data token, if one is
+/// configured.
+#[derive(Clone)]
+pub struct MarketDataInterceptor {
+    token: Option<String>,
+}
+
+impl Interceptor for MarketDataInterceptor {
+    fn call(&mut self, request: Request<()>) -> Result<Request<()>, Status> {
+        let Some(expected) = &self.token else {
+            return Ok(request);
+        };
+        match request.metadata().get("bearer") {
+            Some(token) if token.as_bytes() == expected.as_bytes() => Ok(request),
+            _ => {
+                warn!("rejected unauthenticated market data request");
+                Err(Status::unauthenticated("invalid market data token"))
+            }
+        }
+    }
+}
+
 impl StatStreamer {
+    /// Creates the real-time stat stream along with the delayed one, which is only created when
+    /// the delayed feed is enabled. Both serve the same streamer.
     pub fn create(
-        max_quote_count: usize,
-        max_buffer_size: usize,
-        orderbook_manager: Arc<OrderbookManager>,
-    ) -> StatStreamServer<StatStreamer> {
-        StatStreamServer::new(StatStreamer {
-            max_quote_count,
-            max_buffer_size,
-            orderbook_manager,
+        server_configuration: Arc<ServerConfiguration>,
+        state: Arc<ServerState>,
+    ) -> (
+        StatStreamService,
+        Option<DelayedStatStreamServer<StatStreamer>>,
+    ) {
+        let server_properties = &server_configuration.server_properties;
+        let streamer = Arc::new(StatStreamer {
+            max_quote_count: server_properties.rfq_max_count,
+            max_buffer_size: server_properties.rfq_buffer_size,
+            quote_ttl: server_properties.rfq_quote_ttl,
+            heatmap_interval: server_properties.heatmap_interval,
+            min_interval: server_properties.stream_min_interval,
+            max_levels: server_properties.stream_max_levels,
+            heartbeat_interval: server_properties.stream_heartbeat_interval,
+            slow_consumer: server_properties.slow_consumer,
+            symbols: Arc::clone(&state.symbols),
+            outbox: Arc::clone(&state.outbox),
+            delivery_metrics: Arc::clone(&state.delivery_metrics),
+            trading_halted: Arc::clone(&state.trading_halted),
+            runtime_settings: Arc::clone(&state.runtime_settings),
+            ledger: state.ledger.clone(),
+            delayed_data: server_properties.delayed_data,
+            next_channel: AtomicU64::new(1),
+            depth_channels: Arc::new(Mutex::new(HashMap::new())),
+        });
+        let interceptor = MarketDataInterceptor {
+            token: server_properties.market_data_token.clone(),
+        };
+        let delayed = server_properties
+            .delayed_data
+            .map(|_| DelayedStatStreamServer::from_arc(Arc::clone(&streamer)));
+        (
+            InterceptedService::new(StatStreamServer::from_arc(streamer), interceptor),
+            delayed,
+        )
+    }
+
+    fn open_channel(&self) -> Arc<Channel> {
+        Arc::new(Channel {
+            id: self.next_channel.fetch_add(1, Ordering::Relaxed),
+            sequence: Mutex::new(0),
         })
     }
 
-    fn build_rfq_payload(request: Request<CreateMarketOrderRequest>) -> MarketOrder {
+    fn symbol_state(&self, symbol: &str) -> Result<Arc<SymbolState>, Status> {
+        match self.symbols.read().unwrap().get(symbol) {
+            Some(symbol_state) => Ok(Arc::clone(symbol_state)),
+            None => Err(EngineError::UnknownSymbol(symbol.to_string()).into()),
+        }
+    }
+
+    fn delayed_data(&self) -> Result<DelayedDataSettings, Status> {
+        self.delayed_data
+            .ok_or_else(|| Status::failed_precondition("delayed market data is disabled"))
+    }
+
+    fn ledger(&self) -> Result<Arc<Ledger>, Status> {
+        match &self.ledger {
+            Some(ledger) => Ok(Arc::clone(ledger)),
+            None => Err(Status::failed_precondition("the ledger is disabled")),
+        }
+    }
+
+    /// Sends an update produced by `next_update` every interval of the subscription until it is
+    /// complete or the client goes away. A heartbeat is sent whenever nothing was sent for the
+    /// heartbeat interval, so that clients can tell a quiet stream from a stalled one.
+    /// Conflated streams only produce a frame once the client made room for it, so that the
+    /// frame reflects the latest state and diffs cover everything the client has not seen yet.
+    /// Frames are stamped with the current value of `sequence` and numbered on their channel.
+    /// Other streams wait for a client that fell behind to catch up, and treat it as a slow
+    /// consumer if it does not within the grace period.
+    fn drive_stream<T, F>(
+        &self,
+        tx: Sender<Result<T, Status>>,
+        subscription: Subscription,
+        sequence: Arc<AtomicU64>,
+        channel: Arc<Channel>,
+        mut next_update: F,
+    ) -> impl Future<Output = ()> + Send + 'static
+    where
+        T: StreamFrame + Send + 'static,
+        F: FnMut() -> Option<T> + Send + 'static,
+    {
+        let runtime_settings = Arc::clone(&self.runtime_settings);
+        let heartbeat_interval = self.heartbeat_interval;
+        let slow_consumer = self.slow_consumer;
+        async move {
+            let mut conflate = subscription.conflate;
+            let mut updates = 0;
+            let mut next_update_at = Instant::now();
+            let mut last_sent_at = Instant::now();
+            loop {
+                if tx.is_closed() || subscription.is_complete(updates) {
+                    break;
+                }
+                let permit = match conflate {
+                    true => match tx.reserve().await {
+                        Ok(permit) => Some(permit),
+                        Err(_) => break,
+                    },
+                    false if keep_up(&tx, &slow_consumer).await => None,
+                    false => match slow_consumer.policy {
+                        SlowConsumerPolicy::Conflate => {
+                            warn!(
+                                "conflating stream of slow subscriber on channel {}",
+                                channel.id
+                            );
+                            conflate = true;
+                            continue;
+                        }
+                        SlowConsumerPolicy::Disconnect => {
+                            disconnect(&tx, &slow_consumer, &format!("channel {}", channel.id));
+                            break;
+                        }
+                    },
+                };
+                let sequence = sequence.load(Ordering::Relaxed);
+                let frame = {
+                    let mut channel_sequence = channel.sequence.lock().unwrap();
+                    let mut frame = None;
+                    if Instant::now() >= next_update_at {
+                        next_update_at = Instant::now() + subscription.interval(&runtime_settings);
+                        frame = next_update().map(|frame| {
+                            updates += 1;
+                            *channel_sequence += 1;
+                            let header =
+                                stream_header(sequence, false, channel.id, *channel_sequence);
+                            frame.with_header(header)
+                        });
+                    }
+                    if frame.is_none() && last_sent_at.elapsed() >= heartbeat_interval {
+                        let header = stream_header(sequence, true, channel.id, *channel_sequence);
+                        frame = Some(T::heartbeat(header));
+                    }
+                    frame
+                };
+                if let Some(frame) = frame {
+                    match permit {
+                        Some(permit) => permit.send(Ok(frame)),
+                        None => {
+                            if tx.send(Ok(frame)).await.is_err() {
+                                break;
+                            }
+                        }
+                    }
+                    last_sent_at = Instant::now();
+                }
+                sleep_until(next_update_at.min(last_sent_at + heartbeat_interval)).await;
+            }
+        }
+    }
+
+    fn build_rfq_payload(
+        &self,
+        request: Request<CreateMarketOrderRequest>,
+    ) -> Result<(String, MarketOrder, Vec<MarketOrder>, Subscription), Status> {
+        let request = request.into_inner();
+        if request.ladder.len() > MAX_RFQ_LADDER {
+            return Err(Status::invalid_argument(format!(
+                "a ladder quotes at most {} quantities",
+                MAX_RFQ_LADDER
+            )));
+        }
+        let side = side_from_proto(request.side)?;
+        let order = MarketOrder::new(OrderId(0), Quantity(request.quantity), side);
+        let ladder = request
+            .ladder
+            .iter()
+            .map(|&quantity| MarketOrder::new(OrderId(0), Quantity(quantity), side))
+            .collect();
+        let subscription = Subscription::resolve(
+            request.stream,
+            self.min_interval,
+            self.max_levels,
+            Some(self.max_quote_count),
+        );
+        Ok((request.symbol, order, ladder, subscription))
+    }
+
+    fn build_orderbook_data_payload(
+        &self,
+        request: Request<OrderbookDataRequest>,
+    ) -> Result<(String, Granularity, RangeInclusive<u64>, Subscription), Status> {
         let request = request.into_inner();
-        MarketOrder::new(0, request.quantity, Side::from(request.side))
+        let subscription =
+            Subscription::resolve(request.stream, self.min_interval, self.max_levels, None);
+        let granularity = granularity_from_proto(request.granularity)?;
+        let prices = price_range(request.min_price, request.max_price)?;
+        Ok((request.symbol, granularity, prices, subscription))
     }
 
-    fn build_orderbook_data_payload(request: Request<OrderbookDataRequest>) -> Granularity {
+    fn build_position_payload(&self, request: Request<PositionRequest>) -> (String, Subscription) {
         let request = request.into_inner();
-        match request.granularity {
-            0 => Granularity::P00,
-            1 => Granularity::P0,
-            2 => Granularity::P,
-            3 => Granularity::P10,
-            4 => Granularity::P100,
-            _ => Granularity::P00,
+        let subscription =
+            Subscription::resolve(request.stream, self.min_interval, self.max_levels, None);
+        (request.account, subscription)
+    }
+}
+
+/// Resolves the prices a depth stream is filtered to, where a zero max price leaves the range
+/// open ended.
+/// Waits until the client of a stream is less than `max_backlog` frames behind, returning whether
+/// it caught up within the grace period. A closed stream counts as caught up.
+async fn keep_up<T>(tx: &Sender<T>, settings: &SlowConsumerSettings) -> bool {
+    let room = (tx.max_capacity() + 1).saturating_sub(settings.max_backlog);
+    timeout(settings.grace, tx.reserve_many(room)).await.is_ok()
+}
+
+/// Ends the stream of a slow client, which the backlog left room in the buffer for.
+fn disconnect<T>(tx: &Sender<Result<T, Status>>, settings: &SlowConsumerSettings, stream: &str) {
+    warn!("disconnecting slow subscriber of {}", stream);
+    let status = Status::resource_exhausted(format!(
+        "stayed {} frames behind for {:?}, subscribe again",
+        settings.max_backlog, settings.grace
+    ));
+    let _ = tx.try_send(Err(status));
+}
+
+/// Sends an event unless the client stayed behind for too long, returning whether the stream goes
+/// on. Events cannot be conflated, so slow clients are always disconnected.
+async fn send_event<T>(
+    tx: &Sender<Result<T, Status>>,
+    event: T,
+    settings: &SlowConsumerSettings,
+    stream: &str,
+) -> bool {
+    if !keep_up(tx, settings).await {
+        disconnect(tx, settings, stream);
+        return false;
+    }
+    tx.send(Ok(event)).await.is_ok()
+}
+
+fn price_range(min_price: u64, max_price: u64) -> Result<RangeInclusive<u64>, Status> {
+    let max_price = match max_price {
+        0 => u64::MAX,
+        max_price => max_price,
+    };
+    if min_price > max_price {
+        return Err(Status::invalid_argument(
+            "min price must not exceed max price",
+        ));
+    }
+    Ok(min_price..=max_price)
+}
+
+/// Tells the subscriber the shape its depth stream was resolved to, which differs from the
+/// request wherever the request exceeded the caps of the server or left a setting out.
+fn describe_depth_stream<T>(
+    response: &mut Response<T>,
+    subscription: &Subscription,
+    granularity: Granularity,
+    interval: Duration,
+) {
+    let metadata = response.metadata_mut();
+    metadata.insert(
+        STREAM_LEVELS_KEY,
+        MetadataValue::from(subscription.levels as u64),
+    );
+    metadata.insert(
+        STREAM_GRANULARITY_KEY,
+        MetadataValue::from(granularity as u64),
+    );
+    metadata.insert(
+        STREAM_INTERVAL_KEY,
+        MetadataValue::from(interval.as_millis() as u64),
+    );
+}
+
+/// Quotes a market order against the orderbook. Complete fills are issued as firm quotes, at the
+/// notional of sweeping the book for the quantity.
+fn firm_quote(
+    orderbook: &OrderBook,
+    order: MarketOrder,
+    quotes: &Quotes,
+    quote_ttl: Duration,
+    now: u128,
+) -> RfqResult {
+    let status = orderbook.request_for_quote(order);
+    let mut quote_id = None;
+    if let RfqStatus::CompleteFill(price) = status {
+        let (_, notional, _) = orderbook.sweep(order.side, order.quantity);
+        let quote = FirmQuote {
+            side: order.side,
+            quantity: order.quantity,
+            price,
+            notional,
+            expires_at: now + quote_ttl.as_nanos(),
+        };
+        quote_id = Some(quotes.issue(quote, now));
+    }
+    let mut result = rfq_to_proto(status);
+    if let Some(quote_id) = quote_id {
+        result.quote_id = quote_id.to_be_bytes().to_vec();
+        result.quote_ttl_millis = quote_ttl.as_millis() as u64;
+    }
+    result
+}
+
+/// A message sent on a stream, which carries a header and can be sent as a bare heartbeat.
+trait StreamFrame: Default {
+    fn with_header(self, header: StreamHeader) -> Self;
+
+    fn heartbeat(header: StreamHeader) -> Self {
+        Self::default().with_header(header)
+    }
+}
+
+impl StreamFrame for RfqResult {
+    fn with_header(self, header: StreamHeader) -> Self {
+        Self {
+            header: Some(header),
+            ..self
+        }
+    }
+}
+
+impl StreamFrame for MarketSummary {
+    fn with_header(self, header: StreamHeader) -> Self {
+        Self {
+            header: Some(header),
+            ..self
+        }
+    }
+}
+
+impl StreamFrame for Bbo {
+    fn with_header(self, header: StreamHeader) -> Self {
+        Self {
+            header: Some(header),
+            ..self
+        }
+    }
+}
+
+impl StreamFrame for IndicativeUncross {
+    fn with_header(self, header: StreamHeader) -> Self {
+        Self {
+            header: Some(header),
+            ..self
+        }
+    }
+}
+
+impl StreamFrame for Ticker {
+    fn with_header(self, header: StreamHeader) -> Self {
+        Self {
+            header: Some(header),
+            ..self
+        }
+    }
+}
+
+impl StreamFrame for AveragePrices {
+    fn with_header(self, header: StreamHeader) -> Self {
+        Self {
+            header: Some(header),
+            ..self
+        }
+    }
+}
+
+impl StreamFrame for Candle {
+    fn with_header(self, header: StreamHeader) -> Self {
+        Self {
+            header: Some(header),
+            ..self
+        }
+    }
+}
+
+impl StreamFrame for OrderbookData {
+    fn with_header(self, header: StreamHeader) -> Self {
+        Self {
+            header: Some(header),
+            ..self
+        }
+    }
+}
+
+impl StreamFrame for PositionList {
+    fn with_header(self, header: StreamHeader) -> Self {
+        Self {
+            header: Some(header),
+            ..self
+        }
+    }
+}
+
+impl StreamFrame for OrderbookUpdate {
+    fn with_header(self, header: StreamHeader) -> Self {
+        Self {
+            header: Some(header),
+            ..self
+        }
+    }
+}
+
+/// Numbers the updates of a stream. The sequence stays locked while an update is produced, so
+/// that a resync cannot interleave with it.
+struct Channel {
+    id: u64,
+    sequence: Mutex<u64>,
+}
+
+type TopOfBook = (u64, u64, u64);
+
+/// An orderbook updates stream, which sends the levels that changed since its previous update.
+struct DepthChannel {
+    channel: Arc<Channel>,
+    orderbook_manager: Arc<OrderbookManager>,
+    sequence: Arc<AtomicU64>,
+    granularity: Granularity,
+    levels: usize,
+    prices: RangeInclusive<u64>,
+    /// The state clients hold after applying the last update, which the next one is diffed from.
+    previous: Mutex<Option<(TopOfBook, OrderbookAggregated)>>,
+}
+
+impl DepthChannel {
+    fn capture(&self) -> (TopOfBook, OrderbookAggregated) {
+        let secondary = self.orderbook_manager.get_secondary();
+        (
+            (
+                secondary.get_last_trade_price(),
+                secondary.get_max_bid().unwrap_or(u64::MIN),
+                secondary.get_min_ask().unwrap_or(u64::MAX),
+            ),
+            secondary
+                .orderbook_data(self.granularity)
+                .within(&self.prices)
+                .top(self.levels),
+        )
+    }
+
+    /// The full orderbook on the first call, afterwards the levels that changed since the
+    /// previous update, or nothing if the orderbook did not change.
+    fn next_update(&self) -> Option<OrderbookUpdate> {
+        let (top_of_book, current) = self.capture();
+        let mut previous = self.previous.lock().unwrap();
+        let update = match &*previous {
+            None => Some((true, current.diff(&OrderbookAggregated::default()))),
+            Some((previous_top_of_book, previous_data)) => {
+                let diff = current.diff(previous_data);
+                if diff.is_empty() && top_of_book == *previous_top_of_book {
+                    None
+                } else {
+                    Some((false, diff))
+                }
+            }
+        };
+        let checksum = current.checksum(CHECKSUM_LEVELS);
+        *previous = Some((top_of_book, current));
+        update.map(|(snapshot, diff)| {
+            let (last_trade_price, max_bid, min_ask) = top_of_book;
+            depth_diff_to_proto(snapshot, last_trade_price, max_bid, min_ask, diff, checksum)
+        })
+    }
+
+    /// Takes a full snapshot of the orderbook, which is numbered as the next update of the
+    /// channel. The updates that follow on the stream are diffed from the snapshot, so clients
+    /// resume applying them from the snapshot on.
+    fn resync(&self) -> OrderbookUpdate {
+        let mut channel_sequence = self.channel.sequence.lock().unwrap();
+        let sequence = self.sequence.load(Ordering::Relaxed);
+        let (top_of_book, current) = self.capture();
+        let (last_trade_price, max_bid, min_ask) = top_of_book;
+        let diff = current.diff(&OrderbookAggregated::default());
+        let checksum = current.checksum(CHECKSUM_LEVELS);
+        *self.previous.lock().unwrap() = Some((top_of_book, current));
+        *channel_sequence += 1;
+        let header = stream_header(sequence, false, self.channel.id, *channel_sequence);
+        depth_diff_to_proto(true, last_trade_price, max_bid, min_ask, diff, checksum)
+            .with_header(header)
+    }
+}
+
+/// Settings of a single stream, resolved from the request against the caps of the server.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Subscription {
+    /// Overrides the stream interval of the server when requested.
+    interval: Option<Duration>,
+    max_updates: Option<usize>,
+    /// Price levels sent on either side of the orderbook.
+    levels: usize,
+    conflate: bool,
+}
+
+impl Subscription {
+    /// Resolves the requested parameters, where zero values fall back to the server defaults.
+    /// Intervals are raised to `min_interval` and levels and updates are capped at the maximums.
+    fn resolve(
+        parameters: Option<StreamParameters>,
+        min_interval: Duration,
+        max_levels: usize,
+        max_updates: Option<usize>,
+    ) -> Self {
+        let parameters = parameters.unwrap_or_default();
+        let interval = match parameters.interval_millis {
+            0 => None,
+            interval_millis => Some(Duration::from_millis(interval_millis).max(min_interval)),
+        };
+        let requested_updates = match parameters.max_updates {
+            0 => None,
+            requested_updates => Some(requested_updates as usize),
+        };
+        let max_updates = match (requested_updates, max_updates) {
+            (Some(requested_updates), Some(max_updates)) => {
+                Some(requested_updates.min(max_updates))
+            }
+            (requested_updates, max_updates) => requested_updates.or(max_updates),
+        };
+        let levels = match parameters.levels {
+            0 => max_levels,
+            levels => (levels as usize).min(max_levels),
+        };
+        Self {
+            interval,
+            max_updates,
+            levels,
+            conflate: parameters.conflate,
         }
     }
+
+    /// Conflated streams hold at most a single frame the client has not taken yet.
+    fn buffer_size(&self, max_buffer_size: usize) -> usize {
+        match self.conflate {
+            true => 1,
+            false => max_buffer_size,
+        }
+    }
+
+    fn interval(&self, runtime_settings: &RuntimeSettings) -> Duration {
+        self.interval
+            .unwrap_or_else(|| runtime_settings.current().stream_interval)
+    }
+
+    fn is_complete(&self, updates: usize) -> bool {
+        self.max_updates
+            .is_some_and(|max_updates| updates >= max_updates)
+    }
 }
 
 #[tonic::async_trait]
@@ -52,61 +673,823 @@ impl StatStream for StatStreamer {
         &self,
         request: Request<CreateMarketOrderRequest>,
     ) -> Result<Response<Self::rfqStream>, Status> {
-        let max_quote_count = self.max_quote_count;
-        let payload = Self::build_rfq_payload(request);
+        let (symbol, payload, ladder, subscription) = self.build_rfq_payload(request)?;
+        let symbol_state = self.symbol_state(&symbol)?;
+        let (tx, rx) = tokio::sync::mpsc::channel(subscription.buffer_size(self.max_buffer_size));
+        let orderbook_manager = Arc::clone(&symbol_state.orderbook_manager);
+        let quotes = Arc::clone(&symbol_state.quotes);
+        let quote_ttl = self.quote_ttl;
+        let sequence = Arc::clone(&symbol_state.operation_count);
+        let channel = self.open_channel();
+        tokio::spawn(
+            self.drive_stream(tx, subscription, sequence, channel, move || {
+                let secondary = orderbook_manager.get_secondary();
+                let now = generate_u128_timestamp();
+                let mut result = firm_quote(&secondary, payload, &quotes, quote_ttl, now);
+                result.ladder = ladder
+                    .iter()
+                    .map(|&order| firm_quote(&secondary, order, &quotes, quote_ttl, now))
+                    .collect();
+                Some(result)
+            }),
+        );
+        Ok(Response::new(ReceiverStream::new(rx)))
+    }
+
+    type orderbookStream = ReceiverStream<Result<OrderbookData, Status>>;
+
+    async fn orderbook(
+        &self,
+        request: Request<OrderbookDataRequest>,
+    ) -> Result<Response<Self::orderbookStream>, Status> {
+        let (symbol, payload, prices, subscription) = self.build_orderbook_data_payload(request)?;
+        let symbol_state = self.symbol_state(&symbol)?;
+        let (tx, rx) = tokio::sync::mpsc::channel(subscription.buffer_size(self.max_buffer_size));
+        let orderbook_manager = Arc::clone(&symbol_state.orderbook_manager);
+        let sequence = Arc::clone(&symbol_state.operation_count);
+        let channel = self.open_channel();
+        tokio::spawn(
+            self.drive_stream(tx, subscription, sequence, channel, move || {
+                // a single snapshot, so that the prices and levels sent agree
+                let secondary = orderbook_manager.get_secondary();
+                Some(orderbook_data_to_proto(
+                    secondary.get_last_trade_price(),
+                    secondary.get_max_bid().unwrap_or(u64::MIN),
+                    secondary.get_min_ask().unwrap_or(u64::MAX),
+                    secondary
+                        .orderbook_data(payload)
+                        .within(&prices)
+                        .top(subscription.levels),
+                ))
+            }),
+        );
+        let mut response = Response::new(ReceiverStream::new(rx));
+        let interval = subscription.interval(&self.runtime_settings);
+        describe_depth_stream(&mut response, &subscription, payload, interval);
+        Ok(response)
+    }
+
+    type orderbook_updatesStream = ReceiverStream<Result<OrderbookUpdate, Status>>;
+
+    /// Streams the full orderbook once, followed by the levels that changed since the previous
+    /// update whenever the orderbook changes.
+    async fn orderbook_updates(
+        &self,
+        request: Request<OrderbookDataRequest>,
+    ) -> Result<Response<Self::orderbook_updatesStream>, Status> {
+        let (symbol, payload, prices, subscription) = self.build_orderbook_data_payload(request)?;
+        let symbol_state = self.symbol_state(&symbol)?;
+        let (tx, rx) = tokio::sync::mpsc::channel(subscription.buffer_size(self.max_buffer_size));
+        let depth_channel = Arc::new(DepthChannel {
+            channel: self.open_channel(),
+            orderbook_manager: Arc::clone(&symbol_state.orderbook_manager),
+            sequence: Arc::clone(&symbol_state.operation_count),
+            granularity: payload,
+            levels: subscription.levels,
+            prices,
+            previous: Mutex::new(None),
+        });
+        let id = depth_channel.channel.id;
+        self.depth_channels
+            .lock()
+            .unwrap()
+            .insert(id, Arc::clone(&depth_channel));
+        let stream = self.drive_stream(
+            tx,
+            subscription,
+            Arc::clone(&depth_channel.sequence),
+            Arc::clone(&depth_channel.channel),
+            move || depth_channel.next_update(),
+        );
+        let depth_channels = Arc::clone(&self.depth_channels);
+        tokio::spawn(async move {
+            stream.await;
+            depth_channels.lock().unwrap().remove(&id);
+        });
+        let mut response = Response::new(ReceiverStream::new(rx));
+        let interval = subscription.interval(&self.runtime_settings);
+        describe_depth_stream(&mut response, &subscription, payload, interval);
+        Ok(response)
+    }
+
+    type bboStream = ReceiverStream<Result<Bbo, Status>>;
+
+    /// Streams the best bid and offer once, followed by the top of the book whenever either
+    /// side of it changes.
+    async fn bbo(&self, request: Request<BboRequest>) -> Result<Response<Self::bboStream>, Status> {
+        let request = request.into_inner();
+        let subscription = Subscription::resolve(request.stream, self.min_interval, 1, None);
+        let symbol_state = self.symbol_state(&request.symbol)?;
+        let (tx, rx) = tokio::sync::mpsc::channel(subscription.buffer_size(self.max_buffer_size));
+        let orderbook_manager = Arc::clone(&symbol_state.orderbook_manager);
+        let sequence = Arc::clone(&symbol_state.operation_count);
+        let channel = self.open_channel();
+        let mut previous = None;
+        tokio::spawn(
+            self.drive_stream(tx, subscription, sequence, channel, move || {
+                let current = orderbook_manager.get_secondary().best_bid_offer();
+                if previous == Some(current) {
+                    return None;
+                }
+                previous = Some(current);
+                let (bid, ask) = current;
+                Some(bbo_to_proto(bid, ask))
+            }),
+        );
+        Ok(Response::new(ReceiverStream::new(rx)))
+    }
+
+    type indicative_uncrossStream = ReceiverStream<Result<IndicativeUncross, Status>>;
+
+    /// Streams the price a call of the symbol would uncross at, along with the quantity it would
+    /// match and the imbalance it would leave, whenever any of them changes. Participants can
+    /// follow it during a call to adjust their orders before the uncross.
+    async fn indicative_uncross(
+        &self,
+        request: Request<IndicativeUncrossRequest>,
+    ) -> Result<Response<Self::indicative_uncrossStream>, Status> {
+        let request = request.into_inner();
+        let subscription = Subscription::resolve(request.stream, self.min_interval, 1, None);
+        let symbol_state = self.symbol_state(&request.symbol)?;
+        let (tx, rx) = tokio::sync::mpsc::channel(subscription.buffer_size(self.max_buffer_size));
+        let orderbook_manager = Arc::clone(&symbol_state.orderbook_manager);
+        let sequence = Arc::clone(&symbol_state.operation_count);
+        let channel = self.open_channel();
+        let mut previous = None;
+        tokio::spawn(
+            self.drive_stream(tx, subscription, sequence, channel, move || {
+                let secondary = orderbook_manager.get_secondary();
+                let current = (secondary.in_call(), secondary.indicative_uncross());
+                if previous == Some(current) {
+                    return None;
+                }
+                previous = Some(current);
+                let (call, uncross) = current;
+                Some(indicative_uncross_to_proto(
+                    symbol_state.symbol.clone(),
+                    call,
+                    uncross,
+                ))
+            }),
+        );
+        Ok(Response::new(ReceiverStream::new(rx)))
+    }
+
+    /// Resynchronizes an orderbook updates stream after a client missed one of its updates.
+    /// Returns a full snapshot, numbered on the channel of the stream, that the following
+    /// updates of the stream apply to.
+    async fn resync(
+        &self,
+        request: Request<ResyncRequest>,
+    ) -> Result<Response<OrderbookUpdate>, Status> {
+        let channel = request.into_inner().channel;
+        let depth_channel = self.depth_channels.lock().unwrap().get(&channel).cloned();
+        match depth_channel {
+            Some(depth_channel) => Ok(Response::new(depth_channel.resync())),
+            None => Err(Status::not_found(format!("unknown channel: {}", channel))),
+        }
+    }
+
+    async fn delivery_metrics(
+        &self,
+        _request: Request<DeliveryMetricsRequest>,
+    ) -> Result<Response<DeliveryMetrics>, Status> {
+        Ok(Response::new(delivery_metrics_to_proto(
+            self.delivery_metrics.snapshot(),
+            self.outbox.len(),
+            self.trading_halted.load(Ordering::SeqCst),
+        )))
+    }
+
+    async fn latency(
+        &self,
+        request: Request<LatencyRequest>,
+    ) -> Result<Response<LatencySummary>, Status> {
+        let symbol_state = self.symbol_state(&request.into_inner().symbol)?;
+        Ok(Response::new(latency_summaries_to_proto(
+            symbol_state.latency_metrics.summaries(),
+        )))
+    }
+
+    async fn channel_metrics(
+        &self,
+        request: Request<ChannelMetricsRequest>,
+    ) -> Result<Response<ChannelMetricsList>, Status> {
+        let symbol_state = self.symbol_state(&request.into_inner().symbol)?;
+        Ok(Response::new(channel_metrics_to_proto(
+            symbol_state.channel_metrics(),
+        )))
+    }
+
+    type book_eventsStream = ReceiverStream<Result<BookEvent, Status>>;
+
+    /// Streams every change to the resting orders of a symbol following a snapshot of them. The
+    /// stream ends with a data loss error once the subscriber fell too far behind the feed, and
+    /// with a resource exhausted error once it stayed behind on the stream itself.
+    async fn book_events(
+        &self,
+        request: Request<BookEventsRequest>,
+    ) -> Result<Response<Self::book_eventsStream>, Status> {
+        let symbol_state = self.symbol_state(&request.into_inner().symbol)?;
+        let slow_consumer = self.slow_consumer;
+        let stream = format!("{} book events", symbol_state.symbol);
+        let BookSubscription {
+            orders,
+            sequence,
+            mut events,
+        } = symbol_state
+            .subscribe_book()
+            .await
+            .map_err(|e| EngineError::Unavailable(e.to_string()))?;
         let (tx, rx) = tokio::sync::mpsc::channel(self.max_buffer_size);
-        let mut counter = 0;
-        let orderbook_manager = Arc::clone(&self.orderbook_manager);
         tokio::spawn(async move {
+            let now = generate_u128_timestamp();
+            for order in orders {
+                let event = book_snapshot_to_proto(order, sequence, now);
+                if !send_event(&tx, event, &slow_consumer, &stream).await {
+                    return;
+                }
+            }
             loop {
-                if tx.is_closed() || counter >= max_quote_count {
-                    break;
+                let batch = tokio::select! {
+                    batch = events.recv() => batch,
+                    _ = tx.closed() => return,
+                };
+                match batch {
+                    Ok(batch) => {
+                        for event in batch.iter() {
+                            let event = book_event_to_proto(*event);
+                            if !send_event(&tx, event, &slow_consumer, &stream).await {
+                                return;
+                            }
+                        }
+                    }
+                    Err(RecvError::Lagged(missed)) => {
+                        let status = Status::data_loss(format!(
+                            "missed {} batches of events, subscribe again",
+                            missed
+                        ));
+                        let _ = tx.send(Err(status)).await;
+                        return;
+                    }
+                    Err(RecvError::Closed) => return,
+                }
+            }
+        });
+        Ok(Response::new(ReceiverStream::new(rx)))
+    }
+
+    /// Aggregates the latest liquidity samples of a symbol into a heatmap.
+    async fn heatmap(&self, request: Request<HeatmapRequest>) -> Result<Response<Heatmap>, Status> {
+        let request = request.into_inner();
+        let symbol_state = self.symbol_state(&request.symbol)?;
+        let bucket = Duration::from_millis(request.bucket_millis)
+            .max(self.heatmap_interval)
+            .as_nanos();
+        let now = generate_u128_timestamp();
+        let start =
+            (now - now % bucket).saturating_sub(bucket * request.buckets.saturating_sub(1) as u128);
+        let matrix = symbol_state.heatmap.matrix(start, bucket);
+        Ok(Response::new(heatmap_to_proto(request.symbol, matrix)))
+    }
+
+    async fn volume_profile(
+        &self,
+        request: Request<VolumeProfileRequest>,
+    ) -> Result<Response<VolumeProfile>, Status> {
+        let symbol_state = self.symbol_state(&request.into_inner().symbol)?;
+        Ok(Response::new(volume_profile_to_proto(
+            symbol_state.symbol.clone(),
+            symbol_state.volume_profile.snapshot(),
+        )))
+    }
+
+    /// Estimates the slippage curve of a symbol from the latest snapshot of its book.
+    async fn slippage(
+        &self,
+        request: Request<SlippageRequest>,
+    ) -> Result<Response<SlippageCurve>, Status> {
+        let request = request.into_inner();
+        if request.notionals.len() > MAX_SLIPPAGE_POINTS {
+            return Err(Status::invalid_argument(format!(
+                "a slippage curve has at most {} points",
+                MAX_SLIPPAGE_POINTS
+            )));
+        }
+        let side = side_from_proto(request.side)?;
+        let symbol_state = self.symbol_state(&request.symbol)?;
+        let sequence = symbol_state.operation_count.load(Ordering::Relaxed);
+        let secondary = symbol_state.orderbook_manager.get_secondary();
+        let points = secondary.slippage_curve(side, &request.notionals);
+        Ok(Response::new(slippage_curve_to_proto(
+            request.symbol,
+            side,
+            points,
+            sequence,
+        )))
+    }
+
+    async fn ticker(&self, request: Request<TickerRequest>) -> Result<Response<Ticker>, Status> {
+        let symbol_state = self.symbol_state(&request.into_inner().symbol)?;
+        let stats = symbol_state.ticker.stats(generate_u128_timestamp());
+        Ok(Response::new(ticker_to_proto(
+            symbol_state.symbol.clone(),
+            stats,
+        )))
+    }
+
+    type ticker_updatesStream = ReceiverStream<Result<Ticker, Status>>;
+
+    /// Streams the ticker of a symbol whenever it changed, be it through trades or trades falling
+    /// out of the window.
+    async fn ticker_updates(
+        &self,
+        request: Request<TickerRequest>,
+    ) -> Result<Response<Self::ticker_updatesStream>, Status> {
+        let request = request.into_inner();
+        let subscription = Subscription::resolve(request.stream, self.min_interval, 1, None);
+        let symbol_state = self.symbol_state(&request.symbol)?;
+        let (tx, rx) = tokio::sync::mpsc::channel(subscription.buffer_size(self.max_buffer_size));
+        let sequence = Arc::clone(&symbol_state.operation_count);
+        let channel = self.open_channel();
+        let mut previous = None;
+        tokio::spawn(
+            self.drive_stream(tx, subscription, sequence, channel, move || {
+                let stats = symbol_state.ticker.stats(generate_u128_timestamp());
+                if previous == Some(stats) {
+                    return None;
+                }
+                previous = Some(stats);
+                Some(ticker_to_proto(symbol_state.symbol.clone(), stats))
+            }),
+        );
+        Ok(Response::new(ReceiverStream::new(rx)))
+    }
+
+    type average_pricesStream = ReceiverStream<Result<AveragePrices, Status>>;
+
+    /// Streams the rolling average prices of a symbol whenever the average price task computed
+    /// different ones.
+    async fn average_prices(
+        &self,
+        request: Request<AveragePriceRequest>,
+    ) -> Result<Response<Self::average_pricesStream>, Status> {
+        let request = request.into_inner();
+        let subscription = Subscription::resolve(request.stream, self.min_interval, 1, None);
+        let symbol_state = self.symbol_state(&request.symbol)?;
+        let (tx, rx) = tokio::sync::mpsc::channel(subscription.buffer_size(self.max_buffer_size));
+        let sequence = Arc::clone(&symbol_state.operation_count);
+        let channel = self.open_channel();
+        let mut previous = None;
+        tokio::spawn(
+            self.drive_stream(tx, subscription, sequence, channel, move || {
+                let prices = symbol_state.average_prices.latest();
+                if prices.is_empty() || previous.as_ref() == Some(&prices) {
+                    return None;
                 }
-                counter += 1;
-                let result = unsafe {
-                    rfq_to_proto((*orderbook_manager.get_secondary()).request_for_quote(payload))
+                previous = Some(prices.clone());
+                Some(average_prices_to_proto(symbol_state.symbol.clone(), prices))
+            }),
+        );
+        Ok(Response::new(ReceiverStream::new(rx)))
+    }
+
+    type market_summaryStream = ReceiverStream<Result<MarketSummary, Status>>;
+
+    /// Streams a summary of every symbol whenever one of them changed, for dashboards. Symbols
+    /// created or retired while streaming are picked up with the next summary.
+    async fn market_summary(
+        &self,
+        request: Request<MarketSummaryRequest>,
+    ) -> Result<Response<Self::market_summaryStream>, Status> {
+        let request = request.into_inner();
+        let subscription = Subscription::resolve(request.stream, self.min_interval, 1, None);
+        let (tx, rx) = tokio::sync::mpsc::channel(subscription.buffer_size(self.max_buffer_size));
+        let symbols = Arc::clone(&self.symbols);
+        // every symbol has a sequence of its own, which the summaries carry instead of the header
+        let sequence = Arc::new(AtomicU64::new(0));
+        let channel = self.open_channel();
+        let mut previous = None;
+        tokio::spawn(
+            self.drive_stream(tx, subscription, sequence, channel, move || {
+                let summaries = summarize(&symbols, generate_u128_timestamp());
+                if previous.as_ref() == Some(&summaries) {
+                    return None;
+                }
+                previous = Some(summaries.clone());
+                Some(market_summary_to_proto(summaries))
+            }),
+        );
+        Ok(Response::new(ReceiverStream::new(rx)))
+    }
+
+    type candlesStream = ReceiverStream<Result<Candle, Status>>;
+
+    /// Streams the latest candles of a symbol at an interval, followed by every candle that
+    /// changed. A candle in progress is sent whenever it changed and once more after it closed.
+    async fn candles(
+        &self,
+        request: Request<CandleRequest>,
+    ) -> Result<Response<Self::candlesStream>, Status> {
+        let request = request.into_inner();
+        let interval = candle_interval_from_proto(request.interval)?;
+        let subscription = Subscription::resolve(request.stream, self.min_interval, 1, None);
+        let symbol_state = self.symbol_state(&request.symbol)?;
+        let (tx, rx) = tokio::sync::mpsc::channel(subscription.buffer_size(self.max_buffer_size));
+        let sequence = Arc::clone(&symbol_state.operation_count);
+        let channel = self.open_channel();
+        let now = generate_u128_timestamp();
+        let backfill: Vec<_> = symbol_state
+            .candles
+            .latest(interval, (request.backfill as usize).min(MAX_CANDLES))
+            .into_iter()
+            .map(|candle| (candle, candle.is_closed(interval, now)))
+            .collect();
+        let mut last = backfill.last().copied();
+        let candles = Arc::clone(&symbol_state.candles);
+        let stream = self.drive_stream(
+            tx.clone(),
+            subscription,
+            Arc::clone(&sequence),
+            Arc::clone(&channel),
+            move || {
+                let now = generate_u128_timestamp();
+                let changed = match last {
+                    Some((candle, _)) => candles.since(interval, candle.start),
+                    None => candles.latest(interval, 1),
                 };
-                if tx.send(Ok(result)).await.is_err() {
-                    break;
+                for candle in changed {
+                    let closed = candle.is_closed(interval, now);
+                    if last != Some((candle, closed)) {
+                        last = Some((candle, closed));
+                        return Some(candle_to_proto(candle, closed));
+                    }
+                }
+                None
+            },
+        );
+        // the backfill is numbered on the channel ahead of the updates
+        tokio::spawn(async move {
+            for (candle, closed) in backfill {
+                let header = {
+                    let mut channel_sequence = channel.sequence.lock().unwrap();
+                    *channel_sequence += 1;
+                    let sequence = sequence.load(Ordering::Relaxed);
+                    stream_header(sequence, false, channel.id, *channel_sequence)
+                };
+                let frame = candle_to_proto(candle, closed).with_header(header);
+                if tx.send(Ok(frame)).await.is_err() {
+                    return;
                 }
-                tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
             }
+            stream.await;
         });
         Ok(Response::new(ReceiverStream::new(rx)))
     }
 
+    async fn positions(
+        &self,
+        request: Request<PositionRequest>,
+    ) -> Result<Response<PositionList>, Status> {
+        let ledger = self.ledger()?;
+        let account = request.into_inner().account;
+        let positions = ledger.positions(&account);
+        Ok(Response::new(positions_to_proto(account, positions)))
+    }
+
+    type position_updatesStream = ReceiverStream<Result<PositionList, Status>>;
+
+    /// Streams the positions of the account once, followed by every position whenever a fill
+    /// changed any of them.
+    async fn position_updates(
+        &self,
+        request: Request<PositionRequest>,
+    ) -> Result<Response<Self::position_updatesStream>, Status> {
+        let ledger = self.ledger()?;
+        let (account, subscription) = self.build_position_payload(request);
+        let (tx, rx) = tokio::sync::mpsc::channel(subscription.buffer_size(self.max_buffer_size));
+        let sequence = Arc::clone(&ledger.position_updates);
+        let channel = self.open_channel();
+        let mut previous = None;
+        tokio::spawn(
+            self.drive_stream(tx, subscription, sequence, channel, move || {
+                let positions = ledger.positions(&account);
+                if previous.as_ref() == Some(&positions) {
+                    return None;
+                }
+                previous = Some(positions.clone());
+                Some(positions_to_proto(account.clone(), positions))
+            }),
+        );
+        Ok(Response::new(ReceiverStream::new(rx)))
+    }
+}
+
+/// Serves the samples of the delayed data task that were taken the delay of the feed ago. The
+/// streams follow their real-time counterparts, but carry no sequence in their header.
+#[tonic::async_trait]
+impl DelayedStatStream for StatStreamer {
     type orderbookStream = ReceiverStream<Result<OrderbookData, Status>>;
 
+    /// Streams the sampled orderbook at the price of the orders, whatever granularity was
+    /// requested, and at most as many levels as were sampled.
     async fn orderbook(
         &self,
         request: Request<OrderbookDataRequest>,
     ) -> Result<Response<Self::orderbookStream>, Status> {
-        let (tx, rx) = tokio::sync::mpsc::channel(self.max_buffer_size);
-        let orderbook_manager = Arc::clone(&self.orderbook_manager);
-        let payload = Self::build_orderbook_data_payload(request);
-        tokio::spawn(async move {
-            loop {
-                if tx.is_closed() {
-                    break;
+        let settings = self.delayed_data()?;
+        let request = request.into_inner();
+        let max_levels = self.max_levels.min(settings.levels);
+        let subscription =
+            Subscription::resolve(request.stream, self.min_interval, max_levels, None);
+        let prices = price_range(request.min_price, request.max_price)?;
+        let symbol_state = self.symbol_state(&request.symbol)?;
+        let (tx, rx) = tokio::sync::mpsc::channel(subscription.buffer_size(self.max_buffer_size));
+        let sequence = Arc::new(AtomicU64::new(0));
+        let channel = self.open_channel();
+        tokio::spawn(
+            self.drive_stream(tx, subscription, sequence, channel, move || {
+                let now = generate_u128_timestamp();
+                let sample = symbol_state
+                    .delayed_data
+                    .at(now.saturating_sub(settings.delay.as_nanos()))?;
+                Some(orderbook_data_to_proto(
+                    sample.last_trade_price,
+                    sample.max_bid,
+                    sample.min_ask,
+                    sample.depth.within(&prices).top(subscription.levels),
+                ))
+            }),
+        );
+        let mut response = Response::new(ReceiverStream::new(rx));
+        let interval = subscription.interval(&self.runtime_settings);
+        describe_depth_stream(&mut response, &subscription, Granularity::P00, interval);
+        Ok(response)
+    }
+
+    type bboStream = ReceiverStream<Result<Bbo, Status>>;
+
+    async fn bbo(&self, request: Request<BboRequest>) -> Result<Response<Self::bboStream>, Status> {
+        let settings = self.delayed_data()?;
+        let request = request.into_inner();
+        let subscription = Subscription::resolve(request.stream, self.min_interval, 1, None);
+        let symbol_state = self.symbol_state(&request.symbol)?;
+        let (tx, rx) = tokio::sync::mpsc::channel(subscription.buffer_size(self.max_buffer_size));
+        let sequence = Arc::new(AtomicU64::new(0));
+        let channel = self.open_channel();
+        let mut previous = None;
+        tokio::spawn(
+            self.drive_stream(tx, subscription, sequence, channel, move || {
+                let now = generate_u128_timestamp();
+                let sample = symbol_state
+                    .delayed_data
+                    .at(now.saturating_sub(settings.delay.as_nanos()))?;
+                let current = (sample.best_bid, sample.best_ask);
+                if previous == Some(current) {
+                    return None;
                 }
-                let result = unsafe {
-                    orderbook_data_to_proto(
-                        (*orderbook_manager.get_secondary()).get_last_trade_price(),
-                        (*orderbook_manager.get_secondary())
-                            .get_max_bid()
-                            .unwrap_or(u64::MIN),
-                        (*orderbook_manager.get_secondary())
-                            .get_min_ask()
-                            .unwrap_or(u64::MAX),
-                        (*orderbook_manager.get_secondary()).orderbook_data(payload),
-                    )
-                };
-                if tx.send(Ok(result)).await.is_err() {
-                    break;
+                previous = Some(current);
+                Some(bbo_to_proto(sample.best_bid, sample.best_ask))
+            }),
+        );
+        Ok(Response::new(ReceiverStream::new(rx)))
+    }
+
+    /// Fails with `unavailable` until the first sample is old enough to be served.
+    async fn ticker(&self, request: Request<TickerRequest>) -> Result<Response<Ticker>, Status> {
+        let settings = self.delayed_data()?;
+        let symbol_state = self.symbol_state(&request.into_inner().symbol)?;
+        let now = generate_u128_timestamp();
+        match symbol_state
+            .delayed_data
+            .at(now.saturating_sub(settings.delay.as_nanos()))
+        {
+            Some(sample) => Ok(Response::new(ticker_to_proto(
+                symbol_state.symbol.clone(),
+                sample.ticker,
+            ))),
+            None => Err(Status::unavailable("no delayed market data yet")),
+        }
+    }
+
+    type ticker_updatesStream = ReceiverStream<Result<Ticker, Status>>;
+
+    async fn ticker_updates(
+        &self,
+        request: Request<TickerRequest>,
+    ) -> Result<Response<Self::ticker_updatesStream>, Status> {
+        let settings = self.delayed_data()?;
+        let request = request.into_inner();
+        let subscription = Subscription::resolve(request.stream, self.min_interval, 1, None);
+        let symbol_state = self.symbol_state(&request.symbol)?;
+        let (tx, rx) = tokio::sync::mpsc::channel(subscription.buffer_size(self.max_buffer_size));
+        let sequence = Arc::new(AtomicU64::new(0));
+        let channel = self.open_channel();
+        let mut previous = None;
+        tokio::spawn(
+            self.drive_stream(tx, subscription, sequence, channel, move || {
+                let now = generate_u128_timestamp();
+                let sample = symbol_state
+                    .delayed_data
+                    .at(now.saturating_sub(settings.delay.as_nanos()))?;
+                if previous == Some(sample.ticker) {
+                    return None;
                 }
-                tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
-            }
-        });
+                previous = Some(sample.ticker);
+                Some(ticker_to_proto(symbol_state.symbol.clone(), sample.ticker))
+            }),
+        );
         Ok(Response::new(ReceiverStream::new(rx)))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::core::models::{Granularity, LimitOrder, MarketOrder, Operation, Side};
+    use crate::core::orderbook::OrderBook;
+    use crate::core::types::{OrderId, Price, Quantity};
+    use crate::engine::constants::property_loader::{SlowConsumerPolicy, SlowConsumerSettings};
+    use crate::engine::services::orderbook_manager_service::OrderbookManager;
+    use crate::engine::services::stat_stream_service::{
+        describe_depth_stream, firm_quote, send_event, Channel, DepthChannel, Subscription,
+        STREAM_GRANULARITY_KEY, STREAM_INTERVAL_KEY, STREAM_LEVELS_KEY,
+    };
+    use crate::engine::state::quotes::Quotes;
+    use crate::engine::utils::protobuf::granularity_from_proto;
+    use crate::protobuf::models::{Level, StreamParameters};
+    use std::sync::atomic::AtomicU64;
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+    use tonic::{Code, Response, Status};
+
+    #[test]
+    fn it_diffs_depth_updates_from_a_resync() {
+        let orderbook_manager = Arc::new(OrderbookManager::new("test".to_string(), 100, 1000));
+        let execute = |operation| unsafe {
+            (*orderbook_manager.get_primary()).execute(operation);
+            orderbook_manager.snapshot();
+        };
+        let depth_channel = DepthChannel {
+            channel: Arc::new(Channel {
+                id: 1,
+                sequence: Mutex::new(3),
+            }),
+            orderbook_manager: Arc::clone(&orderbook_manager),
+            sequence: Arc::new(AtomicU64::new(0)),
+            granularity: Granularity::P00,
+            levels: 10,
+            prices: 0..=u64::MAX,
+            previous: Mutex::new(None),
+        };
+        execute(Operation::Limit(LimitOrder::new(
+            OrderId(1),
+            Price(100),
+            Quantity(5),
+            Side::Bid,
+        )));
+        assert!(depth_channel.next_update().unwrap().snapshot);
+
+        execute(Operation::Limit(LimitOrder::new(
+            OrderId(2),
+            Price(90),
+            Quantity(5),
+            Side::Bid,
+        )));
+        let resync = depth_channel.resync();
+        assert!(resync.snapshot);
+        assert_eq!(resync.bids.len(), 2);
+        assert_eq!(resync.header.unwrap().channel_sequence, 4);
+        assert_eq!(depth_channel.next_update(), None);
+
+        execute(Operation::Cancel(OrderId(1)));
+        let update = depth_channel.next_update().unwrap();
+        assert!(!update.snapshot);
+        assert_eq!(
+            update.bids,
+            vec![Level {
+                price: 100,
+                quantity: 0
+            }]
+        );
+    }
+
+    #[test]
+    fn it_quotes_every_quantity_of_a_ladder() {
+        let mut orderbook = OrderBook::default();
+        for (id, price) in [(1, 100), (2, 110)] {
+            let order = LimitOrder::new(OrderId(id), Price(price), Quantity(10), Side::Ask);
+            orderbook.execute(Operation::Limit(order));
+        }
+        let quotes = Quotes::default();
+        let ttl = Duration::from_millis(2000);
+        let ladder: Vec<_> = [5, 20, 30]
+            .into_iter()
+            .map(|quantity| {
+                let order = MarketOrder::new(OrderId(0), Quantity(quantity), Side::Bid);
+                firm_quote(&orderbook, order, &quotes, ttl, 0)
+            })
+            .collect();
+
+        assert_eq!((ladder[0].status, ladder[0].price), (0, 100));
+        assert_eq!((ladder[1].status, ladder[1].price), (0, 105));
+        assert_eq!(ladder[1].quote_ttl_millis, 2000);
+        assert_ne!(ladder[0].quote_id, ladder[1].quote_id);
+        assert_ne!(ladder[2].status, 0);
+        assert!(ladder[2].quote_id.is_empty());
+
+        let quote_id = u128::from_be_bytes(ladder[1].quote_id.clone().try_into().unwrap());
+        let quote = quotes.redeem(quote_id, 1).unwrap();
+        assert_eq!((quote.quantity, quote.notional), (20, 2100));
+    }
+
+    #[test]
+    fn it_resolves_stream_parameters_against_server_caps() {
+        let min_interval = Duration::from_millis(100);
+        let defaults = Subscription::resolve(None, min_interval, 50, Some(10));
+        assert_eq!(
+            defaults,
+            Subscription {
+                interval: None,
+                max_updates: Some(10),
+                levels: 50,
+                conflate: false,
+            }
+        );
+        let parameters = StreamParameters {
+            interval_millis: 10,
+            max_updates: 100,
+            levels: 500,
+            conflate: false,
+        };
+        let capped = Subscription::resolve(Some(parameters), min_interval, 50, Some(10));
+        assert_eq!(
+            capped,
+            Subscription {
+                interval: Some(min_interval),
+                max_updates: Some(10),
+                levels: 50,
+                conflate: false,
+            }
+        );
+        let parameters = StreamParameters {
+            interval_millis: 2000,
+            max_updates: 5,
+            levels: 3,
+            conflate: true,
+        };
+        let requested = Subscription::resolve(Some(parameters), min_interval, 50, None);
+        assert_eq!(
+            requested,
+            Subscription {
+                interval: Some(Duration::from_secs(2)),
+                max_updates: Some(5),
+                levels: 3,
+                conflate: true,
+            }
+        );
+        assert_eq!(requested.buffer_size(100), 1);
+        assert_eq!(capped.buffer_size(100), 100);
+    }
+
+    #[test]
+    fn it_describes_the_resolved_shape_of_a_depth_stream() {
+        let parameters = StreamParameters {
+            interval_millis: 10,
+            levels: 500,
+            ..Default::default()
+        };
+        let min_interval = Duration::from_millis(100);
+        let subscription = Subscription::resolve(Some(parameters), min_interval, 50, None);
+        let granularity = granularity_from_proto(3).unwrap();
+        assert_eq!(granularity, Granularity::P10);
+        let mut response = Response::new(());
+        describe_depth_stream(&mut response, &subscription, granularity, min_interval);
+        let metadata = response.metadata();
+        assert_eq!(metadata.get(STREAM_LEVELS_KEY).unwrap(), "50");
+        assert_eq!(metadata.get(STREAM_GRANULARITY_KEY).unwrap(), "1000");
+        assert_eq!(metadata.get(STREAM_INTERVAL_KEY).unwrap(), "100");
+
+        let status = Status::from(granularity_from_proto(9).unwrap_err());
+        assert_eq!(status.code(), Code::InvalidArgument);
+    }
+
+    #[tokio::test]
+    async fn it_disconnects_subscribers_that_stay_behind() {
+        let settings = SlowConsumerSettings {
+            policy: SlowConsumerPolicy::Disconnect,
+            max_backlog: 2,
+            grace: Duration::from_millis(20),
+        };
+        let (tx, mut rx) = tokio::sync::mpsc::channel(4);
+        assert!(send_event(&tx, 1, &settings, "test").await);
+        assert!(send_event(&tx, 2, &settings, "test").await);
+        assert_eq!(rx.recv().await.unwrap().unwrap(), 1);
+        assert!(send_event(&tx, 3, &settings, "test").await);
+
+        assert!(!send_event(&tx, 4, &settings, "test").await);
+        assert_eq!(rx.recv().await.unwrap().unwrap(), 2);
+        assert_eq!(rx.recv().await.unwrap().unwrap(), 3);
+        let status = rx.recv().await.unwrap().unwrap_err();
+        assert_eq!(status.code(), Code::ResourceExhausted);
+    }
+}
@@ -0,0 +1,160 @@
+use crate::core::models::FillMetaData;
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// A fill of the symbol at the time it was executed.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Trade {
+    timestamp: u128,
+    price: u64,
+    quantity: u64,
+}
+
+/// The average prices of a symbol over a rolling window.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AveragePrice {
+    pub window: Duration,
+    /// The volume weighted average price of the trades of the window, `None` without trades.
+    pub vwap: Option<u64>,
+    /// The last traded price averaged over the time of the window, starting from the price the
+    /// window opened at. `None` until the symbol first traded.
+    pub twap: Option<u64>,
+    /// Quantity traded over the window.
+    pub volume: u64,
+}
+
+/// Rolling VWAP and TWAP of a symbol. Its executor records the fills it produces, which the
+/// average price task turns into averages over the configured windows every interval.
+#[derive(Debug, Default)]
+pub struct AveragePrices {
+    trades: Mutex<VecDeque<Trade>>,
+    latest: Mutex<Vec<AveragePrice>>,
+}
+
+impl AveragePrices {
+    /// Records the fills of an execution, at the time it took place in nanoseconds since the
+    /// unix epoch.
+    pub fn record(&self, fills: &[FillMetaData], timestamp: u128) {
+        if fills.is_empty() {
+            return;
+        }
+        let mut trades = self.trades.lock().unwrap();
+        trades.extend(fills.iter().map(|fill| Trade {
+            timestamp,
+            price: fill.price,
+            quantity: fill.quantity,
+        }));
+    }
+
+    /// Computes the averages over every window ending at `now` in nanoseconds since the unix
+    /// epoch, forgetting the trades that are older than the longest of them.
+    pub fn compute(&self, windows: &[Duration], now: u128) -> Vec<AveragePrice> {
+        let mut trades = self.trades.lock().unwrap();
+        let longest = windows.iter().max().copied().unwrap_or_default();
+        let start = now.saturating_sub(longest.as_nanos());
+        // the last trade before the longest window sets the price its time weighting starts at
+        while trades.get(1).is_some_and(|trade| trade.timestamp <= start) {
+            trades.pop_front();
+        }
+        let averages: Vec<AveragePrice> = windows
+            .iter()
+            .map(|&window| average(&trades, window, now))
+            .collect();
+        *self.latest.lock().unwrap() = averages.clone();
+        averages
+    }
+
+    /// Returns the averages last computed.
+    pub fn latest(&self) -> Vec<AveragePrice> {
+        self.latest.lock().unwrap().clone()
+    }
+}
+
+fn average(trades: &VecDeque<Trade>, window: Duration, now: u128) -> AveragePrice {
+    let start = now.saturating_sub(window.as_nanos());
+    let first = trades.partition_point(|trade| trade.timestamp < start);
+    let (mut volume, mut notional) = (0u128, 0u128);
+    for trade in trades.range(first..) {
+        volume += trade.quantity as u128;
+        notional += trade.price as u128 * trade.quantity as u128;
+    }
+    // the price in force from the start of the window, or from the first trade within it
+    let (mut since, mut price) = match first.checked_sub(1).and_then(|index| trades.get(index)) {
+        Some(trade) => (start, Some(trade.price)),
+        None => (trades.get(first).map_or(now, |trade| trade.timestamp), None),
+    };
+    let (mut weighted, mut elapsed) = (0u128, 0u128);
+    for trade in trades.range(first..) {
+        if let Some(price) = price {
+            weighted += price as u128 * (trade.timestamp - since);
+            elapsed += trade.timestamp - since;
+        }
+        since = trade.timestamp;
+        price = Some(trade.price);
+    }
+    let twap = price.map(|price| {
+        weighted += price as u128 * now.saturating_sub(since);
+        elapsed += now.saturating_sub(since);
+        match elapsed {
+            0 => price,
+            _ => (weighted / elapsed) as u64,
+        }
+    });
+    AveragePrice {
+        window,
+        vwap: (volume > 0).then(|| (notional / volume) as u64),
+        twap,
+        volume: volume.min(u64::MAX as u128) as u64,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::models::{FillMetaData, Side};
+    use crate::engine::state::average_price::{AveragePrice, AveragePrices};
+    use std::time::Duration;
+
+    const SECOND: u128 = 1_000_000_000;
+
+    fn fill(price: u64, quantity: u64) -> FillMetaData {
+        FillMetaData {
+            order_id: 1,
+            matched_order_id: 2,
+            taker_side: Side::Bid,
+            price,
+            quantity,
+        }
+    }
+
+    #[test]
+    fn it_averages_prices_over_rolling_windows() {
+        let prices = AveragePrices::default();
+        let windows = [Duration::from_secs(10), Duration::from_secs(30)];
+        assert_eq!(prices.compute(&windows, 0)[0].twap, None);
+
+        prices.record(&[fill(100, 1)], 0);
+        prices.record(&[fill(110, 3)], 20 * SECOND);
+        prices.record(&[fill(120, 1)], 25 * SECOND);
+        let averages = prices.compute(&windows, 30 * SECOND);
+        // 100 until 20 carries into the short window, then 110 until 25 and 120 until 30
+        assert_eq!(
+            averages[0],
+            AveragePrice {
+                window: Duration::from_secs(10),
+                vwap: Some(112),
+                twap: Some(115),
+                volume: 4,
+            }
+        );
+        assert_eq!(averages[1].vwap, Some(110));
+        assert_eq!(averages[1].twap, Some(105));
+        assert_eq!(prices.latest(), averages);
+
+        // the first trade still sets the price at the start of the longest window
+        let averages = prices.compute(&windows, 45 * SECOND);
+        assert_eq!((averages[0].vwap, averages[0].twap), (None, Some(120)));
+        assert_eq!(averages[1].volume, 4);
+        assert_eq!(averages[1].twap, Some(115));
+    }
+}
@@ -0,0 +1,288 @@
+use crate::core::models::{
+    ExecutionResult, FillMetaData, FillResult, LimitOrder, ModifyResult, Operation, Side,
+};
+use crate::engine::state::channel_metrics::{ChannelMetrics, HighWatermark};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use tokio::sync::broadcast;
+
+/// A change to a single resting order, from which the full orderbook can be replicated.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BookEvent {
+    /// The order started resting at the back of its price level.
+    Add(LimitOrder),
+    /// The resting order traded the quantity at the price, it is gone once nothing is left.
+    Execute {
+        order_id: u128,
+        price: u64,
+        quantity: u64,
+    },
+    /// The resting order was removed.
+    Cancel(u128),
+    /// The resting order now has the quantity of the order, keeping its place in the queue.
+    /// Orders that move to another price are cancelled and added again instead.
+    Replace(LimitOrder),
+}
+
+impl BookEvent {
+    /// The events an operation produced in the book, in the order they happened.
+    pub fn from_execution(operation: &Operation, result: &ExecutionResult) -> Vec<BookEvent> {
+        match (operation, result) {
+            (_, ExecutionResult::Executed(fill_result)) => Self::from_fills(fill_result),
+            (Operation::Modify(order), ExecutionResult::Modified(ModifyResult::Modified(_))) => {
+                vec![BookEvent::Replace(*order)]
+            }
+            (_, ExecutionResult::Modified(ModifyResult::Created(fill_result))) => {
+                let mut events = vec![BookEvent::Cancel(operation.order_id())];
+                events.extend(Self::from_fills(fill_result));
+                events
+            }
+            (_, ExecutionResult::Cancelled(id)) => vec![BookEvent::Cancel(*id)],
+            (_, ExecutionResult::Reduced(order)) => vec![BookEvent::Replace(*order)],
+            (_, ExecutionResult::Quoted(quote)) => {
+                let mut events: Vec<BookEvent> = quote
+                    .replaced
+                    .iter()
+                    .copied()
+                    .map(BookEvent::Cancel)
+                    .collect();
+                // the bid is placed before the ask, each side trading before it rests
+                for (side, rest) in [(Side::Bid, quote.bid), (Side::Ask, quote.ask)] {
+                    let fills = quote.fills.iter().filter(|fill| fill.taker_side == side);
+                    events.extend(Self::from_matches(fills, rest));
+                }
+                events
+            }
+            // both orders of a match in an uncross rested in the book
+            (_, ExecutionResult::Uncrossed(fills)) => fills
+                .iter()
+                .flat_map(|fill| {
+                    [fill.order_id, fill.matched_order_id].map(|order_id| BookEvent::Execute {
+                        order_id,
+                        price: fill.price,
+                        quantity: fill.quantity,
+                    })
+                })
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    fn from_fills(fill_result: &FillResult) -> Vec<BookEvent> {
+        let (fills, rest) = match fill_result {
+            FillResult::Filled(fills) => (fills.as_slice(), None),
+            FillResult::PartiallyFilled(order, fills) => (fills.as_slice(), Some(*order)),
+            FillResult::Created(order) => (&[][..], Some(*order)),
+            FillResult::Failed => return Vec::new(),
+        };
+        Self::from_matches(fills.iter(), rest)
+    }
+
+    fn from_matches<'a>(
+        fills: impl Iterator<Item = &'a FillMetaData>,
+        rest: Option<LimitOrder>,
+    ) -> Vec<BookEvent> {
+        fills
+            .map(|fill| BookEvent::Execute {
+                order_id: fill.matched_order_id,
+                price: fill.price,
+                quantity: fill.quantity,
+            })
+            .chain(rest.map(BookEvent::Add))
+            .collect()
+    }
+}
+
+/// A book event numbered on the feed of its symbol.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SequencedEvent {
+    /// Increases by one with every event, so that a gap tells a subscriber it missed one.
+    pub sequence: u64,
+    /// Nanoseconds since the unix epoch at which the operation was executed.
+    pub timestamp: u128,
+    pub event: BookEvent,
+}
+
+/// The resting orders of a symbol along with the feed of every later change to them.
+pub struct BookSubscription {
+    /// Every resting order in priority order, bids before asks.
+    pub orders: Vec<LimitOrder>,
+    /// The sequence of the last event reflected in the orders.
+    pub sequence: u64,
+    pub events: broadcast::Receiver<Arc<Vec<SequencedEvent>>>,
+}
+
+/// The per order feed of a symbol, published by its executor after every batch. Events are only
+/// produced while someone is subscribed. Buffers the passed number of batches for every
+/// subscriber, a subscriber further behind is dropped.
+#[derive(Debug)]
+pub struct BookFeed {
+    sender: broadcast::Sender<Arc<Vec<SequencedEvent>>>,
+    sequence: AtomicU64,
+    capacity: usize,
+    high_watermark: HighWatermark,
+}
+
+impl BookFeed {
+    pub fn new(capacity: usize) -> Self {
+        BookFeed {
+            sender: broadcast::channel(capacity).0,
+            sequence: AtomicU64::new(0),
+            capacity,
+            high_watermark: HighWatermark::default(),
+        }
+    }
+
+    pub fn has_subscribers(&self) -> bool {
+        self.sender.receiver_count() > 0
+    }
+
+    /// Numbers the events and sends them to every subscriber.
+    pub fn publish(&self, events: Vec<(BookEvent, u128)>) {
+        if events.is_empty() {
+            return;
+        }
+        let first_sequence = self
+            .sequence
+            .fetch_add(events.len() as u64, Ordering::Relaxed)
+            + 1;
+        let events = events
+            .into_iter()
+            .zip(first_sequence..)
+            .map(|((event, timestamp), sequence)| SequencedEvent {
+                sequence,
+                timestamp,
+                event,
+            })
+            .collect();
+        let _ = self.sender.send(Arc::new(events));
+        self.high_watermark.record(self.sender.len());
+    }
+
+    /// The batches buffered for the subscriber furthest behind.
+    pub fn metrics(&self) -> ChannelMetrics {
+        ChannelMetrics {
+            channel: "book_feed",
+            capacity: self.capacity,
+            depth: self.sender.len(),
+            high_watermark: self.high_watermark.get(),
+        }
+    }
+
+    /// Subscribes to the events following the passed resting orders, which have to be taken by
+    /// the executor so that no event is published in between.
+    pub fn subscribe(&self, orders: Vec<LimitOrder>) -> BookSubscription {
+        BookSubscription {
+            orders,
+            sequence: self.sequence.load(Ordering::Relaxed),
+            events: self.sender.subscribe(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::models::{LimitOrder, MarketOrder, Operation, Side};
+    use crate::core::orderbook::OrderBook;
+    use crate::core::types::{OrderId, Price, Quantity};
+    use crate::engine::state::book_feed::{BookEvent, BookFeed};
+
+    fn execute(orderbook: &mut OrderBook, operation: Operation) -> Vec<BookEvent> {
+        let result = orderbook.execute(operation);
+        BookEvent::from_execution(&operation, &result)
+    }
+
+    #[test]
+    fn it_describes_executions_as_book_events() {
+        let mut orderbook = OrderBook::default();
+        let ask = LimitOrder::new(OrderId(1), Price(100), Quantity(10), Side::Ask);
+        assert_eq!(
+            execute(&mut orderbook, Operation::Limit(ask)),
+            vec![BookEvent::Add(ask)]
+        );
+
+        let bid = LimitOrder::new(OrderId(2), Price(100), Quantity(15), Side::Bid);
+        let rest = LimitOrder::new(OrderId(2), Price(100), Quantity(5), Side::Bid);
+        assert_eq!(
+            execute(&mut orderbook, Operation::Limit(bid)),
+            vec![
+                BookEvent::Execute {
+                    order_id: 1,
+                    price: 100,
+                    quantity: 10,
+                },
+                BookEvent::Add(rest),
+            ]
+        );
+
+        let smaller = LimitOrder::new(OrderId(2), Price(100), Quantity(3), Side::Bid);
+        assert_eq!(
+            execute(&mut orderbook, Operation::Modify(smaller)),
+            vec![BookEvent::Replace(smaller)]
+        );
+        let moved = LimitOrder::new(OrderId(2), Price(99), Quantity(3), Side::Bid);
+        assert_eq!(
+            execute(&mut orderbook, Operation::Modify(moved)),
+            vec![BookEvent::Cancel(2), BookEvent::Add(moved)]
+        );
+
+        let market = MarketOrder::new(OrderId(3), Quantity(1), Side::Ask);
+        assert_eq!(
+            execute(&mut orderbook, Operation::Market(market)),
+            vec![BookEvent::Execute {
+                order_id: 2,
+                price: 99,
+                quantity: 1,
+            }]
+        );
+        assert_eq!(
+            execute(&mut orderbook, Operation::Cancel(OrderId(2))),
+            vec![BookEvent::Cancel(2)]
+        );
+        assert!(execute(&mut orderbook, Operation::Cancel(OrderId(2))).is_empty());
+
+        let bid = LimitOrder::new(OrderId(4), Price(98), Quantity(5), Side::Bid);
+        let ask = LimitOrder::new(OrderId(5), Price(101), Quantity(5), Side::Ask);
+        assert_eq!(
+            execute(&mut orderbook, Operation::Quote { bid, ask }),
+            vec![BookEvent::Add(bid), BookEvent::Add(ask)]
+        );
+        orderbook.execute(Operation::Limit(LimitOrder::new(
+            OrderId(6),
+            Price(102),
+            Quantity(2),
+            Side::Ask,
+        )));
+        let bid = LimitOrder { price: 102, ..bid };
+        let ask = LimitOrder { price: 103, ..ask };
+        assert_eq!(
+            execute(&mut orderbook, Operation::Quote { bid, ask }),
+            vec![
+                BookEvent::Cancel(4),
+                BookEvent::Cancel(5),
+                BookEvent::Execute {
+                    order_id: 6,
+                    price: 102,
+                    quantity: 2,
+                },
+                BookEvent::Add(LimitOrder { quantity: 3, ..bid }),
+                BookEvent::Add(ask),
+            ]
+        );
+    }
+
+    #[test]
+    fn it_numbers_the_events_after_the_snapshot() {
+        let feed = BookFeed::new(16);
+        feed.publish(vec![(BookEvent::Cancel(1), 10)]);
+        assert!(!feed.has_subscribers());
+
+        let mut subscription = feed.subscribe(Vec::new());
+        assert_eq!(subscription.sequence, 1);
+        feed.publish(vec![(BookEvent::Cancel(2), 20), (BookEvent::Cancel(3), 20)]);
+        let events = subscription.events.try_recv().unwrap();
+        let sequences: Vec<u64> = events.iter().map(|event| event.sequence).collect();
+        assert_eq!(sequences, vec![2, 3]);
+        assert_eq!(events[1].event, BookEvent::Cancel(3));
+    }
+}
@@ -0,0 +1,206 @@
+use crate::core::models::FillMetaData;
+use std::collections::VecDeque;
+use std::sync::Mutex;
+
+/// Candles kept per interval, which bounds the backfill a subscriber can ask for.
+pub const MAX_CANDLES: usize = 1000;
+
+/// The intervals candles are aggregated at.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CandleInterval {
+    OneMinute,
+    FiveMinutes,
+    OneHour,
+}
+
+impl CandleInterval {
+    pub const ALL: [CandleInterval; 3] = [
+        CandleInterval::OneMinute,
+        CandleInterval::FiveMinutes,
+        CandleInterval::OneHour,
+    ];
+
+    /// The length of the interval in nanoseconds.
+    pub fn nanos(&self) -> u128 {
+        const MINUTE: u128 = 60 * 1_000_000_000;
+        match self {
+            CandleInterval::OneMinute => MINUTE,
+            CandleInterval::FiveMinutes => 5 * MINUTE,
+            CandleInterval::OneHour => 60 * MINUTE,
+        }
+    }
+
+    fn index(&self) -> usize {
+        match self {
+            CandleInterval::OneMinute => 0,
+            CandleInterval::FiveMinutes => 1,
+            CandleInterval::OneHour => 2,
+        }
+    }
+}
+
+/// The trades of a symbol over a single interval.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Candle {
+    /// Start of the interval in nanoseconds since the unix epoch.
+    pub start: u128,
+    pub open: u64,
+    pub high: u64,
+    pub low: u64,
+    pub close: u64,
+    pub volume: u64,
+    pub trade_count: u64,
+}
+
+impl Candle {
+    fn new(start: u128, price: u64) -> Self {
+        Candle {
+            start,
+            open: price,
+            high: price,
+            low: price,
+            close: price,
+            volume: 0,
+            trade_count: 0,
+        }
+    }
+
+    fn record(&mut self, fill: &FillMetaData) {
+        self.high = self.high.max(fill.price);
+        self.low = self.low.min(fill.price);
+        self.close = fill.price;
+        self.volume = self.volume.saturating_add(fill.quantity);
+        self.trade_count += 1;
+    }
+
+    /// Whether the interval of the candle is over at `now`, after which it no longer changes.
+    pub fn is_closed(&self, interval: CandleInterval, now: u128) -> bool {
+        now >= self.start + interval.nanos()
+    }
+}
+
+/// OHLCV candles of a symbol at every [`CandleInterval`], maintained by its executor from the
+/// fills it produces. Intervals without trades have no candle, and only fills executed since
+/// the server started are covered.
+#[derive(Debug, Default)]
+pub struct Candles {
+    candles: Mutex<[VecDeque<Candle>; 3]>,
+}
+
+impl Candles {
+    /// Records the fills of an execution, at the time it took place in nanoseconds since the
+    /// unix epoch.
+    pub fn record(&self, fills: &[FillMetaData], timestamp: u128) {
+        if fills.is_empty() {
+            return;
+        }
+        let mut candles = self.candles.lock().unwrap();
+        for interval in CandleInterval::ALL {
+            let start = timestamp - timestamp % interval.nanos();
+            let candles = &mut candles[interval.index()];
+            for fill in fills {
+                let candle = match candles.back_mut() {
+                    Some(candle) if candle.start >= start => candle,
+                    _ => {
+                        if candles.len() == MAX_CANDLES {
+                            candles.pop_front();
+                        }
+                        candles.push_back(Candle::new(start, fill.price));
+                        candles.back_mut().unwrap()
+                    }
+                };
+                candle.record(fill);
+            }
+        }
+    }
+
+    /// Returns up to `count` of the latest candles of the interval, oldest first. The last one
+    /// may still be in progress.
+    pub fn latest(&self, interval: CandleInterval, count: usize) -> Vec<Candle> {
+        let candles = &self.candles.lock().unwrap()[interval.index()];
+        candles
+            .iter()
+            .skip(candles.len().saturating_sub(count))
+            .copied()
+            .collect()
+    }
+
+    /// Returns the candles of the interval starting at or after `start`, oldest first.
+    pub fn since(&self, interval: CandleInterval, start: u128) -> Vec<Candle> {
+        let candles = &self.candles.lock().unwrap()[interval.index()];
+        let first = candles.partition_point(|candle| candle.start < start);
+        candles.range(first..).copied().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::models::{FillMetaData, Side};
+    use crate::engine::state::candles::{Candle, CandleInterval, Candles, MAX_CANDLES};
+
+    const MINUTE: u128 = 60 * 1_000_000_000;
+
+    fn fill(price: u64, quantity: u64) -> FillMetaData {
+        FillMetaData {
+            order_id: 1,
+            matched_order_id: 2,
+            taker_side: Side::Ask,
+            price,
+            quantity,
+        }
+    }
+
+    #[test]
+    fn it_aggregates_fills_into_candles_of_every_interval() {
+        let candles = Candles::default();
+        candles.record(&[fill(100, 1), fill(120, 2)], 10);
+        candles.record(&[fill(90, 3)], MINUTE + 10);
+        candles.record(&[fill(95, 1)], 6 * MINUTE);
+
+        let minutes = candles.latest(CandleInterval::OneMinute, 10);
+        assert_eq!(minutes.len(), 3);
+        assert_eq!(
+            minutes[0],
+            Candle {
+                start: 0,
+                open: 100,
+                high: 120,
+                low: 100,
+                close: 120,
+                volume: 3,
+                trade_count: 2,
+            }
+        );
+        assert_eq!(minutes[2].start, 6 * MINUTE);
+
+        let five_minutes = candles.latest(CandleInterval::FiveMinutes, 10);
+        assert_eq!(five_minutes.len(), 2);
+        assert_eq!(
+            (
+                five_minutes[0].open,
+                five_minutes[0].low,
+                five_minutes[0].close
+            ),
+            (100, 90, 90)
+        );
+        assert_eq!(five_minutes[0].volume, 6);
+        assert_eq!(candles.latest(CandleInterval::OneHour, 10)[0].volume, 7);
+
+        assert!(minutes[0].is_closed(CandleInterval::OneMinute, MINUTE));
+        assert!(!minutes[2].is_closed(CandleInterval::OneMinute, 6 * MINUTE + 1));
+        let since = candles.since(CandleInterval::OneMinute, MINUTE);
+        assert_eq!(since, minutes[1..].to_vec());
+        assert_eq!(candles.latest(CandleInterval::OneMinute, 1), minutes[2..]);
+    }
+
+    #[test]
+    fn it_bounds_the_candles_kept_per_interval() {
+        let candles = Candles::default();
+        for minute in 0..MAX_CANDLES as u128 + 5 {
+            candles.record(&[fill(100, 1)], minute * MINUTE);
+        }
+        let minutes = candles.latest(CandleInterval::OneMinute, usize::MAX);
+        assert_eq!(minutes.len(), MAX_CANDLES);
+        assert_eq!(minutes[0].start, 5 * MINUTE);
+    }
+}
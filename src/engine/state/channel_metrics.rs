@@ -0,0 +1,82 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use tokio::sync::mpsc::Sender;
+
+/// How full an internal channel of a symbol is.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ChannelMetrics {
+    pub channel: &'static str,
+    pub capacity: usize,
+    /// Messages queued when the metrics were taken.
+    pub depth: usize,
+    /// The most messages queued at once since the server started.
+    pub high_watermark: usize,
+}
+
+impl ChannelMetrics {
+    /// The metrics of a bounded channel, of which the sender tells the current depth.
+    pub fn of_sender<T>(
+        channel: &'static str,
+        sender: &Sender<T>,
+        high_watermark: &HighWatermark,
+    ) -> Self {
+        let depth = sender.max_capacity() - sender.capacity();
+        Self {
+            channel,
+            capacity: sender.max_capacity(),
+            depth,
+            high_watermark: high_watermark.get().max(depth),
+        }
+    }
+}
+
+/// The most messages queued at once on a channel. The depth is recorded by the end that takes
+/// messages off the channel, as it is deepest just before it does.
+#[derive(Debug, Default)]
+pub struct HighWatermark(AtomicUsize);
+
+impl HighWatermark {
+    pub fn record(&self, depth: usize) {
+        self.0.fetch_max(depth, Ordering::Relaxed);
+    }
+
+    pub fn get(&self) -> usize {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// High watermarks of the channels feeding the executor of a symbol, recorded by the executor.
+#[derive(Debug, Default)]
+pub struct ExecutorWatermarks {
+    pub orders: HighWatermark,
+    pub commands: HighWatermark,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::engine::state::channel_metrics::{ChannelMetrics, HighWatermark};
+    use tokio::sync::mpsc;
+
+    #[test]
+    fn it_reports_depth_and_high_watermark() {
+        let (sender, mut receiver) = mpsc::channel(8);
+        let high_watermark = HighWatermark::default();
+        for value in 0..5 {
+            sender.try_send(value).unwrap();
+        }
+        high_watermark.record(receiver.len());
+        receiver.try_recv().unwrap();
+        receiver.try_recv().unwrap();
+        sender.try_send(5).unwrap();
+        assert_eq!(
+            ChannelMetrics::of_sender("orders", &sender, &high_watermark),
+            ChannelMetrics {
+                channel: "orders",
+                capacity: 8,
+                depth: 4,
+                high_watermark: 5,
+            }
+        );
+        high_watermark.record(3);
+        assert_eq!(high_watermark.get(), 5);
+    }
+}
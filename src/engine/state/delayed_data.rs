@@ -0,0 +1,108 @@
+use crate::core::models::{Level, OrderbookAggregated};
+use crate::engine::state::ticker::TickerStats;
+use std::collections::VecDeque;
+use std::sync::Mutex;
+
+/// The market data of a symbol at a point in time, served on the delayed feed once the delay
+/// passed.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MarketSample {
+    /// Nanoseconds since the unix epoch at which the market was sampled.
+    pub timestamp: u128,
+    pub last_trade_price: u64,
+    pub max_bid: u64,
+    pub min_ask: u64,
+    pub best_bid: Option<Level>,
+    pub best_ask: Option<Level>,
+    /// The top levels of the book at the price of the orders.
+    pub depth: OrderbookAggregated,
+    pub ticker: TickerStats,
+}
+
+#[derive(Debug, Default)]
+struct Samples {
+    /// Samples offered so far, of which only every n-th is kept.
+    offered: u64,
+    samples: VecDeque<MarketSample>,
+}
+
+/// Recent samples of the market data of a symbol, taken by the delayed data task and kept for as
+/// long as the delay of the feed.
+#[derive(Debug, Default)]
+pub struct DelayedMarketData {
+    samples: Mutex<Samples>,
+}
+
+impl DelayedMarketData {
+    /// Keeps the first of every `sampling` samples offered. Samples taken at or before
+    /// `retain_from` are forgotten, except for the latest of them, which stays in force until
+    /// the next one.
+    pub fn record(&self, sample: MarketSample, sampling: u64, retain_from: u128) {
+        let mut samples = self.samples.lock().unwrap();
+        let offered = samples.offered;
+        samples.offered += 1;
+        if offered.is_multiple_of(sampling.max(1)) {
+            samples.samples.push_back(sample);
+        }
+        while samples
+            .samples
+            .get(1)
+            .is_some_and(|sample| sample.timestamp <= retain_from)
+        {
+            samples.samples.pop_front();
+        }
+    }
+
+    /// The latest sample taken at or before `time` in nanoseconds since the unix epoch.
+    pub fn at(&self, time: u128) -> Option<MarketSample> {
+        let samples = self.samples.lock().unwrap();
+        let after = samples
+            .samples
+            .partition_point(|sample| sample.timestamp <= time);
+        after
+            .checked_sub(1)
+            .and_then(|index| samples.samples.get(index))
+            .cloned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::engine::state::delayed_data::{DelayedMarketData, MarketSample};
+
+    fn sample(timestamp: u128) -> MarketSample {
+        MarketSample {
+            timestamp,
+            last_trade_price: timestamp as u64,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn it_serves_the_sample_in_force_at_a_time() {
+        let data = DelayedMarketData::default();
+        for timestamp in [10, 20, 30] {
+            data.record(sample(timestamp), 1, 0);
+        }
+        assert_eq!(data.at(5), None);
+        assert_eq!(data.at(10).map(|sample| sample.timestamp), Some(10));
+        assert_eq!(data.at(29).map(|sample| sample.timestamp), Some(20));
+        assert_eq!(data.at(100).map(|sample| sample.timestamp), Some(30));
+
+        // the latest sample before the retention still serves the times up to the next one
+        data.record(sample(40), 1, 25);
+        assert_eq!(data.at(15), None);
+        assert_eq!(data.at(25).map(|sample| sample.timestamp), Some(20));
+    }
+
+    #[test]
+    fn it_keeps_one_in_every_n_samples() {
+        let data = DelayedMarketData::default();
+        for timestamp in 0..7 {
+            data.record(sample(timestamp), 3, 0);
+        }
+        assert_eq!(data.at(2).map(|sample| sample.timestamp), Some(0));
+        assert_eq!(data.at(5).map(|sample| sample.timestamp), Some(3));
+        assert_eq!(data.at(6).map(|sample| sample.timestamp), Some(6));
+    }
+}
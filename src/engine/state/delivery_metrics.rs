@@ -0,0 +1,86 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+/// Counters describing how well execution events are being delivered to kafka.
+#[derive(Debug, Default)]
+pub struct DeliveryMetrics {
+    delivered: AtomicU64,
+    failed: AtomicU64,
+    dropped: AtomicU64,
+    total_latency_micros: AtomicU64,
+    max_latency_micros: AtomicU64,
+}
+
+/// A point in time copy of the [`DeliveryMetrics`] counters.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DeliveryMetricsSnapshot {
+    /// Events acknowledged by the broker.
+    pub delivered: u64,
+    /// Delivery attempts that failed, including the ones that were retried.
+    pub failed: u64,
+    /// Events given up on according to the delivery failure policy.
+    pub dropped: u64,
+    pub average_latency: Duration,
+    pub max_latency: Duration,
+}
+
+impl DeliveryMetrics {
+    /// Records a delivered event along with the time it took to encode and send it.
+    pub fn record_delivery(&self, latency: Duration) {
+        let latency_micros = latency.as_micros() as u64;
+        self.delivered.fetch_add(1, Ordering::Relaxed);
+        self.total_latency_micros
+            .fetch_add(latency_micros, Ordering::Relaxed);
+        self.max_latency_micros
+            .fetch_max(latency_micros, Ordering::Relaxed);
+    }
+
+    pub fn record_failure(&self) {
+        self.failed.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_drop(&self) {
+        self.dropped.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn snapshot(&self) -> DeliveryMetricsSnapshot {
+        let delivered = self.delivered.load(Ordering::Relaxed);
+        let total_latency_micros = self.total_latency_micros.load(Ordering::Relaxed);
+        DeliveryMetricsSnapshot {
+            delivered,
+            failed: self.failed.load(Ordering::Relaxed),
+            dropped: self.dropped.load(Ordering::Relaxed),
+            average_latency: Duration::from_micros(
+                total_latency_micros.checked_div(delivered).unwrap_or(0),
+            ),
+            max_latency: Duration::from_micros(self.max_latency_micros.load(Ordering::Relaxed)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::engine::state::delivery_metrics::{DeliveryMetrics, DeliveryMetricsSnapshot};
+    use std::time::Duration;
+
+    #[test]
+    fn it_aggregates_delivery_counters() {
+        let metrics = DeliveryMetrics::default();
+        assert_eq!(metrics.snapshot().average_latency, Duration::ZERO);
+        metrics.record_delivery(Duration::from_micros(100));
+        metrics.record_delivery(Duration::from_micros(300));
+        metrics.record_failure();
+        metrics.record_failure();
+        metrics.record_drop();
+        assert_eq!(
+            metrics.snapshot(),
+            DeliveryMetricsSnapshot {
+                delivered: 2,
+                failed: 2,
+                dropped: 1,
+                average_latency: Duration::from_micros(200),
+                max_latency: Duration::from_micros(300),
+            }
+        );
+    }
+}
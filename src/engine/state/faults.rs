@@ -0,0 +1,81 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+/// Faults armed through the admin service, so that integration tests can check how the server
+/// copes with a slow engine, a failing kafka and lost snapshots. Only built with the `chaos`
+/// feature.
+#[derive(Debug, Default)]
+pub struct Faults {
+    matching_delay_micros: AtomicU64,
+    failed_sends: AtomicU64,
+    dropped_snapshots: AtomicU64,
+}
+
+impl Faults {
+    /// Delays the matching of every batch by the passed duration, or stops delaying it if zero.
+    pub fn delay_matching(&self, delay: Duration) {
+        self.matching_delay_micros
+            .store(delay.as_micros() as u64, Ordering::Relaxed);
+    }
+
+    pub fn matching_delay(&self) -> Option<Duration> {
+        match self.matching_delay_micros.load(Ordering::Relaxed) {
+            0 => None,
+            micros => Some(Duration::from_micros(micros)),
+        }
+    }
+
+    /// Fails the next sends to kafka, on top of those still to fail.
+    pub fn fail_sends(&self, count: u64) {
+        self.failed_sends.fetch_add(count, Ordering::Relaxed);
+    }
+
+    /// Whether the send at hand is to fail, which counts it off those still to fail.
+    pub fn take_failed_send(&self) -> bool {
+        take(&self.failed_sends)
+    }
+
+    /// Drops the next snapshot uploads, on top of those still to drop.
+    pub fn drop_snapshots(&self, count: u64) {
+        self.dropped_snapshots.fetch_add(count, Ordering::Relaxed);
+    }
+
+    /// Whether the snapshot at hand is to be dropped, which counts it off those still to drop.
+    pub fn take_dropped_snapshot(&self) -> bool {
+        take(&self.dropped_snapshots)
+    }
+}
+
+fn take(remaining: &AtomicU64) -> bool {
+    remaining
+        .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |count| {
+            count.checked_sub(1)
+        })
+        .is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::engine::state::faults::Faults;
+    use std::time::Duration;
+
+    #[test]
+    fn it_counts_off_injected_faults() {
+        let faults = Faults::default();
+        assert_eq!(faults.matching_delay(), None);
+        assert!(!faults.take_failed_send());
+        faults.delay_matching(Duration::from_millis(5));
+        assert_eq!(faults.matching_delay(), Some(Duration::from_millis(5)));
+        faults.delay_matching(Duration::ZERO);
+        assert_eq!(faults.matching_delay(), None);
+
+        faults.fail_sends(1);
+        faults.fail_sends(1);
+        assert!(faults.take_failed_send());
+        assert!(faults.take_failed_send());
+        assert!(!faults.take_failed_send());
+        faults.drop_snapshots(1);
+        assert!(faults.take_dropped_snapshot());
+        assert!(!faults.take_dropped_snapshot());
+    }
+}
@@ -0,0 +1,133 @@
+use crate::engine::state::symbol_state::SnapshotMark;
+use serde::Serialize;
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// The outcome of checking a single dependency of the server.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct HealthCheck {
+    pub name: &'static str,
+    pub healthy: bool,
+    /// What was found wrong, empty when healthy.
+    pub detail: String,
+}
+
+impl HealthCheck {
+    /// Passes when nothing was found wrong, otherwise fails with every problem found.
+    pub fn from_problems(name: &'static str, problems: Vec<String>) -> Self {
+        Self {
+            name,
+            healthy: problems.is_empty(),
+            detail: problems.join(", "),
+        }
+    }
+}
+
+/// The checks of a single run of the health task.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct HealthReport {
+    /// Nanoseconds since the unix epoch at which the checks ran, zero before they first ran.
+    pub checked_at: u128,
+    /// Whether the executor of every symbol is running.
+    pub executors_running: bool,
+    pub checks: Vec<HealthCheck>,
+}
+
+impl HealthReport {
+    /// Live until an executor exited, which the server cannot recover from without a restart.
+    /// The server counts as live while starting up, before the checks first ran.
+    pub fn is_live(&self) -> bool {
+        self.checked_at == 0 || self.executors_running
+    }
+
+    /// Ready to take orders once the checks ran, found every executor running and every
+    /// dependency passed its check.
+    pub fn is_ready(&self) -> bool {
+        self.checked_at > 0 && self.executors_running && self.checks.iter().all(|c| c.healthy)
+    }
+}
+
+/// Liveness and readiness of the server, as last reported by the health task.
+#[derive(Debug, Default)]
+pub struct Health {
+    report: Mutex<HealthReport>,
+}
+
+impl Health {
+    pub fn update(&self, report: HealthReport) {
+        *self.report.lock().unwrap() = report;
+    }
+
+    pub fn report(&self) -> HealthReport {
+        self.report.lock().unwrap().clone()
+    }
+}
+
+/// How full a channel is in percent of its capacity.
+pub fn saturation_percent(free: usize, capacity: usize) -> u64 {
+    match capacity {
+        0 => 0,
+        capacity => (capacity.saturating_sub(free) * 100 / capacity) as u64,
+    }
+}
+
+/// Whether operations executed since the last snapshot have waited longer than `max_age` for
+/// the next one. Unchanged orderbooks are never stale, as the snapshot task leaves them be.
+pub fn snapshot_stale(mark: Option<SnapshotMark>, operation_count: u64, max_age: Duration) -> bool {
+    match mark {
+        Some(mark) => operation_count > mark.operation_count && mark.taken_at.elapsed() > max_age,
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::engine::state::health::{
+        saturation_percent, snapshot_stale, HealthCheck, HealthReport,
+    };
+    use crate::engine::state::symbol_state::SnapshotMark;
+    use std::time::Duration;
+    use tokio::time::Instant;
+
+    #[test]
+    fn it_is_ready_once_live_and_every_check_passed() {
+        let mut report = HealthReport::default();
+        assert!(report.is_live() && !report.is_ready());
+        report.checked_at = 1;
+        assert!(!report.is_live());
+        report.executors_running = true;
+        assert!(report.is_live());
+        report.checks = vec![
+            HealthCheck::from_problems("kafka_producer", Vec::new()),
+            HealthCheck::from_problems(
+                "executor_channels",
+                vec!["ETHUSD at 95%".to_string(), "BTCUSD at 91%".to_string()],
+            ),
+        ];
+        assert!(!report.is_ready());
+        assert_eq!(report.checks[1].detail, "ETHUSD at 95%, BTCUSD at 91%");
+        report.checks.pop();
+        assert!(report.is_ready());
+    }
+
+    #[test]
+    fn it_measures_channel_saturation_and_snapshot_freshness() {
+        assert_eq!(saturation_percent(1000, 1000), 0);
+        assert_eq!(saturation_percent(75, 1000), 92);
+        assert_eq!(saturation_percent(0, 0), 0);
+
+        let max_age = Duration::from_secs(10);
+        let stale = Some(SnapshotMark {
+            operation_count: 5,
+            taken_at: Instant::now() - Duration::from_secs(11),
+        });
+        assert!(!snapshot_stale(None, 5, max_age));
+        assert!(!snapshot_stale(stale, 5, max_age));
+        assert!(snapshot_stale(stale, 6, max_age));
+        let recent = Some(SnapshotMark {
+            operation_count: 5,
+            taken_at: Instant::now(),
+        });
+        assert!(!snapshot_stale(recent, 6, max_age));
+    }
+}
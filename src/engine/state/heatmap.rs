@@ -0,0 +1,150 @@
+use crate::core::models::Level;
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::Mutex;
+
+/// Samples kept per symbol, an hour of them at the default sampling interval.
+pub const MAX_HEATMAP_SAMPLES: usize = 3600;
+
+/// The resting liquidity of a symbol at a point in time.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LiquiditySample {
+    /// Nanoseconds since the unix epoch at which the book was sampled.
+    pub timestamp: u128,
+    pub bids: Vec<Level>,
+    pub asks: Vec<Level>,
+}
+
+/// The average liquidity of a time bucket, aligned with the prices of its [`HeatmapMatrix`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct HeatmapRow {
+    /// Start of the bucket in nanoseconds since the unix epoch.
+    pub start: u128,
+    pub bids: Vec<u64>,
+    pub asks: Vec<u64>,
+}
+
+/// Resting liquidity by time bucket and price level, buckets without samples are left out.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HeatmapMatrix {
+    /// Every price sampled over the buckets in ascending order.
+    pub prices: Vec<u64>,
+    /// The buckets in chronological order.
+    pub rows: Vec<HeatmapRow>,
+}
+
+/// The bid and ask quantities summed up per price over the samples of a bucket.
+#[derive(Default)]
+struct Bucket {
+    samples: u64,
+    liquidity: BTreeMap<u64, (u64, u64)>,
+}
+
+/// Periodic samples of the top levels of a symbol's book, from which depth heatmaps are built.
+#[derive(Debug, Default)]
+pub struct Heatmap {
+    samples: Mutex<VecDeque<LiquiditySample>>,
+}
+
+impl Heatmap {
+    pub fn record(&self, sample: LiquiditySample) {
+        let mut samples = self.samples.lock().unwrap();
+        if samples.len() == MAX_HEATMAP_SAMPLES {
+            samples.pop_front();
+        }
+        samples.push_back(sample);
+    }
+
+    /// Averages the samples taken at or after `start` into buckets of `bucket` nanoseconds.
+    pub fn matrix(&self, start: u128, bucket: u128) -> HeatmapMatrix {
+        let bucket = bucket.max(1);
+        let samples = self.samples.lock().unwrap();
+        let first = samples.partition_point(|sample| sample.timestamp < start);
+        let mut buckets: BTreeMap<u128, Bucket> = BTreeMap::new();
+        for sample in samples.range(first..) {
+            let bucket_start = sample.timestamp - sample.timestamp % bucket;
+            let totals = buckets.entry(bucket_start).or_default();
+            totals.samples += 1;
+            for level in &sample.bids {
+                totals.liquidity.entry(level.price).or_default().0 += level.quantity;
+            }
+            for level in &sample.asks {
+                totals.liquidity.entry(level.price).or_default().1 += level.quantity;
+            }
+        }
+        let mut prices: Vec<u64> = buckets
+            .values()
+            .flat_map(|totals| totals.liquidity.keys().copied())
+            .collect();
+        prices.sort_unstable();
+        prices.dedup();
+        let rows = buckets
+            .into_iter()
+            .map(|(start, totals)| {
+                let average = |quantity: fn(&(u64, u64)) -> u64| -> Vec<u64> {
+                    prices
+                        .iter()
+                        .map(|price| {
+                            totals.liquidity.get(price).map_or(0, quantity) / totals.samples
+                        })
+                        .collect()
+                };
+                HeatmapRow {
+                    start,
+                    bids: average(|&(bid, _)| bid),
+                    asks: average(|&(_, ask)| ask),
+                }
+            })
+            .collect();
+        HeatmapMatrix { prices, rows }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::models::Level;
+    use crate::engine::state::heatmap::{Heatmap, LiquiditySample, MAX_HEATMAP_SAMPLES};
+
+    fn sample(timestamp: u128, bid: (u64, u64), ask: (u64, u64)) -> LiquiditySample {
+        LiquiditySample {
+            timestamp,
+            bids: vec![Level {
+                price: bid.0,
+                quantity: bid.1,
+            }],
+            asks: vec![Level {
+                price: ask.0,
+                quantity: ask.1,
+            }],
+        }
+    }
+
+    #[test]
+    fn it_averages_samples_into_time_buckets() {
+        let heatmap = Heatmap::default();
+        heatmap.record(sample(5, (99, 10), (101, 4)));
+        heatmap.record(sample(10, (99, 10), (101, 4)));
+        heatmap.record(sample(15, (99, 20), (102, 6)));
+        heatmap.record(sample(25, (100, 5), (102, 8)));
+
+        let matrix = heatmap.matrix(10, 10);
+        assert_eq!(matrix.prices, vec![99, 100, 101, 102]);
+        assert_eq!(matrix.rows.len(), 2);
+        assert_eq!(matrix.rows[0].start, 10);
+        assert_eq!(matrix.rows[0].bids, vec![15, 0, 0, 0]);
+        assert_eq!(matrix.rows[0].asks, vec![0, 0, 2, 3]);
+        assert_eq!(matrix.rows[1].start, 20);
+        assert_eq!(matrix.rows[1].bids, vec![0, 5, 0, 0]);
+        assert!(heatmap.matrix(30, 10).rows.is_empty());
+    }
+
+    #[test]
+    fn it_bounds_the_samples_kept() {
+        let heatmap = Heatmap::default();
+        for timestamp in 0..MAX_HEATMAP_SAMPLES as u128 + 5 {
+            heatmap.record(sample(timestamp, (99, 1), (101, 1)));
+        }
+        let matrix = heatmap.matrix(0, 1);
+        assert_eq!(matrix.rows.len(), MAX_HEATMAP_SAMPLES);
+        assert_eq!(matrix.rows[0].start, 5);
+    }
+}
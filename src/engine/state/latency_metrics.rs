@@ -0,0 +1,149 @@
+use crate::core::models::Operation;
+use hdrhistogram::Histogram;
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// The highest latency tracked, anything slower is recorded as this value.
+const MAX_TRACKED_NANOS: u64 = 60_000_000_000;
+const SIGNIFICANT_DIGITS: u8 = 3;
+
+/// The kinds of operation matching latency is tracked for.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OperationKind {
+    Limit,
+    Market,
+    Modify,
+    Cancel,
+    Reduce,
+    Quote,
+    Call,
+    Uncross,
+}
+
+impl OperationKind {
+    const ALL: [OperationKind; 8] = [
+        OperationKind::Limit,
+        OperationKind::Market,
+        OperationKind::Modify,
+        OperationKind::Cancel,
+        OperationKind::Reduce,
+        OperationKind::Quote,
+        OperationKind::Call,
+        OperationKind::Uncross,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            OperationKind::Limit => "limit",
+            OperationKind::Market => "market",
+            OperationKind::Modify => "modify",
+            OperationKind::Cancel => "cancel",
+            OperationKind::Reduce => "reduce",
+            OperationKind::Quote => "quote",
+            OperationKind::Call => "call",
+            OperationKind::Uncross => "uncross",
+        }
+    }
+}
+
+impl From<&Operation> for OperationKind {
+    fn from(operation: &Operation) -> Self {
+        match operation {
+            Operation::Limit(_) => OperationKind::Limit,
+            Operation::Market(_) => OperationKind::Market,
+            Operation::Modify(_) => OperationKind::Modify,
+            Operation::Cancel(_) => OperationKind::Cancel,
+            Operation::Reduce { .. } => OperationKind::Reduce,
+            Operation::Quote { .. } => OperationKind::Quote,
+            Operation::Call => OperationKind::Call,
+            Operation::Uncross => OperationKind::Uncross,
+        }
+    }
+}
+
+/// Summary of the matching latency of a single operation kind.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LatencySummary {
+    pub operation: OperationKind,
+    pub count: u64,
+    pub p50: Duration,
+    pub p90: Duration,
+    pub p99: Duration,
+    pub p999: Duration,
+    pub max: Duration,
+}
+
+/// HDR histograms of the time the orderbook takes to execute each kind of operation.
+pub struct LatencyMetrics {
+    histograms: Mutex<Vec<Histogram<u64>>>,
+}
+
+impl Default for LatencyMetrics {
+    fn default() -> Self {
+        let histograms = OperationKind::ALL
+            .iter()
+            .map(|_| {
+                Histogram::new_with_bounds(1, MAX_TRACKED_NANOS, SIGNIFICANT_DIGITS)
+                    .expect("latency histogram bounds are valid")
+            })
+            .collect();
+        Self {
+            histograms: Mutex::new(histograms),
+        }
+    }
+}
+
+impl LatencyMetrics {
+    /// Records the latencies measured while executing a batch, taking the lock only once.
+    pub fn record_batch(&self, latencies: &[(OperationKind, Duration)]) {
+        let mut histograms = self.histograms.lock().unwrap();
+        for (operation, latency) in latencies {
+            let nanos = (latency.as_nanos() as u64).clamp(1, MAX_TRACKED_NANOS);
+            histograms[*operation as usize].saturating_record(nanos);
+        }
+    }
+
+    /// Returns a summary for every operation kind that has been recorded at least once.
+    pub fn summaries(&self) -> Vec<LatencySummary> {
+        let histograms = self.histograms.lock().unwrap();
+        OperationKind::ALL
+            .iter()
+            .zip(histograms.iter())
+            .filter(|(_, histogram)| !histogram.is_empty())
+            .map(|(operation, histogram)| LatencySummary {
+                operation: *operation,
+                count: histogram.len(),
+                p50: Duration::from_nanos(histogram.value_at_quantile(0.5)),
+                p90: Duration::from_nanos(histogram.value_at_quantile(0.9)),
+                p99: Duration::from_nanos(histogram.value_at_quantile(0.99)),
+                p999: Duration::from_nanos(histogram.value_at_quantile(0.999)),
+                max: Duration::from_nanos(histogram.max()),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::engine::state::latency_metrics::{LatencyMetrics, OperationKind};
+    use std::time::Duration;
+
+    #[test]
+    fn it_summarizes_latencies_per_operation_kind() {
+        let metrics = LatencyMetrics::default();
+        let latencies: Vec<_> = (1..=100)
+            .map(|micros| (OperationKind::Limit, Duration::from_micros(micros)))
+            .chain([(OperationKind::Cancel, Duration::from_micros(7))])
+            .collect();
+        metrics.record_batch(&latencies);
+        let summaries = metrics.summaries();
+        assert_eq!(summaries.len(), 2);
+        let limit = summaries[0];
+        assert_eq!(limit.operation, OperationKind::Limit);
+        assert_eq!(limit.count, 100);
+        assert!(limit.p50.abs_diff(Duration::from_micros(50)) < Duration::from_micros(1));
+        assert!(limit.max.abs_diff(Duration::from_micros(100)) < Duration::from_micros(1));
+        assert_eq!(summaries[1].operation, OperationKind::Cancel);
+        assert_eq!(summaries[1].count, 1);
+    }
+}
@@ -0,0 +1,128 @@
+use crate::engine::state::server_state::Symbols;
+use crate::engine::state::symbol_state::SymbolState;
+use crate::engine::state::ticker::TickerStats;
+use std::sync::atomic::Ordering;
+
+/// The market of a symbol at a glance, read from the latest snapshot of its book and its ticker.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SymbolSummary {
+    pub symbol: String,
+    pub best_bid: Option<u64>,
+    pub best_ask: Option<u64>,
+    /// Trade statistics over the last 24 hours.
+    pub ticker: TickerStats,
+    /// Operations the orderbook of the symbol has executed.
+    pub sequence: u64,
+}
+
+impl SymbolSummary {
+    /// Summarizes the symbol at `now` in nanoseconds since the unix epoch.
+    pub fn of(symbol_state: &SymbolState, now: u128) -> Self {
+        let secondary = symbol_state.orderbook_manager.get_secondary();
+        let (bid, ask) = secondary.best_bid_offer();
+        SymbolSummary {
+            symbol: symbol_state.symbol.clone(),
+            best_bid: bid.map(|level| level.price),
+            best_ask: ask.map(|level| level.price),
+            ticker: symbol_state.ticker.stats(now),
+            sequence: symbol_state.operation_count.load(Ordering::Relaxed),
+        }
+    }
+
+    /// The best ask less the best bid, `None` unless both sides of the book have orders.
+    pub fn spread(&self) -> Option<u64> {
+        Some(self.best_ask?.saturating_sub(self.best_bid?))
+    }
+}
+
+/// Summarizes every symbol at `now`, ordered by symbol.
+pub fn summarize(symbols: &Symbols, now: u128) -> Vec<SymbolSummary> {
+    let mut summaries: Vec<SymbolSummary> = symbols
+        .read()
+        .unwrap()
+        .values()
+        .map(|symbol_state| SymbolSummary::of(symbol_state, now))
+        .collect();
+    summaries.sort_by(|a, b| a.symbol.cmp(&b.symbol));
+    summaries
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::models::{FillMetaData, LimitOrder, MatchingPolicy, Operation, Side};
+    use crate::core::types::{OrderId, Price, Quantity};
+    use crate::engine::accounts::ledger::SymbolAssets;
+    use crate::engine::constants::property_loader::{ChannelCapacities, PersistenceProperties};
+    use crate::engine::risk::resting_limits::RestingLimits;
+    use crate::engine::state::market_summary::summarize;
+    use crate::engine::state::symbol_state::{SymbolSpecification, SymbolState};
+    use std::collections::HashMap;
+    use std::sync::{Arc, RwLock};
+    use std::time::Duration;
+
+    fn symbol_state(symbol: &str) -> SymbolState {
+        let persistence_properties = PersistenceProperties {
+            wal_enabled: false,
+            wal_directory: String::new(),
+            wal_checkpoint_interval: Duration::ZERO,
+            order_store_enabled: false,
+            order_store_path: String::new(),
+            outbox_path: String::new(),
+            audit_directory: None,
+            audit_max_file_size: 0,
+            recording_path: None,
+            traffic_recording_path: None,
+            replay_path: None,
+            replay_speed: 1.0,
+            snapshot_store: None,
+            crash_dump: None,
+        };
+        let specification = SymbolSpecification {
+            tick_size: 1,
+            lot_size: 1,
+            queue_capacity: 10,
+            store_capacity: 100,
+            matching_policy: MatchingPolicy::PriceTime,
+            channel_capacities: ChannelCapacities::default(),
+            assets: SymbolAssets::from_symbol(symbol),
+            resting_limits: RestingLimits::default(),
+        };
+        SymbolState::init(symbol, specification, "events", &persistence_properties).unwrap()
+    }
+
+    #[test]
+    fn it_summarizes_every_symbol() {
+        let eth = symbol_state("ETHUSD");
+        let primary = eth.orderbook_manager.get_primary();
+        for order in [
+            LimitOrder::new(OrderId(1), Price(95), Quantity(10), Side::Bid),
+            LimitOrder::new(OrderId(2), Price(101), Quantity(10), Side::Ask),
+        ] {
+            unsafe { (*primary).execute(Operation::Limit(order)) };
+        }
+        eth.orderbook_manager.snapshot();
+        let fill = FillMetaData {
+            order_id: 3,
+            matched_order_id: 4,
+            taker_side: Side::Bid,
+            price: 100,
+            quantity: 2,
+        };
+        eth.ticker.record(&[fill], 10);
+        let symbols = Arc::new(RwLock::new(HashMap::from([
+            ("ETHUSD".to_string(), Arc::new(eth)),
+            ("BTCUSD".to_string(), Arc::new(symbol_state("BTCUSD"))),
+        ])));
+
+        let summaries = summarize(&symbols, 20);
+        assert_eq!(summaries.len(), 2);
+        assert_eq!(summaries[0].symbol, "BTCUSD");
+        assert_eq!(summaries[0].spread(), None);
+        assert_eq!(summaries[0].ticker.trade_count, 0);
+
+        let eth = &summaries[1];
+        assert_eq!((eth.best_bid, eth.best_ask), (Some(95), Some(101)));
+        assert_eq!(eth.spread(), Some(6));
+        assert_eq!((eth.ticker.last_price, eth.ticker.volume), (100, 2));
+    }
+}
@@ -1 +1,23 @@
+pub mod average_price;
+pub mod book_feed;
+pub mod candles;
+pub mod channel_metrics;
+pub mod delayed_data;
+pub mod delivery_metrics;
+#[cfg(feature = "chaos")]
+pub mod faults;
+pub mod health;
+pub mod heatmap;
+pub mod latency_metrics;
+pub mod market_summary;
+pub mod order_history;
+pub mod overload;
+pub mod quotes;
+pub mod recent_operations;
+pub mod replication;
+pub mod runtime_settings;
 pub mod server_state;
+pub mod session;
+pub mod symbol_state;
+pub mod ticker;
+pub mod volume_profile;
@@ -0,0 +1,269 @@
+use crate::core::models::{ExecutionResult, FillResult, LimitOrder, ModifyResult, Operation, Side};
+use crate::core::orderbook::OrderBook;
+use serde::Serialize;
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+
+/// Revisions kept per order, older ones are dropped while the version keeps counting.
+pub const MAX_REVISIONS: usize = 32;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub enum RevisionKind {
+    /// The order started resting with the price and quantity of the revision.
+    Created,
+    /// The order was modified to the price and quantity of the revision.
+    Modified,
+    /// The order was reduced in place to the quantity of the revision.
+    Reduced,
+    /// The quantity of the revision was filled at its price.
+    Filled,
+}
+
+/// A single change in the life of an order.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct Revision {
+    pub kind: RevisionKind,
+    pub price: u64,
+    pub quantity: u64,
+    pub timestamp: u128,
+}
+
+/// The history of a resting order.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct OrderRevisions {
+    pub side: Side,
+    /// The quantity the order was placed with.
+    pub original_quantity: u64,
+    /// The current price of the order.
+    pub price: u64,
+    /// The quantity of the order left to fill.
+    pub quantity: u64,
+    /// The number of revisions the order went through, including the ones no longer kept.
+    pub version: u64,
+    /// The latest revisions, oldest first.
+    pub revisions: VecDeque<Revision>,
+}
+
+impl OrderRevisions {
+    /// The history of an order whose revisions are not known, such as one recovered on startup.
+    pub fn unknown(order: &LimitOrder) -> Self {
+        OrderRevisions {
+            side: order.side,
+            original_quantity: order.quantity,
+            price: order.price,
+            quantity: order.quantity,
+            version: 0,
+            revisions: VecDeque::new(),
+        }
+    }
+
+    fn push(&mut self, kind: RevisionKind, price: u64, quantity: u64, timestamp: u128) {
+        if self.revisions.len() == MAX_REVISIONS {
+            self.revisions.pop_front();
+        }
+        self.revisions.push_back(Revision {
+            kind,
+            price,
+            quantity,
+            timestamp,
+        });
+        self.version += 1;
+    }
+}
+
+/// The revisions of every order resting in the book of a symbol, kept by its executor so that
+/// the status of an order and its cancel event can tell how it came to be.
+#[derive(Default)]
+pub struct OrderHistory {
+    orders: Mutex<HashMap<u128, OrderRevisions>>,
+}
+
+impl OrderHistory {
+    /// Records the revisions an executed operation made to its own order and to the orders it
+    /// matched, and forgets the orders that no longer rest in the book.
+    ///
+    /// # Returns
+    ///
+    /// * The history of the order the operation cancelled, if it did.
+    pub fn record(
+        &self,
+        operation: &Operation,
+        result: &ExecutionResult,
+        orderbook: &OrderBook,
+        timestamp: u128,
+    ) -> Option<OrderRevisions> {
+        let mut orders = self.orders.lock().unwrap();
+        let fill_result = match result {
+            ExecutionResult::Cancelled(id) => return orders.remove(id),
+            ExecutionResult::Failed(_) => return None,
+            ExecutionResult::Reduced(order) => {
+                if let Some(revisions) = orders.get_mut(&order.id) {
+                    revisions.push(
+                        RevisionKind::Reduced,
+                        order.price,
+                        order.quantity,
+                        timestamp,
+                    );
+                    revisions.quantity = order.quantity;
+                }
+                return None;
+            }
+            ExecutionResult::Executed(fill_result) => Some(fill_result),
+            ExecutionResult::Modified(ModifyResult::Created(fill_result)) => Some(fill_result),
+            ExecutionResult::Modified(_) => None,
+            ExecutionResult::Quoted(_)
+            | ExecutionResult::Called
+            | ExecutionResult::Uncrossed(_) => None,
+        };
+        let mut rested: Vec<&LimitOrder> = match (fill_result, result) {
+            (Some(FillResult::Created(order) | FillResult::PartiallyFilled(order, _)), _) => {
+                vec![order]
+            }
+            (_, ExecutionResult::Quoted(quote)) => quote.bid.iter().chain(&quote.ask).collect(),
+            _ => Vec::new(),
+        };
+        let id = operation.order_id();
+        let placed = match operation {
+            Operation::Modify(order) => {
+                if let Some(revisions) = orders.get_mut(&id) {
+                    revisions.push(
+                        RevisionKind::Modified,
+                        order.price,
+                        order.quantity,
+                        timestamp,
+                    );
+                }
+                // a modified order that rests again keeps its history rather than being created anew
+                rested.clear();
+                vec![id]
+            }
+            Operation::Limit(order) => {
+                orders.insert(id, OrderRevisions::unknown(order));
+                vec![id]
+            }
+            Operation::Market(order) => {
+                orders.insert(id, OrderRevisions::unknown(&order.to_limit(0)));
+                vec![id]
+            }
+            // the orders of a quote start a new history, whatever rested under their ids before
+            Operation::Quote { bid, ask } => {
+                for order in [bid, ask] {
+                    orders.remove(&order.id);
+                    if order.quantity > 0 {
+                        orders.insert(order.id, OrderRevisions::unknown(order));
+                    }
+                }
+                vec![bid.id, ask.id]
+            }
+            Operation::Cancel(_) | Operation::Reduce { .. } => vec![id],
+            // the bids of an uncross are the orders of its fills
+            Operation::Uncross => result.fills().iter().map(|f| f.order_id).collect(),
+            Operation::Call => Vec::new(),
+        };
+        for fill in result.fills() {
+            for filled in [fill.order_id, fill.matched_order_id] {
+                if let Some(revisions) = orders.get_mut(&filled) {
+                    revisions.push(RevisionKind::Filled, fill.price, fill.quantity, timestamp);
+                }
+            }
+        }
+        for order in rested {
+            if let Some(revisions) = orders.get_mut(&order.id) {
+                revisions.push(
+                    RevisionKind::Created,
+                    order.price,
+                    order.quantity,
+                    timestamp,
+                );
+            }
+        }
+        let touched = placed
+            .into_iter()
+            .chain(result.fills().iter().map(|f| f.matched_order_id));
+        for touched in touched {
+            match orderbook.get_order(touched) {
+                Some(order) => {
+                    if let Some(revisions) = orders.get_mut(&touched) {
+                        revisions.price = order.price;
+                        revisions.quantity = order.quantity;
+                    }
+                }
+                None => {
+                    orders.remove(&touched);
+                }
+            }
+        }
+        None
+    }
+
+    /// Returns the history of a resting order, `None` for orders that are not resting or that
+    /// were recovered on startup, whose history is lost.
+    pub fn get(&self, id: u128) -> Option<OrderRevisions> {
+        self.orders.lock().unwrap().get(&id).cloned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::models::{LimitOrder, MarketOrder, Operation, Side};
+    use crate::core::orderbook::OrderBook;
+    use crate::core::types::{OrderId, Price, Quantity};
+    use crate::engine::state::order_history::{OrderHistory, RevisionKind, MAX_REVISIONS};
+
+    fn execute(history: &OrderHistory, book: &mut OrderBook, operation: Operation, at: u128) {
+        let result = book.execute(operation);
+        assert!(history.record(&operation, &result, book, at).is_none());
+    }
+
+    #[test]
+    fn it_tracks_the_revisions_of_resting_orders() {
+        let history = OrderHistory::default();
+        let mut book = OrderBook::default();
+        let bid = LimitOrder::new(OrderId(1), Price(100), Quantity(50), Side::Bid);
+        execute(&history, &mut book, Operation::Limit(bid), 1);
+        let modified = LimitOrder::new(OrderId(1), Price(100), Quantity(40), Side::Bid);
+        execute(&history, &mut book, Operation::Modify(modified), 2);
+        let ask = MarketOrder::new(OrderId(2), Quantity(15), Side::Ask);
+        execute(&history, &mut book, Operation::Market(ask), 3);
+
+        let revisions = history.get(1).unwrap();
+        assert_eq!(revisions.version, 3);
+        assert_eq!(revisions.original_quantity, 50);
+        assert_eq!((revisions.price, revisions.quantity), (100, 25));
+        let kinds: Vec<RevisionKind> = revisions.revisions.iter().map(|r| r.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                RevisionKind::Created,
+                RevisionKind::Modified,
+                RevisionKind::Filled
+            ]
+        );
+        assert_eq!(revisions.revisions[2].quantity, 15);
+        assert_eq!(revisions.revisions[2].timestamp, 3);
+        // the market order was filled completely and never rested
+        assert!(history.get(2).is_none());
+
+        let result = book.execute(Operation::Cancel(OrderId(1)));
+        let cancelled = history.record(&Operation::Cancel(OrderId(1)), &result, &book, 4);
+        assert_eq!(cancelled, Some(revisions));
+        assert!(history.get(1).is_none());
+    }
+
+    #[test]
+    fn it_bounds_the_revisions_kept_per_order() {
+        let history = OrderHistory::default();
+        let mut book = OrderBook::default();
+        let ask = LimitOrder::new(OrderId(1), Price(100), Quantity(1_000), Side::Ask);
+        execute(&history, &mut book, Operation::Limit(ask), 0);
+        for id in 2..=MAX_REVISIONS as u128 + 10 {
+            let bid = MarketOrder::new(OrderId(id), Quantity(1), Side::Bid);
+            execute(&history, &mut book, Operation::Market(bid), id);
+        }
+        let revisions = history.get(1).unwrap();
+        assert_eq!(revisions.version, MAX_REVISIONS as u64 + 10);
+        assert_eq!(revisions.revisions.len(), MAX_REVISIONS);
+        assert_eq!(revisions.revisions[0].kind, RevisionKind::Filled);
+        assert_eq!(revisions.quantity, 1_000 - MAX_REVISIONS as u64 - 9);
+    }
+}
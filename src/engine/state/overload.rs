@@ -0,0 +1,163 @@
+use crate::core::models::{Operation, Side};
+use crate::core::orderbook::OrderBook;
+use crate::engine::constants::property_loader::OverloadSettings;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::time::Duration;
+
+/// How loaded the executor of a symbol was over the last sampling interval.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Load {
+    /// How full the order channel is in percent of its capacity.
+    pub queue_saturation: u64,
+    /// Average matching latency of the operations executed since the last sample, zero when
+    /// nothing was executed.
+    pub latency: Duration,
+}
+
+/// Whether a symbol sheds low priority order flow, along with the matching latency its executor
+/// recorded since the load was last sampled.
+#[derive(Debug, Default)]
+pub struct Overload {
+    shedding: AtomicBool,
+    total_latency_nanos: AtomicU64,
+    operations: AtomicU64,
+}
+
+impl Overload {
+    pub fn is_shedding(&self) -> bool {
+        self.shedding.load(Ordering::SeqCst)
+    }
+
+    /// Records the matching latency of the operations of an executed batch.
+    pub fn record_latency(&self, latencies: impl IntoIterator<Item = Duration>) {
+        let (total, count) = latencies
+            .into_iter()
+            .fold((0, 0), |(total, count), latency| {
+                (total + latency.as_nanos() as u64, count + 1)
+            });
+        self.total_latency_nanos.fetch_add(total, Ordering::Relaxed);
+        self.operations.fetch_add(count, Ordering::Relaxed);
+    }
+
+    /// Samples the load, starting over the recorded latency.
+    pub fn sample(&self, queue_saturation: u64) -> Load {
+        let total = self.total_latency_nanos.swap(0, Ordering::Relaxed);
+        let count = self.operations.swap(0, Ordering::Relaxed);
+        Load {
+            queue_saturation,
+            latency: Duration::from_nanos(total.checked_div(count).unwrap_or(0)),
+        }
+    }
+
+    /// Starts shedding once either measure of the load crosses its shed threshold and stops once
+    /// both are back under their recover threshold, so that a load hovering around a threshold
+    /// does not flip the state with every sample.
+    ///
+    /// # Returns
+    ///
+    /// * Whether the symbol sheds from now on, if that changed.
+    pub fn update(&self, load: Load, settings: &OverloadSettings) -> Option<bool> {
+        let shedding = self.is_shedding();
+        let overloaded = load.queue_saturation >= settings.shed_queue_saturation
+            || load.latency >= settings.shed_latency;
+        let recovered = load.queue_saturation <= settings.recover_queue_saturation
+            && load.latency <= settings.recover_latency;
+        let next = match shedding {
+            false => overloaded,
+            true => !recovered,
+        };
+        (next != shedding).then(|| {
+            self.shedding.store(next, Ordering::SeqCst);
+            next
+        })
+    }
+}
+
+/// Whether the operation is shed while its symbol is overloaded, which are modifies, quotes and
+/// limit orders that would rest in the book rather than trade, as every limit order does during a
+/// call. Cancels, reductions, quotes that withdraw both sides and aggressive orders are always
+/// accepted, as they reduce the work left for the executor.
+pub fn is_low_priority(operation: &Operation, orderbook: &OrderBook) -> bool {
+    match operation {
+        Operation::Modify(_) => true,
+        Operation::Quote { bid, ask } => bid.quantity > 0 || ask.quantity > 0,
+        Operation::Limit(_) if orderbook.in_call() => true,
+        Operation::Limit(order) => match order.side {
+            Side::Bid => orderbook
+                .get_min_ask()
+                .is_none_or(|min_ask| order.price < min_ask),
+            Side::Ask => orderbook
+                .get_max_bid()
+                .is_none_or(|max_bid| order.price > max_bid),
+        },
+        Operation::Market(_)
+        | Operation::Cancel(_)
+        | Operation::Reduce { .. }
+        | Operation::Call
+        | Operation::Uncross => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::models::{LimitOrder, MarketOrder, Operation, Side};
+    use crate::core::orderbook::OrderBook;
+    use crate::core::types::{OrderId, Price, Quantity};
+    use crate::engine::constants::property_loader::OverloadSettings;
+    use crate::engine::state::overload::{is_low_priority, Overload};
+    use std::time::Duration;
+
+    #[test]
+    fn it_sheds_with_hysteresis() {
+        let settings = OverloadSettings {
+            interval: Duration::from_millis(100),
+            shed_queue_saturation: 80,
+            recover_queue_saturation: 50,
+            shed_latency: Duration::from_micros(500),
+            recover_latency: Duration::from_micros(200),
+        };
+        let overload = Overload::default();
+        overload.record_latency([Duration::from_micros(400), Duration::from_micros(800)]);
+        let load = overload.sample(10);
+        assert_eq!(load.latency, Duration::from_micros(600));
+        assert_eq!(overload.update(load, &settings), Some(true));
+        assert!(overload.is_shedding());
+
+        assert_eq!(overload.sample(60).latency, Duration::ZERO);
+        assert_eq!(overload.update(overload.sample(60), &settings), None);
+        overload.record_latency([Duration::from_micros(300)]);
+        assert_eq!(overload.update(overload.sample(40), &settings), None);
+        assert_eq!(overload.update(overload.sample(40), &settings), Some(false));
+        assert_eq!(overload.update(overload.sample(79), &settings), None);
+        assert_eq!(overload.update(overload.sample(80), &settings), Some(true));
+    }
+
+    #[test]
+    fn it_only_sheds_modifies_and_passive_orders() {
+        let mut orderbook = OrderBook::default();
+        orderbook.execute(Operation::Limit(LimitOrder::new(
+            OrderId(1),
+            Price(100),
+            Quantity(10),
+            Side::Ask,
+        )));
+        let limit = |price, side| {
+            Operation::Limit(LimitOrder::new(OrderId(2), Price(price), Quantity(1), side))
+        };
+        assert!(is_low_priority(&limit(99, Side::Bid), &orderbook));
+        assert!(!is_low_priority(&limit(100, Side::Bid), &orderbook));
+        assert!(is_low_priority(&limit(90, Side::Ask), &orderbook));
+        assert!(is_low_priority(
+            &Operation::Modify(LimitOrder::new(
+                OrderId(1),
+                Price(101),
+                Quantity(10),
+                Side::Ask
+            )),
+            &orderbook
+        ));
+        let market = MarketOrder::new(OrderId(3), Quantity(1), Side::Bid);
+        assert!(!is_low_priority(&Operation::Market(market), &orderbook));
+        assert!(!is_low_priority(&Operation::Cancel(OrderId(1)), &orderbook));
+    }
+}
@@ -0,0 +1,139 @@
+use crate::core::errors::OrderBookError;
+use crate::core::models::Side;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use thiserror::Error;
+
+/// A price the engine committed to for a market order of a given side and quantity until the
+/// quote expires.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FirmQuote {
+    pub side: Side,
+    pub quantity: u64,
+    /// The average price of the quote.
+    pub price: u64,
+    /// What filling the whole quantity costs or yields at the time of the quote.
+    pub notional: u64,
+    /// Nanoseconds since the unix epoch after which the quote can no longer be executed.
+    pub expires_at: u128,
+}
+
+impl FirmQuote {
+    /// Whether a fill of `filled` for `notional` is at least as good for the taker as the quote.
+    pub fn honoured_by(&self, filled: u64, notional: u64) -> bool {
+        filled == self.quantity
+            && match self.side {
+                Side::Bid => notional <= self.notional,
+                Side::Ask => notional >= self.notional,
+            }
+    }
+}
+
+/// The reason a quote cannot be executed.
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum QuoteError {
+    #[error("unknown quote")]
+    Unknown,
+    #[error("quote expired")]
+    Expired,
+    /// The book moved so that the quantity of the quote no longer fills at its price.
+    #[error("liquidity no longer honours the quote")]
+    Liquidity,
+    /// The market order of the quote was checked but dropped before it reached the book.
+    #[error("quote was dropped before it executed")]
+    Dropped,
+    /// The market order of the quote was rejected or failed in the book.
+    #[error(transparent)]
+    Failed(OrderBookError),
+}
+
+/// The firm quotes handed out for a symbol, each of which can be executed once.
+#[derive(Debug, Default)]
+pub struct Quotes {
+    quotes: Mutex<HashMap<u128, FirmQuote>>,
+}
+
+impl Quotes {
+    /// Keeps the quote under a new id, forgetting the quotes that expired by `now`.
+    pub fn issue(&self, quote: FirmQuote, now: u128) -> u128 {
+        let quote_id = uuid::Uuid::new_v4().as_u128();
+        let mut quotes = self.quotes.lock().unwrap();
+        quotes.retain(|_, quote| quote.expires_at > now);
+        quotes.insert(quote_id, quote);
+        quote_id
+    }
+
+    /// Looks the quote up without taking it out, so that it is kept if the order is not admitted.
+    pub fn get(&self, quote_id: u128, now: u128) -> Result<FirmQuote, QuoteError> {
+        let quote = *self
+            .quotes
+            .lock()
+            .unwrap()
+            .get(&quote_id)
+            .ok_or(QuoteError::Unknown)?;
+        if quote.expires_at <= now {
+            return Err(QuoteError::Expired);
+        }
+        Ok(quote)
+    }
+
+    /// Takes the quote out so that it cannot be executed again.
+    pub fn redeem(&self, quote_id: u128, now: u128) -> Result<FirmQuote, QuoteError> {
+        let quote = self
+            .quotes
+            .lock()
+            .unwrap()
+            .remove(&quote_id)
+            .ok_or(QuoteError::Unknown)?;
+        if quote.expires_at <= now {
+            return Err(QuoteError::Expired);
+        }
+        Ok(quote)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::models::Side;
+    use crate::engine::state::quotes::{FirmQuote, QuoteError, Quotes};
+
+    fn quote(side: Side, expires_at: u128) -> FirmQuote {
+        FirmQuote {
+            side,
+            quantity: 10,
+            price: 100,
+            notional: 1_000,
+            expires_at,
+        }
+    }
+
+    #[test]
+    fn it_redeems_a_quote_once_before_it_expires() {
+        let quotes = Quotes::default();
+        let quote_id = quotes.issue(quote(Side::Bid, 100), 0);
+        assert_eq!(quotes.get(quote_id, 50), Ok(quote(Side::Bid, 100)));
+        assert_eq!(quotes.redeem(quote_id, 50), Ok(quote(Side::Bid, 100)));
+        assert_eq!(quotes.redeem(quote_id, 50), Err(QuoteError::Unknown));
+        assert_eq!(quotes.get(quote_id, 50), Err(QuoteError::Unknown));
+
+        let quote_id = quotes.issue(quote(Side::Bid, 100), 0);
+        assert_eq!(quotes.redeem(quote_id, 100), Err(QuoteError::Expired));
+
+        let expired = quotes.issue(quote(Side::Bid, 100), 0);
+        quotes.issue(quote(Side::Bid, 300), 200);
+        assert_eq!(quotes.redeem(expired, 0), Err(QuoteError::Unknown));
+    }
+
+    #[test]
+    fn it_only_honours_complete_fills_at_the_quoted_price_or_better() {
+        let bid = quote(Side::Bid, 100);
+        assert!(bid.honoured_by(10, 1_000));
+        assert!(bid.honoured_by(10, 990));
+        assert!(!bid.honoured_by(10, 1_010));
+        assert!(!bid.honoured_by(9, 900));
+
+        let ask = quote(Side::Ask, 100);
+        assert!(ask.honoured_by(10, 1_010));
+        assert!(!ask.honoured_by(10, 990));
+    }
+}
@@ -0,0 +1,77 @@
+use crate::core::models::Operation;
+use std::collections::VecDeque;
+use std::sync::{Mutex, TryLockError};
+
+/// The operations the executor of a symbol processed last along with the time they executed at,
+/// kept so that a crash dump can tell what the engine was doing when it died.
+#[derive(Debug)]
+pub struct RecentOperations {
+    capacity: usize,
+    operations: Mutex<VecDeque<(u128, Operation)>>,
+}
+
+impl RecentOperations {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            operations: Mutex::new(VecDeque::with_capacity(capacity)),
+        }
+    }
+
+    /// Records the operations of a batch executed at the passed time, forgetting the oldest ones
+    /// beyond the capacity.
+    pub fn record(&self, now: u128, batch: &[Operation]) {
+        let mut operations = self.operations.lock().unwrap();
+        let skipped = batch.len().saturating_sub(self.capacity);
+        for operation in &batch[skipped..] {
+            if operations.len() == self.capacity {
+                operations.pop_front();
+            }
+            operations.push_back((now, *operation));
+        }
+    }
+
+    /// The recorded operations, oldest first. Does not wait for the executor, so that it can be
+    /// called while panicking, and returns nothing if the executor is recording a batch.
+    pub fn latest(&self) -> Vec<(u128, Operation)> {
+        match self.operations.try_lock() {
+            Ok(operations) => operations.iter().copied().collect(),
+            Err(TryLockError::Poisoned(poisoned)) => {
+                poisoned.into_inner().iter().copied().collect()
+            }
+            Err(TryLockError::WouldBlock) => Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::models::Operation;
+    use crate::core::types::OrderId;
+    use crate::engine::state::recent_operations::RecentOperations;
+
+    fn order_ids(recent_operations: &RecentOperations) -> Vec<(u128, u128)> {
+        recent_operations
+            .latest()
+            .iter()
+            .map(|(now, operation)| (*now, operation.order_id()))
+            .collect()
+    }
+
+    #[test]
+    fn it_keeps_the_latest_operations() {
+        let recent_operations = RecentOperations::new(3);
+        recent_operations.record(
+            1,
+            &[Operation::Cancel(OrderId(1)), Operation::Cancel(OrderId(2))],
+        );
+        recent_operations.record(
+            2,
+            &[Operation::Cancel(OrderId(3)), Operation::Cancel(OrderId(4))],
+        );
+        assert_eq!(order_ids(&recent_operations), vec![(1, 2), (2, 3), (2, 4)]);
+        let batch: Vec<_> = (5..10).map(OrderId).map(Operation::Cancel).collect();
+        recent_operations.record(3, &batch);
+        assert_eq!(order_ids(&recent_operations), vec![(3, 7), (3, 8), (3, 9)]);
+    }
+}
@@ -0,0 +1,464 @@
+use crate::core::models::{LimitOrder, Operation};
+use crate::engine::constants::property_loader::ReplicationSettings;
+use crate::engine::state::channel_metrics::{ChannelMetrics, HighWatermark};
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, AtomicU8, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+use thiserror::Error;
+use tokio::sync::{broadcast, Notify};
+use tracing::{info, warn};
+
+#[derive(Debug, Error)]
+pub enum ReplicationError {
+    #[error("failed to persist replication state: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("replication state is corrupted: {0}")]
+    Corrupted(String),
+    #[error("fencing token {token} is older than the current token {current}")]
+    StaleToken { token: u64, current: u64 },
+    #[error("only a replica can be promoted, this server is {0}")]
+    NotReplica(&'static str),
+}
+
+/// The part a server plays in an active/standby pair.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Role {
+    /// Accepts order flow and streams the batches it executes to its replicas.
+    Primary,
+    /// Applies the batches of its primary and rejects order flow until promoted.
+    Replica,
+    /// A former primary that learned of a newer one, which rejects order flow for good unless
+    /// leader election makes it a replica again.
+    Fenced,
+}
+
+impl Role {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Role::Primary => "primary",
+            Role::Replica => "replica",
+            Role::Fenced => "fenced",
+        }
+    }
+
+    fn parse(name: &str) -> Option<Role> {
+        match name {
+            "primary" => Some(Role::Primary),
+            "replica" => Some(Role::Replica),
+            "fenced" => Some(Role::Fenced),
+            _ => None,
+        }
+    }
+}
+
+impl From<u8> for Role {
+    fn from(value: u8) -> Self {
+        match value {
+            1 => Role::Replica,
+            2 => Role::Fenced,
+            _ => Role::Primary,
+        }
+    }
+}
+
+/// The role of the server along with its fencing token, which increases with every promotion.
+/// A primary that sees a newer token than its own fences itself, so that of two servers that
+/// both think they are primary only the one promoted last keeps accepting orders. Both are
+/// persisted, so a fenced primary stays fenced across restarts.
+pub struct Replication {
+    role: AtomicU8,
+    fencing_token: AtomicU64,
+    state_path: PathBuf,
+    /// URL of the gRPC server of the primary, only known to a replica.
+    primary_address: RwLock<Option<String>>,
+    /// Notified whenever the role or the primary changes.
+    pub role_changed: Notify,
+    /// Serializes changes to the role and token along with their persistence.
+    transition: Mutex<()>,
+}
+
+impl Replication {
+    /// Opens the persisted state. A server configured with a primary to follow starts as its
+    /// replica, any other server as primary unless it was fenced before. With leader election
+    /// every server starts as a replica until the election decides on the primary.
+    pub fn open(settings: &ReplicationSettings) -> Result<Self, ReplicationError> {
+        let state_path = PathBuf::from(&settings.state_path);
+        let (persisted_role, fencing_token) = read_state(&state_path)?.unzip();
+        let role = match (&settings.primary_address, persisted_role) {
+            _ if settings.election.is_some() => Role::Replica,
+            (Some(_), _) => Role::Replica,
+            (None, Some(Role::Fenced)) => Role::Fenced,
+            (None, _) => Role::Primary,
+        };
+        let fencing_token = fencing_token.unwrap_or(0);
+        let primary_address = settings
+            .primary_address
+            .clone()
+            .filter(|_| settings.election.is_none());
+        if role == Role::Fenced {
+            warn!(
+                "starting fenced by token {}, remove {:?} to start as primary again",
+                fencing_token, state_path
+            );
+        }
+        write_state(&state_path, role, fencing_token)?;
+        Ok(Self {
+            role: AtomicU8::new(role as u8),
+            fencing_token: AtomicU64::new(fencing_token),
+            state_path,
+            primary_address: RwLock::new(primary_address),
+            role_changed: Notify::new(),
+            transition: Mutex::new(()),
+        })
+    }
+
+    pub fn role(&self) -> Role {
+        Role::from(self.role.load(Ordering::SeqCst))
+    }
+
+    pub fn fencing_token(&self) -> u64 {
+        self.fencing_token.load(Ordering::SeqCst)
+    }
+
+    pub fn is_primary(&self) -> bool {
+        self.role() == Role::Primary
+    }
+
+    pub fn primary_address(&self) -> Option<String> {
+        self.primary_address.read().unwrap().clone()
+    }
+
+    /// Adopts the token of a newer primary, fencing this server if it is the primary. Fencing
+    /// again with the current token is accepted, so that a promotion can be retried.
+    pub fn fence(&self, token: u64) -> Result<(), ReplicationError> {
+        let _transition = self.transition.lock().unwrap();
+        let (role, current) = (self.role(), self.fencing_token());
+        if token < current || (token == current && role == Role::Primary) {
+            return Err(ReplicationError::StaleToken { token, current });
+        }
+        let fenced = match role {
+            Role::Primary => Role::Fenced,
+            role => role,
+        };
+        self.transition_to(fenced, token)?;
+        if role == Role::Primary {
+            warn!("fenced by token {}, no longer accepting orders", token);
+        }
+        Ok(())
+    }
+
+    /// Makes this replica the primary under the passed token, which has to be newer than any
+    /// token it has seen.
+    pub fn promote(&self, token: u64) -> Result<(), ReplicationError> {
+        let _transition = self.transition.lock().unwrap();
+        let (role, current) = (self.role(), self.fencing_token());
+        if role != Role::Replica {
+            return Err(ReplicationError::NotReplica(role.name()));
+        }
+        if token <= current {
+            return Err(ReplicationError::StaleToken { token, current });
+        }
+        self.transition_to(Role::Primary, token)?;
+        info!("promoted to primary with token {}", token);
+        Ok(())
+    }
+
+    /// Checks the token a replica received from its primary, adopting it when newer. An older
+    /// token means the primary was fenced since.
+    pub fn observe(&self, token: u64) -> Result<(), ReplicationError> {
+        let _transition = self.transition.lock().unwrap();
+        let current = self.fencing_token();
+        if token < current {
+            return Err(ReplicationError::StaleToken { token, current });
+        }
+        if token > current {
+            self.transition_to(self.role(), token)?;
+        }
+        Ok(())
+    }
+
+    /// Follows the primary at the address, which holds the passed token. Any other role turns
+    /// into a replica of it.
+    pub fn follow(&self, token: u64, address: &str) -> Result<(), ReplicationError> {
+        let _transition = self.transition.lock().unwrap();
+        let (role, current) = (self.role(), self.fencing_token());
+        if token < current {
+            return Err(ReplicationError::StaleToken { token, current });
+        }
+        let moved = self.primary_address().as_deref() != Some(address);
+        if role == Role::Replica && token == current && !moved {
+            return Ok(());
+        }
+        *self.primary_address.write().unwrap() = Some(address.to_string());
+        self.transition_to(Role::Replica, token)?;
+        if moved {
+            info!("following primary at {} with token {}", address, token);
+            self.role_changed.notify_waiters();
+        }
+        Ok(())
+    }
+
+    /// Turns this server into a replica that does not know its primary yet, such as a leader
+    /// whose lease ran out.
+    pub fn step_down(&self) -> Result<(), ReplicationError> {
+        let _transition = self.transition.lock().unwrap();
+        let role = self.role();
+        if role == Role::Replica {
+            return Ok(());
+        }
+        *self.primary_address.write().unwrap() = None;
+        self.transition_to(Role::Replica, self.fencing_token())?;
+        warn!(
+            "stepped down from {}, no longer accepting orders",
+            role.name()
+        );
+        Ok(())
+    }
+
+    fn transition_to(&self, role: Role, token: u64) -> Result<(), ReplicationError> {
+        write_state(&self.state_path, role, token)?;
+        self.fencing_token.store(token, Ordering::SeqCst);
+        if self.role.swap(role as u8, Ordering::SeqCst) != role as u8 {
+            self.role_changed.notify_waiters();
+        }
+        Ok(())
+    }
+}
+
+fn read_state(path: &Path) -> Result<Option<(Role, u64)>, ReplicationError> {
+    let contents = match fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e.into()),
+    };
+    let corrupted = || ReplicationError::Corrupted(contents.trim().to_string());
+    let (role, token) = contents.trim().split_once(' ').ok_or_else(corrupted)?;
+    let role = Role::parse(role).ok_or_else(corrupted)?;
+    let token = token.parse().map_err(|_| corrupted())?;
+    Ok(Some((role, token)))
+}
+
+/// Replaces the persisted state through a rename, so that a crash leaves either state intact.
+fn write_state(path: &Path, role: Role, token: u64) -> Result<(), ReplicationError> {
+    if let Some(directory) = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+    {
+        fs::create_dir_all(directory)?;
+    }
+    let temp_path = path.with_extension("tmp");
+    fs::write(&temp_path, format!("{} {}\n", role.name(), token))?;
+    fs::File::open(&temp_path)?.sync_all()?;
+    fs::rename(&temp_path, path)?;
+    Ok(())
+}
+
+/// The operations a batch accepted along with the clock they executed at.
+#[derive(Debug, Clone)]
+pub struct ReplicatedBatch {
+    /// Increases by one with every batch, so that a gap tells a replica it missed one.
+    pub sequence: u64,
+    pub now: u128,
+    pub operations: Vec<Operation>,
+}
+
+/// The resting orders of a symbol along with the batches executed after them.
+pub struct ReplicationSubscription {
+    /// Every resting order in priority order, bids before asks.
+    pub orders: Vec<LimitOrder>,
+    pub last_trade_price: u64,
+    /// Whether the book was in a call, in which case the orders may cross.
+    pub call: bool,
+    /// The sequence of the last batch reflected in the orders.
+    pub sequence: u64,
+    pub batches: broadcast::Receiver<Arc<ReplicatedBatch>>,
+}
+
+/// The batches a symbol executes, published by its executor once logged and before they are
+/// executed. Batches are only published while a replica is subscribed. Buffers the passed number
+/// of batches for every replica, a replica further behind has to start over from a new
+/// checkpoint.
+#[derive(Debug)]
+pub struct ReplicationFeed {
+    sender: broadcast::Sender<Arc<ReplicatedBatch>>,
+    sequence: AtomicU64,
+    capacity: usize,
+    high_watermark: HighWatermark,
+}
+
+impl ReplicationFeed {
+    pub fn new(capacity: usize) -> Self {
+        ReplicationFeed {
+            sender: broadcast::channel(capacity).0,
+            sequence: AtomicU64::new(0),
+            capacity,
+            high_watermark: HighWatermark::default(),
+        }
+    }
+
+    pub fn has_subscribers(&self) -> bool {
+        self.sender.receiver_count() > 0
+    }
+
+    /// Numbers the batch and sends it to every replica.
+    pub fn publish(&self, now: u128, operations: Vec<Operation>) {
+        let sequence = self.sequence.fetch_add(1, Ordering::Relaxed) + 1;
+        let _ = self.sender.send(Arc::new(ReplicatedBatch {
+            sequence,
+            now,
+            operations,
+        }));
+        self.high_watermark.record(self.sender.len());
+    }
+
+    /// The batches buffered for the replica furthest behind.
+    pub fn metrics(&self) -> ChannelMetrics {
+        ChannelMetrics {
+            channel: "replication_feed",
+            capacity: self.capacity,
+            depth: self.sender.len(),
+            high_watermark: self.high_watermark.get(),
+        }
+    }
+
+    /// Subscribes to the batches following the passed book state, which has to be taken by the
+    /// executor so that no batch is published in between.
+    pub fn subscribe(
+        &self,
+        orders: Vec<LimitOrder>,
+        last_trade_price: u64,
+        call: bool,
+    ) -> ReplicationSubscription {
+        ReplicationSubscription {
+            orders,
+            last_trade_price,
+            call,
+            sequence: self.sequence.load(Ordering::Relaxed),
+            batches: self.sender.subscribe(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::models::Operation;
+    use crate::core::types::OrderId;
+    use crate::engine::constants::property_loader::{ElectionSettings, ReplicationSettings};
+    use crate::engine::state::replication::{Replication, ReplicationError, ReplicationFeed, Role};
+    use std::time::Duration;
+
+    fn settings(state_path: &str, primary_address: Option<&str>) -> ReplicationSettings {
+        ReplicationSettings {
+            primary_address: primary_address.map(str::to_string),
+            state_path: state_path.to_string(),
+            retry_interval: Duration::from_secs(1),
+            election: None,
+        }
+    }
+
+    #[test]
+    fn it_fences_a_primary_promoted_over() {
+        let directory =
+            std::env::temp_dir().join(format!("gemmy-replication-{}", uuid::Uuid::new_v4()));
+        let state_path = directory.join("state");
+        let state_path = state_path.to_str().unwrap();
+
+        let replica = Replication::open(&settings(state_path, Some("http://[::1]:50051"))).unwrap();
+        assert_eq!(
+            (replica.role(), replica.fencing_token()),
+            (Role::Replica, 0)
+        );
+        replica.observe(3).unwrap();
+        assert!(matches!(
+            replica.observe(2),
+            Err(ReplicationError::StaleToken {
+                token: 2,
+                current: 3
+            })
+        ));
+        assert!(replica.promote(3).is_err());
+        replica.promote(4).unwrap();
+        assert!(replica.is_primary());
+        assert!(matches!(
+            replica.promote(5),
+            Err(ReplicationError::NotReplica("primary"))
+        ));
+
+        let primary = Replication::open(&settings(state_path, None)).unwrap();
+        assert_eq!(
+            (primary.role(), primary.fencing_token()),
+            (Role::Primary, 4)
+        );
+        assert!(primary.fence(4).is_err());
+        primary.fence(5).unwrap();
+        primary.fence(5).unwrap();
+        assert_eq!(primary.role(), Role::Fenced);
+
+        let restarted = Replication::open(&settings(state_path, None)).unwrap();
+        assert_eq!(
+            (restarted.role(), restarted.fencing_token()),
+            (Role::Fenced, 5)
+        );
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+
+    #[test]
+    fn it_follows_the_elected_primary() {
+        let directory =
+            std::env::temp_dir().join(format!("gemmy-replication-{}", uuid::Uuid::new_v4()));
+        let state_path = directory.join("state");
+        let mut settings = settings(state_path.to_str().unwrap(), Some("http://[::1]:50051"));
+        settings.election = Some(ElectionSettings {
+            endpoint: "http://localhost:2379".to_string(),
+            key: "gemmy/leader".to_string(),
+            lease_ttl: Duration::from_secs(10),
+            advertised_address: "http://[::1]:50052".to_string(),
+        });
+
+        let replication = Replication::open(&settings).unwrap();
+        assert_eq!(replication.role(), Role::Replica);
+        assert_eq!(replication.primary_address(), None);
+        replication.follow(7, "http://[::1]:50053").unwrap();
+        assert_eq!(
+            replication.primary_address().as_deref(),
+            Some("http://[::1]:50053")
+        );
+        assert!(replication.follow(6, "http://[::1]:50054").is_err());
+
+        replication.promote(9).unwrap();
+        replication.fence(11).unwrap();
+        assert_eq!(replication.role(), Role::Fenced);
+        replication.step_down().unwrap();
+        assert_eq!(
+            (replication.role(), replication.fencing_token()),
+            (Role::Replica, 11)
+        );
+        assert_eq!(replication.primary_address(), None);
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+
+    #[test]
+    fn it_numbers_the_batches_after_the_checkpoint() {
+        let feed = ReplicationFeed::new(16);
+        feed.publish(10, vec![Operation::Cancel(OrderId(1))]);
+        assert!(!feed.has_subscribers());
+
+        let mut subscription = feed.subscribe(Vec::new(), 100, false);
+        assert_eq!(subscription.sequence, 1);
+        feed.publish(
+            20,
+            vec![Operation::Cancel(OrderId(2)), Operation::Cancel(OrderId(3))],
+        );
+        feed.publish(30, vec![Operation::Cancel(OrderId(4))]);
+        assert_eq!(
+            (feed.metrics().depth, feed.metrics().high_watermark),
+            (2, 2)
+        );
+        let batch = subscription.batches.try_recv().unwrap();
+        assert_eq!((batch.sequence, batch.now), (2, 20));
+        assert_eq!(batch.operations.len(), 2);
+        assert_eq!(feed.metrics().depth, 1);
+    }
+}
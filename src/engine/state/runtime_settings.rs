@@ -0,0 +1,103 @@
+use crate::engine::constants::property_loader::{ServerProperties, TunableProperties};
+use std::error::Error;
+use std::path::PathBuf;
+use std::sync::RwLock;
+use tracing::info;
+
+/// The tunable properties currently in effect, which can be reloaded without restarting.
+/// Tasks read them on every iteration, so reloaded values apply from their next iteration on.
+pub struct RuntimeSettings {
+    config_path: Option<PathBuf>,
+    tunable_properties: RwLock<TunableProperties>,
+}
+
+impl RuntimeSettings {
+    pub fn new(config_path: Option<PathBuf>, tunable_properties: TunableProperties) -> Self {
+        Self {
+            config_path,
+            tunable_properties: RwLock::new(tunable_properties),
+        }
+    }
+
+    pub fn from_properties(server_properties: &ServerProperties) -> Self {
+        Self::new(
+            server_properties.config_path.clone(),
+            server_properties.tunable_properties,
+        )
+    }
+
+    pub fn current(&self) -> TunableProperties {
+        *self.tunable_properties.read().unwrap()
+    }
+
+    /// Re-reads `.env` and the config file, with the config file taking precedence as it does at
+    /// startup, and applies the tunable properties found in them. Values from the files override
+    /// the ones set in the environment of the process. The current settings are kept if the
+    /// files cannot be read or contain invalid values.
+    pub fn reload(&self) -> Result<TunableProperties, Box<dyn Error>> {
+        // env files never override variables that are already set
+        for variable in TunableProperties::VARIABLES {
+            std::env::remove_var(variable);
+        }
+        if let Some(config_path) = &self.config_path {
+            dotenv::from_path(config_path)?;
+        }
+        dotenv::dotenv().ok();
+        let tunable_properties = TunableProperties::load()?;
+        *self.tunable_properties.write().unwrap() = tunable_properties;
+        info!("successfully reloaded settings: {:?}", tunable_properties);
+        Ok(tunable_properties)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::engine::constants::property_loader::TunableProperties;
+    use crate::engine::state::runtime_settings::RuntimeSettings;
+    use std::fs;
+    use std::sync::RwLock;
+    use std::time::Duration;
+
+    #[test]
+    fn it_reloads_tunable_properties_from_the_config_file() {
+        let path = std::env::temp_dir().join(format!("gemmy-config-{}", uuid::Uuid::new_v4()));
+        let initial = TunableProperties {
+            order_exec_batch_size: 1,
+            order_exec_batch_timeout: Duration::from_millis(1),
+            orderbook_snapshot_interval: Duration::from_millis(1),
+            orderbook_snapshot_operations: 1,
+            metrics_report_interval: Duration::from_millis(1),
+            stream_interval: Duration::from_millis(1),
+            order_expiry_interval: Duration::from_millis(1),
+        };
+        let runtime_settings = RuntimeSettings {
+            config_path: Some(path.clone()),
+            tunable_properties: RwLock::new(initial),
+        };
+        fs::write(&path, "ORDER_EXEC_BATCH_SIZE=invalid\n").unwrap();
+        assert!(runtime_settings.reload().is_err());
+        assert_eq!(runtime_settings.current(), initial);
+
+        fs::write(
+            &path,
+            "ORDER_EXEC_BATCH_SIZE=42\n\
+             ORDER_EXEC_BATCH_TIMEOUT=5\n\
+             ORDERBOOK_SNAPSHOT_INTERVAL_MILLIS=500\n\
+             ORDERBOOK_SNAPSHOT_OPERATIONS=100\n\
+             METRICS_REPORT_INTERVAL_MILLIS=1000\n\
+             STREAM_INTERVAL_MILLIS=100\n\
+             ORDER_EXPIRY_INTERVAL_MILLIS=100\n",
+        )
+        .unwrap();
+        let reloaded = runtime_settings.reload().unwrap();
+        assert_eq!(runtime_settings.current(), reloaded);
+        assert_eq!(reloaded.order_exec_batch_size, 42);
+        assert_eq!(reloaded.order_exec_batch_timeout, Duration::from_millis(5));
+        assert_eq!(
+            reloaded.orderbook_snapshot_interval,
+            Duration::from_millis(500)
+        );
+        assert_eq!(reloaded.stream_interval, Duration::from_millis(100));
+        fs::remove_file(path).unwrap();
+    }
+}
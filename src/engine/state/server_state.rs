@@ -1,30 +1,130 @@
 use rdkafka::producer::FutureProducer;
 use schema_registry_converter::async_impl::schema_registry::post_schema;
 use schema_registry_converter::schema_registry_common::{SchemaType, SuppliedSchema};
+use std::collections::HashMap;
 use std::error::Error;
 use std::fs;
-use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+use std::sync::{Arc, RwLock};
 use rdkafka::admin::{AdminClient, AdminOptions, NewTopic, TopicReplication};
 use rdkafka::client::DefaultClientContext;
 use rdkafka::error::KafkaError;
+use crate::engine::accounts::ledger::Ledger;
+use crate::engine::accounts::market_making::ProtectionLimits;
+use crate::engine::accounts::settlement::SettlementSettings;
+use crate::engine::accounts::throttle::Throttle;
 use crate::engine::configuration::kafka_configuration::KafkaConfiguration;
+use crate::engine::configuration::persistence_configuration::PersistenceConfiguration;
 use crate::engine::configuration::server_configuration::ServerConfiguration;
-use crate::engine::services::orderbook_manager_service::OrderbookManager;
+use crate::engine::constants::property_loader::{
+    EventSink, KafkaAdminProperties, PersistenceProperties, StartupRetry, TopicSettings,
+};
+use crate::engine::persistence::audit_log::AuditLog;
+use crate::engine::persistence::outbox::Outbox;
+use crate::engine::persistence::recording::FlowRecorder;
+use crate::engine::persistence::traffic::TrafficRecorder;
+use crate::engine::persistence::snapshot_store::SnapshotStore;
+use crate::engine::risk::hooks::TradeHooks;
+use crate::engine::risk::RiskCheck;
+use crate::engine::state::delivery_metrics::DeliveryMetrics;
+#[cfg(feature = "chaos")]
+use crate::engine::state::faults::Faults;
+use crate::engine::state::health::Health;
+use crate::engine::state::replication::Replication;
+use crate::engine::state::runtime_settings::RuntimeSettings;
+use crate::engine::state::session::Session;
+use crate::engine::state::symbol_state::{SymbolSpecification, SymbolState};
+use crate::engine::utils::retry::retry_with_backoff;
+use crate::engine::utils::time::generate_u128_timestamp;
 use tokio::sync::Notify;
-use tracing::info;
+use tracing::{info, warn};
+
+/// The symbols currently traded, keyed by their ticker.
+pub type Symbols = Arc<RwLock<HashMap<String, Arc<SymbolState>>>>;
 
 pub struct ServerState {
     pub shutdown_notification: Arc<Notify>,
-    pub orderbook_manager: Arc<OrderbookManager>,
-    pub kafka_producer: Arc<FutureProducer>,
-    pub kafka_admin_client: Arc<AdminClient<DefaultClientContext>>
+    pub snapshot_notification: Arc<Notify>,
+    /// Notified once the executors are drained during shutdown, telling the publisher to flush
+    /// the outbox and stop.
+    pub drain_notification: Arc<Notify>,
+    /// The kafka clients, `None` when running standalone.
+    pub kafka_producer: Option<Arc<FutureProducer>>,
+    pub kafka_admin_client: Option<Arc<AdminClient<DefaultClientContext>>>,
+    pub symbols: Symbols,
+    pub outbox: Arc<Outbox>,
+    pub delivery_metrics: Arc<DeliveryMetrics>,
+    pub trading_halted: Arc<AtomicBool>,
+    pub runtime_settings: Arc<RuntimeSettings>,
+    pub session: Arc<Session>,
+    /// Liveness and readiness, reported by the health task.
+    pub health: Arc<Health>,
+    /// Balances of the trading accounts, only kept when the ledger is enabled.
+    pub ledger: Option<Arc<Ledger>>,
+    /// Where settlement instructions of fills are published, only set when they are enabled.
+    pub settlement: Option<SettlementSettings>,
+    /// The topic every fill is published to as a trade, only set when trades are enabled.
+    pub trades_topic: Option<String>,
+    /// Flow counters of the trading accounts, only kept when throttling is enabled.
+    pub throttle: Option<Arc<Throttle>>,
+    /// How much quotes may trade before they are pulled, only set when market maker protection
+    /// is enabled along with the ledger, which tells the accounts of quotes apart.
+    pub protection_limits: Option<ProtectionLimits>,
+    /// Record of every inbound request, only kept when auditing is enabled.
+    pub audit_log: Option<Arc<AuditLog>>,
+    /// Recording of the accepted order flow, only kept when recording is enabled.
+    pub recorder: Option<Arc<FlowRecorder>>,
+    /// Recording of the requests reaching the dispatcher, only kept when traffic is recorded.
+    pub traffic_recorder: Option<Arc<TrafficRecorder>>,
+    /// Bucket the book snapshots are uploaded to, only kept when the snapshot store is enabled.
+    pub snapshot_store: Option<Arc<SnapshotStore>>,
+    /// Role and fencing token in an active/standby pair, only kept when replication is enabled.
+    pub replication: Option<Arc<Replication>>,
+    /// The pre-trade check every executor runs in place of the resting limits of its symbol, which
+    /// an application embedding the engine sets before the executors are started.
+    pub risk_check: Option<Arc<dyn RiskCheck>>,
+    /// The hooks every executor runs around matching, which an application embedding the engine
+    /// adds before the executors are started.
+    pub trade_hooks: TradeHooks,
+    /// Faults armed through the admin service, only built with the `chaos` feature.
+    #[cfg(feature = "chaos")]
+    pub faults: Arc<Faults>,
 }
 
 impl ServerState {
+    /// Registers the schemas and creates the kafka topics before building the state, unless
+    /// running standalone. Both are retried until the startup deadline, so that the server
+    /// rides out its dependencies restarting alongside it. Books are then restored from the
+    /// snapshot store when configured to.
     pub async fn init(
         server_configuration: Arc<ServerConfiguration>,
         kafka_configuration: Arc<KafkaConfiguration>,
+        persistence_configuration: Arc<PersistenceConfiguration>,
     ) -> Result<ServerState, Box<dyn Error>> {
+        let startup_retry = kafka_configuration.kafka_admin_properties.startup_retry;
+        let restore = persistence_configuration
+            .persistence_properties
+            .snapshot_store
+            .as_ref()
+            .is_some_and(|settings| settings.restore);
+        if let Some(sink) = &kafka_configuration.kafka_admin_properties.standalone_sink {
+            match sink {
+                EventSink::Count => warn!("dry run, events are counted instead of published"),
+                sink => warn!(
+                    "running standalone, events are written to {:?} instead of kafka",
+                    sink
+                ),
+            }
+            let state = Self::create(
+                server_configuration,
+                kafka_configuration,
+                persistence_configuration,
+            )?;
+            if restore {
+                state.restore_snapshots(startup_retry).await?;
+            }
+            return Ok(state);
+        }
         let proto = fs::read_to_string("resources/protobuf/models.proto")?;
         let schema = SuppliedSchema {
             name: Some("models.proto".to_string()),
@@ -32,53 +132,294 @@ impl ServerState {
             schema: proto.to_string(),
             references: vec![],
         };
-        post_schema(
-            &kafka_configuration.kafka_admin_properties.sr_settings,
-            "models".to_string(),
-            schema,
-        )
+        retry_with_backoff("register schemas", startup_retry, || {
+            post_schema(
+                &kafka_configuration.kafka_admin_properties.sr_settings,
+                "models".to_string(),
+                schema.clone(),
+            )
+        })
         .await?;
         info!("successfully registered schemas");
 
+        let state = Self::create(
+            server_configuration,
+            Arc::clone(&kafka_configuration),
+            persistence_configuration,
+        )?;
+        let admin_properties = &kafka_configuration.kafka_admin_properties;
+        let topics: Vec<(String, TopicSettings)> = state
+            .symbols
+            .read()
+            .unwrap()
+            .values()
+            .map(|symbol_state| {
+                let settings = admin_properties.event_topic_settings.clone();
+                (symbol_state.kafka_topic.clone(), settings)
+            })
+            .chain(state.settlement.iter().map(|settlement| {
+                let settings = admin_properties.settlement_topic_settings.clone();
+                (settlement.kafka_topic.clone(), settings)
+            }))
+            .chain(state.trades_topic.clone().map(|topic| {
+                (topic, admin_properties.trade_topic_settings.clone())
+            }))
+            .collect();
+        let kafka_admin_client = state
+            .kafka_admin_client
+            .clone()
+            .ok_or("kafka admin client is not configured")?;
+        retry_with_backoff("create topics", startup_retry, || {
+            check_and_create_topics(Arc::clone(&kafka_admin_client), &topics)
+        })
+        .await?;
+        if restore {
+            state.restore_snapshots(startup_retry).await?;
+        }
+        Ok(state)
+    }
+
+    /// Restores the books that recovered nothing from local persistence from their latest
+    /// snapshot in the snapshot store, retrying until the startup deadline.
+    async fn restore_snapshots(&self, startup_retry: StartupRetry) -> Result<(), Box<dyn Error>> {
+        let Some(snapshot_store) = &self.snapshot_store else {
+            return Ok(());
+        };
+        let symbols: Vec<_> = self.symbols.read().unwrap().values().cloned().collect();
+        for symbol_state in symbols.iter().filter(|s| s.recovered_nothing()) {
+            let description = format!("restore {} snapshot", symbol_state.symbol);
+            let snapshot = retry_with_backoff(&description, startup_retry, || {
+                snapshot_store.latest(&symbol_state.symbol)
+            })
+            .await?;
+            match snapshot {
+                Some(snapshot) => {
+                    info!(
+                        "restoring {} from snapshot taken at {} with {} resting orders",
+                        symbol_state.symbol,
+                        snapshot.taken_at,
+                        snapshot.orders.len()
+                    );
+                    symbol_state.restore_snapshot(snapshot)?;
+                }
+                None => info!("no snapshot of {} to restore", symbol_state.symbol),
+            }
+        }
+        Ok(())
+    }
+
+    /// Builds the state without registering schemas or creating kafka topics. Without a broker the
+    /// kafka clients keep retrying in the background, and nothing is published unless the
+    /// publisher runs, so this is enough to run the engine on its own.
+    pub fn create(
+        server_configuration: Arc<ServerConfiguration>,
+        kafka_configuration: Arc<KafkaConfiguration>,
+        persistence_configuration: Arc<PersistenceConfiguration>,
+    ) -> Result<ServerState, Box<dyn Error>> {
         let shutdown_notification = Arc::new(Notify::new());
-        let orderbook_manager = Arc::new(OrderbookManager::new(
-            server_configuration
-                .server_properties
-                .orderbook_ticker
-                .clone(),
-            server_configuration
-                .server_properties
-                .orderbook_queue_capacity,
-            server_configuration
-                .server_properties
-                .orderbook_store_capacity,
-        ));
 
-        let kafka_producer = Arc::new(kafka_configuration.producer()?);
-        let kafka_admin_client = Arc::new(kafka_configuration.admin_client()?);
+        let mut symbols = HashMap::new();
+        for ticker in &server_configuration.server_properties.orderbook_tickers {
+            let symbol_state = SymbolState::init(
+                ticker,
+                SymbolSpecification::from_properties(
+                    &server_configuration.server_properties,
+                    ticker,
+                ),
+                &kafka_configuration.kafka_admin_properties.kafka_topic,
+                &persistence_configuration.persistence_properties,
+            )?;
+            symbols.insert(ticker.clone(), Arc::new(symbol_state));
+        }
+        info!("successfully initialized orderbooks for {} symbols", symbols.len());
+
+        let outbox = Arc::new(Outbox::open(
+            &persistence_configuration.persistence_properties.outbox_path,
+        )?);
+        info!("successfully opened outbox with {} pending events", outbox.len());
+
+        let persistence_properties = &persistence_configuration.persistence_properties;
+        let audit_log = match &persistence_properties.audit_directory {
+            Some(directory) => Some(Arc::new(AuditLog::open(
+                directory,
+                persistence_properties.audit_max_file_size,
+            )?)),
+            None => None,
+        };
+        let recorder = match &persistence_properties.recording_path {
+            Some(path) => Some(Arc::new(FlowRecorder::create(path)?)),
+            None => None,
+        };
+        let traffic_recorder = match &persistence_properties.traffic_recording_path {
+            Some(path) => Some(Arc::new(TrafficRecorder::create(path)?)),
+            None => None,
+        };
+
+        let snapshot_store = match &persistence_properties.snapshot_store {
+            Some(settings) => Some(Arc::new(SnapshotStore::new(settings.clone())?)),
+            None => None,
+        };
+
+        let replication = match &server_configuration.server_properties.replication {
+            Some(settings) => {
+                let replication = Replication::open(settings)?;
+                info!(
+                    "successfully opened replication state as {} with fencing token {}",
+                    replication.role().name(),
+                    replication.fencing_token()
+                );
+                Some(Arc::new(replication))
+            }
+            None => None,
+        };
 
-        check_and_create_topics(
-            Arc::clone(&kafka_admin_client),
-            kafka_configuration.kafka_admin_properties.kafka_topic.as_str(),
-        ).await?;
+        // a standalone server never connects to kafka
+        let (kafka_producer, kafka_admin_client) =
+            match kafka_configuration.kafka_admin_properties.standalone_sink {
+                Some(_) => (None, None),
+                None => (
+                    Some(Arc::new(kafka_configuration.producer()?)),
+                    Some(Arc::new(kafka_configuration.admin_client()?)),
+                ),
+            };
 
+        let settlement = server_configuration
+            .server_properties
+            .settlement_fee_schedule
+            .map(|fee_schedule| SettlementSettings {
+                kafka_topic: format!(
+                    "{}-settlements",
+                    kafka_configuration.kafka_admin_properties.kafka_topic
+                ),
+                fee_schedule,
+            });
+        let trades_topic = server_configuration
+            .server_properties
+            .trades_enabled
+            .then(|| format!("{}-trades", kafka_configuration.kafka_admin_properties.kafka_topic));
         Ok(ServerState {
             shutdown_notification,
-            orderbook_manager,
+            snapshot_notification: Arc::new(Notify::new()),
+            drain_notification: Arc::new(Notify::new()),
             kafka_producer,
             kafka_admin_client,
+            symbols: Arc::new(RwLock::new(symbols)),
+            outbox,
+            delivery_metrics: Arc::new(DeliveryMetrics::default()),
+            trading_halted: Arc::new(AtomicBool::new(false)),
+            runtime_settings: Arc::new(RuntimeSettings::from_properties(
+                &server_configuration.server_properties,
+            )),
+            session: Arc::new(Session::new(
+                server_configuration.server_properties.session_schedule,
+                generate_u128_timestamp(),
+            )),
+            health: Arc::new(Health::default()),
+            ledger: server_configuration
+                .server_properties
+                .ledger_enabled
+                .then(|| {
+                    Arc::new(Ledger::new(
+                        server_configuration.server_properties.default_risk_limits,
+                    ))
+                }),
+            settlement,
+            trades_topic,
+            throttle: server_configuration
+                .server_properties
+                .throttle_limits
+                .map(|mut limits| {
+                    if !server_configuration.server_properties.ledger_enabled
+                        && limits.max_message_to_trade_ratio.take().is_some()
+                    {
+                        warn!("message to trade ratio is not enforced without the ledger");
+                    }
+                    Arc::new(Throttle::new(limits))
+                }),
+            protection_limits: server_configuration
+                .server_properties
+                .protection_limits
+                .filter(|_| {
+                    let ledger_enabled = server_configuration.server_properties.ledger_enabled;
+                    if !ledger_enabled {
+                        warn!("market maker protection is not enforced without the ledger");
+                    }
+                    ledger_enabled
+                }),
+            audit_log,
+            recorder,
+            traffic_recorder,
+            snapshot_store,
+            replication,
+            risk_check: None,
+            trade_hooks: TradeHooks::default(),
+            #[cfg(feature = "chaos")]
+            faults: Arc::new(Faults::default()),
         })
     }
+
+    pub fn symbol(&self, symbol: &str) -> Option<Arc<SymbolState>> {
+        self.symbols.read().unwrap().get(symbol).cloned()
+    }
+
+    /// Provisions the orderbooks, persistence and kafka topic of a new symbol and starts routing
+    /// to it. The executor of the symbol still has to be started by the caller. No topic is
+    /// created when running standalone.
+    pub async fn create_symbol(
+        &self,
+        symbol: &str,
+        specification: SymbolSpecification,
+        kafka_admin_properties: &KafkaAdminProperties,
+        persistence_properties: &PersistenceProperties,
+    ) -> Result<Arc<SymbolState>, Box<dyn Error>> {
+        if self.symbol(symbol).is_some() {
+            return Err(format!("symbol {} already exists", symbol).into());
+        }
+        let symbol_state = Arc::new(SymbolState::init(
+            symbol,
+            specification.clone(),
+            &kafka_admin_properties.kafka_topic,
+            persistence_properties,
+        )?);
+        if let Some(kafka_admin_client) = &self.kafka_admin_client {
+            check_and_create_topics(
+                Arc::clone(kafka_admin_client),
+                &[(
+                    symbol_state.kafka_topic.clone(),
+                    kafka_admin_properties.event_topic_settings.clone(),
+                )],
+            )
+            .await?;
+        }
+        let mut symbols = self.symbols.write().unwrap();
+        if symbols.contains_key(symbol) {
+            return Err(format!("symbol {} already exists", symbol).into());
+        }
+        symbols.insert(symbol.to_string(), Arc::clone(&symbol_state));
+        info!("successfully created symbol {}: {:?}", symbol, specification);
+        Ok(symbol_state)
+    }
+
+    /// Stops routing to the symbol, returning its state so the caller can stop its executor.
+    pub fn remove_symbol(&self, symbol: &str) -> Option<Arc<SymbolState>> {
+        self.symbols.write().unwrap().remove(symbol)
+    }
 }
 
 
 async fn check_and_create_topics(
     admin_client: Arc<AdminClient<DefaultClientContext>>, 
-    topic: &str
+    topics: &[(String, TopicSettings)]
 ) -> Result<(), KafkaError> {
-    let topics = vec![
-        NewTopic::new(topic, 1, TopicReplication::Fixed(1))
-    ];
+    let retentions: Vec<Option<String>> = topics
+        .iter()
+        .map(|(_, settings)| settings.retention.map(|retention| retention.as_millis().to_string()))
+        .collect();
+    let topics: Vec<NewTopic> = topics
+        .iter()
+        .zip(&retentions)
+        .map(|((topic, settings), retention)| new_topic(topic, settings, retention.as_deref()))
+        .collect();
     match admin_client.create_topics(&topics, &AdminOptions::default()).await {
         Ok(topic_results) => {
             topic_results.iter().for_each(|res| {
@@ -88,4 +429,49 @@ async fn check_and_create_topics(
         }
         Err(e) => Err(e)
     }
-}
\ No newline at end of file
+}
+
+/// Describes a topic to create with the settings of its kind, where `retention` is the retention
+/// of the settings in milliseconds.
+fn new_topic<'a>(
+    topic: &'a str,
+    settings: &'a TopicSettings,
+    retention: Option<&'a str>,
+) -> NewTopic<'a> {
+    let replication = TopicReplication::Fixed(settings.replication);
+    let new_topic = NewTopic::new(topic, settings.partitions, replication)
+        .set("cleanup.policy", settings.cleanup_policy.as_str());
+    match retention {
+        Some(retention) => new_topic.set("retention.ms", retention),
+        None => new_topic,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::engine::constants::property_loader::{CleanupPolicy, TopicSettings};
+    use crate::engine::state::server_state::new_topic;
+    use std::time::Duration;
+
+    #[test]
+    fn it_creates_topics_with_their_settings() {
+        let settings = TopicSettings {
+            partitions: 6,
+            replication: 3,
+            retention: Some(Duration::from_secs(3600)),
+            cleanup_policy: CleanupPolicy::CompactDelete,
+        };
+        let topic = new_topic("events-ethusd", &settings, Some("3600000"));
+        assert_eq!((topic.name, topic.num_partitions), ("events-ethusd", 6));
+        assert_eq!(
+            topic.config,
+            vec![("cleanup.policy", "compact,delete"), ("retention.ms", "3600000")]
+        );
+
+        let settings = TopicSettings {
+            retention: None,
+            ..settings
+        };
+        assert_eq!(new_topic("events-ethusd", &settings, None).config.len(), 1);
+    }
+}
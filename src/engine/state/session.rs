@@ -0,0 +1,158 @@
+use crate::engine::constants::property_loader::SessionSchedule;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+const NANOS_PER_SECOND: u128 = 1_000_000_000;
+const NANOS_PER_DAY: u128 = 86_400 * NANOS_PER_SECOND;
+
+/// The phase of the trading session, which decides whether orders are accepted.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SessionPhase {
+    /// Books are in a call: every operation but market orders is accepted and orders rest without
+    /// matching until the call is uncrossed at the open.
+    PreOpen,
+    /// Every operation is accepted.
+    Open,
+    /// Only cancels are accepted.
+    Closed,
+}
+
+impl SessionPhase {
+    pub fn name(&self) -> &'static str {
+        match self {
+            SessionPhase::PreOpen => "pre-open",
+            SessionPhase::Open => "open",
+            SessionPhase::Closed => "closed",
+        }
+    }
+}
+
+impl From<u8> for SessionPhase {
+    fn from(value: u8) -> Self {
+        match value {
+            0 => SessionPhase::PreOpen,
+            2 => SessionPhase::Closed,
+            _ => SessionPhase::Open,
+        }
+    }
+}
+
+/// The trading session shared by every symbol. Without a schedule the market is always open.
+/// All times are in nanoseconds since the unix epoch.
+pub struct Session {
+    schedule: Option<SessionSchedule>,
+    phase: AtomicU8,
+}
+
+impl Session {
+    pub fn new(schedule: Option<SessionSchedule>, now: u128) -> Self {
+        let session = Self {
+            schedule,
+            phase: AtomicU8::new(SessionPhase::Open as u8),
+        };
+        session.set_phase(session.phase_at(now));
+        session
+    }
+
+    pub fn schedule(&self) -> Option<SessionSchedule> {
+        self.schedule
+    }
+
+    /// The phase currently in effect, which is advanced by the session task.
+    pub fn phase(&self) -> SessionPhase {
+        SessionPhase::from(self.phase.load(Ordering::SeqCst))
+    }
+
+    pub fn set_phase(&self, phase: SessionPhase) {
+        self.phase.store(phase as u8, Ordering::SeqCst);
+    }
+
+    /// The phase the schedule prescribes at the passed time.
+    pub fn phase_at(&self, now: u128) -> SessionPhase {
+        let Some(schedule) = self.schedule else {
+            return SessionPhase::Open;
+        };
+        let time_of_day = now % NANOS_PER_DAY;
+        if time_of_day < seconds(schedule.pre_open) {
+            SessionPhase::Closed
+        } else if time_of_day < seconds(schedule.open) {
+            SessionPhase::PreOpen
+        } else if time_of_day < seconds(schedule.close) {
+            SessionPhase::Open
+        } else {
+            SessionPhase::Closed
+        }
+    }
+
+    /// The time of the next phase change after the passed time, `None` without a schedule.
+    pub fn next_transition(&self, now: u128) -> Option<u128> {
+        let schedule = self.schedule?;
+        let day_start = now - now % NANOS_PER_DAY;
+        [schedule.pre_open, schedule.open, schedule.close]
+            .into_iter()
+            .map(|time| day_start + seconds(time))
+            .find(|transition| *transition > now)
+            .or(Some(day_start + NANOS_PER_DAY + seconds(schedule.pre_open)))
+    }
+
+    /// The end of the current session, or of the next one once today's session has closed.
+    /// DAY orders expire at this time. `None` without a schedule.
+    pub fn session_close(&self, now: u128) -> Option<u128> {
+        let schedule = self.schedule?;
+        let close = now - now % NANOS_PER_DAY + seconds(schedule.close);
+        Some(if close > now {
+            close
+        } else {
+            close + NANOS_PER_DAY
+        })
+    }
+}
+
+fn seconds(seconds: u32) -> u128 {
+    seconds as u128 * NANOS_PER_SECOND
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::engine::constants::property_loader::SessionSchedule;
+    use crate::engine::state::session::{Session, SessionPhase, NANOS_PER_DAY};
+
+    const HOUR: u128 = 3600 * 1_000_000_000;
+
+    #[test]
+    fn it_follows_the_session_schedule() {
+        let schedule = SessionSchedule::parse("08:45", "09:00", "17:00").unwrap();
+        assert!(SessionSchedule::parse("09:00", "08:45", "17:00").is_err());
+        assert!(SessionSchedule::parse("08:45", "09:00", "24:00").is_err());
+
+        let day = 20_000 * NANOS_PER_DAY;
+        let session = Session::new(Some(schedule), day + 8 * HOUR);
+        assert_eq!(session.phase(), SessionPhase::Closed);
+        assert_eq!(
+            session.phase_at(day + 8 * HOUR + HOUR * 3 / 4),
+            SessionPhase::PreOpen
+        );
+        assert_eq!(session.phase_at(day + 12 * HOUR), SessionPhase::Open);
+        assert_eq!(session.phase_at(day + 17 * HOUR), SessionPhase::Closed);
+        assert_eq!(
+            session.next_transition(day + 12 * HOUR),
+            Some(day + 17 * HOUR)
+        );
+        assert_eq!(
+            session.next_transition(day + 18 * HOUR),
+            Some(day + NANOS_PER_DAY + 8 * HOUR + HOUR * 3 / 4)
+        );
+        assert_eq!(
+            session.session_close(day + 12 * HOUR),
+            Some(day + 17 * HOUR)
+        );
+        assert_eq!(
+            session.session_close(day + 17 * HOUR),
+            Some(day + NANOS_PER_DAY + 17 * HOUR)
+        );
+
+        let unscheduled = Session::new(None, day);
+        assert_eq!(unscheduled.phase(), SessionPhase::Open);
+        assert_eq!(unscheduled.next_transition(day), None);
+        assert_eq!(unscheduled.session_close(day), None);
+    }
+}
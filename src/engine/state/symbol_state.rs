@@ -0,0 +1,525 @@
+use crate::core::models::{CompactionReport, MatchingPolicy, MemoryStats, Operation};
+use crate::core::orderbook::OrderBook;
+use crate::engine::accounts::ledger::SymbolAssets;
+use crate::engine::constants::property_loader::{
+    ChannelCapacities, PersistenceProperties, ServerProperties,
+};
+use crate::engine::errors::ValidationError;
+use crate::engine::persistence::order_store::PersistentOrderStore;
+use crate::engine::persistence::snapshot_store::BookSnapshot;
+use crate::engine::persistence::wal::WriteAheadLog;
+use crate::engine::risk::resting_limits::RestingLimits;
+use crate::engine::services::orderbook_manager_service::OrderbookManager;
+use crate::engine::state::average_price::AveragePrices;
+use crate::engine::state::book_feed::{BookFeed, BookSubscription};
+use crate::engine::state::candles::Candles;
+use crate::engine::state::channel_metrics::{ChannelMetrics, ExecutorWatermarks};
+use crate::engine::state::delayed_data::DelayedMarketData;
+use crate::engine::state::heatmap::Heatmap;
+use crate::engine::state::latency_metrics::LatencyMetrics;
+use crate::engine::state::order_history::OrderHistory;
+use crate::engine::state::overload::Overload;
+use crate::engine::state::quotes::Quotes;
+use crate::engine::state::recent_operations::RecentOperations;
+use crate::engine::state::replication::{ReplicationFeed, ReplicationSubscription};
+use crate::engine::state::ticker::Ticker;
+use crate::engine::state::volume_profile::VolumeProfile;
+use crate::engine::tasks::order_exec_task::ExecutorCommand;
+use std::error::Error;
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, AtomicU8, Ordering};
+use std::sync::{Arc, Mutex};
+use tokio::sync::mpsc::{Receiver, Sender};
+use tokio::sync::{mpsc, oneshot};
+use tokio::task::JoinHandle;
+use tokio::time::Instant;
+use tracing::{info, warn};
+
+/// Controls which operations are accepted for a symbol.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TradingStatus {
+    /// Every operation is accepted.
+    Open,
+    /// No operation is accepted.
+    Halted,
+    /// Only cancels are accepted, so participants can pull their orders but not add new ones.
+    CancelOnly,
+}
+
+impl From<u8> for TradingStatus {
+    fn from(value: u8) -> Self {
+        match value {
+            1 => TradingStatus::Halted,
+            2 => TradingStatus::CancelOnly,
+            _ => TradingStatus::Open,
+        }
+    }
+}
+
+/// Trading parameters of a symbol.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SymbolSpecification {
+    /// Limit prices must be a multiple of the tick size.
+    pub tick_size: u64,
+    /// Order quantities must be a multiple of the lot size.
+    pub lot_size: u64,
+    pub queue_capacity: usize,
+    pub store_capacity: usize,
+    /// Tie-break rule within the price levels of the book.
+    pub matching_policy: MatchingPolicy,
+    pub channel_capacities: ChannelCapacities,
+    pub assets: SymbolAssets,
+    pub resting_limits: RestingLimits,
+}
+
+impl SymbolSpecification {
+    /// The specification of the symbols configured at startup, which trade in single units.
+    pub fn from_properties(server_properties: &ServerProperties, symbol: &str) -> Self {
+        Self {
+            tick_size: 1,
+            lot_size: 1,
+            queue_capacity: server_properties.orderbook_queue_capacity,
+            store_capacity: server_properties.orderbook_store_capacity,
+            matching_policy: server_properties.matching_policy,
+            channel_capacities: server_properties.channel_capacities,
+            assets: SymbolAssets::from_symbol(symbol),
+            resting_limits: server_properties.resting_limits,
+        }
+    }
+}
+
+/// Where the last snapshot of a symbol was taken.
+#[derive(Debug, Clone, Copy)]
+pub struct SnapshotMark {
+    pub operation_count: u64,
+    pub taken_at: Instant,
+}
+
+/// The receiving ends of the channels that feed the executor of a symbol.
+pub struct ExecutorChannels {
+    pub orders: Receiver<Operation>,
+    pub commands: Receiver<ExecutorCommand>,
+}
+
+/// Everything the engine keeps for a single traded symbol: its orderbooks, its persistence and the
+/// kafka topic its execution events are published to.
+pub struct SymbolState {
+    pub symbol: String,
+    pub kafka_topic: String,
+    pub specification: SymbolSpecification,
+    pub orderbook_manager: Arc<OrderbookManager>,
+    pub write_ahead_log: Option<Arc<WriteAheadLog>>,
+    pub order_store: Option<Arc<PersistentOrderStore>>,
+    pub latency_metrics: Arc<LatencyMetrics>,
+    /// Whether low priority order flow is shed, fed with matching latency by the executor.
+    pub overload: Arc<Overload>,
+    /// Revisions of the resting orders, kept by the executor.
+    pub order_history: Arc<OrderHistory>,
+    /// Rolling 24 hour trade statistics, kept by the executor.
+    pub ticker: Arc<Ticker>,
+    /// OHLCV candles of the recent trades, kept by the executor.
+    pub candles: Arc<Candles>,
+    /// Rolling VWAP and TWAP, fed by the executor and computed by the average price task.
+    pub average_prices: Arc<AveragePrices>,
+    /// Traded volume per price since the session started, kept by the executor.
+    pub volume_profile: Arc<VolumeProfile>,
+    /// Firm quotes handed out by the rfq stream that have yet to be executed.
+    pub quotes: Arc<Quotes>,
+    /// Changes to every resting order, published by the executor.
+    pub book_feed: Arc<BookFeed>,
+    /// Batches executed for the symbol, published by the executor to the replicas.
+    pub replication_feed: Arc<ReplicationFeed>,
+    /// Samples of the resting liquidity, taken by the heatmap task.
+    pub heatmap: Arc<Heatmap>,
+    /// Samples of the market data served on the delayed feed, taken by the delayed data task.
+    pub delayed_data: Arc<DelayedMarketData>,
+    /// Number of operations executed so far, published by the executor for the snapshot task.
+    pub operation_count: Arc<AtomicU64>,
+    /// The operations processed last, recorded by the executor when crash dumps are enabled.
+    pub recent_operations: Option<Arc<RecentOperations>>,
+    /// The last snapshot of the orderbook, taken by the snapshot task.
+    pub last_snapshot: Mutex<Option<SnapshotMark>>,
+    pub order_sender: Sender<Operation>,
+    pub command_sender: Sender<ExecutorCommand>,
+    /// How deep the executor channels have been, recorded by the executor.
+    pub executor_watermarks: Arc<ExecutorWatermarks>,
+    trading_status: AtomicU8,
+    executor_channels: Mutex<Option<ExecutorChannels>>,
+    executor_task: Mutex<Option<JoinHandle<()>>>,
+}
+
+impl SymbolState {
+    /// Creates the orderbooks of the symbol and recovers them from the configured persistence.
+    /// Persistence of every symbol lives in its own subdirectory of the configured paths.
+    pub fn init(
+        symbol: &str,
+        specification: SymbolSpecification,
+        kafka_topic_prefix: &str,
+        persistence_properties: &PersistenceProperties,
+    ) -> Result<SymbolState, Box<dyn Error>> {
+        let orderbook = OrderBook::builder()
+            .id(symbol)
+            .queue_capacity(specification.queue_capacity)
+            .store_capacity(specification.store_capacity)
+            .tick_size(specification.tick_size)
+            .lot_size(specification.lot_size)
+            .matching_policy(specification.matching_policy)
+            .build()
+            .map_err(|error| format!("invalid specification of {}: {}", symbol, error))?;
+        let orderbook_manager = Arc::new(OrderbookManager::with_orderbook(orderbook));
+
+        let write_ahead_log = if persistence_properties.wal_enabled {
+            let (write_ahead_log, report) = WriteAheadLog::open(
+                Path::new(&persistence_properties.wal_directory).join(symbol),
+                unsafe { &mut *orderbook_manager.get_primary() },
+            )?;
+            orderbook_manager.snapshot();
+            info!(
+                "successfully recovered {} orderbook from write ahead log, hash {:016x}: {:?}",
+                symbol,
+                unsafe { (*orderbook_manager.get_primary()).state_hash() },
+                report
+            );
+            Some(Arc::new(write_ahead_log))
+        } else {
+            None
+        };
+
+        let order_store = if persistence_properties.order_store_enabled {
+            let order_store = PersistentOrderStore::open(
+                Path::new(&persistence_properties.order_store_path).join(symbol),
+            )?;
+            let primary = unsafe { &mut *orderbook_manager.get_primary() };
+            if write_ahead_log.is_none() {
+                let restored = order_store.restore(primary)?;
+                info!(
+                    "successfully restored {} resting {} orders from persistent order store",
+                    restored, symbol
+                );
+            }
+            let mismatches = order_store.verify(primary)?;
+            if !mismatches.is_empty() {
+                warn!(
+                    "persistent order store of {} diverged from the orderbook, resyncing: {:?}",
+                    symbol, mismatches
+                );
+                order_store.resync(primary)?;
+            }
+            orderbook_manager.snapshot();
+            Some(Arc::new(order_store))
+        } else {
+            None
+        };
+
+        let capacities = specification.channel_capacities;
+        let (order_sender, orders) = mpsc::channel(capacities.orders);
+        let (command_sender, commands) = mpsc::channel(capacities.commands);
+        Ok(SymbolState {
+            symbol: symbol.to_string(),
+            kafka_topic: format!("{}-{}", kafka_topic_prefix, symbol.to_lowercase()),
+            specification,
+            orderbook_manager,
+            write_ahead_log,
+            order_store,
+            latency_metrics: Arc::new(LatencyMetrics::default()),
+            overload: Arc::new(Overload::default()),
+            order_history: Arc::new(OrderHistory::default()),
+            ticker: Arc::new(Ticker::default()),
+            candles: Arc::new(Candles::default()),
+            average_prices: Arc::new(AveragePrices::default()),
+            volume_profile: Arc::new(VolumeProfile::default()),
+            quotes: Arc::new(Quotes::default()),
+            book_feed: Arc::new(BookFeed::new(capacities.book_feed)),
+            replication_feed: Arc::new(ReplicationFeed::new(capacities.replication_feed)),
+            heatmap: Arc::new(Heatmap::default()),
+            delayed_data: Arc::new(DelayedMarketData::default()),
+            operation_count: Arc::new(AtomicU64::new(0)),
+            recent_operations: persistence_properties
+                .crash_dump
+                .as_ref()
+                .map(|crash_dump| Arc::new(RecentOperations::new(crash_dump.operations))),
+            last_snapshot: Mutex::new(None),
+            order_sender,
+            command_sender,
+            executor_watermarks: Arc::new(ExecutorWatermarks::default()),
+            trading_status: AtomicU8::new(TradingStatus::Open as u8),
+            executor_channels: Mutex::new(Some(ExecutorChannels { orders, commands })),
+            executor_task: Mutex::new(None),
+        })
+    }
+
+    /// Checks the operation against the tick and lot size of the symbol.
+    pub fn validate(&self, operation: &Operation) -> Result<(), ValidationError> {
+        let (price, quantity) = match operation {
+            Operation::Limit(order) | Operation::Modify(order) => {
+                (Some(order.price), order.quantity)
+            }
+            Operation::Market(order) => (None, order.quantity),
+            Operation::Reduce { quantity_delta, .. } => (None, *quantity_delta),
+            Operation::Quote { bid, ask } => {
+                self.validate(&Operation::Limit(*bid))?;
+                return self.validate(&Operation::Limit(*ask));
+            }
+            Operation::Cancel(_) | Operation::Call | Operation::Uncross => return Ok(()),
+        };
+        if price.is_some_and(|price| price % self.specification.tick_size != 0) {
+            return Err(ValidationError::TickSize(self.specification.tick_size));
+        }
+        if quantity % self.specification.lot_size != 0 {
+            return Err(ValidationError::LotSize(self.specification.lot_size));
+        }
+        Ok(())
+    }
+
+    pub fn trading_status(&self) -> TradingStatus {
+        TradingStatus::from(self.trading_status.load(Ordering::SeqCst))
+    }
+
+    pub fn set_trading_status(&self, trading_status: TradingStatus) {
+        self.trading_status
+            .store(trading_status as u8, Ordering::SeqCst);
+    }
+
+    /// Whether the book came up without resting orders or trades, as it does without local
+    /// persistence to recover from.
+    pub fn recovered_nothing(&self) -> bool {
+        let primary = unsafe { &*self.orderbook_manager.get_primary() };
+        primary.get_last_trade_price() == 0 && primary.resting_orders().is_empty()
+    }
+
+    /// Restores the book from a snapshot before its executor starts, writing it through to the
+    /// write ahead log and the persistent order store so that they recover it from then on.
+    pub fn restore_snapshot(&self, snapshot: BookSnapshot) -> Result<(), Box<dyn Error>> {
+        let primary = unsafe { &mut *self.orderbook_manager.get_primary() };
+        if snapshot.call {
+            primary.execute(Operation::Call);
+        }
+        for order in snapshot.orders {
+            primary.execute(Operation::Limit(order));
+        }
+        primary.set_last_trade_price(snapshot.last_trade_price);
+        if let Some(write_ahead_log) = &self.write_ahead_log {
+            write_ahead_log.checkpoint(primary)?;
+        }
+        if let Some(order_store) = &self.order_store {
+            order_store.resync(primary)?;
+        }
+        self.orderbook_manager.snapshot();
+        Ok(())
+    }
+
+    /// How full the internal channels of the symbol are.
+    pub fn channel_metrics(&self) -> Vec<ChannelMetrics> {
+        vec![
+            ChannelMetrics::of_sender(
+                "orders",
+                &self.order_sender,
+                &self.executor_watermarks.orders,
+            ),
+            ChannelMetrics::of_sender(
+                "commands",
+                &self.command_sender,
+                &self.executor_watermarks.commands,
+            ),
+            self.book_feed.metrics(),
+            self.replication_feed.metrics(),
+        ]
+    }
+
+    /// Hands out the receiving ends of the executor channels, which can only happen once since a
+    /// symbol is executed by a single executor.
+    pub fn take_executor_channels(&self) -> Option<ExecutorChannels> {
+        self.executor_channels.lock().unwrap().take()
+    }
+
+    pub fn set_executor_task(&self, executor_task: JoinHandle<()>) {
+        *self.executor_task.lock().unwrap() = Some(executor_task);
+    }
+
+    /// Whether the executor was started and has not exited since.
+    pub fn executor_running(&self) -> bool {
+        self.executor_task
+            .lock()
+            .unwrap()
+            .as_ref()
+            .is_some_and(|executor_task| !executor_task.is_finished())
+    }
+
+    /// Hands out the handle of the running executor so that it can be awaited once stopped.
+    pub fn take_executor_task(&self) -> Option<JoinHandle<()>> {
+        self.executor_task.lock().unwrap().take()
+    }
+
+    /// Has the executor cancel every resting order that expired by the passed time.
+    ///
+    /// # Returns
+    ///
+    /// * The number of orders cancelled.
+    pub async fn expire_orders(&self, now: u128) -> Result<usize, Box<dyn Error>> {
+        let (reply, response) = oneshot::channel();
+        self.command_sender
+            .send(ExecutorCommand::Expire(now, reply))
+            .await
+            .map_err(|_| "executor already stopped")?;
+        Ok(response.await?)
+    }
+
+    /// Has the executor start or end the call of its book to follow the trading session.
+    ///
+    /// # Returns
+    ///
+    /// * Whether the call was started or ended.
+    pub async fn follow_session(&self) -> Result<bool, Box<dyn Error>> {
+        let (reply, response) = oneshot::channel();
+        self.command_sender
+            .send(ExecutorCommand::FollowSession(reply))
+            .await
+            .map_err(|_| "executor already stopped")?;
+        Ok(response.await?)
+    }
+
+    /// Has the executor summarize the memory held by its book. The book is read by the executor
+    /// rather than here, as the secondary does not keep the spare capacity of the primary.
+    pub async fn memory_stats(&self) -> Result<MemoryStats, Box<dyn Error>> {
+        let (reply, response) = oneshot::channel();
+        self.command_sender
+            .send(ExecutorCommand::MemoryStats(reply))
+            .await
+            .map_err(|_| "executor already stopped")?;
+        Ok(response.await?)
+    }
+
+    /// Has the executor snapshot its book in between batches.
+    pub async fn snapshot_book(&self) -> Result<(), Box<dyn Error>> {
+        let (reply, response) = oneshot::channel();
+        self.command_sender
+            .send(ExecutorCommand::Snapshot(reply))
+            .await
+            .map_err(|_| "executor already stopped")?;
+        Ok(response.await?)
+    }
+
+    /// Replaces the secondary with a copy of the primary and marks where it was taken. Reads the
+    /// primary, so it is left to the executor once started.
+    pub fn take_snapshot(&self) {
+        let operation_count = self.operation_count.load(Ordering::Relaxed);
+        self.orderbook_manager.snapshot();
+        *self.last_snapshot.lock().unwrap() = Some(SnapshotMark {
+            operation_count,
+            taken_at: Instant::now(),
+        });
+    }
+
+    /// Has the executor compact its book in between batches.
+    pub async fn compact_book(&self) -> Result<CompactionReport, Box<dyn Error>> {
+        let (reply, response) = oneshot::channel();
+        self.command_sender
+            .send(ExecutorCommand::Compact(reply))
+            .await
+            .map_err(|_| "executor already stopped")?;
+        Ok(response.await?)
+    }
+
+    /// Has the executor hand out its resting orders along with a subscription to the changes
+    /// that follow them.
+    pub async fn subscribe_book(&self) -> Result<BookSubscription, Box<dyn Error>> {
+        let (reply, response) = oneshot::channel();
+        self.command_sender
+            .send(ExecutorCommand::SubscribeBook(reply))
+            .await
+            .map_err(|_| "executor already stopped")?;
+        Ok(response.await?)
+    }
+
+    /// Has the executor hand out its resting orders along with a subscription to the batches it
+    /// executes after them.
+    pub async fn subscribe_replication(&self) -> Result<ReplicationSubscription, Box<dyn Error>> {
+        let (reply, response) = oneshot::channel();
+        self.command_sender
+            .send(ExecutorCommand::SubscribeReplication(reply))
+            .await
+            .map_err(|_| "executor already stopped")?;
+        Ok(response.await?)
+    }
+
+    /// Stops the executor once it has executed every queued operation and waits for it to exit.
+    ///
+    /// # Returns
+    ///
+    /// * The number of operations drained from the queue.
+    pub async fn stop_executor(&self) -> Result<usize, Box<dyn Error>> {
+        let (reply, response) = oneshot::channel();
+        self.command_sender
+            .send(ExecutorCommand::Stop(reply))
+            .await
+            .map_err(|_| "executor already stopped")?;
+        let drained = response.await?;
+        if let Some(executor_task) = self.take_executor_task() {
+            executor_task.await?;
+        }
+        Ok(drained)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::models::{LimitOrder, MarketOrder, MatchingPolicy, Operation, Side};
+    use crate::core::types::{OrderId, Price, Quantity};
+    use crate::engine::accounts::ledger::SymbolAssets;
+    use crate::engine::constants::property_loader::{ChannelCapacities, PersistenceProperties};
+    use crate::engine::risk::resting_limits::RestingLimits;
+    use crate::engine::state::symbol_state::{SymbolSpecification, SymbolState};
+    use std::time::Duration;
+
+    #[test]
+    fn it_validates_operations_against_tick_and_lot_size() {
+        let persistence_properties = PersistenceProperties {
+            wal_enabled: false,
+            wal_directory: String::new(),
+            wal_checkpoint_interval: Duration::ZERO,
+            order_store_enabled: false,
+            order_store_path: String::new(),
+            outbox_path: String::new(),
+            audit_directory: None,
+            audit_max_file_size: 0,
+            recording_path: None,
+            traffic_recording_path: None,
+            replay_path: None,
+            replay_speed: 1.0,
+            snapshot_store: None,
+            crash_dump: None,
+        };
+        let specification = SymbolSpecification {
+            tick_size: 5,
+            lot_size: 10,
+            queue_capacity: 10,
+            store_capacity: 100,
+            matching_policy: MatchingPolicy::PriceTime,
+            channel_capacities: ChannelCapacities::default(),
+            assets: SymbolAssets::from_symbol("ETHUSD"),
+            resting_limits: RestingLimits::default(),
+        };
+        let symbol_state =
+            SymbolState::init("ETHUSD", specification, "events", &persistence_properties).unwrap();
+        assert_eq!(symbol_state.kafka_topic, "events-ethusd");
+        let valid = Operation::Limit(LimitOrder::new(
+            OrderId(1),
+            Price(105),
+            Quantity(20),
+            Side::Bid,
+        ));
+        assert!(symbol_state.validate(&valid).is_ok());
+        let off_tick = Operation::Modify(LimitOrder::new(
+            OrderId(1),
+            Price(103),
+            Quantity(20),
+            Side::Bid,
+        ));
+        assert!(symbol_state.validate(&off_tick).is_err());
+        let off_lot = Operation::Market(MarketOrder::new(OrderId(2), Quantity(15), Side::Ask));
+        assert!(symbol_state.validate(&off_lot).is_err());
+        assert!(symbol_state
+            .validate(&Operation::Cancel(OrderId(1)))
+            .is_ok());
+    }
+}
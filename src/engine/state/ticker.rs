@@ -0,0 +1,202 @@
+use crate::core::models::FillMetaData;
+use std::collections::VecDeque;
+use std::sync::Mutex;
+
+/// Length of the rolling window of the ticker in nanoseconds.
+pub const TICKER_WINDOW: u128 = 24 * 60 * 60 * 1_000_000_000;
+/// Width of the buckets fills are aggregated into in nanoseconds. The window moves a bucket at a
+/// time, so it covers up to a bucket more than its length.
+pub const TICKER_BUCKET: u128 = 60 * 1_000_000_000;
+
+/// The fills of a single bucket of the window.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Bucket {
+    index: u128,
+    open: u64,
+    high: u64,
+    low: u64,
+    close: u64,
+    volume: u64,
+    quote_volume: u64,
+    trade_count: u64,
+}
+
+impl Bucket {
+    fn new(index: u128, price: u64) -> Self {
+        Bucket {
+            index,
+            open: price,
+            high: price,
+            low: price,
+            close: price,
+            volume: 0,
+            quote_volume: 0,
+            trade_count: 0,
+        }
+    }
+
+    fn record(&mut self, fill: &FillMetaData) {
+        self.high = self.high.max(fill.price);
+        self.low = self.low.min(fill.price);
+        self.close = fill.price;
+        self.volume = self.volume.saturating_add(fill.quantity);
+        self.quote_volume = self
+            .quote_volume
+            .saturating_add(fill.price.saturating_mul(fill.quantity));
+        self.trade_count += 1;
+    }
+}
+
+/// The statistics of the trades of a symbol over the window. Prices of a window without trades
+/// are zero, except for the last price which is kept until the next trade.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct TickerStats {
+    pub last_price: u64,
+    /// The price of the first trade of the window.
+    pub open_price: u64,
+    pub high_price: u64,
+    pub low_price: u64,
+    /// Quantity traded over the window.
+    pub volume: u64,
+    /// Notional traded over the window, saturating at the maximum.
+    pub quote_volume: u64,
+    pub trade_count: u64,
+    /// Last price less the open price.
+    pub price_change: i64,
+    /// The price change in hundredths of a percent of the open price.
+    pub price_change_bps: i64,
+}
+
+#[derive(Debug, Default)]
+struct Window {
+    buckets: VecDeque<Bucket>,
+    last_price: u64,
+}
+
+/// A rolling 24 hour ticker of a symbol, maintained by its executor from the fills it produces.
+/// Only fills executed since the server started are covered.
+#[derive(Debug, Default)]
+pub struct Ticker {
+    window: Mutex<Window>,
+}
+
+impl Ticker {
+    /// Records the fills of an execution, at the time it took place in nanoseconds since the
+    /// unix epoch.
+    pub fn record(&self, fills: &[FillMetaData], timestamp: u128) {
+        if fills.is_empty() {
+            return;
+        }
+        let index = timestamp / TICKER_BUCKET;
+        let mut window = self.window.lock().unwrap();
+        for fill in fills {
+            let bucket = match window.buckets.back_mut() {
+                Some(bucket) if bucket.index >= index => bucket,
+                _ => {
+                    window.buckets.push_back(Bucket::new(index, fill.price));
+                    window.buckets.back_mut().unwrap()
+                }
+            };
+            bucket.record(fill);
+            window.last_price = fill.price;
+        }
+    }
+
+    /// Returns the statistics of the window ending at `now` in nanoseconds since the unix epoch,
+    /// forgetting the buckets that fell out of it.
+    pub fn stats(&self, now: u128) -> TickerStats {
+        let mut window = self.window.lock().unwrap();
+        let first_index = now.saturating_sub(TICKER_WINDOW) / TICKER_BUCKET;
+        while window
+            .buckets
+            .front()
+            .is_some_and(|bucket| bucket.index < first_index)
+        {
+            window.buckets.pop_front();
+        }
+        let (Some(first), Some(last)) = (window.buckets.front(), window.buckets.back()) else {
+            return TickerStats {
+                last_price: window.last_price,
+                ..Default::default()
+            };
+        };
+        let mut stats = TickerStats {
+            last_price: last.close,
+            open_price: first.open,
+            high_price: first.high,
+            low_price: first.low,
+            ..Default::default()
+        };
+        for bucket in &window.buckets {
+            stats.high_price = stats.high_price.max(bucket.high);
+            stats.low_price = stats.low_price.min(bucket.low);
+            stats.volume = stats.volume.saturating_add(bucket.volume);
+            stats.quote_volume = stats.quote_volume.saturating_add(bucket.quote_volume);
+            stats.trade_count += bucket.trade_count;
+        }
+        let price_change = stats.last_price as i128 - stats.open_price as i128;
+        stats.price_change = price_change as i64;
+        if stats.open_price != 0 {
+            stats.price_change_bps = (price_change * 10_000 / stats.open_price as i128) as i64;
+        }
+        stats
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::models::{FillMetaData, Side};
+    use crate::engine::state::ticker::{Ticker, TickerStats, TICKER_BUCKET, TICKER_WINDOW};
+
+    fn fill(price: u64, quantity: u64) -> FillMetaData {
+        FillMetaData {
+            order_id: 1,
+            matched_order_id: 2,
+            taker_side: Side::Bid,
+            price,
+            quantity,
+        }
+    }
+
+    #[test]
+    fn it_aggregates_fills_over_the_window() {
+        let ticker = Ticker::default();
+        ticker.record(&[fill(100, 2), fill(120, 1)], 0);
+        ticker.record(&[fill(90, 3)], 5 * TICKER_BUCKET);
+        ticker.record(&[], 6 * TICKER_BUCKET);
+        ticker.record(&[fill(110, 4)], 10 * TICKER_BUCKET + 1);
+        assert_eq!(
+            ticker.stats(11 * TICKER_BUCKET),
+            TickerStats {
+                last_price: 110,
+                open_price: 100,
+                high_price: 120,
+                low_price: 90,
+                volume: 10,
+                quote_volume: 200 + 120 + 270 + 440,
+                trade_count: 4,
+                price_change: 10,
+                price_change_bps: 1_000,
+            }
+        );
+    }
+
+    #[test]
+    fn it_forgets_fills_that_fell_out_of_the_window() {
+        let ticker = Ticker::default();
+        ticker.record(&[fill(100, 1)], 0);
+        ticker.record(&[fill(80, 1)], 2 * TICKER_BUCKET);
+        let stats = ticker.stats(TICKER_WINDOW + TICKER_BUCKET);
+        assert_eq!((stats.open_price, stats.high_price), (80, 80));
+        assert_eq!((stats.trade_count, stats.price_change), (1, 0));
+
+        let stats = ticker.stats(TICKER_WINDOW + 3 * TICKER_BUCKET);
+        assert_eq!(
+            stats,
+            TickerStats {
+                last_price: 80,
+                ..Default::default()
+            }
+        );
+    }
+}
@@ -0,0 +1,130 @@
+use crate::core::models::{FillMetaData, Side};
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+
+/// The trades of a symbol at a single price.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct PriceVolume {
+    pub price: u64,
+    /// Quantity traded at the price.
+    pub volume: u64,
+    /// Quantity bought by takers at the price.
+    pub buy_volume: u64,
+    /// Quantity sold by takers at the price.
+    pub sell_volume: u64,
+    pub trade_count: u64,
+}
+
+/// The volume profile of a symbol since a point in time.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct VolumeProfileSnapshot {
+    /// Nanoseconds since the unix epoch the volume is counted from, zero since the server started.
+    pub since: u128,
+    /// The traded prices in ascending order.
+    pub levels: Vec<PriceVolume>,
+}
+
+impl VolumeProfileSnapshot {
+    /// The price that traded the most volume, the lowest of them on a tie.
+    pub fn point_of_control(&self) -> Option<u64> {
+        self.levels
+            .iter()
+            .rev()
+            .max_by_key(|level| level.volume)
+            .map(|level| level.price)
+    }
+}
+
+#[derive(Debug, Default)]
+struct Profile {
+    since: u128,
+    levels: BTreeMap<u64, PriceVolume>,
+}
+
+/// Cumulative traded volume per price of a symbol, maintained by its executor from the fills it
+/// produces and reset by the session task when a new trading session starts.
+#[derive(Debug, Default)]
+pub struct VolumeProfile {
+    profile: Mutex<Profile>,
+}
+
+impl VolumeProfile {
+    pub fn record(&self, fills: &[FillMetaData]) {
+        if fills.is_empty() {
+            return;
+        }
+        let mut profile = self.profile.lock().unwrap();
+        for fill in fills {
+            let level = profile.levels.entry(fill.price).or_insert(PriceVolume {
+                price: fill.price,
+                ..Default::default()
+            });
+            level.volume = level.volume.saturating_add(fill.quantity);
+            match fill.taker_side {
+                Side::Bid => level.buy_volume = level.buy_volume.saturating_add(fill.quantity),
+                Side::Ask => level.sell_volume = level.sell_volume.saturating_add(fill.quantity),
+            }
+            level.trade_count += 1;
+        }
+    }
+
+    /// Forgets the volume traded so far, counting again from `now` in nanoseconds since the unix
+    /// epoch.
+    pub fn reset(&self, now: u128) {
+        *self.profile.lock().unwrap() = Profile {
+            since: now,
+            levels: BTreeMap::new(),
+        };
+    }
+
+    pub fn snapshot(&self) -> VolumeProfileSnapshot {
+        let profile = self.profile.lock().unwrap();
+        VolumeProfileSnapshot {
+            since: profile.since,
+            levels: profile.levels.values().copied().collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::models::{FillMetaData, Side};
+    use crate::engine::state::volume_profile::{PriceVolume, VolumeProfile};
+
+    fn fill(price: u64, quantity: u64, taker_side: Side) -> FillMetaData {
+        FillMetaData {
+            order_id: 1,
+            matched_order_id: 2,
+            taker_side,
+            price,
+            quantity,
+        }
+    }
+
+    #[test]
+    fn it_accumulates_volume_per_price_until_reset() {
+        let profile = VolumeProfile::default();
+        profile.record(&[fill(101, 2, Side::Bid), fill(100, 5, Side::Ask)]);
+        profile.record(&[fill(101, 3, Side::Ask)]);
+
+        let snapshot = profile.snapshot();
+        assert_eq!(snapshot.since, 0);
+        assert_eq!(snapshot.levels.len(), 2);
+        assert_eq!(
+            snapshot.levels[1],
+            PriceVolume {
+                price: 101,
+                volume: 5,
+                buy_volume: 2,
+                sell_volume: 3,
+                trade_count: 2,
+            }
+        );
+        assert_eq!(snapshot.point_of_control(), Some(100));
+
+        profile.reset(50);
+        let snapshot = profile.snapshot();
+        assert_eq!((snapshot.since, snapshot.levels.len()), (50, 0));
+        assert_eq!(snapshot.point_of_control(), None);
+    }
+}
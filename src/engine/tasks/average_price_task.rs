@@ -0,0 +1,49 @@
+use crate::engine::state::server_state::{ServerState, Symbols};
+use crate::engine::utils::time::generate_u128_timestamp;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Notify;
+use tokio::time::sleep;
+use tracing::info;
+
+/// Periodically computes the rolling VWAP and TWAP of every symbol over the configured windows,
+/// which the stat stream and the metrics report serve from then on.
+pub struct AveragePriceCalculator {
+    pub shutdown_notification: Arc<Notify>,
+    pub symbols: Symbols,
+    pub interval: Duration,
+    pub windows: Vec<Duration>,
+}
+
+impl AveragePriceCalculator {
+    pub fn new(state: Arc<ServerState>, interval: Duration, windows: Vec<Duration>) -> Self {
+        Self {
+            shutdown_notification: Arc::clone(&state.shutdown_notification),
+            symbols: Arc::clone(&state.symbols),
+            interval,
+            windows,
+        }
+    }
+
+    pub async fn run(&self) {
+        loop {
+            tokio::select! {
+                _ = self.shutdown_notification.notified() => {
+                    info!("shutting down average_price_task");
+                    break;
+                },
+                _ = sleep(self.interval) => {
+                    self.compute()
+                }
+            }
+        }
+    }
+
+    fn compute(&self) {
+        let symbols: Vec<_> = self.symbols.read().unwrap().values().cloned().collect();
+        let now = generate_u128_timestamp();
+        for symbol_state in symbols {
+            symbol_state.average_prices.compute(&self.windows, now);
+        }
+    }
+}
@@ -0,0 +1,83 @@
+use crate::engine::constants::property_loader::CompactionSettings;
+use crate::engine::state::server_state::{ServerState, Symbols};
+use std::collections::HashMap;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use tokio::sync::Notify;
+use tokio::time::sleep;
+use tracing::{info, warn};
+
+/// Periodically compacts the book of every quiet symbol. A symbol is quiet when its order channel
+/// is empty and its executor processed few operations since it was last checked, so that the
+/// compaction, which the executor carries out in between batches, does not hold up order flow.
+pub struct Compactor {
+    pub shutdown_notification: Arc<Notify>,
+    pub symbols: Symbols,
+    pub settings: CompactionSettings,
+}
+
+impl Compactor {
+    pub fn new(state: Arc<ServerState>, settings: CompactionSettings) -> Self {
+        Self {
+            shutdown_notification: Arc::clone(&state.shutdown_notification),
+            symbols: Arc::clone(&state.symbols),
+            settings,
+        }
+    }
+
+    pub async fn run(&self) {
+        let mut operation_counts = HashMap::new();
+        loop {
+            tokio::select! {
+                _ = self.shutdown_notification.notified() => {
+                    info!("shutting down compaction_task");
+                    break;
+                },
+                _ = sleep(self.settings.interval) => {
+                    operation_counts = self.compact(&operation_counts).await
+                }
+            }
+        }
+    }
+
+    /// Compacts the books of the symbols that were quiet since the last check.
+    ///
+    /// # Returns
+    ///
+    /// * The operation count of every symbol, to tell whether it is quiet by the next check.
+    async fn compact(&self, previous_counts: &HashMap<String, u64>) -> HashMap<String, u64> {
+        let symbols: Vec<_> = self.symbols.read().unwrap().values().cloned().collect();
+        let mut operation_counts = HashMap::with_capacity(symbols.len());
+        for symbol_state in symbols {
+            let count = symbol_state.operation_count.load(Ordering::Relaxed);
+            operation_counts.insert(symbol_state.symbol.clone(), count);
+            let sender = &symbol_state.order_sender;
+            let quiet = sender.capacity() == sender.max_capacity()
+                && previous_counts
+                    .get(&symbol_state.symbol)
+                    .is_some_and(|previous| {
+                        count.saturating_sub(*previous) <= self.settings.quiet_operations
+                    });
+            if !quiet {
+                continue;
+            }
+            match symbol_state.compact_book().await {
+                Ok(report)
+                    if report.pruned_levels + report.shrunk_queues + report.released_slots > 0 =>
+                {
+                    info!(
+                        "compacted {}: pruned_levels={} shrunk_queues={} released_slots={} released_bytes={}",
+                        symbol_state.symbol,
+                        report.pruned_levels,
+                        report.shrunk_queues,
+                        report.released_slots,
+                        report.released_bytes
+                    )
+                }
+                Ok(_) => {}
+                Err(e) => warn!("failed to compact {}: {}", symbol_state.symbol, e),
+            }
+        }
+        operation_counts
+    }
+}
@@ -0,0 +1,66 @@
+use crate::core::models::Granularity;
+use crate::engine::constants::property_loader::DelayedDataSettings;
+use crate::engine::state::delayed_data::MarketSample;
+use crate::engine::state::server_state::{ServerState, Symbols};
+use crate::engine::utils::time::generate_u128_timestamp;
+use std::sync::Arc;
+use tokio::sync::Notify;
+use tokio::time::sleep;
+use tracing::info;
+
+/// Periodically samples the latest snapshot of every symbol's book along with its ticker, which
+/// the delayed stat stream serves once the delay of the feed passed.
+pub struct DelayedDataSampler {
+    pub shutdown_notification: Arc<Notify>,
+    pub symbols: Symbols,
+    pub settings: DelayedDataSettings,
+}
+
+impl DelayedDataSampler {
+    pub fn new(state: Arc<ServerState>, settings: DelayedDataSettings) -> Self {
+        Self {
+            shutdown_notification: Arc::clone(&state.shutdown_notification),
+            symbols: Arc::clone(&state.symbols),
+            settings,
+        }
+    }
+
+    pub async fn run(&self) {
+        loop {
+            tokio::select! {
+                _ = self.shutdown_notification.notified() => {
+                    info!("shutting down delayed_data_task");
+                    break;
+                },
+                _ = sleep(self.settings.interval) => {
+                    self.sample()
+                }
+            }
+        }
+    }
+
+    fn sample(&self) {
+        let symbols: Vec<_> = self.symbols.read().unwrap().values().cloned().collect();
+        let timestamp = generate_u128_timestamp();
+        let retain_from = timestamp.saturating_sub(self.settings.delay.as_nanos());
+        for symbol_state in symbols {
+            let secondary = symbol_state.orderbook_manager.get_secondary();
+            let (best_bid, best_ask) = secondary.best_bid_offer();
+            let sample = MarketSample {
+                timestamp,
+                last_trade_price: secondary.get_last_trade_price(),
+                max_bid: secondary.get_max_bid().unwrap_or(u64::MIN),
+                min_ask: secondary.get_min_ask().unwrap_or(u64::MAX),
+                best_bid,
+                best_ask,
+                depth: secondary
+                    .orderbook_data(Granularity::P00)
+                    .top(self.settings.levels),
+                ticker: symbol_state.ticker.stats(timestamp),
+            };
+            symbol_state
+                .delayed_data
+                .record(sample, self.settings.sampling, retain_from);
+        }
+    }
+}
@@ -0,0 +1,141 @@
+use crate::engine::constants::property_loader::ElectionSettings;
+use crate::engine::persistence::lease_store::{Campaign, LeaseStore};
+use crate::engine::services::replication_service::fence_primary;
+use crate::engine::state::replication::Replication;
+use crate::engine::state::server_state::ServerState;
+use std::error::Error;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Notify;
+use tokio::time::{sleep, sleep_until, Instant};
+use tracing::{info, warn};
+
+/// Campaigns for the leadership of an active/standby pair through a lease in etcd. The server
+/// holding the lease is the primary, every other server follows the address the leader put into
+/// the leadership key. A leader that fails to renew its lease in time steps down before etcd
+/// lets another server take over, and a leader that shuts down stops renewing its lease, so that
+/// a standby takes over once it runs out.
+pub struct LeaderElection {
+    pub shutdown_notification: Arc<Notify>,
+    pub replication: Arc<Replication>,
+    pub lease_store: Arc<LeaseStore>,
+    pub settings: ElectionSettings,
+    pub admin_token: String,
+    pub retry_interval: Duration,
+}
+
+impl LeaderElection {
+    pub fn new(
+        state: Arc<ServerState>,
+        replication: Arc<Replication>,
+        lease_store: Arc<LeaseStore>,
+        settings: ElectionSettings,
+        admin_token: String,
+        retry_interval: Duration,
+    ) -> Self {
+        Self {
+            shutdown_notification: Arc::clone(&state.shutdown_notification),
+            replication,
+            lease_store,
+            settings,
+            admin_token,
+            retry_interval,
+        }
+    }
+
+    pub async fn run(&self) {
+        loop {
+            let round = async {
+                if let Err(e) = self.campaign().await {
+                    warn!("leader election failed: {}", e);
+                }
+                sleep(self.retry_interval).await;
+            };
+            tokio::select! {
+                _ = self.shutdown_notification.notified() => {
+                    info!("shutting down election_task");
+                    break;
+                }
+                _ = round => {}
+            }
+        }
+    }
+
+    /// Campaigns for the leadership under a new lease, leading for as long as the lease lasts
+    /// when elected and following the leader otherwise.
+    async fn campaign(&self) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let requested_at = Instant::now();
+        let lease = self.lease_store.grant(self.settings.lease_ttl).await?;
+        let campaign = self
+            .lease_store
+            .campaign(lease, &self.settings.advertised_address)
+            .await;
+        let result = match campaign {
+            Ok(Campaign::Elected(revision)) => {
+                let led = self.lead(lease, revision, requested_at).await;
+                self.replication.step_down()?;
+                led
+            }
+            Ok(Campaign::Lost(leader)) => {
+                // a key holding our own address is left over from before a restart
+                match leader.filter(|leader| leader.address != self.settings.advertised_address) {
+                    Some(leader) => {
+                        Ok(self.replication.follow(leader.revision, &leader.address)?)
+                    }
+                    None => Ok(()),
+                }
+            }
+            Err(e) => Err(e.into()),
+        };
+        if let Err(e) = self.lease_store.revoke(lease).await {
+            warn!("failed to revoke lease {}: {}", lease, e);
+        }
+        result
+    }
+
+    /// Takes over as primary under the revision of the leadership key, fencing the previous
+    /// primary first in case it stalled rather than stepped down. Renews the lease until it
+    /// fails to, counting the lease from before the request renewing it.
+    async fn lead(
+        &self,
+        lease: i64,
+        revision: u64,
+        mut renewed_at: Instant,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        info!(
+            "elected leader with lease {} at revision {}",
+            lease, revision
+        );
+        if let Some(address) = self.replication.primary_address() {
+            match fence_primary(&address, &self.admin_token, revision).await {
+                Ok(status) => info!(
+                    "fenced primary at {}, which is now {}",
+                    address, status.role
+                ),
+                Err(e) => warn!("failed to fence primary at {}: {}", address, e.message()),
+            }
+        }
+        self.replication.promote(revision)?;
+        let lease_ttl = self.settings.lease_ttl;
+        loop {
+            let renewal = async {
+                sleep(lease_ttl / 3).await;
+                let sent_at = Instant::now();
+                (sent_at, self.lease_store.keep_alive(lease).await)
+            };
+            tokio::select! {
+                _ = sleep_until(renewed_at + lease_ttl) => {
+                    return Err(format!("failed to renew lease {} in time", lease).into());
+                }
+                (sent_at, renewed) = renewal => match renewed {
+                    Ok(true) => renewed_at = sent_at,
+                    Ok(false) => return Err(format!("lease {} expired", lease).into()),
+                    Err(e) => warn!("failed to renew lease {}: {}", lease, e),
+                },
+            }
+            if !self.replication.is_primary() {
+                return Err("fenced while holding the lease".into());
+            }
+        }
+    }
+}
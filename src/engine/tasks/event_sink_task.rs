@@ -0,0 +1,167 @@
+use crate::engine::constants::property_loader::EventSink;
+use crate::engine::persistence::outbox::{Outbox, OutboxEvent};
+use crate::engine::state::delivery_metrics::DeliveryMetrics;
+use crate::engine::state::server_state::ServerState;
+use std::error::Error;
+use std::fs::{File, OpenOptions};
+use std::io::{BufWriter, Write};
+use std::sync::Arc;
+use tokio::sync::Notify;
+use tokio::time::Instant;
+use tracing::{error, info};
+
+const WRITE_BATCH_SIZE: usize = 1000;
+
+/// Takes the place of the publisher when running standalone, draining the outbox into a log or
+/// a file instead of kafka. Every event is written as a line of its outbox sequence, topic,
+/// schema name and hex encoded payload, separated by tabs. A dry run only counts the events in the
+/// delivery metrics.
+pub struct EventSinkWriter {
+    pub drain_notification: Arc<Notify>,
+    pub outbox: Arc<Outbox>,
+    pub sink: EventSink,
+    pub delivery_metrics: Arc<DeliveryMetrics>,
+}
+
+impl EventSinkWriter {
+    pub fn new(state: Arc<ServerState>, sink: EventSink) -> Self {
+        Self {
+            drain_notification: Arc::clone(&state.drain_notification),
+            outbox: Arc::clone(&state.outbox),
+            sink,
+            delivery_metrics: Arc::clone(&state.delivery_metrics),
+        }
+    }
+
+    /// Writes events until drained, which happens once the outbox is empty. Events are only
+    /// acknowledged once written, so a failed write leaves them for the next start.
+    pub async fn run(&self) {
+        let mut file = match self.open() {
+            Ok(file) => file,
+            Err(e) => {
+                error!("failed to open event sink {:?}: {}", self.sink, e);
+                return;
+            }
+        };
+        loop {
+            let events = match self.outbox.peek(WRITE_BATCH_SIZE) {
+                Ok(events) => events,
+                Err(e) => {
+                    error!("failed to read events from outbox: {}", e);
+                    Vec::new()
+                }
+            };
+            if events.is_empty() {
+                tokio::select! {
+                    _ = self.outbox.wait_for_events() => continue,
+                    _ = self.drain_notification.notified() => break,
+                }
+            }
+            for (sequence, event) in events {
+                let started = Instant::now();
+                let written = match (&mut file, &self.sink) {
+                    (Some(file), _) => writeln!(file, "{}", event_line(sequence, &event))
+                        .and_then(|_| file.flush()),
+                    (None, EventSink::Log) => {
+                        info!(topic = %event.topic, "event: {}", event_line(sequence, &event));
+                        Ok(())
+                    }
+                    (None, _) => Ok(()),
+                };
+                if let Err(e) = written {
+                    error!(
+                        "failed to write event {} to {:?}: {}",
+                        sequence, self.sink, e
+                    );
+                    return;
+                }
+                self.delivery_metrics.record_delivery(started.elapsed());
+                if let Err(e) = self.outbox.acknowledge(sequence) {
+                    error!("failed to acknowledge outbox event {}: {}", sequence, e);
+                }
+            }
+        }
+        info!(
+            "shutting down publisher_task with {} events pending",
+            self.outbox.len()
+        );
+    }
+
+    fn open(&self) -> Result<Option<BufWriter<File>>, Box<dyn Error>> {
+        match &self.sink {
+            EventSink::Log | EventSink::Count => Ok(None),
+            EventSink::File(path) => {
+                let file = OpenOptions::new().create(true).append(true).open(path)?;
+                Ok(Some(BufWriter::new(file)))
+            }
+        }
+    }
+}
+
+fn event_line(sequence: u64, event: &OutboxEvent) -> String {
+    let payload: String = event
+        .payload
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect();
+    format!(
+        "{}\t{}\t{}\t{}",
+        sequence, event.topic, event.schema_name, payload
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::engine::constants::property_loader::EventSink;
+    use crate::engine::persistence::outbox::{Outbox, OutboxEvent};
+    use crate::engine::state::delivery_metrics::DeliveryMetrics;
+    use crate::engine::tasks::event_sink_task::{event_line, EventSinkWriter};
+    use std::sync::Arc;
+    use std::time::Duration;
+    use tokio::sync::Notify;
+
+    #[test]
+    fn it_writes_events_as_tab_separated_lines() {
+        let event = OutboxEvent {
+            topic: "orderbook-events-ETHUSD".to_string(),
+            schema_name: "CreateOrder".to_string(),
+            payload: vec![0x0a, 0xff, 0x01],
+        };
+        assert_eq!(
+            event_line(7, &event),
+            "7\torderbook-events-ETHUSD\tCreateOrder\t0aff01"
+        );
+    }
+
+    #[tokio::test]
+    async fn it_counts_events_in_a_dry_run() {
+        let path = std::env::temp_dir().join(format!("gemmy-dry-run-{}", uuid::Uuid::new_v4()));
+        let outbox = Arc::new(Outbox::open(&path).unwrap());
+        let event = OutboxEvent {
+            topic: "orderbook-events-ETHUSD".to_string(),
+            schema_name: "CreateOrder".to_string(),
+            payload: vec![0x0a],
+        };
+        outbox.enqueue(vec![event.clone(), event.clone()]).unwrap();
+        let writer = Arc::new(EventSinkWriter {
+            drain_notification: Arc::new(Notify::new()),
+            outbox: Arc::clone(&outbox),
+            sink: EventSink::Count,
+            delivery_metrics: Arc::new(DeliveryMetrics::default()),
+        });
+        let task = tokio::spawn({
+            let writer = Arc::clone(&writer);
+            async move { writer.run().await }
+        });
+        outbox.enqueue(vec![event]).unwrap();
+        while !outbox.is_empty() {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        writer.drain_notification.notify_one();
+        task.await.unwrap();
+        assert_eq!(writer.delivery_metrics.snapshot().delivered, 3);
+        drop(writer);
+        drop(outbox);
+        std::fs::remove_dir_all(path).unwrap();
+    }
+}
@@ -0,0 +1,51 @@
+use crate::engine::state::runtime_settings::RuntimeSettings;
+use crate::engine::state::server_state::{ServerState, Symbols};
+use crate::engine::utils::time::generate_u128_timestamp;
+use std::sync::Arc;
+use tokio::sync::Notify;
+use tokio::time::sleep;
+use tracing::{debug, info, warn};
+
+/// Periodically sweeps the orderbook of every symbol for expired orders.
+/// The sweep is carried out by the executor of each symbol, so expired orders are cancelled like
+/// any other operation and their cancellation events are published to kafka.
+pub struct OrderExpiry {
+    pub shutdown_notification: Arc<Notify>,
+    pub symbols: Symbols,
+    pub runtime_settings: Arc<RuntimeSettings>,
+}
+
+impl OrderExpiry {
+    pub fn new(state: Arc<ServerState>) -> Self {
+        Self {
+            shutdown_notification: Arc::clone(&state.shutdown_notification),
+            symbols: Arc::clone(&state.symbols),
+            runtime_settings: Arc::clone(&state.runtime_settings),
+        }
+    }
+
+    pub async fn run(&self) {
+        loop {
+            tokio::select! {
+                _ = self.shutdown_notification.notified() => {
+                    info!("shutting down expiry_task");
+                    break;
+                },
+                _ = sleep(self.runtime_settings.current().order_expiry_interval) => {
+                    self.sweep().await
+                }
+            }
+        }
+    }
+
+    async fn sweep(&self) {
+        let symbols: Vec<_> = self.symbols.read().unwrap().values().cloned().collect();
+        let now = generate_u128_timestamp();
+        for symbol_state in symbols {
+            match symbol_state.expire_orders(now).await {
+                Ok(expired) => debug!("expired {} orders of {}", expired, symbol_state.symbol),
+                Err(e) => warn!("failed to expire orders of {}: {}", symbol_state.symbol, e),
+            }
+        }
+    }
+}
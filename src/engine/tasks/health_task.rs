@@ -0,0 +1,157 @@
+use crate::engine::configuration::kafka_configuration::KafkaConfiguration;
+use crate::engine::constants::property_loader::HealthSettings;
+use crate::engine::state::health::{
+    saturation_percent, snapshot_stale, Health, HealthCheck, HealthReport,
+};
+use crate::engine::state::server_state::{ServerState, Symbols};
+use crate::engine::state::symbol_state::SymbolState;
+use crate::engine::utils::time::generate_u128_timestamp;
+use rdkafka::producer::{FutureProducer, Producer};
+use schema_registry_converter::async_impl::schema_registry::{get_all_subjects, SrSettings};
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use tokio::sync::Notify;
+use tokio::time::{sleep, timeout};
+use tonic_health::server::HealthReporter;
+use tonic_health::ServingStatus;
+use tracing::{info, warn};
+
+/// Periodically checks the dependencies of the server: the kafka producer, the schema registry,
+/// the order channels of the executors and the freshness of the orderbook snapshots. The result
+/// is kept for the HTTP health endpoints and reported through the gRPC health service, where the
+/// server as a whole is registered under the empty service name. Kafka and the schema registry
+/// are not checked when running standalone.
+pub struct HealthMonitor {
+    pub shutdown_notification: Arc<Notify>,
+    pub symbols: Symbols,
+    pub health: Arc<Health>,
+    /// The producer of the publisher, `None` when running standalone.
+    pub kafka_producer: Option<Arc<FutureProducer>>,
+    pub sr_settings: Arc<SrSettings>,
+    pub settings: HealthSettings,
+    pub reporter: HealthReporter,
+}
+
+impl HealthMonitor {
+    pub fn new(
+        kafka_configuration: Arc<KafkaConfiguration>,
+        state: Arc<ServerState>,
+        settings: HealthSettings,
+        reporter: HealthReporter,
+    ) -> Self {
+        Self {
+            shutdown_notification: Arc::clone(&state.shutdown_notification),
+            symbols: Arc::clone(&state.symbols),
+            health: Arc::clone(&state.health),
+            kafka_producer: state.kafka_producer.clone(),
+            sr_settings: Arc::clone(&kafka_configuration.kafka_admin_properties.sr_settings),
+            settings,
+            reporter,
+        }
+    }
+
+    pub async fn run(&self) {
+        let mut reporter = self.reporter.clone();
+        loop {
+            tokio::select! {
+                _ = self.shutdown_notification.notified() => {
+                    reporter.set_service_status("", ServingStatus::NotServing).await;
+                    info!("shutting down health_task");
+                    break;
+                },
+                _ = sleep(self.settings.interval) => {
+                    let was_ready = self.health.report().is_ready();
+                    let report = self.check().await;
+                    let ready = report.is_ready();
+                    if was_ready && !ready {
+                        warn!("server is no longer ready: {:?}", report);
+                    } else if !was_ready && ready {
+                        info!("server is ready");
+                    }
+                    let status = match ready {
+                        true => ServingStatus::Serving,
+                        false => ServingStatus::NotServing,
+                    };
+                    reporter.set_service_status("", status).await;
+                    self.health.update(report);
+                }
+            }
+        }
+    }
+
+    async fn check(&self) -> HealthReport {
+        let symbols: Vec<_> = self.symbols.read().unwrap().values().cloned().collect();
+        let mut checks = Vec::new();
+        if let Some(producer) = &self.kafka_producer {
+            checks.push(self.check_kafka_producer(Arc::clone(producer)).await);
+            checks.push(self.check_schema_registry().await);
+        }
+        checks.push(self.check_executor_channels(&symbols));
+        checks.push(self.check_snapshots(&symbols));
+        HealthReport {
+            checked_at: generate_u128_timestamp(),
+            executors_running: symbols
+                .iter()
+                .all(|symbol_state| symbol_state.executor_running()),
+            checks,
+        }
+    }
+
+    /// Fetches the cluster metadata, which fails unless a broker is reachable.
+    async fn check_kafka_producer(&self, producer: Arc<FutureProducer>) -> HealthCheck {
+        let timeout = self.settings.timeout;
+        let fetched = tokio::task::spawn_blocking(move || {
+            producer
+                .client()
+                .fetch_metadata(None, timeout)
+                .map_err(|e| e.to_string())
+        })
+        .await;
+        let problems = match fetched {
+            Ok(Ok(_)) => Vec::new(),
+            Ok(Err(e)) => vec![e],
+            Err(e) => vec![e.to_string()],
+        };
+        HealthCheck::from_problems("kafka_producer", problems)
+    }
+
+    async fn check_schema_registry(&self) -> HealthCheck {
+        let problems =
+            match timeout(self.settings.timeout, get_all_subjects(&self.sr_settings)).await {
+                Ok(Ok(_)) => Vec::new(),
+                Ok(Err(e)) => vec![e.to_string()],
+                Err(_) => vec!["timed out".to_string()],
+            };
+        HealthCheck::from_problems("schema_registry", problems)
+    }
+
+    fn check_executor_channels(&self, symbols: &[Arc<SymbolState>]) -> HealthCheck {
+        let problems = symbols
+            .iter()
+            .filter_map(|symbol_state| {
+                let saturation = saturation_percent(
+                    symbol_state.order_sender.capacity(),
+                    symbol_state.order_sender.max_capacity(),
+                );
+                (saturation > self.settings.max_channel_saturation)
+                    .then(|| format!("{} at {}%", symbol_state.symbol, saturation))
+            })
+            .collect();
+        HealthCheck::from_problems("executor_channels", problems)
+    }
+
+    fn check_snapshots(&self, symbols: &[Arc<SymbolState>]) -> HealthCheck {
+        let problems = symbols
+            .iter()
+            .filter(|symbol_state| {
+                snapshot_stale(
+                    *symbol_state.last_snapshot.lock().unwrap(),
+                    symbol_state.operation_count.load(Ordering::Relaxed),
+                    self.settings.max_snapshot_age,
+                )
+            })
+            .map(|symbol_state| format!("{} is stale", symbol_state.symbol))
+            .collect();
+        HealthCheck::from_problems("snapshots", problems)
+    }
+}
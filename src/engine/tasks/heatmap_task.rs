@@ -0,0 +1,63 @@
+use crate::engine::state::heatmap::LiquiditySample;
+use crate::engine::state::server_state::{ServerState, Symbols};
+use crate::engine::utils::time::generate_u128_timestamp;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Notify;
+use tokio::time::sleep;
+use tracing::info;
+
+/// Periodically samples the top levels of the latest snapshot of every symbol's book, which
+/// the heatmap endpoint aggregates into time buckets.
+pub struct HeatmapSampler {
+    pub shutdown_notification: Arc<Notify>,
+    pub symbols: Symbols,
+    pub interval: Duration,
+    pub levels: usize,
+}
+
+impl HeatmapSampler {
+    pub fn new(state: Arc<ServerState>, interval: Duration, levels: usize) -> Self {
+        Self {
+            shutdown_notification: Arc::clone(&state.shutdown_notification),
+            symbols: Arc::clone(&state.symbols),
+            interval,
+            levels,
+        }
+    }
+
+    pub async fn run(&self) {
+        loop {
+            tokio::select! {
+                _ = self.shutdown_notification.notified() => {
+                    info!("shutting down heatmap_task");
+                    break;
+                },
+                _ = sleep(self.interval) => {
+                    self.sample()
+                }
+            }
+        }
+    }
+
+    fn sample(&self) {
+        let symbols: Vec<_> = self.symbols.read().unwrap().values().cloned().collect();
+        let timestamp = generate_u128_timestamp();
+        for symbol_state in symbols {
+            let secondary = symbol_state.orderbook_manager.get_secondary();
+            symbol_state.heatmap.record(LiquiditySample {
+                timestamp,
+                bids: secondary
+                    .iter_bids()
+                    .filter(|level| level.quantity > 0)
+                    .take(self.levels)
+                    .collect(),
+                asks: secondary
+                    .iter_asks()
+                    .filter(|level| level.quantity > 0)
+                    .take(self.levels)
+                    .collect(),
+            });
+        }
+    }
+}
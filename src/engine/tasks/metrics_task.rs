@@ -0,0 +1,92 @@
+use crate::engine::state::delivery_metrics::DeliveryMetrics;
+use crate::engine::state::runtime_settings::RuntimeSettings;
+use crate::engine::state::server_state::{ServerState, Symbols};
+use std::sync::Arc;
+use tokio::sync::Notify;
+use tokio::time::sleep;
+use tracing::{info, warn};
+
+/// Periodically logs a summary of the engine metrics.
+pub struct MetricsReporter {
+    pub shutdown_notification: Arc<Notify>,
+    pub symbols: Symbols,
+    pub delivery_metrics: Arc<DeliveryMetrics>,
+    pub runtime_settings: Arc<RuntimeSettings>,
+}
+
+impl MetricsReporter {
+    pub fn new(state: Arc<ServerState>) -> Self {
+        Self {
+            shutdown_notification: Arc::clone(&state.shutdown_notification),
+            symbols: Arc::clone(&state.symbols),
+            delivery_metrics: Arc::clone(&state.delivery_metrics),
+            runtime_settings: Arc::clone(&state.runtime_settings),
+        }
+    }
+
+    pub async fn run(&self) {
+        loop {
+            tokio::select! {
+                _ = self.shutdown_notification.notified() => {
+                    info!("shutting down metrics_task");
+                    break;
+                },
+                _ = sleep(self.runtime_settings.current().metrics_report_interval) => {
+                    self.report().await
+                }
+            }
+        }
+    }
+
+    async fn report(&self) {
+        let symbols: Vec<_> = self.symbols.read().unwrap().values().cloned().collect();
+        for symbol_state in symbols {
+            for summary in symbol_state.latency_metrics.summaries() {
+                info!(
+                    "{} {} latency: count={} p50={:?} p90={:?} p99={:?} p999={:?} max={:?}",
+                    symbol_state.symbol,
+                    summary.operation.name(),
+                    summary.count,
+                    summary.p50,
+                    summary.p90,
+                    summary.p99,
+                    summary.p999,
+                    summary.max
+                );
+            }
+            for metrics in symbol_state.channel_metrics() {
+                info!(
+                    "{} {} channel: depth={} high_watermark={} capacity={}",
+                    symbol_state.symbol,
+                    metrics.channel,
+                    metrics.depth,
+                    metrics.high_watermark,
+                    metrics.capacity
+                );
+            }
+            for price in symbol_state.average_prices.latest() {
+                info!(
+                    "{} average prices over {:?}: vwap={:?} twap={:?} volume={}",
+                    symbol_state.symbol, price.window, price.vwap, price.twap, price.volume
+                );
+            }
+            match symbol_state.memory_stats().await {
+                Ok(stats) => info!(
+                    "{} memory: store={}/{} slots peak={} capacity={} levels={}/{} empty={} queue_allocations={} bytes={}",
+                    symbol_state.symbol,
+                    stats.store_occupancy,
+                    stats.store_slots,
+                    stats.peak_store_occupancy,
+                    stats.store_capacity,
+                    stats.bid_levels,
+                    stats.ask_levels,
+                    stats.empty_levels,
+                    stats.queue_allocations,
+                    stats.allocated_bytes
+                ),
+                Err(e) => warn!("failed to read memory stats of {}: {}", symbol_state.symbol, e),
+            }
+        }
+        info!("delivery metrics: {:?}", self.delivery_metrics.snapshot());
+    }
+}
@@ -1,4 +1,20 @@
+pub mod average_price_task;
+pub mod compaction_task;
+pub mod delayed_data_task;
+pub mod election_task;
+pub mod event_sink_task;
+pub mod expiry_task;
+pub mod health_task;
+pub mod heatmap_task;
+pub mod metrics_task;
 pub mod order_exec_task;
+pub mod overload_task;
+pub mod publisher_task;
+pub mod reload_task;
+pub mod replay_task;
+pub mod replication_task;
+pub mod session_task;
 pub mod shutdown_task;
 pub mod snapshot_task;
+pub mod snapshot_upload_task;
 pub mod task_manager;
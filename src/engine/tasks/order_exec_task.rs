@@ -1,61 +1,222 @@
-use crate::core::models::Operation;
-use crate::engine::configuration::kafka_configuration::KafkaConfiguration;
-use crate::engine::configuration::server_configuration::ServerConfiguration;
+use crate::core::errors::{OrderBookError, Rejection};
+use crate::core::models::{
+    CompactionReport, ExecutionResult, LimitOrder, MarketOrder, MemoryStats, Operation,
+};
+use crate::core::types::OrderId;
+use crate::engine::accounts::ledger::{Ledger, SymbolAssets};
+use crate::engine::accounts::market_making::MarketMakerProtection;
+use crate::engine::accounts::settlement::{SettlementInstruction, SettlementSettings};
+use crate::engine::accounts::throttle::Throttle;
+use crate::engine::persistence::crash_dump;
+use crate::engine::persistence::order_store::PersistentOrderStore;
+use crate::engine::persistence::outbox::{Outbox, OutboxEvent};
+use crate::engine::persistence::wal::WriteAheadLog;
+use crate::engine::risk::hooks::TradeHooks;
+use crate::engine::risk::limits_check::LimitsRiskCheck;
+use crate::engine::risk::{BookStats, RiskCheck};
 use crate::engine::services::orderbook_manager_service::OrderbookManager;
+use crate::engine::state::average_price::AveragePrices;
+use crate::engine::state::book_feed::{BookEvent, BookFeed, BookSubscription};
+use crate::engine::state::candles::Candles;
+use crate::engine::state::channel_metrics::ExecutorWatermarks;
+#[cfg(feature = "chaos")]
+use crate::engine::state::faults::Faults;
+use crate::engine::state::latency_metrics::{LatencyMetrics, OperationKind};
+use crate::engine::state::order_history::OrderHistory;
+use crate::engine::state::overload::Overload;
+use crate::engine::state::quotes::{FirmQuote, QuoteError};
+use crate::engine::state::recent_operations::RecentOperations;
+use crate::engine::state::replication::{Replication, ReplicationFeed, ReplicationSubscription};
+use crate::engine::state::runtime_settings::RuntimeSettings;
 use crate::engine::state::server_state::ServerState;
-use crate::engine::utils::protobuf::exec_to_proto_encoded;
+use crate::engine::state::session::{Session, SessionPhase};
+use crate::engine::state::symbol_state::{SymbolState, TradingStatus};
+use crate::engine::state::ticker::Ticker;
+use crate::engine::state::volume_profile::VolumeProfile;
+use crate::engine::utils::protobuf::{
+    exec_to_proto, protection_event_to_proto, settlement_to_proto, trade_to_proto,
+};
 use crate::engine::utils::time::generate_u128_timestamp;
-use rdkafka::producer::{FutureProducer, FutureRecord};
-use rdkafka::util::Timeout;
-use schema_registry_converter::async_impl::proto_raw::ProtoRawEncoder;
-use schema_registry_converter::async_impl::schema_registry::SrSettings;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use tokio::sync::mpsc::Receiver;
-use tokio::sync::Notify;
-use tracing::{error, info};
+use tokio::sync::oneshot;
+use tokio::time::Interval;
+use tracing::{debug, error, info, info_span, warn, Instrument};
+
+/// Requests an operator can send to the executor of a symbol, answered once carried out.
+pub enum ExecutorCommand {
+    /// Executes every queued operation right away, answering with the number of operations.
+    Drain(oneshot::Sender<usize>),
+    /// Cancels every resting order, answering with the number of orders cancelled.
+    Clear(oneshot::Sender<usize>),
+    /// Cancels every resting order that expired by the passed time in nanoseconds since the unix
+    /// epoch, answering with the number of orders cancelled.
+    Expire(u128, oneshot::Sender<usize>),
+    /// Drains the queue, checkpoints the write ahead log and stops the executor, answering with
+    /// the number of operations drained.
+    Stop(oneshot::Sender<usize>),
+    /// Executes the market order for a firm quote if the book still fills it at the quoted price
+    /// or better, answering with the reason otherwise.
+    ExecuteQuote(
+        FirmQuote,
+        MarketOrder,
+        oneshot::Sender<Result<(), QuoteError>>,
+    ),
+    /// Executes the quotes of every level of a mass quote as a batch of their own, answering with
+    /// the outcome of every quote, or `None` if the batch was dropped.
+    MassQuote(
+        Vec<Operation>,
+        oneshot::Sender<Option<Vec<Result<(), OrderBookError>>>>,
+    ),
+    /// Answers with the memory held by the book and how much of it is in use.
+    MemoryStats(oneshot::Sender<MemoryStats>),
+    /// Compacts the book, answering with what the compaction gave back.
+    Compact(oneshot::Sender<CompactionReport>),
+    /// Answers with the resting orders and a subscription to the book feed from there on.
+    SubscribeBook(oneshot::Sender<BookSubscription>),
+    /// Answers with the resting orders and a subscription to the replication feed from there on.
+    SubscribeReplication(oneshot::Sender<ReplicationSubscription>),
+    /// Replaces the book of a replica with the last trade price, the call and the resting orders
+    /// of its primary.
+    ResetReplica(u64, bool, Vec<LimitOrder>),
+    /// Executes a batch the primary executed at the passed time on a replica.
+    Replicate(u128, Vec<Operation>),
+    /// Starts or ends the call to follow the phase of the trading session, answering with
+    /// whether it did either.
+    FollowSession(oneshot::Sender<bool>),
+    /// Lets the account quote again once its market maker protection tripped, answering with
+    /// whether it was locked.
+    ResetProtection(String, oneshot::Sender<bool>),
+    /// Snapshots the book, so that the copy is never taken while a batch changes it.
+    Snapshot(oneshot::Sender<()>),
+}
 
 pub struct Executor {
+    pub symbol: String,
+    /// The state of the symbol, whose trading is halted once its batches can no longer be logged.
+    pub symbol_state: Arc<SymbolState>,
     pub batch_size: usize,
     pub batch_timeout: Duration,
-    pub shutdown_notification: Arc<Notify>,
+    pub runtime_settings: Arc<RuntimeSettings>,
     pub orderbook_manager: Arc<OrderbookManager>,
     pub kafka_topic: String,
-    pub kafka_producer: Arc<FutureProducer>,
-    pub sr_settings: Arc<SrSettings>,
+    pub outbox: Arc<Outbox>,
+    pub write_ahead_log: Option<Arc<WriteAheadLog>>,
+    pub order_store: Option<Arc<PersistentOrderStore>>,
+    pub latency_metrics: Arc<LatencyMetrics>,
+    pub overload: Arc<Overload>,
+    pub recent_operations: Option<Arc<RecentOperations>>,
+    pub order_history: Arc<OrderHistory>,
+    pub ticker: Arc<Ticker>,
+    pub candles: Arc<Candles>,
+    pub average_prices: Arc<AveragePrices>,
+    pub volume_profile: Arc<VolumeProfile>,
+    pub book_feed: Arc<BookFeed>,
+    pub replication_feed: Arc<ReplicationFeed>,
+    pub replication: Option<Arc<Replication>>,
+    pub operation_count: Arc<AtomicU64>,
+    pub ledger: Option<Arc<Ledger>>,
+    pub assets: SymbolAssets,
+    pub risk_check: Arc<dyn RiskCheck>,
+    pub hooks: TradeHooks,
+    /// The trading session, whose pre-open is a call that is uncrossed at the open.
+    pub session: Arc<Session>,
+    pub settlement: Option<SettlementSettings>,
+    pub trades_topic: Option<String>,
+    pub throttle: Option<Arc<Throttle>>,
+    pub protection: Option<MarketMakerProtection>,
     pub rx: Receiver<Operation>,
+    pub commands: Receiver<ExecutorCommand>,
+    pub watermarks: Arc<ExecutorWatermarks>,
+    #[cfg(feature = "chaos")]
+    pub faults: Arc<Faults>,
 }
 
 impl Executor {
-    pub fn new(
-        server_configuration: Arc<ServerConfiguration>,
-        kafka_configuration: Arc<KafkaConfiguration>,
-        state: Arc<ServerState>,
-        rx: Receiver<Operation>,
-    ) -> Executor {
+    pub fn new(state: Arc<ServerState>, symbol_state: Arc<SymbolState>) -> Executor {
+        let channels = symbol_state
+            .take_executor_channels()
+            .expect("executor channels of the symbol are already taken");
+        let tunable_properties = state.runtime_settings.current();
         Self {
-            batch_size: server_configuration.server_properties.order_exec_batch_size,
-            batch_timeout: server_configuration
-                .server_properties
-                .order_exec_batch_timeout,
-            shutdown_notification: Arc::clone(&state.shutdown_notification),
-            orderbook_manager: Arc::clone(&state.orderbook_manager),
-            kafka_topic: kafka_configuration
-                .kafka_admin_properties
-                .kafka_topic
-                .clone(),
-            kafka_producer: Arc::clone(&state.kafka_producer),
-            sr_settings: Arc::clone(&kafka_configuration.kafka_admin_properties.sr_settings),
-            rx,
+            symbol: symbol_state.symbol.clone(),
+            symbol_state: Arc::clone(&symbol_state),
+            batch_size: tunable_properties.order_exec_batch_size,
+            batch_timeout: tunable_properties.order_exec_batch_timeout,
+            runtime_settings: Arc::clone(&state.runtime_settings),
+            orderbook_manager: Arc::clone(&symbol_state.orderbook_manager),
+            kafka_topic: symbol_state.kafka_topic.clone(),
+            outbox: Arc::clone(&state.outbox),
+            write_ahead_log: symbol_state.write_ahead_log.clone(),
+            order_store: symbol_state.order_store.clone(),
+            latency_metrics: Arc::clone(&symbol_state.latency_metrics),
+            overload: Arc::clone(&symbol_state.overload),
+            recent_operations: symbol_state.recent_operations.clone(),
+            order_history: Arc::clone(&symbol_state.order_history),
+            ticker: Arc::clone(&symbol_state.ticker),
+            candles: Arc::clone(&symbol_state.candles),
+            average_prices: Arc::clone(&symbol_state.average_prices),
+            volume_profile: Arc::clone(&symbol_state.volume_profile),
+            book_feed: Arc::clone(&symbol_state.book_feed),
+            replication_feed: Arc::clone(&symbol_state.replication_feed),
+            replication: state.replication.clone(),
+            operation_count: Arc::clone(&symbol_state.operation_count),
+            ledger: state.ledger.clone(),
+            assets: symbol_state.specification.assets.clone(),
+            risk_check: state.risk_check.clone().unwrap_or_else(|| {
+                Arc::new(LimitsRiskCheck::new(
+                    symbol_state.specification.resting_limits,
+                ))
+            }),
+            hooks: state.trade_hooks.clone(),
+            session: Arc::clone(&state.session),
+            settlement: state.settlement.clone(),
+            trades_topic: state.trades_topic.clone(),
+            throttle: state.throttle.clone(),
+            protection: state.protection_limits.map(MarketMakerProtection::new),
+            rx: channels.orders,
+            commands: channels.commands,
+            watermarks: Arc::clone(&symbol_state.executor_watermarks),
+            #[cfg(feature = "chaos")]
+            faults: Arc::clone(&state.faults),
         }
     }
 
+    /// Starts the executor of the symbol and keeps its handle on the symbol, so it can be awaited
+    /// once stopped.
+    pub fn spawn(state: Arc<ServerState>, symbol_state: Arc<SymbolState>) {
+        let span = info_span!("order_exec_task", symbol = %symbol_state.symbol);
+        let executor_task = tokio::spawn(
+            crash_dump::run_executor(symbol_state.symbol.clone(), {
+                let symbol_state = Arc::clone(&symbol_state);
+                async move {
+                    Executor::new(state, symbol_state).run().await;
+                }
+            })
+            .instrument(span),
+        );
+        symbol_state.set_executor_task(executor_task);
+    }
+
     pub async fn run(&mut self) {
         let mut batch = Vec::with_capacity(self.batch_size);
         let mut batch_timer = tokio::time::interval(self.batch_timeout);
         loop {
+            // commands go first, so that the batches a replica queued before it was promoted
+            // execute ahead of the orders it takes afterwards
             tokio::select! {
+                biased;
+                Some(command) = self.commands.recv() => {
+                    self.watermarks.commands.record(self.commands.len() + 1);
+                    if !self.handle_command(command, &mut batch).await {
+                        info!("stopping order_exec_task");
+                        break;
+                    }
+                }
                 Some(order) = self.rx.recv() => {
+                    self.watermarks.orders.record(self.rx.len() + 1);
                     batch.push(order);
                     if batch.len() >= self.batch_size {
                         self.process_batch(&batch).await;
@@ -67,46 +228,1078 @@ impl Executor {
                         self.process_batch(&batch).await;
                         batch.clear();
                     }
+                    self.apply_settings(&mut batch_timer);
                 }
-                _ = self.shutdown_notification.notified() => {
-                    info!("shutting down order_exec_task");
-                    break;
+            }
+        }
+    }
+
+    /// Picks up reloaded batch settings, restarting the batch timer if its period changed.
+    fn apply_settings(&mut self, batch_timer: &mut Interval) {
+        let tunable_properties = self.runtime_settings.current();
+        self.batch_size = tunable_properties.order_exec_batch_size;
+        if self.batch_timeout != tunable_properties.order_exec_batch_timeout {
+            self.batch_timeout = tunable_properties.order_exec_batch_timeout;
+            *batch_timer = tokio::time::interval(self.batch_timeout);
+            info!("batch timeout changed to {:?}", self.batch_timeout);
+        }
+    }
+
+    /// Carries out an operator command.
+    ///
+    /// # Returns
+    ///
+    /// * `false` if the executor has to stop.
+    async fn handle_command(
+        &mut self,
+        command: ExecutorCommand,
+        batch: &mut Vec<Operation>,
+    ) -> bool {
+        match command {
+            ExecutorCommand::Drain(reply) => {
+                let drained = self.drain(batch).await;
+                let _ = reply.send(drained);
+            }
+            ExecutorCommand::Stop(reply) => {
+                let drained = self.drain(batch).await;
+                self.checkpoint();
+                let _ = reply.send(drained);
+                return false;
+            }
+            ExecutorCommand::Clear(reply) => {
+                let ids: Vec<u128> = unsafe {
+                    (*self.orderbook_manager.get_primary())
+                        .resting_orders()
+                        .iter()
+                        .map(|order| order.id)
+                        .collect()
+                };
+                let cancelled = self.cancel(ids, batch).await;
+                info!("cleared {} resting orders", cancelled);
+                let _ = reply.send(cancelled);
+            }
+            ExecutorCommand::Expire(now, reply) => {
+                let ids = unsafe { (*self.orderbook_manager.get_primary()).expired_orders(now) };
+                let cancelled = self.cancel(ids, batch).await;
+                if cancelled > 0 {
+                    info!("cancelled {} expired orders", cancelled);
                 }
+                let _ = reply.send(cancelled);
+            }
+            ExecutorCommand::MemoryStats(reply) => {
+                let stats = unsafe { (*self.orderbook_manager.get_primary()).memory_stats() };
+                let _ = reply.send(stats);
+            }
+            ExecutorCommand::Compact(reply) => {
+                let report = unsafe { (*self.orderbook_manager.get_primary()).compact() };
+                let _ = reply.send(report);
+            }
+            ExecutorCommand::SubscribeBook(reply) => {
+                let orders = unsafe { (*self.orderbook_manager.get_primary()).resting_orders() };
+                let _ = reply.send(self.book_feed.subscribe(orders));
+            }
+            ExecutorCommand::ExecuteQuote(quote, order, reply) => {
+                let executed = self.execute_quote(quote, order, batch).await;
+                let _ = reply.send(executed);
+            }
+            ExecutorCommand::MassQuote(quotes, reply) => {
+                let outcomes = self.mass_quote(&quotes, batch).await;
+                let _ = reply.send(outcomes);
+            }
+            ExecutorCommand::SubscribeReplication(reply) => {
+                let primary = unsafe { &*self.orderbook_manager.get_primary() };
+                let subscription = self.replication_feed.subscribe(
+                    primary.resting_orders(),
+                    primary.get_last_trade_price(),
+                    primary.in_call(),
+                );
+                let _ = reply.send(subscription);
+            }
+            // a follower may still hand over a batch after the server was promoted
+            ExecutorCommand::ResetReplica(..) | ExecutorCommand::Replicate(..)
+                if self.accepts_orders() =>
+            {
+                warn!("dropping replicated state, server is primary");
+            }
+            ExecutorCommand::ResetReplica(last_trade_price, call, orders) => {
+                self.reset(last_trade_price, call, orders);
+            }
+            ExecutorCommand::Replicate(now, operations) => {
+                let checks: Vec<_> = operations.iter().map(|_| Ok(())).collect();
+                self.execute_batch(now, &operations, &checks, true).await;
+            }
+            ExecutorCommand::FollowSession(reply) => {
+                // orders queued in the pre-open rest in the call before it is uncrossed
+                if !batch.is_empty() {
+                    self.process_batch(batch).await;
+                    batch.clear();
+                }
+                let followed = self.follow_session().await;
+                let _ = reply.send(followed);
+            }
+            ExecutorCommand::Snapshot(reply) => {
+                self.symbol_state.take_snapshot();
+                let _ = reply.send(());
+            }
+            ExecutorCommand::ResetProtection(account, reply) => {
+                let reset = self
+                    .protection
+                    .as_ref()
+                    .is_some_and(|protection| protection.reset(&account));
+                let _ = reply.send(reset);
+            }
+        }
+        true
+    }
+
+    /// Whether this executor takes order flow of its own, which only the primary of an
+    /// active/standby pair does.
+    fn accepts_orders(&self) -> bool {
+        self.replication
+            .as_ref()
+            .is_none_or(|replication| replication.is_primary())
+    }
+
+    /// Replaces the book with the state of the primary, writing it through to the write ahead
+    /// log and the persistent order store.
+    fn reset(&self, last_trade_price: u64, call: bool, orders: Vec<LimitOrder>) {
+        let primary = unsafe { &mut *self.orderbook_manager.get_primary() };
+        for order in primary.resting_orders() {
+            primary.execute(Operation::Cancel(OrderId(order.id)));
+        }
+        // the book is empty by now, so ending a call matches nothing
+        if primary.in_call() != call {
+            primary.execute(match call {
+                true => Operation::Call,
+                false => Operation::Uncross,
+            });
+        }
+        let restored = orders.len();
+        for order in orders {
+            primary.execute(Operation::Limit(order));
+        }
+        primary.set_last_trade_price(last_trade_price);
+        self.checkpoint();
+        if let Some(order_store) = &self.order_store {
+            if let Err(e) = order_store.resync(primary) {
+                error!("failed to resync persistent order store: {}", e);
+            }
+        }
+        self.orderbook_manager.snapshot();
+        info!("reset book to {} resting orders of the primary", restored);
+    }
+
+    /// Starts a call once the session is in the pre-open and uncrosses it once the session left
+    /// it. Both are logged and published like any other operation, so replicas and the log follow
+    /// the call of the primary rather than the session of their own.
+    ///
+    /// # Returns
+    ///
+    /// * Whether the call was started or ended.
+    async fn follow_session(&self) -> bool {
+        if !self.accepts_orders() {
+            return false;
+        }
+        let in_call = unsafe { (*self.orderbook_manager.get_primary()).in_call() };
+        let operation = match self.session.phase() {
+            SessionPhase::PreOpen if !in_call => Operation::Call,
+            SessionPhase::Open | SessionPhase::Closed if in_call => Operation::Uncross,
+            _ => return false,
+        };
+        self.execute_batch(generate_u128_timestamp(), &[operation], &[Ok(())], false)
+            .await
+            .is_some()
+    }
+
+    /// Executes the pending batch, then checks the quote against the book and executes its
+    /// market order. Nothing else runs in between, so the order fills the way it was checked,
+    /// unless it is rejected, fails in the book or is dropped, which is answered as an error.
+    async fn execute_quote(
+        &mut self,
+        quote: FirmQuote,
+        order: MarketOrder,
+        batch: &mut Vec<Operation>,
+    ) -> Result<(), QuoteError> {
+        if !batch.is_empty() {
+            self.process_batch(batch).await;
+            batch.clear();
+        }
+        if quote.expires_at <= generate_u128_timestamp() {
+            return Err(QuoteError::Expired);
+        }
+        let (filled, notional, _) =
+            unsafe { (*self.orderbook_manager.get_primary()).sweep(quote.side, quote.quantity) };
+        if !quote.honoured_by(filled, notional) {
+            return Err(QuoteError::Liquidity);
+        }
+        let outcome = self.process_batch(&[Operation::Market(order)]).await;
+        match outcome.and_then(|outcomes| outcomes.into_iter().next()) {
+            Some(Ok(())) => Ok(()),
+            Some(Err(e)) => Err(QuoteError::Failed(e)),
+            None => Err(QuoteError::Dropped),
+        }
+    }
+
+    /// Executes the pending batch, then the quotes of a mass quote. Nothing else runs in between
+    /// its levels, even if there are more of them than fit a batch.
+    async fn mass_quote(
+        &mut self,
+        quotes: &[Operation],
+        batch: &mut Vec<Operation>,
+    ) -> Option<Vec<Result<(), OrderBookError>>> {
+        if !batch.is_empty() {
+            self.process_batch(batch).await;
+            batch.clear();
+        }
+        self.process_batch(quotes).await
+    }
+
+    /// Executes the pending batch followed by a cancel of every passed order, so that the
+    /// cancellations are logged and published like any other operation.
+    async fn cancel(&mut self, ids: Vec<u128>, batch: &mut Vec<Operation>) -> usize {
+        // a replica leaves cancelling to its primary, whose cancels it replicates
+        if !self.accepts_orders() {
+            return 0;
+        }
+        if !batch.is_empty() {
+            self.process_batch(batch).await;
+            batch.clear();
+        }
+        let cancels: Vec<Operation> = ids
+            .into_iter()
+            .map(OrderId)
+            .map(Operation::Cancel)
+            .collect();
+        for chunk in cancels.chunks(self.batch_size) {
+            self.process_batch(chunk).await;
+        }
+        cancels.len()
+    }
+
+    /// Executes the pending batch along with every operation waiting in the queue.
+    async fn drain(&mut self, batch: &mut Vec<Operation>) -> usize {
+        let mut drained = batch.len();
+        self.watermarks.orders.record(self.rx.len());
+        while let Ok(order) = self.rx.try_recv() {
+            batch.push(order);
+            drained += 1;
+            if batch.len() >= self.batch_size {
+                self.process_batch(batch).await;
+                batch.clear();
             }
         }
+        if !batch.is_empty() {
+            self.process_batch(batch).await;
+            batch.clear();
+        }
+        info!("drained {} queued operations", drained);
+        drained
     }
 
-    async fn process_batch(&self, batch: &[Operation]) {
+    fn checkpoint(&self) {
+        if let Some(write_ahead_log) = &self.write_ahead_log {
+            let primary = self.orderbook_manager.get_primary();
+            match unsafe { write_ahead_log.checkpoint(&*primary) } {
+                Ok(()) => info!("checkpointed write ahead log"),
+                Err(e) => error!("failed to checkpoint write ahead log: {}", e),
+            }
+        }
+    }
+
+    /// Checks the operations of the batch and executes those that pass. Once the market maker
+    /// protection of an account trips, its quotes are pulled before the rest of the batch is
+    /// executed, in which the quotes of the account are rejected.
+    ///
+    /// # Returns
+    ///
+    /// * Whether every operation executed or why it failed, `None` if the batch, or the rest of
+    ///   it after quotes were pulled, was dropped.
+    async fn process_batch(&self, batch: &[Operation]) -> Option<Vec<Result<(), OrderBookError>>> {
+        // operations queued before the server was fenced are never executed
+        if !self.accepts_orders() {
+            warn!("not the primary, dropping {} operations", batch.len());
+            return None;
+        }
+        // the session may have moved on before its scheduler got to this symbol
+        self.follow_session().await;
         let primary = self.orderbook_manager.get_primary();
+        // rejected operations are never logged, so replaying the log skips them too
+        let mut stats =
+            unsafe { BookStats::capture(&*primary, self.risk_check.needs_resting_totals()) };
+        let mut checks: Vec<Result<(), Rejection>> = batch
+            .iter()
+            .map(|order| {
+                let check = self
+                    .risk_check
+                    .check(order, &stats)
+                    .and_then(|()| self.hooks.before_trade(order, &stats));
+                if check.is_ok() {
+                    stats.record(order);
+                }
+                check
+            })
+            .collect();
+        if let Some(ledger) = &self.ledger {
+            let ledger_checks = unsafe { ledger.check_batch(batch, &self.assets, &*primary) };
+            for (check, ledger_check) in checks.iter_mut().zip(ledger_checks) {
+                if check.is_ok() {
+                    *check = ledger_check;
+                }
+            }
+        }
+        let mut outcomes = Vec::with_capacity(batch.len());
+        loop {
+            let (remaining, checks) = (&batch[outcomes.len()..], &mut checks[outcomes.len()..]);
+            self.reject_locked_quotes(remaining, checks);
+            let executed = self
+                .execute_batch(generate_u128_timestamp(), remaining, checks, false)
+                .await;
+            self.pull_tripped_quotes().await;
+            outcomes.extend(executed?);
+            if outcomes.len() == batch.len() {
+                return Some(outcomes);
+            }
+        }
+    }
+
+    /// Rejects the quotes of every account whose market maker protection tripped, other than
+    /// those withdrawing both sides.
+    fn reject_locked_quotes(&self, batch: &[Operation], checks: &mut [Result<(), Rejection>]) {
+        let Some(protection) = &self.protection else {
+            return;
+        };
+        for (order, check) in batch.iter().zip(checks) {
+            let Operation::Quote { bid, ask } = order else {
+                continue;
+            };
+            if check.is_ok()
+                && (bid.quantity > 0 || ask.quantity > 0)
+                && protection.is_locked_quote(bid.id)
+            {
+                *check = Err(Rejection::ProtectionTripped);
+            }
+        }
+    }
+
+    /// Pulls the quotes left in the book by every account whose market maker protection tripped,
+    /// publishing why along with the orders pulled.
+    async fn pull_tripped_quotes(&self) {
+        let Some(protection) = &self.protection else {
+            return;
+        };
+        let tripped = protection.take_tripped();
+        if tripped.is_empty() {
+            return;
+        }
+        let mut pulls = Vec::new();
+        let mut events = Vec::new();
+        for trip in tripped {
+            let primary = self.orderbook_manager.get_primary();
+            let pulled: Vec<u128> = protection
+                .quotes_of(&trip.account)
+                .into_iter()
+                .filter(|id| unsafe { (*primary).get_order(*id) }.is_some())
+                .collect();
+            warn!(
+                account = %trip.account,
+                pulled = pulled.len(),
+                "market maker protection tripped, {}",
+                trip.breach
+            );
+            pulls.extend(pulled.iter().copied().map(OrderId).map(Operation::Cancel));
+            let (payload, schema_name) = protection_event_to_proto(
+                trip,
+                self.symbol.clone(),
+                &pulled,
+                generate_u128_timestamp(),
+            );
+            events.push(OutboxEvent {
+                topic: self.kafka_topic.clone(),
+                schema_name: schema_name.to_string(),
+                payload,
+            });
+        }
+        // the pulls only cancel, so they are never rejected and trip nothing further
+        let checks: Vec<_> = pulls.iter().map(|_| Ok(())).collect();
+        self.execute_batch(generate_u128_timestamp(), &pulls, &checks, false)
+            .await;
+        if let Err(e) = self.outbox.enqueue(events) {
+            error!("failed to enqueue protection events to outbox: {}", e);
+        }
+    }
+
+    /// Logs the operations that passed their checks and executes the batch at the passed time.
+    /// The batch stops short once the market maker protection of an account trips, the
+    /// operations left are taken off the log again and executed once its quotes were pulled.
+    ///
+    /// # Arguments
+    ///
+    /// * `replicated` - Whether the batch was executed by the primary, which already accounted
+    ///   for it in its ledger and published its events.
+    ///
+    /// # Returns
+    ///
+    /// * Whether every executed operation executed or why it failed, `None` if the batch could
+    ///   not be logged and was dropped.
+    async fn execute_batch(
+        &self,
+        now: u128,
+        batch: &[Operation],
+        checks: &[Result<(), Rejection>],
+        replicated: bool,
+    ) -> Option<Vec<Result<(), OrderBookError>>> {
+        #[cfg(feature = "chaos")]
+        if let Some(delay) = self.faults.matching_delay() {
+            tokio::time::sleep(delay).await;
+        }
+        let primary = self.orderbook_manager.get_primary();
+        // the clock is logged with the batch, so replaying the log purges the same expired orders
+        let feed_replicas = self.replication_feed.has_subscribers();
+        let accepted: Vec<Operation> = if self.write_ahead_log.is_some() || feed_replicas {
+            batch
+                .iter()
+                .zip(checks)
+                .filter(|(_, check)| check.is_ok())
+                .map(|(order, _)| *order)
+                .collect()
+        } else {
+            Vec::new()
+        };
+        if let Some(write_ahead_log) = &self.write_ahead_log {
+            match write_ahead_log.append_batch(now, &accepted) {
+                Ok(sequence) => debug!(
+                    sequence,
+                    operations = accepted.len(),
+                    "persisted batch to write ahead log"
+                ),
+                // the batch was rolled back, but the log is likely to fail again, so trading
+                // stays halted until an operator resumes it
+                Err(e) => {
+                    self.symbol_state.set_trading_status(TradingStatus::Halted);
+                    error!(
+                        "failed to persist batch to write ahead log, dropping {} operations and halting trading: {}",
+                        batch.len(),
+                        e
+                    );
+                    return None;
+                }
+            }
+        }
+        let ledger = self.ledger.as_deref().filter(|_| !replicated);
+        let settlement = self.settlement.as_ref().filter(|_| !replicated);
+        let protection = self.protection.as_ref().filter(|_| !replicated);
+        unsafe { (*primary).set_clock(now) };
         let id = unsafe { (*primary).get_id() };
         let mut results = vec![];
-        for order in batch {
-            results.push((
-                unsafe { (*primary).execute(*order) },
-                generate_u128_timestamp(),
-            ));
-        }
-        let kafka_producer = self.kafka_producer.clone();
-        let kafka_topic = self.kafka_topic.clone();
-        let encoder = ProtoRawEncoder::new(self.sr_settings.as_ref().clone());
-        tokio::spawn(async move {
-            for (result, timestamp) in results {
-                let encoded_data =
-                    exec_to_proto_encoded(result, id.clone(), timestamp, &encoder).await;
-                let delivery_result = kafka_producer
-                    .send(
-                        FutureRecord::<(), Vec<u8>>::to(kafka_topic.as_str())
-                            .payload(&encoded_data),
-                        Timeout::After(Duration::new(5, 0)),
-                    )
-                    .await;
-                match delivery_result {
-                    Ok(_) => info!("Successfully sent message"),
-                    Err((e, _)) => {
-                        error!("Error sending message: {:?}", e);
+        let mut settlements = vec![];
+        let mut trades = vec![];
+        let mut book_events = vec![];
+        let mut hook_events = vec![];
+        let feed_book = self.book_feed.has_subscribers();
+        let first_sequence = self.operation_count.load(Ordering::Relaxed) + 1;
+        let mut latencies = Vec::with_capacity(batch.len());
+        let mut outcomes = Vec::with_capacity(batch.len());
+        for ((order, check), sequence) in batch.iter().zip(checks).zip(first_sequence..) {
+            // the quotes of a tripped account are pulled before anything else executes
+            if protection.is_some_and(|protection| protection.has_tripped()) {
+                break;
+            }
+            // recorded ahead of execution, so that a crash dump holds the operation that panicked
+            if let Some(recent_operations) = &self.recent_operations {
+                recent_operations.record(now, std::slice::from_ref(order));
+            }
+            let started = Instant::now();
+            let result = match check {
+                Ok(()) => unsafe { (*primary).execute(*order) },
+                Err(rejection) => {
+                    ExecutionResult::Failed(OrderBookError::Rejected(rejection.clone()))
+                }
+            };
+            let timestamp = generate_u128_timestamp();
+            // expired orders purged by a taker are reported like cancellations
+            for expired in unsafe { (*primary).take_expired() } {
+                let expiry = ExecutionResult::Cancelled(expired);
+                let cancel = Operation::Cancel(OrderId(expired));
+                if feed_book {
+                    book_events.push((BookEvent::Cancel(expired), timestamp));
+                }
+                if let Some(ledger) = ledger {
+                    unsafe { ledger.apply(&cancel, &expiry, id, &self.assets, &*primary) };
+                }
+                let history = unsafe {
+                    self.order_history
+                        .record(&cancel, &expiry, &*primary, timestamp)
+                };
+                results.push((expiry, timestamp, history));
+            }
+            // accounts are resolved before the ledger forgets the owners of filled orders
+            if let Some(settlement) = settlement {
+                settlements.extend(result.fills().iter().map(|fill| {
+                    let instruction =
+                        SettlementInstruction::from_fill(fill, &settlement.fee_schedule, |id| {
+                            ledger.and_then(|ledger| ledger.owner(id))
+                        });
+                    (instruction, timestamp)
+                }));
+            }
+            if feed_book {
+                book_events.extend(
+                    BookEvent::from_execution(order, &result)
+                        .into_iter()
+                        .map(|event| (event, timestamp)),
+                );
+            }
+            if self.trades_topic.is_some() && !replicated {
+                trades.extend(
+                    result
+                        .fills()
+                        .iter()
+                        .map(|fill| (*fill, sequence, timestamp)),
+                );
+            }
+            if let (Some(throttle), Some(ledger)) = (&self.throttle, ledger) {
+                let traders = result
+                    .fills()
+                    .iter()
+                    .flat_map(|fill| [fill.order_id, fill.matched_order_id]);
+                for account in traders.filter_map(|id| ledger.owner(id)) {
+                    throttle.record_trade(&account);
+                }
+            }
+            // quotes are registered before their fills are counted, and before the ledger
+            // forgets the owners of filled quotes
+            if let (Some(protection), Some(ledger)) = (protection, ledger) {
+                if let Operation::Quote { bid, ask } = order {
+                    if let Some(account) = ledger.owner(bid.id) {
+                        protection.register(&account, [bid.id, ask.id]);
                     }
                 }
+                for fill in result.fills() {
+                    protection.record(fill, timestamp);
+                }
             }
-        });
+            if let Some(ledger) = ledger {
+                unsafe { ledger.apply(order, &result, id, &self.assets, &*primary) };
+            }
+            latencies.push((OperationKind::from(order), started.elapsed()));
+            self.ticker.record(result.fills(), timestamp);
+            self.candles.record(result.fills(), timestamp);
+            self.average_prices.record(result.fills(), timestamp);
+            self.volume_profile.record(result.fills());
+            outcomes.push(match &result {
+                ExecutionResult::Failed(reason) => {
+                    debug!(
+                        order_id = %order.order_id(),
+                        reason = %reason,
+                        "failed to execute operation"
+                    );
+                    Err(reason.clone())
+                }
+                _ => Ok(()),
+            });
+            if !replicated {
+                hook_events.extend(unsafe {
+                    self.hooks.after_trade(order, &result, &*primary, timestamp)
+                });
+            }
+            let history = unsafe {
+                self.order_history
+                    .record(order, &result, &*primary, timestamp)
+            };
+            results.push((result, timestamp, history));
+        }
+        let executed = outcomes.len();
+        let accepted_executed = checks[..executed]
+            .iter()
+            .filter(|check| check.is_ok())
+            .count();
+        if executed < batch.len() {
+            debug!(
+                operations = batch.len() - executed,
+                "market maker protection tripped, deferring the rest of the batch"
+            );
+            if let Some(write_ahead_log) = &self.write_ahead_log {
+                if let Err(e) = write_ahead_log.retract(&accepted[accepted_executed..]) {
+                    self.symbol_state.set_trading_status(TradingStatus::Halted);
+                    error!(
+                        "failed to retract deferred operations from write ahead log, halting trading: {}",
+                        e
+                    );
+                }
+            }
+        }
+        if feed_replicas && accepted_executed > 0 {
+            self.replication_feed
+                .publish(now, accepted[..accepted_executed].to_vec());
+        }
+        self.book_feed.publish(book_events);
+        self.latency_metrics.record_batch(&latencies);
+        self.overload
+            .record_latency(latencies.iter().map(|(_, latency)| *latency));
+        self.operation_count
+            .fetch_add(executed as u64, Ordering::Relaxed);
+        if let Some(order_store) = &self.order_store {
+            let executions = results.iter().map(|(result, _, _)| result);
+            if let Err(e) = unsafe { order_store.apply(executions, &*primary) } {
+                error!("failed to mirror batch to persistent order store: {}", e);
+            }
+        }
+        if let Some(write_ahead_log) = &self.write_ahead_log {
+            if write_ahead_log.take_checkpoint_request() {
+                if let Err(e) = unsafe { write_ahead_log.checkpoint(&*primary) } {
+                    error!("failed to checkpoint write ahead log: {}", e);
+                }
+            }
+        }
+        // the primary published the events of the batch
+        if replicated {
+            return Some(outcomes);
+        }
+        let mut events: Vec<OutboxEvent> = results
+            .into_iter()
+            .map(|(result, timestamp, history)| {
+                let (payload, schema_name) = exec_to_proto(result, id.clone(), timestamp, history);
+                OutboxEvent {
+                    topic: self.kafka_topic.clone(),
+                    schema_name: schema_name.to_string(),
+                    payload,
+                }
+            })
+            .collect();
+        if let Some(settlement) = settlement {
+            events.extend(settlements.into_iter().map(|(instruction, timestamp)| {
+                let (payload, schema_name) =
+                    settlement_to_proto(instruction, id.clone(), timestamp);
+                OutboxEvent {
+                    topic: settlement.kafka_topic.clone(),
+                    schema_name: schema_name.to_string(),
+                    payload,
+                }
+            }));
+        }
+        if let Some(trades_topic) = &self.trades_topic {
+            events.extend(trades.into_iter().map(|(fill, sequence, timestamp)| {
+                let (payload, schema_name) = trade_to_proto(fill, id.clone(), sequence, timestamp);
+                OutboxEvent {
+                    topic: trades_topic.clone(),
+                    schema_name: schema_name.to_string(),
+                    payload,
+                }
+            }));
+        }
+        events.extend(hook_events);
+        let event_count = events.len();
+        if let Err(e) = self.outbox.enqueue(events) {
+            error!("failed to enqueue {} events to outbox: {}", event_count, e);
+        }
+        Some(outcomes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::errors::{OrderBookError, Rejection};
+    use crate::core::models::{
+        ExecutionResult, LimitOrder, MarketOrder, MatchingPolicy, Operation, Side,
+    };
+    use crate::core::orderbook::OrderBook;
+    use crate::core::types::{OrderId, Price, Quantity};
+    use crate::engine::accounts::ledger::{Ledger, SymbolAssets};
+    use crate::engine::accounts::limits::RiskLimits;
+    use crate::engine::accounts::market_making::{
+        quote_id, MarketMakerProtection, ProtectionLimits,
+    };
+    use crate::engine::constants::property_loader::{
+        ChannelCapacities, PersistenceProperties, SessionSchedule, TunableProperties,
+    };
+    use crate::engine::persistence::outbox::{Outbox, OutboxEvent};
+    use crate::engine::persistence::wal::WriteAheadLog;
+    use crate::engine::risk::hooks::{PostTradeHook, PreTradeHook, TradeHooks};
+    use crate::engine::risk::resting_limits::RestingLimits;
+    use crate::engine::risk::{BookStats, NoRiskCheck};
+    use crate::engine::state::book_feed::BookFeed;
+    use crate::engine::state::quotes::{FirmQuote, QuoteError};
+    use crate::engine::state::replication::ReplicationFeed;
+    use crate::engine::state::runtime_settings::RuntimeSettings;
+    use crate::engine::state::session::{Session, SessionPhase};
+    use crate::engine::state::symbol_state::{SymbolSpecification, SymbolState};
+    use crate::engine::tasks::order_exec_task::{Executor, ExecutorCommand};
+    use std::path::{Path, PathBuf};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    /// An executor of `ETHUSD` logging to a write ahead log in the passed directory, without
+    /// replication or any other persistence.
+    fn executor(directory: &Path, ledger: Option<Arc<Ledger>>) -> Executor {
+        let persistence_properties = PersistenceProperties {
+            wal_enabled: true,
+            wal_directory: directory.join("wal").to_string_lossy().to_string(),
+            wal_checkpoint_interval: Duration::ZERO,
+            order_store_enabled: false,
+            order_store_path: String::new(),
+            outbox_path: String::new(),
+            audit_directory: None,
+            audit_max_file_size: 0,
+            recording_path: None,
+            traffic_recording_path: None,
+            replay_path: None,
+            replay_speed: 1.0,
+            snapshot_store: None,
+            crash_dump: None,
+        };
+        let specification = SymbolSpecification {
+            tick_size: 1,
+            lot_size: 1,
+            queue_capacity: 10,
+            store_capacity: 100,
+            matching_policy: MatchingPolicy::PriceTime,
+            channel_capacities: ChannelCapacities::default(),
+            assets: SymbolAssets::from_symbol("ETHUSD"),
+            resting_limits: RestingLimits::default(),
+        };
+        let symbol_state = Arc::new(
+            SymbolState::init("ETHUSD", specification, "events", &persistence_properties).unwrap(),
+        );
+        let channels = symbol_state.take_executor_channels().unwrap();
+        let tunable_properties = TunableProperties {
+            order_exec_batch_size: 100,
+            order_exec_batch_timeout: Duration::from_millis(1),
+            orderbook_snapshot_interval: Duration::from_secs(1),
+            orderbook_snapshot_operations: 1_000,
+            metrics_report_interval: Duration::from_secs(1),
+            stream_interval: Duration::from_secs(1),
+            order_expiry_interval: Duration::from_secs(1),
+        };
+        Executor {
+            symbol: symbol_state.symbol.clone(),
+            symbol_state: Arc::clone(&symbol_state),
+            batch_size: tunable_properties.order_exec_batch_size,
+            batch_timeout: tunable_properties.order_exec_batch_timeout,
+            runtime_settings: Arc::new(RuntimeSettings::new(None, tunable_properties)),
+            orderbook_manager: Arc::clone(&symbol_state.orderbook_manager),
+            kafka_topic: symbol_state.kafka_topic.clone(),
+            outbox: Arc::new(Outbox::open(directory.join("outbox")).unwrap()),
+            write_ahead_log: symbol_state.write_ahead_log.clone(),
+            order_store: None,
+            latency_metrics: Arc::clone(&symbol_state.latency_metrics),
+            overload: Arc::clone(&symbol_state.overload),
+            recent_operations: None,
+            order_history: Arc::clone(&symbol_state.order_history),
+            ticker: Arc::clone(&symbol_state.ticker),
+            candles: Arc::clone(&symbol_state.candles),
+            average_prices: Arc::clone(&symbol_state.average_prices),
+            volume_profile: Arc::clone(&symbol_state.volume_profile),
+            book_feed: Arc::new(BookFeed::new(16)),
+            replication_feed: Arc::new(ReplicationFeed::new(16)),
+            replication: None,
+            operation_count: Arc::clone(&symbol_state.operation_count),
+            ledger,
+            assets: symbol_state.specification.assets.clone(),
+            risk_check: Arc::new(NoRiskCheck),
+            hooks: TradeHooks::default(),
+            session: Arc::new(Session::new(None, 0)),
+            settlement: None,
+            trades_topic: None,
+            throttle: None,
+            protection: None,
+            rx: channels.orders,
+            commands: channels.commands,
+            watermarks: Arc::clone(&symbol_state.executor_watermarks),
+            #[cfg(feature = "chaos")]
+            faults: Default::default(),
+        }
+    }
+
+    fn test_directory(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("gemmy-executor-{}-{}", name, uuid::Uuid::new_v4()))
+    }
+
+    fn recovered_orders(directory: &Path) -> Vec<LimitOrder> {
+        let mut recovered = OrderBook::default();
+        WriteAheadLog::open(directory.join("wal").join("ETHUSD"), &mut recovered).unwrap();
+        recovered.resting_orders()
+    }
+
+    #[tokio::test]
+    async fn it_pulls_tripped_quotes_before_the_rest_of_the_batch() {
+        let directory = test_directory("protection");
+        let ledger = Arc::new(Ledger::new(RiskLimits::default()));
+        ledger.credit("maker", "ETH", 100).unwrap();
+        ledger.credit("maker", "USD", 10_000).unwrap();
+        ledger.credit("taker", "USD", 10_000).unwrap();
+        let mut executor = executor(&directory, Some(Arc::clone(&ledger)));
+        let limits = ProtectionLimits::from_raw(Duration::from_secs(60), 2, 0);
+        executor.protection = Some(MarketMakerProtection::new(limits));
+
+        let bid = quote_id("ETHUSD", "maker", 0, Side::Bid);
+        let ask = quote_id("ETHUSD", "maker", 0, Side::Ask);
+        let quote = Operation::Quote {
+            bid: LimitOrder::new(OrderId(bid), Price(99), Quantity(10), Side::Bid),
+            ask: LimitOrder::new(OrderId(ask), Price(101), Quantity(10), Side::Ask),
+        };
+        ledger.assign(bid, "maker");
+        ledger.assign(ask, "maker");
+        let mut batch = vec![quote];
+        for id in 1..=4 {
+            ledger.assign(id, "taker");
+            batch.push(Operation::Market(MarketOrder::new(
+                OrderId(id),
+                Quantity(2),
+                Side::Bid,
+            )));
+        }
+        ledger.assign(5, "taker");
+        batch.push(Operation::Limit(LimitOrder::new(
+            OrderId(5),
+            Price(90),
+            Quantity(1),
+            Side::Bid,
+        )));
+
+        let outcomes = executor.process_batch(&batch).await.unwrap();
+        // the third fill trips the protection, so the fourth finds the quotes pulled
+        assert_eq!(
+            outcomes,
+            vec![
+                Ok(()),
+                Ok(()),
+                Ok(()),
+                Ok(()),
+                Err(OrderBookError::EmptyBook),
+                Ok(())
+            ]
+        );
+        let primary = unsafe { &*executor.orderbook_manager.get_primary() };
+        let resting: Vec<u128> = primary.resting_orders().iter().map(|o| o.id).collect();
+        assert_eq!(resting, vec![5]);
+        let events = executor.outbox.peek(100).unwrap();
+        assert!(events
+            .iter()
+            .any(|(_, event)| event.schema_name == "ProtectionEvent"));
+        // the log replays the pulls where they were executed
+        assert_eq!(recovered_orders(&directory), primary.resting_orders());
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+
+    #[tokio::test]
+    async fn it_rejects_the_quotes_of_a_tripped_account_until_reset() {
+        let directory = test_directory("protection-lock");
+        let ledger = Arc::new(Ledger::new(RiskLimits::default()));
+        ledger.credit("maker", "ETH", 100).unwrap();
+        ledger.credit("maker", "USD", 10_000).unwrap();
+        ledger.credit("taker", "USD", 10_000).unwrap();
+        let mut executor = executor(&directory, Some(Arc::clone(&ledger)));
+        let limits = ProtectionLimits::from_raw(Duration::from_secs(60), 2, 0);
+        executor.protection = Some(MarketMakerProtection::new(limits));
+
+        let bid = quote_id("ETHUSD", "maker", 0, Side::Bid);
+        let ask = quote_id("ETHUSD", "maker", 0, Side::Ask);
+        let quote = Operation::Quote {
+            bid: LimitOrder::new(OrderId(bid), Price(99), Quantity(10), Side::Bid),
+            ask: LimitOrder::new(OrderId(ask), Price(101), Quantity(10), Side::Ask),
+        };
+        ledger.assign(bid, "maker");
+        ledger.assign(ask, "maker");
+        let mut batch = vec![quote];
+        for id in 1..=3 {
+            ledger.assign(id, "taker");
+            batch.push(Operation::Market(MarketOrder::new(
+                OrderId(id),
+                Quantity(2),
+                Side::Bid,
+            )));
+        }
+        // the account quotes again right after the fill that trips its protection
+        batch.push(quote);
+
+        let outcomes = executor.process_batch(&batch).await.unwrap();
+        assert_eq!(
+            outcomes,
+            vec![
+                Ok(()),
+                Ok(()),
+                Ok(()),
+                Ok(()),
+                Err(OrderBookError::Rejected(Rejection::ProtectionTripped))
+            ]
+        );
+        let primary = unsafe { &*executor.orderbook_manager.get_primary() };
+        assert!(primary.resting_orders().is_empty());
+
+        let (reply, reset) = tokio::sync::oneshot::channel();
+        let command = ExecutorCommand::ResetProtection("maker".to_string(), reply);
+        assert!(executor.handle_command(command, &mut Vec::new()).await);
+        assert!(reset.await.unwrap());
+        ledger.assign(bid, "maker");
+        ledger.assign(ask, "maker");
+        let outcomes = executor.process_batch(&[quote]).await.unwrap();
+        assert_eq!(outcomes, vec![Ok(())]);
+        assert_eq!(primary.resting_orders().len(), 2);
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+
+    /// Rejects limit orders above a price.
+    struct PriceBand(u64);
+
+    impl PreTradeHook for PriceBand {
+        fn before_trade(&self, operation: &Operation, _stats: &BookStats) -> Result<(), Rejection> {
+            match operation {
+                Operation::Limit(order) if order.price > self.0 => {
+                    Err(Rejection::Custom(format!("price above {}", self.0)))
+                }
+                _ => Ok(()),
+            }
+        }
+    }
+
+    /// Reports the quantity of every fill.
+    struct FillReport;
+
+    impl PostTradeHook for FillReport {
+        fn after_trade(
+            &self,
+            _operation: &Operation,
+            result: &ExecutionResult,
+            orderbook: &OrderBook,
+            _timestamp: u128,
+        ) -> Vec<OutboxEvent> {
+            result
+                .fills()
+                .iter()
+                .map(|fill| OutboxEvent {
+                    topic: orderbook.get_id().clone(),
+                    schema_name: "FillReport".to_string(),
+                    payload: fill.quantity.to_be_bytes().to_vec(),
+                })
+                .collect()
+        }
+    }
+
+    #[tokio::test]
+    async fn it_runs_the_trade_hooks_around_matching() {
+        let directory = test_directory("hooks");
+        let mut executor = executor(&directory, None);
+        executor.hooks.add_pre_trade(Arc::new(PriceBand(100)));
+        executor.hooks.add_post_trade(Arc::new(FillReport));
+        let batch = [
+            Operation::Limit(LimitOrder::new(
+                OrderId(1),
+                Price(101),
+                Quantity(10),
+                Side::Ask,
+            )),
+            Operation::Limit(LimitOrder::new(
+                OrderId(2),
+                Price(100),
+                Quantity(10),
+                Side::Ask,
+            )),
+            Operation::Market(MarketOrder::new(OrderId(3), Quantity(4), Side::Bid)),
+        ];
+        let outcomes = executor.process_batch(&batch).await.unwrap();
+        assert_eq!(
+            outcomes,
+            vec![
+                Err(OrderBookError::Rejected(Rejection::Custom(
+                    "price above 100".to_string()
+                ))),
+                Ok(()),
+                Ok(())
+            ]
+        );
+        let reports: Vec<Vec<u8>> = executor
+            .outbox
+            .peek(100)
+            .unwrap()
+            .into_iter()
+            .filter(|(_, event)| event.schema_name == "FillReport")
+            .map(|(_, event)| event.payload)
+            .collect();
+        assert_eq!(reports, vec![4u64.to_be_bytes().to_vec()]);
+        // rejected operations are never logged
+        let recovered: Vec<u128> = recovered_orders(&directory)
+            .iter()
+            .map(|order| order.id)
+            .collect();
+        assert_eq!(recovered, vec![2]);
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+
+    #[tokio::test]
+    async fn it_calls_the_book_in_the_pre_open_and_uncrosses_it_at_the_open() {
+        let directory = test_directory("call");
+        let mut executor = executor(&directory, None);
+        let schedule = SessionSchedule::parse("08:45", "09:00", "17:00").unwrap();
+        let pre_open = 20_000 * 86_400 * 1_000_000_000 + 31_800 * 1_000_000_000;
+        executor.session = Arc::new(Session::new(Some(schedule), pre_open));
+        assert_eq!(executor.session.phase(), SessionPhase::PreOpen);
+
+        let batch = [
+            Operation::Limit(LimitOrder::new(
+                OrderId(1),
+                Price(105),
+                Quantity(100),
+                Side::Bid,
+            )),
+            Operation::Limit(LimitOrder::new(
+                OrderId(2),
+                Price(98),
+                Quantity(80),
+                Side::Ask,
+            )),
+        ];
+        let outcomes = executor.process_batch(&batch).await.unwrap();
+        assert_eq!(outcomes, vec![Ok(()), Ok(())]);
+        let primary = unsafe { &*executor.orderbook_manager.get_primary() };
+        assert!(primary.in_call());
+        assert_eq!(primary.resting_orders().len(), 2);
+        assert!(!executor.follow_session().await);
+
+        executor.session.set_phase(SessionPhase::Open);
+        assert!(executor.follow_session().await);
+        assert!(!primary.in_call());
+        assert_eq!(primary.get_last_trade_price(), 98);
+        let events = executor.outbox.peek(100).unwrap();
+        let schema_names: Vec<&str> = events
+            .iter()
+            .map(|(_, event)| event.schema_name.as_str())
+            .filter(|name| name.ends_with("Event"))
+            .collect();
+        assert_eq!(schema_names[0], "CallEvent");
+        assert!(schema_names.contains(&"UncrossEvent"));
+        // the log replays the call and its uncross
+        assert_eq!(recovered_orders(&directory), primary.resting_orders());
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+
+    #[tokio::test]
+    async fn it_answers_a_quote_whose_order_fails_with_an_error() {
+        let directory = test_directory("quote");
+        let mut executor = executor(&directory, None);
+        let schedule = SessionSchedule::parse("08:45", "09:00", "17:00").unwrap();
+        let pre_open = 20_000 * 86_400 * 1_000_000_000 + 31_800 * 1_000_000_000;
+        executor.session = Arc::new(Session::new(Some(schedule), pre_open));
+        let ask = LimitOrder::new(OrderId(1), Price(100), Quantity(10), Side::Ask);
+        executor.process_batch(&[Operation::Limit(ask)]).await;
+
+        // the book honours the quote, but market orders fail during the call
+        let quote = FirmQuote {
+            side: Side::Bid,
+            quantity: 10,
+            price: 100,
+            notional: 1_000,
+            expires_at: u128::MAX,
+        };
+        let order = MarketOrder::new(OrderId(2), Quantity(10), Side::Bid);
+        let executed = executor.execute_quote(quote, order, &mut Vec::new()).await;
+        assert_eq!(executed, Err(QuoteError::Failed(OrderBookError::InCall)));
+        let primary = unsafe { &*executor.orderbook_manager.get_primary() };
+        assert_eq!(primary.resting_orders(), vec![ask]);
+
+        executor.session.set_phase(SessionPhase::Open);
+        let executed = executor.execute_quote(quote, order, &mut Vec::new()).await;
+        assert_eq!(executed, Ok(()));
+        assert!(primary.resting_orders().is_empty());
+        std::fs::remove_dir_all(directory).unwrap();
     }
 }
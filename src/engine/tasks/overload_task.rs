@@ -0,0 +1,85 @@
+use crate::engine::constants::property_loader::OverloadSettings;
+use crate::engine::persistence::outbox::{Outbox, OutboxEvent};
+use crate::engine::state::overload::Load;
+use crate::engine::state::server_state::{ServerState, Symbols};
+use crate::engine::state::symbol_state::SymbolState;
+use crate::engine::utils::protobuf::overload_event_to_proto;
+use crate::engine::utils::time::generate_u128_timestamp;
+use std::sync::Arc;
+use tokio::sync::Notify;
+use tokio::time::sleep;
+use tracing::{error, info, warn};
+
+/// Samples the load of every executor, which is how full its order channel is and how long it
+/// took to match, and decides whether its symbol sheds low priority order flow. Every change is
+/// published to the topic of the symbol.
+pub struct OverloadController {
+    pub shutdown_notification: Arc<Notify>,
+    pub symbols: Symbols,
+    pub outbox: Arc<Outbox>,
+    pub settings: OverloadSettings,
+}
+
+impl OverloadController {
+    pub fn new(state: Arc<ServerState>, settings: OverloadSettings) -> Self {
+        Self {
+            shutdown_notification: Arc::clone(&state.shutdown_notification),
+            symbols: Arc::clone(&state.symbols),
+            outbox: Arc::clone(&state.outbox),
+            settings,
+        }
+    }
+
+    pub async fn run(&self) {
+        loop {
+            tokio::select! {
+                _ = self.shutdown_notification.notified() => {
+                    info!("shutting down overload_task");
+                    break;
+                },
+                _ = sleep(self.settings.interval) => self.sample()
+            }
+        }
+    }
+
+    fn sample(&self) {
+        let symbols: Vec<_> = self.symbols.read().unwrap().values().cloned().collect();
+        for symbol_state in symbols {
+            let sender = &symbol_state.order_sender;
+            let depth = sender.max_capacity() - sender.capacity();
+            let queue_saturation = (depth * 100 / sender.max_capacity()) as u64;
+            let load = symbol_state.overload.sample(queue_saturation);
+            if let Some(shedding) = symbol_state.overload.update(load, &self.settings) {
+                self.announce(&symbol_state, shedding, load);
+            }
+        }
+    }
+
+    fn announce(&self, symbol_state: &SymbolState, shedding: bool, load: Load) {
+        if shedding {
+            warn!(
+                "{} is overloaded at {}% queue saturation and {:?} latency, shedding modifies and passive orders",
+                symbol_state.symbol, load.queue_saturation, load.latency
+            );
+        } else {
+            info!(
+                "{} recovered at {}% queue saturation and {:?} latency, accepting all orders",
+                symbol_state.symbol, load.queue_saturation, load.latency
+            );
+        }
+        let (payload, schema_name) = overload_event_to_proto(
+            symbol_state.symbol.clone(),
+            shedding,
+            load,
+            generate_u128_timestamp(),
+        );
+        let event = OutboxEvent {
+            topic: symbol_state.kafka_topic.clone(),
+            schema_name: schema_name.to_string(),
+            payload,
+        };
+        if let Err(e) = self.outbox.enqueue(vec![event]) {
+            error!("failed to enqueue overload event to outbox: {}", e);
+        }
+    }
+}
@@ -0,0 +1,299 @@
+use crate::engine::configuration::kafka_configuration::KafkaConfiguration;
+use crate::engine::constants::property_loader::DeliveryFailurePolicy;
+use crate::engine::persistence::outbox::{Outbox, OutboxEvent};
+use crate::engine::state::delivery_metrics::DeliveryMetrics;
+#[cfg(feature = "chaos")]
+use crate::engine::state::faults::Faults;
+use crate::engine::state::server_state::ServerState;
+use crate::engine::utils::protobuf::encode_proto;
+use rdkafka::error::{KafkaError, RDKafkaErrorCode};
+use rdkafka::producer::{DeliveryFuture, FutureProducer, FutureRecord};
+use rdkafka::util::Timeout;
+use schema_registry_converter::async_impl::proto_raw::ProtoRawEncoder;
+use std::collections::VecDeque;
+use std::error::Error;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Notify;
+use tokio::time::{sleep, Instant};
+use tracing::{debug, error, info, warn};
+
+const PUBLISH_BATCH_SIZE: usize = 1000;
+
+/// An event handed to the producer whose delivery has not been settled yet.
+struct InFlight {
+    sequence: u64,
+    event: OutboxEvent,
+    started: Instant,
+    delivery: Result<DeliveryFuture, Box<dyn Error + Send + Sync>>,
+}
+
+pub struct Publisher<'a> {
+    pub drain_notification: Arc<Notify>,
+    pub outbox: Arc<Outbox>,
+    pub kafka_producer: Arc<FutureProducer>,
+    pub encoder: ProtoRawEncoder<'a>,
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+    pub delivery_failure_policy: DeliveryFailurePolicy,
+    pub max_in_flight: usize,
+    pub delivery_metrics: Arc<DeliveryMetrics>,
+    pub trading_halted: Arc<AtomicBool>,
+    #[cfg(feature = "chaos")]
+    pub faults: Arc<Faults>,
+}
+
+impl Publisher<'_> {
+    pub fn new(kafka_configuration: Arc<KafkaConfiguration>, state: Arc<ServerState>) -> Self {
+        Self {
+            drain_notification: Arc::clone(&state.drain_notification),
+            outbox: Arc::clone(&state.outbox),
+            kafka_producer: state
+                .kafka_producer
+                .clone()
+                .expect("standalone servers write events to their sink instead of publishing"),
+            encoder: ProtoRawEncoder::new(
+                kafka_configuration
+                    .kafka_admin_properties
+                    .sr_settings
+                    .as_ref()
+                    .clone(),
+            ),
+            initial_backoff: kafka_configuration
+                .kafka_producer_properties
+                .outbox_initial_backoff,
+            max_backoff: kafka_configuration
+                .kafka_producer_properties
+                .outbox_max_backoff,
+            delivery_failure_policy: kafka_configuration
+                .kafka_producer_properties
+                .delivery_failure_policy,
+            max_in_flight: kafka_configuration
+                .kafka_producer_properties
+                .max_in_flight
+                .max(1),
+            delivery_metrics: Arc::clone(&state.delivery_metrics),
+            trading_halted: Arc::clone(&state.trading_halted),
+            #[cfg(feature = "chaos")]
+            faults: Arc::clone(&state.faults),
+        }
+    }
+
+    /// Publishes events until drained, which happens once the outbox is empty. A delivery that
+    /// fails while draining stops the publisher, leaving the remaining events in the outbox to be
+    /// published on the next start.
+    pub async fn run(&self) {
+        loop {
+            let events = match self.outbox.peek(PUBLISH_BATCH_SIZE) {
+                Ok(events) => events,
+                Err(e) => {
+                    error!("failed to read events from outbox: {}", e);
+                    Vec::new()
+                }
+            };
+            if events.is_empty() {
+                tokio::select! {
+                    _ = self.outbox.wait_for_events() => continue,
+                    _ = self.drain_notification.notified() => break,
+                }
+            }
+            if !self.publish_batch(events).await {
+                info!(
+                    "shutting down publisher_task with {} events pending",
+                    self.outbox.len()
+                );
+                return;
+            }
+        }
+        info!(
+            "shutting down publisher_task with {} events pending",
+            self.outbox.len()
+        );
+    }
+
+    /// Hands the events of a batch to the producer without waiting for the earlier ones to be
+    /// delivered, keeping at most `max_in_flight` deliveries outstanding, and settles every
+    /// delivery before the next batch is read. Events are acknowledged in order, so the outbox
+    /// only lets go of an event once it and every event before it was delivered or dropped.
+    ///
+    /// A failed delivery is retried on its own while the events behind it are already on their
+    /// way, so a retried event may land after them.
+    ///
+    /// # Returns
+    ///
+    /// * `false` if draining was signalled before the batch could be delivered or dropped.
+    async fn publish_batch(&self, events: Vec<(u64, OutboxEvent)>) -> bool {
+        let mut in_flight = VecDeque::with_capacity(self.max_in_flight);
+        for (sequence, event) in events {
+            if in_flight.len() >= self.max_in_flight && !self.settle(&mut in_flight).await {
+                return false;
+            }
+            let started = Instant::now();
+            let delivery = loop {
+                match self.send(&event).await {
+                    Err(e) if queue_full(e.as_ref()) && !in_flight.is_empty() => {
+                        if !self.settle(&mut in_flight).await {
+                            return false;
+                        }
+                    }
+                    delivery => break delivery,
+                }
+            };
+            in_flight.push_back(InFlight {
+                sequence,
+                event,
+                started,
+                delivery,
+            });
+        }
+        while !in_flight.is_empty() {
+            if !self.settle(&mut in_flight).await {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Awaits the delivery of the oldest event in flight and acknowledges it once delivered or
+    /// dropped.
+    async fn settle(&self, in_flight: &mut VecDeque<InFlight>) -> bool {
+        let Some(InFlight {
+            sequence,
+            event,
+            started,
+            delivery,
+        }) = in_flight.pop_front()
+        else {
+            return true;
+        };
+        let outcome = match delivery {
+            Ok(delivery) => match delivery.await {
+                Ok(Ok(_)) => Ok(()),
+                Ok(Err((e, _))) => Err(e.into()),
+                Err(e) => Err(e.into()),
+            },
+            Err(e) => Err(e),
+        };
+        if !self.deliver(sequence, &event, started, outcome).await {
+            return false;
+        }
+        if let Err(e) = self.outbox.acknowledge(sequence) {
+            error!("failed to acknowledge outbox event {}: {}", sequence, e);
+        }
+        true
+    }
+
+    /// Settles the first attempt at publishing the event, applying the delivery failure policy
+    /// whenever an attempt fails. Failed attempts are retried with exponential backoff until the
+    /// event is delivered or the policy gives up on it, in which case it is dropped.
+    ///
+    /// # Returns
+    ///
+    /// * `false` if draining was signalled before the event could be delivered or dropped.
+    async fn deliver(
+        &self,
+        sequence: u64,
+        event: &OutboxEvent,
+        mut started: Instant,
+        mut outcome: Result<(), Box<dyn Error + Send + Sync>>,
+    ) -> bool {
+        let mut backoff = self.initial_backoff;
+        let mut retries = 0;
+        loop {
+            let e = match outcome {
+                Ok(()) => {
+                    self.delivery_metrics.record_delivery(started.elapsed());
+                    debug!(topic = %event.topic, sequence, "successfully sent message");
+                    if self.trading_halted.swap(false, Ordering::SeqCst) {
+                        info!("event delivery recovered, resuming trading");
+                    }
+                    return true;
+                }
+                Err(e) => e,
+            };
+            self.delivery_metrics.record_failure();
+            match self.delivery_failure_policy {
+                DeliveryFailurePolicy::Drop => {
+                    self.drop_event(event, e);
+                    return true;
+                }
+                DeliveryFailurePolicy::Retry(max_retries) if retries >= max_retries => {
+                    self.drop_event(event, e);
+                    return true;
+                }
+                DeliveryFailurePolicy::Retry(_) => warn!(
+                    "failed to publish {}, retrying in {:?}: {}",
+                    event.schema_name, backoff, e
+                ),
+                DeliveryFailurePolicy::Halt => {
+                    if !self.trading_halted.swap(true, Ordering::SeqCst) {
+                        error!(
+                            "failed to publish {}, halting trading until delivery recovers: {}",
+                            event.schema_name, e
+                        );
+                    }
+                }
+            }
+            retries += 1;
+            tokio::select! {
+                _ = sleep(backoff) => {},
+                _ = self.drain_notification.notified() => return false,
+            }
+            backoff = (backoff * 2).min(self.max_backoff);
+            started = Instant::now();
+            outcome = self.publish(event).await;
+        }
+    }
+
+    fn drop_event(&self, event: &OutboxEvent, e: Box<dyn Error + Send + Sync>) {
+        self.delivery_metrics.record_drop();
+        error!(
+            "dropping {} after failed delivery: {}",
+            event.schema_name, e
+        );
+    }
+
+    /// Enqueues the event on the producer, returning the future of its delivery.
+    async fn send(
+        &self,
+        event: &OutboxEvent,
+    ) -> Result<DeliveryFuture, Box<dyn Error + Send + Sync>> {
+        #[cfg(feature = "chaos")]
+        if self.faults.take_failed_send() {
+            return Err("injected send failure".into());
+        }
+        let encoded_data = encode_proto(&event.payload, &event.schema_name, &self.encoder).await?;
+        let delivery = self
+            .kafka_producer
+            .send_result(
+                FutureRecord::<(), Vec<u8>>::to(event.topic.as_str()).payload(&encoded_data),
+            )
+            .map_err(|(e, _)| e)?;
+        Ok(delivery)
+    }
+
+    async fn publish(&self, event: &OutboxEvent) -> Result<(), Box<dyn Error + Send + Sync>> {
+        #[cfg(feature = "chaos")]
+        if self.faults.take_failed_send() {
+            return Err("injected send failure".into());
+        }
+        let encoded_data = encode_proto(&event.payload, &event.schema_name, &self.encoder).await?;
+        self.kafka_producer
+            .send(
+                FutureRecord::<(), Vec<u8>>::to(event.topic.as_str()).payload(&encoded_data),
+                Timeout::After(Duration::new(5, 0)),
+            )
+            .await
+            .map_err(|(e, _)| e)?;
+        Ok(())
+    }
+}
+
+/// Whether the producer turned the event away because its queue is full.
+fn queue_full(e: &(dyn Error + Send + Sync + 'static)) -> bool {
+    matches!(
+        e.downcast_ref::<KafkaError>(),
+        Some(KafkaError::MessageProduction(RDKafkaErrorCode::QueueFull))
+    )
+}
@@ -0,0 +1,38 @@
+use crate::engine::state::runtime_settings::RuntimeSettings;
+use std::sync::Arc;
+use tokio::signal::unix::{signal, SignalKind};
+use tokio::sync::Notify;
+use tracing::{error, info};
+
+/// Reloads the runtime settings whenever the process receives `SIGHUP`.
+pub struct Reload {
+    pub shutdown_notification: Arc<Notify>,
+    pub runtime_settings: Arc<RuntimeSettings>,
+}
+
+impl Reload {
+    pub fn new(shutdown_notification: Arc<Notify>, runtime_settings: Arc<RuntimeSettings>) -> Self {
+        Self {
+            shutdown_notification,
+            runtime_settings,
+        }
+    }
+
+    pub async fn run(&self) {
+        let mut hangup = signal(SignalKind::hangup()).expect("failed to listen for reload signal");
+        loop {
+            tokio::select! {
+                _ = self.shutdown_notification.notified() => {
+                    info!("shutting down reload_task");
+                    break;
+                },
+                _ = hangup.recv() => {
+                    info!("reload signal received");
+                    if let Err(e) = self.runtime_settings.reload() {
+                        error!("failed to reload settings: {}", e);
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,81 @@
+use crate::engine::persistence::recording::{read_recording, RecordedOperation};
+use crate::engine::state::server_state::{ServerState, Symbols};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Notify;
+use tokio::time::{sleep_until, Instant};
+use tracing::{error, info, warn};
+
+/// Replays a recording through the executors of the symbols once on startup, keeping the pace
+/// the operations were recorded at, sped up by the replay speed. Replayed operations skip the
+/// dispatcher, so they are neither checked against the account limits nor recorded again.
+pub struct Replay {
+    pub shutdown_notification: Arc<Notify>,
+    pub symbols: Symbols,
+    pub path: String,
+    pub speed: f64,
+}
+
+impl Replay {
+    pub fn new(state: Arc<ServerState>, path: String, speed: f64) -> Self {
+        Self {
+            shutdown_notification: Arc::clone(&state.shutdown_notification),
+            symbols: Arc::clone(&state.symbols),
+            path,
+            speed,
+        }
+    }
+
+    pub async fn run(&self) {
+        let shutdown = self.shutdown_notification.notified();
+        tokio::pin!(shutdown);
+        // boxed errors are not send, so the error is turned into a string before awaiting
+        match read_recording(&self.path).map_err(|e| e.to_string()) {
+            Ok(recorded) => tokio::select! {
+                _ = &mut shutdown => {
+                    info!("shutting down replay_task");
+                    return;
+                },
+                replayed = self.replay(recorded) => {
+                    info!("replayed {} operations from {}", replayed, self.path)
+                }
+            },
+            Err(e) => error!("failed to read recording {}: {}", self.path, e),
+        }
+        // the recording is only replayed once, after which the task waits for shutdown
+        shutdown.await;
+        info!("shutting down replay_task");
+    }
+
+    /// Sends the recorded operations to the executors of their symbols, skipping the symbols the
+    /// server does not trade.
+    ///
+    /// # Returns
+    ///
+    /// * The number of operations replayed.
+    async fn replay(&self, recorded: Vec<RecordedOperation>) -> usize {
+        let start = Instant::now();
+        let first_timestamp = recorded.first().map_or(0, |recorded| recorded.timestamp);
+        let mut replayed = 0;
+        for recorded in recorded {
+            if self.speed > 0.0 {
+                let offset = recorded.timestamp.saturating_sub(first_timestamp) as f64 / self.speed;
+                sleep_until(start + Duration::from_nanos(offset as u64)).await;
+            }
+            let Some(symbol_state) = self.symbols.read().unwrap().get(&recorded.symbol).cloned()
+            else {
+                warn!(
+                    "skipping replayed operation of unknown symbol {}",
+                    recorded.symbol
+                );
+                continue;
+            };
+            if let Err(e) = symbol_state.order_sender.send(recorded.operation).await {
+                error!("failed to replay operation of {}: {}", recorded.symbol, e);
+                continue;
+            }
+            replayed += 1;
+        }
+        replayed
+    }
+}
@@ -0,0 +1,168 @@
+use crate::engine::persistence::wal::{decode_batch, decode_checkpoint};
+use crate::engine::services::replication_service::authenticated;
+use crate::engine::state::replication::{Replication, Role};
+use crate::engine::state::server_state::{ServerState, Symbols};
+use crate::engine::state::symbol_state::SymbolState;
+use crate::engine::tasks::order_exec_task::ExecutorCommand;
+use crate::protobuf::models::ReplicationRequest;
+use crate::protobuf::services::replication_client::ReplicationClient;
+use std::error::Error;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Notify;
+use tokio::task::JoinSet;
+use tokio::time::sleep;
+use tonic::transport::{Channel, Endpoint};
+use tracing::{error, info, warn};
+
+/// Keeps the books of a replica in step with its primary. Every symbol subscribes to the batches
+/// its primary executes and hands them to its executor, starting over from a new checkpoint
+/// whenever the stream breaks off. The symbols follow the primary anew whenever the role of the
+/// server or its primary changes, so a server that is promoted stops replicating and one that
+/// is demoted starts. Symbols created in between are not replicated until then.
+pub struct ReplicationFollower {
+    pub shutdown_notification: Arc<Notify>,
+    pub symbols: Symbols,
+    pub replication: Arc<Replication>,
+    pub admin_token: String,
+    pub retry_interval: Duration,
+}
+
+impl ReplicationFollower {
+    pub fn new(
+        state: Arc<ServerState>,
+        replication: Arc<Replication>,
+        admin_token: String,
+        retry_interval: Duration,
+    ) -> Self {
+        Self {
+            shutdown_notification: Arc::clone(&state.shutdown_notification),
+            symbols: Arc::clone(&state.symbols),
+            replication,
+            admin_token,
+            retry_interval,
+        }
+    }
+
+    pub async fn run(&self) {
+        loop {
+            // registered before the role is read, so that no change goes unnoticed
+            let changed = self.replication.role_changed.notified();
+            let mut followers = self.follow_primary();
+            tokio::select! {
+                _ = self.shutdown_notification.notified() => {
+                    info!("shutting down replication_task");
+                    break;
+                }
+                _ = changed => {}
+            }
+            followers.shutdown().await;
+        }
+    }
+
+    /// Follows the primary with every symbol, unless this server is not a replica or does not
+    /// know its primary yet.
+    fn follow_primary(&self) -> JoinSet<()> {
+        let mut followers = JoinSet::new();
+        if self.replication.role() != Role::Replica {
+            return followers;
+        }
+        let Some(address) = self.replication.primary_address() else {
+            return followers;
+        };
+        match channel(address) {
+            Ok(channel) => {
+                let symbols: Vec<_> = self.symbols.read().unwrap().values().cloned().collect();
+                for symbol_state in symbols {
+                    followers.spawn(follow(
+                        ReplicationClient::new(channel.clone()),
+                        Arc::clone(&self.replication),
+                        symbol_state,
+                        self.admin_token.clone(),
+                        self.retry_interval,
+                    ));
+                }
+            }
+            Err(e) => error!("failed to follow primary: {}", e),
+        }
+        followers
+    }
+}
+
+/// Connects to the primary lazily, so that the replica starts before it.
+fn channel(address: String) -> Result<Channel, Box<dyn Error>> {
+    Ok(Endpoint::from_shared(address)?.connect_lazy())
+}
+
+/// Replicates a symbol for as long as the server is a replica.
+async fn follow(
+    mut client: ReplicationClient<Channel>,
+    replication: Arc<Replication>,
+    symbol_state: Arc<SymbolState>,
+    admin_token: String,
+    retry_interval: Duration,
+) {
+    while replication.role() == Role::Replica {
+        match replicate(&mut client, &replication, &symbol_state, &admin_token).await {
+            Ok(()) => warn!(
+                "primary ended replication of {}, subscribing again in {:?}",
+                symbol_state.symbol, retry_interval
+            ),
+            Err(e) => warn!(
+                "replication of {} broke off, subscribing again in {:?}: {}",
+                symbol_state.symbol, retry_interval, e
+            ),
+        }
+        sleep(retry_interval).await;
+    }
+}
+
+/// Applies the checkpoint of the primary followed by the batches it streams after it, until the
+/// stream ends or a batch is missing.
+async fn replicate(
+    client: &mut ReplicationClient<Channel>,
+    replication: &Replication,
+    symbol_state: &SymbolState,
+    admin_token: &str,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let request = ReplicationRequest {
+        symbol: symbol_state.symbol.clone(),
+        fencing_token: replication.fencing_token(),
+    };
+    let mut updates = client
+        .subscribe(authenticated(request, admin_token)?)
+        .await?
+        .into_inner();
+    let mut next_sequence = None;
+    while let Some(update) = updates.message().await? {
+        replication.observe(update.fencing_token)?;
+        let command = if !update.checkpoint.is_empty() {
+            let (sequence, last_trade_price, call, orders) =
+                decode_checkpoint(&update.checkpoint).map_err(|e| e.to_string())?;
+            info!(
+                "replicating {} from batch {} with {} resting orders",
+                symbol_state.symbol,
+                sequence,
+                orders.len()
+            );
+            next_sequence = Some(sequence + 1);
+            ExecutorCommand::ResetReplica(last_trade_price, call, orders)
+        } else if next_sequence == Some(update.sequence) {
+            let (now, operations) = decode_batch(&update.batch).map_err(|e| e.to_string())?;
+            next_sequence = Some(update.sequence + 1);
+            ExecutorCommand::Replicate(now, operations)
+        } else {
+            return Err(format!(
+                "expected batch {:?} but received {}",
+                next_sequence, update.sequence
+            )
+            .into());
+        };
+        symbol_state
+            .command_sender
+            .send(command)
+            .await
+            .map_err(|_| "executor already stopped")?;
+    }
+    Ok(())
+}
@@ -0,0 +1,111 @@
+use crate::engine::persistence::outbox::{Outbox, OutboxEvent};
+use crate::engine::state::server_state::{ServerState, Symbols};
+use crate::engine::state::session::{Session, SessionPhase};
+use crate::engine::utils::protobuf::session_event_to_proto;
+use crate::engine::utils::time::generate_u128_timestamp;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Notify;
+use tokio::time::sleep;
+use tracing::{error, info, warn};
+
+/// Advances the trading session along its schedule. Every phase change is published to the topic
+/// of each symbol, books are called in the pre-open and uncrossed at the open, DAY orders are
+/// purged once the session closes and volume profiles start over once the next session opens.
+pub struct SessionScheduler {
+    pub shutdown_notification: Arc<Notify>,
+    pub session: Arc<Session>,
+    pub symbols: Symbols,
+    pub outbox: Arc<Outbox>,
+}
+
+impl SessionScheduler {
+    pub fn new(state: Arc<ServerState>) -> Self {
+        Self {
+            shutdown_notification: Arc::clone(&state.shutdown_notification),
+            session: Arc::clone(&state.session),
+            symbols: Arc::clone(&state.symbols),
+            outbox: Arc::clone(&state.outbox),
+        }
+    }
+
+    pub async fn run(&self) {
+        if self.session.schedule().is_none() {
+            info!("no session schedule configured, market stays open");
+            self.shutdown_notification.notified().await;
+            info!("shutting down session_task");
+            return;
+        }
+        // the phase is published on start so consumers learn the state of the market
+        let mut published = None;
+        loop {
+            let now = generate_u128_timestamp();
+            let phase = self.session.phase_at(now);
+            if published != Some(phase) {
+                self.transition(phase, now).await;
+                published = Some(phase);
+            }
+            let wait = self
+                .session
+                .next_transition(now)
+                .map_or(Duration::ZERO, |transition| {
+                    Duration::from_nanos((transition - now) as u64)
+                });
+            tokio::select! {
+                _ = self.shutdown_notification.notified() => {
+                    info!("shutting down session_task");
+                    break;
+                },
+                _ = sleep(wait) => {}
+            }
+        }
+    }
+
+    async fn transition(&self, phase: SessionPhase, now: u128) {
+        let rolled_over =
+            self.session.phase() == SessionPhase::Closed && phase != SessionPhase::Closed;
+        self.session.set_phase(phase);
+        info!("trading session is {}", phase.name());
+        let symbols: Vec<_> = self.symbols.read().unwrap().values().cloned().collect();
+        if rolled_over {
+            for symbol_state in &symbols {
+                symbol_state.volume_profile.reset(now);
+            }
+        }
+        // the pre-open is a call, which is uncrossed at the open
+        for symbol_state in &symbols {
+            match symbol_state.follow_session().await {
+                Ok(true) => info!(
+                    "{} follows the {} session",
+                    symbol_state.symbol,
+                    phase.name()
+                ),
+                Ok(false) => (),
+                Err(e) => warn!("failed to follow session on {}: {}", symbol_state.symbol, e),
+            }
+        }
+        if phase == SessionPhase::Closed {
+            for symbol_state in &symbols {
+                match symbol_state.expire_orders(now).await {
+                    Ok(purged) => info!("purged {} orders of {}", purged, symbol_state.symbol),
+                    Err(e) => warn!("failed to purge orders of {}: {}", symbol_state.symbol, e),
+                }
+            }
+        }
+        let events: Vec<OutboxEvent> = symbols
+            .iter()
+            .map(|symbol_state| {
+                let (payload, schema_name) =
+                    session_event_to_proto(phase, symbol_state.symbol.clone(), now);
+                OutboxEvent {
+                    topic: symbol_state.kafka_topic.clone(),
+                    schema_name: schema_name.to_string(),
+                    payload,
+                }
+            })
+            .collect();
+        if let Err(e) = self.outbox.enqueue(events) {
+            error!("failed to enqueue session events to outbox: {}", e);
+        }
+    }
+}
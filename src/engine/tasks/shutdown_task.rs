@@ -14,11 +14,15 @@ impl Shutdown {
         }
     }
     pub async fn run(&self) {
-        signal::ctrl_c()
-            .await
-            .expect("failed to listen for shutdown signal");
-        info!("shutdown signal received");
-        self.shutdown_notification.notify_waiters();
-        info!("notified all waiters for shutdown");
+        tokio::select! {
+            result = signal::ctrl_c() => {
+                result.expect("failed to listen for shutdown signal");
+                info!("shutdown signal received");
+                self.shutdown_notification.notify_waiters();
+                info!("notified all waiters for shutdown");
+            }
+            // shutdown escalated by the task manager
+            _ = self.shutdown_notification.notified() => {}
+        }
     }
 }
@@ -1,40 +1,142 @@
-use crate::engine::services::orderbook_manager_service::OrderbookManager;
+use crate::engine::constants::property_loader::TunableProperties;
+use crate::engine::state::runtime_settings::RuntimeSettings;
+use crate::engine::state::server_state::Symbols;
+use crate::engine::state::symbol_state::{SnapshotMark, SymbolState};
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::sync::Notify;
-use tokio::time::sleep;
-use tracing::info;
+use tokio::time::{sleep, Instant};
+use tracing::{info, warn};
 
+/// How often the operation counters of the symbols are checked.
+const CHURN_CHECK_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Snapshots the orderbook of every symbol as it changes, after a number of operations or after
+/// an interval, whichever comes first. Unchanged orderbooks are not snapshotted. The snapshots
+/// are taken by the executors in between batches, as they copy the book the executor changes.
 pub struct Snapshot {
     pub shutdown_notification: Arc<Notify>,
-    pub orderbook_manager: Arc<OrderbookManager>,
-    pub snapshot_interval: Duration,
+    pub snapshot_notification: Arc<Notify>,
+    pub symbols: Symbols,
+    pub runtime_settings: Arc<RuntimeSettings>,
+    pub checkpoint_interval: Duration,
 }
 
 impl Snapshot {
     pub fn new(
         shutdown_notification: Arc<Notify>,
-        orderbook_manager: Arc<OrderbookManager>,
-        snapshot_interval: Duration,
+        snapshot_notification: Arc<Notify>,
+        symbols: Symbols,
+        runtime_settings: Arc<RuntimeSettings>,
+        checkpoint_interval: Duration,
     ) -> Self {
         Self {
             shutdown_notification,
-            orderbook_manager,
-            snapshot_interval,
+            snapshot_notification,
+            symbols,
+            runtime_settings,
+            checkpoint_interval,
         }
     }
 
     pub async fn run(&self) {
+        let mut last_checkpoint = Instant::now();
         loop {
+            let tunable_properties = self.runtime_settings.current();
+            let check_interval = tunable_properties
+                .orderbook_snapshot_interval
+                .min(CHURN_CHECK_INTERVAL);
             tokio::select! {
                 _ = self.shutdown_notification.notified() => {
                     info!("shutting down snapshot_task");
                     break;
                 },
-                _ = sleep(self.snapshot_interval) => {
-                    self.orderbook_manager.snapshot();
+                _ = self.snapshot_notification.notified() => {
+                    info!("taking requested snapshot");
+                    for symbol_state in self.symbols() {
+                        take_snapshot(&symbol_state).await;
+                    }
+                }
+                _ = sleep(check_interval) => {
+                    let symbols = self.symbols();
+                    for symbol_state in &symbols {
+                        let operation_count = symbol_state.operation_count.load(Ordering::Relaxed);
+                        let due = match *symbol_state.last_snapshot.lock().unwrap() {
+                            Some(mark) => snapshot_due(&mark, operation_count, &tunable_properties),
+                            None => true,
+                        };
+                        if due {
+                            take_snapshot(symbol_state).await;
+                        }
+                    }
+                    if last_checkpoint.elapsed() >= self.checkpoint_interval {
+                        for symbol_state in &symbols {
+                            if let Some(write_ahead_log) = &symbol_state.write_ahead_log {
+                                write_ahead_log.request_checkpoint();
+                            }
+                        }
+                        last_checkpoint = Instant::now();
+                    }
                 }
             }
         }
     }
+
+    fn symbols(&self) -> Vec<Arc<SymbolState>> {
+        self.symbols.read().unwrap().values().cloned().collect()
+    }
+}
+
+async fn take_snapshot(symbol_state: &SymbolState) {
+    if let Err(e) = symbol_state.snapshot_book().await {
+        warn!("failed to snapshot {}: {}", symbol_state.symbol, e);
+    }
+}
+
+fn snapshot_due(
+    mark: &SnapshotMark,
+    operation_count: u64,
+    tunable_properties: &TunableProperties,
+) -> bool {
+    let operations = operation_count - mark.operation_count;
+    operations > 0
+        && (operations >= tunable_properties.orderbook_snapshot_operations
+            || mark.taken_at.elapsed() >= tunable_properties.orderbook_snapshot_interval)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::engine::constants::property_loader::TunableProperties;
+    use crate::engine::state::symbol_state::SnapshotMark;
+    use crate::engine::tasks::snapshot_task::snapshot_due;
+    use std::time::Duration;
+    use tokio::time::Instant;
+
+    #[test]
+    fn it_snapshots_after_operations_or_interval() {
+        let tunable_properties = TunableProperties {
+            order_exec_batch_size: 1,
+            order_exec_batch_timeout: Duration::from_millis(1),
+            orderbook_snapshot_interval: Duration::from_secs(60),
+            orderbook_snapshot_operations: 100,
+            metrics_report_interval: Duration::from_millis(1),
+            stream_interval: Duration::from_millis(1),
+            order_expiry_interval: Duration::from_millis(1),
+        };
+        let recent = SnapshotMark {
+            operation_count: 50,
+            taken_at: Instant::now(),
+        };
+        assert!(!snapshot_due(&recent, 50, &tunable_properties));
+        assert!(!snapshot_due(&recent, 149, &tunable_properties));
+        assert!(snapshot_due(&recent, 150, &tunable_properties));
+
+        let stale = SnapshotMark {
+            operation_count: 50,
+            taken_at: Instant::now() - Duration::from_secs(61),
+        };
+        assert!(!snapshot_due(&stale, 50, &tunable_properties));
+        assert!(snapshot_due(&stale, 51, &tunable_properties));
+    }
 }
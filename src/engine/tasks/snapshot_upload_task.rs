@@ -0,0 +1,120 @@
+use crate::engine::persistence::snapshot_store::{BookSnapshot, SnapshotStore};
+#[cfg(feature = "chaos")]
+use crate::engine::state::faults::Faults;
+use crate::engine::state::server_state::{ServerState, Symbols};
+use crate::engine::utils::time::generate_u128_timestamp;
+use std::collections::HashMap;
+use std::sync::atomic::Ordering;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::Notify;
+use tokio::time::sleep;
+#[cfg(feature = "chaos")]
+use tracing::warn;
+use tracing::{error, info};
+
+/// Periodically uploads the latest snapshot of every book that changed since its last upload to
+/// the snapshot store.
+pub struct SnapshotUploader {
+    pub shutdown_notification: Arc<Notify>,
+    pub symbols: Symbols,
+    pub snapshot_store: Arc<SnapshotStore>,
+    pub interval: Duration,
+    /// Operation count of the last upload of every symbol.
+    uploaded: Mutex<HashMap<String, u64>>,
+    #[cfg(feature = "chaos")]
+    faults: Arc<Faults>,
+}
+
+impl SnapshotUploader {
+    pub fn new(
+        state: Arc<ServerState>,
+        snapshot_store: Arc<SnapshotStore>,
+        interval: Duration,
+    ) -> Self {
+        Self {
+            shutdown_notification: Arc::clone(&state.shutdown_notification),
+            symbols: Arc::clone(&state.symbols),
+            snapshot_store,
+            interval,
+            uploaded: Mutex::new(HashMap::new()),
+            #[cfg(feature = "chaos")]
+            faults: Arc::clone(&state.faults),
+        }
+    }
+
+    pub async fn run(&self) {
+        loop {
+            tokio::select! {
+                _ = self.shutdown_notification.notified() => {
+                    info!("shutting down snapshot_upload_task");
+                    break;
+                },
+                _ = sleep(self.interval) => {
+                    self.upload().await
+                }
+            }
+        }
+    }
+
+    /// Uploads the books whose snapshot changed since they were last uploaded. A failed upload
+    /// is tried again on the next run.
+    pub async fn upload(&self) {
+        let symbols: Vec<_> = self.symbols.read().unwrap().values().cloned().collect();
+        for symbol_state in symbols {
+            let operation_count = match *symbol_state.last_snapshot.lock().unwrap() {
+                Some(mark) => mark.operation_count,
+                None => symbol_state.operation_count.load(Ordering::Relaxed),
+            };
+            let uploaded = self
+                .uploaded
+                .lock()
+                .unwrap()
+                .get(&symbol_state.symbol)
+                .copied();
+            if uploaded == Some(operation_count) {
+                continue;
+            }
+            // a dropped snapshot counts as uploaded, so it is lost until the book changes again
+            #[cfg(feature = "chaos")]
+            if self.faults.take_dropped_snapshot() {
+                warn!("dropping snapshot of {}", symbol_state.symbol);
+                self.uploaded
+                    .lock()
+                    .unwrap()
+                    .insert(symbol_state.symbol.clone(), operation_count);
+                continue;
+            }
+            let secondary = symbol_state.orderbook_manager.get_secondary();
+            let snapshot = BookSnapshot {
+                taken_at: generate_u128_timestamp(),
+                operation_count,
+                last_trade_price: secondary.get_last_trade_price(),
+                call: secondary.in_call(),
+                orders: secondary.resting_orders(),
+            };
+            match self
+                .snapshot_store
+                .upload(&symbol_state.symbol, &snapshot)
+                .await
+            {
+                Ok(key) => {
+                    info!(
+                        "uploaded snapshot of {} with {} resting orders to {}",
+                        symbol_state.symbol,
+                        snapshot.orders.len(),
+                        key
+                    );
+                    self.uploaded
+                        .lock()
+                        .unwrap()
+                        .insert(symbol_state.symbol.clone(), operation_count);
+                }
+                Err(e) => error!(
+                    "failed to upload snapshot of {}: {}",
+                    symbol_state.symbol, e
+                ),
+            }
+        }
+    }
+}
@@ -1,54 +1,436 @@
-use crate::engine::services::orderbook_manager_service::OrderbookManager;
+use crate::engine::constants::property_loader::TaskStopTimeouts;
+use crate::engine::state::runtime_settings::RuntimeSettings;
+use crate::engine::state::server_state::Symbols;
+use crate::engine::tasks::reload_task::Reload;
 use crate::engine::tasks::shutdown_task::Shutdown;
 use crate::engine::tasks::snapshot_task::Snapshot;
 use std::collections::HashMap;
+use std::error::Error;
 use std::future::Future;
-use std::sync::Arc;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 use tokio::sync::Notify;
-use tokio::task::JoinHandle;
-use tracing::info;
+use tokio::task::{AbortHandle, JoinHandle};
+use tokio::time::sleep;
+use tracing::{error, info, warn};
 
+/// Time to wait before restarting a task that exited unexpectedly.
+const RESTART_DELAY: Duration = Duration::from_secs(1);
+
+/// Decides what the task manager does when a task exits before shutdown, either by panicking or
+/// by returning.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RestartPolicy {
+    /// Restarts the task up to the given number of times before escalating to shutdown.
+    Restart(u32),
+    /// Shuts down the server right away.
+    Escalate,
+}
+
+/// The lifecycle of a managed task.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TaskStatus {
+    Running,
+    /// Exited unexpectedly and waiting to be restarted.
+    Restarting,
+    Stopped,
+    /// Exited unexpectedly and could not be recovered.
+    Failed,
+}
+
+impl TaskStatus {
+    pub fn name(&self) -> &'static str {
+        match self {
+            TaskStatus::Running => "running",
+            TaskStatus::Restarting => "restarting",
+            TaskStatus::Stopped => "stopped",
+            TaskStatus::Failed => "failed",
+        }
+    }
+}
+
+/// Point in time description of a managed task.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TaskInfo {
+    pub id: String,
+    pub status: TaskStatus,
+    pub restarts: u32,
+}
+
+/// State shared between a managed task, its supervisor and the task manager.
+struct TaskState {
+    status: Mutex<TaskStatus>,
+    restarts: AtomicU32,
+    /// Notified when the task is deregistered.
+    stop_notification: Notify,
+    /// Aborts the current run of the task.
+    abort_handle: Mutex<Option<AbortHandle>>,
+}
+
+impl TaskState {
+    fn set_status(&self, status: TaskStatus) {
+        *self.status.lock().unwrap() = status;
+    }
+
+    fn abort(&self) {
+        if let Some(abort_handle) = self.abort_handle.lock().unwrap().as_ref() {
+            abort_handle.abort();
+        }
+    }
+}
+
+struct ManagedTask {
+    state: Arc<TaskState>,
+    supervisor: JoinHandle<()>,
+}
+
+/// Runs and supervises the long-running tasks of the server, which can be registered and
+/// deregistered at any time.
 pub struct TaskManager {
-    tasks: HashMap<&'static str, JoinHandle<()>>,
+    shutdown_notification: Arc<Notify>,
+    tasks: Mutex<HashMap<String, ManagedTask>>,
+    stop_timeouts: TaskStopTimeouts,
+    /// The tasks that did not stop cleanly, along with what went wrong.
+    unclean_stops: Mutex<Vec<(String, String)>>,
 }
 
 impl TaskManager {
     pub fn init(
         shutdown_notification: Arc<Notify>,
-        orderbook_manager: Arc<OrderbookManager>,
-        snapshot_interval: Duration,
+        snapshot_notification: Arc<Notify>,
+        symbols: Symbols,
+        runtime_settings: Arc<RuntimeSettings>,
+        checkpoint_interval: Duration,
+        stop_timeouts: TaskStopTimeouts,
     ) -> Self {
-        let mut task_manager = TaskManager {
-            tasks: HashMap::new(),
+        let task_manager = TaskManager {
+            shutdown_notification: Arc::clone(&shutdown_notification),
+            tasks: Mutex::new(HashMap::new()),
+            stop_timeouts,
+            unclean_stops: Mutex::new(Vec::new()),
         };
-        task_manager.register("shutdown_task", {
+        task_manager.register("shutdown_task", RestartPolicy::Escalate, {
+            let shutdown_notify = Arc::clone(&shutdown_notification);
+            move || {
+                let shutdown_notify = Arc::clone(&shutdown_notify);
+                async move {
+                    Shutdown::new(shutdown_notify).run().await;
+                }
+            }
+        });
+        task_manager.register("reload_task", RestartPolicy::Restart(3), {
             let shutdown_notify = Arc::clone(&shutdown_notification);
-            async move {
-                Shutdown::new(shutdown_notify).run().await;
+            let runtime_settings = Arc::clone(&runtime_settings);
+            move || {
+                let shutdown_notify = Arc::clone(&shutdown_notify);
+                let runtime_settings = Arc::clone(&runtime_settings);
+                async move {
+                    Reload::new(shutdown_notify, runtime_settings).run().await;
+                }
             }
         });
-        task_manager.register("snapshot_task", {
+        task_manager.register("snapshot_task", RestartPolicy::Restart(3), {
             let shutdown_notify = Arc::clone(&shutdown_notification);
-            let manager = Arc::clone(&orderbook_manager);
-            async move {
-                Snapshot::new(shutdown_notify, manager, snapshot_interval)
+            move || {
+                let shutdown_notify = Arc::clone(&shutdown_notify);
+                let snapshot_notification = Arc::clone(&snapshot_notification);
+                let symbols = Arc::clone(&symbols);
+                let runtime_settings = Arc::clone(&runtime_settings);
+                async move {
+                    Snapshot::new(
+                        shutdown_notify,
+                        snapshot_notification,
+                        symbols,
+                        runtime_settings,
+                        checkpoint_interval,
+                    )
                     .run()
                     .await;
+                }
             }
         });
         task_manager
     }
 
-    pub fn register<F>(&mut self, id: &'static str, task: F)
+    /// Starts a supervised task, which is created again by `task` whenever the restart policy
+    /// restarts it.
+    pub fn register<T, F>(&self, id: impl Into<String>, restart_policy: RestartPolicy, task: T)
     where
+        T: Fn() -> F + Send + 'static,
         F: Future<Output = ()> + Send + 'static,
     {
-        self.tasks.insert(id, tokio::spawn(task));
+        let id = id.into();
+        let mut tasks = self.tasks.lock().unwrap();
+        if tasks.contains_key(&id) {
+            warn!("task {} is already registered", id);
+            return;
+        }
+        let state = Arc::new(TaskState {
+            status: Mutex::new(TaskStatus::Running),
+            restarts: AtomicU32::new(0),
+            stop_notification: Notify::new(),
+            abort_handle: Mutex::new(None),
+        });
+        let supervisor = tokio::spawn(supervise(
+            id.clone(),
+            restart_policy,
+            task,
+            Arc::clone(&state),
+            Arc::clone(&self.shutdown_notification),
+        ));
         info!("successfully registered task: {}", id);
+        tasks.insert(id, ManagedTask { state, supervisor });
+    }
+
+    /// Signals the task to stop and waits for it to exit, aborting it if it takes longer than
+    /// `timeout`. Tasks exit by themselves on shutdown, otherwise they are aborted right away.
+    /// Tasks that are aborted or that failed are recorded as unclean stops.
+    pub async fn deregister(&self, id: &str, timeout: Duration) -> Result<(), Box<dyn Error>> {
+        let task = self
+            .tasks
+            .lock()
+            .unwrap()
+            .remove(id)
+            .ok_or_else(|| format!("unknown task: {}", id))?;
+        task.state.stop_notification.notify_one();
+        match tokio::time::timeout(timeout, task.supervisor).await {
+            Ok(result) => {
+                if let Err(e) = &result {
+                    self.record_unclean_stop(id, format!("supervisor failed: {}", e));
+                } else if *task.state.status.lock().unwrap() == TaskStatus::Failed {
+                    self.record_unclean_stop(id, "failed".to_string());
+                }
+                Ok(result?)
+            }
+            Err(_) => {
+                task.state.abort();
+                task.state.set_status(TaskStatus::Stopped);
+                self.record_unclean_stop(id, format!("aborted after {:?}", timeout));
+                Err(format!("task {} did not stop within {:?}, aborted it", id, timeout).into())
+            }
+        }
+    }
+
+    /// Deregisters the task with the stop timeout configured for it.
+    pub async fn stop(&self, id: &str) -> Result<(), Box<dyn Error>> {
+        self.deregister(id, self.stop_timeouts.of(id)).await
+    }
+
+    /// The tasks that did not stop cleanly so far and why, in the order they were stopped.
+    pub fn unclean_stops(&self) -> Vec<(String, String)> {
+        self.unclean_stops.lock().unwrap().clone()
+    }
+
+    fn record_unclean_stop(&self, id: &str, reason: String) {
+        self.unclean_stops
+            .lock()
+            .unwrap()
+            .push((id.to_string(), reason));
+    }
+
+    pub fn status(&self, id: &str) -> Option<TaskStatus> {
+        let tasks = self.tasks.lock().unwrap();
+        tasks.get(id).map(|task| *task.state.status.lock().unwrap())
+    }
+
+    /// Returns the registered tasks ordered by id.
+    pub fn list(&self) -> Vec<TaskInfo> {
+        let mut tasks: Vec<TaskInfo> = self
+            .tasks
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(id, task)| TaskInfo {
+                id: id.clone(),
+                status: *task.state.status.lock().unwrap(),
+                restarts: task.state.restarts.load(Ordering::Relaxed),
+            })
+            .collect();
+        tasks.sort_by(|a, b| a.id.cmp(&b.id));
+        tasks
+    }
+}
+
+/// Runs the task until it exits after shutdown was signalled or until it is deregistered,
+/// applying the restart policy every time it exits before that.
+async fn supervise<T, F>(
+    id: String,
+    restart_policy: RestartPolicy,
+    task: T,
+    state: Arc<TaskState>,
+    shutdown_notification: Arc<Notify>,
+) where
+    T: Fn() -> F,
+    F: Future<Output = ()> + Send + 'static,
+{
+    let shutdown = shutdown_notification.notified();
+    tokio::pin!(shutdown);
+    loop {
+        let mut handle = tokio::spawn(task());
+        *state.abort_handle.lock().unwrap() = Some(handle.abort_handle());
+        state.set_status(TaskStatus::Running);
+        tokio::select! {
+            biased;
+            _ = &mut shutdown => {
+                match handle.await {
+                    Ok(()) => state.set_status(TaskStatus::Stopped),
+                    Err(e) if e.is_cancelled() => state.set_status(TaskStatus::Stopped),
+                    Err(e) => {
+                        error!("task {} failed while shutting down: {}", id, e);
+                        state.set_status(TaskStatus::Failed);
+                    }
+                }
+                return;
+            }
+            _ = state.stop_notification.notified() => {
+                handle.abort();
+                let _ = handle.await;
+                state.set_status(TaskStatus::Stopped);
+                info!("stopped task {}", id);
+                return;
+            }
+            result = &mut handle => match result {
+                Ok(()) => error!("task {} exited unexpectedly", id),
+                Err(e) => error!("task {} failed: {}", id, e),
+            },
+        }
+        let restarts = state.restarts.load(Ordering::Relaxed);
+        match restart_policy {
+            RestartPolicy::Restart(max_restarts) if restarts < max_restarts => {
+                state.restarts.store(restarts + 1, Ordering::Relaxed);
+                state.set_status(TaskStatus::Restarting);
+                warn!(
+                    "restarting task {} in {:?}, attempt {} of {}",
+                    id,
+                    RESTART_DELAY,
+                    restarts + 1,
+                    max_restarts
+                );
+                tokio::select! {
+                    _ = sleep(RESTART_DELAY) => {},
+                    _ = state.stop_notification.notified() => {
+                        state.set_status(TaskStatus::Stopped);
+                        return;
+                    }
+                }
+            }
+            _ => {
+                state.set_status(TaskStatus::Failed);
+                error!("task {} cannot be recovered, shutting down", id);
+                shutdown_notification.notify_waiters();
+                return;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::engine::constants::property_loader::TaskStopTimeouts;
+    use crate::engine::tasks::task_manager::{RestartPolicy, TaskManager, TaskStatus};
+    use std::collections::HashMap;
+    use std::future::pending;
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+    use tokio::sync::Notify;
+
+    const TIMEOUT: Duration = Duration::from_secs(1);
+
+    fn task_manager(shutdown_notification: &Arc<Notify>) -> TaskManager {
+        TaskManager {
+            shutdown_notification: Arc::clone(shutdown_notification),
+            tasks: Mutex::new(HashMap::new()),
+            stop_timeouts: TaskStopTimeouts::default(),
+            unclean_stops: Mutex::new(Vec::new()),
+        }
+    }
+
+    #[tokio::test]
+    async fn it_restarts_failed_tasks_then_escalates() {
+        let shutdown_notification = Arc::new(Notify::new());
+        let task_manager = task_manager(&shutdown_notification);
+        let starts = Arc::new(AtomicU32::new(0));
+        let shutdown = shutdown_notification.notified();
+        task_manager.register("failing_task", RestartPolicy::Restart(1), {
+            let starts = Arc::clone(&starts);
+            move || {
+                let starts = Arc::clone(&starts);
+                async move {
+                    starts.fetch_add(1, Ordering::SeqCst);
+                    panic!("task failure");
+                }
+            }
+        });
+        shutdown.await;
+        assert_eq!(starts.load(Ordering::SeqCst), 2);
+        let tasks = task_manager.list();
+        assert_eq!(tasks[0].status, TaskStatus::Failed);
+        assert_eq!(tasks[0].restarts, 1);
+        task_manager
+            .deregister("failing_task", TIMEOUT)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn it_stops_supervising_on_shutdown() {
+        let shutdown_notification = Arc::new(Notify::new());
+        let task_manager = task_manager(&shutdown_notification);
+        task_manager.register("waiting_task", RestartPolicy::Escalate, {
+            let shutdown_notification = Arc::clone(&shutdown_notification);
+            move || {
+                let shutdown_notification = Arc::clone(&shutdown_notification);
+                async move { shutdown_notification.notified().await }
+            }
+        });
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        shutdown_notification.notify_waiters();
+        task_manager
+            .deregister("waiting_task", TIMEOUT)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn it_deregisters_running_tasks() {
+        let shutdown_notification = Arc::new(Notify::new());
+        let task_manager = task_manager(&shutdown_notification);
+        task_manager.register("idle_task", RestartPolicy::Escalate, pending::<()>);
+        task_manager.register("other_task", RestartPolicy::Escalate, pending::<()>);
+        assert_eq!(task_manager.status("idle_task"), Some(TaskStatus::Running));
+        task_manager.deregister("idle_task", TIMEOUT).await.unwrap();
+        assert_eq!(task_manager.status("idle_task"), None);
+        assert!(task_manager.deregister("idle_task", TIMEOUT).await.is_err());
+        let tasks = task_manager.list();
+        assert_eq!(tasks.len(), 1);
+        assert_eq!(tasks[0].id, "other_task");
     }
 
-    pub fn deregister(&mut self, id: &'static str) -> JoinHandle<()> {
-        self.tasks.remove(&id).unwrap()
+    #[tokio::test]
+    async fn it_aborts_tasks_that_ignore_shutdown() {
+        let shutdown_notification = Arc::new(Notify::new());
+        let mut task_manager = task_manager(&shutdown_notification);
+        task_manager.stop_timeouts = TaskStopTimeouts {
+            default: TIMEOUT,
+            tasks: HashMap::from([("stuck_task".to_string(), Duration::from_millis(10))]),
+        };
+        task_manager.register("stuck_task", RestartPolicy::Escalate, pending::<()>);
+        task_manager.register("waiting_task", RestartPolicy::Escalate, {
+            let shutdown_notification = Arc::clone(&shutdown_notification);
+            move || {
+                let shutdown_notification = Arc::clone(&shutdown_notification);
+                async move { shutdown_notification.notified().await }
+            }
+        });
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        shutdown_notification.notify_waiters();
+        assert!(task_manager.stop("stuck_task").await.is_err());
+        task_manager.stop("waiting_task").await.unwrap();
+        assert_eq!(
+            task_manager.unclean_stops(),
+            vec![("stuck_task".to_string(), "aborted after 10ms".to_string())]
+        );
     }
 }
@@ -0,0 +1,51 @@
+use crate::engine::persistence::audit_log::AuditLog;
+use crate::engine::utils::time::generate_u128_timestamp;
+use std::fmt::Debug;
+use std::future::Future;
+use tonic::metadata::MetadataValue;
+use tonic::{Request, Response, Status};
+use tracing::error;
+
+/// Metadata key of the response carrying the sequence number of the audit record of the request.
+pub const AUDIT_SEQUENCE_KEY: &str = "audit-sequence";
+
+/// Runs the handler of a request and, when the audit log is kept, records the request along
+/// with the decision taken on it. The sequence number of the record is returned to the client in
+/// the `audit-sequence` metadata of the response, whether the request was accepted or not.
+pub async fn audited<T, R, F>(
+    audit_log: Option<&AuditLog>,
+    principal: &str,
+    method: &str,
+    request: Request<T>,
+    handler: impl FnOnce(Request<T>) -> F,
+) -> Result<Response<R>, Status>
+where
+    T: Debug,
+    F: Future<Output = Result<Response<R>, Status>>,
+{
+    let Some(audit_log) = audit_log else {
+        return handler(request).await;
+    };
+    let principal = match principal {
+        "" => "anonymous",
+        principal => principal,
+    };
+    let payload = format!("{:?}", request.get_ref());
+    let mut outcome = handler(request).await;
+    let decision = match &outcome {
+        Ok(_) => "accepted".to_string(),
+        Err(status) => format!("rejected: {:?}: {}", status.code(), status.message()),
+    };
+    let timestamp = generate_u128_timestamp();
+    match audit_log.record(timestamp, principal, method, &payload, &decision) {
+        Ok(sequence) => {
+            let metadata = match &mut outcome {
+                Ok(response) => response.metadata_mut(),
+                Err(status) => status.metadata_mut(),
+            };
+            metadata.insert(AUDIT_SEQUENCE_KEY, MetadataValue::from(sequence));
+        }
+        Err(e) => error!("failed to record {} request in audit log: {}", method, e),
+    }
+    outcome
+}
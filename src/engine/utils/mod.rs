@@ -1,2 +1,5 @@
+pub mod audit;
+pub mod order_flow;
 pub mod protobuf;
+pub mod retry;
 pub mod time;
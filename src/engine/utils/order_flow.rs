@@ -0,0 +1,235 @@
+use crate::core::models::Side;
+use std::str::FromStr;
+use std::time::Duration;
+
+/// A small, fast pseudo random generator (splitmix64). It is not suited for anything security
+/// related, but it makes synthetic order flow reproducible from a seed without extra dependencies.
+#[derive(Debug, Clone)]
+pub struct SeededRng {
+    state: u64,
+}
+
+impl SeededRng {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    pub fn next_u128(&mut self) -> u128 {
+        (self.next_u64() as u128) << 64 | self.next_u64() as u128
+    }
+
+    /// A uniformly distributed value in `[0, 1)`.
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// A uniformly distributed value in `[0, bound)`, or zero if the bound is zero.
+    pub fn below(&mut self, bound: u64) -> u64 {
+        match bound {
+            0 => 0,
+            bound => self.next_u64() % bound,
+        }
+    }
+
+    /// A standard normally distributed value, using the Box-Muller transform.
+    pub fn next_gaussian(&mut self) -> f64 {
+        let u1 = 1.0 - self.next_f64();
+        let u2 = self.next_f64();
+        (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos()
+    }
+
+    /// An exponentially distributed value with the given mean.
+    pub fn next_exponential(&mut self, mean: f64) -> f64 {
+        -(1.0 - self.next_f64()).ln() * mean
+    }
+}
+
+/// How the prices of generated limit orders are spread around the mid price.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PriceDistribution {
+    /// Every price within the range is equally likely.
+    Uniform,
+    /// Prices cluster around the mid price, with the range covering three standard deviations.
+    Normal,
+}
+
+impl FromStr for PriceDistribution {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "uniform" => Ok(PriceDistribution::Uniform),
+            "normal" => Ok(PriceDistribution::Normal),
+            _ => Err(format!("unknown price distribution: {}", s)),
+        }
+    }
+}
+
+/// Shape of the synthetic order flow.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OrderFlowProperties {
+    pub mid_price: u64,
+    /// Largest distance of a limit price from the mid price. Bids and asks are both spread on
+    /// either side of the mid price, so part of the flow crosses the book and trades.
+    pub price_range: u64,
+    pub price_distribution: PriceDistribution,
+    /// Quantities are drawn uniformly from `1..=max_quantity`.
+    pub max_quantity: u64,
+    /// Share of the flow that cancels an earlier order instead of placing a new one.
+    pub cancel_ratio: f64,
+    /// Number of accounts the flow is spread over, named `account-0` onwards.
+    pub accounts: u32,
+}
+
+/// A single message of the synthetic flow.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FlowEvent {
+    Limit {
+        account: String,
+        side: Side,
+        price: u64,
+        quantity: u64,
+    },
+    Cancel {
+        account: String,
+        order_id: u128,
+    },
+}
+
+/// Generates order flow and arrival times from a seed, so that the same seed always yields the
+/// same sequence of events.
+pub struct OrderFlow {
+    properties: OrderFlowProperties,
+    rng: SeededRng,
+}
+
+impl OrderFlow {
+    pub fn new(properties: OrderFlowProperties, seed: u64) -> Self {
+        Self {
+            properties,
+            rng: SeededRng::new(seed),
+        }
+    }
+
+    /// The time until the next message of a poisson process arriving at the given rate.
+    pub fn next_arrival(&mut self, rate_per_second: f64) -> Duration {
+        Duration::from_secs_f64(self.rng.next_exponential(1.0 / rate_per_second))
+    }
+
+    /// Draws the next event.
+    ///
+    /// # Arguments
+    ///
+    /// * `cancellable` - Picks the id of the order a cancel targets, drawing from the generator
+    ///   it is given, or returns `None` when there is nothing to cancel, in which case a new order
+    ///   is placed instead.
+    pub fn next_event(
+        &mut self,
+        cancellable: impl FnOnce(&mut SeededRng) -> Option<u128>,
+    ) -> FlowEvent {
+        let account = format!(
+            "account-{}",
+            self.rng.below(self.properties.accounts.max(1) as u64)
+        );
+        if self.rng.next_f64() < self.properties.cancel_ratio {
+            if let Some(order_id) = cancellable(&mut self.rng) {
+                return FlowEvent::Cancel { account, order_id };
+            }
+        }
+        let side = match self.rng.below(2) {
+            0 => Side::Bid,
+            _ => Side::Ask,
+        };
+        FlowEvent::Limit {
+            account,
+            side,
+            price: self.next_price(),
+            quantity: 1 + self.rng.below(self.properties.max_quantity.max(1)),
+        }
+    }
+
+    fn next_price(&mut self) -> u64 {
+        let range = self.properties.price_range as f64;
+        let offset = match self.properties.price_distribution {
+            PriceDistribution::Uniform => (self.rng.next_f64() * 2.0 - 1.0) * range,
+            PriceDistribution::Normal => {
+                (self.rng.next_gaussian() * range / 3.0).clamp(-range, range)
+            }
+        };
+        (self.properties.mid_price as f64 + offset.round()).max(1.0) as u64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::engine::utils::order_flow::{
+        FlowEvent, OrderFlow, OrderFlowProperties, PriceDistribution,
+    };
+
+    fn properties(price_distribution: PriceDistribution) -> OrderFlowProperties {
+        OrderFlowProperties {
+            mid_price: 1_000,
+            price_range: 50,
+            price_distribution,
+            max_quantity: 10,
+            cancel_ratio: 0.25,
+            accounts: 3,
+        }
+    }
+
+    fn generate(flow: &mut OrderFlow, count: usize) -> Vec<FlowEvent> {
+        (0..count)
+            .map(|i| flow.next_event(|rng| (i > 0).then(|| rng.below(i as u64) as u128)))
+            .collect()
+    }
+
+    #[test]
+    fn it_generates_reproducible_flow_within_bounds() {
+        for distribution in [PriceDistribution::Uniform, PriceDistribution::Normal] {
+            let events = generate(&mut OrderFlow::new(properties(distribution), 7), 10_000);
+            assert_eq!(
+                events,
+                generate(&mut OrderFlow::new(properties(distribution), 7), 10_000)
+            );
+            assert_ne!(
+                events,
+                generate(&mut OrderFlow::new(properties(distribution), 8), 10_000)
+            );
+
+            let mut cancels = 0;
+            for event in &events {
+                match event {
+                    FlowEvent::Limit {
+                        account,
+                        price,
+                        quantity,
+                        ..
+                    } => {
+                        assert!((950..=1_050).contains(price));
+                        assert!((1..=10).contains(quantity));
+                        let accounts = ["account-0", "account-1", "account-2"];
+                        assert!(accounts.contains(&account.as_str()));
+                    }
+                    FlowEvent::Cancel { .. } => cancels += 1,
+                }
+            }
+            assert!((2_000..3_000).contains(&cancels));
+        }
+
+        let mut flow = OrderFlow::new(properties(PriceDistribution::Uniform), 7);
+        let mean = (0..10_000)
+            .map(|_| flow.next_arrival(1_000.0).as_secs_f64())
+            .sum::<f64>()
+            / 10_000.0;
+        assert!((0.0009..0.0011).contains(&mean));
+        assert!("poisson".parse::<PriceDistribution>().is_err());
+    }
+}
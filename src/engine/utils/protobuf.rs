@@ -1,64 +1,228 @@
 use crate::core::models::{
-    ExecutionResult, FillMetaData, FillResult, LimitOrder, ModifyResult, OrderbookAggregated,
-    RfqStatus,
+    DepthDiff, ExecutionResult, FillMetaData, FillResult, Granularity,
+    IndicativeUncross as BookIndicativeUncross, Level as BookLevel, LimitOrder,
+    MemoryStats as BookMemoryStats, ModifyResult, OrderbookAggregated, RfqStatus, Side,
+    SlippagePoint, CHECKSUM_LEVELS,
 };
+use crate::engine::accounts::ledger::Balance as AccountBalance;
+use crate::engine::accounts::limits::RiskLimits as AccountRiskLimits;
+use crate::engine::accounts::market_making::ProtectionTrip;
+use crate::engine::accounts::positions::Position as AccountPosition;
+use crate::engine::accounts::settlement::SettlementInstruction as AccountSettlementInstruction;
+use crate::engine::errors::ValidationError;
+use crate::engine::state::average_price::AveragePrice as RollingAveragePrice;
+use crate::engine::state::book_feed::{BookEvent as BookFeedEvent, SequencedEvent};
+use crate::engine::state::candles::{Candle as BookCandle, CandleInterval};
+use crate::engine::state::channel_metrics::ChannelMetrics as ChannelMetricsSnapshot;
+use crate::engine::state::delivery_metrics::DeliveryMetricsSnapshot;
+use crate::engine::state::heatmap::HeatmapMatrix;
+use crate::engine::state::latency_metrics::LatencySummary as OperationLatencySummary;
+use crate::engine::state::market_summary::SymbolSummary;
+use crate::engine::state::order_history::{OrderRevisions, Revision, RevisionKind};
+use crate::engine::state::overload::Load;
+use crate::engine::state::replication::Replication;
+use crate::engine::state::session::SessionPhase;
+use crate::engine::state::ticker::TickerStats;
+use crate::engine::state::volume_profile::VolumeProfileSnapshot;
+use crate::engine::tasks::task_manager::TaskInfo as ManagedTaskInfo;
+use crate::engine::utils::time::generate_u128_timestamp;
 use crate::protobuf::models::{
-    CancelModifyOrder, CreateOrder, FillOrder, FillOrderData, GenericMessage, Level, OrderbookData,
-    PartialFillOrder, RfqResult,
+    AveragePrice, AveragePrices, Balance, BalanceList, Bbo, BookEvent, BookEventKind, CallEvent,
+    CancelModifyOrder, Candle, CandleInterval as ProtoCandleInterval, ChannelMetrics,
+    ChannelMetricsList, CreateOrder, DeliveryMetrics, FillOrder, FillOrderData, GenericMessage,
+    Granularity as ProtoGranularity, Heatmap, HeatmapRow, IndicativeUncross, LatencySummary, Level,
+    MarketSummary, MemoryStats, OperationLatency, OrderRevision, OrderStatusResponse,
+    OrderbookData, OrderbookUpdate, OverloadEvent, PartialFillOrder, Position, PositionList,
+    PriceVolume, ProtectionEvent, QuoteOrder, ReduceOrder, ReplicationStatus, RfqResult,
+    RiskLimits, SessionEvent, SettlementInstruction, SlippageCurve,
+    SlippagePoint as ProtoSlippagePoint, StreamHeader, SymbolSummary as ProtoSymbolSummary,
+    TaskInfo, TaskList, ThrottleEvent, Ticker, Trade, UncrossEvent, VolumeProfile,
 };
 use prost::Message;
 use schema_registry_converter::async_impl::proto_raw::ProtoRawEncoder;
+use schema_registry_converter::error::SRCError;
 use schema_registry_converter::schema_registry_common::SubjectNameStrategy;
 
-pub async fn exec_to_proto_encoded<'a>(
+/// Decodes the side of a request, rejecting values that are not a [`Side`].
+pub fn side_from_proto(side: i32) -> Result<Side, ValidationError> {
+    Ok(Side::try_from(side)?)
+}
+
+/// Decodes the granularity of a depth request, rejecting values that are not a [`Granularity`].
+pub fn granularity_from_proto(granularity: i32) -> Result<Granularity, ValidationError> {
+    match ProtoGranularity::try_from(granularity) {
+        Ok(ProtoGranularity::P00) => Ok(Granularity::P00),
+        Ok(ProtoGranularity::P0) => Ok(Granularity::P0),
+        Ok(ProtoGranularity::P) => Ok(Granularity::P),
+        Ok(ProtoGranularity::P10) => Ok(Granularity::P10),
+        Ok(ProtoGranularity::P100) => Ok(Granularity::P100),
+        Err(_) => Err(ValidationError::Granularity(granularity)),
+    }
+}
+
+/// Decodes the interval of a candle request, rejecting values that are not a [`CandleInterval`].
+pub fn candle_interval_from_proto(interval: i32) -> Result<CandleInterval, ValidationError> {
+    match ProtoCandleInterval::try_from(interval) {
+        Ok(ProtoCandleInterval::OneMinute) => Ok(CandleInterval::OneMinute),
+        Ok(ProtoCandleInterval::FiveMinutes) => Ok(CandleInterval::FiveMinutes),
+        Ok(ProtoCandleInterval::OneHour) => Ok(CandleInterval::OneHour),
+        Err(_) => Err(ValidationError::CandleInterval(interval)),
+    }
+}
+
+/// Decodes an order id sent as its 16 big endian bytes, rejecting ids of any other length.
+pub fn order_id_from_proto(order_id: &[u8]) -> Result<u128, ValidationError> {
+    order_id
+        .try_into()
+        .map(u128::from_be_bytes)
+        .map_err(|_| ValidationError::OrderIdLength(order_id.len()))
+}
+
+/// Encodes an execution event, a cancel event carries the history of the order if it is known.
+pub fn exec_to_proto<'a>(
     execution_result: ExecutionResult,
     symbol: String,
     timestamp: u128,
-    encoder: &ProtoRawEncoder<'a>,
-) -> Vec<u8> {
-    let (encoded_data, schema_name) = match execution_result {
+    history: Option<OrderRevisions>,
+) -> (Vec<u8>, &'a str) {
+    match execution_result {
         ExecutionResult::Executed(fill_result) => {
             fill_result_to_proto(fill_result, symbol, timestamp)
         }
         ExecutionResult::Modified(modify_result) => {
             modify_result_to_proto(modify_result, symbol, timestamp)
         }
-        ExecutionResult::Cancelled(id) => (
-            CancelModifyOrder {
-                status: 4,
-                order_id: id.to_be_bytes().to_vec(),
+        ExecutionResult::Cancelled(id) => {
+            let (version, original_quantity, revisions) = match history {
+                Some(history) => (
+                    history.version,
+                    history.original_quantity,
+                    history.revisions.iter().map(revision_to_proto).collect(),
+                ),
+                None => (0, 0, Vec::new()),
+            };
+            (
+                CancelModifyOrder {
+                    status: 4,
+                    order_id: id.to_be_bytes().to_vec(),
+                    symbol,
+                    timestamp: timestamp.to_be_bytes().to_vec(),
+                    version,
+                    original_quantity,
+                    revisions,
+                }
+                .encode_to_vec(),
+                "CancelModifyOrder",
+            )
+        }
+        ExecutionResult::Reduced(order) => (
+            ReduceOrder {
+                status: 5,
+                order_id: order.id.to_be_bytes().to_vec(),
+                price: order.price,
+                quantity: order.quantity,
+                side: order.side as i32,
                 symbol,
                 timestamp: timestamp.to_be_bytes().to_vec(),
             }
             .encode_to_vec(),
-            "CancelModifyOrder",
+            "ReduceOrder",
+        ),
+        ExecutionResult::Quoted(quote) => (
+            QuoteOrder {
+                status: 6,
+                replaced_order_ids: quote
+                    .replaced
+                    .iter()
+                    .map(|id| id.to_be_bytes().to_vec())
+                    .collect(),
+                bid: quote
+                    .bid
+                    .map(|order| limit_to_proto(order, symbol.clone(), timestamp)),
+                ask: quote
+                    .ask
+                    .map(|order| limit_to_proto(order, symbol.clone(), timestamp)),
+                filled_orders: quote
+                    .fills
+                    .iter()
+                    .map(|fill_data| fill_meta_data_to_proto(*fill_data))
+                    .collect(),
+                symbol,
+                timestamp: timestamp.to_be_bytes().to_vec(),
+            }
+            .encode_to_vec(),
+            "QuoteOrder",
         ),
-        ExecutionResult::Failed(message) => (
+        ExecutionResult::Called => (
+            CallEvent {
+                symbol,
+                timestamp: timestamp.to_be_bytes().to_vec(),
+            }
+            .encode_to_vec(),
+            "CallEvent",
+        ),
+        ExecutionResult::Uncrossed(fills) => (
+            UncrossEvent {
+                price: fills.first().map_or(0, |fill| fill.price),
+                quantity: fills.iter().map(|fill| fill.quantity).sum(),
+                filled_orders: fills.into_iter().map(fill_meta_data_to_proto).collect(),
+                symbol,
+                timestamp: timestamp.to_be_bytes().to_vec(),
+            }
+            .encode_to_vec(),
+            "UncrossEvent",
+        ),
+        ExecutionResult::Failed(error) => (
             GenericMessage {
-                message: message.clone(),
+                message: error.to_string(),
                 symbol,
                 timestamp: timestamp.to_be_bytes().to_vec(),
             }
             .encode_to_vec(),
             "GenericMessage",
         ),
+    }
+}
+
+pub fn order_status_to_proto(order_id: u128, history: OrderRevisions) -> OrderStatusResponse {
+    OrderStatusResponse {
+        order_id: order_id.to_be_bytes().to_vec(),
+        side: history.side as i32,
+        price: history.price,
+        quantity: history.quantity,
+        original_quantity: history.original_quantity,
+        version: history.version,
+        revisions: history.revisions.iter().map(revision_to_proto).collect(),
+    }
+}
+
+fn revision_to_proto(revision: &Revision) -> OrderRevision {
+    let kind = match revision.kind {
+        RevisionKind::Created => 0,
+        RevisionKind::Modified => 1,
+        RevisionKind::Filled => 2,
+        RevisionKind::Reduced => 3,
     };
-    encode_proto(encoded_data, schema_name, encoder).await
+    OrderRevision {
+        kind,
+        price: revision.price,
+        quantity: revision.quantity,
+        timestamp: revision.timestamp.to_be_bytes().to_vec(),
+    }
 }
 
-async fn encode_proto<'a>(
-    encoded_data: Vec<u8>,
+pub async fn encode_proto<'a>(
+    encoded_data: &[u8],
     schema_name: &str,
     proto_raw_encoder: &ProtoRawEncoder<'a>,
-) -> Vec<u8> {
+) -> Result<Vec<u8>, SRCError> {
     proto_raw_encoder
         .encode(
-            &encoded_data,
+            encoded_data,
             format!("models.{}", schema_name).as_str(),
             SubjectNameStrategy::RecordNameStrategy("models".to_string()),
         )
         .await
-        .unwrap()
 }
 
 pub fn rfq_to_proto(rfq_status: RfqStatus) -> RfqResult {
@@ -67,25 +231,241 @@ pub fn rfq_to_proto(rfq_status: RfqStatus) -> RfqResult {
             status: 0,
             price,
             quantity: 0,
+            ..Default::default()
         },
         RfqStatus::PartialFillAndLimitPlaced(price, quantity) => RfqResult {
             status: 1,
             price,
             quantity,
+            ..Default::default()
         },
         RfqStatus::ConvertToLimit(price, quantity) => RfqResult {
             status: 2,
             price,
             quantity,
+            ..Default::default()
         },
         RfqStatus::NotPossible => RfqResult {
             status: 3,
             price: 0,
             quantity: 0,
+            ..Default::default()
         },
     }
 }
 
+pub fn book_event_to_proto(event: SequencedEvent) -> BookEvent {
+    let (kind, order_id, side, price, quantity) = match event.event {
+        BookFeedEvent::Add(order) => (
+            BookEventKind::BookAdd,
+            order.id,
+            order.side,
+            order.price,
+            order.quantity,
+        ),
+        BookFeedEvent::Execute {
+            order_id,
+            price,
+            quantity,
+        } => (
+            BookEventKind::BookExecute,
+            order_id,
+            Side::Bid,
+            price,
+            quantity,
+        ),
+        BookFeedEvent::Cancel(order_id) => (BookEventKind::BookCancel, order_id, Side::Bid, 0, 0),
+        BookFeedEvent::Replace(order) => (
+            BookEventKind::BookReplace,
+            order.id,
+            order.side,
+            order.price,
+            order.quantity,
+        ),
+    };
+    BookEvent {
+        sequence: event.sequence,
+        kind: kind as i32,
+        order_id: order_id.to_be_bytes().to_vec(),
+        side: side as i32,
+        price,
+        quantity,
+        snapshot: false,
+        timestamp: event.timestamp.to_be_bytes().to_vec(),
+    }
+}
+
+/// Describes a resting order of the snapshot a book feed subscription starts with.
+pub fn book_snapshot_to_proto(order: LimitOrder, sequence: u64, timestamp: u128) -> BookEvent {
+    BookEvent {
+        snapshot: true,
+        ..book_event_to_proto(SequencedEvent {
+            sequence,
+            timestamp,
+            event: BookFeedEvent::Add(order),
+        })
+    }
+}
+
+pub fn bbo_to_proto(bid: Option<BookLevel>, ask: Option<BookLevel>) -> Bbo {
+    Bbo {
+        bid_price: bid.map_or(u64::MIN, |bid| bid.price),
+        bid_quantity: bid.map_or(0, |bid| bid.quantity),
+        ask_price: ask.map_or(u64::MAX, |ask| ask.price),
+        ask_quantity: ask.map_or(0, |ask| ask.quantity),
+        header: None,
+    }
+}
+
+/// Encodes the indicative uncross of a symbol, which is all zeros while the book does not cross.
+pub fn indicative_uncross_to_proto(
+    symbol: String,
+    call: bool,
+    uncross: Option<BookIndicativeUncross>,
+) -> IndicativeUncross {
+    let uncross = uncross.unwrap_or(BookIndicativeUncross {
+        price: 0,
+        quantity: 0,
+        imbalance: 0,
+        imbalance_side: None,
+    });
+    IndicativeUncross {
+        symbol,
+        call,
+        price: uncross.price,
+        quantity: uncross.quantity,
+        imbalance: uncross.imbalance,
+        imbalance_side: uncross.imbalance_side.unwrap_or(Side::Bid) as i32,
+        header: None,
+    }
+}
+
+pub fn candle_to_proto(candle: BookCandle, closed: bool) -> Candle {
+    Candle {
+        start: candle.start.to_be_bytes().to_vec(),
+        open: candle.open,
+        high: candle.high,
+        low: candle.low,
+        close: candle.close,
+        volume: candle.volume,
+        trade_count: candle.trade_count,
+        closed,
+        header: None,
+    }
+}
+
+pub fn ticker_to_proto(symbol: String, stats: TickerStats) -> Ticker {
+    Ticker {
+        symbol,
+        last_price: stats.last_price,
+        open_price: stats.open_price,
+        high_price: stats.high_price,
+        low_price: stats.low_price,
+        volume: stats.volume,
+        quote_volume: stats.quote_volume,
+        trade_count: stats.trade_count,
+        price_change: stats.price_change,
+        price_change_bps: stats.price_change_bps,
+        header: None,
+    }
+}
+
+pub fn average_prices_to_proto(symbol: String, prices: Vec<RollingAveragePrice>) -> AveragePrices {
+    AveragePrices {
+        symbol,
+        prices: prices
+            .into_iter()
+            .map(|price| AveragePrice {
+                window_millis: price.window.as_millis() as u64,
+                vwap: price.vwap.unwrap_or_default(),
+                twap: price.twap.unwrap_or_default(),
+                volume: price.volume,
+            })
+            .collect(),
+        header: None,
+    }
+}
+
+pub fn volume_profile_to_proto(symbol: String, profile: VolumeProfileSnapshot) -> VolumeProfile {
+    VolumeProfile {
+        symbol,
+        since: profile.since.to_be_bytes().to_vec(),
+        point_of_control: profile.point_of_control().unwrap_or_default(),
+        levels: profile
+            .levels
+            .into_iter()
+            .map(|level| PriceVolume {
+                price: level.price,
+                volume: level.volume,
+                buy_volume: level.buy_volume,
+                sell_volume: level.sell_volume,
+                trade_count: level.trade_count,
+            })
+            .collect(),
+    }
+}
+
+pub fn heatmap_to_proto(symbol: String, matrix: HeatmapMatrix) -> Heatmap {
+    Heatmap {
+        symbol,
+        prices: matrix.prices,
+        rows: matrix
+            .rows
+            .into_iter()
+            .map(|row| HeatmapRow {
+                start: row.start.to_be_bytes().to_vec(),
+                bids: row.bids,
+                asks: row.asks,
+            })
+            .collect(),
+    }
+}
+
+pub fn slippage_curve_to_proto(
+    symbol: String,
+    side: Side,
+    points: Vec<SlippagePoint>,
+    sequence: u64,
+) -> SlippageCurve {
+    SlippageCurve {
+        symbol,
+        side: side as i32,
+        points: points
+            .into_iter()
+            .map(|point| ProtoSlippagePoint {
+                notional: point.notional,
+                quantity: point.quantity,
+                average_price: point.average_price.unwrap_or_default(),
+                worst_price: point.worst_price.unwrap_or_default(),
+                impact_bps: point.impact_bps,
+                complete: point.complete,
+            })
+            .collect(),
+        sequence,
+    }
+}
+
+pub fn market_summary_to_proto(summaries: Vec<SymbolSummary>) -> MarketSummary {
+    MarketSummary {
+        symbols: summaries
+            .into_iter()
+            .map(|summary| ProtoSymbolSummary {
+                best_bid: summary.best_bid.unwrap_or_default(),
+                best_ask: summary.best_ask.unwrap_or_default(),
+                spread: summary.spread().unwrap_or_default(),
+                last_price: summary.ticker.last_price,
+                volume: summary.ticker.volume,
+                quote_volume: summary.ticker.quote_volume,
+                trade_count: summary.ticker.trade_count,
+                price_change_bps: summary.ticker.price_change_bps,
+                sequence: summary.sequence,
+                symbol: summary.symbol,
+            })
+            .collect(),
+        header: None,
+    }
+}
+
 pub fn orderbook_data_to_proto(
     last_trade_price: u64,
     max_bid: u64,
@@ -112,6 +492,309 @@ pub fn orderbook_data_to_proto(
                 quantity: *q,
             })
             .collect(),
+        header: None,
+        checksum: orderbook_data.checksum(CHECKSUM_LEVELS),
+    }
+}
+
+pub fn depth_diff_to_proto(
+    snapshot: bool,
+    last_trade_price: u64,
+    max_bid: u64,
+    min_ask: u64,
+    depth_diff: DepthDiff,
+    checksum: u32,
+) -> OrderbookUpdate {
+    OrderbookUpdate {
+        snapshot,
+        last_trade_price,
+        max_bid,
+        min_ask,
+        bids: depth_diff
+            .bids
+            .iter()
+            .map(|(p, q)| Level {
+                price: *p,
+                quantity: *q,
+            })
+            .collect(),
+        asks: depth_diff
+            .asks
+            .iter()
+            .map(|(p, q)| Level {
+                price: *p,
+                quantity: *q,
+            })
+            .collect(),
+        header: None,
+        checksum,
+    }
+}
+
+pub fn session_event_to_proto<'a>(
+    phase: SessionPhase,
+    symbol: String,
+    timestamp: u128,
+) -> (Vec<u8>, &'a str) {
+    (
+        SessionEvent {
+            phase: phase as i32,
+            symbol,
+            timestamp: timestamp.to_be_bytes().to_vec(),
+        }
+        .encode_to_vec(),
+        "SessionEvent",
+    )
+}
+
+pub fn settlement_to_proto<'a>(
+    instruction: AccountSettlementInstruction,
+    symbol: String,
+    timestamp: u128,
+) -> (Vec<u8>, &'a str) {
+    (
+        SettlementInstruction {
+            symbol,
+            buy_order_id: instruction.buy_order_id.to_be_bytes().to_vec(),
+            sell_order_id: instruction.sell_order_id.to_be_bytes().to_vec(),
+            buyer: instruction.buyer,
+            seller: instruction.seller,
+            price: instruction.price,
+            quantity: instruction.quantity,
+            notional: instruction.notional,
+            buyer_fee: instruction.buyer_fee,
+            seller_fee: instruction.seller_fee,
+            taker_side: instruction.taker_side as i32,
+            timestamp: timestamp.to_be_bytes().to_vec(),
+        }
+        .encode_to_vec(),
+        "SettlementInstruction",
+    )
+}
+
+pub fn trade_to_proto<'a>(
+    fill: FillMetaData,
+    symbol: String,
+    sequence: u64,
+    timestamp: u128,
+) -> (Vec<u8>, &'a str) {
+    (
+        Trade {
+            symbol,
+            price: fill.price,
+            quantity: fill.quantity,
+            aggressor_side: fill.taker_side as i32,
+            taker_order_id: fill.order_id.to_be_bytes().to_vec(),
+            maker_order_id: fill.matched_order_id.to_be_bytes().to_vec(),
+            sequence,
+            timestamp: timestamp.to_be_bytes().to_vec(),
+        }
+        .encode_to_vec(),
+        "Trade",
+    )
+}
+
+pub fn throttle_event_to_proto<'a>(
+    account: String,
+    symbol: String,
+    reason: String,
+    timestamp: u128,
+) -> (Vec<u8>, &'a str) {
+    (
+        ThrottleEvent {
+            account,
+            symbol,
+            reason,
+            timestamp: timestamp.to_be_bytes().to_vec(),
+        }
+        .encode_to_vec(),
+        "ThrottleEvent",
+    )
+}
+
+pub fn protection_event_to_proto<'a>(
+    trip: ProtectionTrip,
+    symbol: String,
+    pulled_order_ids: &[u128],
+    timestamp: u128,
+) -> (Vec<u8>, &'a str) {
+    (
+        ProtectionEvent {
+            reason: trip.breach.to_string(),
+            account: trip.account,
+            symbol,
+            fills: trip.fills,
+            quantity: trip.quantity,
+            pulled_order_ids: pulled_order_ids
+                .iter()
+                .map(|id| id.to_be_bytes().to_vec())
+                .collect(),
+            timestamp: timestamp.to_be_bytes().to_vec(),
+        }
+        .encode_to_vec(),
+        "ProtectionEvent",
+    )
+}
+
+pub fn overload_event_to_proto<'a>(
+    symbol: String,
+    shedding: bool,
+    load: Load,
+    timestamp: u128,
+) -> (Vec<u8>, &'a str) {
+    (
+        OverloadEvent {
+            symbol,
+            shedding,
+            queue_saturation: load.queue_saturation,
+            latency_micros: load.latency.as_micros() as u64,
+            timestamp: timestamp.to_be_bytes().to_vec(),
+        }
+        .encode_to_vec(),
+        "OverloadEvent",
+    )
+}
+
+pub fn balances_to_proto(account: String, balances: Vec<(String, AccountBalance)>) -> BalanceList {
+    BalanceList {
+        account,
+        balances: balances
+            .into_iter()
+            .map(|(asset, balance)| Balance {
+                asset,
+                total: balance.total,
+                held: balance.held,
+                available: balance.available(),
+            })
+            .collect(),
+    }
+}
+
+pub fn risk_limits_to_proto(account: String, limits: AccountRiskLimits) -> RiskLimits {
+    RiskLimits {
+        account,
+        max_order_quantity: limits.max_order_quantity.unwrap_or(0),
+        max_open_orders: limits.max_open_orders.unwrap_or(0) as u64,
+        max_notional: limits.max_notional.unwrap_or(0),
+    }
+}
+
+pub fn positions_to_proto(
+    account: String,
+    positions: Vec<(String, AccountPosition)>,
+) -> PositionList {
+    PositionList {
+        account,
+        positions: positions
+            .into_iter()
+            .map(|(symbol, position)| Position {
+                symbol,
+                net_quantity: position.net_quantity,
+                bought_quantity: position.bought_quantity,
+                sold_quantity: position.sold_quantity,
+                bought_notional: position.bought_notional,
+                sold_notional: position.sold_notional,
+            })
+            .collect(),
+        header: None,
+    }
+}
+
+pub fn stream_header(
+    sequence: u64,
+    heartbeat: bool,
+    channel: u64,
+    channel_sequence: u64,
+) -> StreamHeader {
+    StreamHeader {
+        timestamp: generate_u128_timestamp().to_be_bytes().to_vec(),
+        sequence,
+        heartbeat,
+        channel,
+        channel_sequence,
+    }
+}
+
+pub fn delivery_metrics_to_proto(
+    snapshot: DeliveryMetricsSnapshot,
+    pending: usize,
+    trading_halted: bool,
+) -> DeliveryMetrics {
+    DeliveryMetrics {
+        delivered: snapshot.delivered,
+        failed: snapshot.failed,
+        dropped: snapshot.dropped,
+        pending: pending as u64,
+        average_latency_micros: snapshot.average_latency.as_micros() as u64,
+        max_latency_micros: snapshot.max_latency.as_micros() as u64,
+        trading_halted,
+    }
+}
+
+pub fn latency_summaries_to_proto(summaries: Vec<OperationLatencySummary>) -> LatencySummary {
+    LatencySummary {
+        operations: summaries
+            .into_iter()
+            .map(|summary| OperationLatency {
+                operation: summary.operation.name().to_string(),
+                count: summary.count,
+                p50_nanos: summary.p50.as_nanos() as u64,
+                p90_nanos: summary.p90.as_nanos() as u64,
+                p99_nanos: summary.p99.as_nanos() as u64,
+                p999_nanos: summary.p999.as_nanos() as u64,
+                max_nanos: summary.max.as_nanos() as u64,
+            })
+            .collect(),
+    }
+}
+
+pub fn channel_metrics_to_proto(metrics: Vec<ChannelMetricsSnapshot>) -> ChannelMetricsList {
+    ChannelMetricsList {
+        channels: metrics
+            .into_iter()
+            .map(|metrics| ChannelMetrics {
+                channel: metrics.channel.to_string(),
+                capacity: metrics.capacity as u64,
+                depth: metrics.depth as u64,
+                high_watermark: metrics.high_watermark as u64,
+            })
+            .collect(),
+    }
+}
+
+pub fn replication_status_to_proto(replication: &Replication) -> ReplicationStatus {
+    ReplicationStatus {
+        role: replication.role().name().to_string(),
+        fencing_token: replication.fencing_token(),
+    }
+}
+
+pub fn tasks_to_proto(tasks: Vec<ManagedTaskInfo>) -> TaskList {
+    TaskList {
+        tasks: tasks
+            .into_iter()
+            .map(|task| TaskInfo {
+                id: task.id,
+                status: task.status.name().to_string(),
+                restarts: task.restarts,
+            })
+            .collect(),
+    }
+}
+
+pub fn memory_stats_to_proto(symbol: String, stats: BookMemoryStats) -> MemoryStats {
+    MemoryStats {
+        symbol,
+        store_capacity: stats.store_capacity as u64,
+        store_slots: stats.store_slots as u64,
+        store_occupancy: stats.store_occupancy as u64,
+        peak_store_occupancy: stats.peak_store_occupancy as u64,
+        bid_levels: stats.bid_levels as u64,
+        ask_levels: stats.ask_levels as u64,
+        empty_levels: stats.empty_levels as u64,
+        queue_capacity: stats.queue_capacity as u64,
+        queue_allocations: stats.queue_allocations as u64,
+        allocated_bytes: stats.allocated_bytes as u64,
     }
 }
 
@@ -182,6 +865,7 @@ fn modify_result_to_proto<'a>(
                 order_id: id.to_be_bytes().to_vec(),
                 symbol,
                 timestamp: timestamp.to_be_bytes().to_vec(),
+                ..Default::default()
             }
             .encode_to_vec(),
             "CancelModifyOrder",
@@ -0,0 +1,90 @@
+use crate::engine::constants::property_loader::StartupRetry;
+use std::fmt::Display;
+use std::future::Future;
+use tokio::time::{sleep, Instant};
+use tracing::{error, info, warn};
+
+/// Attempts `action` until it succeeds, backing off exponentially between attempts. Gives up
+/// with the error of the last attempt once the next one would start past the deadline.
+pub async fn retry_with_backoff<T, E, F, Fut>(
+    description: &str,
+    retry: StartupRetry,
+    mut action: F,
+) -> Result<T, E>
+where
+    E: Display,
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+{
+    let deadline = Instant::now() + retry.deadline;
+    let mut backoff = retry.initial_backoff;
+    let mut attempts = 1;
+    loop {
+        match action().await {
+            Ok(value) => {
+                if attempts > 1 {
+                    info!("{} succeeded after {} attempts", description, attempts);
+                }
+                return Ok(value);
+            }
+            Err(e) if Instant::now() + backoff > deadline => {
+                error!(
+                    "failed to {} after {} attempts, giving up: {}",
+                    description, attempts, e
+                );
+                return Err(e);
+            }
+            Err(e) => {
+                warn!(
+                    "attempt {} to {} failed, retrying in {:?}: {}",
+                    attempts, description, backoff, e
+                );
+                sleep(backoff).await;
+                backoff = (backoff * 2).min(retry.max_backoff);
+                attempts += 1;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::engine::constants::property_loader::StartupRetry;
+    use crate::engine::utils::retry::retry_with_backoff;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn it_retries_until_success_or_the_deadline() {
+        let retry = StartupRetry {
+            initial_backoff: Duration::from_millis(1),
+            max_backoff: Duration::from_millis(4),
+            deadline: Duration::from_millis(200),
+        };
+        let mut attempts = 0;
+        let result: Result<u32, String> = retry_with_backoff("connect", retry, || {
+            attempts += 1;
+            let attempt = attempts;
+            async move {
+                match attempt {
+                    3 => Ok(attempt),
+                    _ => Err("unreachable".to_string()),
+                }
+            }
+        })
+        .await;
+        assert_eq!(result, Ok(3));
+
+        let retry = StartupRetry {
+            deadline: Duration::from_millis(20),
+            ..retry
+        };
+        let mut attempts = 0;
+        let result: Result<(), String> = retry_with_backoff("connect", retry, || {
+            attempts += 1;
+            async { Err("unreachable".to_string()) }
+        })
+        .await;
+        assert_eq!(result, Err("unreachable".to_string()));
+        assert!(attempts > 1);
+    }
+}
@@ -1,3 +1,21 @@
+#[cfg(feature = "client")]
+pub mod client;
 pub mod core;
+#[cfg(feature = "engine")]
 pub mod engine;
+#[cfg(any(feature = "engine", feature = "client"))]
 pub mod protobuf;
+#[cfg(feature = "engine")]
+pub mod sim;
+
+/// The orderbook modules under the crate root paths they had before they moved into [`core`].
+#[cfg(feature = "legacy")]
+#[deprecated(note = "the orderbook moved to `gemmy::core::orderbook`")]
+pub mod orderbook {
+    pub use crate::core::orderbook::*;
+}
+#[cfg(feature = "legacy")]
+#[deprecated(note = "the models moved to `gemmy::core::models`")]
+pub mod models {
+    pub use crate::core::models::*;
+}
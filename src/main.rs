@@ -1,25 +1,62 @@
+use clap::Parser;
 use gemmy::engine::configuration::configuration_loader::ConfigurationLoader;
+use gemmy::engine::constants::cli::{AuditArgs, Cli, Command};
+use gemmy::engine::constants::property_loader::EnvironmentProperties;
+use gemmy::engine::persistence::audit_log::AuditLog;
+use gemmy::engine::persistence::crash_dump;
+use gemmy::engine::persistence::lease_store::LeaseStore;
 use gemmy::engine::services::{
-    order_dispatch_service::OrderDispatchService, stat_stream_service::StatStreamer,
+    admin_service::AdminService, binary_gateway::BinaryGateway, health_endpoint::HealthEndpoint,
+    http_gateway::HttpGateway, order_dispatch_service::OrderDispatchService,
+    replication_service::ReplicationService, stat_stream_service::StatStreamer,
 };
 use gemmy::engine::state::server_state::ServerState;
-use gemmy::engine::tasks::task_manager::TaskManager;
+use gemmy::engine::tasks::average_price_task::AveragePriceCalculator;
+use gemmy::engine::tasks::compaction_task::Compactor;
+use gemmy::engine::tasks::delayed_data_task::DelayedDataSampler;
+use gemmy::engine::tasks::election_task::LeaderElection;
+use gemmy::engine::tasks::event_sink_task::EventSinkWriter;
+use gemmy::engine::tasks::expiry_task::OrderExpiry;
+use gemmy::engine::tasks::health_task::HealthMonitor;
+use gemmy::engine::tasks::heatmap_task::HeatmapSampler;
+use gemmy::engine::tasks::metrics_task::MetricsReporter;
+use gemmy::engine::tasks::overload_task::OverloadController;
+use gemmy::engine::tasks::publisher_task::Publisher;
+use gemmy::engine::tasks::replay_task::Replay;
+use gemmy::engine::tasks::replication_task::ReplicationFollower;
+use gemmy::engine::tasks::session_task::SessionScheduler;
+use gemmy::engine::tasks::snapshot_upload_task::SnapshotUploader;
+use gemmy::engine::tasks::task_manager::{RestartPolicy, TaskManager};
 use std::{error::Error, sync::Arc};
 use tracing::{error, info};
+
 #[tokio::main]
 pub async fn main() -> Result<(), Box<dyn Error>> {
+    let cli = Cli::parse();
+    cli.apply()?;
+    match &cli.command {
+        Some(Command::ValidateConfig) => {
+            EnvironmentProperties::load()?;
+            println!("configuration is valid");
+            return Ok(());
+        }
+        Some(Command::Audit(audit_args)) => return print_audit_log(audit_args),
+        None => (),
+    }
+
     info!("initiating orderbook server");
 
     // load configurations
     let ConfigurationLoader {
         server_configuration,
         kafka_configuration,
+        persistence_configuration,
         ..
     } = ConfigurationLoader::load()?;
 
     info!(
-        "successfully loaded configurations: {}",
-        server_configuration.server_properties.orderbook_ticker
+        "successfully loaded configurations: {:?}",
+        server_configuration.server_properties.orderbook_tickers
     );
 
     // initialize server state
@@ -27,41 +64,356 @@ pub async fn main() -> Result<(), Box<dyn Error>> {
         ServerState::init(
             Arc::clone(&server_configuration),
             Arc::clone(&kafka_configuration),
+            Arc::clone(&persistence_configuration),
         )
         .await?,
     );
+    if let Some(settings) = &persistence_configuration.persistence_properties.crash_dump {
+        crash_dump::install(
+            settings.directory.clone().into(),
+            Arc::clone(&state.symbols),
+        );
+    }
 
     // initialize task manager and register tasks
-    let mut task_manager = TaskManager::init(
+    let task_manager = Arc::new(TaskManager::init(
         Arc::clone(&state.shutdown_notification),
-        Arc::clone(&state.orderbook_manager),
+        Arc::clone(&state.snapshot_notification),
+        Arc::clone(&state.symbols),
+        Arc::clone(&state.runtime_settings),
+        persistence_configuration
+            .persistence_properties
+            .wal_checkpoint_interval,
         server_configuration
             .server_properties
-            .orderbook_snapshot_interval,
-    );
+            .task_stop_timeouts
+            .clone(),
+    ));
+
+    // a standalone server writes its events to the sink in place of publishing them
+    match &kafka_configuration.kafka_admin_properties.standalone_sink {
+        Some(sink) => task_manager.register("publisher_task", RestartPolicy::Restart(3), {
+            let state = Arc::clone(&state);
+            let sink = sink.clone();
+            move || {
+                let state = Arc::clone(&state);
+                let sink = sink.clone();
+                async move {
+                    EventSinkWriter::new(state, sink).run().await;
+                }
+            }
+        }),
+        None => task_manager.register("publisher_task", RestartPolicy::Restart(3), {
+            let kafka_configuration = Arc::clone(&kafka_configuration);
+            let state = Arc::clone(&state);
+            move || {
+                let kafka_configuration = Arc::clone(&kafka_configuration);
+                let state = Arc::clone(&state);
+                async move {
+                    Publisher::new(kafka_configuration, state).run().await;
+                }
+            }
+        }),
+    }
+
+    task_manager.register("metrics_task", RestartPolicy::Restart(3), {
+        let state = Arc::clone(&state);
+        move || {
+            let state = Arc::clone(&state);
+            async move {
+                MetricsReporter::new(state).run().await;
+            }
+        }
+    });
+
+    task_manager.register("expiry_task", RestartPolicy::Restart(3), {
+        let state = Arc::clone(&state);
+        move || {
+            let state = Arc::clone(&state);
+            async move {
+                OrderExpiry::new(state).run().await;
+            }
+        }
+    });
+
+    task_manager.register("session_task", RestartPolicy::Restart(3), {
+        let state = Arc::clone(&state);
+        move || {
+            let state = Arc::clone(&state);
+            async move {
+                SessionScheduler::new(state).run().await;
+            }
+        }
+    });
+
+    task_manager.register("heatmap_task", RestartPolicy::Restart(3), {
+        let state = Arc::clone(&state);
+        let server_properties = &server_configuration.server_properties;
+        let interval = server_properties.heatmap_interval;
+        let levels = server_properties.heatmap_levels;
+        move || {
+            let state = Arc::clone(&state);
+            async move {
+                HeatmapSampler::new(state, interval, levels).run().await;
+            }
+        }
+    });
+
+    task_manager.register("average_price_task", RestartPolicy::Restart(3), {
+        let state = Arc::clone(&state);
+        let server_properties = &server_configuration.server_properties;
+        let interval = server_properties.average_price_interval;
+        let windows = server_properties.average_price_windows.clone();
+        move || {
+            let state = Arc::clone(&state);
+            let windows = windows.clone();
+            async move {
+                AveragePriceCalculator::new(state, interval, windows)
+                    .run()
+                    .await;
+            }
+        }
+    });
+
+    let (health_reporter, health_service) = tonic_health::server::health_reporter();
+    task_manager.register("health_task", RestartPolicy::Restart(3), {
+        let kafka_configuration = Arc::clone(&kafka_configuration);
+        let state = Arc::clone(&state);
+        let settings = server_configuration.server_properties.health_settings;
+        move || {
+            let kafka_configuration = Arc::clone(&kafka_configuration);
+            let state = Arc::clone(&state);
+            let health_reporter = health_reporter.clone();
+            async move {
+                HealthMonitor::new(kafka_configuration, state, settings, health_reporter)
+                    .run()
+                    .await;
+            }
+        }
+    });
+
+    if let Some(settings) = server_configuration.server_properties.delayed_data {
+        task_manager.register("delayed_data_task", RestartPolicy::Restart(3), {
+            let state = Arc::clone(&state);
+            move || {
+                let state = Arc::clone(&state);
+                async move {
+                    DelayedDataSampler::new(state, settings).run().await;
+                }
+            }
+        });
+    }
+
+    if let Some(settings) = server_configuration.server_properties.overload {
+        task_manager.register("overload_task", RestartPolicy::Restart(3), {
+            let state = Arc::clone(&state);
+            move || {
+                let state = Arc::clone(&state);
+                async move {
+                    OverloadController::new(state, settings).run().await;
+                }
+            }
+        });
+    }
+
+    if let Some(settings) = server_configuration.server_properties.compaction {
+        task_manager.register("compaction_task", RestartPolicy::Restart(3), {
+            let state = Arc::clone(&state);
+            move || {
+                let state = Arc::clone(&state);
+                async move {
+                    Compactor::new(state, settings).run().await;
+                }
+            }
+        });
+    }
+
+    let persistence_properties = &persistence_configuration.persistence_properties;
+    if let (Some(snapshot_store), Some(settings)) = (
+        &state.snapshot_store,
+        &persistence_properties.snapshot_store,
+    ) {
+        task_manager.register("snapshot_upload_task", RestartPolicy::Restart(3), {
+            let state = Arc::clone(&state);
+            let snapshot_store = Arc::clone(snapshot_store);
+            let interval = settings.interval;
+            move || {
+                let state = Arc::clone(&state);
+                let snapshot_store = Arc::clone(&snapshot_store);
+                async move {
+                    SnapshotUploader::new(state, snapshot_store, interval)
+                        .run()
+                        .await;
+                }
+            }
+        });
+    }
+
+    if let Some(replay_path) = &persistence_properties.replay_path {
+        let replay_speed = persistence_properties.replay_speed;
+        task_manager.register("replay_task", RestartPolicy::Escalate, {
+            let state = Arc::clone(&state);
+            let replay_path = replay_path.clone();
+            move || {
+                let state = Arc::clone(&state);
+                let replay_path = replay_path.clone();
+                async move {
+                    Replay::new(state, replay_path, replay_speed).run().await;
+                }
+            }
+        });
+    }
+
+    // a replica follows its primary, the task idles for as long as the server is primary
+    if let (Some(replication), Some(settings)) = (
+        &state.replication,
+        &server_configuration.server_properties.replication,
+    ) {
+        task_manager.register("replication_task", RestartPolicy::Restart(3), {
+            let state = Arc::clone(&state);
+            let replication = Arc::clone(replication);
+            let admin_token = server_configuration.server_properties.admin_token.clone();
+            let retry_interval = settings.retry_interval;
+            move || {
+                let state = Arc::clone(&state);
+                let replication = Arc::clone(&replication);
+                let admin_token = admin_token.clone();
+                async move {
+                    ReplicationFollower::new(state, replication, admin_token, retry_interval)
+                        .run()
+                        .await;
+                }
+            }
+        });
+        if let Some(election) = &settings.election {
+            let lease_store = Arc::new(LeaseStore::new(election)?);
+            task_manager.register("election_task", RestartPolicy::Restart(3), {
+                let state = Arc::clone(&state);
+                let replication = Arc::clone(replication);
+                let election = election.clone();
+                let admin_token = server_configuration.server_properties.admin_token.clone();
+                let retry_interval = settings.retry_interval;
+                move || {
+                    let state = Arc::clone(&state);
+                    let replication = Arc::clone(&replication);
+                    let lease_store = Arc::clone(&lease_store);
+                    let election = election.clone();
+                    let admin_token = admin_token.clone();
+                    async move {
+                        LeaderElection::new(
+                            state,
+                            replication,
+                            lease_store,
+                            election,
+                            admin_token,
+                            retry_interval,
+                        )
+                        .run()
+                        .await;
+                    }
+                }
+            });
+        }
+    }
 
     info!("successfully created and registered tasks");
 
     // create services
-    let order_dispatcher_service = OrderDispatchService::create(
+    let order_dispatcher_service = OrderDispatchService::create(Arc::clone(&state));
+
+    let (stat_streamer_service, delayed_stat_streamer_service) =
+        StatStreamer::create(Arc::clone(&server_configuration), Arc::clone(&state));
+
+    let admin_service = AdminService::create(
         Arc::clone(&server_configuration),
         Arc::clone(&kafka_configuration),
+        Arc::clone(&persistence_configuration),
         Arc::clone(&state),
-        &mut task_manager,
+        Arc::clone(&task_manager),
     );
 
-    let stat_streamer_service = StatStreamer::create(
-        server_configuration.server_properties.rfq_max_count,
-        server_configuration.server_properties.rfq_buffer_size,
-        Arc::clone(&state.orderbook_manager),
-    );
+    let replication_service =
+        ReplicationService::create(Arc::clone(&server_configuration), Arc::clone(&state));
 
     info!("successfully created and services, starting server");
 
+    // start the REST gateway, which stops along with the gRPC server
+    let http_gateway = match server_configuration.server_properties.http_socket_address {
+        Some(http_socket_address) => {
+            let router = HttpGateway::create(Arc::clone(&server_configuration), Arc::clone(&state));
+            let listener = tokio::net::TcpListener::bind(http_socket_address).await?;
+            let state = Arc::clone(&state);
+            info!(
+                "successfully started REST gateway at: {}",
+                http_socket_address
+            );
+            Some(tokio::spawn(async move {
+                let shutdown_notification = Arc::clone(&state.shutdown_notification);
+                let served = axum::serve(listener, router)
+                    .with_graceful_shutdown(async move { shutdown_notification.notified().await })
+                    .await;
+                if let Err(e) = served {
+                    error!("error while serving REST gateway: {}", e);
+                    state.shutdown_notification.notify_waiters();
+                }
+            }))
+        }
+        None => None,
+    };
+
+    // serve the health endpoints over plain HTTP, which stop along with the gRPC server
+    let health_endpoint = match server_configuration.server_properties.health_socket_address {
+        Some(health_socket_address) => {
+            let router = HealthEndpoint::create(Arc::clone(&state));
+            let listener = tokio::net::TcpListener::bind(health_socket_address).await?;
+            let state = Arc::clone(&state);
+            info!(
+                "successfully started health endpoints at: {}",
+                health_socket_address
+            );
+            Some(tokio::spawn(async move {
+                let shutdown_notification = Arc::clone(&state.shutdown_notification);
+                let served = axum::serve(listener, router)
+                    .with_graceful_shutdown(async move { shutdown_notification.notified().await })
+                    .await;
+                if let Err(e) = served {
+                    error!("error while serving health endpoints: {}", e);
+                    state.shutdown_notification.notify_waiters();
+                }
+            }))
+        }
+        None => None,
+    };
+
+    // start the binary order entry gateway, which closes its connections on shutdown
+    let binary_gateway = match server_configuration.server_properties.binary_socket_address {
+        Some(binary_socket_address) => {
+            let gateway = Arc::new(BinaryGateway::new(&state));
+            let listener = tokio::net::TcpListener::bind(binary_socket_address).await?;
+            let state = Arc::clone(&state);
+            info!(
+                "successfully started binary gateway at: {}",
+                binary_socket_address
+            );
+            Some(tokio::spawn(async move {
+                if let Err(e) = gateway.serve(listener).await {
+                    error!("error while serving binary gateway: {}", e);
+                    state.shutdown_notification.notify_waiters();
+                }
+            }))
+        }
+        None => None,
+    };
+
     // start the server thread
+    let shutdown = state.shutdown_notification.notified();
     let server = tonic::transport::Server::builder()
         .add_service(order_dispatcher_service)
         .add_service(stat_streamer_service)
+        .add_optional_service(delayed_stat_streamer_service)
+        .add_service(admin_service)
+        .add_optional_service(replication_service)
+        .add_service(health_service)
         .serve_with_shutdown(
             server_configuration.server_properties.socket_address,
             async {
@@ -69,28 +421,143 @@ pub async fn main() -> Result<(), Box<dyn Error>> {
                     "successfully started gRPC server at: {}",
                     server_configuration.server_properties.socket_address
                 );
-                task_manager
-                    .deregister("shutdown_task")
-                    .await
-                    .expect("failed to shut down server");
+                shutdown.await;
             },
         );
 
-    // handle graceful shutdown
-    tokio::select! {
-        result = server => {
-            if let Err(e) = result {
-                error!("error while starting server: {}", e);
-            }
-        },
-        _ = state.shutdown_notification.notified() => {
-            info!("initiating server shutdown");
-            task_manager.deregister("order_exec_task").await.expect("failed to shut down order executor task");
-            task_manager.deregister("snapshot_task").await.expect("failed to shut down snapshot task");
-        },
+    // the server stops accepting requests on shutdown and completes the ones in flight
+    if let Err(e) = server.await {
+        error!("error while starting server: {}", e);
+        state.shutdown_notification.notify_waiters();
+    }
+
+    // handle graceful shutdown, draining everything accepted before exiting
+    info!("initiating server shutdown");
+    if let Some(http_gateway) = http_gateway {
+        if let Err(e) = http_gateway.await {
+            error!("REST gateway failed: {}", e);
+        }
+    }
+    if let Some(binary_gateway) = binary_gateway {
+        if let Err(e) = binary_gateway.await {
+            error!("binary gateway failed: {}", e);
+        }
+    }
+    if let Some(health_endpoint) = health_endpoint {
+        if let Err(e) = health_endpoint.await {
+            error!("health endpoints failed: {}", e);
+        }
+    }
+    for id in [
+        "snapshot_task",
+        "expiry_task",
+        "session_task",
+        "heatmap_task",
+        "average_price_task",
+        "health_task",
+    ] {
+        stop_task(&task_manager, id).await;
+    }
+    // these tasks are only registered when enabled
+    for id in [
+        "delayed_data_task",
+        "overload_task",
+        "compaction_task",
+        "replay_task",
+        "snapshot_upload_task",
+        "replication_task",
+        "election_task",
+    ] {
+        if task_manager.status(id).is_some() {
+            stop_task(&task_manager, id).await;
+        }
+    }
+    let symbols: Vec<_> = state.symbols.read().unwrap().values().cloned().collect();
+    for symbol_state in symbols {
+        match symbol_state.stop_executor().await {
+            Ok(drained) => info!("drained {} operations of {}", drained, symbol_state.symbol),
+            Err(e) => error!("failed to drain {}: {}", symbol_state.symbol, e),
+        }
+        symbol_state.orderbook_manager.snapshot();
+    }
+    // the drained books are uploaded once more, so that a restore picks up where they stopped
+    if let (Some(snapshot_store), Some(settings)) = (
+        &state.snapshot_store,
+        &persistence_properties.snapshot_store,
+    ) {
+        SnapshotUploader::new(
+            Arc::clone(&state),
+            Arc::clone(snapshot_store),
+            settings.interval,
+        )
+        .upload()
+        .await;
+    }
+    if let Some(recorder) = &state.recorder {
+        if let Err(e) = recorder.finish() {
+            error!("failed to finish recording: {}", e);
+        }
+    }
+    state.drain_notification.notify_one();
+    for id in [
+        "publisher_task",
+        "reload_task",
+        "metrics_task",
+        "shutdown_task",
+    ] {
+        stop_task(&task_manager, id).await;
     }
 
-    info!("gRPC server stopped gracefully");
+    let unclean_stops = task_manager.unclean_stops();
+    for (id, reason) in &unclean_stops {
+        error!("task {} did not stop cleanly: {}", id, reason);
+    }
+    match unclean_stops.len() {
+        0 => info!("gRPC server stopped gracefully"),
+        unclean => error!(
+            "gRPC server stopped, {} tasks did not stop cleanly",
+            unclean
+        ),
+    }
 
     Ok(())
 }
+
+/// Prints the records of the audit log matching the filters, one per line, after verifying the
+/// whole chain.
+fn print_audit_log(audit_args: &AuditArgs) -> Result<(), Box<dyn Error>> {
+    let AuditArgs {
+        directory,
+        principal,
+        method,
+        from,
+    } = audit_args;
+    let records = AuditLog::read(directory)?;
+    let verified = records.len();
+    for (record, hash) in records {
+        let matches = record.sequence >= *from
+            && principal.as_ref().is_none_or(|p| *p == record.principal)
+            && method.as_ref().is_none_or(|m| *m == record.method);
+        if !matches {
+            continue;
+        }
+        println!(
+            "{}\t{}\t{}\t{}\t{}\t{}\t{}",
+            record.sequence,
+            record.timestamp,
+            record.principal,
+            record.method,
+            record.decision,
+            record.payload,
+            hash
+        );
+    }
+    eprintln!("verified {} audit records", verified);
+    Ok(())
+}
+
+async fn stop_task(task_manager: &TaskManager, id: &str) {
+    if let Err(e) = task_manager.stop(id).await {
+        error!("failed to shut down {}: {}", id, e);
+    }
+}
@@ -63,86 +63,1165 @@ pub struct CancelModifyOrder {
     pub symbol: ::prost::alloc::string::String,
     #[prost(bytes = "vec", tag = "4")]
     pub timestamp: ::prost::alloc::vec::Vec<u8>,
+    /// The history of a cancelled order, empty if it was recovered on startup.
+    #[prost(uint64, tag = "5")]
+    pub version: u64,
+    #[prost(uint64, tag = "6")]
+    pub original_quantity: u64,
+    #[prost(message, repeated, tag = "7")]
+    pub revisions: ::prost::alloc::vec::Vec<OrderRevision>,
+}
+/// An order reduced in place, which keeps its priority.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ReduceOrder {
+    #[prost(enumeration = "OrderStatus", tag = "1")]
+    pub status: i32,
+    #[prost(bytes = "vec", tag = "2")]
+    pub order_id: ::prost::alloc::vec::Vec<u8>,
+    #[prost(uint64, tag = "3")]
+    pub price: u64,
+    /// The quantity left resting.
+    #[prost(uint64, tag = "4")]
+    pub quantity: u64,
+    #[prost(enumeration = "OrderSide", tag = "5")]
+    pub side: i32,
+    #[prost(string, tag = "6")]
+    pub symbol: ::prost::alloc::string::String,
+    #[prost(bytes = "vec", tag = "7")]
+    pub timestamp: ::prost::alloc::vec::Vec<u8>,
+}
+/// A quote that replaced the orders resting under its ids, with the sides it left resting and the
+/// matches of both.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct QuoteOrder {
+    #[prost(enumeration = "OrderStatus", tag = "1")]
+    pub status: i32,
+    /// The orders of the previous quote that were still resting.
+    #[prost(bytes = "vec", repeated, tag = "2")]
+    pub replaced_order_ids: ::prost::alloc::vec::Vec<::prost::alloc::vec::Vec<u8>>,
+    /// Unset if the side was withdrawn or filled in full.
+    #[prost(message, optional, tag = "3")]
+    pub bid: ::core::option::Option<CreateOrder>,
+    #[prost(message, optional, tag = "4")]
+    pub ask: ::core::option::Option<CreateOrder>,
+    #[prost(message, repeated, tag = "5")]
+    pub filled_orders: ::prost::alloc::vec::Vec<FillOrderData>,
+    #[prost(string, tag = "6")]
+    pub symbol: ::prost::alloc::string::String,
+    #[prost(bytes = "vec", tag = "7")]
+    pub timestamp: ::prost::alloc::vec::Vec<u8>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct OrderRevision {
+    #[prost(enumeration = "RevisionKind", tag = "1")]
+    pub kind: i32,
+    #[prost(uint64, tag = "2")]
+    pub price: u64,
+    #[prost(uint64, tag = "3")]
+    pub quantity: u64,
+    #[prost(bytes = "vec", tag = "4")]
+    pub timestamp: ::prost::alloc::vec::Vec<u8>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SessionEvent {
+    #[prost(enumeration = "SessionPhase", tag = "1")]
+    pub phase: i32,
+    #[prost(string, tag = "2")]
+    pub symbol: ::prost::alloc::string::String,
+    #[prost(bytes = "vec", tag = "3")]
+    pub timestamp: ::prost::alloc::vec::Vec<u8>,
+}
+/// A call started, in which limit orders rest without matching until the uncross.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CallEvent {
+    #[prost(string, tag = "1")]
+    pub symbol: ::prost::alloc::string::String,
+    #[prost(bytes = "vec", tag = "2")]
+    pub timestamp: ::prost::alloc::vec::Vec<u8>,
+}
+/// The uncross that ended a call, matching the crossed part of the book at a single price. Every
+/// match names the bid as the order and the ask as the matched order.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct UncrossEvent {
+    /// Zero if the book was not crossed and nothing matched.
+    #[prost(uint64, tag = "1")]
+    pub price: u64,
+    #[prost(uint64, tag = "2")]
+    pub quantity: u64,
+    #[prost(message, repeated, tag = "3")]
+    pub filled_orders: ::prost::alloc::vec::Vec<FillOrderData>,
+    #[prost(string, tag = "4")]
+    pub symbol: ::prost::alloc::string::String,
+    #[prost(bytes = "vec", tag = "5")]
+    pub timestamp: ::prost::alloc::vec::Vec<u8>,
+}
+/// Obligations a fill creates between the buyer and the seller, fees are in the quote asset.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SettlementInstruction {
+    #[prost(string, tag = "1")]
+    pub symbol: ::prost::alloc::string::String,
+    #[prost(bytes = "vec", tag = "2")]
+    pub buy_order_id: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes = "vec", tag = "3")]
+    pub sell_order_id: ::prost::alloc::vec::Vec<u8>,
+    #[prost(string, tag = "4")]
+    pub buyer: ::prost::alloc::string::String,
+    #[prost(string, tag = "5")]
+    pub seller: ::prost::alloc::string::String,
+    #[prost(uint64, tag = "6")]
+    pub price: u64,
+    #[prost(uint64, tag = "7")]
+    pub quantity: u64,
+    #[prost(uint64, tag = "8")]
+    pub notional: u64,
+    #[prost(uint64, tag = "9")]
+    pub buyer_fee: u64,
+    #[prost(uint64, tag = "10")]
+    pub seller_fee: u64,
+    #[prost(enumeration = "OrderSide", tag = "11")]
+    pub taker_side: i32,
+    #[prost(bytes = "vec", tag = "12")]
+    pub timestamp: ::prost::alloc::vec::Vec<u8>,
+}
+/// A single fill published to the trades topic, so that market data consumers do not have to
+/// follow the lifecycle of orders. Fills of one operation share its sequence.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Trade {
+    #[prost(string, tag = "1")]
+    pub symbol: ::prost::alloc::string::String,
+    #[prost(uint64, tag = "2")]
+    pub price: u64,
+    #[prost(uint64, tag = "3")]
+    pub quantity: u64,
+    /// The side of the order that took liquidity.
+    #[prost(enumeration = "OrderSide", tag = "4")]
+    pub aggressor_side: i32,
+    #[prost(bytes = "vec", tag = "5")]
+    pub taker_order_id: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes = "vec", tag = "6")]
+    pub maker_order_id: ::prost::alloc::vec::Vec<u8>,
+    /// The number of operations the orderbook of the symbol had executed, including this one.
+    #[prost(uint64, tag = "7")]
+    pub sequence: u64,
+    #[prost(bytes = "vec", tag = "8")]
+    pub timestamp: ::prost::alloc::vec::Vec<u8>,
+}
+/// Published when an account first exceeds a flow limit within a second.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ThrottleEvent {
+    #[prost(string, tag = "1")]
+    pub account: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub symbol: ::prost::alloc::string::String,
+    #[prost(string, tag = "3")]
+    pub reason: ::prost::alloc::string::String,
+    #[prost(bytes = "vec", tag = "4")]
+    pub timestamp: ::prost::alloc::vec::Vec<u8>,
+}
+/// Published when the quotes of an account traded more within the protection window than allowed,
+/// once the quotes it had left in the symbol were pulled.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ProtectionEvent {
+    #[prost(string, tag = "1")]
+    pub account: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub symbol: ::prost::alloc::string::String,
+    #[prost(string, tag = "3")]
+    pub reason: ::prost::alloc::string::String,
+    /// Fills of the quotes of the account within the window, including the one that tripped it.
+    #[prost(uint64, tag = "4")]
+    pub fills: u64,
+    #[prost(uint64, tag = "5")]
+    pub quantity: u64,
+    #[prost(bytes = "vec", repeated, tag = "6")]
+    pub pulled_order_ids: ::prost::alloc::vec::Vec<::prost::alloc::vec::Vec<u8>>,
+    #[prost(bytes = "vec", tag = "7")]
+    pub timestamp: ::prost::alloc::vec::Vec<u8>,
+}
+/// Published when a symbol starts or stops shedding low priority order flow.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct OverloadEvent {
+    #[prost(string, tag = "1")]
+    pub symbol: ::prost::alloc::string::String,
+    #[prost(bool, tag = "2")]
+    pub shedding: bool,
+    #[prost(uint64, tag = "3")]
+    pub queue_saturation: u64,
+    #[prost(uint64, tag = "4")]
+    pub latency_micros: u64,
+    #[prost(bytes = "vec", tag = "5")]
+    pub timestamp: ::prost::alloc::vec::Vec<u8>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GenericMessage {
+    #[prost(string, tag = "1")]
+    pub message: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub symbol: ::prost::alloc::string::String,
+    #[prost(bytes = "vec", tag = "3")]
+    pub timestamp: ::prost::alloc::vec::Vec<u8>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct StringResponse {
+    #[prost(string, tag = "1")]
+    pub message: ::prost::alloc::string::String,
+}
+/// Acknowledges an operation sent to the dispatcher. Accepted operations are handed to the engine
+/// to execute, rejected ones never reach it.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct OrderAck {
+    #[prost(enumeration = "AckStatus", tag = "1")]
+    pub status: i32,
+    /// The id the order was created with, or the id of the order modified or cancelled. Empty for
+    /// rejected new orders, which are never assigned one.
+    #[prost(bytes = "vec", tag = "2")]
+    pub order_id: ::prost::alloc::vec::Vec<u8>,
+    #[prost(string, tag = "3")]
+    pub rejection_reason: ::prost::alloc::string::String,
+    #[prost(bytes = "vec", tag = "4")]
+    pub accepted_at: ::prost::alloc::vec::Vec<u8>,
+    /// Operations the engine had executed for the symbol when the order was accepted. The order is
+    /// executed later, so this is not the sequence it is assigned.
+    #[prost(uint64, tag = "5")]
+    pub executed_operations: u64,
+    /// Operations queued for the engine of the symbol when the order was accepted, including it.
+    #[prost(uint64, tag = "6")]
+    pub queue_depth: u64,
+}
+/// Sent with every message of a stream. Heartbeats only carry the header, they are sent whenever
+/// nothing else was sent on the stream for a while.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct StreamHeader {
+    #[prost(bytes = "vec", tag = "1")]
+    pub timestamp: ::prost::alloc::vec::Vec<u8>,
+    /// Number of operations the orderbook of the symbol has executed, or of fills applied to
+    /// positions on position streams. Zero on market summary streams, where every symbol carries
+    /// its own, and on delayed streams.
+    #[prost(uint64, tag = "2")]
+    pub sequence: u64,
+    #[prost(bool, tag = "3")]
+    pub heartbeat: bool,
+    /// Identifies the stream, for resynchronizing it after a missed update.
+    #[prost(uint64, tag = "4")]
+    pub channel: u64,
+    /// Increases by one with every update of the stream, so that clients can detect a missed update
+    /// by a gap in the sequence. Heartbeats carry the sequence of the last update.
+    #[prost(uint64, tag = "5")]
+    pub channel_sequence: u64,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RfqResult {
+    #[prost(enumeration = "RfqStatus", tag = "1")]
+    pub status: i32,
+    #[prost(uint64, tag = "2")]
+    pub price: u64,
+    #[prost(uint64, tag = "3")]
+    pub quantity: u64,
+    #[prost(message, optional, tag = "4")]
+    pub header: ::core::option::Option<StreamHeader>,
+    /// A complete fill is a firm quote, which can be executed once through the order dispatcher
+    /// until its time to live in milliseconds runs out. Other statuses carry no quote.
+    #[prost(bytes = "vec", tag = "5")]
+    pub quote_id: ::prost::alloc::vec::Vec<u8>,
+    #[prost(uint64, tag = "6")]
+    pub quote_ttl_millis: u64,
+    /// The quotes for the ladder of the request in the order requested, each with a firm quote of
+    /// its own and without a header.
+    #[prost(message, repeated, tag = "7")]
+    pub ladder: ::prost::alloc::vec::Vec<RfqResult>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CreateLimitOrderRequest {
+    #[prost(uint64, tag = "1")]
+    pub price: u64,
+    #[prost(uint64, tag = "2")]
+    pub quantity: u64,
+    #[prost(enumeration = "OrderSide", tag = "3")]
+    pub side: i32,
+    #[prost(string, tag = "4")]
+    pub symbol: ::prost::alloc::string::String,
+    /// Milliseconds since the unix epoch after which a resting order expires, zero never expires.
+    #[prost(uint64, tag = "5")]
+    pub expires_at_millis: u64,
+    #[prost(enumeration = "TimeInForce", tag = "6")]
+    pub time_in_force: i32,
+    /// Required when the ledger is enabled.
+    #[prost(string, tag = "7")]
+    pub account: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CreateMarketOrderRequest {
+    #[prost(uint64, tag = "1")]
+    pub quantity: u64,
+    #[prost(enumeration = "OrderSide", tag = "2")]
+    pub side: i32,
+    #[prost(string, tag = "3")]
+    pub symbol: ::prost::alloc::string::String,
+    /// Only used when requesting a stream of quotes.
+    #[prost(message, optional, tag = "4")]
+    pub stream: ::core::option::Option<StreamParameters>,
+    /// Required when the ledger is enabled.
+    #[prost(string, tag = "5")]
+    pub account: ::prost::alloc::string::String,
+    /// Only used when requesting a stream of quotes, further quantities to quote on every update.
+    #[prost(uint64, repeated, tag = "6")]
+    pub ladder: ::prost::alloc::vec::Vec<u64>,
+}
+/// Per-subscription settings of a stream, zero values fall back to the server defaults.
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct StreamParameters {
+    #[prost(uint64, tag = "1")]
+    pub interval_millis: u64,
+    #[prost(uint64, tag = "2")]
+    pub max_updates: u64,
+    #[prost(uint32, tag = "3")]
+    pub levels: u32,
+    /// Coalesces the updates a slow client has not taken yet into the latest state, instead of
+    /// queueing one for every interval.
+    #[prost(bool, tag = "4")]
+    pub conflate: bool,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ModifyLimitOrderRequest {
+    #[prost(bytes = "vec", tag = "1")]
+    pub order_id: ::prost::alloc::vec::Vec<u8>,
+    #[prost(uint64, tag = "2")]
+    pub price: u64,
+    #[prost(uint64, tag = "3")]
+    pub quantity: u64,
+    #[prost(enumeration = "OrderSide", tag = "4")]
+    pub side: i32,
+    #[prost(string, tag = "5")]
+    pub symbol: ::prost::alloc::string::String,
+    /// Required when the ledger is enabled, must own the order.
+    #[prost(string, tag = "6")]
+    pub account: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CancelLimitOrderRequest {
+    #[prost(bytes = "vec", tag = "1")]
+    pub order_id: ::prost::alloc::vec::Vec<u8>,
+    #[prost(string, tag = "2")]
+    pub symbol: ::prost::alloc::string::String,
+    /// Required when the ledger is enabled, must own the order.
+    #[prost(string, tag = "3")]
+    pub account: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ReduceLimitOrderRequest {
+    #[prost(bytes = "vec", tag = "1")]
+    pub order_id: ::prost::alloc::vec::Vec<u8>,
+    /// Taken off the order, which must keep some quantity resting.
+    #[prost(uint64, tag = "2")]
+    pub quantity_delta: u64,
+    #[prost(string, tag = "3")]
+    pub symbol: ::prost::alloc::string::String,
+    /// Required when the ledger is enabled, must own the order.
+    #[prost(string, tag = "4")]
+    pub account: ::prost::alloc::string::String,
+}
+/// Replaces the quote of the account in the symbol, a side without quantity is withdrawn.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct QuoteRequest {
+    #[prost(string, tag = "1")]
+    pub symbol: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub account: ::prost::alloc::string::String,
+    #[prost(uint64, tag = "3")]
+    pub bid_price: u64,
+    #[prost(uint64, tag = "4")]
+    pub bid_quantity: u64,
+    #[prost(uint64, tag = "5")]
+    pub ask_price: u64,
+    #[prost(uint64, tag = "6")]
+    pub ask_quantity: u64,
+}
+/// Replaces the quote of the account at a level, a side without quantity is withdrawn.
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct QuoteLevel {
+    #[prost(uint32, tag = "1")]
+    pub level: u32,
+    #[prost(uint64, tag = "2")]
+    pub bid_price: u64,
+    #[prost(uint64, tag = "3")]
+    pub bid_quantity: u64,
+    #[prost(uint64, tag = "4")]
+    pub ask_price: u64,
+    #[prost(uint64, tag = "5")]
+    pub ask_quantity: u64,
+}
+/// Replaces the quotes of the account at several levels of the symbol at once. Levels left out
+/// keep their quote.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MassQuoteRequest {
+    #[prost(string, tag = "1")]
+    pub symbol: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub account: ::prost::alloc::string::String,
+    #[prost(message, repeated, tag = "3")]
+    pub levels: ::prost::alloc::vec::Vec<QuoteLevel>,
+}
+/// Acknowledges a mass quote once the engine executed it. Rejected mass quotes carry no levels,
+/// accepted ones the acknowledgment of every level in the order they were sent, each rejected on
+/// its own if it failed.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MassQuoteAck {
+    #[prost(enumeration = "AckStatus", tag = "1")]
+    pub status: i32,
+    #[prost(string, tag = "2")]
+    pub rejection_reason: ::prost::alloc::string::String,
+    #[prost(message, repeated, tag = "3")]
+    pub levels: ::prost::alloc::vec::Vec<OrderAck>,
+}
+/// Executes a firm quote as a market order, which is rejected unless the whole quantity still
+/// fills at the quoted price or better.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ExecuteQuoteRequest {
+    #[prost(string, tag = "1")]
+    pub symbol: ::prost::alloc::string::String,
+    #[prost(bytes = "vec", tag = "2")]
+    pub quote_id: ::prost::alloc::vec::Vec<u8>,
+    #[prost(string, tag = "3")]
+    pub account: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct OrderStatusRequest {
+    #[prost(string, tag = "1")]
+    pub symbol: ::prost::alloc::string::String,
+    #[prost(bytes = "vec", tag = "2")]
+    pub order_id: ::prost::alloc::vec::Vec<u8>,
+    #[prost(string, tag = "3")]
+    pub account: ::prost::alloc::string::String,
+}
+/// A resting order along with its latest revisions, orders recovered on startup have version 0
+/// and no revisions.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct OrderStatusResponse {
+    #[prost(bytes = "vec", tag = "1")]
+    pub order_id: ::prost::alloc::vec::Vec<u8>,
+    #[prost(enumeration = "OrderSide", tag = "2")]
+    pub side: i32,
+    #[prost(uint64, tag = "3")]
+    pub price: u64,
+    #[prost(uint64, tag = "4")]
+    pub quantity: u64,
+    #[prost(uint64, tag = "5")]
+    pub original_quantity: u64,
+    #[prost(uint64, tag = "6")]
+    pub version: u64,
+    #[prost(message, repeated, tag = "7")]
+    pub revisions: ::prost::alloc::vec::Vec<OrderRevision>,
+}
+/// Every subscription gets the levels, granularity and interval it asks for, within the caps of
+/// the server. The shape it was resolved to is returned in the `stream-levels`,
+/// `stream-granularity` and `stream-interval-millis` metadata of the response.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct OrderbookDataRequest {
+    #[prost(enumeration = "Granularity", tag = "1")]
+    pub granularity: i32,
+    #[prost(string, tag = "2")]
+    pub symbol: ::prost::alloc::string::String,
+    #[prost(message, optional, tag = "3")]
+    pub stream: ::core::option::Option<StreamParameters>,
+    /// Only the levels priced within the range are sent and checksummed, zero leaves a bound open.
+    /// The levels of the stream are the best ones within the range.
+    #[prost(uint64, tag = "4")]
+    pub min_price: u64,
+    #[prost(uint64, tag = "5")]
+    pub max_price: u64,
+}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct ResyncRequest {
+    /// Channel of the orderbook updates stream to resynchronize.
+    #[prost(uint64, tag = "1")]
+    pub channel: u64,
+}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct Level {
+    #[prost(uint64, tag = "1")]
+    pub price: u64,
+    #[prost(uint64, tag = "2")]
+    pub quantity: u64,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct OrderbookData {
+    #[prost(uint64, tag = "1")]
+    pub max_bid: u64,
+    #[prost(uint64, tag = "2")]
+    pub min_ask: u64,
+    #[prost(uint64, tag = "3")]
+    pub last_trade_price: u64,
+    #[prost(message, repeated, tag = "4")]
+    pub bids: ::prost::alloc::vec::Vec<Level>,
+    #[prost(message, repeated, tag = "5")]
+    pub asks: ::prost::alloc::vec::Vec<Level>,
+    #[prost(message, optional, tag = "6")]
+    pub header: ::core::option::Option<StreamHeader>,
+    /// CRC32 over the best 25 levels on either side, interleaved from the best price outwards as
+    /// bid_price:bid_quantity:ask_price:ask_quantity joined by colons, skipping an exhausted side.
+    #[prost(uint32, tag = "7")]
+    pub checksum: u32,
+}
+/// A full orderbook when snapshot is set, otherwise only the levels that changed since the previous
+/// update, where a quantity of zero removes the level.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct OrderbookUpdate {
+    #[prost(bool, tag = "1")]
+    pub snapshot: bool,
+    #[prost(uint64, tag = "2")]
+    pub max_bid: u64,
+    #[prost(uint64, tag = "3")]
+    pub min_ask: u64,
+    #[prost(uint64, tag = "4")]
+    pub last_trade_price: u64,
+    #[prost(message, repeated, tag = "5")]
+    pub bids: ::prost::alloc::vec::Vec<Level>,
+    #[prost(message, repeated, tag = "6")]
+    pub asks: ::prost::alloc::vec::Vec<Level>,
+    #[prost(message, optional, tag = "7")]
+    pub header: ::core::option::Option<StreamHeader>,
+    /// Checksum of the orderbook after applying the update, computed as in OrderbookData.
+    #[prost(uint32, tag = "8")]
+    pub checksum: u32,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct IndicativeUncrossRequest {
+    #[prost(string, tag = "1")]
+    pub symbol: ::prost::alloc::string::String,
+    /// Levels are ignored, the stream only carries the indicative uncross.
+    #[prost(message, optional, tag = "2")]
+    pub stream: ::core::option::Option<StreamParameters>,
+}
+/// The price a call would uncross at if it ended now, sent whenever it changes. The price is the
+/// one matching the most quantity, among those the one leaving the smallest imbalance and then the
+/// one closest to the last traded price. Everything but the call is zero while the book does not
+/// cross.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct IndicativeUncross {
+    #[prost(string, tag = "1")]
+    pub symbol: ::prost::alloc::string::String,
+    /// Whether the symbol is in a call, orders only match at the uncross while it is.
+    #[prost(bool, tag = "2")]
+    pub call: bool,
+    #[prost(uint64, tag = "3")]
+    pub price: u64,
+    /// The quantity that would match at the price.
+    #[prost(uint64, tag = "4")]
+    pub quantity: u64,
+    /// The quantity that would be left unmatched at the price on the side wanting more.
+    #[prost(uint64, tag = "5")]
+    pub imbalance: u64,
+    /// The side wanting more, only set while there is an imbalance.
+    #[prost(enumeration = "OrderSide", tag = "6")]
+    pub imbalance_side: i32,
+    #[prost(message, optional, tag = "7")]
+    pub header: ::core::option::Option<StreamHeader>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct BboRequest {
+    #[prost(string, tag = "1")]
+    pub symbol: ::prost::alloc::string::String,
+    /// Levels are ignored, the stream only carries the best level on either side.
+    #[prost(message, optional, tag = "2")]
+    pub stream: ::core::option::Option<StreamParameters>,
+}
+/// The best bid and offer of a symbol, sent whenever either of them changes. An empty side has a
+/// quantity of zero, with a bid price of zero or an ask price of the maximum as in OrderbookData.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Bbo {
+    #[prost(uint64, tag = "1")]
+    pub bid_price: u64,
+    #[prost(uint64, tag = "2")]
+    pub bid_quantity: u64,
+    #[prost(uint64, tag = "3")]
+    pub ask_price: u64,
+    #[prost(uint64, tag = "4")]
+    pub ask_quantity: u64,
+    #[prost(message, optional, tag = "5")]
+    pub header: ::core::option::Option<StreamHeader>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SlippageRequest {
+    #[prost(string, tag = "1")]
+    pub symbol: ::prost::alloc::string::String,
+    /// The side of the market orders, which walk the opposite side of the book.
+    #[prost(enumeration = "OrderSide", tag = "2")]
+    pub side: i32,
+    /// The notionals to estimate the execution of, which market orders spend on the bid side and
+    /// raise on the ask side.
+    #[prost(uint64, repeated, tag = "3")]
+    pub notionals: ::prost::alloc::vec::Vec<u64>,
+}
+/// The expected execution of a market order of the notional against the current book.
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct SlippagePoint {
+    #[prost(uint64, tag = "1")]
+    pub notional: u64,
+    #[prost(uint64, tag = "2")]
+    pub quantity: u64,
+    /// Zero when not even a single unit fills.
+    #[prost(uint64, tag = "3")]
+    pub average_price: u64,
+    #[prost(uint64, tag = "4")]
+    pub worst_price: u64,
+    /// The distance of the average price from the best price in hundredths of a percent.
+    #[prost(uint64, tag = "5")]
+    pub impact_bps: u64,
+    /// Whether the book holds enough liquidity for the whole notional.
+    #[prost(bool, tag = "6")]
+    pub complete: bool,
+}
+/// The price impact of market orders of increasing size, in the order of the requested notionals.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SlippageCurve {
+    #[prost(string, tag = "1")]
+    pub symbol: ::prost::alloc::string::String,
+    #[prost(enumeration = "OrderSide", tag = "2")]
+    pub side: i32,
+    #[prost(message, repeated, tag = "3")]
+    pub points: ::prost::alloc::vec::Vec<SlippagePoint>,
+    /// Operations the orderbook of the symbol had executed when the curve was estimated.
+    #[prost(uint64, tag = "4")]
+    pub sequence: u64,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct HeatmapRequest {
+    #[prost(string, tag = "1")]
+    pub symbol: ::prost::alloc::string::String,
+    /// Width of a time bucket, raised to the sampling interval of the server when below it.
+    #[prost(uint64, tag = "2")]
+    pub bucket_millis: u64,
+    /// Latest buckets to return, at most an hour of samples is kept.
+    #[prost(uint32, tag = "3")]
+    pub buckets: u32,
+}
+/// The average liquidity of a time bucket, with a quantity for every price of the heatmap.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct HeatmapRow {
+    /// Start of the bucket in nanoseconds since the unix epoch.
+    #[prost(bytes = "vec", tag = "1")]
+    pub start: ::prost::alloc::vec::Vec<u8>,
+    #[prost(uint64, repeated, tag = "2")]
+    pub bids: ::prost::alloc::vec::Vec<u64>,
+    #[prost(uint64, repeated, tag = "3")]
+    pub asks: ::prost::alloc::vec::Vec<u64>,
+}
+/// Resting liquidity by time bucket and price level, built from periodic samples of the top of
+/// the book. Buckets without samples are left out.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Heatmap {
+    #[prost(string, tag = "1")]
+    pub symbol: ::prost::alloc::string::String,
+    /// Every price sampled over the buckets in ascending order.
+    #[prost(uint64, repeated, tag = "2")]
+    pub prices: ::prost::alloc::vec::Vec<u64>,
+    /// The buckets in chronological order.
+    #[prost(message, repeated, tag = "3")]
+    pub rows: ::prost::alloc::vec::Vec<HeatmapRow>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AveragePriceRequest {
+    #[prost(string, tag = "1")]
+    pub symbol: ::prost::alloc::string::String,
+    /// Levels are ignored.
+    #[prost(message, optional, tag = "2")]
+    pub stream: ::core::option::Option<StreamParameters>,
+}
+/// The average prices of a symbol over a rolling window.
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct AveragePrice {
+    #[prost(uint64, tag = "1")]
+    pub window_millis: u64,
+    /// The volume weighted average price of the trades of the window, zero without trades.
+    #[prost(uint64, tag = "2")]
+    pub vwap: u64,
+    /// The last traded price averaged over the time of the window, zero until the symbol traded.
+    #[prost(uint64, tag = "3")]
+    pub twap: u64,
+    /// Quantity traded over the window.
+    #[prost(uint64, tag = "4")]
+    pub volume: u64,
+}
+/// Rolling VWAP and TWAP of a symbol over every window configured on the server, sent whenever
+/// they are recomputed to a different value.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AveragePrices {
+    #[prost(string, tag = "1")]
+    pub symbol: ::prost::alloc::string::String,
+    #[prost(message, repeated, tag = "2")]
+    pub prices: ::prost::alloc::vec::Vec<AveragePrice>,
+    #[prost(message, optional, tag = "3")]
+    pub header: ::core::option::Option<StreamHeader>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct VolumeProfileRequest {
+    #[prost(string, tag = "1")]
+    pub symbol: ::prost::alloc::string::String,
+}
+/// The trades of a symbol at a single price, split by the side of the taker.
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct PriceVolume {
+    #[prost(uint64, tag = "1")]
+    pub price: u64,
+    #[prost(uint64, tag = "2")]
+    pub volume: u64,
+    #[prost(uint64, tag = "3")]
+    pub buy_volume: u64,
+    #[prost(uint64, tag = "4")]
+    pub sell_volume: u64,
+    #[prost(uint64, tag = "5")]
+    pub trade_count: u64,
+}
+/// Cumulative traded volume per price since the trading session started, or since the server
+/// started without a session schedule.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct VolumeProfile {
+    #[prost(string, tag = "1")]
+    pub symbol: ::prost::alloc::string::String,
+    /// Nanoseconds since the unix epoch the volume is counted from, zero since the server started.
+    #[prost(bytes = "vec", tag = "2")]
+    pub since: ::prost::alloc::vec::Vec<u8>,
+    /// The traded prices in ascending order.
+    #[prost(message, repeated, tag = "3")]
+    pub levels: ::prost::alloc::vec::Vec<PriceVolume>,
+    /// The price that traded the most volume, zero before the first trade.
+    #[prost(uint64, tag = "4")]
+    pub point_of_control: u64,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TickerRequest {
+    #[prost(string, tag = "1")]
+    pub symbol: ::prost::alloc::string::String,
+    /// Only used by ticker updates, levels are ignored.
+    #[prost(message, optional, tag = "2")]
+    pub stream: ::core::option::Option<StreamParameters>,
+}
+/// Trade statistics of a symbol over the last 24 hours. Prices are zero when nothing traded over
+/// the window, except for the last price.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Ticker {
+    #[prost(string, tag = "1")]
+    pub symbol: ::prost::alloc::string::String,
+    #[prost(uint64, tag = "2")]
+    pub last_price: u64,
+    #[prost(uint64, tag = "3")]
+    pub open_price: u64,
+    #[prost(uint64, tag = "4")]
+    pub high_price: u64,
+    #[prost(uint64, tag = "5")]
+    pub low_price: u64,
+    #[prost(uint64, tag = "6")]
+    pub volume: u64,
+    #[prost(uint64, tag = "7")]
+    pub quote_volume: u64,
+    #[prost(uint64, tag = "8")]
+    pub trade_count: u64,
+    #[prost(int64, tag = "9")]
+    pub price_change: i64,
+    /// The price change in hundredths of a percent of the open price.
+    #[prost(int64, tag = "10")]
+    pub price_change_bps: i64,
+    #[prost(message, optional, tag = "11")]
+    pub header: ::core::option::Option<StreamHeader>,
+}
+/// A single summary is sent when the stream asks for at most one update, levels are ignored.
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct MarketSummaryRequest {
+    #[prost(message, optional, tag = "1")]
+    pub stream: ::core::option::Option<StreamParameters>,
+}
+/// The market of a symbol at a glance. Prices of an empty side are zero, as is the spread unless
+/// both sides of the book have orders.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SymbolSummary {
+    #[prost(string, tag = "1")]
+    pub symbol: ::prost::alloc::string::String,
+    #[prost(uint64, tag = "2")]
+    pub best_bid: u64,
+    #[prost(uint64, tag = "3")]
+    pub best_ask: u64,
+    #[prost(uint64, tag = "4")]
+    pub spread: u64,
+    #[prost(uint64, tag = "5")]
+    pub last_price: u64,
+    /// Quantity and notional traded over the last 24 hours.
+    #[prost(uint64, tag = "6")]
+    pub volume: u64,
+    #[prost(uint64, tag = "7")]
+    pub quote_volume: u64,
+    #[prost(uint64, tag = "8")]
+    pub trade_count: u64,
+    /// The price change over the last 24 hours in hundredths of a percent of the open price.
+    #[prost(int64, tag = "9")]
+    pub price_change_bps: i64,
+    /// Number of operations the orderbook of the symbol has executed.
+    #[prost(uint64, tag = "10")]
+    pub sequence: u64,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MarketSummary {
+    #[prost(message, repeated, tag = "1")]
+    pub symbols: ::prost::alloc::vec::Vec<SymbolSummary>,
+    #[prost(message, optional, tag = "2")]
+    pub header: ::core::option::Option<StreamHeader>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct BookEventsRequest {
+    #[prost(string, tag = "1")]
+    pub symbol: ::prost::alloc::string::String,
+}
+/// A change to a single resting order. Adds rest at the back of their price level, executes take
+/// the quantity off the order until nothing is left, and replaces set the quantity of the order
+/// while keeping its place. Orders moving to another price are cancelled and added again.
+/// A subscription starts with an add of every resting order in priority order, marked as
+/// snapshot and carrying the sequence of the last event they reflect. Every later event follows
+/// with the next sequence, a gap means the subscriber fell behind and has to subscribe again.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct BookEvent {
+    #[prost(uint64, tag = "1")]
+    pub sequence: u64,
+    #[prost(enumeration = "BookEventKind", tag = "2")]
+    pub kind: i32,
+    #[prost(bytes = "vec", tag = "3")]
+    pub order_id: ::prost::alloc::vec::Vec<u8>,
+    /// Side and price are only set on adds and replaces, and the price of executes.
+    #[prost(enumeration = "OrderSide", tag = "4")]
+    pub side: i32,
+    #[prost(uint64, tag = "5")]
+    pub price: u64,
+    #[prost(uint64, tag = "6")]
+    pub quantity: u64,
+    #[prost(bool, tag = "7")]
+    pub snapshot: bool,
+    #[prost(bytes = "vec", tag = "8")]
+    pub timestamp: ::prost::alloc::vec::Vec<u8>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CandleRequest {
+    #[prost(string, tag = "1")]
+    pub symbol: ::prost::alloc::string::String,
+    #[prost(enumeration = "CandleInterval", tag = "2")]
+    pub interval: i32,
+    /// Latest candles sent on connect before any update, at most 1000.
+    #[prost(uint32, tag = "3")]
+    pub backfill: u32,
+    /// Levels are ignored.
+    #[prost(message, optional, tag = "4")]
+    pub stream: ::core::option::Option<StreamParameters>,
+}
+/// The trades of a symbol over an interval. A candle is sent again whenever it changed until it is
+/// sent as closed, intervals without trades have no candle.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Candle {
+    /// Start of the interval in nanoseconds since the unix epoch.
+    #[prost(bytes = "vec", tag = "1")]
+    pub start: ::prost::alloc::vec::Vec<u8>,
+    #[prost(uint64, tag = "2")]
+    pub open: u64,
+    #[prost(uint64, tag = "3")]
+    pub high: u64,
+    #[prost(uint64, tag = "4")]
+    pub low: u64,
+    #[prost(uint64, tag = "5")]
+    pub close: u64,
+    #[prost(uint64, tag = "6")]
+    pub volume: u64,
+    #[prost(uint64, tag = "7")]
+    pub trade_count: u64,
+    #[prost(bool, tag = "8")]
+    pub closed: bool,
+    #[prost(message, optional, tag = "9")]
+    pub header: ::core::option::Option<StreamHeader>,
+}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct DeliveryMetricsRequest {}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct LatencyRequest {
+    #[prost(string, tag = "1")]
+    pub symbol: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct OperationLatency {
+    #[prost(string, tag = "1")]
+    pub operation: ::prost::alloc::string::String,
+    #[prost(uint64, tag = "2")]
+    pub count: u64,
+    #[prost(uint64, tag = "3")]
+    pub p50_nanos: u64,
+    #[prost(uint64, tag = "4")]
+    pub p90_nanos: u64,
+    #[prost(uint64, tag = "5")]
+    pub p99_nanos: u64,
+    #[prost(uint64, tag = "6")]
+    pub p999_nanos: u64,
+    #[prost(uint64, tag = "7")]
+    pub max_nanos: u64,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct GenericMessage {
+pub struct LatencySummary {
+    #[prost(message, repeated, tag = "1")]
+    pub operations: ::prost::alloc::vec::Vec<OperationLatency>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ChannelMetricsRequest {
     #[prost(string, tag = "1")]
-    pub message: ::prost::alloc::string::String,
-    #[prost(string, tag = "2")]
     pub symbol: ::prost::alloc::string::String,
-    #[prost(bytes = "vec", tag = "3")]
-    pub timestamp: ::prost::alloc::vec::Vec<u8>,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct StringResponse {
+pub struct ChannelMetrics {
     #[prost(string, tag = "1")]
-    pub message: ::prost::alloc::string::String,
+    pub channel: ::prost::alloc::string::String,
+    #[prost(uint64, tag = "2")]
+    pub capacity: u64,
+    #[prost(uint64, tag = "3")]
+    pub depth: u64,
+    #[prost(uint64, tag = "4")]
+    pub high_watermark: u64,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ChannelMetricsList {
+    #[prost(message, repeated, tag = "1")]
+    pub channels: ::prost::alloc::vec::Vec<ChannelMetrics>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AdminSymbolRequest {
+    #[prost(string, tag = "1")]
+    pub symbol: ::prost::alloc::string::String,
 }
 #[derive(Clone, Copy, PartialEq, ::prost::Message)]
-pub struct RfqResult {
-    #[prost(enumeration = "RfqStatus", tag = "1")]
-    pub status: i32,
+pub struct SnapshotRequest {}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct ReloadConfigRequest {}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct ListTasksRequest {}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TaskInfo {
+    #[prost(string, tag = "1")]
+    pub id: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub status: ::prost::alloc::string::String,
+    #[prost(uint32, tag = "3")]
+    pub restarts: u32,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TaskList {
+    #[prost(message, repeated, tag = "1")]
+    pub tasks: ::prost::alloc::vec::Vec<TaskInfo>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MemoryStats {
+    #[prost(string, tag = "1")]
+    pub symbol: ::prost::alloc::string::String,
+    /// Order slots pre-allocated by ORDERBOOK_STORE_CAPACITY.
     #[prost(uint64, tag = "2")]
-    pub price: u64,
+    pub store_capacity: u64,
+    /// Order slots in the store, more than the capacity once it had to grow.
     #[prost(uint64, tag = "3")]
-    pub quantity: u64,
+    pub store_slots: u64,
+    #[prost(uint64, tag = "4")]
+    pub store_occupancy: u64,
+    #[prost(uint64, tag = "5")]
+    pub peak_store_occupancy: u64,
+    #[prost(uint64, tag = "6")]
+    pub bid_levels: u64,
+    #[prost(uint64, tag = "7")]
+    pub ask_levels: u64,
+    /// Price levels left without orders.
+    #[prost(uint64, tag = "8")]
+    pub empty_levels: u64,
+    /// Order slots a new price level is allocated with, set by ORDERBOOK_QUEUE_CAPACITY.
+    #[prost(uint64, tag = "9")]
+    pub queue_capacity: u64,
+    /// Order slots allocated by the queues of all price levels.
+    #[prost(uint64, tag = "10")]
+    pub queue_allocations: u64,
+    /// Estimate of the heap memory held by the book.
+    #[prost(uint64, tag = "11")]
+    pub allocated_bytes: u64,
 }
-#[derive(Clone, Copy, PartialEq, ::prost::Message)]
-pub struct CreateLimitOrderRequest {
-    #[prost(uint64, tag = "1")]
-    pub price: u64,
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CreateSymbolRequest {
+    #[prost(string, tag = "1")]
+    pub symbol: ::prost::alloc::string::String,
     #[prost(uint64, tag = "2")]
-    pub quantity: u64,
-    #[prost(enumeration = "OrderSide", tag = "3")]
-    pub side: i32,
+    pub tick_size: u64,
+    #[prost(uint64, tag = "3")]
+    pub lot_size: u64,
+    #[prost(uint64, tag = "4")]
+    pub queue_capacity: u64,
+    #[prost(uint64, tag = "5")]
+    pub store_capacity: u64,
+    /// Derived from the symbol when empty.
+    #[prost(string, tag = "6")]
+    pub base_asset: ::prost::alloc::string::String,
+    #[prost(string, tag = "7")]
+    pub quote_asset: ::prost::alloc::string::String,
+    /// Caps on the quantity and notional resting in the orderbook, the server defaults when zero.
+    #[prost(uint64, tag = "8")]
+    pub max_resting_quantity: u64,
+    #[prost(uint64, tag = "9")]
+    pub max_resting_notional: u64,
+    /// Tie-break rule within a price level, one of `price_time`, `size_time` or `random`, the server
+    /// default when empty.
+    #[prost(string, tag = "10")]
+    pub matching_policy: ::prost::alloc::string::String,
+    /// Seed of the `random` matching policy.
+    #[prost(uint64, tag = "11")]
+    pub matching_seed: u64,
 }
-#[derive(Clone, Copy, PartialEq, ::prost::Message)]
-pub struct CreateMarketOrderRequest {
-    #[prost(uint64, tag = "1")]
-    pub quantity: u64,
-    #[prost(enumeration = "OrderSide", tag = "2")]
-    pub side: i32,
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct BalanceAdjustmentRequest {
+    #[prost(string, tag = "1")]
+    pub account: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub asset: ::prost::alloc::string::String,
+    #[prost(uint64, tag = "3")]
+    pub amount: u64,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct ModifyLimitOrderRequest {
-    #[prost(bytes = "vec", tag = "1")]
-    pub order_id: ::prost::alloc::vec::Vec<u8>,
+pub struct AccountRequest {
+    #[prost(string, tag = "1")]
+    pub account: ::prost::alloc::string::String,
+}
+/// Lifts the lock a tripped market maker protection put on the quotes of the account in the symbol.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ProtectionResetRequest {
+    #[prost(string, tag = "1")]
+    pub symbol: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub account: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Balance {
+    #[prost(string, tag = "1")]
+    pub asset: ::prost::alloc::string::String,
     #[prost(uint64, tag = "2")]
-    pub price: u64,
+    pub total: u64,
     #[prost(uint64, tag = "3")]
-    pub quantity: u64,
-    #[prost(enumeration = "OrderSide", tag = "4")]
-    pub side: i32,
+    pub held: u64,
+    #[prost(uint64, tag = "4")]
+    pub available: u64,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct CancelLimitOrderRequest {
-    #[prost(bytes = "vec", tag = "1")]
-    pub order_id: ::prost::alloc::vec::Vec<u8>,
+pub struct BalanceList {
+    #[prost(string, tag = "1")]
+    pub account: ::prost::alloc::string::String,
+    #[prost(message, repeated, tag = "2")]
+    pub balances: ::prost::alloc::vec::Vec<Balance>,
 }
-#[derive(Clone, Copy, PartialEq, ::prost::Message)]
-pub struct OrderbookDataRequest {
-    #[prost(enumeration = "Granularity", tag = "1")]
-    pub granularity: i32,
+/// Limits of zero are unlimited.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RiskLimits {
+    #[prost(string, tag = "1")]
+    pub account: ::prost::alloc::string::String,
+    #[prost(uint64, tag = "2")]
+    pub max_order_quantity: u64,
+    #[prost(uint64, tag = "3")]
+    pub max_open_orders: u64,
+    #[prost(uint64, tag = "4")]
+    pub max_notional: u64,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PositionRequest {
+    #[prost(string, tag = "1")]
+    pub account: ::prost::alloc::string::String,
+    #[prost(message, optional, tag = "2")]
+    pub stream: ::core::option::Option<StreamParameters>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Position {
+    #[prost(string, tag = "1")]
+    pub symbol: ::prost::alloc::string::String,
+    /// Bought minus sold quantity, negative when the account is short.
+    #[prost(sint64, tag = "2")]
+    pub net_quantity: i64,
+    #[prost(uint64, tag = "3")]
+    pub bought_quantity: u64,
+    #[prost(uint64, tag = "4")]
+    pub sold_quantity: u64,
+    #[prost(uint64, tag = "5")]
+    pub bought_notional: u64,
+    #[prost(uint64, tag = "6")]
+    pub sold_notional: u64,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PositionList {
+    #[prost(string, tag = "1")]
+    pub account: ::prost::alloc::string::String,
+    #[prost(message, repeated, tag = "2")]
+    pub positions: ::prost::alloc::vec::Vec<Position>,
+    #[prost(message, optional, tag = "3")]
+    pub header: ::core::option::Option<StreamHeader>,
 }
 #[derive(Clone, Copy, PartialEq, ::prost::Message)]
-pub struct Level {
+pub struct DeliveryMetrics {
     #[prost(uint64, tag = "1")]
-    pub price: u64,
+    pub delivered: u64,
     #[prost(uint64, tag = "2")]
-    pub quantity: u64,
+    pub failed: u64,
+    #[prost(uint64, tag = "3")]
+    pub dropped: u64,
+    #[prost(uint64, tag = "4")]
+    pub pending: u64,
+    #[prost(uint64, tag = "5")]
+    pub average_latency_micros: u64,
+    #[prost(uint64, tag = "6")]
+    pub max_latency_micros: u64,
+    #[prost(bool, tag = "7")]
+    pub trading_halted: bool,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct OrderbookData {
+pub struct ReplicationRequest {
+    #[prost(string, tag = "1")]
+    pub symbol: ::prost::alloc::string::String,
+    /// The fencing token of the replica, a primary holding an older token fences itself.
+    #[prost(uint64, tag = "2")]
+    pub fencing_token: u64,
+}
+/// Either the checkpoint the replica starts from or a batch of operations following it, both in
+/// the encoding of the write ahead log.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ReplicationUpdate {
     #[prost(uint64, tag = "1")]
-    pub max_bid: u64,
+    pub fencing_token: u64,
+    /// The batch the checkpoint reflects, or the number of the batch.
     #[prost(uint64, tag = "2")]
-    pub min_ask: u64,
+    pub sequence: u64,
+    #[prost(bytes = "vec", tag = "3")]
+    pub checkpoint: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes = "vec", tag = "4")]
+    pub batch: ::prost::alloc::vec::Vec<u8>,
+}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct FenceRequest {
+    #[prost(uint64, tag = "1")]
+    pub fencing_token: u64,
+}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct PromoteRequest {
+    /// Promotes the replica even if the primary cannot be reached to fence it.
+    #[prost(bool, tag = "1")]
+    pub force: bool,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ReplicationStatus {
+    #[prost(string, tag = "1")]
+    pub role: ::prost::alloc::string::String,
+    #[prost(uint64, tag = "2")]
+    pub fencing_token: u64,
+}
+/// Faults to inject into a server built with the `chaos` feature, for resilience tests.
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct FaultRequest {
+    /// Delay of the matching of every batch, replacing the current one, zero stops delaying.
+    #[prost(uint64, tag = "1")]
+    pub matching_delay_micros: u64,
+    /// Number of upcoming sends to kafka to fail, on top of those still to fail.
+    #[prost(uint64, tag = "2")]
+    pub failed_sends: u64,
+    /// Number of upcoming snapshot uploads to drop, on top of those still to drop.
     #[prost(uint64, tag = "3")]
-    pub last_trade_price: u64,
-    #[prost(message, repeated, tag = "4")]
-    pub bids: ::prost::alloc::vec::Vec<Level>,
-    #[prost(message, repeated, tag = "5")]
-    pub asks: ::prost::alloc::vec::Vec<Level>,
+    pub dropped_snapshots: u64,
 }
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
 #[repr(i32)]
@@ -178,6 +1257,8 @@ pub enum OrderStatus {
     PartiallyFilled = 2,
     Modified = 3,
     Cancelled = 4,
+    Reduced = 5,
+    Quoted = 6,
 }
 impl OrderStatus {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -191,6 +1272,8 @@ impl OrderStatus {
             Self::PartiallyFilled => "PartiallyFilled",
             Self::Modified => "Modified",
             Self::Cancelled => "Cancelled",
+            Self::Reduced => "Reduced",
+            Self::Quoted => "Quoted",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
@@ -201,6 +1284,154 @@ impl OrderStatus {
             "PartiallyFilled" => Some(Self::PartiallyFilled),
             "Modified" => Some(Self::Modified),
             "Cancelled" => Some(Self::Cancelled),
+            "Reduced" => Some(Self::Reduced),
+            "Quoted" => Some(Self::Quoted),
+            _ => None,
+        }
+    }
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum SessionPhase {
+    PreOpen = 0,
+    Open = 1,
+    Closed = 2,
+}
+impl SessionPhase {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Self::PreOpen => "PreOpen",
+            Self::Open => "Open",
+            Self::Closed => "Closed",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "PreOpen" => Some(Self::PreOpen),
+            "Open" => Some(Self::Open),
+            "Closed" => Some(Self::Closed),
+            _ => None,
+        }
+    }
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum TimeInForce {
+    GoodTillCancelled = 0,
+    /// Expires at the end of the trading session.
+    Day = 1,
+}
+impl TimeInForce {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Self::GoodTillCancelled => "GoodTillCancelled",
+            Self::Day => "Day",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "GoodTillCancelled" => Some(Self::GoodTillCancelled),
+            "Day" => Some(Self::Day),
+            _ => None,
+        }
+    }
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum AckStatus {
+    Accepted = 0,
+    Rejected = 1,
+}
+impl AckStatus {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Self::Accepted => "Accepted",
+            Self::Rejected => "Rejected",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "Accepted" => Some(Self::Accepted),
+            "Rejected" => Some(Self::Rejected),
+            _ => None,
+        }
+    }
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum RevisionKind {
+    RevisionCreated = 0,
+    RevisionModified = 1,
+    RevisionFilled = 2,
+    RevisionReduced = 3,
+}
+impl RevisionKind {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Self::RevisionCreated => "RevisionCreated",
+            Self::RevisionModified => "RevisionModified",
+            Self::RevisionFilled => "RevisionFilled",
+            Self::RevisionReduced => "RevisionReduced",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "RevisionCreated" => Some(Self::RevisionCreated),
+            "RevisionModified" => Some(Self::RevisionModified),
+            "RevisionFilled" => Some(Self::RevisionFilled),
+            "RevisionReduced" => Some(Self::RevisionReduced),
+            _ => None,
+        }
+    }
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum BookEventKind {
+    BookAdd = 0,
+    BookExecute = 1,
+    BookCancel = 2,
+    BookReplace = 3,
+}
+impl BookEventKind {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Self::BookAdd => "BookAdd",
+            Self::BookExecute => "BookExecute",
+            Self::BookCancel => "BookCancel",
+            Self::BookReplace => "BookReplace",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "BookAdd" => Some(Self::BookAdd),
+            "BookExecute" => Some(Self::BookExecute),
+            "BookCancel" => Some(Self::BookCancel),
+            "BookReplace" => Some(Self::BookReplace),
             _ => None,
         }
     }
@@ -272,3 +1503,32 @@ impl Granularity {
         }
     }
 }
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum CandleInterval {
+    OneMinute = 0,
+    FiveMinutes = 1,
+    OneHour = 2,
+}
+impl CandleInterval {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Self::OneMinute => "OneMinute",
+            Self::FiveMinutes => "FiveMinutes",
+            Self::OneHour => "OneHour",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "OneMinute" => Some(Self::OneMinute),
+            "FiveMinutes" => Some(Self::FiveMinutes),
+            "OneHour" => Some(Self::OneHour),
+            _ => None,
+        }
+    }
+}
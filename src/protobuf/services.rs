@@ -1,6 +1,4844 @@
 // This file is @generated by prost-build.
+/// Generated client implementations.
+pub mod order_dispatcher_client {
+    #![allow(
+        unused_variables,
+        dead_code,
+        missing_docs,
+        clippy::wildcard_imports,
+        clippy::let_unit_value,
+    )]
+    use tonic::codegen::*;
+    use tonic::codegen::http::Uri;
+    #[derive(Debug, Clone)]
+    pub struct OrderDispatcherClient<T> {
+        inner: tonic::client::Grpc<T>,
+    }
+    impl OrderDispatcherClient<tonic::transport::Channel> {
+        /// Attempt to create a new client by connecting to a given endpoint.
+        pub async fn connect<D>(dst: D) -> Result<Self, tonic::transport::Error>
+        where
+            D: TryInto<tonic::transport::Endpoint>,
+            D::Error: Into<StdError>,
+        {
+            let conn = tonic::transport::Endpoint::new(dst)?.connect().await?;
+            Ok(Self::new(conn))
+        }
+    }
+    impl<T> OrderDispatcherClient<T>
+    where
+        T: tonic::client::GrpcService<tonic::body::BoxBody>,
+        T::Error: Into<StdError>,
+        T::ResponseBody: Body<Data = Bytes> + std::marker::Send + 'static,
+        <T::ResponseBody as Body>::Error: Into<StdError> + std::marker::Send,
+    {
+        pub fn new(inner: T) -> Self {
+            let inner = tonic::client::Grpc::new(inner);
+            Self { inner }
+        }
+        pub fn with_origin(inner: T, origin: Uri) -> Self {
+            let inner = tonic::client::Grpc::with_origin(inner, origin);
+            Self { inner }
+        }
+        pub fn with_interceptor<F>(
+            inner: T,
+            interceptor: F,
+        ) -> OrderDispatcherClient<InterceptedService<T, F>>
+        where
+            F: tonic::service::Interceptor,
+            T::ResponseBody: Default,
+            T: tonic::codegen::Service<
+                http::Request<tonic::body::BoxBody>,
+                Response = http::Response<
+                    <T as tonic::client::GrpcService<tonic::body::BoxBody>>::ResponseBody,
+                >,
+            >,
+            <T as tonic::codegen::Service<
+                http::Request<tonic::body::BoxBody>,
+            >>::Error: Into<StdError> + std::marker::Send + std::marker::Sync,
+        {
+            OrderDispatcherClient::new(InterceptedService::new(inner, interceptor))
+        }
+        /// Compress requests with the given encoding.
+        ///
+        /// This requires the server to support it otherwise it might respond with an
+        /// error.
+        #[must_use]
+        pub fn send_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.inner = self.inner.send_compressed(encoding);
+            self
+        }
+        /// Enable decompressing responses.
+        #[must_use]
+        pub fn accept_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.inner = self.inner.accept_compressed(encoding);
+            self
+        }
+        /// Limits the maximum size of a decoded message.
+        ///
+        /// Default: `4MB`
+        #[must_use]
+        pub fn max_decoding_message_size(mut self, limit: usize) -> Self {
+            self.inner = self.inner.max_decoding_message_size(limit);
+            self
+        }
+        /// Limits the maximum size of an encoded message.
+        ///
+        /// Default: `usize::MAX`
+        #[must_use]
+        pub fn max_encoding_message_size(mut self, limit: usize) -> Self {
+            self.inner = self.inner.max_encoding_message_size(limit);
+            self
+        }
+        pub async fn limit(
+            &mut self,
+            request: impl tonic::IntoRequest<
+                super::super::models::CreateLimitOrderRequest,
+            >,
+        ) -> std::result::Result<
+            tonic::Response<super::super::models::OrderAck>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/services.OrderDispatcher/limit",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("services.OrderDispatcher", "limit"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn market(
+            &mut self,
+            request: impl tonic::IntoRequest<
+                super::super::models::CreateMarketOrderRequest,
+            >,
+        ) -> std::result::Result<
+            tonic::Response<super::super::models::OrderAck>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/services.OrderDispatcher/market",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("services.OrderDispatcher", "market"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn modify(
+            &mut self,
+            request: impl tonic::IntoRequest<
+                super::super::models::ModifyLimitOrderRequest,
+            >,
+        ) -> std::result::Result<
+            tonic::Response<super::super::models::OrderAck>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/services.OrderDispatcher/modify",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("services.OrderDispatcher", "modify"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn cancel(
+            &mut self,
+            request: impl tonic::IntoRequest<
+                super::super::models::CancelLimitOrderRequest,
+            >,
+        ) -> std::result::Result<
+            tonic::Response<super::super::models::OrderAck>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/services.OrderDispatcher/cancel",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("services.OrderDispatcher", "cancel"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn reduce(
+            &mut self,
+            request: impl tonic::IntoRequest<
+                super::super::models::ReduceLimitOrderRequest,
+            >,
+        ) -> std::result::Result<
+            tonic::Response<super::super::models::OrderAck>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/services.OrderDispatcher/reduce",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("services.OrderDispatcher", "reduce"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn quote(
+            &mut self,
+            request: impl tonic::IntoRequest<super::super::models::QuoteRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::super::models::OrderAck>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/services.OrderDispatcher/quote",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("services.OrderDispatcher", "quote"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn mass_quote(
+            &mut self,
+            request: impl tonic::IntoRequest<super::super::models::MassQuoteRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::super::models::MassQuoteAck>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/services.OrderDispatcher/mass_quote",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("services.OrderDispatcher", "mass_quote"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn execute_quote(
+            &mut self,
+            request: impl tonic::IntoRequest<super::super::models::ExecuteQuoteRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::super::models::OrderAck>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/services.OrderDispatcher/execute_quote",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("services.OrderDispatcher", "execute_quote"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn status(
+            &mut self,
+            request: impl tonic::IntoRequest<super::super::models::OrderStatusRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::super::models::OrderStatusResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/services.OrderDispatcher/status",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("services.OrderDispatcher", "status"));
+            self.inner.unary(req, path, codec).await
+        }
+    }
+}
+/// Generated client implementations.
+pub mod admin_client {
+    #![allow(
+        unused_variables,
+        dead_code,
+        missing_docs,
+        clippy::wildcard_imports,
+        clippy::let_unit_value,
+    )]
+    use tonic::codegen::*;
+    use tonic::codegen::http::Uri;
+    #[derive(Debug, Clone)]
+    pub struct AdminClient<T> {
+        inner: tonic::client::Grpc<T>,
+    }
+    impl AdminClient<tonic::transport::Channel> {
+        /// Attempt to create a new client by connecting to a given endpoint.
+        pub async fn connect<D>(dst: D) -> Result<Self, tonic::transport::Error>
+        where
+            D: TryInto<tonic::transport::Endpoint>,
+            D::Error: Into<StdError>,
+        {
+            let conn = tonic::transport::Endpoint::new(dst)?.connect().await?;
+            Ok(Self::new(conn))
+        }
+    }
+    impl<T> AdminClient<T>
+    where
+        T: tonic::client::GrpcService<tonic::body::BoxBody>,
+        T::Error: Into<StdError>,
+        T::ResponseBody: Body<Data = Bytes> + std::marker::Send + 'static,
+        <T::ResponseBody as Body>::Error: Into<StdError> + std::marker::Send,
+    {
+        pub fn new(inner: T) -> Self {
+            let inner = tonic::client::Grpc::new(inner);
+            Self { inner }
+        }
+        pub fn with_origin(inner: T, origin: Uri) -> Self {
+            let inner = tonic::client::Grpc::with_origin(inner, origin);
+            Self { inner }
+        }
+        pub fn with_interceptor<F>(
+            inner: T,
+            interceptor: F,
+        ) -> AdminClient<InterceptedService<T, F>>
+        where
+            F: tonic::service::Interceptor,
+            T::ResponseBody: Default,
+            T: tonic::codegen::Service<
+                http::Request<tonic::body::BoxBody>,
+                Response = http::Response<
+                    <T as tonic::client::GrpcService<tonic::body::BoxBody>>::ResponseBody,
+                >,
+            >,
+            <T as tonic::codegen::Service<
+                http::Request<tonic::body::BoxBody>,
+            >>::Error: Into<StdError> + std::marker::Send + std::marker::Sync,
+        {
+            AdminClient::new(InterceptedService::new(inner, interceptor))
+        }
+        /// Compress requests with the given encoding.
+        ///
+        /// This requires the server to support it otherwise it might respond with an
+        /// error.
+        #[must_use]
+        pub fn send_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.inner = self.inner.send_compressed(encoding);
+            self
+        }
+        /// Enable decompressing responses.
+        #[must_use]
+        pub fn accept_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.inner = self.inner.accept_compressed(encoding);
+            self
+        }
+        /// Limits the maximum size of a decoded message.
+        ///
+        /// Default: `4MB`
+        #[must_use]
+        pub fn max_decoding_message_size(mut self, limit: usize) -> Self {
+            self.inner = self.inner.max_decoding_message_size(limit);
+            self
+        }
+        /// Limits the maximum size of an encoded message.
+        ///
+        /// Default: `usize::MAX`
+        #[must_use]
+        pub fn max_encoding_message_size(mut self, limit: usize) -> Self {
+            self.inner = self.inner.max_encoding_message_size(limit);
+            self
+        }
+        pub async fn halt(
+            &mut self,
+            request: impl tonic::IntoRequest<super::super::models::AdminSymbolRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::super::models::StringResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/services.Admin/halt");
+            let mut req = request.into_request();
+            req.extensions_mut().insert(GrpcMethod::new("services.Admin", "halt"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn resume(
+            &mut self,
+            request: impl tonic::IntoRequest<super::super::models::AdminSymbolRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::super::models::StringResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/services.Admin/resume");
+            let mut req = request.into_request();
+            req.extensions_mut().insert(GrpcMethod::new("services.Admin", "resume"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn cancel_only(
+            &mut self,
+            request: impl tonic::IntoRequest<super::super::models::AdminSymbolRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::super::models::StringResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/services.Admin/cancel_only",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("services.Admin", "cancel_only"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn clear(
+            &mut self,
+            request: impl tonic::IntoRequest<super::super::models::AdminSymbolRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::super::models::StringResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/services.Admin/clear");
+            let mut req = request.into_request();
+            req.extensions_mut().insert(GrpcMethod::new("services.Admin", "clear"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn drain(
+            &mut self,
+            request: impl tonic::IntoRequest<super::super::models::AdminSymbolRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::super::models::StringResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/services.Admin/drain");
+            let mut req = request.into_request();
+            req.extensions_mut().insert(GrpcMethod::new("services.Admin", "drain"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn snapshot(
+            &mut self,
+            request: impl tonic::IntoRequest<super::super::models::SnapshotRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::super::models::StringResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/services.Admin/snapshot");
+            let mut req = request.into_request();
+            req.extensions_mut().insert(GrpcMethod::new("services.Admin", "snapshot"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn create_symbol(
+            &mut self,
+            request: impl tonic::IntoRequest<super::super::models::CreateSymbolRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::super::models::StringResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/services.Admin/create_symbol",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("services.Admin", "create_symbol"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn retire_symbol(
+            &mut self,
+            request: impl tonic::IntoRequest<super::super::models::AdminSymbolRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::super::models::StringResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/services.Admin/retire_symbol",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("services.Admin", "retire_symbol"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn reload_config(
+            &mut self,
+            request: impl tonic::IntoRequest<super::super::models::ReloadConfigRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::super::models::StringResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/services.Admin/reload_config",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("services.Admin", "reload_config"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn list_tasks(
+            &mut self,
+            request: impl tonic::IntoRequest<super::super::models::ListTasksRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::super::models::TaskList>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/services.Admin/list_tasks",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut().insert(GrpcMethod::new("services.Admin", "list_tasks"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn memory_stats(
+            &mut self,
+            request: impl tonic::IntoRequest<super::super::models::AdminSymbolRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::super::models::MemoryStats>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/services.Admin/memory_stats",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("services.Admin", "memory_stats"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn credit(
+            &mut self,
+            request: impl tonic::IntoRequest<
+                super::super::models::BalanceAdjustmentRequest,
+            >,
+        ) -> std::result::Result<
+            tonic::Response<super::super::models::BalanceList>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/services.Admin/credit");
+            let mut req = request.into_request();
+            req.extensions_mut().insert(GrpcMethod::new("services.Admin", "credit"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn debit(
+            &mut self,
+            request: impl tonic::IntoRequest<
+                super::super::models::BalanceAdjustmentRequest,
+            >,
+        ) -> std::result::Result<
+            tonic::Response<super::super::models::BalanceList>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/services.Admin/debit");
+            let mut req = request.into_request();
+            req.extensions_mut().insert(GrpcMethod::new("services.Admin", "debit"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn balances(
+            &mut self,
+            request: impl tonic::IntoRequest<super::super::models::AccountRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::super::models::BalanceList>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/services.Admin/balances");
+            let mut req = request.into_request();
+            req.extensions_mut().insert(GrpcMethod::new("services.Admin", "balances"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn set_risk_limits(
+            &mut self,
+            request: impl tonic::IntoRequest<super::super::models::RiskLimits>,
+        ) -> std::result::Result<
+            tonic::Response<super::super::models::RiskLimits>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/services.Admin/set_risk_limits",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("services.Admin", "set_risk_limits"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn risk_limits(
+            &mut self,
+            request: impl tonic::IntoRequest<super::super::models::AccountRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::super::models::RiskLimits>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/services.Admin/risk_limits",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("services.Admin", "risk_limits"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn reset_protection(
+            &mut self,
+            request: impl tonic::IntoRequest<
+                super::super::models::ProtectionResetRequest,
+            >,
+        ) -> std::result::Result<
+            tonic::Response<super::super::models::StringResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/services.Admin/reset_protection",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("services.Admin", "reset_protection"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn promote(
+            &mut self,
+            request: impl tonic::IntoRequest<super::super::models::PromoteRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::super::models::ReplicationStatus>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/services.Admin/promote");
+            let mut req = request.into_request();
+            req.extensions_mut().insert(GrpcMethod::new("services.Admin", "promote"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn inject_fault(
+            &mut self,
+            request: impl tonic::IntoRequest<super::super::models::FaultRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::super::models::StringResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/services.Admin/inject_fault",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("services.Admin", "inject_fault"));
+            self.inner.unary(req, path, codec).await
+        }
+    }
+}
+/// Generated client implementations.
+pub mod stat_stream_client {
+    #![allow(
+        unused_variables,
+        dead_code,
+        missing_docs,
+        clippy::wildcard_imports,
+        clippy::let_unit_value,
+    )]
+    use tonic::codegen::*;
+    use tonic::codegen::http::Uri;
+    #[derive(Debug, Clone)]
+    pub struct StatStreamClient<T> {
+        inner: tonic::client::Grpc<T>,
+    }
+    impl StatStreamClient<tonic::transport::Channel> {
+        /// Attempt to create a new client by connecting to a given endpoint.
+        pub async fn connect<D>(dst: D) -> Result<Self, tonic::transport::Error>
+        where
+            D: TryInto<tonic::transport::Endpoint>,
+            D::Error: Into<StdError>,
+        {
+            let conn = tonic::transport::Endpoint::new(dst)?.connect().await?;
+            Ok(Self::new(conn))
+        }
+    }
+    impl<T> StatStreamClient<T>
+    where
+        T: tonic::client::GrpcService<tonic::body::BoxBody>,
+        T::Error: Into<StdError>,
+        T::ResponseBody: Body<Data = Bytes> + std::marker::Send + 'static,
+        <T::ResponseBody as Body>::Error: Into<StdError> + std::marker::Send,
+    {
+        pub fn new(inner: T) -> Self {
+            let inner = tonic::client::Grpc::new(inner);
+            Self { inner }
+        }
+        pub fn with_origin(inner: T, origin: Uri) -> Self {
+            let inner = tonic::client::Grpc::with_origin(inner, origin);
+            Self { inner }
+        }
+        pub fn with_interceptor<F>(
+            inner: T,
+            interceptor: F,
+        ) -> StatStreamClient<InterceptedService<T, F>>
+        where
+            F: tonic::service::Interceptor,
+            T::ResponseBody: Default,
+            T: tonic::codegen::Service<
+                http::Request<tonic::body::BoxBody>,
+                Response = http::Response<
+                    <T as tonic::client::GrpcService<tonic::body::BoxBody>>::ResponseBody,
+                >,
+            >,
+            <T as tonic::codegen::Service<
+                http::Request<tonic::body::BoxBody>,
+            >>::Error: Into<StdError> + std::marker::Send + std::marker::Sync,
+        {
+            StatStreamClient::new(InterceptedService::new(inner, interceptor))
+        }
+        /// Compress requests with the given encoding.
+        ///
+        /// This requires the server to support it otherwise it might respond with an
+        /// error.
+        #[must_use]
+        pub fn send_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.inner = self.inner.send_compressed(encoding);
+            self
+        }
+        /// Enable decompressing responses.
+        #[must_use]
+        pub fn accept_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.inner = self.inner.accept_compressed(encoding);
+            self
+        }
+        /// Limits the maximum size of a decoded message.
+        ///
+        /// Default: `4MB`
+        #[must_use]
+        pub fn max_decoding_message_size(mut self, limit: usize) -> Self {
+            self.inner = self.inner.max_decoding_message_size(limit);
+            self
+        }
+        /// Limits the maximum size of an encoded message.
+        ///
+        /// Default: `usize::MAX`
+        #[must_use]
+        pub fn max_encoding_message_size(mut self, limit: usize) -> Self {
+            self.inner = self.inner.max_encoding_message_size(limit);
+            self
+        }
+        pub async fn rfq(
+            &mut self,
+            request: impl tonic::IntoRequest<
+                super::super::models::CreateMarketOrderRequest,
+            >,
+        ) -> std::result::Result<
+            tonic::Response<tonic::codec::Streaming<super::super::models::RfqResult>>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/services.StatStream/rfq");
+            let mut req = request.into_request();
+            req.extensions_mut().insert(GrpcMethod::new("services.StatStream", "rfq"));
+            self.inner.server_streaming(req, path, codec).await
+        }
+        pub async fn orderbook(
+            &mut self,
+            request: impl tonic::IntoRequest<super::super::models::OrderbookDataRequest>,
+        ) -> std::result::Result<
+            tonic::Response<
+                tonic::codec::Streaming<super::super::models::OrderbookData>,
+            >,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/services.StatStream/orderbook",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("services.StatStream", "orderbook"));
+            self.inner.server_streaming(req, path, codec).await
+        }
+        pub async fn orderbook_updates(
+            &mut self,
+            request: impl tonic::IntoRequest<super::super::models::OrderbookDataRequest>,
+        ) -> std::result::Result<
+            tonic::Response<
+                tonic::codec::Streaming<super::super::models::OrderbookUpdate>,
+            >,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/services.StatStream/orderbook_updates",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("services.StatStream", "orderbook_updates"));
+            self.inner.server_streaming(req, path, codec).await
+        }
+        pub async fn resync(
+            &mut self,
+            request: impl tonic::IntoRequest<super::super::models::ResyncRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::super::models::OrderbookUpdate>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/services.StatStream/resync",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("services.StatStream", "resync"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn bbo(
+            &mut self,
+            request: impl tonic::IntoRequest<super::super::models::BboRequest>,
+        ) -> std::result::Result<
+            tonic::Response<tonic::codec::Streaming<super::super::models::Bbo>>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/services.StatStream/bbo");
+            let mut req = request.into_request();
+            req.extensions_mut().insert(GrpcMethod::new("services.StatStream", "bbo"));
+            self.inner.server_streaming(req, path, codec).await
+        }
+        pub async fn indicative_uncross(
+            &mut self,
+            request: impl tonic::IntoRequest<
+                super::super::models::IndicativeUncrossRequest,
+            >,
+        ) -> std::result::Result<
+            tonic::Response<
+                tonic::codec::Streaming<super::super::models::IndicativeUncross>,
+            >,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/services.StatStream/indicative_uncross",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("services.StatStream", "indicative_uncross"));
+            self.inner.server_streaming(req, path, codec).await
+        }
+        pub async fn book_events(
+            &mut self,
+            request: impl tonic::IntoRequest<super::super::models::BookEventsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<tonic::codec::Streaming<super::super::models::BookEvent>>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/services.StatStream/book_events",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("services.StatStream", "book_events"));
+            self.inner.server_streaming(req, path, codec).await
+        }
+        pub async fn slippage(
+            &mut self,
+            request: impl tonic::IntoRequest<super::super::models::SlippageRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::super::models::SlippageCurve>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/services.StatStream/slippage",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("services.StatStream", "slippage"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn heatmap(
+            &mut self,
+            request: impl tonic::IntoRequest<super::super::models::HeatmapRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::super::models::Heatmap>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/services.StatStream/heatmap",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("services.StatStream", "heatmap"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn volume_profile(
+            &mut self,
+            request: impl tonic::IntoRequest<super::super::models::VolumeProfileRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::super::models::VolumeProfile>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/services.StatStream/volume_profile",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("services.StatStream", "volume_profile"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn ticker(
+            &mut self,
+            request: impl tonic::IntoRequest<super::super::models::TickerRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::super::models::Ticker>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/services.StatStream/ticker",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("services.StatStream", "ticker"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn average_prices(
+            &mut self,
+            request: impl tonic::IntoRequest<super::super::models::AveragePriceRequest>,
+        ) -> std::result::Result<
+            tonic::Response<
+                tonic::codec::Streaming<super::super::models::AveragePrices>,
+            >,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/services.StatStream/average_prices",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("services.StatStream", "average_prices"));
+            self.inner.server_streaming(req, path, codec).await
+        }
+        pub async fn ticker_updates(
+            &mut self,
+            request: impl tonic::IntoRequest<super::super::models::TickerRequest>,
+        ) -> std::result::Result<
+            tonic::Response<tonic::codec::Streaming<super::super::models::Ticker>>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/services.StatStream/ticker_updates",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("services.StatStream", "ticker_updates"));
+            self.inner.server_streaming(req, path, codec).await
+        }
+        pub async fn candles(
+            &mut self,
+            request: impl tonic::IntoRequest<super::super::models::CandleRequest>,
+        ) -> std::result::Result<
+            tonic::Response<tonic::codec::Streaming<super::super::models::Candle>>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/services.StatStream/candles",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("services.StatStream", "candles"));
+            self.inner.server_streaming(req, path, codec).await
+        }
+        pub async fn market_summary(
+            &mut self,
+            request: impl tonic::IntoRequest<super::super::models::MarketSummaryRequest>,
+        ) -> std::result::Result<
+            tonic::Response<
+                tonic::codec::Streaming<super::super::models::MarketSummary>,
+            >,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/services.StatStream/market_summary",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("services.StatStream", "market_summary"));
+            self.inner.server_streaming(req, path, codec).await
+        }
+        pub async fn delivery_metrics(
+            &mut self,
+            request: impl tonic::IntoRequest<
+                super::super::models::DeliveryMetricsRequest,
+            >,
+        ) -> std::result::Result<
+            tonic::Response<super::super::models::DeliveryMetrics>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/services.StatStream/delivery_metrics",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("services.StatStream", "delivery_metrics"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn latency(
+            &mut self,
+            request: impl tonic::IntoRequest<super::super::models::LatencyRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::super::models::LatencySummary>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/services.StatStream/latency",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("services.StatStream", "latency"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn channel_metrics(
+            &mut self,
+            request: impl tonic::IntoRequest<super::super::models::ChannelMetricsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::super::models::ChannelMetricsList>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/services.StatStream/channel_metrics",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("services.StatStream", "channel_metrics"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn positions(
+            &mut self,
+            request: impl tonic::IntoRequest<super::super::models::PositionRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::super::models::PositionList>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/services.StatStream/positions",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("services.StatStream", "positions"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn position_updates(
+            &mut self,
+            request: impl tonic::IntoRequest<super::super::models::PositionRequest>,
+        ) -> std::result::Result<
+            tonic::Response<tonic::codec::Streaming<super::super::models::PositionList>>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/services.StatStream/position_updates",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("services.StatStream", "position_updates"));
+            self.inner.server_streaming(req, path, codec).await
+        }
+    }
+}
+/// Generated client implementations.
+pub mod delayed_stat_stream_client {
+    #![allow(
+        unused_variables,
+        dead_code,
+        missing_docs,
+        clippy::wildcard_imports,
+        clippy::let_unit_value,
+    )]
+    use tonic::codegen::*;
+    use tonic::codegen::http::Uri;
+    /// Market data delayed and sampled as configured, which is served without the market data token
+    /// the real-time StatStream may require.
+    #[derive(Debug, Clone)]
+    pub struct DelayedStatStreamClient<T> {
+        inner: tonic::client::Grpc<T>,
+    }
+    impl DelayedStatStreamClient<tonic::transport::Channel> {
+        /// Attempt to create a new client by connecting to a given endpoint.
+        pub async fn connect<D>(dst: D) -> Result<Self, tonic::transport::Error>
+        where
+            D: TryInto<tonic::transport::Endpoint>,
+            D::Error: Into<StdError>,
+        {
+            let conn = tonic::transport::Endpoint::new(dst)?.connect().await?;
+            Ok(Self::new(conn))
+        }
+    }
+    impl<T> DelayedStatStreamClient<T>
+    where
+        T: tonic::client::GrpcService<tonic::body::BoxBody>,
+        T::Error: Into<StdError>,
+        T::ResponseBody: Body<Data = Bytes> + std::marker::Send + 'static,
+        <T::ResponseBody as Body>::Error: Into<StdError> + std::marker::Send,
+    {
+        pub fn new(inner: T) -> Self {
+            let inner = tonic::client::Grpc::new(inner);
+            Self { inner }
+        }
+        pub fn with_origin(inner: T, origin: Uri) -> Self {
+            let inner = tonic::client::Grpc::with_origin(inner, origin);
+            Self { inner }
+        }
+        pub fn with_interceptor<F>(
+            inner: T,
+            interceptor: F,
+        ) -> DelayedStatStreamClient<InterceptedService<T, F>>
+        where
+            F: tonic::service::Interceptor,
+            T::ResponseBody: Default,
+            T: tonic::codegen::Service<
+                http::Request<tonic::body::BoxBody>,
+                Response = http::Response<
+                    <T as tonic::client::GrpcService<tonic::body::BoxBody>>::ResponseBody,
+                >,
+            >,
+            <T as tonic::codegen::Service<
+                http::Request<tonic::body::BoxBody>,
+            >>::Error: Into<StdError> + std::marker::Send + std::marker::Sync,
+        {
+            DelayedStatStreamClient::new(InterceptedService::new(inner, interceptor))
+        }
+        /// Compress requests with the given encoding.
+        ///
+        /// This requires the server to support it otherwise it might respond with an
+        /// error.
+        #[must_use]
+        pub fn send_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.inner = self.inner.send_compressed(encoding);
+            self
+        }
+        /// Enable decompressing responses.
+        #[must_use]
+        pub fn accept_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.inner = self.inner.accept_compressed(encoding);
+            self
+        }
+        /// Limits the maximum size of a decoded message.
+        ///
+        /// Default: `4MB`
+        #[must_use]
+        pub fn max_decoding_message_size(mut self, limit: usize) -> Self {
+            self.inner = self.inner.max_decoding_message_size(limit);
+            self
+        }
+        /// Limits the maximum size of an encoded message.
+        ///
+        /// Default: `usize::MAX`
+        #[must_use]
+        pub fn max_encoding_message_size(mut self, limit: usize) -> Self {
+            self.inner = self.inner.max_encoding_message_size(limit);
+            self
+        }
+        pub async fn orderbook(
+            &mut self,
+            request: impl tonic::IntoRequest<super::super::models::OrderbookDataRequest>,
+        ) -> std::result::Result<
+            tonic::Response<
+                tonic::codec::Streaming<super::super::models::OrderbookData>,
+            >,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/services.DelayedStatStream/orderbook",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("services.DelayedStatStream", "orderbook"));
+            self.inner.server_streaming(req, path, codec).await
+        }
+        pub async fn bbo(
+            &mut self,
+            request: impl tonic::IntoRequest<super::super::models::BboRequest>,
+        ) -> std::result::Result<
+            tonic::Response<tonic::codec::Streaming<super::super::models::Bbo>>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/services.DelayedStatStream/bbo",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("services.DelayedStatStream", "bbo"));
+            self.inner.server_streaming(req, path, codec).await
+        }
+        pub async fn ticker(
+            &mut self,
+            request: impl tonic::IntoRequest<super::super::models::TickerRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::super::models::Ticker>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/services.DelayedStatStream/ticker",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("services.DelayedStatStream", "ticker"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn ticker_updates(
+            &mut self,
+            request: impl tonic::IntoRequest<super::super::models::TickerRequest>,
+        ) -> std::result::Result<
+            tonic::Response<tonic::codec::Streaming<super::super::models::Ticker>>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/services.DelayedStatStream/ticker_updates",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("services.DelayedStatStream", "ticker_updates"));
+            self.inner.server_streaming(req, path, codec).await
+        }
+    }
+}
+/// Generated client implementations.
+pub mod replication_client {
+    #![allow(
+        unused_variables,
+        dead_code,
+        missing_docs,
+        clippy::wildcard_imports,
+        clippy::let_unit_value,
+    )]
+    use tonic::codegen::*;
+    use tonic::codegen::http::Uri;
+    /// Served by the primary to its replicas, which authenticate with the admin token.
+    #[derive(Debug, Clone)]
+    pub struct ReplicationClient<T> {
+        inner: tonic::client::Grpc<T>,
+    }
+    impl ReplicationClient<tonic::transport::Channel> {
+        /// Attempt to create a new client by connecting to a given endpoint.
+        pub async fn connect<D>(dst: D) -> Result<Self, tonic::transport::Error>
+        where
+            D: TryInto<tonic::transport::Endpoint>,
+            D::Error: Into<StdError>,
+        {
+            let conn = tonic::transport::Endpoint::new(dst)?.connect().await?;
+            Ok(Self::new(conn))
+        }
+    }
+    impl<T> ReplicationClient<T>
+    where
+        T: tonic::client::GrpcService<tonic::body::BoxBody>,
+        T::Error: Into<StdError>,
+        T::ResponseBody: Body<Data = Bytes> + std::marker::Send + 'static,
+        <T::ResponseBody as Body>::Error: Into<StdError> + std::marker::Send,
+    {
+        pub fn new(inner: T) -> Self {
+            let inner = tonic::client::Grpc::new(inner);
+            Self { inner }
+        }
+        pub fn with_origin(inner: T, origin: Uri) -> Self {
+            let inner = tonic::client::Grpc::with_origin(inner, origin);
+            Self { inner }
+        }
+        pub fn with_interceptor<F>(
+            inner: T,
+            interceptor: F,
+        ) -> ReplicationClient<InterceptedService<T, F>>
+        where
+            F: tonic::service::Interceptor,
+            T::ResponseBody: Default,
+            T: tonic::codegen::Service<
+                http::Request<tonic::body::BoxBody>,
+                Response = http::Response<
+                    <T as tonic::client::GrpcService<tonic::body::BoxBody>>::ResponseBody,
+                >,
+            >,
+            <T as tonic::codegen::Service<
+                http::Request<tonic::body::BoxBody>,
+            >>::Error: Into<StdError> + std::marker::Send + std::marker::Sync,
+        {
+            ReplicationClient::new(InterceptedService::new(inner, interceptor))
+        }
+        /// Compress requests with the given encoding.
+        ///
+        /// This requires the server to support it otherwise it might respond with an
+        /// error.
+        #[must_use]
+        pub fn send_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.inner = self.inner.send_compressed(encoding);
+            self
+        }
+        /// Enable decompressing responses.
+        #[must_use]
+        pub fn accept_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.inner = self.inner.accept_compressed(encoding);
+            self
+        }
+        /// Limits the maximum size of a decoded message.
+        ///
+        /// Default: `4MB`
+        #[must_use]
+        pub fn max_decoding_message_size(mut self, limit: usize) -> Self {
+            self.inner = self.inner.max_decoding_message_size(limit);
+            self
+        }
+        /// Limits the maximum size of an encoded message.
+        ///
+        /// Default: `usize::MAX`
+        #[must_use]
+        pub fn max_encoding_message_size(mut self, limit: usize) -> Self {
+            self.inner = self.inner.max_encoding_message_size(limit);
+            self
+        }
+        pub async fn subscribe(
+            &mut self,
+            request: impl tonic::IntoRequest<super::super::models::ReplicationRequest>,
+        ) -> std::result::Result<
+            tonic::Response<
+                tonic::codec::Streaming<super::super::models::ReplicationUpdate>,
+            >,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/services.Replication/subscribe",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("services.Replication", "subscribe"));
+            self.inner.server_streaming(req, path, codec).await
+        }
+        pub async fn fence(
+            &mut self,
+            request: impl tonic::IntoRequest<super::super::models::FenceRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::super::models::ReplicationStatus>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/services.Replication/fence",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("services.Replication", "fence"));
+            self.inner.unary(req, path, codec).await
+        }
+    }
+}
+/// Generated server implementations.
+pub mod order_dispatcher_server {
+    #![allow(
+        unused_variables,
+        dead_code,
+        missing_docs,
+        clippy::wildcard_imports,
+        clippy::let_unit_value,
+    )]
+    use tonic::codegen::*;
+    /// Generated trait containing gRPC methods that should be implemented for use with OrderDispatcherServer.
+    #[async_trait]
+    pub trait OrderDispatcher: std::marker::Send + std::marker::Sync + 'static {
+        async fn limit(
+            &self,
+            request: tonic::Request<super::super::models::CreateLimitOrderRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::super::models::OrderAck>,
+            tonic::Status,
+        >;
+        async fn market(
+            &self,
+            request: tonic::Request<super::super::models::CreateMarketOrderRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::super::models::OrderAck>,
+            tonic::Status,
+        >;
+        async fn modify(
+            &self,
+            request: tonic::Request<super::super::models::ModifyLimitOrderRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::super::models::OrderAck>,
+            tonic::Status,
+        >;
+        async fn cancel(
+            &self,
+            request: tonic::Request<super::super::models::CancelLimitOrderRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::super::models::OrderAck>,
+            tonic::Status,
+        >;
+        async fn reduce(
+            &self,
+            request: tonic::Request<super::super::models::ReduceLimitOrderRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::super::models::OrderAck>,
+            tonic::Status,
+        >;
+        async fn quote(
+            &self,
+            request: tonic::Request<super::super::models::QuoteRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::super::models::OrderAck>,
+            tonic::Status,
+        >;
+        async fn mass_quote(
+            &self,
+            request: tonic::Request<super::super::models::MassQuoteRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::super::models::MassQuoteAck>,
+            tonic::Status,
+        >;
+        async fn execute_quote(
+            &self,
+            request: tonic::Request<super::super::models::ExecuteQuoteRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::super::models::OrderAck>,
+            tonic::Status,
+        >;
+        async fn status(
+            &self,
+            request: tonic::Request<super::super::models::OrderStatusRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::super::models::OrderStatusResponse>,
+            tonic::Status,
+        >;
+    }
+    #[derive(Debug)]
+    pub struct OrderDispatcherServer<T> {
+        inner: Arc<T>,
+        accept_compression_encodings: EnabledCompressionEncodings,
+        send_compression_encodings: EnabledCompressionEncodings,
+        max_decoding_message_size: Option<usize>,
+        max_encoding_message_size: Option<usize>,
+    }
+    impl<T> OrderDispatcherServer<T> {
+        pub fn new(inner: T) -> Self {
+            Self::from_arc(Arc::new(inner))
+        }
+        pub fn from_arc(inner: Arc<T>) -> Self {
+            Self {
+                inner,
+                accept_compression_encodings: Default::default(),
+                send_compression_encodings: Default::default(),
+                max_decoding_message_size: None,
+                max_encoding_message_size: None,
+            }
+        }
+        pub fn with_interceptor<F>(
+            inner: T,
+            interceptor: F,
+        ) -> InterceptedService<Self, F>
+        where
+            F: tonic::service::Interceptor,
+        {
+            InterceptedService::new(Self::new(inner), interceptor)
+        }
+        /// Enable decompressing requests with the given encoding.
+        #[must_use]
+        pub fn accept_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.accept_compression_encodings.enable(encoding);
+            self
+        }
+        /// Compress responses with the given encoding, if the client supports it.
+        #[must_use]
+        pub fn send_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.send_compression_encodings.enable(encoding);
+            self
+        }
+        /// Limits the maximum size of a decoded message.
+        ///
+        /// Default: `4MB`
+        #[must_use]
+        pub fn max_decoding_message_size(mut self, limit: usize) -> Self {
+            self.max_decoding_message_size = Some(limit);
+            self
+        }
+        /// Limits the maximum size of an encoded message.
+        ///
+        /// Default: `usize::MAX`
+        #[must_use]
+        pub fn max_encoding_message_size(mut self, limit: usize) -> Self {
+            self.max_encoding_message_size = Some(limit);
+            self
+        }
+    }
+    impl<T, B> tonic::codegen::Service<http::Request<B>> for OrderDispatcherServer<T>
+    where
+        T: OrderDispatcher,
+        B: Body + std::marker::Send + 'static,
+        B::Error: Into<StdError> + std::marker::Send + 'static,
+    {
+        type Response = http::Response<tonic::body::BoxBody>;
+        type Error = std::convert::Infallible;
+        type Future = BoxFuture<Self::Response, Self::Error>;
+        fn poll_ready(
+            &mut self,
+            _cx: &mut Context<'_>,
+        ) -> Poll<std::result::Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+        fn call(&mut self, req: http::Request<B>) -> Self::Future {
+            match req.uri().path() {
+                "/services.OrderDispatcher/limit" => {
+                    #[allow(non_camel_case_types)]
+                    struct limitSvc<T: OrderDispatcher>(pub Arc<T>);
+                    impl<
+                        T: OrderDispatcher,
+                    > tonic::server::UnaryService<
+                        super::super::models::CreateLimitOrderRequest,
+                    > for limitSvc<T> {
+                        type Response = super::super::models::OrderAck;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<
+                                super::super::models::CreateLimitOrderRequest,
+                            >,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as OrderDispatcher>::limit(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = limitSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/services.OrderDispatcher/market" => {
+                    #[allow(non_camel_case_types)]
+                    struct marketSvc<T: OrderDispatcher>(pub Arc<T>);
+                    impl<
+                        T: OrderDispatcher,
+                    > tonic::server::UnaryService<
+                        super::super::models::CreateMarketOrderRequest,
+                    > for marketSvc<T> {
+                        type Response = super::super::models::OrderAck;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<
+                                super::super::models::CreateMarketOrderRequest,
+                            >,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as OrderDispatcher>::market(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = marketSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/services.OrderDispatcher/modify" => {
+                    #[allow(non_camel_case_types)]
+                    struct modifySvc<T: OrderDispatcher>(pub Arc<T>);
+                    impl<
+                        T: OrderDispatcher,
+                    > tonic::server::UnaryService<
+                        super::super::models::ModifyLimitOrderRequest,
+                    > for modifySvc<T> {
+                        type Response = super::super::models::OrderAck;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<
+                                super::super::models::ModifyLimitOrderRequest,
+                            >,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as OrderDispatcher>::modify(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = modifySvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/services.OrderDispatcher/cancel" => {
+                    #[allow(non_camel_case_types)]
+                    struct cancelSvc<T: OrderDispatcher>(pub Arc<T>);
+                    impl<
+                        T: OrderDispatcher,
+                    > tonic::server::UnaryService<
+                        super::super::models::CancelLimitOrderRequest,
+                    > for cancelSvc<T> {
+                        type Response = super::super::models::OrderAck;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<
+                                super::super::models::CancelLimitOrderRequest,
+                            >,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as OrderDispatcher>::cancel(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = cancelSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/services.OrderDispatcher/reduce" => {
+                    #[allow(non_camel_case_types)]
+                    struct reduceSvc<T: OrderDispatcher>(pub Arc<T>);
+                    impl<
+                        T: OrderDispatcher,
+                    > tonic::server::UnaryService<
+                        super::super::models::ReduceLimitOrderRequest,
+                    > for reduceSvc<T> {
+                        type Response = super::super::models::OrderAck;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<
+                                super::super::models::ReduceLimitOrderRequest,
+                            >,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as OrderDispatcher>::reduce(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = reduceSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/services.OrderDispatcher/quote" => {
+                    #[allow(non_camel_case_types)]
+                    struct quoteSvc<T: OrderDispatcher>(pub Arc<T>);
+                    impl<
+                        T: OrderDispatcher,
+                    > tonic::server::UnaryService<super::super::models::QuoteRequest>
+                    for quoteSvc<T> {
+                        type Response = super::super::models::OrderAck;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::super::models::QuoteRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as OrderDispatcher>::quote(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = quoteSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/services.OrderDispatcher/mass_quote" => {
+                    #[allow(non_camel_case_types)]
+                    struct mass_quoteSvc<T: OrderDispatcher>(pub Arc<T>);
+                    impl<
+                        T: OrderDispatcher,
+                    > tonic::server::UnaryService<super::super::models::MassQuoteRequest>
+                    for mass_quoteSvc<T> {
+                        type Response = super::super::models::MassQuoteAck;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<
+                                super::super::models::MassQuoteRequest,
+                            >,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as OrderDispatcher>::mass_quote(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = mass_quoteSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/services.OrderDispatcher/execute_quote" => {
+                    #[allow(non_camel_case_types)]
+                    struct execute_quoteSvc<T: OrderDispatcher>(pub Arc<T>);
+                    impl<
+                        T: OrderDispatcher,
+                    > tonic::server::UnaryService<
+                        super::super::models::ExecuteQuoteRequest,
+                    > for execute_quoteSvc<T> {
+                        type Response = super::super::models::OrderAck;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<
+                                super::super::models::ExecuteQuoteRequest,
+                            >,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as OrderDispatcher>::execute_quote(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = execute_quoteSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/services.OrderDispatcher/status" => {
+                    #[allow(non_camel_case_types)]
+                    struct statusSvc<T: OrderDispatcher>(pub Arc<T>);
+                    impl<
+                        T: OrderDispatcher,
+                    > tonic::server::UnaryService<
+                        super::super::models::OrderStatusRequest,
+                    > for statusSvc<T> {
+                        type Response = super::super::models::OrderStatusResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<
+                                super::super::models::OrderStatusRequest,
+                            >,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as OrderDispatcher>::status(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = statusSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                _ => {
+                    Box::pin(async move {
+                        let mut response = http::Response::new(empty_body());
+                        let headers = response.headers_mut();
+                        headers
+                            .insert(
+                                tonic::Status::GRPC_STATUS,
+                                (tonic::Code::Unimplemented as i32).into(),
+                            );
+                        headers
+                            .insert(
+                                http::header::CONTENT_TYPE,
+                                tonic::metadata::GRPC_CONTENT_TYPE,
+                            );
+                        Ok(response)
+                    })
+                }
+            }
+        }
+    }
+    impl<T> Clone for OrderDispatcherServer<T> {
+        fn clone(&self) -> Self {
+            let inner = self.inner.clone();
+            Self {
+                inner,
+                accept_compression_encodings: self.accept_compression_encodings,
+                send_compression_encodings: self.send_compression_encodings,
+                max_decoding_message_size: self.max_decoding_message_size,
+                max_encoding_message_size: self.max_encoding_message_size,
+            }
+        }
+    }
+    /// Generated gRPC service name
+    pub const SERVICE_NAME: &str = "services.OrderDispatcher";
+    impl<T> tonic::server::NamedService for OrderDispatcherServer<T> {
+        const NAME: &'static str = SERVICE_NAME;
+    }
+}
+/// Generated server implementations.
+pub mod admin_server {
+    #![allow(
+        unused_variables,
+        dead_code,
+        missing_docs,
+        clippy::wildcard_imports,
+        clippy::let_unit_value,
+    )]
+    use tonic::codegen::*;
+    /// Generated trait containing gRPC methods that should be implemented for use with AdminServer.
+    #[async_trait]
+    pub trait Admin: std::marker::Send + std::marker::Sync + 'static {
+        async fn halt(
+            &self,
+            request: tonic::Request<super::super::models::AdminSymbolRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::super::models::StringResponse>,
+            tonic::Status,
+        >;
+        async fn resume(
+            &self,
+            request: tonic::Request<super::super::models::AdminSymbolRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::super::models::StringResponse>,
+            tonic::Status,
+        >;
+        async fn cancel_only(
+            &self,
+            request: tonic::Request<super::super::models::AdminSymbolRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::super::models::StringResponse>,
+            tonic::Status,
+        >;
+        async fn clear(
+            &self,
+            request: tonic::Request<super::super::models::AdminSymbolRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::super::models::StringResponse>,
+            tonic::Status,
+        >;
+        async fn drain(
+            &self,
+            request: tonic::Request<super::super::models::AdminSymbolRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::super::models::StringResponse>,
+            tonic::Status,
+        >;
+        async fn snapshot(
+            &self,
+            request: tonic::Request<super::super::models::SnapshotRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::super::models::StringResponse>,
+            tonic::Status,
+        >;
+        async fn create_symbol(
+            &self,
+            request: tonic::Request<super::super::models::CreateSymbolRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::super::models::StringResponse>,
+            tonic::Status,
+        >;
+        async fn retire_symbol(
+            &self,
+            request: tonic::Request<super::super::models::AdminSymbolRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::super::models::StringResponse>,
+            tonic::Status,
+        >;
+        async fn reload_config(
+            &self,
+            request: tonic::Request<super::super::models::ReloadConfigRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::super::models::StringResponse>,
+            tonic::Status,
+        >;
+        async fn list_tasks(
+            &self,
+            request: tonic::Request<super::super::models::ListTasksRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::super::models::TaskList>,
+            tonic::Status,
+        >;
+        async fn memory_stats(
+            &self,
+            request: tonic::Request<super::super::models::AdminSymbolRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::super::models::MemoryStats>,
+            tonic::Status,
+        >;
+        async fn credit(
+            &self,
+            request: tonic::Request<super::super::models::BalanceAdjustmentRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::super::models::BalanceList>,
+            tonic::Status,
+        >;
+        async fn debit(
+            &self,
+            request: tonic::Request<super::super::models::BalanceAdjustmentRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::super::models::BalanceList>,
+            tonic::Status,
+        >;
+        async fn balances(
+            &self,
+            request: tonic::Request<super::super::models::AccountRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::super::models::BalanceList>,
+            tonic::Status,
+        >;
+        async fn set_risk_limits(
+            &self,
+            request: tonic::Request<super::super::models::RiskLimits>,
+        ) -> std::result::Result<
+            tonic::Response<super::super::models::RiskLimits>,
+            tonic::Status,
+        >;
+        async fn risk_limits(
+            &self,
+            request: tonic::Request<super::super::models::AccountRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::super::models::RiskLimits>,
+            tonic::Status,
+        >;
+        async fn reset_protection(
+            &self,
+            request: tonic::Request<super::super::models::ProtectionResetRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::super::models::StringResponse>,
+            tonic::Status,
+        >;
+        async fn promote(
+            &self,
+            request: tonic::Request<super::super::models::PromoteRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::super::models::ReplicationStatus>,
+            tonic::Status,
+        >;
+        async fn inject_fault(
+            &self,
+            request: tonic::Request<super::super::models::FaultRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::super::models::StringResponse>,
+            tonic::Status,
+        >;
+    }
+    #[derive(Debug)]
+    pub struct AdminServer<T> {
+        inner: Arc<T>,
+        accept_compression_encodings: EnabledCompressionEncodings,
+        send_compression_encodings: EnabledCompressionEncodings,
+        max_decoding_message_size: Option<usize>,
+        max_encoding_message_size: Option<usize>,
+    }
+    impl<T> AdminServer<T> {
+        pub fn new(inner: T) -> Self {
+            Self::from_arc(Arc::new(inner))
+        }
+        pub fn from_arc(inner: Arc<T>) -> Self {
+            Self {
+                inner,
+                accept_compression_encodings: Default::default(),
+                send_compression_encodings: Default::default(),
+                max_decoding_message_size: None,
+                max_encoding_message_size: None,
+            }
+        }
+        pub fn with_interceptor<F>(
+            inner: T,
+            interceptor: F,
+        ) -> InterceptedService<Self, F>
+        where
+            F: tonic::service::Interceptor,
+        {
+            InterceptedService::new(Self::new(inner), interceptor)
+        }
+        /// Enable decompressing requests with the given encoding.
+        #[must_use]
+        pub fn accept_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.accept_compression_encodings.enable(encoding);
+            self
+        }
+        /// Compress responses with the given encoding, if the client supports it.
+        #[must_use]
+        pub fn send_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.send_compression_encodings.enable(encoding);
+            self
+        }
+        /// Limits the maximum size of a decoded message.
+        ///
+        /// Default: `4MB`
+        #[must_use]
+        pub fn max_decoding_message_size(mut self, limit: usize) -> Self {
+            self.max_decoding_message_size = Some(limit);
+            self
+        }
+        /// Limits the maximum size of an encoded message.
+        ///
+        /// Default: `usize::MAX`
+        #[must_use]
+        pub fn max_encoding_message_size(mut self, limit: usize) -> Self {
+            self.max_encoding_message_size = Some(limit);
+            self
+        }
+    }
+    impl<T, B> tonic::codegen::Service<http::Request<B>> for AdminServer<T>
+    where
+        T: Admin,
+        B: Body + std::marker::Send + 'static,
+        B::Error: Into<StdError> + std::marker::Send + 'static,
+    {
+        type Response = http::Response<tonic::body::BoxBody>;
+        type Error = std::convert::Infallible;
+        type Future = BoxFuture<Self::Response, Self::Error>;
+        fn poll_ready(
+            &mut self,
+            _cx: &mut Context<'_>,
+        ) -> Poll<std::result::Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+        fn call(&mut self, req: http::Request<B>) -> Self::Future {
+            match req.uri().path() {
+                "/services.Admin/halt" => {
+                    #[allow(non_camel_case_types)]
+                    struct haltSvc<T: Admin>(pub Arc<T>);
+                    impl<
+                        T: Admin,
+                    > tonic::server::UnaryService<
+                        super::super::models::AdminSymbolRequest,
+                    > for haltSvc<T> {
+                        type Response = super::super::models::StringResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<
+                                super::super::models::AdminSymbolRequest,
+                            >,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Admin>::halt(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = haltSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/services.Admin/resume" => {
+                    #[allow(non_camel_case_types)]
+                    struct resumeSvc<T: Admin>(pub Arc<T>);
+                    impl<
+                        T: Admin,
+                    > tonic::server::UnaryService<
+                        super::super::models::AdminSymbolRequest,
+                    > for resumeSvc<T> {
+                        type Response = super::super::models::StringResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<
+                                super::super::models::AdminSymbolRequest,
+                            >,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Admin>::resume(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = resumeSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/services.Admin/cancel_only" => {
+                    #[allow(non_camel_case_types)]
+                    struct cancel_onlySvc<T: Admin>(pub Arc<T>);
+                    impl<
+                        T: Admin,
+                    > tonic::server::UnaryService<
+                        super::super::models::AdminSymbolRequest,
+                    > for cancel_onlySvc<T> {
+                        type Response = super::super::models::StringResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<
+                                super::super::models::AdminSymbolRequest,
+                            >,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Admin>::cancel_only(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = cancel_onlySvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/services.Admin/clear" => {
+                    #[allow(non_camel_case_types)]
+                    struct clearSvc<T: Admin>(pub Arc<T>);
+                    impl<
+                        T: Admin,
+                    > tonic::server::UnaryService<
+                        super::super::models::AdminSymbolRequest,
+                    > for clearSvc<T> {
+                        type Response = super::super::models::StringResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<
+                                super::super::models::AdminSymbolRequest,
+                            >,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Admin>::clear(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = clearSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/services.Admin/drain" => {
+                    #[allow(non_camel_case_types)]
+                    struct drainSvc<T: Admin>(pub Arc<T>);
+                    impl<
+                        T: Admin,
+                    > tonic::server::UnaryService<
+                        super::super::models::AdminSymbolRequest,
+                    > for drainSvc<T> {
+                        type Response = super::super::models::StringResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<
+                                super::super::models::AdminSymbolRequest,
+                            >,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Admin>::drain(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = drainSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/services.Admin/snapshot" => {
+                    #[allow(non_camel_case_types)]
+                    struct snapshotSvc<T: Admin>(pub Arc<T>);
+                    impl<
+                        T: Admin,
+                    > tonic::server::UnaryService<super::super::models::SnapshotRequest>
+                    for snapshotSvc<T> {
+                        type Response = super::super::models::StringResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<
+                                super::super::models::SnapshotRequest,
+                            >,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Admin>::snapshot(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = snapshotSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/services.Admin/create_symbol" => {
+                    #[allow(non_camel_case_types)]
+                    struct create_symbolSvc<T: Admin>(pub Arc<T>);
+                    impl<
+                        T: Admin,
+                    > tonic::server::UnaryService<
+                        super::super::models::CreateSymbolRequest,
+                    > for create_symbolSvc<T> {
+                        type Response = super::super::models::StringResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<
+                                super::super::models::CreateSymbolRequest,
+                            >,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Admin>::create_symbol(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = create_symbolSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/services.Admin/retire_symbol" => {
+                    #[allow(non_camel_case_types)]
+                    struct retire_symbolSvc<T: Admin>(pub Arc<T>);
+                    impl<
+                        T: Admin,
+                    > tonic::server::UnaryService<
+                        super::super::models::AdminSymbolRequest,
+                    > for retire_symbolSvc<T> {
+                        type Response = super::super::models::StringResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<
+                                super::super::models::AdminSymbolRequest,
+                            >,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Admin>::retire_symbol(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = retire_symbolSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/services.Admin/reload_config" => {
+                    #[allow(non_camel_case_types)]
+                    struct reload_configSvc<T: Admin>(pub Arc<T>);
+                    impl<
+                        T: Admin,
+                    > tonic::server::UnaryService<
+                        super::super::models::ReloadConfigRequest,
+                    > for reload_configSvc<T> {
+                        type Response = super::super::models::StringResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<
+                                super::super::models::ReloadConfigRequest,
+                            >,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Admin>::reload_config(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = reload_configSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/services.Admin/list_tasks" => {
+                    #[allow(non_camel_case_types)]
+                    struct list_tasksSvc<T: Admin>(pub Arc<T>);
+                    impl<
+                        T: Admin,
+                    > tonic::server::UnaryService<super::super::models::ListTasksRequest>
+                    for list_tasksSvc<T> {
+                        type Response = super::super::models::TaskList;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<
+                                super::super::models::ListTasksRequest,
+                            >,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Admin>::list_tasks(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = list_tasksSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/services.Admin/memory_stats" => {
+                    #[allow(non_camel_case_types)]
+                    struct memory_statsSvc<T: Admin>(pub Arc<T>);
+                    impl<
+                        T: Admin,
+                    > tonic::server::UnaryService<
+                        super::super::models::AdminSymbolRequest,
+                    > for memory_statsSvc<T> {
+                        type Response = super::super::models::MemoryStats;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<
+                                super::super::models::AdminSymbolRequest,
+                            >,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Admin>::memory_stats(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = memory_statsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/services.Admin/credit" => {
+                    #[allow(non_camel_case_types)]
+                    struct creditSvc<T: Admin>(pub Arc<T>);
+                    impl<
+                        T: Admin,
+                    > tonic::server::UnaryService<
+                        super::super::models::BalanceAdjustmentRequest,
+                    > for creditSvc<T> {
+                        type Response = super::super::models::BalanceList;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<
+                                super::super::models::BalanceAdjustmentRequest,
+                            >,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Admin>::credit(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = creditSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/services.Admin/debit" => {
+                    #[allow(non_camel_case_types)]
+                    struct debitSvc<T: Admin>(pub Arc<T>);
+                    impl<
+                        T: Admin,
+                    > tonic::server::UnaryService<
+                        super::super::models::BalanceAdjustmentRequest,
+                    > for debitSvc<T> {
+                        type Response = super::super::models::BalanceList;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<
+                                super::super::models::BalanceAdjustmentRequest,
+                            >,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Admin>::debit(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = debitSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/services.Admin/balances" => {
+                    #[allow(non_camel_case_types)]
+                    struct balancesSvc<T: Admin>(pub Arc<T>);
+                    impl<
+                        T: Admin,
+                    > tonic::server::UnaryService<super::super::models::AccountRequest>
+                    for balancesSvc<T> {
+                        type Response = super::super::models::BalanceList;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::super::models::AccountRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Admin>::balances(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = balancesSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/services.Admin/set_risk_limits" => {
+                    #[allow(non_camel_case_types)]
+                    struct set_risk_limitsSvc<T: Admin>(pub Arc<T>);
+                    impl<
+                        T: Admin,
+                    > tonic::server::UnaryService<super::super::models::RiskLimits>
+                    for set_risk_limitsSvc<T> {
+                        type Response = super::super::models::RiskLimits;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::super::models::RiskLimits>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Admin>::set_risk_limits(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = set_risk_limitsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/services.Admin/risk_limits" => {
+                    #[allow(non_camel_case_types)]
+                    struct risk_limitsSvc<T: Admin>(pub Arc<T>);
+                    impl<
+                        T: Admin,
+                    > tonic::server::UnaryService<super::super::models::AccountRequest>
+                    for risk_limitsSvc<T> {
+                        type Response = super::super::models::RiskLimits;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::super::models::AccountRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Admin>::risk_limits(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = risk_limitsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/services.Admin/reset_protection" => {
+                    #[allow(non_camel_case_types)]
+                    struct reset_protectionSvc<T: Admin>(pub Arc<T>);
+                    impl<
+                        T: Admin,
+                    > tonic::server::UnaryService<
+                        super::super::models::ProtectionResetRequest,
+                    > for reset_protectionSvc<T> {
+                        type Response = super::super::models::StringResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<
+                                super::super::models::ProtectionResetRequest,
+                            >,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Admin>::reset_protection(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = reset_protectionSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/services.Admin/promote" => {
+                    #[allow(non_camel_case_types)]
+                    struct promoteSvc<T: Admin>(pub Arc<T>);
+                    impl<
+                        T: Admin,
+                    > tonic::server::UnaryService<super::super::models::PromoteRequest>
+                    for promoteSvc<T> {
+                        type Response = super::super::models::ReplicationStatus;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::super::models::PromoteRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Admin>::promote(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = promoteSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/services.Admin/inject_fault" => {
+                    #[allow(non_camel_case_types)]
+                    struct inject_faultSvc<T: Admin>(pub Arc<T>);
+                    impl<
+                        T: Admin,
+                    > tonic::server::UnaryService<super::super::models::FaultRequest>
+                    for inject_faultSvc<T> {
+                        type Response = super::super::models::StringResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::super::models::FaultRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Admin>::inject_fault(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = inject_faultSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                _ => {
+                    Box::pin(async move {
+                        let mut response = http::Response::new(empty_body());
+                        let headers = response.headers_mut();
+                        headers
+                            .insert(
+                                tonic::Status::GRPC_STATUS,
+                                (tonic::Code::Unimplemented as i32).into(),
+                            );
+                        headers
+                            .insert(
+                                http::header::CONTENT_TYPE,
+                                tonic::metadata::GRPC_CONTENT_TYPE,
+                            );
+                        Ok(response)
+                    })
+                }
+            }
+        }
+    }
+    impl<T> Clone for AdminServer<T> {
+        fn clone(&self) -> Self {
+            let inner = self.inner.clone();
+            Self {
+                inner,
+                accept_compression_encodings: self.accept_compression_encodings,
+                send_compression_encodings: self.send_compression_encodings,
+                max_decoding_message_size: self.max_decoding_message_size,
+                max_encoding_message_size: self.max_encoding_message_size,
+            }
+        }
+    }
+    /// Generated gRPC service name
+    pub const SERVICE_NAME: &str = "services.Admin";
+    impl<T> tonic::server::NamedService for AdminServer<T> {
+        const NAME: &'static str = SERVICE_NAME;
+    }
+}
+/// Generated server implementations.
+pub mod stat_stream_server {
+    #![allow(
+        unused_variables,
+        dead_code,
+        missing_docs,
+        clippy::wildcard_imports,
+        clippy::let_unit_value,
+    )]
+    use tonic::codegen::*;
+    /// Generated trait containing gRPC methods that should be implemented for use with StatStreamServer.
+    #[async_trait]
+    pub trait StatStream: std::marker::Send + std::marker::Sync + 'static {
+        /// Server streaming response type for the rfq method.
+        type rfqStream: tonic::codegen::tokio_stream::Stream<
+                Item = std::result::Result<
+                    super::super::models::RfqResult,
+                    tonic::Status,
+                >,
+            >
+            + std::marker::Send
+            + 'static;
+        async fn rfq(
+            &self,
+            request: tonic::Request<super::super::models::CreateMarketOrderRequest>,
+        ) -> std::result::Result<tonic::Response<Self::rfqStream>, tonic::Status>;
+        /// Server streaming response type for the orderbook method.
+        type orderbookStream: tonic::codegen::tokio_stream::Stream<
+                Item = std::result::Result<
+                    super::super::models::OrderbookData,
+                    tonic::Status,
+                >,
+            >
+            + std::marker::Send
+            + 'static;
+        async fn orderbook(
+            &self,
+            request: tonic::Request<super::super::models::OrderbookDataRequest>,
+        ) -> std::result::Result<tonic::Response<Self::orderbookStream>, tonic::Status>;
+        /// Server streaming response type for the orderbook_updates method.
+        type orderbook_updatesStream: tonic::codegen::tokio_stream::Stream<
+                Item = std::result::Result<
+                    super::super::models::OrderbookUpdate,
+                    tonic::Status,
+                >,
+            >
+            + std::marker::Send
+            + 'static;
+        async fn orderbook_updates(
+            &self,
+            request: tonic::Request<super::super::models::OrderbookDataRequest>,
+        ) -> std::result::Result<
+            tonic::Response<Self::orderbook_updatesStream>,
+            tonic::Status,
+        >;
+        async fn resync(
+            &self,
+            request: tonic::Request<super::super::models::ResyncRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::super::models::OrderbookUpdate>,
+            tonic::Status,
+        >;
+        /// Server streaming response type for the bbo method.
+        type bboStream: tonic::codegen::tokio_stream::Stream<
+                Item = std::result::Result<super::super::models::Bbo, tonic::Status>,
+            >
+            + std::marker::Send
+            + 'static;
+        async fn bbo(
+            &self,
+            request: tonic::Request<super::super::models::BboRequest>,
+        ) -> std::result::Result<tonic::Response<Self::bboStream>, tonic::Status>;
+        /// Server streaming response type for the indicative_uncross method.
+        type indicative_uncrossStream: tonic::codegen::tokio_stream::Stream<
+                Item = std::result::Result<
+                    super::super::models::IndicativeUncross,
+                    tonic::Status,
+                >,
+            >
+            + std::marker::Send
+            + 'static;
+        async fn indicative_uncross(
+            &self,
+            request: tonic::Request<super::super::models::IndicativeUncrossRequest>,
+        ) -> std::result::Result<
+            tonic::Response<Self::indicative_uncrossStream>,
+            tonic::Status,
+        >;
+        /// Server streaming response type for the book_events method.
+        type book_eventsStream: tonic::codegen::tokio_stream::Stream<
+                Item = std::result::Result<
+                    super::super::models::BookEvent,
+                    tonic::Status,
+                >,
+            >
+            + std::marker::Send
+            + 'static;
+        async fn book_events(
+            &self,
+            request: tonic::Request<super::super::models::BookEventsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<Self::book_eventsStream>,
+            tonic::Status,
+        >;
+        async fn slippage(
+            &self,
+            request: tonic::Request<super::super::models::SlippageRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::super::models::SlippageCurve>,
+            tonic::Status,
+        >;
+        async fn heatmap(
+            &self,
+            request: tonic::Request<super::super::models::HeatmapRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::super::models::Heatmap>,
+            tonic::Status,
+        >;
+        async fn volume_profile(
+            &self,
+            request: tonic::Request<super::super::models::VolumeProfileRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::super::models::VolumeProfile>,
+            tonic::Status,
+        >;
+        async fn ticker(
+            &self,
+            request: tonic::Request<super::super::models::TickerRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::super::models::Ticker>,
+            tonic::Status,
+        >;
+        /// Server streaming response type for the average_prices method.
+        type average_pricesStream: tonic::codegen::tokio_stream::Stream<
+                Item = std::result::Result<
+                    super::super::models::AveragePrices,
+                    tonic::Status,
+                >,
+            >
+            + std::marker::Send
+            + 'static;
+        async fn average_prices(
+            &self,
+            request: tonic::Request<super::super::models::AveragePriceRequest>,
+        ) -> std::result::Result<
+            tonic::Response<Self::average_pricesStream>,
+            tonic::Status,
+        >;
+        /// Server streaming response type for the ticker_updates method.
+        type ticker_updatesStream: tonic::codegen::tokio_stream::Stream<
+                Item = std::result::Result<super::super::models::Ticker, tonic::Status>,
+            >
+            + std::marker::Send
+            + 'static;
+        async fn ticker_updates(
+            &self,
+            request: tonic::Request<super::super::models::TickerRequest>,
+        ) -> std::result::Result<
+            tonic::Response<Self::ticker_updatesStream>,
+            tonic::Status,
+        >;
+        /// Server streaming response type for the candles method.
+        type candlesStream: tonic::codegen::tokio_stream::Stream<
+                Item = std::result::Result<super::super::models::Candle, tonic::Status>,
+            >
+            + std::marker::Send
+            + 'static;
+        async fn candles(
+            &self,
+            request: tonic::Request<super::super::models::CandleRequest>,
+        ) -> std::result::Result<tonic::Response<Self::candlesStream>, tonic::Status>;
+        /// Server streaming response type for the market_summary method.
+        type market_summaryStream: tonic::codegen::tokio_stream::Stream<
+                Item = std::result::Result<
+                    super::super::models::MarketSummary,
+                    tonic::Status,
+                >,
+            >
+            + std::marker::Send
+            + 'static;
+        async fn market_summary(
+            &self,
+            request: tonic::Request<super::super::models::MarketSummaryRequest>,
+        ) -> std::result::Result<
+            tonic::Response<Self::market_summaryStream>,
+            tonic::Status,
+        >;
+        async fn delivery_metrics(
+            &self,
+            request: tonic::Request<super::super::models::DeliveryMetricsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::super::models::DeliveryMetrics>,
+            tonic::Status,
+        >;
+        async fn latency(
+            &self,
+            request: tonic::Request<super::super::models::LatencyRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::super::models::LatencySummary>,
+            tonic::Status,
+        >;
+        async fn channel_metrics(
+            &self,
+            request: tonic::Request<super::super::models::ChannelMetricsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::super::models::ChannelMetricsList>,
+            tonic::Status,
+        >;
+        async fn positions(
+            &self,
+            request: tonic::Request<super::super::models::PositionRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::super::models::PositionList>,
+            tonic::Status,
+        >;
+        /// Server streaming response type for the position_updates method.
+        type position_updatesStream: tonic::codegen::tokio_stream::Stream<
+                Item = std::result::Result<
+                    super::super::models::PositionList,
+                    tonic::Status,
+                >,
+            >
+            + std::marker::Send
+            + 'static;
+        async fn position_updates(
+            &self,
+            request: tonic::Request<super::super::models::PositionRequest>,
+        ) -> std::result::Result<
+            tonic::Response<Self::position_updatesStream>,
+            tonic::Status,
+        >;
+    }
+    #[derive(Debug)]
+    pub struct StatStreamServer<T> {
+        inner: Arc<T>,
+        accept_compression_encodings: EnabledCompressionEncodings,
+        send_compression_encodings: EnabledCompressionEncodings,
+        max_decoding_message_size: Option<usize>,
+        max_encoding_message_size: Option<usize>,
+    }
+    impl<T> StatStreamServer<T> {
+        pub fn new(inner: T) -> Self {
+            Self::from_arc(Arc::new(inner))
+        }
+        pub fn from_arc(inner: Arc<T>) -> Self {
+            Self {
+                inner,
+                accept_compression_encodings: Default::default(),
+                send_compression_encodings: Default::default(),
+                max_decoding_message_size: None,
+                max_encoding_message_size: None,
+            }
+        }
+        pub fn with_interceptor<F>(
+            inner: T,
+            interceptor: F,
+        ) -> InterceptedService<Self, F>
+        where
+            F: tonic::service::Interceptor,
+        {
+            InterceptedService::new(Self::new(inner), interceptor)
+        }
+        /// Enable decompressing requests with the given encoding.
+        #[must_use]
+        pub fn accept_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.accept_compression_encodings.enable(encoding);
+            self
+        }
+        /// Compress responses with the given encoding, if the client supports it.
+        #[must_use]
+        pub fn send_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.send_compression_encodings.enable(encoding);
+            self
+        }
+        /// Limits the maximum size of a decoded message.
+        ///
+        /// Default: `4MB`
+        #[must_use]
+        pub fn max_decoding_message_size(mut self, limit: usize) -> Self {
+            self.max_decoding_message_size = Some(limit);
+            self
+        }
+        /// Limits the maximum size of an encoded message.
+        ///
+        /// Default: `usize::MAX`
+        #[must_use]
+        pub fn max_encoding_message_size(mut self, limit: usize) -> Self {
+            self.max_encoding_message_size = Some(limit);
+            self
+        }
+    }
+    impl<T, B> tonic::codegen::Service<http::Request<B>> for StatStreamServer<T>
+    where
+        T: StatStream,
+        B: Body + std::marker::Send + 'static,
+        B::Error: Into<StdError> + std::marker::Send + 'static,
+    {
+        type Response = http::Response<tonic::body::BoxBody>;
+        type Error = std::convert::Infallible;
+        type Future = BoxFuture<Self::Response, Self::Error>;
+        fn poll_ready(
+            &mut self,
+            _cx: &mut Context<'_>,
+        ) -> Poll<std::result::Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+        fn call(&mut self, req: http::Request<B>) -> Self::Future {
+            match req.uri().path() {
+                "/services.StatStream/rfq" => {
+                    #[allow(non_camel_case_types)]
+                    struct rfqSvc<T: StatStream>(pub Arc<T>);
+                    impl<
+                        T: StatStream,
+                    > tonic::server::ServerStreamingService<
+                        super::super::models::CreateMarketOrderRequest,
+                    > for rfqSvc<T> {
+                        type Response = super::super::models::RfqResult;
+                        type ResponseStream = T::rfqStream;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::ResponseStream>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<
+                                super::super::models::CreateMarketOrderRequest,
+                            >,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as StatStream>::rfq(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = rfqSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.server_streaming(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/services.StatStream/orderbook" => {
+                    #[allow(non_camel_case_types)]
+                    struct orderbookSvc<T: StatStream>(pub Arc<T>);
+                    impl<
+                        T: StatStream,
+                    > tonic::server::ServerStreamingService<
+                        super::super::models::OrderbookDataRequest,
+                    > for orderbookSvc<T> {
+                        type Response = super::super::models::OrderbookData;
+                        type ResponseStream = T::orderbookStream;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::ResponseStream>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<
+                                super::super::models::OrderbookDataRequest,
+                            >,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as StatStream>::orderbook(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = orderbookSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.server_streaming(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/services.StatStream/orderbook_updates" => {
+                    #[allow(non_camel_case_types)]
+                    struct orderbook_updatesSvc<T: StatStream>(pub Arc<T>);
+                    impl<
+                        T: StatStream,
+                    > tonic::server::ServerStreamingService<
+                        super::super::models::OrderbookDataRequest,
+                    > for orderbook_updatesSvc<T> {
+                        type Response = super::super::models::OrderbookUpdate;
+                        type ResponseStream = T::orderbook_updatesStream;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::ResponseStream>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<
+                                super::super::models::OrderbookDataRequest,
+                            >,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as StatStream>::orderbook_updates(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = orderbook_updatesSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.server_streaming(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/services.StatStream/resync" => {
+                    #[allow(non_camel_case_types)]
+                    struct resyncSvc<T: StatStream>(pub Arc<T>);
+                    impl<
+                        T: StatStream,
+                    > tonic::server::UnaryService<super::super::models::ResyncRequest>
+                    for resyncSvc<T> {
+                        type Response = super::super::models::OrderbookUpdate;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::super::models::ResyncRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as StatStream>::resync(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = resyncSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/services.StatStream/bbo" => {
+                    #[allow(non_camel_case_types)]
+                    struct bboSvc<T: StatStream>(pub Arc<T>);
+                    impl<
+                        T: StatStream,
+                    > tonic::server::ServerStreamingService<
+                        super::super::models::BboRequest,
+                    > for bboSvc<T> {
+                        type Response = super::super::models::Bbo;
+                        type ResponseStream = T::bboStream;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::ResponseStream>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::super::models::BboRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as StatStream>::bbo(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = bboSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.server_streaming(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/services.StatStream/indicative_uncross" => {
+                    #[allow(non_camel_case_types)]
+                    struct indicative_uncrossSvc<T: StatStream>(pub Arc<T>);
+                    impl<
+                        T: StatStream,
+                    > tonic::server::ServerStreamingService<
+                        super::super::models::IndicativeUncrossRequest,
+                    > for indicative_uncrossSvc<T> {
+                        type Response = super::super::models::IndicativeUncross;
+                        type ResponseStream = T::indicative_uncrossStream;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::ResponseStream>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<
+                                super::super::models::IndicativeUncrossRequest,
+                            >,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as StatStream>::indicative_uncross(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = indicative_uncrossSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.server_streaming(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/services.StatStream/book_events" => {
+                    #[allow(non_camel_case_types)]
+                    struct book_eventsSvc<T: StatStream>(pub Arc<T>);
+                    impl<
+                        T: StatStream,
+                    > tonic::server::ServerStreamingService<
+                        super::super::models::BookEventsRequest,
+                    > for book_eventsSvc<T> {
+                        type Response = super::super::models::BookEvent;
+                        type ResponseStream = T::book_eventsStream;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::ResponseStream>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<
+                                super::super::models::BookEventsRequest,
+                            >,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as StatStream>::book_events(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = book_eventsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.server_streaming(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/services.StatStream/slippage" => {
+                    #[allow(non_camel_case_types)]
+                    struct slippageSvc<T: StatStream>(pub Arc<T>);
+                    impl<
+                        T: StatStream,
+                    > tonic::server::UnaryService<super::super::models::SlippageRequest>
+                    for slippageSvc<T> {
+                        type Response = super::super::models::SlippageCurve;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<
+                                super::super::models::SlippageRequest,
+                            >,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as StatStream>::slippage(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = slippageSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/services.StatStream/heatmap" => {
+                    #[allow(non_camel_case_types)]
+                    struct heatmapSvc<T: StatStream>(pub Arc<T>);
+                    impl<
+                        T: StatStream,
+                    > tonic::server::UnaryService<super::super::models::HeatmapRequest>
+                    for heatmapSvc<T> {
+                        type Response = super::super::models::Heatmap;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::super::models::HeatmapRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as StatStream>::heatmap(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = heatmapSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/services.StatStream/volume_profile" => {
+                    #[allow(non_camel_case_types)]
+                    struct volume_profileSvc<T: StatStream>(pub Arc<T>);
+                    impl<
+                        T: StatStream,
+                    > tonic::server::UnaryService<
+                        super::super::models::VolumeProfileRequest,
+                    > for volume_profileSvc<T> {
+                        type Response = super::super::models::VolumeProfile;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<
+                                super::super::models::VolumeProfileRequest,
+                            >,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as StatStream>::volume_profile(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = volume_profileSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/services.StatStream/ticker" => {
+                    #[allow(non_camel_case_types)]
+                    struct tickerSvc<T: StatStream>(pub Arc<T>);
+                    impl<
+                        T: StatStream,
+                    > tonic::server::UnaryService<super::super::models::TickerRequest>
+                    for tickerSvc<T> {
+                        type Response = super::super::models::Ticker;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::super::models::TickerRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as StatStream>::ticker(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = tickerSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/services.StatStream/average_prices" => {
+                    #[allow(non_camel_case_types)]
+                    struct average_pricesSvc<T: StatStream>(pub Arc<T>);
+                    impl<
+                        T: StatStream,
+                    > tonic::server::ServerStreamingService<
+                        super::super::models::AveragePriceRequest,
+                    > for average_pricesSvc<T> {
+                        type Response = super::super::models::AveragePrices;
+                        type ResponseStream = T::average_pricesStream;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::ResponseStream>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<
+                                super::super::models::AveragePriceRequest,
+                            >,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as StatStream>::average_prices(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = average_pricesSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.server_streaming(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/services.StatStream/ticker_updates" => {
+                    #[allow(non_camel_case_types)]
+                    struct ticker_updatesSvc<T: StatStream>(pub Arc<T>);
+                    impl<
+                        T: StatStream,
+                    > tonic::server::ServerStreamingService<
+                        super::super::models::TickerRequest,
+                    > for ticker_updatesSvc<T> {
+                        type Response = super::super::models::Ticker;
+                        type ResponseStream = T::ticker_updatesStream;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::ResponseStream>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::super::models::TickerRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as StatStream>::ticker_updates(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = ticker_updatesSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.server_streaming(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/services.StatStream/candles" => {
+                    #[allow(non_camel_case_types)]
+                    struct candlesSvc<T: StatStream>(pub Arc<T>);
+                    impl<
+                        T: StatStream,
+                    > tonic::server::ServerStreamingService<
+                        super::super::models::CandleRequest,
+                    > for candlesSvc<T> {
+                        type Response = super::super::models::Candle;
+                        type ResponseStream = T::candlesStream;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::ResponseStream>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::super::models::CandleRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as StatStream>::candles(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = candlesSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.server_streaming(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/services.StatStream/market_summary" => {
+                    #[allow(non_camel_case_types)]
+                    struct market_summarySvc<T: StatStream>(pub Arc<T>);
+                    impl<
+                        T: StatStream,
+                    > tonic::server::ServerStreamingService<
+                        super::super::models::MarketSummaryRequest,
+                    > for market_summarySvc<T> {
+                        type Response = super::super::models::MarketSummary;
+                        type ResponseStream = T::market_summaryStream;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::ResponseStream>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<
+                                super::super::models::MarketSummaryRequest,
+                            >,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as StatStream>::market_summary(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = market_summarySvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.server_streaming(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/services.StatStream/delivery_metrics" => {
+                    #[allow(non_camel_case_types)]
+                    struct delivery_metricsSvc<T: StatStream>(pub Arc<T>);
+                    impl<
+                        T: StatStream,
+                    > tonic::server::UnaryService<
+                        super::super::models::DeliveryMetricsRequest,
+                    > for delivery_metricsSvc<T> {
+                        type Response = super::super::models::DeliveryMetrics;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<
+                                super::super::models::DeliveryMetricsRequest,
+                            >,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as StatStream>::delivery_metrics(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = delivery_metricsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/services.StatStream/latency" => {
+                    #[allow(non_camel_case_types)]
+                    struct latencySvc<T: StatStream>(pub Arc<T>);
+                    impl<
+                        T: StatStream,
+                    > tonic::server::UnaryService<super::super::models::LatencyRequest>
+                    for latencySvc<T> {
+                        type Response = super::super::models::LatencySummary;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::super::models::LatencyRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as StatStream>::latency(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = latencySvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/services.StatStream/channel_metrics" => {
+                    #[allow(non_camel_case_types)]
+                    struct channel_metricsSvc<T: StatStream>(pub Arc<T>);
+                    impl<
+                        T: StatStream,
+                    > tonic::server::UnaryService<
+                        super::super::models::ChannelMetricsRequest,
+                    > for channel_metricsSvc<T> {
+                        type Response = super::super::models::ChannelMetricsList;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<
+                                super::super::models::ChannelMetricsRequest,
+                            >,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as StatStream>::channel_metrics(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = channel_metricsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/services.StatStream/positions" => {
+                    #[allow(non_camel_case_types)]
+                    struct positionsSvc<T: StatStream>(pub Arc<T>);
+                    impl<
+                        T: StatStream,
+                    > tonic::server::UnaryService<super::super::models::PositionRequest>
+                    for positionsSvc<T> {
+                        type Response = super::super::models::PositionList;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<
+                                super::super::models::PositionRequest,
+                            >,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as StatStream>::positions(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = positionsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/services.StatStream/position_updates" => {
+                    #[allow(non_camel_case_types)]
+                    struct position_updatesSvc<T: StatStream>(pub Arc<T>);
+                    impl<
+                        T: StatStream,
+                    > tonic::server::ServerStreamingService<
+                        super::super::models::PositionRequest,
+                    > for position_updatesSvc<T> {
+                        type Response = super::super::models::PositionList;
+                        type ResponseStream = T::position_updatesStream;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::ResponseStream>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<
+                                super::super::models::PositionRequest,
+                            >,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as StatStream>::position_updates(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = position_updatesSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.server_streaming(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                _ => {
+                    Box::pin(async move {
+                        let mut response = http::Response::new(empty_body());
+                        let headers = response.headers_mut();
+                        headers
+                            .insert(
+                                tonic::Status::GRPC_STATUS,
+                                (tonic::Code::Unimplemented as i32).into(),
+                            );
+                        headers
+                            .insert(
+                                http::header::CONTENT_TYPE,
+                                tonic::metadata::GRPC_CONTENT_TYPE,
+                            );
+                        Ok(response)
+                    })
+                }
+            }
+        }
+    }
+    impl<T> Clone for StatStreamServer<T> {
+        fn clone(&self) -> Self {
+            let inner = self.inner.clone();
+            Self {
+                inner,
+                accept_compression_encodings: self.accept_compression_encodings,
+                send_compression_encodings: self.send_compression_encodings,
+                max_decoding_message_size: self.max_decoding_message_size,
+                max_encoding_message_size: self.max_encoding_message_size,
+            }
+        }
+    }
+    /// Generated gRPC service name
+    pub const SERVICE_NAME: &str = "services.StatStream";
+    impl<T> tonic::server::NamedService for StatStreamServer<T> {
+        const NAME: &'static str = SERVICE_NAME;
+    }
+}
 /// Generated server implementations.
-pub mod order_dispatcher_server {
+pub mod delayed_stat_stream_server {
     #![allow(
         unused_variables,
         dead_code,
@@ -9,47 +4847,64 @@ pub mod order_dispatcher_server {
         clippy::let_unit_value,
     )]
     use tonic::codegen::*;
-    /// Generated trait containing gRPC methods that should be implemented for use with OrderDispatcherServer.
+    /// Generated trait containing gRPC methods that should be implemented for use with DelayedStatStreamServer.
     #[async_trait]
-    pub trait OrderDispatcher: std::marker::Send + std::marker::Sync + 'static {
-        async fn limit(
+    pub trait DelayedStatStream: std::marker::Send + std::marker::Sync + 'static {
+        /// Server streaming response type for the orderbook method.
+        type orderbookStream: tonic::codegen::tokio_stream::Stream<
+                Item = std::result::Result<
+                    super::super::models::OrderbookData,
+                    tonic::Status,
+                >,
+            >
+            + std::marker::Send
+            + 'static;
+        async fn orderbook(
             &self,
-            request: tonic::Request<super::super::models::CreateLimitOrderRequest>,
-        ) -> std::result::Result<
-            tonic::Response<super::super::models::StringResponse>,
-            tonic::Status,
-        >;
-        async fn market(
+            request: tonic::Request<super::super::models::OrderbookDataRequest>,
+        ) -> std::result::Result<tonic::Response<Self::orderbookStream>, tonic::Status>;
+        /// Server streaming response type for the bbo method.
+        type bboStream: tonic::codegen::tokio_stream::Stream<
+                Item = std::result::Result<super::super::models::Bbo, tonic::Status>,
+            >
+            + std::marker::Send
+            + 'static;
+        async fn bbo(
             &self,
-            request: tonic::Request<super::super::models::CreateMarketOrderRequest>,
-        ) -> std::result::Result<
-            tonic::Response<super::super::models::StringResponse>,
-            tonic::Status,
-        >;
-        async fn modify(
+            request: tonic::Request<super::super::models::BboRequest>,
+        ) -> std::result::Result<tonic::Response<Self::bboStream>, tonic::Status>;
+        async fn ticker(
             &self,
-            request: tonic::Request<super::super::models::ModifyLimitOrderRequest>,
+            request: tonic::Request<super::super::models::TickerRequest>,
         ) -> std::result::Result<
-            tonic::Response<super::super::models::StringResponse>,
+            tonic::Response<super::super::models::Ticker>,
             tonic::Status,
         >;
-        async fn cancel(
+        /// Server streaming response type for the ticker_updates method.
+        type ticker_updatesStream: tonic::codegen::tokio_stream::Stream<
+                Item = std::result::Result<super::super::models::Ticker, tonic::Status>,
+            >
+            + std::marker::Send
+            + 'static;
+        async fn ticker_updates(
             &self,
-            request: tonic::Request<super::super::models::CancelLimitOrderRequest>,
+            request: tonic::Request<super::super::models::TickerRequest>,
         ) -> std::result::Result<
-            tonic::Response<super::super::models::StringResponse>,
+            tonic::Response<Self::ticker_updatesStream>,
             tonic::Status,
         >;
     }
+    /// Market data delayed and sampled as configured, which is served without the market data token
+    /// the real-time StatStream may require.
     #[derive(Debug)]
-    pub struct OrderDispatcherServer<T> {
+    pub struct DelayedStatStreamServer<T> {
         inner: Arc<T>,
         accept_compression_encodings: EnabledCompressionEncodings,
         send_compression_encodings: EnabledCompressionEncodings,
         max_decoding_message_size: Option<usize>,
         max_encoding_message_size: Option<usize>,
     }
-    impl<T> OrderDispatcherServer<T> {
+    impl<T> DelayedStatStreamServer<T> {
         pub fn new(inner: T) -> Self {
             Self::from_arc(Arc::new(inner))
         }
@@ -100,9 +4955,9 @@ pub mod order_dispatcher_server {
             self
         }
     }
-    impl<T, B> tonic::codegen::Service<http::Request<B>> for OrderDispatcherServer<T>
+    impl<T, B> tonic::codegen::Service<http::Request<B>> for DelayedStatStreamServer<T>
     where
-        T: OrderDispatcher,
+        T: DelayedStatStream,
         B: Body + std::marker::Send + 'static,
         B::Error: Into<StdError> + std::marker::Send + 'static,
     {
@@ -117,28 +4972,29 @@ pub mod order_dispatcher_server {
         }
         fn call(&mut self, req: http::Request<B>) -> Self::Future {
             match req.uri().path() {
-                "/services.OrderDispatcher/limit" => {
+                "/services.DelayedStatStream/orderbook" => {
                     #[allow(non_camel_case_types)]
-                    struct limitSvc<T: OrderDispatcher>(pub Arc<T>);
+                    struct orderbookSvc<T: DelayedStatStream>(pub Arc<T>);
                     impl<
-                        T: OrderDispatcher,
-                    > tonic::server::UnaryService<
-                        super::super::models::CreateLimitOrderRequest,
-                    > for limitSvc<T> {
-                        type Response = super::super::models::StringResponse;
+                        T: DelayedStatStream,
+                    > tonic::server::ServerStreamingService<
+                        super::super::models::OrderbookDataRequest,
+                    > for orderbookSvc<T> {
+                        type Response = super::super::models::OrderbookData;
+                        type ResponseStream = T::orderbookStream;
                         type Future = BoxFuture<
-                            tonic::Response<Self::Response>,
+                            tonic::Response<Self::ResponseStream>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
                             request: tonic::Request<
-                                super::super::models::CreateLimitOrderRequest,
+                                super::super::models::OrderbookDataRequest,
                             >,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as OrderDispatcher>::limit(&inner, request).await
+                                <T as DelayedStatStream>::orderbook(&inner, request).await
                             };
                             Box::pin(fut)
                         }
@@ -149,7 +5005,7 @@ pub mod order_dispatcher_server {
                     let max_encoding_message_size = self.max_encoding_message_size;
                     let inner = self.inner.clone();
                     let fut = async move {
-                        let method = limitSvc(inner);
+                        let method = orderbookSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -160,33 +5016,32 @@ pub mod order_dispatcher_server {
                                 max_decoding_message_size,
                                 max_encoding_message_size,
                             );
-                        let res = grpc.unary(method, req).await;
+                        let res = grpc.server_streaming(method, req).await;
                         Ok(res)
                     };
                     Box::pin(fut)
                 }
-                "/services.OrderDispatcher/market" => {
+                "/services.DelayedStatStream/bbo" => {
                     #[allow(non_camel_case_types)]
-                    struct marketSvc<T: OrderDispatcher>(pub Arc<T>);
+                    struct bboSvc<T: DelayedStatStream>(pub Arc<T>);
                     impl<
-                        T: OrderDispatcher,
-                    > tonic::server::UnaryService<
-                        super::super::models::CreateMarketOrderRequest,
-                    > for marketSvc<T> {
-                        type Response = super::super::models::StringResponse;
+                        T: DelayedStatStream,
+                    > tonic::server::ServerStreamingService<
+                        super::super::models::BboRequest,
+                    > for bboSvc<T> {
+                        type Response = super::super::models::Bbo;
+                        type ResponseStream = T::bboStream;
                         type Future = BoxFuture<
-                            tonic::Response<Self::Response>,
+                            tonic::Response<Self::ResponseStream>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<
-                                super::super::models::CreateMarketOrderRequest,
-                            >,
+                            request: tonic::Request<super::super::models::BboRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as OrderDispatcher>::market(&inner, request).await
+                                <T as DelayedStatStream>::bbo(&inner, request).await
                             };
                             Box::pin(fut)
                         }
@@ -197,7 +5052,7 @@ pub mod order_dispatcher_server {
                     let max_encoding_message_size = self.max_encoding_message_size;
                     let inner = self.inner.clone();
                     let fut = async move {
-                        let method = marketSvc(inner);
+                        let method = bboSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -208,33 +5063,30 @@ pub mod order_dispatcher_server {
                                 max_decoding_message_size,
                                 max_encoding_message_size,
                             );
-                        let res = grpc.unary(method, req).await;
+                        let res = grpc.server_streaming(method, req).await;
                         Ok(res)
                     };
                     Box::pin(fut)
                 }
-                "/services.OrderDispatcher/modify" => {
+                "/services.DelayedStatStream/ticker" => {
                     #[allow(non_camel_case_types)]
-                    struct modifySvc<T: OrderDispatcher>(pub Arc<T>);
+                    struct tickerSvc<T: DelayedStatStream>(pub Arc<T>);
                     impl<
-                        T: OrderDispatcher,
-                    > tonic::server::UnaryService<
-                        super::super::models::ModifyLimitOrderRequest,
-                    > for modifySvc<T> {
-                        type Response = super::super::models::StringResponse;
+                        T: DelayedStatStream,
+                    > tonic::server::UnaryService<super::super::models::TickerRequest>
+                    for tickerSvc<T> {
+                        type Response = super::super::models::Ticker;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<
-                                super::super::models::ModifyLimitOrderRequest,
-                            >,
+                            request: tonic::Request<super::super::models::TickerRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as OrderDispatcher>::modify(&inner, request).await
+                                <T as DelayedStatStream>::ticker(&inner, request).await
                             };
                             Box::pin(fut)
                         }
@@ -245,7 +5097,7 @@ pub mod order_dispatcher_server {
                     let max_encoding_message_size = self.max_encoding_message_size;
                     let inner = self.inner.clone();
                     let fut = async move {
-                        let method = modifySvc(inner);
+                        let method = tickerSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -261,28 +5113,28 @@ pub mod order_dispatcher_server {
                     };
                     Box::pin(fut)
                 }
-                "/services.OrderDispatcher/cancel" => {
+                "/services.DelayedStatStream/ticker_updates" => {
                     #[allow(non_camel_case_types)]
-                    struct cancelSvc<T: OrderDispatcher>(pub Arc<T>);
+                    struct ticker_updatesSvc<T: DelayedStatStream>(pub Arc<T>);
                     impl<
-                        T: OrderDispatcher,
-                    > tonic::server::UnaryService<
-                        super::super::models::CancelLimitOrderRequest,
-                    > for cancelSvc<T> {
-                        type Response = super::super::models::StringResponse;
+                        T: DelayedStatStream,
+                    > tonic::server::ServerStreamingService<
+                        super::super::models::TickerRequest,
+                    > for ticker_updatesSvc<T> {
+                        type Response = super::super::models::Ticker;
+                        type ResponseStream = T::ticker_updatesStream;
                         type Future = BoxFuture<
-                            tonic::Response<Self::Response>,
+                            tonic::Response<Self::ResponseStream>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<
-                                super::super::models::CancelLimitOrderRequest,
-                            >,
+                            request: tonic::Request<super::super::models::TickerRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as OrderDispatcher>::cancel(&inner, request).await
+                                <T as DelayedStatStream>::ticker_updates(&inner, request)
+                                    .await
                             };
                             Box::pin(fut)
                         }
@@ -293,7 +5145,7 @@ pub mod order_dispatcher_server {
                     let max_encoding_message_size = self.max_encoding_message_size;
                     let inner = self.inner.clone();
                     let fut = async move {
-                        let method = cancelSvc(inner);
+                        let method = ticker_updatesSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -304,7 +5156,7 @@ pub mod order_dispatcher_server {
                                 max_decoding_message_size,
                                 max_encoding_message_size,
                             );
-                        let res = grpc.unary(method, req).await;
+                        let res = grpc.server_streaming(method, req).await;
                         Ok(res)
                     };
                     Box::pin(fut)
@@ -329,7 +5181,7 @@ pub mod order_dispatcher_server {
             }
         }
     }
-    impl<T> Clone for OrderDispatcherServer<T> {
+    impl<T> Clone for DelayedStatStreamServer<T> {
         fn clone(&self) -> Self {
             let inner = self.inner.clone();
             Self {
@@ -342,13 +5194,13 @@ pub mod order_dispatcher_server {
         }
     }
     /// Generated gRPC service name
-    pub const SERVICE_NAME: &str = "services.OrderDispatcher";
-    impl<T> tonic::server::NamedService for OrderDispatcherServer<T> {
+    pub const SERVICE_NAME: &str = "services.DelayedStatStream";
+    impl<T> tonic::server::NamedService for DelayedStatStreamServer<T> {
         const NAME: &'static str = SERVICE_NAME;
     }
 }
 /// Generated server implementations.
-pub mod stat_stream_server {
+pub mod replication_server {
     #![allow(
         unused_variables,
         dead_code,
@@ -357,45 +5209,40 @@ pub mod stat_stream_server {
         clippy::let_unit_value,
     )]
     use tonic::codegen::*;
-    /// Generated trait containing gRPC methods that should be implemented for use with StatStreamServer.
+    /// Generated trait containing gRPC methods that should be implemented for use with ReplicationServer.
     #[async_trait]
-    pub trait StatStream: std::marker::Send + std::marker::Sync + 'static {
-        /// Server streaming response type for the rfq method.
-        type rfqStream: tonic::codegen::tokio_stream::Stream<
+    pub trait Replication: std::marker::Send + std::marker::Sync + 'static {
+        /// Server streaming response type for the subscribe method.
+        type subscribeStream: tonic::codegen::tokio_stream::Stream<
                 Item = std::result::Result<
-                    super::super::models::RfqResult,
+                    super::super::models::ReplicationUpdate,
                     tonic::Status,
                 >,
             >
             + std::marker::Send
             + 'static;
-        async fn rfq(
+        async fn subscribe(
             &self,
-            request: tonic::Request<super::super::models::CreateMarketOrderRequest>,
-        ) -> std::result::Result<tonic::Response<Self::rfqStream>, tonic::Status>;
-        /// Server streaming response type for the orderbook method.
-        type orderbookStream: tonic::codegen::tokio_stream::Stream<
-                Item = std::result::Result<
-                    super::super::models::OrderbookData,
-                    tonic::Status,
-                >,
-            >
-            + std::marker::Send
-            + 'static;
-        async fn orderbook(
+            request: tonic::Request<super::super::models::ReplicationRequest>,
+        ) -> std::result::Result<tonic::Response<Self::subscribeStream>, tonic::Status>;
+        async fn fence(
             &self,
-            request: tonic::Request<super::super::models::OrderbookDataRequest>,
-        ) -> std::result::Result<tonic::Response<Self::orderbookStream>, tonic::Status>;
+            request: tonic::Request<super::super::models::FenceRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::super::models::ReplicationStatus>,
+            tonic::Status,
+        >;
     }
+    /// Served by the primary to its replicas, which authenticate with the admin token.
     #[derive(Debug)]
-    pub struct StatStreamServer<T> {
+    pub struct ReplicationServer<T> {
         inner: Arc<T>,
         accept_compression_encodings: EnabledCompressionEncodings,
         send_compression_encodings: EnabledCompressionEncodings,
         max_decoding_message_size: Option<usize>,
         max_encoding_message_size: Option<usize>,
     }
-    impl<T> StatStreamServer<T> {
+    impl<T> ReplicationServer<T> {
         pub fn new(inner: T) -> Self {
             Self::from_arc(Arc::new(inner))
         }
@@ -446,9 +5293,9 @@ pub mod stat_stream_server {
             self
         }
     }
-    impl<T, B> tonic::codegen::Service<http::Request<B>> for StatStreamServer<T>
+    impl<T, B> tonic::codegen::Service<http::Request<B>> for ReplicationServer<T>
     where
-        T: StatStream,
+        T: Replication,
         B: Body + std::marker::Send + 'static,
         B::Error: Into<StdError> + std::marker::Send + 'static,
     {
@@ -463,16 +5310,16 @@ pub mod stat_stream_server {
         }
         fn call(&mut self, req: http::Request<B>) -> Self::Future {
             match req.uri().path() {
-                "/services.StatStream/rfq" => {
+                "/services.Replication/subscribe" => {
                     #[allow(non_camel_case_types)]
-                    struct rfqSvc<T: StatStream>(pub Arc<T>);
+                    struct subscribeSvc<T: Replication>(pub Arc<T>);
                     impl<
-                        T: StatStream,
+                        T: Replication,
                     > tonic::server::ServerStreamingService<
-                        super::super::models::CreateMarketOrderRequest,
-                    > for rfqSvc<T> {
-                        type Response = super::super::models::RfqResult;
-                        type ResponseStream = T::rfqStream;
+                        super::super::models::ReplicationRequest,
+                    > for subscribeSvc<T> {
+                        type Response = super::super::models::ReplicationUpdate;
+                        type ResponseStream = T::subscribeStream;
                         type Future = BoxFuture<
                             tonic::Response<Self::ResponseStream>,
                             tonic::Status,
@@ -480,12 +5327,12 @@ pub mod stat_stream_server {
                         fn call(
                             &mut self,
                             request: tonic::Request<
-                                super::super::models::CreateMarketOrderRequest,
+                                super::super::models::ReplicationRequest,
                             >,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as StatStream>::rfq(&inner, request).await
+                                <T as Replication>::subscribe(&inner, request).await
                             };
                             Box::pin(fut)
                         }
@@ -496,7 +5343,7 @@ pub mod stat_stream_server {
                     let max_encoding_message_size = self.max_encoding_message_size;
                     let inner = self.inner.clone();
                     let fut = async move {
-                        let method = rfqSvc(inner);
+                        let method = subscribeSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -512,29 +5359,25 @@ pub mod stat_stream_server {
                     };
                     Box::pin(fut)
                 }
-                "/services.StatStream/orderbook" => {
+                "/services.Replication/fence" => {
                     #[allow(non_camel_case_types)]
-                    struct orderbookSvc<T: StatStream>(pub Arc<T>);
+                    struct fenceSvc<T: Replication>(pub Arc<T>);
                     impl<
-                        T: StatStream,
-                    > tonic::server::ServerStreamingService<
-                        super::super::models::OrderbookDataRequest,
-                    > for orderbookSvc<T> {
-                        type Response = super::super::models::OrderbookData;
-                        type ResponseStream = T::orderbookStream;
+                        T: Replication,
+                    > tonic::server::UnaryService<super::super::models::FenceRequest>
+                    for fenceSvc<T> {
+                        type Response = super::super::models::ReplicationStatus;
                         type Future = BoxFuture<
-                            tonic::Response<Self::ResponseStream>,
+                            tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<
-                                super::super::models::OrderbookDataRequest,
-                            >,
+                            request: tonic::Request<super::super::models::FenceRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as StatStream>::orderbook(&inner, request).await
+                                <T as Replication>::fence(&inner, request).await
                             };
                             Box::pin(fut)
                         }
@@ -545,7 +5388,7 @@ pub mod stat_stream_server {
                     let max_encoding_message_size = self.max_encoding_message_size;
                     let inner = self.inner.clone();
                     let fut = async move {
-                        let method = orderbookSvc(inner);
+                        let method = fenceSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -556,7 +5399,7 @@ pub mod stat_stream_server {
                                 max_decoding_message_size,
                                 max_encoding_message_size,
                             );
-                        let res = grpc.server_streaming(method, req).await;
+                        let res = grpc.unary(method, req).await;
                         Ok(res)
                     };
                     Box::pin(fut)
@@ -581,7 +5424,7 @@ pub mod stat_stream_server {
             }
         }
     }
-    impl<T> Clone for StatStreamServer<T> {
+    impl<T> Clone for ReplicationServer<T> {
         fn clone(&self) -> Self {
             let inner = self.inner.clone();
             Self {
@@ -594,8 +5437,8 @@ pub mod stat_stream_server {
         }
     }
     /// Generated gRPC service name
-    pub const SERVICE_NAME: &str = "services.StatStream";
-    impl<T> tonic::server::NamedService for StatStreamServer<T> {
+    pub const SERVICE_NAME: &str = "services.Replication";
+    impl<T> tonic::server::NamedService for ReplicationServer<T> {
         const NAME: &'static str = SERVICE_NAME;
     }
 }
@@ -0,0 +1,78 @@
+use crate::core::models::{LimitOrder, Operation};
+use crate::core::types::{OrderId, Price, Quantity};
+use crate::engine::persistence::recording::RecordedOperation;
+use crate::engine::utils::order_flow::{FlowEvent, OrderFlow, OrderFlowProperties};
+
+/// An operation along with the virtual time it reaches the orderbook at, in nanoseconds.
+#[derive(Debug, Clone, Copy)]
+pub struct TimedOperation {
+    pub timestamp: u128,
+    pub operation: Operation,
+}
+
+/// Generates background flow from a seed until the given virtual time. Orders are numbered from
+/// one, and cancels target one of the orders generated before them, which may have been filled
+/// or cancelled in the meantime.
+///
+/// # Arguments
+///
+/// * `rate_per_second` - Average operations per second of virtual time.
+/// * `duration` - Virtual nanoseconds the flow spans.
+pub fn synthetic_flow(
+    properties: OrderFlowProperties,
+    seed: u64,
+    rate_per_second: f64,
+    duration: u128,
+) -> impl Iterator<Item = TimedOperation> {
+    let mut flow = OrderFlow::new(properties, seed);
+    let mut timestamp = 0u128;
+    let mut next_id = 1u128;
+    std::iter::from_fn(move || {
+        timestamp += flow.next_arrival(rate_per_second).as_nanos();
+        if timestamp >= duration {
+            return None;
+        }
+        let placed = next_id - 1;
+        let operation = match flow
+            .next_event(|rng| (placed > 0).then(|| 1 + rng.below(placed as u64) as u128))
+        {
+            FlowEvent::Limit {
+                side,
+                price,
+                quantity,
+                ..
+            } => {
+                next_id += 1;
+                Operation::Limit(LimitOrder::new(
+                    OrderId(next_id - 1),
+                    Price(price),
+                    Quantity(quantity),
+                    side,
+                ))
+            }
+            FlowEvent::Cancel { order_id, .. } => Operation::Cancel(OrderId(order_id)),
+        };
+        Some(TimedOperation {
+            timestamp,
+            operation,
+        })
+    })
+}
+
+/// Turns the operations of a symbol in a recording into background flow starting at virtual
+/// time zero, with the time between operations shortened by the speed.
+pub fn recorded_flow(
+    recorded: &[RecordedOperation],
+    symbol: &str,
+    speed: f64,
+) -> Vec<TimedOperation> {
+    let first_timestamp = recorded.first().map_or(0, |recorded| recorded.timestamp);
+    recorded
+        .iter()
+        .filter(|recorded| recorded.symbol == symbol)
+        .map(|recorded| TimedOperation {
+            timestamp: ((recorded.timestamp - first_timestamp) as f64 / speed) as u128,
+            operation: recorded.operation,
+        })
+        .collect()
+}
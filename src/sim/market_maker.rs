@@ -0,0 +1,63 @@
+use crate::core::models::Side;
+use crate::sim::strategy::{Strategy, StrategyContext};
+
+/// Quotes both sides around the mid price at every step, replacing the quotes of the previous
+/// step. A side stops being quoted once the position reaches the limit in its direction.
+pub struct MarketMaker {
+    name: String,
+    /// Price quoted around until the book has a mid price or a last trade.
+    reference_price: u64,
+    half_spread: u64,
+    quantity: u64,
+    max_position: i64,
+    quotes: Vec<u128>,
+}
+
+impl MarketMaker {
+    pub fn new(
+        name: &str,
+        reference_price: u64,
+        half_spread: u64,
+        quantity: u64,
+        max_position: i64,
+    ) -> Self {
+        Self {
+            name: name.to_string(),
+            reference_price,
+            half_spread,
+            quantity,
+            max_position,
+            quotes: vec![],
+        }
+    }
+}
+
+impl Strategy for MarketMaker {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn on_step(&mut self, context: &mut StrategyContext) {
+        let book = context.book();
+        let mid_price = match (book.get_max_bid(), book.get_min_ask()) {
+            (Some(bid), Some(ask)) => (bid + ask) / 2,
+            _ if book.get_last_trade_price() > 0 => book.get_last_trade_price(),
+            _ => self.reference_price,
+        };
+        for id in self.quotes.drain(..) {
+            if context.book().get_order(id).is_some() {
+                context.cancel(id);
+            }
+        }
+        if context.position() < self.max_position {
+            let price = mid_price.saturating_sub(self.half_spread).max(1);
+            self.quotes
+                .push(context.limit(price, self.quantity, Side::Bid));
+        }
+        if context.position() > -self.max_position {
+            let price = mid_price + self.half_spread;
+            self.quotes
+                .push(context.limit(price, self.quantity, Side::Ask));
+        }
+    }
+}
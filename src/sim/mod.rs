@@ -0,0 +1,9 @@
+/// Contains the background order flow a simulation is driven by, recorded or synthetic.
+pub mod flow;
+/// Contains a simple market making strategy, useful as a baseline and as an example.
+pub mod market_maker;
+/// Contains the simulation, which runs the flow and the strategies against an orderbook on a
+/// virtual clock and reports the statistics of every strategy.
+pub mod simulation;
+/// Contains the trait strategies implement and the context they act through.
+pub mod strategy;
@@ -0,0 +1,485 @@
+use crate::core::models::{ExecutionResult, Operation, Side};
+use crate::core::orderbook::OrderBook;
+use crate::engine::utils::order_flow::SeededRng;
+use crate::sim::flow::TimedOperation;
+use crate::sim::strategy::{Strategy, StrategyContext, StrategyFill};
+use hdrhistogram::Histogram;
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap};
+use std::iter::Peekable;
+
+/// Ids of strategy orders start here, so that they never collide with the ids of the flow.
+pub const STRATEGY_ORDER_ID_OFFSET: u128 = 1 << 64;
+
+/// Fill latencies beyond an hour of virtual time are recorded as an hour.
+const MAX_FILL_LATENCY: u64 = 3_600_000_000_000;
+
+/// How a strategy is scheduled, in virtual nanoseconds.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StrategyTiming {
+    /// Time between submitting an operation and it reaching the book.
+    pub latency: u128,
+    /// Time between two steps of the strategy, zero steps only once at the start.
+    pub step_interval: u128,
+}
+
+/// Statistics of a strategy at the end of a simulation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StrategyReport {
+    pub name: String,
+    pub orders: u64,
+    pub modifies: u64,
+    pub cancels: u64,
+    /// Operations the book refused, such as cancels of orders that had already filled.
+    pub rejected: u64,
+    pub fills: u64,
+    pub filled_quantity: u64,
+    pub position: i64,
+    /// Cash spent and received on fills, negative when more was bought than sold.
+    pub cash: i128,
+    /// Cash plus the position marked at the mark price of the simulation.
+    pub pnl: i128,
+    /// Nanoseconds between submitting an order and each of its fills, to three significant digits.
+    pub fill_latency_p50: u64,
+    pub fill_latency_p99: u64,
+    pub fill_latency_max: u64,
+}
+
+/// Outcome of a simulation. The same flow, strategies and seed always produce the same report.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimulationReport {
+    /// Virtual time the simulation ran until.
+    pub end_time: u128,
+    pub operations: u64,
+    pub trades: u64,
+    pub volume: u64,
+    /// Last trade price, or the mid price if nothing traded, which positions are marked at.
+    pub mark_price: u64,
+    /// Hash of the orders left resting in the book.
+    pub state_hash: u64,
+    pub strategies: Vec<StrategyReport>,
+}
+
+enum Event {
+    Step(usize),
+    Arrival {
+        strategy: usize,
+        submitted_at: u128,
+        operation: Operation,
+    },
+}
+
+struct Scheduled {
+    timestamp: u128,
+    sequence: u64,
+    event: Event,
+}
+
+impl PartialEq for Scheduled {
+    fn eq(&self, other: &Self) -> bool {
+        (self.timestamp, self.sequence) == (other.timestamp, other.sequence)
+    }
+}
+
+impl Eq for Scheduled {}
+
+impl PartialOrd for Scheduled {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Scheduled {
+    // reversed, so that the binary heap pops the earliest event first
+    fn cmp(&self, other: &Self) -> Ordering {
+        (other.timestamp, other.sequence).cmp(&(self.timestamp, self.sequence))
+    }
+}
+
+struct Participant {
+    strategy: Box<dyn Strategy>,
+    timing: StrategyTiming,
+    orders: u64,
+    modifies: u64,
+    cancels: u64,
+    rejected: u64,
+    fills: u64,
+    filled_quantity: u64,
+    position: i64,
+    cash: i128,
+    fill_latency: Histogram<u64>,
+}
+
+impl Participant {
+    fn record_fill(&mut self, fill: &StrategyFill, submitted_at: u128) {
+        let notional = fill.price as i128 * fill.quantity as i128;
+        match fill.side {
+            Side::Bid => {
+                self.position += fill.quantity as i64;
+                self.cash -= notional;
+            }
+            Side::Ask => {
+                self.position -= fill.quantity as i64;
+                self.cash += notional;
+            }
+        }
+        self.fills += 1;
+        self.filled_quantity += fill.quantity;
+        self.fill_latency
+            .saturating_record((fill.timestamp - submitted_at) as u64);
+    }
+
+    fn report(&self, mark_price: u64) -> StrategyReport {
+        StrategyReport {
+            name: self.strategy.name().to_string(),
+            orders: self.orders,
+            modifies: self.modifies,
+            cancels: self.cancels,
+            rejected: self.rejected,
+            fills: self.fills,
+            filled_quantity: self.filled_quantity,
+            position: self.position,
+            cash: self.cash,
+            pnl: self.cash + self.position as i128 * mark_price as i128,
+            fill_latency_p50: self.fill_latency.value_at_quantile(0.5),
+            fill_latency_p99: self.fill_latency.value_at_quantile(0.99),
+            fill_latency_max: self.fill_latency.max(),
+        }
+    }
+}
+
+/// Drives an orderbook from a background flow and a set of strategies on a virtual clock.
+/// Nothing depends on wall clock time, so results only depend on the inputs and the seed.
+pub struct Simulation {
+    book: OrderBook,
+    flow: Peekable<Box<dyn Iterator<Item = TimedOperation>>>,
+    participants: Vec<Participant>,
+    queue: BinaryHeap<Scheduled>,
+    sequence: u64,
+    rng: SeededRng,
+    next_order_id: u128,
+    /// The strategy and submission time of every strategy order that may still fill.
+    owners: HashMap<u128, (usize, u128)>,
+    now: u128,
+    operations: u64,
+    trades: u64,
+    volume: u64,
+}
+
+impl Simulation {
+    /// Creates a simulation over an empty book.
+    ///
+    /// # Arguments
+    ///
+    /// * `flow` - Background operations, ordered by their timestamps.
+    /// * `seed` - Seed of the generator strategies draw from.
+    pub fn new(flow: impl IntoIterator<Item = TimedOperation> + 'static, seed: u64) -> Self {
+        let flow: Box<dyn Iterator<Item = TimedOperation>> = Box::new(flow.into_iter());
+        Self {
+            book: OrderBook::default(),
+            flow: flow.peekable(),
+            participants: vec![],
+            queue: BinaryHeap::new(),
+            sequence: 0,
+            rng: SeededRng::new(seed),
+            next_order_id: STRATEGY_ORDER_ID_OFFSET,
+            owners: HashMap::new(),
+            now: 0,
+            operations: 0,
+            trades: 0,
+            volume: 0,
+        }
+    }
+
+    /// Adds a strategy, which takes its first step at the current virtual time.
+    pub fn add_strategy(&mut self, strategy: Box<dyn Strategy>, timing: StrategyTiming) {
+        self.participants.push(Participant {
+            strategy,
+            timing,
+            orders: 0,
+            modifies: 0,
+            cancels: 0,
+            rejected: 0,
+            fills: 0,
+            filled_quantity: 0,
+            position: 0,
+            cash: 0,
+            fill_latency: Histogram::new_with_bounds(1, MAX_FILL_LATENCY, 3).unwrap(),
+        });
+        self.schedule(self.now, Event::Step(self.participants.len() - 1));
+    }
+
+    pub fn book(&self) -> &OrderBook {
+        &self.book
+    }
+
+    /// Runs every event up to and including the given virtual time.
+    pub fn run(&mut self, until: u128) -> SimulationReport {
+        loop {
+            let flow_time = self.flow.peek().map(|timed| timed.timestamp);
+            let queue_time = self.queue.peek().map(|scheduled| scheduled.timestamp);
+            match (flow_time, queue_time) {
+                (Some(flow_time), queue_time)
+                    if flow_time <= until && queue_time.is_none_or(|time| flow_time <= time) =>
+                {
+                    let timed = self.flow.next().unwrap();
+                    self.now = self.now.max(timed.timestamp);
+                    self.execute(timed.operation, None);
+                }
+                (_, Some(queue_time)) if queue_time <= until => {
+                    let scheduled = self.queue.pop().unwrap();
+                    self.now = scheduled.timestamp;
+                    self.handle(scheduled.event);
+                }
+                _ => break,
+            }
+        }
+        self.now = self.now.max(until);
+        self.report()
+    }
+
+    /// Statistics of the simulation so far.
+    pub fn report(&self) -> SimulationReport {
+        let mark_price = match (self.book.get_max_bid(), self.book.get_min_ask()) {
+            _ if self.book.get_last_trade_price() > 0 => self.book.get_last_trade_price(),
+            (Some(bid), Some(ask)) => (bid + ask) / 2,
+            _ => 0,
+        };
+        SimulationReport {
+            end_time: self.now,
+            operations: self.operations,
+            trades: self.trades,
+            volume: self.volume,
+            mark_price,
+            state_hash: self.book.state_hash(),
+            strategies: self
+                .participants
+                .iter()
+                .map(|participant| participant.report(mark_price))
+                .collect(),
+        }
+    }
+
+    fn schedule(&mut self, timestamp: u128, event: Event) {
+        self.sequence += 1;
+        self.queue.push(Scheduled {
+            timestamp,
+            sequence: self.sequence,
+            event,
+        });
+    }
+
+    fn handle(&mut self, event: Event) {
+        match event {
+            Event::Step(index) => {
+                self.call(index, |strategy, context| strategy.on_step(context));
+                let step_interval = self.participants[index].timing.step_interval;
+                if step_interval > 0 {
+                    self.schedule(self.now + step_interval, Event::Step(index));
+                }
+            }
+            Event::Arrival {
+                strategy,
+                submitted_at,
+                operation,
+            } => self.execute(operation, Some((strategy, submitted_at))),
+        }
+    }
+
+    /// Lets a strategy act and schedules the arrival of the operations it submitted.
+    fn call(&mut self, index: usize, action: impl FnOnce(&mut dyn Strategy, &mut StrategyContext)) {
+        let participant = &mut self.participants[index];
+        let mut context = StrategyContext {
+            now: self.now,
+            book: &self.book,
+            rng: &mut self.rng,
+            next_order_id: &mut self.next_order_id,
+            position: participant.position,
+            submitted: vec![],
+        };
+        action(participant.strategy.as_mut(), &mut context);
+        let submitted = context.submitted;
+        let arrival = self.now + participant.timing.latency;
+        for operation in submitted {
+            let participant = &mut self.participants[index];
+            match operation {
+                Operation::Limit(_) | Operation::Market(_) => participant.orders += 1,
+                Operation::Modify(_) | Operation::Reduce { .. } | Operation::Quote { .. } => {
+                    participant.modifies += 1
+                }
+                Operation::Cancel(_) => participant.cancels += 1,
+                Operation::Call | Operation::Uncross => (),
+            }
+            let event = Event::Arrival {
+                strategy: index,
+                submitted_at: self.now,
+                operation,
+            };
+            self.schedule(arrival, event);
+        }
+    }
+
+    /// Executes an operation against the book, attributing the fills to the strategies whose
+    /// orders took part in them and notifying those strategies.
+    fn execute(&mut self, operation: Operation, owner: Option<(usize, u128)>) {
+        self.operations += 1;
+        let order_id = operation.order_id();
+        if let (Some(owner), Operation::Limit(_) | Operation::Market(_)) = (owner, operation) {
+            self.owners.insert(order_id, owner);
+        }
+        let result = self.book.execute(operation);
+        if let (Some((index, _)), ExecutionResult::Failed(_)) = (owner, &result) {
+            self.participants[index].rejected += 1;
+        }
+        let mut notifications = vec![];
+        for fill in result.fills() {
+            self.trades += 1;
+            self.volume += fill.quantity;
+            let maker_side = match fill.taker_side {
+                Side::Bid => Side::Ask,
+                Side::Ask => Side::Bid,
+            };
+            let sides = [
+                (fill.order_id, fill.taker_side, true),
+                (fill.matched_order_id, maker_side, false),
+            ];
+            for (id, side, is_taker) in sides {
+                let Some(&(index, submitted_at)) = self.owners.get(&id) else {
+                    continue;
+                };
+                let strategy_fill = StrategyFill {
+                    order_id: id,
+                    side,
+                    price: fill.price,
+                    quantity: fill.quantity,
+                    is_taker,
+                    timestamp: self.now,
+                };
+                self.participants[index].record_fill(&strategy_fill, submitted_at);
+                notifications.push((index, strategy_fill));
+            }
+            if self.book.get_order(fill.matched_order_id).is_none() {
+                self.owners.remove(&fill.matched_order_id);
+            }
+        }
+        if self.book.get_order(order_id).is_none() {
+            self.owners.remove(&order_id);
+        }
+        for (index, fill) in notifications {
+            self.call(index, |strategy, context| strategy.on_fill(&fill, context));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::models::{LimitOrder, Operation, Side};
+    use crate::core::types::{OrderId, Price, Quantity};
+    use crate::engine::utils::order_flow::{OrderFlowProperties, PriceDistribution};
+    use crate::sim::flow::{synthetic_flow, TimedOperation};
+    use crate::sim::market_maker::MarketMaker;
+    use crate::sim::simulation::{Simulation, SimulationReport, StrategyTiming};
+    use crate::sim::strategy::{Strategy, StrategyContext, StrategyFill};
+
+    struct Bidder {
+        fills: Vec<StrategyFill>,
+    }
+
+    impl Strategy for Bidder {
+        fn name(&self) -> &str {
+            "bidder"
+        }
+
+        fn on_step(&mut self, context: &mut StrategyContext) {
+            context.limit(100, 10, Side::Bid);
+        }
+
+        fn on_fill(&mut self, fill: &StrategyFill, context: &mut StrategyContext) {
+            self.fills.push(*fill);
+            if context.position() == 10 {
+                context.market(10, Side::Ask);
+            }
+        }
+    }
+
+    fn ask(timestamp: u128, id: u128, price: u64, quantity: u64) -> TimedOperation {
+        TimedOperation {
+            timestamp,
+            operation: Operation::Limit(LimitOrder::new(
+                OrderId(id),
+                Price(price),
+                Quantity(quantity),
+                Side::Ask,
+            )),
+        }
+    }
+
+    #[test]
+    fn it_attributes_fills_and_tracks_positions() {
+        let flow = vec![
+            ask(500, 1, 100, 4),
+            ask(5_000, 2, 100, 4),
+            ask(6_000, 3, 99, 10),
+            TimedOperation {
+                timestamp: 6_500,
+                operation: Operation::Limit(LimitOrder::new(
+                    OrderId(4),
+                    Price(98),
+                    Quantity(10),
+                    Side::Bid,
+                )),
+            },
+        ];
+        let mut simulation = Simulation::new(flow, 1);
+        let timing = StrategyTiming {
+            latency: 1_000,
+            step_interval: 0,
+        };
+        simulation.add_strategy(Box::new(Bidder { fills: vec![] }), timing);
+        let report = simulation.run(10_000);
+
+        // the bid lifts the resting ask on arrival and rests until the flow fills it, then the
+        // position is sold into the bid of the flow
+        let bidder = &report.strategies[0];
+        assert_eq!(report.trades, 4);
+        assert_eq!(
+            (bidder.orders, bidder.fills, bidder.filled_quantity),
+            (2, 4, 20)
+        );
+        assert_eq!(bidder.position, 0);
+        assert_eq!(bidder.cash, 10 * 98 - (4 * 100 + 4 * 100 + 2 * 100));
+        assert_eq!(bidder.pnl, bidder.cash);
+        assert_eq!(bidder.fill_latency_p50, 1_000);
+        assert!((6_000..6_010).contains(&bidder.fill_latency_max));
+        assert_eq!(report.mark_price, 98);
+    }
+
+    fn simulate(seed: u64) -> SimulationReport {
+        let properties = OrderFlowProperties {
+            mid_price: 1_000,
+            price_range: 20,
+            price_distribution: PriceDistribution::Normal,
+            max_quantity: 10,
+            cancel_ratio: 0.3,
+            accounts: 1,
+        };
+        let flow = synthetic_flow(properties, seed, 10_000.0, 1_000_000_000);
+        let mut simulation = Simulation::new(flow, seed);
+        let timing = StrategyTiming {
+            latency: 50_000,
+            step_interval: 1_000_000,
+        };
+        simulation.add_strategy(Box::new(MarketMaker::new("mm", 1_000, 2, 5, 50)), timing);
+        simulation.run(1_000_000_000)
+    }
+
+    #[test]
+    fn it_reproduces_results_from_a_seed() {
+        let report = simulate(3);
+        assert!(report.operations > 10_000);
+        assert!(report.strategies[0].fills > 0);
+        assert!(report.strategies[0].position.abs() <= 50 + 5);
+        assert_eq!(report, simulate(3));
+        assert_ne!(report, simulate(4));
+    }
+}
@@ -0,0 +1,114 @@
+use crate::core::models::{LimitOrder, MarketOrder, Operation, Side};
+use crate::core::orderbook::OrderBook;
+use crate::core::types::{OrderId, Price, Quantity};
+use crate::engine::utils::order_flow::SeededRng;
+
+/// A participant of a simulation. Strategies act on a schedule and when their orders fill, and
+/// every operation they submit reaches the book after the latency of the strategy.
+pub trait Strategy {
+    fn name(&self) -> &str;
+
+    /// Called every step interval of the strategy.
+    fn on_step(&mut self, context: &mut StrategyContext);
+
+    /// Called as soon as an order of the strategy is matched. Fills are seen without latency, so
+    /// that the reaction to a fill is delayed only by the latency of the operations it submits.
+    fn on_fill(&mut self, _fill: &StrategyFill, _context: &mut StrategyContext) {}
+}
+
+/// A match of an order of a strategy.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StrategyFill {
+    pub order_id: u128,
+    /// Side of the order of the strategy.
+    pub side: Side,
+    pub price: u64,
+    pub quantity: u64,
+    /// Whether the order of the strategy took liquidity.
+    pub is_taker: bool,
+    pub timestamp: u128,
+}
+
+/// What a strategy sees of the simulation and the way it submits operations.
+pub struct StrategyContext<'a> {
+    pub(crate) now: u128,
+    pub(crate) book: &'a OrderBook,
+    pub(crate) rng: &'a mut SeededRng,
+    pub(crate) next_order_id: &'a mut u128,
+    pub(crate) position: i64,
+    pub(crate) submitted: Vec<Operation>,
+}
+
+impl StrategyContext<'_> {
+    /// The virtual time in nanoseconds.
+    pub fn now(&self) -> u128 {
+        self.now
+    }
+
+    /// The book as it is now, before any of the operations submitted by this call arrive.
+    pub fn book(&self) -> &OrderBook {
+        self.book
+    }
+
+    /// A generator seeded by the simulation, to keep random decisions reproducible.
+    pub fn rng(&mut self) -> &mut SeededRng {
+        self.rng
+    }
+
+    /// Quantity held by the strategy, negative when it is short.
+    pub fn position(&self) -> i64 {
+        self.position
+    }
+
+    /// Submits a limit order.
+    ///
+    /// # Returns
+    ///
+    /// * The id assigned to the order.
+    pub fn limit(&mut self, price: u64, quantity: u64, side: Side) -> u128 {
+        let id = self.next_id();
+        self.submitted.push(Operation::Limit(LimitOrder::new(
+            OrderId(id),
+            Price(price),
+            Quantity(quantity),
+            side,
+        )));
+        id
+    }
+
+    /// Submits a market order.
+    ///
+    /// # Returns
+    ///
+    /// * The id assigned to the order.
+    pub fn market(&mut self, quantity: u64, side: Side) -> u128 {
+        let id = self.next_id();
+        self.submitted.push(Operation::Market(MarketOrder::new(
+            OrderId(id),
+            Quantity(quantity),
+            side,
+        )));
+        id
+    }
+
+    /// Submits a change of price and quantity of a resting order of the strategy.
+    pub fn modify(&mut self, id: u128, price: u64, quantity: u64, side: Side) {
+        self.submitted.push(Operation::Modify(LimitOrder::new(
+            OrderId(id),
+            Price(price),
+            Quantity(quantity),
+            side,
+        )));
+    }
+
+    /// Submits a cancel of a resting order of the strategy.
+    pub fn cancel(&mut self, id: u128) {
+        self.submitted.push(Operation::Cancel(OrderId(id)));
+    }
+
+    fn next_id(&mut self) -> u128 {
+        let id = *self.next_order_id;
+        *self.next_order_id += 1;
+        id
+    }
+}
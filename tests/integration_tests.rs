@@ -2,14 +2,15 @@
 mod integration_tests {
     use gemmy::core::{
         models::{ExecutionResult, FillResult, LimitOrder, MarketOrder, Operation, Side},
-        orderbook::OrderBook
+        orderbook::OrderBook,
+        types::{OrderId, Price, Quantity},
     };
 
     #[test]
     fn orderbook_flow_place_limit_ask_order() {
         let mut orderbook = OrderBook::default();
 
-        let test_order = LimitOrder::new(1, 100, 100, Side::Bid);
+        let test_order = LimitOrder::new(OrderId(1), Price(100), Quantity(100), Side::Bid);
         let operation = Operation::Limit(test_order);
         let execution_result = orderbook.execute(operation);
 
@@ -35,10 +36,10 @@ mod integration_tests {
     fn orderbook_flow_place_2_limit_ask_orders() {
         let mut orderbook = OrderBook::default();
 
-        let test_order_1 = LimitOrder::new(1, 100, 100, Side::Bid);
+        let test_order_1 = LimitOrder::new(OrderId(1), Price(100), Quantity(100), Side::Bid);
         let operation_1 = Operation::Limit(test_order_1);
 
-        let test_order_2 = LimitOrder::new(2, 110, 200, Side::Ask);
+        let test_order_2 = LimitOrder::new(OrderId(2), Price(110), Quantity(200), Side::Ask);
         let operation_2 = Operation::Limit(test_order_2);
 
         let execution_result_1 = orderbook.execute(operation_1);
@@ -75,7 +76,7 @@ mod integration_tests {
         let mut orderbook = OrderBook::default();
 
         // create an order and wrap it in the corresponding operation
-        let order_ask = LimitOrder::new(1, 100, 100, Side::Ask);
+        let order_ask = LimitOrder::new(OrderId(1), Price(100), Quantity(100), Side::Ask);
         let operation_limit_ask = Operation::Limit(order_ask);
 
         // call the execute method with the operation
@@ -93,7 +94,7 @@ mod integration_tests {
         }
 
         // placing another order here that can fill completely
-        let order_bid = MarketOrder::new(2, 50, Side::Bid);
+        let order_bid = MarketOrder::new(OrderId(2), Quantity(50), Side::Bid);
         let operation_market_bid = Operation::Market(order_bid);
         match orderbook.execute(operation_market_bid) {
             // this time we can see how exactly the order got matched
@@ -105,7 +106,7 @@ mod integration_tests {
         }
 
         // we create a third and final order to see a better view of the book
-        let order_bid_second = LimitOrder::new(3, 50, 100, Side::Bid);
+        let order_bid_second = LimitOrder::new(OrderId(3), Price(50), Quantity(100), Side::Bid);
         let operation_limit_bid = Operation::Limit(order_bid_second);
         match orderbook.execute(operation_limit_bid) {
             ExecutionResult::Executed(FillResult::Created(created_order), ..) => {
@@ -116,4 +117,39 @@ mod integration_tests {
             _ => panic!("expected order to be created"),
         }
     }
+
+    #[test]
+    #[cfg(feature = "engine")]
+    fn orderbook_flow_recovers_state_from_write_ahead_log() {
+        use gemmy::engine::persistence::wal::WriteAheadLog;
+
+        let directory = std::env::temp_dir().join(format!("gemmy-it-{}", uuid::Uuid::new_v4()));
+        let operations = [
+            Operation::Limit(LimitOrder::new(OrderId(1), Price(100), Quantity(100), Side::Ask)),
+            Operation::Limit(LimitOrder::new(OrderId(2), Price(100), Quantity(50), Side::Ask)),
+            Operation::Limit(LimitOrder::new(OrderId(3), Price(90), Quantity(30), Side::Bid)),
+            Operation::Market(MarketOrder::new(OrderId(4), Quantity(120), Side::Bid)),
+            Operation::Modify(LimitOrder::new(OrderId(3), Price(95), Quantity(30), Side::Bid)),
+            Operation::Limit(LimitOrder::new(OrderId(5), Price(95), Quantity(10), Side::Bid)),
+        ];
+
+        let mut orderbook = OrderBook::default();
+        let (write_ahead_log, _) = WriteAheadLog::open(&directory, &mut orderbook).unwrap();
+        write_ahead_log.append_batch(0, &operations[..3]).unwrap();
+        for operation in &operations[..3] {
+            orderbook.execute(*operation);
+        }
+        write_ahead_log.checkpoint(&orderbook).unwrap();
+        write_ahead_log.append_batch(0, &operations[3..]).unwrap();
+        for operation in &operations[3..] {
+            orderbook.execute(*operation);
+        }
+        drop(write_ahead_log);
+
+        let mut recovered = OrderBook::default();
+        WriteAheadLog::open(&directory, &mut recovered).unwrap();
+        assert_ne!(orderbook.state_hash(), OrderBook::default().state_hash());
+        assert_eq!(recovered.state_hash(), orderbook.state_hash());
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
 }
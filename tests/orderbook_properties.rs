@@ -0,0 +1,459 @@
+#[cfg(test)]
+mod orderbook_properties {
+    use gemmy::core::types::{OrderId, Price, Quantity};
+    use gemmy::core::{
+        models::{ExecutionResult, LimitOrder, MarketOrder, Operation, Side},
+        orderbook::OrderBook,
+    };
+    use proptest::prelude::*;
+    use proptest::sample::Index;
+    use std::collections::BTreeMap;
+
+    /// An operation to generate, with modifies, cancels and reductions picking one of the ids
+    /// placed before them, so that they hit resting, filled and cancelled orders alike.
+    #[derive(Debug, Clone)]
+    enum Action {
+        Limit {
+            price: u64,
+            quantity: u64,
+            side: Side,
+        },
+        Market {
+            quantity: u64,
+            side: Side,
+        },
+        Modify {
+            order: Index,
+            price: u64,
+            quantity: u64,
+            side: Side,
+        },
+        Cancel {
+            order: Index,
+        },
+        Reduce {
+            order: Index,
+            quantity_delta: u64,
+        },
+        /// Replaces the quote of a single market maker, which rests under ids of its own.
+        Quote {
+            bid_price: u64,
+            bid_quantity: u64,
+            ask_price: u64,
+            ask_quantity: u64,
+        },
+    }
+
+    const QUOTE_IDS: (u128, u128) = (u128::MAX - 2, u128::MAX - 1);
+
+    fn side() -> impl Strategy<Value = Side> {
+        prop_oneof![Just(Side::Bid), Just(Side::Ask)]
+    }
+
+    /// Prices are kept to a few levels, so that orders share levels and cross often.
+    fn action() -> impl Strategy<Value = Action> {
+        prop_oneof![
+            4 => (90u64..=110, 1u64..=500, side())
+                .prop_map(|(price, quantity, side)| Action::Limit { price, quantity, side }),
+            1 => (1u64..=1_000, side())
+                .prop_map(|(quantity, side)| Action::Market { quantity, side }),
+            2 => (any::<Index>(), 90u64..=110, 1u64..=500, side()).prop_map(
+                |(order, price, quantity, side)| Action::Modify { order, price, quantity, side }
+            ),
+            2 => any::<Index>().prop_map(|order| Action::Cancel { order }),
+            1 => (any::<Index>(), 0u64..=500)
+                .prop_map(|(order, quantity_delta)| Action::Reduce { order, quantity_delta }),
+            1 => (90u64..=110, 0u64..=500, 90u64..=110, 0u64..=500).prop_map(
+                |(bid_price, bid_quantity, ask_price, ask_quantity)| Action::Quote {
+                    bid_price,
+                    bid_quantity,
+                    ask_price,
+                    ask_quantity,
+                }
+            ),
+        ]
+    }
+
+    /// Turns the actions into operations, giving every new order a fresh id.
+    fn operations(actions: Vec<Action>) -> Vec<Operation> {
+        let mut next_id = 0u128;
+        let placed = |order: Index, next_id: u128| match next_id {
+            0 => u128::MAX,
+            next_id => order.index(next_id as usize) as u128,
+        };
+        actions
+            .into_iter()
+            .map(|action| match action {
+                Action::Limit {
+                    price,
+                    quantity,
+                    side,
+                } => {
+                    next_id += 1;
+                    Operation::Limit(LimitOrder::new(
+                        OrderId(next_id - 1),
+                        Price(price),
+                        Quantity(quantity),
+                        side,
+                    ))
+                }
+                Action::Market { quantity, side } => {
+                    next_id += 1;
+                    Operation::Market(MarketOrder::new(
+                        OrderId(next_id - 1),
+                        Quantity(quantity),
+                        side,
+                    ))
+                }
+                Action::Modify {
+                    order,
+                    price,
+                    quantity,
+                    side,
+                } => Operation::Modify(LimitOrder::new(
+                    OrderId(placed(order, next_id)),
+                    Price(price),
+                    Quantity(quantity),
+                    side,
+                )),
+                Action::Cancel { order } => Operation::Cancel(OrderId(placed(order, next_id))),
+                Action::Reduce {
+                    order,
+                    quantity_delta,
+                } => Operation::Reduce {
+                    id: OrderId(placed(order, next_id)),
+                    quantity_delta,
+                },
+                Action::Quote {
+                    bid_price,
+                    bid_quantity,
+                    ask_price,
+                    ask_quantity,
+                } => Operation::Quote {
+                    bid: LimitOrder::new(
+                        OrderId(QUOTE_IDS.0),
+                        Price(bid_price),
+                        Quantity(bid_quantity),
+                        Side::Bid,
+                    ),
+                    ask: LimitOrder::new(
+                        OrderId(QUOTE_IDS.1),
+                        Price(ask_price),
+                        Quantity(ask_quantity),
+                        Side::Ask,
+                    ),
+                },
+            })
+            .collect()
+    }
+
+    /// A reference book, written for clarity over speed. Resting orders are kept in the sequence
+    /// they were placed in and every match scans all of them for the best price.
+    #[derive(Default)]
+    struct ModelBook {
+        orders: Vec<LimitOrder>,
+    }
+
+    /// What the model expects of an execution: whether it fails and the matches it produces, as
+    /// the matched order id, price and quantity.
+    #[derive(Debug, PartialEq)]
+    struct Outcome {
+        failed: bool,
+        fills: Vec<(u128, u64, u64)>,
+    }
+
+    impl ModelBook {
+        fn execute(&mut self, operation: Operation) -> Outcome {
+            match operation {
+                Operation::Limit(order) => self.place(order),
+                Operation::Market(order) => {
+                    if !self.orders.iter().any(|resting| resting.side != order.side) {
+                        return Self::failed();
+                    }
+                    let (fills, remaining) = self.take(order.side, order.quantity, None);
+                    // the remainder of a market order rests at the last price it matched at
+                    if remaining > 0 {
+                        let price = fills.last().unwrap().1;
+                        self.orders.push(LimitOrder::new(
+                            OrderId(order.id),
+                            Price(price),
+                            Quantity(remaining),
+                            order.side,
+                        ));
+                    }
+                    Outcome {
+                        failed: false,
+                        fills,
+                    }
+                }
+                Operation::Modify(order) => {
+                    let Some(position) = self
+                        .orders
+                        .iter()
+                        .position(|resting| resting.id == order.id && resting.side == order.side)
+                    else {
+                        return Self::failed();
+                    };
+                    let existing = self.orders[position];
+                    if existing.price != order.price {
+                        self.orders.remove(position);
+                        self.place(order)
+                    } else if existing.quantity != order.quantity {
+                        self.orders[position].quantity = order.quantity;
+                        Outcome {
+                            failed: false,
+                            fills: Vec::new(),
+                        }
+                    } else {
+                        Self::failed()
+                    }
+                }
+                Operation::Cancel(OrderId(id)) => {
+                    match self.orders.iter().position(|resting| resting.id == id) {
+                        Some(position) => {
+                            self.orders.remove(position);
+                            Outcome {
+                                failed: false,
+                                fills: Vec::new(),
+                            }
+                        }
+                        None => Self::failed(),
+                    }
+                }
+                Operation::Reduce {
+                    id: OrderId(id),
+                    quantity_delta,
+                } => match self.orders.iter_mut().find(|resting| resting.id == id) {
+                    Some(resting) if quantity_delta > 0 && quantity_delta < resting.quantity => {
+                        resting.quantity -= quantity_delta;
+                        Outcome {
+                            failed: false,
+                            fills: Vec::new(),
+                        }
+                    }
+                    _ => Self::failed(),
+                },
+                Operation::Quote { bid, ask } => {
+                    if bid.quantity > 0 && ask.quantity > 0 && bid.price >= ask.price {
+                        return Self::failed();
+                    }
+                    self.orders
+                        .retain(|resting| resting.id != bid.id && resting.id != ask.id);
+                    let mut fills = Vec::new();
+                    for order in [bid, ask] {
+                        if order.quantity > 0 {
+                            fills.extend(self.place(order).fills);
+                        }
+                    }
+                    Outcome {
+                        failed: false,
+                        fills,
+                    }
+                }
+                Operation::Call | Operation::Uncross => unreachable!("calls are not generated"),
+            }
+        }
+
+        fn failed() -> Outcome {
+            Outcome {
+                failed: true,
+                fills: Vec::new(),
+            }
+        }
+
+        fn place(&mut self, order: LimitOrder) -> Outcome {
+            let (fills, remaining) = self.take(order.side, order.quantity, Some(order.price));
+            if remaining > 0 {
+                self.orders.push(LimitOrder::new(
+                    OrderId(order.id),
+                    Price(order.price),
+                    Quantity(remaining),
+                    order.side,
+                ));
+            }
+            Outcome {
+                failed: false,
+                fills,
+            }
+        }
+
+        /// Matches a taker against the best priced and then the earliest resting orders of the
+        /// other side, up to the limit price if there is one.
+        fn take(
+            &mut self,
+            side: Side,
+            mut quantity: u64,
+            limit: Option<u64>,
+        ) -> (Vec<(u128, u64, u64)>, u64) {
+            let mut fills = Vec::new();
+            while quantity > 0 {
+                let crosses = |resting: &LimitOrder| match side {
+                    Side::Bid => limit.is_none_or(|limit| resting.price <= limit),
+                    Side::Ask => limit.is_none_or(|limit| resting.price >= limit),
+                };
+                let best = self
+                    .orders
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, resting)| resting.side != side && crosses(resting))
+                    .min_by_key(|(position, resting)| match side {
+                        Side::Bid => (resting.price, *position),
+                        Side::Ask => (u64::MAX - resting.price, *position),
+                    })
+                    .map(|(position, _)| position);
+                let Some(position) = best else {
+                    break;
+                };
+                let maker = &mut self.orders[position];
+                let filled = quantity.min(maker.quantity);
+                fills.push((maker.id, maker.price, filled));
+                quantity -= filled;
+                maker.quantity -= filled;
+                if maker.quantity == 0 {
+                    self.orders.remove(position);
+                }
+            }
+            (fills, quantity)
+        }
+
+        /// Resting orders in the sequence [`OrderBook::resting_orders`] returns them in.
+        fn resting_orders(&self) -> Vec<LimitOrder> {
+            let mut bids: Vec<LimitOrder> = self
+                .orders
+                .iter()
+                .filter(|o| o.side == Side::Bid)
+                .copied()
+                .collect();
+            let mut asks: Vec<LimitOrder> = self
+                .orders
+                .iter()
+                .filter(|o| o.side == Side::Ask)
+                .copied()
+                .collect();
+            bids.sort_by_key(|order| u64::MAX - order.price);
+            asks.sort_by_key(|order| order.price);
+            bids.into_iter().chain(asks).collect()
+        }
+    }
+
+    fn fills(result: &ExecutionResult) -> Vec<(u128, u64, u64)> {
+        result
+            .fills()
+            .iter()
+            .map(|fill| (fill.matched_order_id, fill.price, fill.quantity))
+            .collect()
+    }
+
+    fn resting_quantity(book: &OrderBook, side: Side) -> u64 {
+        book.resting_orders()
+            .iter()
+            .filter(|order| order.side == side)
+            .map(|order| order.quantity)
+            .sum()
+    }
+
+    fn opposite(side: Side) -> Side {
+        match side {
+            Side::Bid => Side::Ask,
+            Side::Ask => Side::Bid,
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn orderbook_agrees_with_reference_model(
+            actions in prop::collection::vec(action(), 1..200)
+        ) {
+            // a small store keeps the audit cheap and has the store grow past its capacity
+            let mut book = OrderBook::new("properties".to_string(), 4, 16);
+            let mut model = ModelBook::default();
+            for operation in operations(actions) {
+                let result = book.execute(operation);
+                let expected = model.execute(operation);
+                let outcome = Outcome {
+                    failed: matches!(result, ExecutionResult::Failed(_)),
+                    fills: fills(&result),
+                };
+                prop_assert_eq!(outcome, expected, "after {:?}", operation);
+                let resting = model.resting_orders();
+                prop_assert_eq!(book.resting_orders(), resting.clone(), "after {:?}", operation);
+                let best = |side: Side| resting.iter().find(|order| order.side == side);
+                prop_assert_eq!(book.get_max_bid(), best(Side::Bid).map(|order| order.price));
+                prop_assert_eq!(book.get_min_ask(), best(Side::Ask).map(|order| order.price));
+                let audit = book.audit();
+                prop_assert!(audit.is_ok(), "after {:?}: {:?}", operation, audit);
+            }
+        }
+
+        #[test]
+        fn fills_remove_as_much_resting_quantity_as_they_fill(
+            actions in prop::collection::vec(action(), 1..200)
+        ) {
+            let mut book = OrderBook::default();
+            for operation in operations(actions) {
+                let (side, quantity) = match operation {
+                    Operation::Limit(order) => (order.side, order.quantity),
+                    Operation::Market(order) => (order.side, order.quantity),
+                    _ => {
+                        book.execute(operation);
+                        continue;
+                    }
+                };
+                let makers_before = resting_quantity(&book, opposite(side));
+                let takers_before = resting_quantity(&book, side);
+                let result = book.execute(operation);
+                let filled: u64 = result.fills().iter().map(|fill| fill.quantity).sum();
+                prop_assert!(filled <= quantity);
+                prop_assert_eq!(makers_before - resting_quantity(&book, opposite(side)), filled);
+                if !matches!(result, ExecutionResult::Failed(_)) {
+                    prop_assert_eq!(
+                        resting_quantity(&book, side) - takers_before,
+                        quantity - filled
+                    );
+                }
+            }
+        }
+
+        #[test]
+        fn depth_sums_match_resting_orders(
+            actions in prop::collection::vec(action(), 1..200)
+        ) {
+            let mut book = OrderBook::default();
+            let operations = operations(actions);
+            // a book never holds more levels than orders were placed on it
+            let levels = operations.len();
+            for operation in operations {
+                book.execute(operation);
+                let depth = book.depth(levels);
+                for (side, depth_levels) in [(Side::Bid, &depth.bids), (Side::Ask, &depth.asks)] {
+                    let mut expected = BTreeMap::new();
+                    for order in book.resting_orders().iter().filter(|order| order.side == side) {
+                        *expected.entry(order.price).or_insert(0u64) += order.quantity;
+                    }
+                    let levels: BTreeMap<u64, u64> = depth_levels
+                        .iter()
+                        .filter(|level| level.quantity > 0)
+                        .map(|level| (level.price, level.quantity))
+                        .collect();
+                    prop_assert_eq!(levels, expected);
+                }
+            }
+        }
+
+        #[test]
+        fn cancelled_orders_can_no_longer_be_found(
+            actions in prop::collection::vec(action(), 1..200)
+        ) {
+            let mut book = OrderBook::default();
+            for operation in operations(actions) {
+                let result = book.execute(operation);
+                if let ExecutionResult::Cancelled(id) = result {
+                    prop_assert_eq!(book.get_order(id), None);
+                    prop_assert!(book.resting_orders().iter().all(|order| order.id != id));
+                    let cancelled_again = book.execute(Operation::Cancel(OrderId(id)));
+                    prop_assert!(matches!(cancelled_again, ExecutionResult::Failed(_)));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,82 @@
+#[cfg(test)]
+mod result_serialization {
+    use gemmy::core::types::{OrderId, Price, Quantity};
+    use gemmy::core::{
+        errors::{OrderBookError, Rejection, RiskViolation},
+        models::{ExecutionResult, LimitOrder, MarketOrder, Operation, Side},
+        orderbook::OrderBook,
+    };
+    use serde::{de::DeserializeOwned, Serialize};
+    use std::fmt::Debug;
+
+    fn round_trip<T: Serialize + DeserializeOwned + PartialEq + Debug>(value: &T) -> String {
+        let json = serde_json::to_string(value).unwrap();
+        assert_eq!(&serde_json::from_str::<T>(&json).unwrap(), value);
+        json
+    }
+
+    #[test]
+    fn it_round_trips_execution_results() {
+        let mut book = OrderBook::default();
+        let id = u128::MAX - 1;
+        let operations = [
+            Operation::Limit(LimitOrder::new(
+                OrderId(id),
+                Price(100),
+                Quantity(10),
+                Side::Ask,
+            )),
+            Operation::Limit(
+                LimitOrder::new(OrderId(2), Price(100), Quantity(4), Side::Bid)
+                    .with_expiry(u128::MAX),
+            ),
+            Operation::Modify(LimitOrder::new(
+                OrderId(id),
+                Price(100),
+                Quantity(3),
+                Side::Ask,
+            )),
+            Operation::Market(MarketOrder::new(OrderId(3), Quantity(10), Side::Bid)),
+            Operation::Cancel(OrderId(id)),
+        ];
+        for operation in operations {
+            round_trip(&book.execute(operation));
+        }
+        let rejected = ExecutionResult::Failed(OrderBookError::Rejected(Rejection::AccountLimit(
+            RiskViolation::OpenOrders { limit: 5 },
+        )));
+        round_trip(&rejected);
+    }
+
+    #[test]
+    fn it_serializes_ids_as_exact_integers() {
+        let mut book = OrderBook::default();
+        let id = u128::MAX - 1;
+        let json = round_trip(&book.execute(Operation::Limit(LimitOrder::new(
+            OrderId(id),
+            Price(100),
+            Quantity(10),
+            Side::Ask,
+        ))));
+        assert!(json.contains(&id.to_string()));
+    }
+
+    #[test]
+    fn it_round_trips_depth_and_quotes() {
+        let mut book = OrderBook::default();
+        book.execute(Operation::Limit(LimitOrder::new(
+            OrderId(1),
+            Price(100),
+            Quantity(10),
+            Side::Ask,
+        )));
+        book.execute(Operation::Limit(LimitOrder::new(
+            OrderId(2),
+            Price(90),
+            Quantity(5),
+            Side::Bid,
+        )));
+        round_trip(&book.depth(5));
+        round_trip(&book.request_for_quote(MarketOrder::new(OrderId(3), Quantity(15), Side::Bid)));
+    }
+}